@@ -1,5 +1,5 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use gamey::{Coordinates, GameY, Movement, PlayerId, RenderOptions};
+use gamey::{Coordinates, GameY, Movement, PlayerId, RenderOptions, Symmetry};
 
 /// Benchmarks for coordinate conversion functions
 fn bench_coordinates(c: &mut Criterion) {
@@ -131,12 +131,20 @@ fn bench_render(c: &mut Criterion) {
         show_3d_coords: false,
         show_idx: false,
         show_colors: false,
+        show_edge_labels: false,
+        highlight_last_move: false,
+        show_move_numbers: false,
+        view: Symmetry::Identity,
     };
 
     let options_full = RenderOptions {
         show_3d_coords: true,
         show_idx: true,
         show_colors: true,
+        show_edge_labels: true,
+        highlight_last_move: true,
+        show_move_numbers: true,
+        view: Symmetry::Identity,
     };
 
     for board_size in [5, 10, 15].iter() {
@@ -198,6 +206,61 @@ fn bench_touches_side(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmarks for random playouts, the core of MCTS rollouts
+fn bench_random_playout(c: &mut Criterion) {
+    let mut group = c.benchmark_group("random_playout");
+
+    for board_size in [7, 11, 15].iter() {
+        let total_cells = (board_size * (board_size + 1)) / 2;
+        // Report moves per second: a playout fills at most every cell
+        group.throughput(criterion::Throughput::Elements(total_cells as u64));
+        group.bench_with_input(
+            BenchmarkId::new("empty_board", board_size),
+            board_size,
+            |b, &size| {
+                let mut rng = rand::rng();
+                let start = GameY::new(size);
+                b.iter_batched(
+                    || start.clone(),
+                    |mut game| black_box(game.random_playout(&mut rng)),
+                    criterion::BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+
+    group.finish();
+}
+
+fn bench_snapshot_restore(c: &mut Criterion) {
+    let mut group = c.benchmark_group("snapshot_restore");
+
+    for board_size in [7, 11, 15].iter() {
+        let mut root = GameY::new(*board_size);
+        root.random_playout(&mut rand::rng());
+        let snapshot = root.snapshot();
+        let mut game = GameY::new(*board_size);
+
+        group.bench_with_input(
+            BenchmarkId::new("clone", board_size),
+            &root,
+            |b, root| {
+                b.iter(|| game = black_box(root.clone()))
+            },
+        );
+
+        group.bench_with_input(
+            BenchmarkId::new("restore", board_size),
+            &snapshot,
+            |b, snapshot| {
+                b.iter(|| game.restore(black_box(snapshot)))
+            },
+        );
+    }
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_coordinates,
@@ -205,6 +268,8 @@ criterion_group!(
     bench_add_move,
     bench_render,
     bench_touches_side,
+    bench_random_playout,
+    bench_snapshot_restore,
 );
 
 criterion_main!(benches);
@@ -0,0 +1,473 @@
+//! Export of archives as tables of moves.
+//!
+//! [`export`] flattens the games of an archive into one [`MoveRow`] per
+//! move, so that they can be loaded by data analysis tools such as pandas or
+//! Polars without parsing YEN. The table is written as CSV, or as Parquet
+//! with the `parquet` feature; in both the columns are:
+//!
+//! | Column        | Type            | Content                                      |
+//! |---------------|-----------------|----------------------------------------------|
+//! | `game`        | integer         | number of the game in the archive, from 1    |
+//! | `move_number` | integer         | number of the move in the game, from 1       |
+//! | `player`      | integer         | player who made the move                     |
+//! | `cell`        | integer or null | cell index of a placement                    |
+//! | `action`      | string or null  | `swap` or `resign` for other moves           |
+//! | `topology`    | string          | `y` or `hex`                                 |
+//! | `size`        | integer         | board size                                   |
+//! | `result`      | string or null  | `win` or `loss` for the player, null if the game was not finished |
+
+use crate::ArchivedGame;
+use gamey_core::{BoardTopology, GameY, GameYError, Movement};
+use std::fmt::Display;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// The rows written at once, one row group in Parquet files.
+const BATCH_ROWS: usize = 65_536;
+
+/// One move of an archived game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRow {
+    /// The number of the game in the archive.
+    pub game: usize,
+    /// The number of the move in the game, from 1.
+    pub move_number: usize,
+    /// The player who made the move.
+    pub player: u32,
+    /// The cell index of a placement.
+    pub cell: Option<u32>,
+    /// The action of a move that is not a placement.
+    pub action: Option<String>,
+    /// The topology of the board.
+    pub topology: BoardTopology,
+    /// The size of the board.
+    pub size: u32,
+    /// Whether the player won the game, if it was finished.
+    pub won: Option<bool>,
+}
+
+impl MoveRow {
+    /// Returns the rows of the moves of `game`, the game number `number` of
+    /// its archive.
+    pub fn rows(number: usize, game: &GameY) -> Vec<MoveRow> {
+        let winner = game.winner();
+        game.history()
+            .iter()
+            .enumerate()
+            .map(|(index, movement)| {
+                let (cell, action) = match movement {
+                    Movement::Placement { coords, .. } => (Some(game.cell_index(*coords)), None),
+                    Movement::Action { action, .. } => {
+                        (None, Some(action.to_string().to_lowercase()))
+                    }
+                };
+                MoveRow {
+                    game: number,
+                    move_number: index + 1,
+                    player: movement.player().id(),
+                    cell,
+                    action,
+                    topology: game.topology(),
+                    size: game.board_size(),
+                    won: winner.map(|winner| winner == movement.player()),
+                }
+            })
+            .collect()
+    }
+
+    fn result(&self) -> Option<&'static str> {
+        self.won.map(|won| if won { "win" } else { "loss" })
+    }
+}
+
+/// The file formats of [`export`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Comma-separated values with a header row; nulls are empty fields.
+    Csv,
+    /// Apache Parquet, one row group per 65536 moves.
+    #[cfg(feature = "parquet")]
+    Parquet,
+}
+
+impl ExportFormat {
+    /// Returns the format suggested by the extension of `path`: Parquet
+    /// for `.parquet`, CSV otherwise.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            #[cfg(feature = "parquet")]
+            Some("parquet") => ExportFormat::Parquet,
+            _ => ExportFormat::Csv,
+        }
+    }
+}
+
+impl Display for ExportFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportFormat::Csv => write!(f, "csv"),
+            #[cfg(feature = "parquet")]
+            ExportFormat::Parquet => write!(f, "parquet"),
+        }
+    }
+}
+
+impl FromStr for ExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(ExportFormat::Csv),
+            #[cfg(feature = "parquet")]
+            "parquet" => Ok(ExportFormat::Parquet),
+            #[cfg(not(feature = "parquet"))]
+            "parquet" => Err("gamey was built without the parquet feature".to_string()),
+            _ => Err(format!(
+                "Unknown export format '{}', expected csv or parquet",
+                s
+            )),
+        }
+    }
+}
+
+/// Writes the moves of `games` to the file at `path` in `format` and
+/// returns the number of games written.
+///
+/// # Errors
+/// Returns the first error of `games`, and `GameYError::IoError` if the
+/// file cannot be written.
+pub fn export(
+    games: impl IntoIterator<Item = Result<ArchivedGame, GameYError>>,
+    format: ExportFormat,
+    path: &Path,
+) -> Result<usize, GameYError> {
+    let name = path.display().to_string();
+    let file = File::create(path).map_err(|e| io_error(&name, e))?;
+    export_to(games, format, file, &name)
+}
+
+/// Writes the moves of `games` to `writer` in `format`, like [`export`];
+/// `name` stands for the writer in errors.
+///
+/// # Errors
+/// Returns the first error of `games`, and `GameYError::IoError` if the
+/// table cannot be written.
+pub fn export_to<W: Write + Send + 'static>(
+    games: impl IntoIterator<Item = Result<ArchivedGame, GameYError>>,
+    format: ExportFormat,
+    writer: W,
+    name: &str,
+) -> Result<usize, GameYError> {
+    let mut table: Box<dyn Table> = match format {
+        ExportFormat::Csv => {
+            Box::new(CsvTable::new(Box::new(writer)).map_err(|e| io_error(name, e))?)
+        }
+        #[cfg(feature = "parquet")]
+        ExportFormat::Parquet => {
+            Box::new(parquet_table::ParquetTable::new(Box::new(writer), name)?)
+        }
+    };
+    let mut rows = Vec::new();
+    let mut written = 0;
+    for archived in games {
+        let archived = archived?;
+        rows.extend(MoveRow::rows(archived.number, &archived.game));
+        written += 1;
+        if rows.len() >= BATCH_ROWS {
+            table.write_rows(&rows, name)?;
+            rows.clear();
+        }
+    }
+    if !rows.is_empty() {
+        table.write_rows(&rows, name)?;
+    }
+    table.finish(name)?;
+    Ok(written)
+}
+
+/// The writer a table is written to.
+type Output = Box<dyn Write + Send>;
+
+/// A file the rows are written to.
+trait Table {
+    fn write_rows(&mut self, rows: &[MoveRow], name: &str) -> Result<(), GameYError>;
+    fn finish(self: Box<Self>, name: &str) -> Result<(), GameYError>;
+}
+
+struct CsvTable {
+    writer: BufWriter<Output>,
+}
+
+impl CsvTable {
+    fn new(output: Output) -> std::io::Result<Self> {
+        let mut writer = BufWriter::new(output);
+        writeln!(
+            writer,
+            "game,move_number,player,cell,action,topology,size,result"
+        )?;
+        Ok(CsvTable { writer })
+    }
+}
+
+impl Table for CsvTable {
+    fn write_rows(&mut self, rows: &[MoveRow], name: &str) -> Result<(), GameYError> {
+        for row in rows {
+            writeln!(
+                self.writer,
+                "{},{},{},{},{},{},{},{}",
+                row.game,
+                row.move_number,
+                row.player,
+                row.cell.map(|cell| cell.to_string()).unwrap_or_default(),
+                row.action.as_deref().unwrap_or_default(),
+                row.topology,
+                row.size,
+                row.result().unwrap_or_default()
+            )
+            .map_err(|e| io_error(name, e))?;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self: Box<Self>, name: &str) -> Result<(), GameYError> {
+        self.writer.flush().map_err(|e| io_error(name, e))
+    }
+}
+
+#[cfg(feature = "parquet")]
+mod parquet_table {
+    use super::{MoveRow, Output, Table};
+    use gamey_core::GameYError;
+    use parquet::data_type::{ByteArray, ByteArrayType, Int32Type, Int64Type};
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+    use std::sync::Arc;
+
+    const SCHEMA: &str = "
+        message moves {
+            REQUIRED INT64 game;
+            REQUIRED INT32 move_number;
+            REQUIRED INT32 player;
+            OPTIONAL INT32 cell;
+            OPTIONAL BYTE_ARRAY action (UTF8);
+            REQUIRED BYTE_ARRAY topology (UTF8);
+            REQUIRED INT32 size;
+            OPTIONAL BYTE_ARRAY result (UTF8);
+        }
+    ";
+
+    pub(super) struct ParquetTable {
+        writer: SerializedFileWriter<Output>,
+    }
+
+    impl ParquetTable {
+        pub(super) fn new(output: Output, name: &str) -> Result<Self, GameYError> {
+            let schema = parse_message_type(SCHEMA).map_err(|e| parquet_error(name, e))?;
+            let writer = SerializedFileWriter::new(output, Arc::new(schema), Default::default())
+                .map_err(|e| parquet_error(name, e))?;
+            Ok(ParquetTable { writer })
+        }
+    }
+
+    /// The values of a column and, for optional columns, whether each row
+    /// has one.
+    enum Column {
+        Int64(Vec<i64>),
+        Int32(Vec<i32>, Option<Vec<i16>>),
+        Text(Vec<ByteArray>, Option<Vec<i16>>),
+    }
+
+    fn int32(rows: &[MoveRow], value: impl Fn(&MoveRow) -> Option<u32>, optional: bool) -> Column {
+        let values = rows.iter().filter_map(&value).map(|v| v as i32).collect();
+        let levels = optional.then(|| rows.iter().map(|row| value(row).is_some() as i16).collect());
+        Column::Int32(values, levels)
+    }
+
+    fn text(
+        rows: &[MoveRow],
+        value: impl Fn(&MoveRow) -> Option<String>,
+        optional: bool,
+    ) -> Column {
+        let values = rows
+            .iter()
+            .filter_map(&value)
+            .map(|v| ByteArray::from(v.as_str()))
+            .collect();
+        let levels = optional.then(|| rows.iter().map(|row| value(row).is_some() as i16).collect());
+        Column::Text(values, levels)
+    }
+
+    impl Table for ParquetTable {
+        fn write_rows(&mut self, rows: &[MoveRow], name: &str) -> Result<(), GameYError> {
+            let columns = [
+                Column::Int64(rows.iter().map(|row| row.game as i64).collect()),
+                int32(rows, |row| Some(row.move_number as u32), false),
+                int32(rows, |row| Some(row.player), false),
+                int32(rows, |row| row.cell, true),
+                text(rows, |row| row.action.clone(), true),
+                text(rows, |row| Some(row.topology.to_string()), false),
+                int32(rows, |row| Some(row.size), false),
+                text(rows, |row| row.result().map(str::to_string), true),
+            ];
+            let error = |e| parquet_error(name, e);
+            let mut group = self.writer.next_row_group().map_err(error)?;
+            for column in &columns {
+                let Some(mut writer) = group.next_column().map_err(error)? else {
+                    break;
+                };
+                match column {
+                    Column::Int64(values) => {
+                        writer.typed::<Int64Type>().write_batch(values, None, None)
+                    }
+                    Column::Int32(values, levels) => {
+                        writer
+                            .typed::<Int32Type>()
+                            .write_batch(values, levels.as_deref(), None)
+                    }
+                    Column::Text(values, levels) => {
+                        writer
+                            .typed::<ByteArrayType>()
+                            .write_batch(values, levels.as_deref(), None)
+                    }
+                }
+                .map_err(error)?;
+                writer.close().map_err(error)?;
+            }
+            group.close().map_err(error)?;
+            Ok(())
+        }
+
+        fn finish(self: Box<Self>, name: &str) -> Result<(), GameYError> {
+            self.writer
+                .close()
+                .map(|_| ())
+                .map_err(|e| parquet_error(name, e))
+        }
+    }
+
+    fn parquet_error(name: &str, e: parquet::errors::ParquetError) -> GameYError {
+        GameYError::IoError {
+            message: format!("Failed to write {}", name),
+            error: e.to_string(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{ExportFormat, export};
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        #[test]
+        fn test_parquet_export() {
+            let dir = tempfile::tempdir().unwrap();
+            let path = dir.path().join("moves.parquet");
+            assert_eq!(ExportFormat::from_path(&path), ExportFormat::Parquet);
+            export(super::super::tests::games(), ExportFormat::Parquet, &path).unwrap();
+
+            let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+            assert_eq!(reader.metadata().file_metadata().num_rows(), 3);
+            let rows: Vec<String> = reader
+                .get_row_iter(None)
+                .unwrap()
+                .map(|row| row.unwrap().to_string())
+                .collect();
+            assert!(rows[0].contains("cell: 0"));
+            assert!(rows[0].contains("action: null"));
+            assert!(rows[2].contains("result: null"));
+        }
+    }
+}
+
+fn io_error(name: &str, e: std::io::Error) -> GameYError {
+    GameYError::IoError {
+        message: format!("Failed to write {}", name),
+        error: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{Coordinates, GameAction, PlayerId};
+
+    /// A finished game with a swap and an unfinished one.
+    pub(crate) fn games() -> Vec<Result<ArchivedGame, GameYError>> {
+        let mut swapped = GameY::new(2);
+        swapped
+            .add_move(Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(1, 0, 0),
+            })
+            .unwrap();
+        swapped
+            .add_move(Movement::Action {
+                player: PlayerId::new(1),
+                action: GameAction::Swap,
+            })
+            .unwrap();
+        let mut won = GameY::new(1);
+        won.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 0, 0),
+        })
+        .unwrap();
+        vec![
+            Ok(ArchivedGame {
+                number: 1,
+                game: won,
+            }),
+            Ok(ArchivedGame {
+                number: 2,
+                game: swapped,
+            }),
+        ]
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("moves.csv");
+        assert_eq!(ExportFormat::from_path(&path), ExportFormat::Csv);
+        assert_eq!(export(games(), ExportFormat::Csv, &path).unwrap(), 2);
+        let csv = std::fs::read_to_string(&path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "game,move_number,player,cell,action,topology,size,result",
+                "1,1,0,0,,y,1,win",
+                "2,1,0,0,,y,2,",
+                "2,2,1,,swap,y,2,",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_export_stops_at_an_invalid_game() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut games = games();
+        games.insert(
+            1,
+            Err(GameYError::InvalidArchive {
+                file: "archive.jsonl".to_string(),
+                line: 2,
+                message: "not json".to_string(),
+            }),
+        );
+        let result = export(games, ExportFormat::Csv, &dir.path().join("moves.csv"));
+        assert!(matches!(result, Err(GameYError::InvalidArchive { .. })));
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!("CSV".parse::<ExportFormat>(), Ok(ExportFormat::Csv));
+        assert!("xlsx".parse::<ExportFormat>().is_err());
+        assert_eq!(ExportFormat::Csv.to_string(), "csv");
+        #[cfg(feature = "parquet")]
+        assert_eq!("parquet".parse::<ExportFormat>(), Ok(ExportFormat::Parquet));
+        #[cfg(not(feature = "parquet"))]
+        assert!("parquet".parse::<ExportFormat>().is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Archives of finished games.
+//!
+//! An archive is a [JSON Lines](https://jsonlines.org) file holding one game
+//! per line, as a YEN document with its move history (see
+//! [`YEN::with_history`]). Games are numbered from 1 in the order of the
+//! file. [`ArchiveWriter`] appends games to an archive and
+//! [`ArchiveReader`] reads them back one at a time, so archives larger than
+//! memory can be scanned. Archives ending in `.gz` or `.zst` are compressed
+//! (see [`Compression`]); games appended to them are compressed as a new
+//! stream after the previous ones.
+//!
+//! [`ArchiveStats`] (see [`stats`]) summarizes the games of an archive, and
+//! a [`PositionIndex`] (see [`position`]) finds the games reaching a
+//! position. [`export`] flattens an archive into a table of moves, as CSV
+//! or Parquet, and [`TrainingSamples`] (see [`samples`]) turns its games
+//! into samples for training neural networks.
+//!
+//! # Example
+//! ```
+//! use gamey_archive::{ArchiveReader, ArchiveWriter};
+//! use gamey_core::GameY;
+//!
+//! let mut archive = Vec::new();
+//! ArchiveWriter::new(&mut archive).append(&GameY::new(3)).unwrap();
+//! let games: Vec<_> = ArchiveReader::new(archive.as_slice(), "memory").collect();
+//! assert_eq!(games.len(), 1);
+//! ```
+
+pub mod export;
+pub mod position;
+pub mod samples;
+pub mod stats;
+pub use export::{ExportFormat, MoveRow, export, export_to};
+pub use position::*;
+pub use samples::*;
+pub use stats::*;
+
+use gamey_core::{CompressedWriter, Compression, GameY, GameYError, MoveRecord, YEN};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::path::Path;
+
+/// A game read from an archive.
+#[derive(Debug, Clone)]
+pub struct ArchivedGame {
+    /// The number of the game in the archive, from 1.
+    pub number: usize,
+    /// The game, with its history.
+    pub game: GameY,
+}
+
+/// Reads the games of an archive in order.
+///
+/// Blank lines are skipped. A line that is not a valid game yields a
+/// `GameYError::InvalidArchive` naming the line; reading can go on after
+/// it.
+pub struct ArchiveReader<R> {
+    lines: std::io::Lines<BufReader<R>>,
+    name: String,
+    line: usize,
+    number: usize,
+}
+
+impl ArchiveReader<Box<dyn Read>> {
+    /// Opens the archive file at `path`, decompressing it if its extension
+    /// says so.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be opened or its
+    /// compression is not available.
+    pub fn open(path: &Path) -> Result<Self, GameYError> {
+        let file = File::open(path).map_err(|e| GameYError::IoError {
+            message: format!("Failed to open archive {}", path.display()),
+            error: e.to_string(),
+        })?;
+        let reader = Compression::from_path(path).reader(file)?;
+        Ok(ArchiveReader::new(reader, &path.display().to_string()))
+    }
+}
+
+impl<R: Read> ArchiveReader<R> {
+    /// Reads an archive from `reader`; `name` stands for it in errors.
+    pub fn new(reader: R, name: &str) -> Self {
+        ArchiveReader {
+            lines: BufReader::new(reader).lines(),
+            name: name.to_string(),
+            line: 0,
+            number: 0,
+        }
+    }
+
+    fn invalid(&self, message: String) -> GameYError {
+        GameYError::InvalidArchive {
+            file: self.name.clone(),
+            line: self.line,
+            message,
+        }
+    }
+}
+
+impl<R: Read> Iterator for ArchiveReader<R> {
+    type Item = Result<ArchivedGame, GameYError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let text = match self.lines.next()? {
+                Ok(text) => text,
+                Err(e) => {
+                    return Some(Err(GameYError::IoError {
+                        message: format!("Failed to read archive {}", self.name),
+                        error: e.to_string(),
+                    }));
+                }
+            };
+            self.line += 1;
+            if text.trim().is_empty() {
+                continue;
+            }
+            self.number += 1;
+            let game = serde_json::from_str::<YEN>(&text)
+                .map_err(|e| e.to_string())
+                .and_then(|yen| GameY::try_from(yen).map_err(|e| e.to_string()));
+            return Some(match game {
+                Ok(game) => Ok(ArchivedGame {
+                    number: self.number,
+                    game,
+                }),
+                Err(message) => Err(self.invalid(message)),
+            });
+        }
+    }
+}
+
+/// Appends games to an archive.
+pub struct ArchiveWriter<W> {
+    writer: W,
+}
+
+impl ArchiveWriter<CompressedWriter<File>> {
+    /// Opens the archive file at `path` for appending, creating it if
+    /// needed, and compressing the games if its extension says so.
+    ///
+    /// The compressed stream is completed by [`ArchiveWriter::finish`], or
+    /// when the writer is dropped.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be opened or its
+    /// compression is not available.
+    pub fn append_to(path: &Path) -> Result<Self, GameYError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|e| GameYError::IoError {
+                message: format!("Failed to open archive {}", path.display()),
+                error: e.to_string(),
+            })?;
+        Ok(ArchiveWriter::new(
+            Compression::from_path(path).writer(file)?,
+        ))
+    }
+
+    /// Completes the archive file.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the end of a compressed stream
+    /// cannot be written.
+    pub fn finish(self) -> Result<(), GameYError> {
+        self.writer.finish().map(|_| ())
+    }
+}
+
+impl<W: Write> ArchiveWriter<W> {
+    /// Writes an archive to `writer`.
+    pub fn new(writer: W) -> Self {
+        ArchiveWriter { writer }
+    }
+
+    /// Writes `game` with its history as one line.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the line cannot be written.
+    pub fn append(&mut self, game: &GameY) -> Result<(), GameYError> {
+        let history = game.history().iter().map(MoveRecord::from).collect();
+        let yen = YEN::from(game)
+            .with_history(history, game.annotations().to_vec())
+            .with_metadata(game.metadata().clone());
+        let mut line = serde_json::to_vec(&yen).map_err(|e| GameYError::SerdeError { error: e })?;
+        line.push(b'\n');
+        self.writer
+            .write_all(&line)
+            .map_err(|e| GameYError::IoError {
+                message: "Failed to write to the archive".to_string(),
+                error: e.to_string(),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{Coordinates, Movement, PlayerId};
+
+    fn played(size: u32, moves: &[Coordinates]) -> GameY {
+        let mut game = GameY::new(size);
+        for &coords in moves {
+            let player = game.next_player().unwrap();
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_write_and_read_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("archive.jsonl");
+        let game = played(3, &[Coordinates::new(2, 0, 0), Coordinates::new(1, 1, 0)]);
+        ArchiveWriter::append_to(&path)
+            .unwrap()
+            .append(&game)
+            .unwrap();
+        let mut writer = ArchiveWriter::append_to(&path).unwrap();
+        writer.append(&GameY::new(4)).unwrap();
+
+        let games: Vec<ArchivedGame> = ArchiveReader::open(&path)
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(games.len(), 2);
+        assert_eq!(games[0].number, 1);
+        assert_eq!(games[0].game.history(), game.history());
+        assert_eq!(games[0].game.next_player(), Some(PlayerId::new(0)));
+        assert_eq!(games[1].game.board_size(), 4);
+    }
+
+    #[test]
+    fn test_invalid_lines_are_reported() {
+        let archive =
+            "{\"size\":1,\"turn\":0,\"players\":[\"B\",\"R\"],\"layout\":\".\"}\n\nnot json\n";
+        let results: Vec<_> = ArchiveReader::new(archive.as_bytes(), "test.jsonl").collect();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].is_ok());
+        match &results[1] {
+            Err(GameYError::InvalidArchive { file, line, .. }) => {
+                assert_eq!((file.as_str(), *line), ("test.jsonl", 3));
+            }
+            other => panic!("expected an invalid archive error, got {:?}", other),
+        }
+        assert!(ArchiveReader::open(Path::new("/no/such/archive.jsonl")).is_err());
+    }
+}
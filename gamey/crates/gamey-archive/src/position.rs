@@ -0,0 +1,297 @@
+//! A small position database over the games of an archive.
+//!
+//! A [`PositionIndex`] records every position reached by the games added to
+//! it, keyed by [`GameY::canonical_hash`], so that symmetric positions are
+//! found as one. [`PositionIndex::find`] returns the games that reached a
+//! position and the moves played next, with how they scored. Continuations
+//! are grouped by the position they lead to and given in the orientation of
+//! the position asked for, whatever the orientation the games reached it
+//! in.
+
+use gamey_core::{GameAction, GameY, Movement, PlayerId};
+use std::collections::HashMap;
+use std::fmt::Display;
+
+/// A game reaching an indexed position.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    /// The number of the game in the archive.
+    pub number: usize,
+    /// The moves played when the game reached the position.
+    pub moves: usize,
+    /// The move played next, in the orientation of the game; `None` if the
+    /// game stopped there.
+    pub next: Option<Movement>,
+    /// The winner of the game, if it was finished.
+    pub winner: Option<PlayerId>,
+    /// The canonical hash of the position after `next`.
+    next_hash: Option<u64>,
+}
+
+/// A move played from a position, and how it scored.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Continuation {
+    /// The move, in the orientation of the position asked for.
+    pub movement: Movement,
+    /// The games where the move was played.
+    pub games: u32,
+    /// The games among them that reached a winner.
+    pub finished: u32,
+    /// The finished games won by the player who made the move.
+    pub wins: u32,
+}
+
+impl Continuation {
+    /// Returns the fraction of the finished games won by the player who
+    /// made the move.
+    pub fn win_rate(&self) -> f64 {
+        match self.finished {
+            0 => 0.0,
+            finished => f64::from(self.wins) / f64::from(finished),
+        }
+    }
+}
+
+/// The games reaching a position and what was played next.
+#[derive(Debug, Clone)]
+pub struct PositionReport {
+    /// The position asked for.
+    pub position: GameY,
+    /// Every time a game reached it, by game number.
+    pub occurrences: Vec<Occurrence>,
+    /// The moves played next, the most played first.
+    pub continuations: Vec<Continuation>,
+}
+
+/// The positions reached by a collection of games.
+#[derive(Debug, Clone, Default)]
+pub struct PositionIndex {
+    positions: HashMap<u64, Vec<Occurrence>>,
+    games: usize,
+}
+
+impl PositionIndex {
+    /// Creates an empty index.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the number of games indexed.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Returns the number of distinct positions indexed, up to symmetry.
+    pub fn positions(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Indexes every position reached by `game`, the game number `number`
+    /// of its archive.
+    pub fn add(&mut self, number: usize, game: &GameY) {
+        self.games += 1;
+        let Ok(mut replay) = game.position_after(0) else {
+            return;
+        };
+        let winner = game.winner();
+        let mut hash = replay.canonical_hash();
+        let history = game.history();
+        for moves in 0..=history.len() {
+            let next = history.get(moves).cloned();
+            let next_hash = match &next {
+                Some(movement) if replay.add_move(movement.clone()).is_ok() => {
+                    Some(replay.canonical_hash())
+                }
+                _ => None,
+            };
+            self.positions.entry(hash).or_default().push(Occurrence {
+                number,
+                moves,
+                next: next.filter(|_| next_hash.is_some()),
+                winner,
+                next_hash,
+            });
+            let Some(next_hash) = next_hash else {
+                break;
+            };
+            hash = next_hash;
+        }
+    }
+
+    /// Returns the games reaching `position`, or a position symmetric to
+    /// it, and the moves played next.
+    pub fn find(&self, position: &GameY) -> PositionReport {
+        let occurrences = self
+            .positions
+            .get(&position.canonical_hash())
+            .cloned()
+            .unwrap_or_default();
+
+        // The move of `position` leading to each position after it
+        let mut moves: HashMap<u64, Movement> = HashMap::new();
+        if let Some(player) = position.next_player() {
+            let candidates = position
+                .available_cells_in_order()
+                .map(|index| Movement::Placement {
+                    player,
+                    coords: position.cell_coords(index),
+                })
+                .chain(
+                    [GameAction::Swap, GameAction::Resign]
+                        .map(|action| Movement::Action { player, action }),
+                );
+            for movement in candidates {
+                let mut after = position.clone();
+                if after.add_move(movement.clone()).is_ok() {
+                    moves.entry(after.canonical_hash()).or_insert(movement);
+                }
+            }
+        }
+
+        let mut continuations: Vec<Continuation> = Vec::new();
+        for occurrence in &occurrences {
+            let (Some(next), Some(hash)) = (&occurrence.next, occurrence.next_hash) else {
+                continue;
+            };
+            let movement = moves.get(&hash).cloned().unwrap_or_else(|| next.clone());
+            let index = match continuations.iter().position(|c| c.movement == movement) {
+                Some(index) => index,
+                None => {
+                    continuations.push(Continuation {
+                        movement,
+                        games: 0,
+                        finished: 0,
+                        wins: 0,
+                    });
+                    continuations.len() - 1
+                }
+            };
+            let continuation = &mut continuations[index];
+            continuation.games += 1;
+            if let Some(winner) = occurrence.winner {
+                continuation.finished += 1;
+                if winner == next.player() {
+                    continuation.wins += 1;
+                }
+            }
+        }
+        continuations.sort_by(|a, b| {
+            b.games
+                .cmp(&a.games)
+                .then_with(|| b.win_rate().total_cmp(&a.win_rate()))
+        });
+        PositionReport {
+            position: position.clone(),
+            occurrences,
+            continuations,
+        }
+    }
+}
+
+impl Display for PositionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.occurrences.is_empty() {
+            return writeln!(f, "No game reached the position");
+        }
+        let games: Vec<String> = self
+            .occurrences
+            .iter()
+            .map(|o| format!("{} (before move {})", o.number, o.moves + 1))
+            .collect();
+        writeln!(
+            f,
+            "Reached in {} games: {}",
+            self.occurrences.len(),
+            games.join(", ")
+        )?;
+        let stopped = self.occurrences.iter().filter(|o| o.next.is_none()).count();
+        if stopped > 0 {
+            writeln!(f, "{} of them stopped there", stopped)?;
+        }
+        if self.continuations.is_empty() {
+            return Ok(());
+        }
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:>10} {:>6} {:>6} {:>6}",
+            "Next", "Games", "Wins", "Win %"
+        )?;
+        for continuation in &self.continuations {
+            let next = match &continuation.movement {
+                Movement::Placement { coords, .. } => coords.to_string(),
+                Movement::Action { action, .. } => action.to_string(),
+            };
+            writeln!(
+                f,
+                "{:>10} {:>6} {:>6} {:>6.1}",
+                next,
+                continuation.games,
+                continuation.wins,
+                100.0 * continuation.win_rate()
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::Coordinates;
+
+    fn played(moves: &[Coordinates]) -> GameY {
+        let mut game = GameY::new(3);
+        for &coords in moves {
+            let player = game.next_player().unwrap();
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_symmetric_positions_are_found_together() {
+        // Games opening in two different corners reach symmetric positions
+        let games = [
+            played(&[Coordinates::new(2, 0, 0), Coordinates::new(1, 1, 0)]),
+            played(&[Coordinates::new(0, 2, 0), Coordinates::new(1, 1, 0)]),
+            played(&[Coordinates::new(0, 0, 2)]),
+            played(&[Coordinates::new(1, 0, 1)]),
+        ];
+        let mut index = PositionIndex::new();
+        for (number, game) in games.iter().enumerate() {
+            index.add(number + 1, game);
+        }
+        assert_eq!(index.games(), 4);
+
+        let report = index.find(&played(&[Coordinates::new(2, 0, 0)]));
+        let numbers: Vec<usize> = report.occurrences.iter().map(|o| o.number).collect();
+        assert_eq!(numbers, vec![1, 2, 3]);
+        assert_eq!(report.continuations.len(), 1);
+        let continuation = &report.continuations[0];
+        assert_eq!(continuation.games, 2);
+        // Game 2 opened in another corner, but its reply is the same up to
+        // symmetry
+        assert!(matches!(
+            continuation.movement,
+            Movement::Placement { player, .. } if player == PlayerId::new(1)
+        ));
+        let text = report.to_string();
+        assert!(text.starts_with(
+            "Reached in 3 games: 1 (before move 2), 2 (before move 2), 3 (before move 2)"
+        ));
+        assert!(text.contains("1 of them stopped there"));
+    }
+
+    #[test]
+    fn test_unknown_position() {
+        let mut index = PositionIndex::new();
+        index.add(1, &played(&[Coordinates::new(2, 0, 0)]));
+        let report = index.find(&played(&[Coordinates::new(1, 0, 1)]));
+        assert!(report.occurrences.is_empty());
+        assert_eq!(report.to_string(), "No game reached the position\n");
+        // The empty board is reached by every game
+        assert_eq!(index.find(&GameY::new(3)).occurrences.len(), 1);
+    }
+}
@@ -0,0 +1,195 @@
+//! Training samples for neural networks, from the games of an archive.
+//!
+//! [`TrainingSamples`] walks the history of a game and yields one
+//! [`TrainingSample`] per placement: the position before the move, encoded
+//! by [`GameY::encode_planes`], the cell played and the result of the game.
+//! With [`TrainingSamples::with_symmetries`] every position is also yielded
+//! under each symmetry of the board (see [`Symmetry::of`]), the move moved
+//! along, which multiplies the data by six on Y boards and by two on Hex.
+//!
+//! # Example
+//! ```
+//! use gamey_archive::TrainingSamples;
+//! use gamey_core::GameY;
+//!
+//! // The first player connects the three sides of a size 2 board
+//! let mut game = GameY::new(2);
+//! game.play_move_list("1 0 2").unwrap();
+//! let samples: Vec<_> = TrainingSamples::new(&game).collect();
+//! assert_eq!(samples.len(), 3);
+//! assert_eq!(samples[0].move_index, 1);
+//! assert_eq!(samples[0].value(), 1.0);
+//! assert_eq!(samples[1].value(), -1.0);
+//!
+//! let augmented = TrainingSamples::new(&game).with_symmetries().count();
+//! assert_eq!(augmented, 3 * 6);
+//! ```
+
+use gamey_core::{GameY, Movement, PlayerId, Symmetry};
+
+/// A position of a game with the move played in it and the result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrainingSample {
+    /// The position before the move, see [`GameY::encode_planes`].
+    pub planes: Vec<f32>,
+    /// The index of the cell played, in the position of `planes`.
+    pub move_index: u32,
+    /// The player who made the move.
+    pub player: PlayerId,
+    /// The winner of the game, or `None` if it did not end.
+    pub winner: Option<PlayerId>,
+    /// The number of the move in the history of the game, from 1.
+    pub move_number: usize,
+    /// The symmetry the position and the move were transformed by.
+    pub symmetry: Symmetry,
+}
+
+impl TrainingSample {
+    /// Returns the result for the player who moved: 1 for a win, -1 for a
+    /// loss and 0 for a game that did not end.
+    pub fn value(&self) -> f32 {
+        match self.winner {
+            Some(winner) if winner == self.player => 1.0,
+            Some(_) => -1.0,
+            None => 0.0,
+        }
+    }
+}
+
+/// Yields the [`TrainingSample`]s of a game, move by move; see the
+/// [module documentation](self).
+///
+/// Actions, such as a swap or a resignation, are replayed but yield no
+/// sample. Under augmentation, the samples of a move come one per symmetry,
+/// [`Symmetry::Identity`] first.
+pub struct TrainingSamples {
+    game: GameY,
+    symmetries: Vec<Symmetry>,
+    winner: Option<PlayerId>,
+    /// The position before the next move under every symmetry, built on the
+    /// first call to `next`.
+    boards: Option<Vec<(Symmetry, GameY)>>,
+    move_number: usize,
+    board: usize,
+}
+
+impl TrainingSamples {
+    /// Walks the moves of `game`, without augmentation.
+    pub fn new(game: &GameY) -> Self {
+        let winner = game.winner();
+        TrainingSamples {
+            game: game.clone(),
+            symmetries: vec![Symmetry::Identity],
+            winner,
+            boards: None,
+            move_number: 0,
+            board: 0,
+        }
+    }
+
+    /// Also yields every position under each other symmetry of the board.
+    ///
+    /// Symmetries under which the game cannot be replayed, e.g. because a
+    /// move would land on a blocked cell of the variant, are left out.
+    pub fn with_symmetries(mut self) -> Self {
+        self.symmetries = Symmetry::of(self.game.topology()).to_vec();
+        self
+    }
+
+    /// Returns the starting position of the game under every symmetry it
+    /// can be replayed under.
+    fn start(&self) -> Vec<(Symmetry, GameY)> {
+        self.symmetries
+            .iter()
+            .filter_map(|&symmetry| {
+                let start = self.game.transformed(symmetry).ok()?.position_after(0);
+                Some((symmetry, start.ok()?))
+            })
+            .collect()
+    }
+}
+
+impl Iterator for TrainingSamples {
+    type Item = TrainingSample;
+
+    fn next(&mut self) -> Option<TrainingSample> {
+        if self.boards.is_none() {
+            self.boards = Some(self.start());
+        }
+        let boards = self.boards.as_mut()?;
+        let size = self.game.board_size();
+        loop {
+            let movement = self.game.history().get(self.move_number)?;
+            if let Some((symmetry, position)) = boards.get(self.board) {
+                self.board += 1;
+                if let Movement::Placement { player, coords } = movement {
+                    return Some(TrainingSample {
+                        planes: position.encode_planes(),
+                        move_index: position.cell_index(symmetry.apply(*coords, size)),
+                        player: *player,
+                        winner: self.winner,
+                        move_number: self.move_number + 1,
+                        symmetry: *symmetry,
+                    });
+                }
+                continue;
+            }
+            for (symmetry, position) in boards.iter_mut() {
+                position
+                    .add_move(movement.transformed(*symmetry, size))
+                    .ok()?;
+            }
+            self.move_number += 1;
+            self.board = 0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{BoardTopology, GameAction};
+
+    #[test]
+    fn test_samples_follow_the_game() {
+        let mut game = GameY::new(4);
+        game.play_move_list("0 6 3 9").unwrap();
+        let samples: Vec<TrainingSample> = TrainingSamples::new(&game).collect();
+        let moves: Vec<u32> = samples.iter().map(|s| s.move_index).collect();
+        assert_eq!(moves, vec![0, 6, 3, 9]);
+        assert_eq!(
+            samples[1].planes,
+            game.position_after(1).unwrap().encode_planes()
+        );
+        assert_eq!(samples[3].move_number, 4);
+        // The game is not over
+        assert!(
+            samples
+                .iter()
+                .all(|s| s.winner.is_none() && s.value() == 0.0)
+        );
+    }
+
+    #[test]
+    fn test_symmetries_move_the_position_and_the_move_together() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        game.play_move_list("0 4").unwrap();
+        game.add_move(Movement::Action {
+            player: PlayerId::new(0),
+            action: GameAction::Resign,
+        })
+        .unwrap();
+        let samples: Vec<TrainingSample> = TrainingSamples::new(&game).with_symmetries().collect();
+        assert_eq!(samples.len(), 4);
+        assert_eq!(samples[0].symmetry, Symmetry::Identity);
+        assert_eq!(samples[1].symmetry, Symmetry::HalfTurn);
+        // The corner goes to the opposite corner
+        assert_eq!(samples[1].move_index, 8);
+        assert_eq!(samples[2].move_index, 4);
+        assert_eq!(samples[3].move_index, 4);
+        assert_eq!(samples[3].planes[8], 1.0);
+        // The first player resigned
+        assert_eq!(samples[0].value(), -1.0);
+        assert_eq!(samples[2].value(), 1.0);
+    }
+}
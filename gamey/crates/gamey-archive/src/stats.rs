@@ -0,0 +1,408 @@
+//! Statistics over the games of an archive.
+//!
+//! [`ArchiveStats`] gathers, for every board (topology and size), how often
+//! the first player wins, how often the swap rule is used, how long games
+//! last, how many moves are available on average (the branching factor)
+//! and which opening cells are played the most; and the distribution of
+//! game lengths over the whole archive. It prints as text tables, and the
+//! `*_csv` methods give the same figures in a form ready for plotting.
+
+use gamey_core::{BoardTopology, Coordinates, GameAction, GameStatus, GameY, GameYError, Movement};
+use std::collections::BTreeMap;
+use std::fmt::Display;
+
+/// The number of opening cells of every board shown in the text tables.
+const TOP_OPENINGS: usize = 5;
+
+/// The statistics of the games played on one board.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoardStats {
+    /// The topology of the board.
+    pub topology: BoardTopology,
+    /// The size of the board.
+    pub size: u32,
+    /// The games played on the board.
+    pub games: u32,
+    /// The games that reached a winner.
+    pub finished: u32,
+    /// The finished games won by the player who moved first.
+    pub first_player_wins: u32,
+    /// The games where the swap rule was used.
+    pub swaps: u32,
+    /// The moves of all the games, actions included.
+    pub moves: u64,
+    /// The placements of all the games.
+    pub placements: u64,
+    /// The cells available before every placement, summed.
+    pub choices: u64,
+    /// How many games opened on each cell, by cell index.
+    pub openings: BTreeMap<u32, u32>,
+}
+
+impl BoardStats {
+    fn new(topology: BoardTopology, size: u32) -> Self {
+        BoardStats {
+            topology,
+            size,
+            games: 0,
+            finished: 0,
+            first_player_wins: 0,
+            swaps: 0,
+            moves: 0,
+            placements: 0,
+            choices: 0,
+            openings: BTreeMap::new(),
+        }
+    }
+
+    /// Returns the fraction of the finished games won by the first player.
+    pub fn first_player_win_rate(&self) -> f64 {
+        ratio(u64::from(self.first_player_wins), u64::from(self.finished))
+    }
+
+    /// Returns the fraction of the games where the swap rule was used.
+    pub fn swap_rate(&self) -> f64 {
+        ratio(u64::from(self.swaps), u64::from(self.games))
+    }
+
+    /// Returns the average number of moves of a game.
+    pub fn average_length(&self) -> f64 {
+        ratio(self.moves, u64::from(self.games))
+    }
+
+    /// Returns the average number of cells available to a placement.
+    pub fn branching_factor(&self) -> f64 {
+        ratio(self.choices, self.placements)
+    }
+
+    /// Returns the opening cells with their coordinates and number of
+    /// games, the most played first.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidBoardSize` if `size` is not a size games
+    /// can be played on, see [`GameY::check_board_size`].
+    pub fn top_openings(&self) -> Result<Vec<(u32, Coordinates, u32)>, GameYError> {
+        GameY::check_board_size(self.size)?;
+        let board = GameY::with_topology(self.size, self.topology);
+        let mut openings: Vec<(u32, Coordinates, u32)> = self
+            .openings
+            .iter()
+            .map(|(&cell, &games)| (cell, board.cell_coords(cell), games))
+            .collect();
+        openings.sort_by_key(|&(cell, _, games)| (std::cmp::Reverse(games), cell));
+        Ok(openings)
+    }
+
+    fn name(&self) -> String {
+        format!("{} {}", self.topology, self.size)
+    }
+}
+
+/// Statistics over a collection of games.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ArchiveStats {
+    /// One entry per board, by topology and then size.
+    pub boards: Vec<BoardStats>,
+    /// How many games lasted each number of moves.
+    pub lengths: BTreeMap<usize, u32>,
+}
+
+impl ArchiveStats {
+    /// Creates empty statistics.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a game to the statistics.
+    pub fn add(&mut self, game: &GameY) {
+        let (topology, size) = (game.topology(), game.board_size());
+        let position = match self
+            .boards
+            .iter()
+            .position(|b| b.topology == topology && b.size == size)
+        {
+            Some(position) => position,
+            None => {
+                self.boards.push(BoardStats::new(topology, size));
+                self.boards
+                    .sort_by_key(|b| (b.topology == BoardTopology::Hex, b.size));
+                self.boards
+                    .iter()
+                    .position(|b| b.topology == topology && b.size == size)
+                    .unwrap_or_default()
+            }
+        };
+        let board = &mut self.boards[position];
+        let history = game.history();
+        board.games += 1;
+        board.moves += history.len() as u64;
+        *self.lengths.entry(history.len()).or_default() += 1;
+
+        if let GameStatus::Finished { winner } = game.status() {
+            board.finished += 1;
+            let first = history.first().map(|movement| match movement {
+                Movement::Placement { player, .. } | Movement::Action { player, .. } => *player,
+            });
+            if first == Some(*winner) {
+                board.first_player_wins += 1;
+            }
+        }
+        if history.iter().any(|movement| {
+            matches!(
+                movement,
+                Movement::Action {
+                    action: GameAction::Swap,
+                    ..
+                }
+            )
+        }) {
+            board.swaps += 1;
+        }
+        if let Some(Movement::Placement { coords, .. }) = history.first() {
+            *board.openings.entry(game.cell_index(*coords)).or_default() += 1;
+        }
+
+        // Replay the game to count the cells available to every placement
+        let Ok(mut replay) = game.position_after(0) else {
+            return;
+        };
+        for movement in history {
+            if matches!(movement, Movement::Placement { .. }) {
+                board.placements += 1;
+                board.choices += replay.available_cells().len() as u64;
+            }
+            if replay.add_move(movement.clone()).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Returns the number of games.
+    pub fn games(&self) -> u32 {
+        self.boards.iter().map(|b| b.games).sum()
+    }
+
+    /// Returns the number of games that did not reach a winner.
+    pub fn unfinished(&self) -> u32 {
+        self.boards.iter().map(|b| b.games - b.finished).sum()
+    }
+
+    /// Returns the statistics of every board as CSV, with a header row.
+    pub fn boards_csv(&self) -> String {
+        let mut csv = String::from(
+            "topology,size,games,finished,first_player_wins,first_player_win_rate,swaps,swap_rate,average_length,branching_factor\n",
+        );
+        for board in &self.boards {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{:.4},{},{:.4},{:.2},{:.2}\n",
+                board.topology,
+                board.size,
+                board.games,
+                board.finished,
+                board.first_player_wins,
+                board.first_player_win_rate(),
+                board.swaps,
+                board.swap_rate(),
+                board.average_length(),
+                board.branching_factor()
+            ));
+        }
+        csv
+    }
+
+    /// Returns the distribution of game lengths as CSV, with a header row.
+    pub fn lengths_csv(&self) -> String {
+        let mut csv = String::from("moves,games\n");
+        for (moves, games) in &self.lengths {
+            csv.push_str(&format!("{},{}\n", moves, games));
+        }
+        csv
+    }
+
+    /// Returns every opening cell of every board as CSV, with a header row.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidBoardSize` for boards no game can be
+    /// played on, see [`BoardStats::top_openings`].
+    pub fn openings_csv(&self) -> Result<String, GameYError> {
+        let mut csv = String::from("topology,size,cell,x,y,z,games\n");
+        for board in &self.boards {
+            for (cell, coords, games) in board.top_openings()? {
+                csv.push_str(&format!(
+                    "{},{},{},{},{},{},{}\n",
+                    board.topology,
+                    board.size,
+                    cell,
+                    coords.x(),
+                    coords.y(),
+                    coords.z(),
+                    games
+                ));
+            }
+        }
+        Ok(csv)
+    }
+}
+
+impl<'a> FromIterator<&'a GameY> for ArchiveStats {
+    fn from_iter<I: IntoIterator<Item = &'a GameY>>(games: I) -> Self {
+        let mut stats = ArchiveStats::new();
+        for game in games {
+            stats.add(game);
+        }
+        stats
+    }
+}
+
+impl Display for ArchiveStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} games, {} unfinished",
+            self.games(),
+            self.unfinished()
+        )?;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<8} {:>6} {:>8} {:>7} {:>8} {:>9}",
+            "Board", "Games", "1st wins", "Swaps", "Length", "Branching"
+        )?;
+        for board in &self.boards {
+            writeln!(
+                f,
+                "{:<8} {:>6} {:>7.1}% {:>6.1}% {:>8.1} {:>9.1}",
+                board.name(),
+                board.games,
+                100.0 * board.first_player_win_rate(),
+                100.0 * board.swap_rate(),
+                board.average_length(),
+                board.branching_factor()
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "{:>6} {:>6}  Distribution", "Moves", "Games")?;
+        let most = self.lengths.values().copied().max().unwrap_or(1);
+        for (moves, &games) in &self.lengths {
+            let bar = (games as usize * 40).div_ceil(most as usize);
+            writeln!(f, "{:>6} {:>6}  {}", moves, games, "#".repeat(bar))?;
+        }
+
+        writeln!(f)?;
+        writeln!(
+            f,
+            "{:<8} {:>6} {:>10} {:>6} {:>7}",
+            "Board", "Cell", "Coords", "Games", "Share"
+        )?;
+        for board in &self.boards {
+            // Boards no game can be played on have no openings to show
+            let openings = board.top_openings().unwrap_or_default();
+            for (cell, coords, games) in openings.into_iter().take(TOP_OPENINGS) {
+                writeln!(
+                    f,
+                    "{:<8} {:>6} {:>10} {:>6} {:>6.1}%",
+                    board.name(),
+                    cell,
+                    coords.to_string(),
+                    games,
+                    100.0 * ratio(u64::from(games), u64::from(board.games))
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+fn ratio(part: u64, whole: u64) -> f64 {
+    match whole {
+        0 => 0.0,
+        whole => part as f64 / whole as f64,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::PlayerId;
+
+    fn finished(size: u32, moves: &[Coordinates]) -> GameY {
+        let mut game = GameY::new(size);
+        for &coords in moves {
+            let player = game.next_player().unwrap();
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_board_statistics() {
+        let mut swapped = GameY::new(2);
+        swapped
+            .add_move(Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(1, 0, 0),
+            })
+            .unwrap();
+        swapped
+            .add_move(Movement::Action {
+                player: PlayerId::new(1),
+                action: GameAction::Swap,
+            })
+            .unwrap();
+        let games = [
+            finished(1, &[Coordinates::new(0, 0, 0)]),
+            finished(2, &[Coordinates::new(1, 0, 0)]),
+            swapped,
+        ];
+        let stats: ArchiveStats = games.iter().collect();
+        assert_eq!(stats.games(), 3);
+        assert_eq!(stats.unfinished(), 2);
+        assert_eq!(stats.boards.len(), 2);
+
+        let tiny = &stats.boards[0];
+        assert_eq!((tiny.size, tiny.finished), (1, 1));
+        assert_eq!(tiny.first_player_win_rate(), 1.0);
+        assert_eq!(tiny.branching_factor(), 1.0);
+
+        let small = &stats.boards[1];
+        assert_eq!(small.games, 2);
+        assert_eq!(small.swap_rate(), 0.5);
+        assert_eq!(small.average_length(), 1.5);
+        assert_eq!(small.branching_factor(), 3.0);
+        assert_eq!(small.openings.values().sum::<u32>(), 2);
+        assert_eq!(stats.lengths, BTreeMap::from([(1, 2), (2, 1)]));
+    }
+
+    #[test]
+    fn test_tables_and_csv() {
+        let games = [
+            finished(3, &[Coordinates::new(2, 0, 0)]),
+            finished(3, &[Coordinates::new(2, 0, 0), Coordinates::new(0, 2, 0)]),
+        ];
+        let stats: ArchiveStats = games.iter().collect();
+        let table = stats.to_string();
+        assert!(table.starts_with("2 games, 2 unfinished"));
+        assert!(table.contains("y 3"));
+        assert_eq!(stats.lengths_csv(), "moves,games\n1,1\n2,1\n");
+        assert_eq!(
+            stats.openings_csv().unwrap().lines().nth(1),
+            Some("y,3,0,2,0,0,2")
+        );
+        assert_eq!(stats.boards_csv().lines().count(), 2);
+        assert!(ArchiveStats::new().to_string().starts_with("0 games"));
+    }
+
+    #[test]
+    fn test_oversized_boards_have_no_openings() {
+        let mut stats: ArchiveStats = [finished(3, &[Coordinates::new(2, 0, 0)])].iter().collect();
+        stats.boards[0].size = gamey_core::MAX_BOARD_SIZE + 1;
+        assert!(matches!(
+            stats.boards[0].top_openings(),
+            Err(GameYError::InvalidBoardSize { .. })
+        ));
+        assert!(stats.openings_csv().is_err());
+        assert!(stats.to_string().contains("Share"));
+    }
+}
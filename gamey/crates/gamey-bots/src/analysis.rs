@@ -0,0 +1,289 @@
+//! How good every first move is.
+//!
+//! [`OpeningAnalysis::run`] estimates the win rate of each move available
+//! to the player to move by letting a bot play the game out against itself
+//! from the position after the move, many times. Moves that lead to
+//! symmetric positions (see [`GameY::canonical_hash`]) are equally good, so
+//! only one of each is played out and the result is shared: on an empty Y
+//! board that divides the work by up to six. The analysis prints as a
+//! ranked table, and [`OpeningAnalysis::heatmap`] lays the win rates out on
+//! the board, which helps to tune the swap rule and opening books.
+//!
+//! Every class of moves is played out from its own random stream (see
+//! [`gamey_core::reseed`]), so under a [`gamey_core::DeterministicConfig`]
+//! the analysis is the same whatever the number of threads.
+
+use crate::YBot;
+use gamey_core::{
+    BoardTopology, Coordinates, GameStatus, GameY, Movement, PlayerId, reseed, worker_threads,
+};
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The estimated value of one first move and of the moves symmetric to it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FirstMove {
+    /// The move played out.
+    pub coords: Coordinates,
+    /// Every move leading to a position symmetric to that of `coords`,
+    /// `coords` included.
+    pub symmetric: Vec<Coordinates>,
+    /// The games played out after the move.
+    pub playouts: u32,
+    /// The games won by the player who made the move.
+    pub wins: u32,
+}
+
+impl FirstMove {
+    /// Returns the fraction of the playouts won by the player who made the
+    /// move.
+    pub fn win_rate(&self) -> f64 {
+        match self.playouts {
+            0 => 0.0,
+            playouts => f64::from(self.wins) / f64::from(playouts),
+        }
+    }
+}
+
+/// The estimated win rates of the moves available in a position.
+#[derive(Debug, Clone)]
+pub struct OpeningAnalysis {
+    /// The position analyzed.
+    pub game: GameY,
+    /// The player whose moves were analyzed.
+    pub player: PlayerId,
+    /// The bot that played the games out.
+    pub bot: String,
+    /// One entry per class of symmetric moves, the best first.
+    pub moves: Vec<FirstMove>,
+}
+
+impl OpeningAnalysis {
+    /// Estimates the win rate of every move of the player to move in
+    /// `game`, playing `playouts` games out with `bot` after each class of
+    /// symmetric moves. The classes are played out in parallel.
+    ///
+    /// Returns `None` if the game is over.
+    pub fn run(game: &GameY, bot: &dyn YBot, playouts: u32) -> Option<Self> {
+        let player = game.next_player()?;
+        let mut classes: Vec<Vec<Coordinates>> = Vec::new();
+        let mut class_of: HashMap<u64, usize> = HashMap::new();
+        for index in game.available_cells_in_order() {
+            let coords = game.cell_coords(index);
+            let mut after = game.clone();
+            if after
+                .add_move(Movement::Placement { player, coords })
+                .is_err()
+            {
+                continue;
+            }
+            let hash = after.canonical_hash();
+            match class_of.get(&hash) {
+                Some(&class) => classes[class].push(coords),
+                None => {
+                    class_of.insert(hash, classes.len());
+                    classes.push(vec![coords]);
+                }
+            }
+        }
+
+        let results = Mutex::new(Vec::new());
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..worker_threads().min(classes.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let class = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(symmetric) = classes.get(class) else {
+                            break;
+                        };
+                        let coords = symmetric[0];
+                        reseed(class as u64);
+                        let wins = (0..playouts)
+                            .filter(|_| play_out(game, coords, bot) == Some(player))
+                            .count() as u32;
+                        let first_move = FirstMove {
+                            coords,
+                            symmetric: symmetric.clone(),
+                            playouts,
+                            wins,
+                        };
+                        results
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(first_move);
+                    }
+                });
+            }
+        });
+        let mut moves = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        moves.sort_by(|a, b| {
+            b.win_rate()
+                .total_cmp(&a.win_rate())
+                .then_with(|| game.cell_index(a.coords).cmp(&game.cell_index(b.coords)))
+        });
+        Some(OpeningAnalysis {
+            game: game.clone(),
+            player,
+            bot: bot.name().to_string(),
+            moves,
+        })
+    }
+
+    /// Returns the estimated win rate of playing `coords`, if it was
+    /// analyzed.
+    pub fn win_rate(&self, coords: Coordinates) -> Option<f64> {
+        self.moves
+            .iter()
+            .find(|first_move| first_move.symmetric.contains(&coords))
+            .map(FirstMove::win_rate)
+    }
+
+    /// Draws the board with the estimated win rate of every move, in
+    /// percent, on its cell; cells that cannot be played show `.`.
+    pub fn heatmap(&self) -> String {
+        let size = self.game.board_size();
+        let topology = self.game.topology();
+        let mut heatmap = String::new();
+        for (row, cells) in topology.rows(size).into_iter().enumerate() {
+            let level = match topology {
+                BoardTopology::Y => size as usize - 1 - row,
+                BoardTopology::Hex => row,
+            };
+            let mut line = " ".repeat(level * 2);
+            for coords in cells {
+                match self.win_rate(coords) {
+                    Some(rate) => {
+                        let _ = write!(line, "{:>3} ", (100.0 * rate).round());
+                    }
+                    None => line.push_str("  . "),
+                }
+            }
+            heatmap.push_str(line.trim_end());
+            heatmap.push('\n');
+        }
+        heatmap
+    }
+}
+
+impl Display for OpeningAnalysis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let playouts = self
+            .moves
+            .first()
+            .map_or(0, |first_move| first_move.playouts);
+        writeln!(
+            f,
+            "Moves of player {} played out {} times each by {}",
+            self.player, playouts, self.bot
+        )?;
+        writeln!(
+            f,
+            "{:>4} {:>6} {:>10} {:>6}  Symmetric",
+            "Rank", "Cell", "Coords", "Win %"
+        )?;
+        for (rank, first_move) in self.moves.iter().enumerate() {
+            let symmetric: Vec<String> = first_move.symmetric[1..]
+                .iter()
+                .map(|coords| self.game.cell_index(*coords).to_string())
+                .collect();
+            writeln!(
+                f,
+                "{:>4} {:>6} {:>10} {:>6.1}  {}",
+                rank + 1,
+                self.game.cell_index(first_move.coords),
+                first_move.coords.to_string(),
+                100.0 * first_move.win_rate(),
+                symmetric.join(" ")
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Plays `coords` and then lets `bot` play both sides until the game ends,
+/// returning the winner; `None` if the bot gave up.
+pub(crate) fn play_out(game: &GameY, coords: Coordinates, bot: &dyn YBot) -> Option<PlayerId> {
+    let mut game = game.clone();
+    let player = game.next_player()?;
+    game.add_move(Movement::Placement { player, coords }).ok()?;
+    loop {
+        let player = match game.status() {
+            GameStatus::Ongoing { next_player } => *next_player,
+            GameStatus::Finished { winner } => return Some(*winner),
+        };
+        let coords = bot.choose_move(&game)?;
+        game.add_move(Movement::Placement { player, coords }).ok()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RandomBot;
+
+    #[test]
+    fn test_symmetric_moves_are_played_out_once() {
+        let game = GameY::new(4);
+        let analysis = OpeningAnalysis::run(&game, &RandomBot, 20).unwrap();
+        // The 10 cells of a size 4 Y board fall into a corner, an edge and a
+        // center class
+        assert_eq!(analysis.moves.len(), 3);
+        let mut sizes: Vec<usize> = analysis.moves.iter().map(|m| m.symmetric.len()).collect();
+        sizes.sort();
+        assert_eq!(sizes, vec![1, 3, 6]);
+        assert!(analysis.moves.iter().all(|m| m.playouts == 20));
+        assert!(
+            analysis
+                .moves
+                .windows(2)
+                .all(|pair| pair[0].win_rate() >= pair[1].win_rate())
+        );
+        let corner = Coordinates::new(3, 0, 0);
+        assert!(analysis.win_rate(corner).is_some());
+    }
+
+    #[test]
+    fn test_table_and_heatmap() {
+        let analysis = OpeningAnalysis::run(&GameY::new(3), &RandomBot, 5).unwrap();
+        let table = analysis.to_string();
+        assert!(table.starts_with("Moves of player 0 played out 5 times each by random_bot"));
+        assert_eq!(table.lines().count(), 2 + analysis.moves.len());
+        let heatmap = analysis.heatmap();
+        let rows: Vec<&str> = heatmap.lines().collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(rows[2].split_whitespace().count(), 3);
+        assert!(rows[0].starts_with("    "));
+    }
+
+    #[test]
+    fn test_seeded_analysis_does_not_depend_on_threads() {
+        let wins = |threads| {
+            let _config = gamey_core::DeterministicConfig::new(5)
+                .with_threads(threads)
+                .install();
+            let analysis = OpeningAnalysis::run(&GameY::new(5), &RandomBot, 30).unwrap();
+            let mut wins: Vec<(u32, u32)> = analysis
+                .moves
+                .iter()
+                .map(|m| (analysis.game.cell_index(m.coords), m.wins))
+                .collect();
+            wins.sort();
+            wins
+        };
+        assert_eq!(wins(1), wins(3));
+    }
+
+    #[test]
+    fn test_finished_game_has_no_analysis() {
+        let mut game = GameY::new(1);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 0, 0),
+        })
+        .unwrap();
+        assert!(OpeningAnalysis::run(&game, &RandomBot, 5).is_none());
+    }
+}
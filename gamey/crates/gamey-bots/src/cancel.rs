@@ -0,0 +1,103 @@
+//! Cooperative cancellation of bot computations.
+//!
+//! Bots run synchronously, so a search that is no longer wanted (because
+//! the client went away or the request timed out) has to notice it on its
+//! own: searches check a [`CancellationToken`] between iterations and stop
+//! early. The API follows the token of `tokio_util`, which the bots do not
+//! depend on.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag shared between the code running a search and the code that may
+/// want to stop it.
+///
+/// Clones share the same flag, so a token can be handed to a search while
+/// its owner keeps a clone to cancel it.
+///
+/// # Example
+///
+/// ```
+/// use gamey_bots::{CancellationToken, MctsBot, YBot};
+/// use gamey_core::GameY;
+///
+/// let cancel = CancellationToken::new();
+/// cancel.cancel();
+/// let bot = MctsBot::new(1_000_000);
+/// assert!(bot.choose_move_with_progress(&GameY::new(5), &mut |_| {}, &cancel).is_none());
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+impl CancellationToken {
+    /// Creates a token that is not cancelled.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the token and every clone of it.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns true once the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Returns a guard that cancels the token when dropped, e.g. when the
+    /// future of a request handler is dropped because the client went away.
+    pub fn drop_guard(self) -> DropGuard {
+        DropGuard { token: Some(self) }
+    }
+}
+
+/// Cancels its token when dropped, unless disarmed first; see
+/// [`CancellationToken::drop_guard`].
+#[derive(Debug)]
+pub struct DropGuard {
+    token: Option<CancellationToken>,
+}
+
+impl DropGuard {
+    /// Returns the token without cancelling it.
+    pub fn disarm(mut self) -> CancellationToken {
+        self.token.take().expect("the token is only taken once")
+    }
+}
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        if let Some(token) = &self.token {
+            token.cancel();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clones_share_the_flag() {
+        let token = CancellationToken::new();
+        let clone = token.clone();
+        assert!(!clone.is_cancelled());
+        token.cancel();
+        assert!(clone.is_cancelled());
+    }
+
+    #[test]
+    fn test_drop_guard_cancels_unless_disarmed() {
+        let token = CancellationToken::new();
+        drop(token.clone().drop_guard());
+        assert!(token.is_cancelled());
+
+        let token = CancellationToken::new();
+        let disarmed = token.clone().drop_guard().disarm();
+        assert!(!token.is_cancelled());
+        assert!(!disarmed.is_cancelled());
+    }
+}
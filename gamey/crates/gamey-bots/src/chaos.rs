@@ -0,0 +1,246 @@
+//! Simulated latency and faults for resilience testing.
+//!
+//! A [`Chaos`] describes how badly a bot (or a remote bot server) should
+//! behave: how long every answer is delayed and how often it fails at once
+//! or hangs before failing. [`ChaosBot`] wraps any bot with it, so the code
+//! driving the bot can be tested against slow and unreliable opponents
+//! without a real network.
+
+use crate::{CancellationToken, MoveStats, SearchProgress, SearchResult, YBot};
+use gamey_core::{Coordinates, GameY, rng};
+use rand::Rng;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// How a call disturbed by [`Chaos`] goes wrong.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fault {
+    /// The call fails at once.
+    Failure,
+    /// The call hangs for [`Chaos::hang`] and then fails.
+    Timeout,
+}
+
+/// Artificial latency and faults injected into calls to a bot.
+///
+/// Written on the command line as comma-separated `key=value` pairs, e.g.
+/// `latency=200,fail=0.1,timeout=0.05,hang=2000`:
+/// - `latency`: milliseconds added to every call,
+/// - `fail`: fraction of the calls that fail at once,
+/// - `timeout`: fraction of the calls that hang and then fail,
+/// - `hang`: milliseconds a timed out call hangs, 5000 by default.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chaos {
+    /// Delay added to every call.
+    pub latency: Duration,
+    /// Fraction of the calls that fail at once, between 0 and 1.
+    pub failure_rate: f64,
+    /// Fraction of the calls that hang and then fail, between 0 and 1.
+    pub timeout_rate: f64,
+    /// How long a timed out call hangs before failing.
+    pub hang: Duration,
+}
+
+impl Default for Chaos {
+    /// No latency and no faults.
+    fn default() -> Self {
+        Chaos {
+            latency: Duration::ZERO,
+            failure_rate: 0.0,
+            timeout_rate: 0.0,
+            hang: Duration::from_secs(5),
+        }
+    }
+}
+
+impl Chaos {
+    /// Draws the fault of one call, if any.
+    ///
+    /// Latency is not included: callers wait [`Chaos::latency`] themselves,
+    /// with a blocking or an async sleep.
+    pub fn draw(&self) -> Option<Fault> {
+        let roll: f64 = rng().random();
+        if roll < self.failure_rate {
+            Some(Fault::Failure)
+        } else if roll < self.failure_rate + self.timeout_rate {
+            Some(Fault::Timeout)
+        } else {
+            None
+        }
+    }
+
+    /// Waits the latency of one call, and the hang if it times out.
+    ///
+    /// Returns the fault drawn, so the caller fails the call if there is one.
+    pub fn disturb(&self) -> Option<Fault> {
+        std::thread::sleep(self.latency);
+        let fault = self.draw();
+        if fault == Some(Fault::Timeout) {
+            std::thread::sleep(self.hang);
+        }
+        fault
+    }
+}
+
+impl FromStr for Chaos {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chaos = Chaos::default();
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value in chaos, found '{}'", pair))?;
+            let millis = || {
+                value
+                    .parse::<u64>()
+                    .map(Duration::from_millis)
+                    .map_err(|_| format!("Invalid milliseconds for {}: '{}'", key, value))
+            };
+            let rate = || match value.parse::<f64>() {
+                Ok(rate) if (0.0..=1.0).contains(&rate) => Ok(rate),
+                _ => Err(format!("Invalid rate for {}: '{}'", key, value)),
+            };
+            match key {
+                "latency" => chaos.latency = millis()?,
+                "fail" => chaos.failure_rate = rate()?,
+                "timeout" => chaos.timeout_rate = rate()?,
+                "hang" => chaos.hang = millis()?,
+                _ => {
+                    return Err(format!(
+                        "Unknown chaos setting '{}', expected latency, fail, timeout or hang",
+                        key
+                    ));
+                }
+            }
+        }
+        if chaos.failure_rate + chaos.timeout_rate > 1.0 {
+            return Err("The fail and timeout rates add up to more than 1".to_string());
+        }
+        Ok(chaos)
+    }
+}
+
+impl Display for Chaos {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "latency={},fail={},timeout={},hang={}",
+            self.latency.as_millis(),
+            self.failure_rate,
+            self.timeout_rate,
+            self.hang.as_millis()
+        )
+    }
+}
+
+/// A bot whose calls are delayed and fail as described by a [`Chaos`].
+///
+/// Failed calls return `None`, as a bot without moves would; callers tell
+/// them apart because the game still has empty cells. The wrapped bot's
+/// sessions are not used, so it does not ponder.
+pub struct ChaosBot {
+    bot: Arc<dyn YBot>,
+    chaos: Chaos,
+}
+
+impl ChaosBot {
+    /// Wraps `bot`, disturbing its calls with `chaos`.
+    pub fn new(bot: Arc<dyn YBot>, chaos: Chaos) -> Self {
+        ChaosBot { bot, chaos }
+    }
+
+    /// Disturbs one call, returning `None` if it fails.
+    fn survive(&self) -> Option<()> {
+        match self.chaos.disturb() {
+            Some(_) => None,
+            None => Some(()),
+        }
+    }
+}
+
+impl YBot for ChaosBot {
+    fn name(&self) -> &str {
+        self.bot.name()
+    }
+
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        self.survive()?;
+        self.bot.choose_move(board)
+    }
+
+    fn search(&self, board: &GameY) -> Option<SearchResult> {
+        self.survive()?;
+        self.bot.search(board)
+    }
+
+    fn choose_move_with_stats(&self, board: &GameY) -> Option<(Coordinates, MoveStats)> {
+        self.survive()?;
+        self.bot.choose_move_with_stats(board)
+    }
+
+    fn choose_move_with_progress(
+        &self,
+        board: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        self.survive()?;
+        self.bot.choose_move_with_progress(board, progress, cancel)
+    }
+
+    fn explain_move(&self, board: &GameY, coords: Coordinates) -> Option<String> {
+        self.bot.explain_move(board, coords)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RandomBot;
+
+    #[test]
+    fn test_parse_chaos() {
+        let chaos: Chaos = "latency=200, fail=0.1,timeout=0.05".parse().unwrap();
+        assert_eq!(chaos.latency, Duration::from_millis(200));
+        assert_eq!(chaos.failure_rate, 0.1);
+        assert_eq!(chaos.timeout_rate, 0.05);
+        assert_eq!(chaos.hang, Duration::from_secs(5));
+        assert_eq!(chaos.to_string().parse::<Chaos>().unwrap(), chaos);
+        assert_eq!("".parse::<Chaos>().unwrap(), Chaos::default());
+    }
+
+    #[test]
+    fn test_parse_invalid_chaos() {
+        assert!("latency".parse::<Chaos>().is_err());
+        assert!("latency=fast".parse::<Chaos>().is_err());
+        assert!("fail=1.5".parse::<Chaos>().is_err());
+        assert!("fail=0.6,timeout=0.6".parse::<Chaos>().is_err());
+        assert!("jitter=10".parse::<Chaos>().is_err());
+    }
+
+    #[test]
+    fn test_chaos_bot_faults() {
+        let game = GameY::new(3);
+        let always_fails = Chaos {
+            failure_rate: 1.0,
+            ..Chaos::default()
+        };
+        let bot = ChaosBot::new(Arc::new(RandomBot), always_fails);
+        assert_eq!(bot.name(), "random_bot");
+        assert_eq!(bot.choose_move(&game), None);
+
+        let times_out = Chaos {
+            timeout_rate: 1.0,
+            hang: Duration::from_millis(1),
+            ..Chaos::default()
+        };
+        let bot = ChaosBot::new(Arc::new(RandomBot), times_out);
+        assert_eq!(bot.choose_move_with_stats(&game), None);
+
+        let bot = ChaosBot::new(Arc::new(RandomBot), Chaos::default());
+        assert!(bot.choose_move(&game).is_some());
+    }
+}
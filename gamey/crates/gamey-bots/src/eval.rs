@@ -0,0 +1,154 @@
+//! A quick estimate of who is ahead, for an evaluation bar.
+//!
+//! [`Evaluation::run`] fills the empty cells of a position at random many
+//! times and counts who wins each filled board. Y and Hex boards cannot end
+//! in a draw, so every fill has a winner, and a fill only costs one move per
+//! empty cell: a few hundred of them take milliseconds on the usual board
+//! sizes, cheap enough to run after every move of an interactive game.
+//!
+//! Random fills say more about the shape of the position than about the
+//! best play, so the estimate is a hint, not a verdict; see
+//! `Adjudication` in `gamey-tournament` for one played out by a bot.
+
+use gamey_core::{GameStatus, GameY, Movement, PlayerId};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use std::fmt::Display;
+
+/// The fraction of random fills of a position won by each player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Evaluation {
+    /// The fills played.
+    pub playouts: u32,
+    /// The fills won by the first and by the second player.
+    pub wins: [u32; 2],
+}
+
+impl Evaluation {
+    /// Fills the empty cells of `game` at random `playouts` times, the
+    /// players taking turns, and counts the wins of each player.
+    ///
+    /// The fills are drawn from a generator seeded by the position, so the
+    /// same position always gets the same estimate and the random choices
+    /// of the bots are left alone.
+    ///
+    /// Returns `None` if the game is over.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_bots::Evaluation;
+    /// use gamey_core::{GameY, PlayerId};
+    ///
+    /// let mut game = GameY::new(5);
+    /// game.play_move_list("4").unwrap();
+    /// let evaluation = Evaluation::run(&game, 200).unwrap();
+    /// assert_eq!(evaluation.wins[0] + evaluation.wins[1], 200);
+    /// // The center of the board is worth more than a random cell
+    /// assert!(evaluation.win_rate(PlayerId::new(0)) > 0.5);
+    /// ```
+    pub fn run(game: &GameY, playouts: u32) -> Option<Self> {
+        game.next_player()?;
+        let mut rng = StdRng::seed_from_u64(game.zobrist_hash());
+        let mut cells = game.available_cells();
+        let mut wins = [0u32; 2];
+        for _ in 0..playouts {
+            cells.shuffle(&mut rng);
+            let mut fill = game.clone();
+            for &index in &cells {
+                let Some(player) = fill.next_player() else {
+                    break;
+                };
+                let coords = fill.cell_coords(index);
+                if fill
+                    .add_move(Movement::Placement { player, coords })
+                    .is_err()
+                {
+                    break;
+                }
+            }
+            if let GameStatus::Finished { winner } = fill.status()
+                && let Some(count) = wins.get_mut(winner.id() as usize)
+            {
+                *count += 1;
+            }
+        }
+        Some(Evaluation { playouts, wins })
+    }
+
+    /// Returns the fraction of the fills won by `player`, or one half if
+    /// none had a winner.
+    pub fn win_rate(&self, player: PlayerId) -> f64 {
+        let decided = self.wins[0] + self.wins[1];
+        match (decided, self.wins.get(player.id() as usize)) {
+            (0, _) | (_, None) => 0.5,
+            (decided, Some(&wins)) => f64::from(wins) / f64::from(decided),
+        }
+    }
+
+    /// Returns a bar of `width` characters whose left part, the share of
+    /// the first player, is drawn with `#` and the rest with `-`.
+    pub fn bar(&self, width: usize) -> String {
+        let first = (self.win_rate(PlayerId::new(0)) * width as f64).round() as usize;
+        format!(
+            "{}{}",
+            "#".repeat(first),
+            "-".repeat(width - first.min(width))
+        )
+    }
+}
+
+impl Display for Evaluation {
+    /// Writes the bar and the win rates, e.g. `[#######---] 0: 70% 1: 30%`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let first = self.win_rate(PlayerId::new(0));
+        write!(
+            f,
+            "[{}] 0: {:.0}% 1: {:.0}%",
+            self.bar(20),
+            first * 100.0,
+            (1.0 - first) * 100.0
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::BoardTopology;
+
+    #[test]
+    fn test_won_positions_are_clear() {
+        // Player 0 holds the top and needs one of the two cells of the
+        // bottom row, which player 1 cannot both take
+        let mut game = GameY::new(2);
+        game.play_move_list("0").unwrap();
+        let evaluation = Evaluation::run(&game, 50).unwrap();
+        assert_eq!(evaluation.wins, [50, 0]);
+        assert_eq!(evaluation.bar(10), "##########");
+        assert_eq!(
+            evaluation.to_string(),
+            "[####################] 0: 100% 1: 0%"
+        );
+
+        game.play_move_list("1 2").unwrap();
+        assert!(Evaluation::run(&game, 50).is_none());
+    }
+
+    #[test]
+    fn test_same_position_same_estimate() {
+        let mut game = GameY::with_topology(5, BoardTopology::Hex);
+        game.play_move_list("12 3").unwrap();
+        let evaluation = Evaluation::run(&game, 100).unwrap();
+        assert_eq!(Evaluation::run(&game, 100), Some(evaluation));
+        assert_eq!(evaluation.wins[0] + evaluation.wins[1], 100);
+        assert_eq!(evaluation.bar(8).len(), 8);
+
+        let empty = Evaluation {
+            playouts: 0,
+            wins: [0, 0],
+        };
+        assert_eq!(empty.win_rate(PlayerId::new(1)), 0.5);
+        assert_eq!(empty.bar(4), "##--");
+    }
+}
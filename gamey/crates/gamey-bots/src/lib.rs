@@ -0,0 +1,47 @@
+//! Bots for the Game of Y.
+//!
+//! This module provides the infrastructure for creating and managing AI bots
+//! that can play the Game of Y. It includes:
+//!
+//! - [`YBot`] - A trait that defines the interface for all bots
+//! - [`YBotRegistry`] - A registry for managing multiple bot implementations
+//! - [`RandomBot`] - A simple bot that makes random valid moves
+//! - [`MctsBot`] - A Monte Carlo Tree Search bot
+//! - [`SolverBot`] - A perfect player for small boards, built on [`solve`]
+//! - [`SearchResult`] - The expected line of play reported by a search
+//! - [`MoveStats`] and [`SearchProgress`] - How a move was chosen, after and
+//!   during the search
+//! - [`BotSession`] - A stateful bot following a single game
+//! - [`CancellationToken`] - Stops a search that is no longer wanted
+//! - [`Tree`] - An arena-allocated game tree for search bots
+//! - [`ChaosBot`] - A bot with simulated latency and faults, see [`Chaos`]
+//! - [`OpeningAnalysis`] - The estimated win rate of every first move
+//! - [`Evaluation`] - A quick estimate of who is ahead, from random fills
+//! - [`Review`] - The mistakes of a game and the accuracy of its players
+
+pub mod analysis;
+pub mod cancel;
+pub mod chaos;
+pub mod eval;
+pub mod mcts;
+pub mod random;
+pub mod review;
+pub mod search;
+pub mod session;
+pub mod solver;
+pub mod tree;
+pub mod ybot;
+pub mod ybot_registry;
+pub use analysis::*;
+pub use cancel::*;
+pub use chaos::*;
+pub use eval::*;
+pub use mcts::*;
+pub use random::*;
+pub use review::*;
+pub use search::*;
+pub use session::*;
+pub use solver::*;
+pub use tree::*;
+pub use ybot::*;
+pub use ybot_registry::*;
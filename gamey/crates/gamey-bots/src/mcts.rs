@@ -0,0 +1,714 @@
+//! A Monte Carlo Tree Search bot.
+//!
+//! This module provides [`MctsBot`], a bot that builds a search tree with the
+//! UCT selection rule and evaluates leaves with random playouts. Since a full
+//! Y board always has exactly one winner, random playouts are a meaningful
+//! (if noisy) estimate of who is ahead. Playouts are scored with the winner
+//! reported by the game, so reversed rulesets such as misère flip the
+//! evaluation without any special handling here.
+//!
+//! The tree is unbounded by default. With a node limit (see
+//! [`MctsBot::with_max_nodes`]) a full tree recycles the nodes of its least
+//! recently visited leaves: their statistics stay in their parents and their
+//! moves can be expanded again later.
+
+use crate::{
+    BotSession, CancellationToken, MoveStats, NodeId, SearchProgress, SearchResult, Tree, YBot,
+};
+use gamey_core::{Coordinates, GameY, Movement, PlayerId, rng};
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+/// Exploration constant of the UCT formula.
+const EXPLORATION: f64 = std::f64::consts::SQRT_2;
+
+/// Pondering stops after this many playouts per move budget, so that a long
+/// think by the opponent cannot exhaust memory.
+const PONDER_PLAYOUTS_PER_ITERATION: u32 = 50;
+
+/// Progress is reported every this many playouts.
+const PROGRESS_INTERVAL: u32 = 100;
+
+/// A bot that chooses moves with Monte Carlo Tree Search.
+///
+/// # Example
+///
+/// ```
+/// use gamey_bots::{MctsBot, YBot};
+/// use gamey_core::GameY;
+///
+/// let bot = MctsBot::new(200);
+/// let game = GameY::new(4);
+///
+/// let result = bot.search(&game).unwrap();
+/// assert_eq!(result.pv[0], result.best_move);
+/// ```
+pub struct MctsBot {
+    iterations: u32,
+    max_nodes: usize,
+}
+
+impl MctsBot {
+    /// Creates a bot that runs the given number of playouts per move.
+    pub fn new(iterations: u32) -> Self {
+        MctsBot {
+            iterations: iterations.max(1),
+            max_nodes: usize::MAX,
+        }
+    }
+
+    /// Limits the search tree to `max_nodes` nodes (at least 2), recycling
+    /// the least recently visited leaves once it is full.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes.max(2);
+        self
+    }
+
+    /// Returns the number of playouts run per move.
+    pub fn iterations(&self) -> u32 {
+        self.iterations
+    }
+
+    /// Returns the largest number of nodes of the search tree.
+    pub fn max_nodes(&self) -> usize {
+        self.max_nodes
+    }
+}
+
+impl Default for MctsBot {
+    fn default() -> Self {
+        MctsBot::new(1000)
+    }
+}
+
+impl YBot for MctsBot {
+    fn name(&self) -> &str {
+        "mcts_bot"
+    }
+
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        self.search(board).map(|result| result.best_move)
+    }
+
+    fn search(&self, board: &GameY) -> Option<SearchResult> {
+        let mut tree = SearchTree::new(board, self.max_nodes)?;
+        let cancel = CancellationToken::new();
+        tree.run(board, self.iterations, &mut |_| {}, &cancel);
+        tree.result()
+    }
+
+    fn choose_move_with_stats(&self, board: &GameY) -> Option<(Coordinates, MoveStats)> {
+        self.choose_move_with_progress(board, &mut |_| {}, &CancellationToken::new())
+    }
+
+    fn choose_move_with_progress(
+        &self,
+        board: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        let start = Instant::now();
+        let mut tree = SearchTree::new(board, self.max_nodes)?;
+        let playouts = tree.run(board, self.iterations, progress, cancel);
+        tree.move_stats(start, playouts)
+    }
+
+    fn explain_move(&self, board: &GameY, coords: Coordinates) -> Option<String> {
+        let mut tree = SearchTree::new(board, self.max_nodes)?;
+        let cancel = CancellationToken::new();
+        tree.run(board, self.iterations, &mut |_| {}, &cancel);
+        tree.explain(coords)
+    }
+
+    fn new_session(&self) -> Option<Box<dyn BotSession>> {
+        let session = MctsSession::new(self.iterations).with_max_nodes(self.max_nodes);
+        Some(Box::new(session))
+    }
+}
+
+/// A session of [`MctsBot`] that keeps its search tree between moves.
+///
+/// When the moves played since the last call are already in the tree, the
+/// matching subtree becomes the new root, so the playouts spent on it (for
+/// example while pondering) are not lost.
+pub struct MctsSession {
+    iterations: u32,
+    max_nodes: usize,
+    game: GameY,
+    tree: Option<SearchTree>,
+}
+
+impl MctsSession {
+    /// Creates a session running `iterations` new playouts per move.
+    pub fn new(iterations: u32) -> Self {
+        MctsSession {
+            iterations: iterations.max(1),
+            max_nodes: usize::MAX,
+            game: GameY::new(0),
+            tree: None,
+        }
+    }
+
+    /// Limits the kept tree to `max_nodes` nodes, see
+    /// [`MctsBot::with_max_nodes`].
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.max_nodes = max_nodes.max(2);
+        self
+    }
+
+    /// Returns the number of playouts stored in the current tree.
+    pub fn reused_playouts(&self) -> u32 {
+        self.tree.as_ref().map_or(0, |tree| tree.root().visits)
+    }
+
+    /// Brings the tree up to date with `game`, reusing subtrees for the
+    /// moves that were explored.
+    fn sync(&mut self, game: &GameY) {
+        let known = self.game.history();
+        let extends = game.board_size() == self.game.board_size()
+            && game.history().len() >= known.len()
+            && game.history()[..known.len()] == *known;
+        if extends {
+            for movement in &game.history()[known.len()..] {
+                self.tree = match (self.tree.take(), movement) {
+                    (Some(tree), Movement::Placement { coords, .. }) => {
+                        let child = tree.child_for(tree.nodes.root(), *coords);
+                        child.map(|child| tree.reroot(child))
+                    }
+                    _ => None,
+                };
+            }
+        } else {
+            self.tree = None;
+        }
+        self.game = game.clone();
+        if self.tree.is_none() {
+            self.tree = SearchTree::new(game, self.max_nodes);
+        }
+    }
+}
+
+impl BotSession for MctsSession {
+    fn choose_move(&mut self, game: &GameY) -> Option<Coordinates> {
+        self.choose_move_with_stats(game).map(|(coords, _)| coords)
+    }
+
+    fn choose_move_with_stats(&mut self, game: &GameY) -> Option<(Coordinates, MoveStats)> {
+        self.choose_move_with_progress(game, &mut |_| {}, &CancellationToken::new())
+    }
+
+    fn choose_move_with_progress(
+        &mut self,
+        game: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        let start = Instant::now();
+        self.sync(game);
+        let tree = self.tree.as_mut()?;
+        let playouts = tree.run(game, self.iterations, progress, cancel);
+        tree.move_stats(start, playouts)
+    }
+
+    fn ponder(&mut self, game: &GameY, stop: &AtomicBool) {
+        self.sync(game);
+        let Some(tree) = self.tree.as_mut() else {
+            return;
+        };
+        let max_playouts = PONDER_PLAYOUTS_PER_ITERATION.saturating_mul(self.iterations);
+        let mut rng = rng();
+        while !stop.load(Ordering::Relaxed) && tree.root().visits < max_playouts {
+            tree.iterate(game, &mut rng);
+        }
+    }
+}
+
+/// A node of the search tree.
+struct Node {
+    /// The move that led to this node (`None` for the root).
+    mv: Option<Coordinates>,
+    /// The player who played `mv`.
+    player: Option<PlayerId>,
+    untried: Vec<Coordinates>,
+    visits: u32,
+    wins: f64,
+    /// The iteration that last went through this node.
+    last_visit: u64,
+}
+
+impl Node {
+    fn new(game: &GameY, mv: Option<Coordinates>, player: Option<PlayerId>) -> Self {
+        let untried = if game.check_game_over() {
+            Vec::new()
+        } else {
+            game.available_cells()
+                .iter()
+                .map(|&idx| game.cell_coords(idx))
+                .collect()
+        };
+        Node {
+            mv,
+            player,
+            untried,
+            visits: 0,
+            wins: 0.0,
+            last_visit: 0,
+        }
+    }
+
+    fn win_rate(&self) -> f64 {
+        if self.visits == 0 {
+            0.0
+        } else {
+            self.wins / self.visits as f64
+        }
+    }
+}
+
+/// A UCT search tree rooted at a position.
+struct SearchTree {
+    nodes: Tree<Node>,
+    max_depth: u32,
+    /// The largest number of nodes.
+    max_nodes: usize,
+    /// The number of iterations run, used to find stale leaves.
+    clock: u64,
+}
+
+impl SearchTree {
+    /// Creates a tree for the given position holding at most `max_nodes`
+    /// nodes, or `None` if the game is over or there are no moves left.
+    fn new(root: &GameY, max_nodes: usize) -> Option<Self> {
+        if root.check_game_over() || root.available_cells().is_empty() {
+            return None;
+        }
+        Some(SearchTree {
+            nodes: Tree::new(Node::new(root, None, None)),
+            max_depth: 0,
+            max_nodes,
+            clock: 0,
+        })
+    }
+
+    /// Returns the root node.
+    fn root(&self) -> &Node {
+        &self.nodes[self.nodes.root()]
+    }
+
+    /// Returns the number of nodes in use.
+    fn live_nodes(&self) -> usize {
+        self.nodes.node_count()
+    }
+
+    /// Makes sure a node can be added below `node`, recycling leaves if the
+    /// tree is full; returns false if no room could be made.
+    fn make_room(&mut self, node: NodeId) -> bool {
+        if self.live_nodes() < self.max_nodes {
+            return true;
+        }
+        self.recycle(node);
+        self.live_nodes() < self.max_nodes
+    }
+
+    /// Frees the least recently visited quarter of the leaves, except
+    /// `keep`. Their moves go back to the untried moves of their parents.
+    fn recycle(&mut self, keep: NodeId) {
+        let root = self.nodes.root();
+        let mut leaves: Vec<NodeId> = self
+            .nodes
+            .nodes()
+            .map(|(id, _)| id)
+            .filter(|&id| id != root && id != keep && self.nodes.is_leaf(id))
+            .collect();
+        leaves.sort_unstable_by_key(|&id| self.nodes[id].last_visit);
+        leaves.truncate(leaves.len().div_ceil(4));
+        for leaf in leaves {
+            let parent = self.nodes.parent(leaf).expect("leaves are not the root");
+            if let Some(mv) = self.nodes.remove(leaf).mv {
+                self.nodes[parent].untried.push(mv);
+            }
+        }
+    }
+
+    /// Runs one select-expand-simulate-backpropagate iteration.
+    fn iterate<R: Rng>(&mut self, root: &GameY, rng: &mut R) {
+        self.clock += 1;
+        let mut game = root.clone();
+        let mut node = self.nodes.root();
+        let mut path = vec![node];
+
+        // Selection
+        while self.nodes[node].untried.is_empty() && !self.nodes.is_leaf(node) {
+            node = self.select_child(node);
+            play(&mut game, self.nodes[node].mv.unwrap());
+            path.push(node);
+        }
+
+        // Expansion
+        if !self.nodes[node].untried.is_empty() && self.make_room(node) {
+            let i = rng.random_range(0..self.nodes[node].untried.len());
+            let mv = self.nodes[node].untried.swap_remove(i);
+            let player = game.next_player();
+            play(&mut game, mv);
+            let child = Node::new(&game, Some(mv), player);
+            path.push(self.nodes.push_child(node, child));
+        }
+        self.max_depth = self.max_depth.max(path.len() as u32 - 1);
+
+        // Simulation
+        let winner = game.random_playout(rng);
+
+        // Backpropagation
+        for &idx in &path {
+            let n = &mut self.nodes[idx];
+            n.visits += 1;
+            n.last_visit = self.clock;
+            if n.player.is_some() && n.player == winner {
+                n.wins += 1.0;
+            }
+        }
+    }
+
+    /// Runs `playouts` iterations, reporting the progress every
+    /// [`PROGRESS_INTERVAL`] playouts and when done, and returns how many
+    /// ran before `cancel` stopped the search.
+    fn run(
+        &mut self,
+        root: &GameY,
+        playouts: u32,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> u32 {
+        let mut rng = rng();
+        for done in 1..=playouts {
+            if cancel.is_cancelled() {
+                return done - 1;
+            }
+            self.iterate(root, &mut rng);
+            if done % PROGRESS_INTERVAL == 0 || done == playouts {
+                let best = self.best_child(self.nodes.root());
+                progress(&SearchProgress {
+                    iterations: done as u64,
+                    depth: self.max_depth,
+                    best_move: best.and_then(|best| self.nodes[best].mv),
+                    score: best.map(|best| self.nodes[best].win_rate()),
+                });
+            }
+        }
+        playouts
+    }
+
+    /// Picks the child with the highest UCT value.
+    fn select_child(&self, node: NodeId) -> NodeId {
+        let log_parent = (self.nodes[node].visits.max(1) as f64).ln();
+        self.nodes
+            .children(node)
+            .max_by(|&a, &b| {
+                let ua = self.uct(a, log_parent);
+                let ub = self.uct(b, log_parent);
+                ua.total_cmp(&ub)
+            })
+            .unwrap()
+    }
+
+    fn uct(&self, node: NodeId, log_parent: f64) -> f64 {
+        let n = &self.nodes[node];
+        if n.visits == 0 {
+            return f64::INFINITY;
+        }
+        n.win_rate() + EXPLORATION * (log_parent / n.visits as f64).sqrt()
+    }
+
+    /// Returns the most visited child of a node.
+    fn best_child(&self, node: NodeId) -> Option<NodeId> {
+        self.nodes
+            .children(node)
+            .max_by_key(|&child| self.nodes[child].visits)
+    }
+
+    /// Returns the child of `node` reached by playing `coords`.
+    fn child_for(&self, node: NodeId, coords: Coordinates) -> Option<NodeId> {
+        self.nodes
+            .children(node)
+            .find(|&child| self.nodes[child].mv == Some(coords))
+    }
+
+    /// Follows the most visited children starting at (and including) `node`.
+    fn principal_variation(&self, node: NodeId) -> Vec<Coordinates> {
+        let mut pv = Vec::new();
+        let mut current = Some(node);
+        while let Some(n) = current {
+            if let Some(mv) = self.nodes[n].mv {
+                pv.push(mv);
+            }
+            current = self.best_child(n);
+        }
+        pv
+    }
+
+    /// Keeps only the subtree rooted at `node`, which becomes the root.
+    fn reroot(self, node: NodeId) -> SearchTree {
+        let mut nodes = self.nodes.reroot(node);
+        let root = nodes.root();
+        nodes[root].mv = None;
+        nodes[root].player = None;
+        SearchTree {
+            nodes,
+            max_depth: self.max_depth.saturating_sub(1),
+            max_nodes: self.max_nodes,
+            clock: self.clock,
+        }
+    }
+
+    /// Returns the win rate of the player to move at the root, averaged
+    /// over the playouts of every child.
+    fn root_eval(&self) -> Option<f64> {
+        let children = || self.nodes.children(self.nodes.root());
+        let visits: u32 = children().map(|c| self.nodes[c].visits).sum();
+        let wins: f64 = children().map(|c| self.nodes[c].wins).sum();
+        (visits > 0).then(|| wins / visits as f64)
+    }
+
+    /// Returns the best move with the statistics of a search that started
+    /// at `start` and ran `playouts` playouts.
+    fn move_stats(&self, start: Instant, playouts: u32) -> Option<(Coordinates, MoveStats)> {
+        let result = self.result()?;
+        let stats = MoveStats {
+            elapsed: start.elapsed(),
+            nodes: Some(playouts as u64),
+            eval_before: self.root_eval(),
+            eval_after: Some(result.score),
+            explanation: self.explain(result.best_move),
+        };
+        Some((result.best_move, stats))
+    }
+
+    /// Describes how `coords` fared in the search, or `None` if it was
+    /// never tried.
+    fn explain(&self, coords: Coordinates) -> Option<String> {
+        let node = self.child_for(self.nodes.root(), coords)?;
+        let stats = &self.nodes[node];
+        let line = self
+            .principal_variation(node)
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        Some(format!(
+            "{} won {:.1}% of {} playouts; expected line: {}",
+            coords,
+            100.0 * stats.win_rate(),
+            stats.visits,
+            line
+        ))
+    }
+
+    fn result(&self) -> Option<SearchResult> {
+        let best = self.best_child(self.nodes.root())?;
+        Some(SearchResult {
+            best_move: self.nodes[best].mv?,
+            pv: self.principal_variation(best),
+            score: self.nodes[best].win_rate(),
+            nodes: self.live_nodes() as u64,
+            depth: self.max_depth,
+        })
+    }
+}
+
+/// Places a stone for the player to move.
+fn play(game: &mut GameY, coords: Coordinates) {
+    if let Some(player) = game.next_player() {
+        let _ = game.add_move(Movement::Placement { player, coords });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn place(game: &mut GameY, player: u32, x: u32, y: u32, z: u32) {
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(player),
+            coords: Coordinates::new(x, y, z),
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_mcts_bot_name() {
+        assert_eq!(MctsBot::default().name(), "mcts_bot");
+    }
+
+    #[test]
+    fn test_search_returns_consistent_pv() {
+        let bot = MctsBot::new(300);
+        let game = GameY::new(4);
+        let result = bot.search(&game).unwrap();
+
+        assert_eq!(result.pv.first(), Some(&result.best_move));
+        assert!(result.nodes > 1);
+        assert!(result.depth >= 1);
+        assert!((0.0..=1.0).contains(&result.score));
+        // The PV never repeats a cell
+        let mut cells: Vec<u32> = result.pv.iter().map(|c| c.to_index(4)).collect();
+        cells.sort();
+        cells.dedup();
+        assert_eq!(cells.len(), result.pv.len());
+    }
+
+    #[test]
+    fn test_search_takes_immediate_win() {
+        let mut game = GameY::new(3);
+        place(&mut game, 0, 0, 0, 2);
+        place(&mut game, 1, 1, 1, 0);
+        place(&mut game, 0, 0, 1, 1);
+        place(&mut game, 1, 2, 0, 0);
+        // Player 0 wins with (0, 2, 0), joining sides A, B and C
+        let bot = MctsBot::new(500);
+        assert_eq!(bot.choose_move(&game), Some(Coordinates::new(0, 2, 0)));
+    }
+
+    #[test]
+    fn test_node_limit_recycles_stale_leaves() {
+        let bot = MctsBot::new(2000).with_max_nodes(50);
+        let result = bot.search(&GameY::new(5)).unwrap();
+        assert!(result.nodes <= 50);
+        assert_eq!(result.pv.first(), Some(&result.best_move));
+
+        let mut game = GameY::new(3);
+        place(&mut game, 0, 0, 0, 2);
+        place(&mut game, 1, 1, 1, 0);
+        place(&mut game, 0, 0, 1, 1);
+        place(&mut game, 1, 2, 0, 0);
+        let bot = MctsBot::new(500).with_max_nodes(4);
+        assert_eq!(bot.choose_move(&game), Some(Coordinates::new(0, 2, 0)));
+
+        let mut session = MctsSession::new(500).with_max_nodes(20);
+        session.choose_move(&GameY::new(5)).unwrap();
+        assert!(session.tree.as_ref().unwrap().live_nodes() <= 20);
+    }
+
+    #[test]
+    fn test_move_stats_evaluate_both_positions() {
+        let mut game = GameY::new(3);
+        place(&mut game, 0, 0, 0, 2);
+        place(&mut game, 1, 1, 1, 0);
+        place(&mut game, 0, 0, 1, 1);
+        place(&mut game, 1, 2, 0, 0);
+        let (coords, stats) = MctsBot::new(500).choose_move_with_stats(&game).unwrap();
+        assert_eq!(coords, Coordinates::new(0, 2, 0));
+        assert_eq!(stats.nodes, Some(500));
+        assert_eq!(stats.eval_after, Some(1.0));
+        assert!(stats.eval_before.unwrap() < 1.0);
+
+        let mut session = MctsSession::new(100);
+        let (_, stats) = session.choose_move_with_stats(&game).unwrap();
+        assert_eq!(stats.nodes, Some(100));
+        assert!(stats.eval_before.is_some());
+    }
+
+    #[test]
+    fn test_progress_is_reported_while_searching() {
+        let mut reports = Vec::new();
+        let bot = MctsBot::new(250);
+        let (coords, _) = bot
+            .choose_move_with_progress(
+                &GameY::new(4),
+                &mut |p| reports.push(p.clone()),
+                &CancellationToken::new(),
+            )
+            .unwrap();
+        let iterations: Vec<u64> = reports.iter().map(|p| p.iterations).collect();
+        assert_eq!(iterations, vec![100, 200, 250]);
+        assert_eq!(reports.last().unwrap().best_move, Some(coords));
+        assert!(reports.iter().all(|p| p.depth >= 1));
+    }
+
+    #[test]
+    fn test_cancellation_stops_the_search_promptly() {
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            canceller.cancel();
+        });
+        let start = Instant::now();
+        let bot = MctsBot::new(u32::MAX);
+        let (_, stats) = bot
+            .choose_move_with_progress(&GameY::new(7), &mut |_| {}, &cancel)
+            .unwrap();
+        handle.join().unwrap();
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+        assert!(stats.nodes.unwrap() < u32::MAX as u64);
+
+        let mut session = MctsSession::new(u32::MAX);
+        let choice = session.choose_move_with_progress(&GameY::new(7), &mut |_| {}, &cancel);
+        assert!(choice.is_none());
+    }
+
+    #[test]
+    fn test_search_on_finished_game_returns_none() {
+        let mut game = GameY::new(1);
+        place(&mut game, 0, 0, 0, 0);
+        assert!(MctsBot::new(10).search(&game).is_none());
+    }
+
+    #[test]
+    fn test_session_reuses_tree_after_moves() {
+        let mut session = MctsSession::new(300);
+        let mut game = GameY::new(4);
+        let bot_move = session.choose_move(&game).unwrap();
+        play(&mut game, bot_move);
+        session.sync(&game);
+        assert!(session.reused_playouts() > 0);
+
+        // The opponent replies with the move the session expects most
+        let reply = session.tree.as_ref().unwrap().result().unwrap().best_move;
+        play(&mut game, reply);
+        session.sync(&game);
+        assert!(session.reused_playouts() > 0);
+    }
+
+    #[test]
+    fn test_ponder_grows_tree_up_to_limit() {
+        let mut session = MctsSession::new(10);
+        let stop = AtomicBool::new(false);
+        session.ponder(&GameY::new(4), &stop);
+        assert!(session.reused_playouts() > 0);
+        assert!(session.reused_playouts() <= 10 * PONDER_PLAYOUTS_PER_ITERATION);
+    }
+
+    #[test]
+    fn test_session_resets_on_unrelated_game() {
+        let mut session = MctsSession::new(50);
+        session.choose_move(&GameY::new(4)).unwrap();
+        session.sync(&GameY::new(5));
+        assert_eq!(session.reused_playouts(), 0);
+    }
+
+    #[test]
+    fn test_ponder_stops_when_requested() {
+        let mut session = MctsSession::new(10);
+        let stop = AtomicBool::new(true);
+        session.ponder(&GameY::new(4), &stop);
+        assert_eq!(session.reused_playouts(), 0);
+    }
+
+    #[test]
+    fn test_explain_move_mentions_playouts() {
+        let bot = MctsBot::new(200);
+        let game = GameY::new(3);
+        let coords = bot.choose_move(&game).unwrap();
+        let explanation = bot.explain_move(&game, coords).unwrap();
+        assert!(explanation.contains("playouts"));
+    }
+
+    #[test]
+    fn test_chosen_move_is_explained_by_its_search() {
+        let bot = MctsBot::new(200);
+        let (coords, stats) = bot.choose_move_with_stats(&GameY::new(3)).unwrap();
+        let explanation = stats.explanation.unwrap();
+        assert!(explanation.starts_with(&format!("{} won", coords)));
+    }
+}
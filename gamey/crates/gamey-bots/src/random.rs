@@ -3,7 +3,8 @@
 //! This module provides [`RandomBot`], a bot that makes random valid moves.
 //! It is useful for testing and as a baseline opponent.
 
-use crate::{Coordinates, GameY, YBot};
+use crate::YBot;
+use gamey_core::{Coordinates, GameY, rng};
 use rand::prelude::IndexedRandom;
 
 /// A bot that chooses moves randomly from the available cells.
@@ -15,7 +16,8 @@ use rand::prelude::IndexedRandom;
 /// # Example
 ///
 /// ```
-/// use gamey::{GameY, RandomBot, YBot};
+/// use gamey_bots::{RandomBot, YBot};
+/// use gamey_core::GameY;
 ///
 /// let bot = RandomBot;
 /// let game = GameY::new(5);
@@ -33,16 +35,24 @@ impl YBot for RandomBot {
 
     fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
         let available_cells = board.available_cells();
-        let cell = available_cells.choose(&mut rand::rng())?;
-        let coordinates = Coordinates::from_index(*cell, board.board_size());
+        let cell = available_cells.choose(&mut rng())?;
+        let coordinates = board.cell_coords(*cell);
         Some(coordinates)
     }
+
+    fn explain_move(&self, board: &GameY, coords: Coordinates) -> Option<String> {
+        Some(format!(
+            "{} was picked uniformly at random among {} available cells",
+            coords,
+            board.available_cells().len()
+        ))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Movement, PlayerId};
+    use gamey_core::{Movement, PlayerId};
 
     #[test]
     fn test_random_bot_name() {
@@ -122,6 +132,17 @@ mod tests {
         assert!(game.available_cells().contains(&index));
     }
 
+    #[test]
+    fn test_random_bot_explains_move() {
+        let bot = RandomBot;
+        let game = GameY::new(3);
+        let coords = bot.choose_move(&game).unwrap();
+
+        let explanation = bot.explain_move(&game, coords).unwrap();
+        assert!(explanation.contains("random"));
+        assert!(explanation.contains("6 available cells"));
+    }
+
     #[test]
     fn test_random_bot_multiple_calls_return_valid_moves() {
         let bot = RandomBot;
@@ -0,0 +1,267 @@
+//! How well every move of a finished game was played.
+//!
+//! [`Review::run`] goes through the placements of a game and, for each,
+//! asks a bot for the move it would have played instead. Both the played
+//! move and the bot's move are played out many times by the bot, and the
+//! difference between their win rates for the player who moved is what the
+//! move cost. Moves that cost more than a threshold are flagged as
+//! mistakes, and the average cost of a player's moves gives their accuracy.
+//!
+//! Like [`OpeningAnalysis`](crate::OpeningAnalysis), every move is played
+//! out from its own random stream (see [`gamey_core::reseed`]), so under a
+//! [`gamey_core::DeterministicConfig`] the review is the same whatever the
+//! number of threads.
+
+use crate::YBot;
+use crate::analysis::play_out;
+use gamey_core::{Coordinates, GameY, Movement, PlayerId, reseed, worker_threads};
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The estimated cost of one placement of a game.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReviewedMove {
+    /// The number of the move in the history, from 1.
+    pub number: usize,
+    /// The player who made the move.
+    pub player: PlayerId,
+    /// The cell played.
+    pub played: Coordinates,
+    /// The cell the bot would have played.
+    pub best: Coordinates,
+    /// The fraction of the playouts after the played move won by `player`.
+    pub played_rate: f64,
+    /// The fraction of the playouts after the bot's move won by `player`.
+    pub best_rate: f64,
+}
+
+impl ReviewedMove {
+    /// Returns how much the win rate of the player dropped by not playing
+    /// the bot's move, from 0 to 1; moves that did better than the bot's
+    /// cost nothing.
+    pub fn drop(&self) -> f64 {
+        (self.best_rate - self.played_rate).max(0.0)
+    }
+}
+
+/// The placements of a game, each compared with the move of a bot.
+#[derive(Debug, Clone)]
+pub struct Review {
+    /// The game reviewed.
+    pub game: GameY,
+    /// The bot that chose the alternatives and played the games out.
+    pub bot: String,
+    /// The games played out after every move.
+    pub playouts: u32,
+    /// The drop of win rate above which a move is a mistake.
+    pub threshold: f64,
+    /// One entry per placement, in the order they were played.
+    pub moves: Vec<ReviewedMove>,
+}
+
+impl Review {
+    /// Reviews every placement of `game`, playing `playouts` games out with
+    /// `bot` after the move played and after the move the bot prefers. The
+    /// moves are reviewed in parallel. Actions, such as a swap, are not
+    /// reviewed.
+    pub fn run(game: &GameY, bot: &dyn YBot, playouts: u32, threshold: f64) -> Self {
+        let placements: Vec<(usize, PlayerId, Coordinates)> = game
+            .history()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, movement)| match movement {
+                Movement::Placement { player, coords } => Some((index, *player, *coords)),
+                Movement::Action { .. } => None,
+            })
+            .collect();
+
+        let results = Mutex::new(Vec::new());
+        let next = AtomicUsize::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..worker_threads().min(placements.len()) {
+                scope.spawn(|| {
+                    loop {
+                        let task = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(&(index, player, played)) = placements.get(task) else {
+                            break;
+                        };
+                        reseed(index as u64);
+                        let Some(reviewed) =
+                            review_move(game, index, player, played, bot, playouts)
+                        else {
+                            continue;
+                        };
+                        results
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .push(reviewed);
+                    }
+                });
+            }
+        });
+        let mut moves = results.into_inner().unwrap_or_else(|e| e.into_inner());
+        moves.sort_by_key(|reviewed| reviewed.number);
+        Review {
+            game: game.clone(),
+            bot: bot.name().to_string(),
+            playouts,
+            threshold,
+            moves,
+        }
+    }
+
+    /// Returns the moves whose drop is above the threshold.
+    pub fn mistakes(&self) -> impl Iterator<Item = &ReviewedMove> {
+        self.moves
+            .iter()
+            .filter(|reviewed| reviewed.drop() > self.threshold)
+    }
+
+    /// Returns the accuracy of `player`, from 0 to 1: one minus the average
+    /// drop of their moves. `None` if they made no reviewed move.
+    pub fn accuracy(&self, player: PlayerId) -> Option<f64> {
+        let drops: Vec<f64> = self
+            .moves
+            .iter()
+            .filter(|reviewed| reviewed.player == player)
+            .map(ReviewedMove::drop)
+            .collect();
+        if drops.is_empty() {
+            return None;
+        }
+        Some(1.0 - drops.iter().sum::<f64>() / drops.len() as f64)
+    }
+}
+
+impl Display for Review {
+    /// Writes the flagged moves, then the accuracy of each player.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} moves played out {} times each by {}, mistakes drop more than {:.0}%",
+            self.moves.len(),
+            self.playouts,
+            self.bot,
+            100.0 * self.threshold
+        )?;
+        writeln!(
+            f,
+            "{:>4} {:>6} {:>6} {:>6} {:>7} {:>6}",
+            "Move", "Player", "Played", "Best", "Win %", "Drop %"
+        )?;
+        for reviewed in self.mistakes() {
+            writeln!(
+                f,
+                "{:>4} {:>6} {:>6} {:>6} {:>7.1} {:>6.1}",
+                reviewed.number,
+                reviewed.player.id(),
+                self.game.cell_index(reviewed.played),
+                self.game.cell_index(reviewed.best),
+                100.0 * reviewed.played_rate,
+                100.0 * reviewed.drop()
+            )?;
+        }
+        for player in [PlayerId::new(0), PlayerId::new(1)] {
+            let mistakes = self
+                .mistakes()
+                .filter(|reviewed| reviewed.player == player)
+                .count();
+            match self.accuracy(player) {
+                Some(accuracy) => writeln!(
+                    f,
+                    "Player {}: accuracy {:.1}%, {} mistake(s)",
+                    player,
+                    100.0 * accuracy,
+                    mistakes
+                )?,
+                None => writeln!(f, "Player {}: no moves", player)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Compares the placement of `played` by `player`, move `index` of the
+/// history of `game`, with the move `bot` prefers in its place; `None` if
+/// the position cannot be rebuilt or the bot gives up.
+fn review_move(
+    game: &GameY,
+    index: usize,
+    player: PlayerId,
+    played: Coordinates,
+    bot: &dyn YBot,
+    playouts: u32,
+) -> Option<ReviewedMove> {
+    let before = game.position_after(index).ok()?;
+    let best = bot.choose_move(&before)?;
+    let win_rate = |coords| {
+        let wins = (0..playouts)
+            .filter(|_| play_out(&before, coords, bot) == Some(player))
+            .count();
+        match playouts {
+            0 => 0.0,
+            playouts => wins as f64 / f64::from(playouts),
+        }
+    };
+    let played_rate = win_rate(played);
+    let best_rate = if best == played {
+        played_rate
+    } else {
+        win_rate(best)
+    };
+    Some(ReviewedMove {
+        number: index + 1,
+        player,
+        played,
+        best,
+        played_rate,
+        best_rate,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RandomBot, SolverBot};
+
+    #[test]
+    fn test_losing_move_is_a_mistake() {
+        // The top corner loses a size 3 board that the first player wins
+        // from the second row
+        let mut game = GameY::new(3);
+        game.play_move_list("0 4 3 1").unwrap();
+        let review = Review::run(&game, &SolverBot::default(), 1, 0.5);
+        assert_eq!(review.moves.len(), 4);
+        assert!(review.moves.windows(2).all(|w| w[0].number < w[1].number));
+        let mistakes: Vec<&ReviewedMove> = review.mistakes().collect();
+        assert_eq!(mistakes.len(), 1);
+        assert_eq!(mistakes[0].number, 1);
+        assert_eq!(mistakes[0].played, Coordinates::new(2, 0, 0));
+        assert_eq!(mistakes[0].drop(), 1.0);
+        assert_eq!(review.accuracy(PlayerId::new(0)), Some(0.5));
+        assert_eq!(review.accuracy(PlayerId::new(1)), Some(1.0));
+
+        let report = review.to_string();
+        assert!(report.starts_with("4 moves played out 1 times each by solver_bot"));
+        assert!(report.contains("Player 0: accuracy 50.0%, 1 mistake(s)"));
+        assert!(report.contains("Player 1: accuracy 100.0%, 0 mistake(s)"));
+    }
+
+    #[test]
+    fn test_accuracy_of_an_empty_game() {
+        let review = Review::run(&GameY::new(4), &RandomBot, 5, 0.2);
+        assert!(review.moves.is_empty());
+        assert_eq!(review.accuracy(PlayerId::new(1)), None);
+        assert!(review.to_string().ends_with("Player 1: no moves\n"));
+        let reviewed = ReviewedMove {
+            number: 1,
+            player: PlayerId::new(0),
+            played: Coordinates::new(3, 0, 0),
+            best: Coordinates::new(1, 1, 1),
+            played_rate: 0.7,
+            best_rate: 0.6,
+        };
+        assert_eq!(reviewed.drop(), 0.0);
+    }
+}
@@ -0,0 +1,113 @@
+//! Results reported by searching bots.
+//!
+//! Besides the move they would play, search-based bots (such as
+//! [`MctsBot`](crate::MctsBot)) can report the line of play they expect and
+//! some statistics about the search that produced it. [`MoveStats`]
+//! summarizes how a single move was chosen, for display after each move,
+//! and [`SearchProgress`] snapshots are reported while a search runs.
+
+use gamey_core::Coordinates;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// The outcome of a bot search on a position.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchResult {
+    /// The move the bot would play.
+    pub best_move: Coordinates,
+    /// The principal variation: the expected line of play, starting with
+    /// `best_move` and alternating between both players.
+    pub pv: Vec<Coordinates>,
+    /// Estimated probability (between 0 and 1) that the player to move wins
+    /// after playing `best_move`. Bots that do not evaluate positions report 0.5.
+    pub score: f64,
+    /// Number of nodes (positions) visited by the search.
+    pub nodes: u64,
+    /// Deepest ply reached by the search.
+    pub depth: u32,
+}
+
+impl SearchResult {
+    /// Creates a result for a bot that only knows which move it wants to play.
+    pub fn from_move(best_move: Coordinates) -> Self {
+        SearchResult {
+            best_move,
+            pv: vec![best_move],
+            score: 0.5,
+            nodes: 1,
+            depth: 1,
+        }
+    }
+}
+
+/// Statistics about how a bot chose one move.
+///
+/// Evaluations are estimated probabilities (between 0 and 1) that the
+/// player who moved wins; bots that do not evaluate positions leave them
+/// out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveStats {
+    /// Time spent choosing the move.
+    pub elapsed: Duration,
+    /// Number of nodes or playouts the search went through, if the bot
+    /// searches.
+    pub nodes: Option<u64>,
+    /// Evaluation of the position before the move.
+    pub eval_before: Option<f64>,
+    /// Evaluation of the position after the move.
+    pub eval_after: Option<f64>,
+    /// Why the move was chosen, if the search that chose it can tell; see
+    /// [`YBot::explain_move`](crate::YBot::explain_move).
+    pub explanation: Option<String>,
+}
+
+impl MoveStats {
+    /// Creates statistics that only know how long the move took.
+    pub fn timed(elapsed: Duration) -> Self {
+        MoveStats {
+            elapsed,
+            nodes: None,
+            eval_before: None,
+            eval_after: None,
+            explanation: None,
+        }
+    }
+}
+
+/// A snapshot of a search in progress, reported to the progress callback
+/// of [`YBot::choose_move_with_progress`](crate::YBot::choose_move_with_progress).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SearchProgress {
+    /// Number of iterations (playouts or nodes) run so far.
+    pub iterations: u64,
+    /// Deepest ply reached so far.
+    pub depth: u32,
+    /// The move the bot would play if the search stopped now.
+    pub best_move: Option<Coordinates>,
+    /// Estimated probability that the player to move wins after
+    /// `best_move`.
+    pub score: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_move() {
+        let coords = Coordinates::new(1, 0, 1);
+        let result = SearchResult::from_move(coords);
+        assert_eq!(result.best_move, coords);
+        assert_eq!(result.pv, vec![coords]);
+        assert_eq!(result.score, 0.5);
+        assert_eq!(result.depth, 1);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let result = SearchResult::from_move(Coordinates::new(1, 0, 1));
+        let json = serde_json::to_string(&result).unwrap();
+        assert!(json.contains("\"best_move\":{\"x\":1,\"y\":0,\"z\":1}"));
+        assert!(json.contains("\"pv\":[{\"x\":1,\"y\":0,\"z\":1}]"));
+    }
+}
@@ -0,0 +1,137 @@
+//! Stateful bot sessions.
+//!
+//! [`YBot::choose_move`] is stateless: every call starts from scratch. A
+//! [`BotSession`] instead follows a single game from start to finish, which
+//! lets bots keep work between moves (for example reusing a search tree) and
+//! think while the opponent is deciding ("pondering").
+
+use crate::{CancellationToken, MoveStats, SearchProgress, YBot};
+use gamey_core::{Coordinates, GameY};
+use std::sync::Arc;
+use std::sync::atomic::AtomicBool;
+use std::time::Instant;
+
+/// A bot bound to a single game.
+///
+/// Sessions are given the full current game state on each call and are
+/// responsible for noticing which moves were played since the last call.
+pub trait BotSession: Send {
+    /// Chooses a move for the current game state, reusing earlier work
+    /// when possible.
+    fn choose_move(&mut self, game: &GameY) -> Option<Coordinates>;
+
+    /// Chooses a move like [`BotSession::choose_move`] and reports how it
+    /// was chosen; by default only the time taken is known.
+    fn choose_move_with_stats(&mut self, game: &GameY) -> Option<(Coordinates, MoveStats)> {
+        let start = Instant::now();
+        let coords = self.choose_move(game)?;
+        Some((coords, MoveStats::timed(start.elapsed())))
+    }
+
+    /// Chooses a move like [`BotSession::choose_move_with_stats`], reporting
+    /// the progress of the search and stopping early once `cancel` is
+    /// cancelled, see [`YBot::choose_move_with_progress`].
+    ///
+    /// By default `progress` is never called and `cancel` is only checked
+    /// before starting.
+    fn choose_move_with_progress(
+        &mut self,
+        game: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        let _ = progress;
+        if cancel.is_cancelled() {
+            return None;
+        }
+        self.choose_move_with_stats(game)
+    }
+
+    /// Thinks about the current game state while the opponent is to move,
+    /// until `stop` is set.
+    ///
+    /// Sessions that cannot ponder return immediately, which is the default.
+    fn ponder(&mut self, game: &GameY, stop: &AtomicBool) {
+        let _ = (game, stop);
+    }
+}
+
+/// A session for bots without state: every move is chosen from scratch.
+pub struct StatelessSession {
+    bot: Arc<dyn YBot>,
+}
+
+impl StatelessSession {
+    /// Creates a session that delegates to [`YBot::choose_move`].
+    pub fn new(bot: Arc<dyn YBot>) -> Self {
+        StatelessSession { bot }
+    }
+}
+
+impl BotSession for StatelessSession {
+    fn choose_move(&mut self, game: &GameY) -> Option<Coordinates> {
+        self.bot.choose_move(game)
+    }
+
+    fn choose_move_with_stats(&mut self, game: &GameY) -> Option<(Coordinates, MoveStats)> {
+        self.bot.choose_move_with_stats(game)
+    }
+
+    fn choose_move_with_progress(
+        &mut self,
+        game: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        self.bot.choose_move_with_progress(game, progress, cancel)
+    }
+}
+
+/// Starts a session for the given bot.
+///
+/// Bots providing their own session through [`YBot::new_session`] get it;
+/// otherwise the bot is wrapped in a [`StatelessSession`].
+pub fn start_session(bot: Arc<dyn YBot>) -> Box<dyn BotSession> {
+    match bot.new_session() {
+        Some(session) => session,
+        None => Box::new(StatelessSession::new(bot)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MctsBot, RandomBot};
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_stateless_session_delegates_to_bot() {
+        let mut session = start_session(Arc::new(RandomBot));
+        let game = GameY::new(3);
+        let coords = session.choose_move(&game).unwrap();
+        assert!(game.available_cells().contains(&coords.to_index(3)));
+    }
+
+    #[test]
+    fn test_stateless_session_reports_bot_stats() {
+        let mut session = start_session(Arc::new(RandomBot));
+        let (_, stats) = session.choose_move_with_stats(&GameY::new(3)).unwrap();
+        assert_eq!(stats.nodes, Some(1));
+        assert_eq!(stats.eval_after, None);
+    }
+
+    #[test]
+    fn test_stateless_ponder_returns_immediately() {
+        let mut session = start_session(Arc::new(RandomBot));
+        let stop = AtomicBool::new(false);
+        session.ponder(&GameY::new(3), &stop);
+        assert!(!stop.load(Ordering::Relaxed));
+    }
+
+    #[test]
+    fn test_mcts_bot_provides_its_own_session() {
+        let bot = MctsBot::new(10);
+        assert!(bot.new_session().is_some());
+        assert!(RandomBot.new_session().is_none());
+    }
+}
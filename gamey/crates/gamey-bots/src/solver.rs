@@ -0,0 +1,480 @@
+//! An exact solver for small Y boards.
+//!
+//! A full Y board always has exactly one connected player, and who it is can
+//! be read off with the Y-reduction (micro-reduction): replacing every
+//! triangle of three adjacent cells with its majority color yields a board
+//! one size smaller with the same connected player, down to a single cell.
+//!
+//! Stones never hurt their owner in Y, so a player has already connected
+//! the sides exactly when filling every empty cell with the opponent's stones
+//! still reduces to that player. The [`solve`] search uses this test to stop
+//! as soon as a position is decided, and a transposition table to visit each
+//! position once. The cost still grows exponentially with the number of
+//! empty cells: an empty board of size 5 is solved in a fraction of a second,
+//! while an empty board of size 6 takes minutes.
+//!
+//! The transposition table can be bounded with [`solve_bounded`]: a full
+//! table is emptied and refilled, trading repeated work for memory.
+
+use crate::{CancellationToken, MctsBot, MoveStats, SearchProgress, YBot};
+use gamey_core::{BoardTopology, Coordinates, GameStatus, GameY, GameYError, PlayerId};
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Instant;
+
+/// The largest board size [`solve`] accepts.
+pub const MAX_SOLVER_SIZE: u32 = 7;
+
+/// The number of empty cells up to which [`SolverBot`] uses the solver by
+/// default.
+pub const DEFAULT_SOLVER_EMPTY_CELLS: usize = 15;
+
+/// The game-theoretic value of a position, as computed by [`solve`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Solution {
+    /// The player who wins with perfect play from both sides.
+    pub winner: PlayerId,
+    /// Every move that keeps the win for the player to move, in cell index
+    /// order. Empty if the player to move loses or the game is over.
+    pub winning_moves: Vec<Coordinates>,
+    /// Number of positions visited by the search.
+    pub nodes: u64,
+}
+
+impl Solution {
+    /// Returns a winning move for the player to move, if there is one.
+    pub fn best_move(&self) -> Option<Coordinates> {
+        self.winning_moves.first().copied()
+    }
+}
+
+/// Solves the position exactly: who wins with perfect play and with which
+/// moves.
+///
+/// # Errors
+/// Returns `GameYError::UnsupportedBySolver` for boards larger than
+/// [`MAX_SOLVER_SIZE`], for Hex boards, and for rulesets with blocked cells,
+/// which the Y-reduction cannot evaluate.
+pub fn solve(game: &GameY) -> Result<Solution, GameYError> {
+    solve_bounded(game, usize::MAX)
+}
+
+/// Solves the position like [`solve`], keeping at most `max_table_entries`
+/// positions in the transposition table.
+///
+/// # Errors
+/// The same as [`solve`].
+pub fn solve_bounded(game: &GameY, max_table_entries: usize) -> Result<Solution, GameYError> {
+    let unsupported = |reason: String| GameYError::UnsupportedBySolver { reason };
+    if game.topology() != BoardTopology::Y {
+        return Err(unsupported(format!(
+            "only Y boards can be solved, not {}",
+            game.topology()
+        )));
+    }
+    if game.board_size() > MAX_SOLVER_SIZE {
+        return Err(unsupported(format!(
+            "the board size {} is larger than {}",
+            game.board_size(),
+            MAX_SOLVER_SIZE
+        )));
+    }
+    if (0..game.total_cells()).any(|i| game.is_blocked(game.cell_coords(i))) {
+        return Err(unsupported(format!(
+            "the {} ruleset blocks cells",
+            game.ruleset()
+        )));
+    }
+
+    let next_player = match game.status() {
+        GameStatus::Finished { winner } => {
+            return Ok(Solution {
+                winner: *winner,
+                winning_moves: Vec::new(),
+                nodes: 0,
+            });
+        }
+        GameStatus::Ongoing { next_player } => *next_player,
+    };
+
+    let mut stones = [0u32; 2];
+    for cell in game.to_state().board {
+        if let Some(player) = cell.player {
+            stones[player as usize] |= 1 << cell.index;
+        }
+    }
+    let mut solver = Solver::new(game);
+    solver.max_table_entries = max_table_entries;
+    let to_move = next_player.id() as usize;
+    let mut winning_moves = Vec::new();
+    for &cell in Rc::clone(&solver.order).iter() {
+        let bit = 1 << cell;
+        if (stones[0] | stones[1]) & bit != 0 {
+            continue;
+        }
+        let mut child = stones;
+        child[to_move] |= bit;
+        if !solver.wins(1 - to_move, child) {
+            winning_moves.push(cell);
+        }
+    }
+    winning_moves.sort_unstable();
+
+    let winner = if winning_moves.is_empty() {
+        next_player.opponent()
+    } else {
+        next_player
+    };
+    Ok(Solution {
+        winner,
+        winning_moves: winning_moves
+            .into_iter()
+            .map(|cell| game.cell_coords(cell as u32))
+            .collect(),
+        nodes: solver.nodes,
+    })
+}
+
+/// Search state of [`solve`]. Positions are pairs of bitboards indexed by
+/// cell index, one per player.
+struct Solver {
+    board_size: usize,
+    full: u32,
+    misere: bool,
+    /// Cells in the order moves are tried: central cells first.
+    order: Rc<[usize]>,
+    table: HashMap<(u32, u32, usize), bool>,
+    /// The table is emptied when it reaches this many entries.
+    max_table_entries: usize,
+    nodes: u64,
+}
+
+impl Solver {
+    fn new(game: &GameY) -> Self {
+        let cells = game.total_cells();
+        let mut order: Vec<usize> = (0..cells as usize).collect();
+        order.sort_by_key(|&i| {
+            let c = game.cell_coords(i as u32);
+            (c.x().max(c.y()).max(c.z()), i)
+        });
+        Solver {
+            board_size: game.board_size() as usize,
+            full: (1 << cells) - 1,
+            misere: game.ruleset().is_misere(),
+            order: order.into(),
+            table: HashMap::new(),
+            max_table_entries: usize::MAX,
+            nodes: 0,
+        }
+    }
+
+    /// Returns true if `to_move` wins the position with perfect play.
+    fn wins(&mut self, to_move: usize, stones: [u32; 2]) -> bool {
+        self.nodes += 1;
+        if let Some(connected) = self.connected_player(stones) {
+            let winner = if self.misere {
+                1 - connected
+            } else {
+                connected
+            };
+            return winner == to_move;
+        }
+        let key = (stones[0], stones[1], to_move);
+        if let Some(&wins) = self.table.get(&key) {
+            return wins;
+        }
+        let occupied = stones[0] | stones[1];
+        let mut wins = false;
+        for &cell in Rc::clone(&self.order).iter() {
+            let bit = 1 << cell;
+            if occupied & bit != 0 {
+                continue;
+            }
+            let mut child = stones;
+            child[to_move] |= bit;
+            if !self.wins(1 - to_move, child) {
+                wins = true;
+                break;
+            }
+        }
+        if self.table.len() >= self.max_table_entries {
+            self.table.clear();
+        }
+        self.table.insert(key, wins);
+        wins
+    }
+
+    /// Returns the player who has already connected the sides, if any.
+    fn connected_player(&self, stones: [u32; 2]) -> Option<usize> {
+        let empty = self.full & !(stones[0] | stones[1]);
+        if self.reduces_to_player_0(stones[0]) {
+            Some(0)
+        } else if !self.reduces_to_player_0(stones[0] | empty) {
+            Some(1)
+        } else {
+            None
+        }
+    }
+
+    /// Applies the Y-reduction to a full board where the set bits are the
+    /// stones of player 0 and every other cell belongs to player 1.
+    fn reduces_to_player_0(&self, player_0: u32) -> bool {
+        let mut cells = player_0;
+        for size in (1..self.board_size).rev() {
+            // Cell (row, col) of the reduced board is the majority of the
+            // triangle (row, col), (row + 1, col), (row + 1, col + 1), so a
+            // whole row is reduced at once with shifted masks.
+            let mut reduced = 0;
+            for row in 0..size {
+                let start = row * (row + 1) / 2;
+                let below = start + row + 1;
+                let mask = (1 << (row + 1)) - 1;
+                let a = (cells >> start) & mask;
+                let b = (cells >> below) & mask;
+                let c = (cells >> (below + 1)) & mask;
+                reduced |= ((a & b) | (a & c) | (b & c)) << start;
+            }
+            cells = reduced;
+        }
+        cells & 1 != 0
+    }
+}
+
+/// A bot that plays perfectly once few enough cells remain empty.
+///
+/// With more empty cells than its limit, in lost positions, and on boards
+/// the solver cannot handle, it falls back to an [`MctsBot`].
+pub struct SolverBot {
+    max_empty_cells: usize,
+    max_table_entries: usize,
+    fallback: MctsBot,
+}
+
+impl SolverBot {
+    /// Creates a bot that solves positions with at most `max_empty_cells`
+    /// empty cells and plays the others with `fallback`.
+    pub fn new(max_empty_cells: usize, fallback: MctsBot) -> Self {
+        SolverBot {
+            max_empty_cells,
+            max_table_entries: usize::MAX,
+            fallback,
+        }
+    }
+
+    /// Limits the transposition table of the solver, see [`solve_bounded`].
+    pub fn with_max_table_entries(mut self, max_table_entries: usize) -> Self {
+        self.max_table_entries = max_table_entries;
+        self
+    }
+
+    /// Solves the position if it is within the bot's limits.
+    fn solve(&self, board: &GameY) -> Option<Solution> {
+        if board.available_cells().len() > self.max_empty_cells {
+            return None;
+        }
+        solve_bounded(board, self.max_table_entries).ok()
+    }
+}
+
+impl Default for SolverBot {
+    fn default() -> Self {
+        SolverBot::new(DEFAULT_SOLVER_EMPTY_CELLS, MctsBot::default())
+    }
+}
+
+impl YBot for SolverBot {
+    fn name(&self) -> &str {
+        "solver_bot"
+    }
+
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        match self.solve(board).and_then(|s| s.best_move()) {
+            Some(coords) => Some(coords),
+            None => self.fallback.choose_move(board),
+        }
+    }
+
+    /// Solved positions are evaluated exactly: 1 when the player to move
+    /// wins and 0 when they lose, in which case the fallback picks the move.
+    fn choose_move_with_stats(&self, board: &GameY) -> Option<(Coordinates, MoveStats)> {
+        self.choose_move_with_progress(board, &mut |_| {}, &CancellationToken::new())
+    }
+
+    /// Only the fallback reports progress and stops when cancelled: the
+    /// solver has no move until it is done, and it is only used on
+    /// positions it solves quickly.
+    fn choose_move_with_progress(
+        &self,
+        board: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        if cancel.is_cancelled() {
+            return None;
+        }
+        let start = Instant::now();
+        let solution = self.solve(board);
+        let (coords, mut stats) = match solution.as_ref().and_then(|s| s.best_move()) {
+            Some(coords) => (coords, MoveStats::timed(start.elapsed())),
+            None => self
+                .fallback
+                .choose_move_with_progress(board, progress, cancel)?,
+        };
+        if let Some(solution) = solution {
+            let wins = solution.best_move().is_some();
+            let eval = if wins { 1.0 } else { 0.0 };
+            stats.nodes = Some(solution.nodes);
+            stats.eval_before = Some(eval);
+            stats.eval_after = Some(eval);
+        }
+        stats.elapsed = start.elapsed();
+        Some((coords, stats))
+    }
+
+    fn explain_move(&self, board: &GameY, coords: Coordinates) -> Option<String> {
+        let solution = self.solve(board)?;
+        let player = board.next_player()?;
+        Some(if solution.winning_moves.contains(&coords) {
+            format!("{} wins for player {} with perfect play", coords, player)
+        } else if solution.winner == player {
+            format!(
+                "{} throws away the win; winning moves: {}",
+                coords,
+                join(&solution.winning_moves)
+            )
+        } else {
+            format!(
+                "player {} loses with perfect play whatever they play",
+                player
+            )
+        })
+    }
+}
+
+fn join(cells: &[Coordinates]) -> String {
+    cells
+        .iter()
+        .map(|c| c.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{Movement, Ruleset};
+
+    fn play(game: &mut GameY, moves: &[(u32, Coordinates)]) {
+        for &(player, coords) in moves {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_first_player_wins_empty_boards() {
+        for size in 1..=4 {
+            let solution = solve(&GameY::new(size)).unwrap();
+            assert_eq!(solution.winner, PlayerId::new(0), "size {}", size);
+            assert!(solution.best_move().is_some());
+        }
+    }
+
+    #[test]
+    fn test_finds_the_only_winning_move() {
+        // Player 0 needs the last cell of the bottom row to connect
+        let mut game = GameY::new(2);
+        play(&mut game, &[(0, Coordinates::new(0, 1, 0))]);
+        play(&mut game, &[(1, Coordinates::new(1, 0, 0))]);
+        let solution = solve(&game).unwrap();
+        assert_eq!(solution.winner, PlayerId::new(0));
+        assert_eq!(solution.winning_moves, vec![Coordinates::new(0, 0, 1)]);
+    }
+
+    #[test]
+    fn test_finished_games_are_already_solved() {
+        let mut game = GameY::new(1);
+        play(&mut game, &[(0, Coordinates::new(0, 0, 0))]);
+        let solution = solve(&game).unwrap();
+        assert_eq!(solution.winner, PlayerId::new(0));
+        assert!(solution.winning_moves.is_empty());
+    }
+
+    #[test]
+    fn test_misere_reverses_the_winner() {
+        // Player 0 plays twice on the three cells, and any two cells connect
+        let solution = solve(&GameY::with_ruleset(2, Ruleset::Misere)).unwrap();
+        assert_eq!(solution.winner, PlayerId::new(1));
+        assert!(solution.winning_moves.is_empty());
+    }
+
+    #[test]
+    fn test_reduction_matches_the_engine() {
+        let mut rng = rand::rng();
+        for _ in 0..50 {
+            let game = gamey_core::testing::random_game(
+                5,
+                BoardTopology::Y,
+                Ruleset::Standard,
+                usize::MAX,
+                &mut rng,
+            );
+            let solution = solve(&game).unwrap();
+            let GameStatus::Finished { winner } = game.status() else {
+                panic!("random games end with a winner");
+            };
+            assert_eq!(solution.winner, *winner);
+            // Filling the rest of the board does not change the connection
+            let solver = Solver::new(&game);
+            let mut stones = [0u32; 2];
+            for cell in game.to_state().board {
+                if let Some(player) = cell.player {
+                    stones[player as usize] |= 1 << cell.index;
+                }
+            }
+            assert_eq!(solver.connected_player(stones), Some(winner.id() as usize));
+        }
+    }
+
+    #[test]
+    fn test_bounded_table_gives_the_same_solution() {
+        let game = GameY::new(4);
+        let unbounded = solve(&game).unwrap();
+        let bounded = solve_bounded(&game, 16).unwrap();
+        assert_eq!(bounded.winner, unbounded.winner);
+        assert_eq!(bounded.winning_moves, unbounded.winning_moves);
+        assert!(bounded.nodes >= unbounded.nodes);
+    }
+
+    #[test]
+    fn test_rejects_unsupported_boards() {
+        assert!(solve(&GameY::new(MAX_SOLVER_SIZE + 1)).is_err());
+        assert!(solve(&GameY::with_topology(3, BoardTopology::Hex)).is_err());
+        assert!(solve(&GameY::with_ruleset(5, Ruleset::MasterY)).is_err());
+    }
+
+    #[test]
+    fn test_solver_bot_plays_winning_moves() {
+        let mut game = GameY::new(2);
+        play(&mut game, &[(0, Coordinates::new(0, 1, 0))]);
+        play(&mut game, &[(1, Coordinates::new(1, 0, 0))]);
+        let bot = SolverBot::default();
+        assert_eq!(bot.name(), "solver_bot");
+        assert_eq!(bot.choose_move(&game), Some(Coordinates::new(0, 0, 1)));
+        assert!(bot.explain_move(&game, Coordinates::new(0, 0, 1)).is_some());
+        let (coords, stats) = bot.choose_move_with_stats(&game).unwrap();
+        assert_eq!(coords, Coordinates::new(0, 0, 1));
+        assert_eq!(stats.eval_before, Some(1.0));
+    }
+
+    #[test]
+    fn test_solver_bot_falls_back_on_large_positions() {
+        let bot = SolverBot::new(3, MctsBot::new(10));
+        let game = GameY::new(3);
+        assert!(bot.explain_move(&game, Coordinates::new(1, 1, 0)).is_none());
+        assert!(bot.choose_move(&game).is_some());
+    }
+}
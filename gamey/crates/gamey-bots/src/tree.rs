@@ -0,0 +1,323 @@
+//! Arena-allocated game trees for search bots.
+//!
+//! Search trees grow by many small nodes and are thrown away or cut down to
+//! a subtree after every move. [`Tree`] keeps all of its nodes in a single
+//! `Vec` and links them with indices instead of pointers or per-node child
+//! lists: adding a node reuses the slot of a removed one when there is one,
+//! and [`Tree::reroot`] keeps the subtree of the move that was played so a
+//! bot does not lose the work it already did on it.
+
+use std::collections::VecDeque;
+use std::ops::{Index, IndexMut};
+
+/// The handle of a node of a [`Tree`].
+///
+/// Handles stay valid until their node is removed; the slot of a removed
+/// node may then be reused by a new one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct NodeId(u32);
+
+impl NodeId {
+    /// Returns the position of the node in the arena, handy to keep
+    /// per-node data in a separate `Vec`.
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A slot of the arena, holding a node or free.
+#[derive(Debug, Clone)]
+struct Slot<T> {
+    /// The data of the node, `None` while the slot is free.
+    data: Option<T>,
+    parent: Option<NodeId>,
+    first_child: Option<NodeId>,
+    last_child: Option<NodeId>,
+    next_sibling: Option<NodeId>,
+}
+
+/// A rooted tree whose nodes hold a `T`, stored in an arena.
+///
+/// The root is created with the tree and cannot be removed. Children keep
+/// the order they were added in.
+///
+/// # Example
+///
+/// ```
+/// use gamey_bots::Tree;
+///
+/// let mut tree = Tree::new("root");
+/// let a = tree.push_child(tree.root(), "a");
+/// let b = tree.push_child(tree.root(), "b");
+/// tree.push_child(a, "a1");
+/// assert_eq!(tree.children(tree.root()).collect::<Vec<_>>(), vec![a, b]);
+///
+/// // Keep only the subtree of `a`, as after playing the move that led to it
+/// let tree = tree.reroot(a);
+/// assert_eq!(tree[tree.root()], "a");
+/// assert_eq!(tree.node_count(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tree<T> {
+    slots: Vec<Slot<T>>,
+    /// The slots of removed nodes, reused before the arena grows.
+    free: Vec<NodeId>,
+}
+
+impl<T> Tree<T> {
+    /// Creates a tree holding only a root.
+    pub fn new(root: T) -> Self {
+        Tree::with_capacity(1, root)
+    }
+
+    /// Creates a tree holding only a root, with room for `capacity` nodes
+    /// before the arena has to grow.
+    pub fn with_capacity(capacity: usize, root: T) -> Self {
+        let mut slots = Vec::with_capacity(capacity.max(1));
+        slots.push(Slot {
+            data: Some(root),
+            parent: None,
+            first_child: None,
+            last_child: None,
+            next_sibling: None,
+        });
+        Tree {
+            slots,
+            free: Vec::new(),
+        }
+    }
+
+    /// Returns the root of the tree.
+    pub fn root(&self) -> NodeId {
+        NodeId(0)
+    }
+
+    /// Returns the number of nodes of the tree, the root included.
+    pub fn node_count(&self) -> usize {
+        self.slots.len() - self.free.len()
+    }
+
+    /// Returns the data of a node, or `None` if it was removed.
+    pub fn get(&self, node: NodeId) -> Option<&T> {
+        self.slots.get(node.index())?.data.as_ref()
+    }
+
+    /// Returns the data of a node mutably, or `None` if it was removed.
+    pub fn get_mut(&mut self, node: NodeId) -> Option<&mut T> {
+        self.slots.get_mut(node.index())?.data.as_mut()
+    }
+
+    /// Returns the parent of a node, `None` for the root.
+    pub fn parent(&self, node: NodeId) -> Option<NodeId> {
+        self.slots[node.index()].parent
+    }
+
+    /// Returns the children of a node, in the order they were added.
+    pub fn children(&self, node: NodeId) -> Children<'_, T> {
+        Children {
+            tree: self,
+            next: self.slots[node.index()].first_child,
+        }
+    }
+
+    /// Returns true if the node has no children.
+    pub fn is_leaf(&self, node: NodeId) -> bool {
+        self.slots[node.index()].first_child.is_none()
+    }
+
+    /// Returns every node of the tree with its data, in arena order.
+    pub fn nodes(&self) -> impl Iterator<Item = (NodeId, &T)> {
+        self.slots
+            .iter()
+            .enumerate()
+            .filter_map(|(i, slot)| Some((NodeId(i as u32), slot.data.as_ref()?)))
+    }
+
+    /// Adds a node as the last child of `parent`, in a free slot if there
+    /// is one.
+    ///
+    /// # Panics
+    /// Panics if `parent` was removed.
+    pub fn push_child(&mut self, parent: NodeId, data: T) -> NodeId {
+        assert!(self.get(parent).is_some(), "the parent was removed");
+        let slot = Slot {
+            data: Some(data),
+            parent: Some(parent),
+            first_child: None,
+            last_child: None,
+            next_sibling: None,
+        };
+        let node = match self.free.pop() {
+            Some(node) => {
+                self.slots[node.index()] = slot;
+                node
+            }
+            None => {
+                let index = u32::try_from(self.slots.len()).expect("too many nodes");
+                self.slots.push(slot);
+                NodeId(index)
+            }
+        };
+        match self.slots[parent.index()].last_child {
+            Some(last) => self.slots[last.index()].next_sibling = Some(node),
+            None => self.slots[parent.index()].first_child = Some(node),
+        }
+        self.slots[parent.index()].last_child = Some(node);
+        node
+    }
+
+    /// Removes a node and its whole subtree, returning the data of the node.
+    /// The freed slots are reused by the next nodes added.
+    ///
+    /// # Panics
+    /// Panics if `node` is the root or was already removed.
+    pub fn remove(&mut self, node: NodeId) -> T {
+        assert!(self.get(node).is_some(), "the node was already removed");
+        let parent = self.parent(node).expect("the root cannot be removed");
+        let next = self.slots[node.index()].next_sibling;
+        let mut previous = None;
+        let mut current = self.slots[parent.index()].first_child;
+        while let Some(child) = current
+            && child != node
+        {
+            previous = Some(child);
+            current = self.slots[child.index()].next_sibling;
+        }
+        match previous {
+            Some(previous) => self.slots[previous.index()].next_sibling = next,
+            None => self.slots[parent.index()].first_child = next,
+        }
+        if self.slots[parent.index()].last_child == Some(node) {
+            self.slots[parent.index()].last_child = previous;
+        }
+
+        let data = self.free_slot(node);
+        let mut pending: Vec<NodeId> = self.children(node).collect();
+        while let Some(child) = pending.pop() {
+            pending.extend(self.children(child));
+            self.free_slot(child);
+        }
+        data
+    }
+
+    /// Marks the slot of a node as free and returns its data. The links of
+    /// the slot are kept until it is reused.
+    fn free_slot(&mut self, node: NodeId) -> T {
+        let data = self.slots[node.index()].data.take();
+        self.free.push(node);
+        data.expect("the node was already removed")
+    }
+
+    /// Returns a tree holding only the subtree rooted at `node`, which
+    /// becomes its root. The nodes are moved, not copied, and packed at the
+    /// start of the new arena.
+    ///
+    /// # Panics
+    /// Panics if `node` was removed.
+    pub fn reroot(mut self, node: NodeId) -> Tree<T> {
+        let root = self.slots[node.index()].data.take();
+        let mut tree =
+            Tree::with_capacity(self.node_count(), root.expect("the new root was removed"));
+        let mut queue = VecDeque::from([(node, tree.root())]);
+        while let Some((old, new)) = queue.pop_front() {
+            let mut child = self.slots[old.index()].first_child;
+            while let Some(old_child) = child {
+                child = self.slots[old_child.index()].next_sibling;
+                let data = self.slots[old_child.index()].data.take();
+                let new_child = tree.push_child(new, data.expect("children are live"));
+                queue.push_back((old_child, new_child));
+            }
+        }
+        tree
+    }
+}
+
+impl<T> Index<NodeId> for Tree<T> {
+    type Output = T;
+
+    fn index(&self, node: NodeId) -> &T {
+        self.get(node).expect("the node was removed")
+    }
+}
+
+impl<T> IndexMut<NodeId> for Tree<T> {
+    fn index_mut(&mut self, node: NodeId) -> &mut T {
+        self.get_mut(node).expect("the node was removed")
+    }
+}
+
+/// The children of a node, see [`Tree::children`].
+pub struct Children<'a, T> {
+    tree: &'a Tree<T>,
+    next: Option<NodeId>,
+}
+
+impl<T> Iterator for Children<'_, T> {
+    type Item = NodeId;
+
+    fn next(&mut self) -> Option<NodeId> {
+        let node = self.next?;
+        self.next = self.tree.slots[node.index()].next_sibling;
+        Some(node)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_removed_slots_are_reused() {
+        let mut tree = Tree::new(0);
+        let root = tree.root();
+        let a = tree.push_child(root, 1);
+        let b = tree.push_child(root, 2);
+        let c = tree.push_child(root, 3);
+        let a1 = tree.push_child(a, 11);
+        assert_eq!(tree.node_count(), 5);
+
+        assert_eq!(tree.remove(b), 2);
+        assert_eq!(tree.children(root).collect::<Vec<_>>(), vec![a, c]);
+        assert_eq!(tree.remove(a), 1);
+        assert_eq!(tree.get(a1), None);
+        assert_eq!(tree.children(root).collect::<Vec<_>>(), vec![c]);
+        assert_eq!(tree.node_count(), 2);
+
+        let d = tree.push_child(c, 4);
+        let e = tree.push_child(root, 5);
+        assert!([a, b, a1].contains(&d) && [a, b, a1].contains(&e));
+        assert_eq!(tree.parent(d), Some(c));
+        assert_eq!(tree.children(root).collect::<Vec<_>>(), vec![c, e]);
+        assert_eq!(tree.nodes().count(), 4);
+        assert!(tree.is_leaf(d) && !tree.is_leaf(c));
+    }
+
+    #[test]
+    fn test_reroot_keeps_the_subtree_in_order() {
+        let mut tree = Tree::new("root");
+        let a = tree.push_child(tree.root(), "a");
+        tree.push_child(tree.root(), "b");
+        let a1 = tree.push_child(a, "a1");
+        tree.push_child(a, "a2");
+        tree.push_child(a1, "a1x");
+
+        let tree = tree.reroot(a);
+        let root = tree.root();
+        assert_eq!(tree[root], "a");
+        assert_eq!(tree.parent(root), None);
+        let children: Vec<&str> = tree.children(root).map(|c| tree[c]).collect();
+        assert_eq!(children, vec!["a1", "a2"]);
+        let a1 = tree.children(root).next().unwrap();
+        let grandchildren: Vec<&str> = tree.children(a1).map(|c| tree[c]).collect();
+        assert_eq!(grandchildren, vec!["a1x"]);
+        assert_eq!(tree.node_count(), 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "the root cannot be removed")]
+    fn test_root_cannot_be_removed() {
+        let mut tree = Tree::new(());
+        let root = tree.root();
+        tree.remove(root);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::{BotSession, CancellationToken, MoveStats, SearchProgress, SearchResult};
+use gamey_core::{Coordinates, GameY};
+use std::time::Instant;
+
+/// Trait representing a Y game bot (YBot)
+/// A YBot is an AI that can choose moves in the game of Y.
+/// Implementors of this trait must provide a name and a method to choose a move given the current game state.
+pub trait YBot: Send + Sync {
+    /// Returns the name of the bot.
+    fn name(&self) -> &str;
+
+    /// Chooses a move based on the current game state.
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates>;
+
+    /// Searches the current game state and reports the chosen move together
+    /// with the expected line of play and search statistics.
+    ///
+    /// The default implementation wraps [`YBot::choose_move`], so the
+    /// principal variation only contains the chosen move. Search bots should
+    /// override this method and implement `choose_move` on top of it.
+    fn search(&self, board: &GameY) -> Option<SearchResult> {
+        self.choose_move(board).map(SearchResult::from_move)
+    }
+
+    /// Chooses a move and reports how it was chosen.
+    ///
+    /// The default implementation times [`YBot::search`] and reports its
+    /// node count without evaluations. Bots that evaluate positions should
+    /// override it.
+    fn choose_move_with_stats(&self, board: &GameY) -> Option<(Coordinates, MoveStats)> {
+        let start = Instant::now();
+        let result = self.search(board)?;
+        let stats = MoveStats {
+            nodes: Some(result.nodes),
+            ..MoveStats::timed(start.elapsed())
+        };
+        Some((result.best_move, stats))
+    }
+
+    /// Chooses a move like [`YBot::choose_move_with_stats`], calling
+    /// `progress` from time to time while searching so callers can show
+    /// the best move found so far.
+    ///
+    /// Searches stop early once `cancel` is cancelled, returning the best
+    /// move found so far, or `None` if they had not found one yet.
+    ///
+    /// Bots that do not search never call `progress` and only check
+    /// `cancel` before starting, which is the default.
+    fn choose_move_with_progress(
+        &self,
+        board: &GameY,
+        progress: &mut dyn FnMut(&SearchProgress),
+        cancel: &CancellationToken,
+    ) -> Option<(Coordinates, MoveStats)> {
+        let _ = progress;
+        if cancel.is_cancelled() {
+            return None;
+        }
+        self.choose_move_with_stats(board)
+    }
+
+    /// Explains why the bot would play `coords` in the given game state.
+    ///
+    /// This is intended for teaching and debugging, so the explanation is a
+    /// human-readable text. Bots that cannot justify their moves return `None`,
+    /// which is the default.
+    ///
+    /// Search bots may have to search again to explain a move; callers that
+    /// have just chosen it should use [`MoveStats::explanation`] first.
+    fn explain_move(&self, board: &GameY, coords: Coordinates) -> Option<String> {
+        let _ = (board, coords);
+        None
+    }
+
+    /// Returns true if the bot always chooses the same move, with the same
+    /// explanation, in the same position, so its answers can be cached.
+    ///
+    /// Bots that use randomness or a time budget are not deterministic,
+    /// which is the default.
+    fn is_deterministic(&self) -> bool {
+        false
+    }
+
+    /// Creates a stateful session that follows one game from start to finish.
+    ///
+    /// Bots that benefit from keeping state between moves (such as search
+    /// trees) return their own session; the default `None` makes
+    /// [`start_session`](crate::start_session) fall back to a stateless one.
+    fn new_session(&self) -> Option<Box<dyn BotSession>> {
+        None
+    }
+}
@@ -3,7 +3,7 @@
 //! The [`YBotRegistry`] provides a centralized way to register and retrieve
 //! bot implementations by name.
 
-use std::{collections::HashMap, sync::Arc};
+use std::{collections::BTreeMap, sync::Arc};
 
 use crate::YBot;
 
@@ -16,7 +16,7 @@ use crate::YBot;
 ///
 /// ```
 /// use std::sync::Arc;
-/// use gamey::{YBotRegistry, RandomBot};
+/// use gamey_bots::{YBotRegistry, RandomBot};
 ///
 /// let registry = YBotRegistry::new()
 ///     .with_bot(Arc::new(RandomBot));
@@ -25,14 +25,14 @@ use crate::YBot;
 /// assert!(bot.is_some());
 /// ```
 pub struct YBotRegistry {
-    bots: HashMap<String, Arc<dyn YBot>>,
+    bots: BTreeMap<String, Arc<dyn YBot>>,
 }
 
 impl YBotRegistry {
     /// Creates a new empty registry.
     pub fn new() -> Self {
         YBotRegistry {
-            bots: HashMap::new(),
+            bots: BTreeMap::new(),
         }
     }
 
@@ -51,7 +51,7 @@ impl YBotRegistry {
         self.bots.get(name).cloned()
     }
 
-    /// Returns a list of all registered bot names.
+    /// Returns the names of all registered bots, sorted by name.
     pub fn names(&self) -> Vec<String> {
         self.bots.keys().cloned().collect()
     }
@@ -66,7 +66,8 @@ impl Default for YBotRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::{Coordinates, GameY, RandomBot};
+    use crate::RandomBot;
+    use gamey_core::{Coordinates, GameY};
 
     /// A mock bot for testing purposes.
     struct MockBot {
@@ -91,6 +92,12 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_default_explanation_is_none() {
+        let bot = MockBot::new("mock");
+        assert!(bot.explain_move(&GameY::new(3), Coordinates::new(2, 0, 0)).is_none());
+    }
+
     #[test]
     fn test_new_registry_is_empty() {
         let registry = YBotRegistry::new();
@@ -144,4 +151,17 @@ mod tests {
 
         assert_eq!(registry.names().len(), 1);
     }
+
+    #[test]
+    fn test_names_are_sorted() {
+        let registry = YBotRegistry::new()
+            .with_bot(Arc::new(MockBot::new("mcts_bot")))
+            .with_bot(Arc::new(MockBot::new("alpha_bot")))
+            .with_bot(Arc::new(MockBot::new("random_bot")));
+
+        assert_eq!(
+            registry.names(),
+            vec!["alpha_bot", "mcts_bot", "random_bot"]
+        );
+    }
 }
@@ -0,0 +1,141 @@
+//! Settings of the terminal game, read from `config.toml` in the
+//! [`config_dir`].
+//!
+//! Every setting is optional and falls back to its default, so a missing
+//! file plays like an empty one.
+//!
+//! ```toml
+//! # Tell the player when the opponent has moved, e.g. after a long search
+//! [notifications]
+//! bell = true
+//! # A desktop notification, with the `notify` feature
+//! desktop = true
+//! ```
+
+use crate::config_dir;
+use gamey_core::GameYError;
+use serde::Deserialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// The settings of the terminal game.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct CliConfig {
+    /// How the player is told that the opponent has moved.
+    pub notifications: Notifications,
+}
+
+impl CliConfig {
+    /// Parses a configuration in the format of `config.toml`.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Reads a configuration file.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be read and
+    /// `GameYError::InvalidConfig` if it is not a valid configuration.
+    pub fn load(file: &Path) -> Result<Self, GameYError> {
+        let text = std::fs::read_to_string(file).map_err(|e| GameYError::IoError {
+            message: format!("Failed to read {}", file.display()),
+            error: e.to_string(),
+        })?;
+        CliConfig::parse(&text).map_err(|e| GameYError::InvalidConfig {
+            file: file.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Reads the [`config_file`] if there is one, falling back to the
+    /// defaults with a warning if it cannot be read.
+    pub fn load_default() -> Self {
+        let file = config_file();
+        if !file.is_file() {
+            return CliConfig::default();
+        }
+        let config = CliConfig::load(&file).unwrap_or_else(|e| {
+            tracing::warn!("Ignoring the settings: {}", e);
+            CliConfig::default()
+        });
+        if config.notifications.desktop && !cfg!(feature = "notify") {
+            tracing::warn!("Desktop notifications need gamey built with the notify feature");
+        }
+        config
+    }
+}
+
+/// Returns the file the settings of the terminal game are read from.
+pub fn config_file() -> PathBuf {
+    config_dir().join("config.toml")
+}
+
+/// How the player is told that the opponent has moved; nothing by default.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Notifications {
+    /// Ring the terminal bell.
+    pub bell: bool,
+    /// Show a desktop notification, with the `notify` feature.
+    pub desktop: bool,
+}
+
+impl Notifications {
+    /// Tells the player that the opponent has moved, `text` saying how.
+    pub fn opponent_moved(&self, text: &str) {
+        if self.bell {
+            print!("\x07");
+            let _ = std::io::stdout().flush();
+        }
+        if self.desktop {
+            show_desktop_notification(text);
+        }
+    }
+}
+
+#[cfg(feature = "notify")]
+fn show_desktop_notification(text: &str) {
+    let shown = notify_rust::Notification::new()
+        .summary("gamey")
+        .body(text)
+        .show();
+    if let Err(e) = shown {
+        tracing::warn!("Cannot show a desktop notification: {}", e);
+    }
+}
+
+#[cfg(not(feature = "notify"))]
+fn show_desktop_notification(_text: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_are_off_by_default() {
+        assert_eq!(CliConfig::parse("").unwrap(), CliConfig::default());
+        assert_eq!(
+            CliConfig::default().notifications,
+            Notifications {
+                bell: false,
+                desktop: false
+            }
+        );
+        let config = CliConfig::parse("[notifications]\nbell = true\n").unwrap();
+        assert!(config.notifications.bell);
+        assert!(!config.notifications.desktop);
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings() {
+        assert!(CliConfig::parse("[notifications]\nbeep = true\n").is_err());
+        assert!(CliConfig::parse("[notifications]\nbell = \"yes\"\n").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config.toml");
+        std::fs::write(&file, "lang = \"es\"").unwrap();
+        let err = CliConfig::load(&file).unwrap_err();
+        assert!(matches!(err, GameYError::InvalidConfig { .. }));
+    }
+}
@@ -0,0 +1,163 @@
+//! Browsing the moves of the game in the interactive CLI.
+//!
+//! `history` lists the moves played so far, `show N` displays the board as
+//! it was after move N, and `back`/`forward` step through the game one move
+//! at a time. Browsing only changes what is displayed: the live game is not
+//! modified, and playing a move returns to the live position.
+
+use gamey_core::{GameY, Movement};
+
+/// Which position of the game the CLI displays.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct HistoryView {
+    /// The number of moves of the displayed position, `None` for the live game.
+    shown: Option<usize>,
+}
+
+impl HistoryView {
+    /// Returns the number of moves of the displayed past position, or `None`
+    /// when the live game is displayed.
+    pub fn shown(&self) -> Option<usize> {
+        self.shown
+    }
+
+    /// Displays the position after move `number` of `game`; the last move
+    /// or beyond displays the live game.
+    pub fn show(&mut self, game: &GameY, number: usize) {
+        self.shown = (number < game.move_count()).then_some(number);
+    }
+
+    /// Steps one move back, stopping at the start of the game.
+    pub fn back(&mut self, game: &GameY) {
+        let current = self.shown.unwrap_or(game.move_count());
+        self.show(game, current.saturating_sub(1));
+    }
+
+    /// Steps one move forward, up to the live game.
+    pub fn forward(&mut self, game: &GameY) {
+        if let Some(current) = self.shown {
+            self.show(game, current + 1);
+        }
+    }
+
+    /// Displays the live game again.
+    pub fn live(&mut self) {
+        self.shown = None;
+    }
+}
+
+/// Returns one line per move of `game`, with its number, the player and the
+/// move in cell-index notation followed by its coordinates. Glyphs are
+/// appended to the move they judge, and comments follow the move they were
+/// made after, in braces.
+pub fn history_lines(game: &GameY) -> Vec<String> {
+    let annotations = |number: usize| {
+        game.annotations()
+            .iter()
+            .filter(move |annotation| annotation.move_number == number)
+    };
+    let notes = |number: usize| {
+        annotations(number)
+            .filter(|annotation| !annotation.text.is_empty())
+            .map(|annotation| format!("     {{{}}}", annotation.text))
+    };
+    let mut lines: Vec<String> = notes(0).collect();
+    for (i, movement) in game.history().iter().enumerate() {
+        let glyphs: String = annotations(i + 1)
+            .filter_map(|annotation| annotation.glyph)
+            .map(|glyph| glyph.to_string())
+            .collect();
+        let notation = match movement {
+            Movement::Placement { player, coords } => {
+                let index = game.cell_index(*coords);
+                format!("{}: {}{} {}", player, index, glyphs, coords)
+            }
+            Movement::Action { player, action } => {
+                let action = action.to_string().to_lowercase();
+                format!("{}: {}{}", player, action, glyphs)
+            }
+        };
+        lines.push(format!("{:>3}. {}", i + 1, notation));
+        lines.extend(notes(i + 1));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{GameAction, Glyph, PlayerId};
+
+    fn game() -> GameY {
+        let mut game = GameY::new(3);
+        for index in [0, 1] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game.add_move(Movement::Action {
+            player: PlayerId::new(0),
+            action: GameAction::Resign,
+        })
+        .unwrap();
+        game
+    }
+
+    #[test]
+    fn test_history_lines() {
+        assert_eq!(
+            history_lines(&game()),
+            vec![
+                "  1. 0: 0 (2, 0, 0)",
+                "  2. 1: 1 (1, 0, 1)",
+                "  3. 0: resign"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_lines_show_annotations() {
+        let mut game = game();
+        game.take_back(PlayerId::new(1)).unwrap();
+        assert_eq!(
+            history_lines(&game),
+            vec![
+                "  1. 0: 0 (2, 0, 0)",
+                "     {Takeback: player 1 took back 2 move(s)}"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_history_lines_show_glyphs() {
+        let mut game = game().position_after(1).unwrap();
+        game.add_annotation(Some(Glyph::Brilliant), "");
+        game.add_annotation(None, "Takes the corner");
+        assert_eq!(
+            history_lines(&game),
+            vec!["  1. 0: 0!! (2, 0, 0)", "     {Takes the corner}"]
+        );
+    }
+
+    #[test]
+    fn test_browsing() {
+        let game = game();
+        let mut view = HistoryView::default();
+        view.forward(&game);
+        assert_eq!(view.shown(), None);
+        view.back(&game);
+        assert_eq!(view.shown(), Some(2));
+        view.show(&game, 0);
+        view.back(&game);
+        assert_eq!(view.shown(), Some(0));
+        view.forward(&game);
+        view.forward(&game);
+        assert_eq!(view.shown(), Some(2));
+        view.forward(&game);
+        assert_eq!(view.shown(), None);
+        view.show(&game, 1);
+        view.live();
+        assert_eq!(view.shown(), None);
+    }
+}
@@ -0,0 +1,972 @@
+//! Translations of the user-facing messages of the CLI.
+//!
+//! Every message the interactive game prints is a [`Message`], rendered in
+//! the [`Lang`] chosen with `--lang`. Errors coming from the engine keep
+//! their English text and are only wrapped in a translated sentence.
+
+use clap::ValueEnum;
+use gamey_bots::{MoveStats, SearchProgress};
+use std::fmt::Display;
+
+/// A language the CLI can print its messages in.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq, Default)]
+pub enum Lang {
+    /// English.
+    #[default]
+    En,
+    /// Spanish.
+    Es,
+}
+
+impl Display for Lang {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Lang::En => "en",
+            Lang::Es => "es",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl Lang {
+    /// Returns true if `answer` means yes in this language; the English
+    /// answers are accepted in every language.
+    pub fn is_yes(&self, answer: &str) -> bool {
+        let answer = answer.trim().to_lowercase();
+        let spanish = *self == Lang::Es && ["s", "si", "sí"].contains(&answer.as_str());
+        spanish || ["y", "yes"].contains(&answer.as_str())
+    }
+}
+
+/// A user-facing message of the CLI, with the values it mentions.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Message<'a> {
+    /// The requested bot is not registered.
+    BotNotFound {
+        /// The requested bot.
+        bot: &'a str,
+        /// The names of the registered bots.
+        available: &'a [String],
+    },
+    /// The command line uses a form of an earlier version.
+    DeprecatedUsage {
+        /// The deprecated form.
+        old: &'a str,
+        /// The form to use instead.
+        new: &'a str,
+    },
+    /// The game ended.
+    GameOver {
+        /// The winning player.
+        winner: String,
+    },
+    /// The game is still going on after the last move.
+    NextPlayer {
+        /// The player to move.
+        player: String,
+    },
+    /// A move of a scripted game could not be played.
+    MoveRejected {
+        /// The 1-based position of the move in the script.
+        number: usize,
+        /// The move as written in the script.
+        token: &'a str,
+        /// Why the move was rejected.
+        error: String,
+    },
+    /// A move was given after the end of the game.
+    AlreadyOver,
+    /// The prompt asking the current player for an action.
+    Prompt {
+        /// The player to move.
+        player: String,
+    },
+    /// The user pressed Ctrl-C.
+    Interrupted,
+    /// Reading the input line failed.
+    ReadError {
+        /// What went wrong.
+        error: String,
+    },
+    /// The user left the game.
+    Exiting,
+    /// The user entered an empty line.
+    NoCommand,
+    /// The input could not be parsed as a command.
+    ParseError {
+        /// Why parsing failed.
+        error: String,
+    },
+    /// A command that needs a file name was given none.
+    FilenameRequired {
+        /// The command, e.g. "save".
+        command: &'a str,
+    },
+    /// A cell index is not a number.
+    InvalidIndex,
+    /// A cell index is past the last cell.
+    IndexOutOfBounds {
+        /// The index entered.
+        index: u32,
+        /// The largest valid index.
+        max: u32,
+    },
+    /// The place command was given no cell.
+    CellRequired,
+    /// A cell written as `x,y,z` is not three numbers.
+    InvalidCoords,
+    /// A cell written as `x,y,z` is not on the board.
+    CoordsOffBoard {
+        /// The coordinates entered.
+        coords: &'a str,
+    },
+    /// A cell written by name, such as `b3`, is not on the board.
+    InvalidCellName {
+        /// The name entered.
+        name: &'a str,
+    },
+    /// A move of the user was rejected by the engine.
+    MoveError {
+        /// The engine error.
+        error: String,
+    },
+    /// A resignation was rejected by the engine.
+    ResignError {
+        /// The engine error.
+        error: String,
+    },
+    /// A move of the bot was rejected by the engine.
+    BotMoveError {
+        /// The engine error.
+        error: String,
+    },
+    /// The bot found no move to play.
+    NoBotMoves,
+    /// The bot has played, as told by a notification.
+    BotMoved {
+        /// The name of the bot.
+        bot: &'a str,
+        /// The name of the cell it played.
+        cell: String,
+    },
+    /// The bot failed to answer and is asked again.
+    BotRetry {
+        /// The number of this retry, from 1.
+        attempt: u32,
+        /// The number of retries allowed.
+        retries: u32,
+    },
+    /// The bot kept failing, so another bot plays the move.
+    BotFallback {
+        /// The name of the bot playing instead.
+        fallback: &'a str,
+    },
+    /// The bot is still searching.
+    Thinking {
+        /// The progress reported by the search.
+        progress: &'a SearchProgress,
+    },
+    /// How the bot chose its last move.
+    BotStats {
+        /// The statistics reported by the bot.
+        stats: &'a MoveStats,
+    },
+    /// The move the bot suggests.
+    BotWouldPlay {
+        /// The name of the bot.
+        bot: &'a str,
+        /// The suggested cell.
+        coords: String,
+    },
+    /// The bot does not explain its moves.
+    NoExplanation {
+        /// The name of the bot.
+        bot: &'a str,
+    },
+    /// The solver result when the player to move cannot win.
+    WinsWithPerfectPlay {
+        /// The player who wins.
+        player: String,
+    },
+    /// The solver result when the player to move wins.
+    WinningMoves {
+        /// The player who wins.
+        player: String,
+        /// The winning moves, already formatted.
+        moves: String,
+    },
+    /// A YEN file passed validation.
+    FileValid {
+        /// The file name.
+        file: String,
+    },
+    /// A YEN file failed validation.
+    FileInvalid {
+        /// The file name.
+        file: String,
+        /// How many problems were found.
+        problems: usize,
+    },
+    /// No move has been played, so there is no history to list.
+    NoMovesYet,
+    /// A past position is displayed instead of the live game.
+    ViewingMove {
+        /// The number of moves of the displayed position.
+        number: usize,
+        /// The number of moves of the live game.
+        total: usize,
+    },
+    /// The `show` command was given no move number.
+    MoveNumberRequired,
+    /// The `eval` command was given something else than `on` or `off`.
+    EvalArgument,
+    /// The estimate of who is ahead in the displayed position.
+    Evaluation {
+        /// The evaluation bar and the win rates.
+        evaluation: String,
+    },
+    /// The `show` command was given a move that was not played.
+    MoveNumberOutOfRange {
+        /// The move number entered.
+        number: usize,
+        /// The number of moves of the live game.
+        total: usize,
+    },
+    /// A player asks the opponent to take back the last move pair.
+    TakebackPrompt {
+        /// The player asking.
+        requester: String,
+        /// The player who has to answer.
+        opponent: String,
+    },
+    /// The opponent declined the takeback.
+    TakebackDeclined,
+    /// Teaching mode: the move was played after the end of the game.
+    TeachGameOver,
+    /// Teaching mode: the cell is not on the board.
+    TeachOffBoard {
+        /// The largest cell index.
+        max: u32,
+    },
+    /// Teaching mode: the move was made out of turn.
+    TeachNotYourTurn {
+        /// The player to move.
+        player: String,
+    },
+    /// Teaching mode: the cell already has a stone.
+    TeachOccupied {
+        /// The name of the cell.
+        cell: String,
+    },
+    /// Teaching mode: the cell is blocked by the variant.
+    TeachBlocked {
+        /// The name of the cell.
+        cell: String,
+    },
+    /// Teaching mode: a placement made a bridge.
+    TeachBridge {
+        /// The player who placed the stone.
+        player: String,
+        /// The names of the stone placed and of the stone it bridges to.
+        cells: [String; 2],
+        /// The names of the two empty cells both stones touch.
+        links: [String; 2],
+    },
+    /// Teaching mode: a chain reached new goal sides.
+    TeachSide {
+        /// The player owning the chain.
+        player: String,
+        /// The names of the sides reached.
+        sides_reached: String,
+        /// The goal sides the chain touches now.
+        touched: usize,
+        /// The goal sides a chain has to touch to win.
+        sides: usize,
+    },
+    /// Teaching mode: asks before a move that lets the opponent win at once.
+    ConfirmGiveaway {
+        /// The name of the cell where the opponent would win.
+        cell: String,
+    },
+    /// Asks before exiting a game with unsaved moves.
+    ConfirmExit,
+    /// Asks before loading a game over one with unsaved moves.
+    ConfirmLoad {
+        /// The file to load.
+        file: &'a str,
+    },
+    /// Asks before writing over an existing file.
+    ConfirmOverwrite {
+        /// The existing file.
+        file: &'a str,
+    },
+    /// A command was not confirmed and nothing was done.
+    Cancelled,
+    /// Asks for a file to save an interrupted game with unsaved moves to.
+    SaveOnInterrupt,
+    /// The game was saved to a file.
+    GameSaved {
+        /// The file written.
+        file: &'a str,
+    },
+    /// Saving the game failed.
+    SaveError {
+        /// Why saving failed.
+        error: String,
+    },
+    /// The bot accepts every takeback.
+    BotAcceptsTakeback {
+        /// The name of the bot.
+        bot: &'a str,
+    },
+    /// Moves were taken back.
+    TakenBack {
+        /// How many moves were taken back.
+        moves: usize,
+    },
+    /// The takeback could not be done.
+    TakebackError {
+        /// Why the takeback failed.
+        error: String,
+    },
+    /// The `comment` command was given neither a glyph nor a text.
+    CommentRequired,
+    /// One line of the list of openings.
+    OpeningSummary {
+        /// The name of the opening.
+        name: &'a str,
+        /// The board size of the opening.
+        size: u32,
+        /// The number of moves of the opening.
+        moves: usize,
+        /// What the opening is about.
+        description: &'a str,
+    },
+    /// No opening has the requested name.
+    OpeningNotFound {
+        /// The requested opening.
+        name: &'a str,
+    },
+    /// An opening with that name is already in the library.
+    OpeningExists {
+        /// The name of the new opening.
+        name: &'a str,
+    },
+    /// Built-in openings cannot be removed.
+    OpeningIsBuiltin {
+        /// The opening to remove.
+        name: &'a str,
+    },
+    /// A user opening was added to the library.
+    OpeningAdded {
+        /// The name of the opening.
+        name: &'a str,
+        /// The file holding the user openings.
+        file: String,
+    },
+    /// A user opening was removed from the library.
+    OpeningRemoved {
+        /// The name of the opening.
+        name: &'a str,
+    },
+    /// The list of commands of the interactive game.
+    Help,
+}
+
+impl Message<'_> {
+    /// Returns the message in the given language.
+    pub fn text(&self, lang: Lang) -> String {
+        match (self, lang) {
+            (Message::BotNotFound { bot, available }, Lang::En) => {
+                format!("Bot '{}' not found. Available bots: {:?}", bot, available)
+            }
+            (Message::BotNotFound { bot, available }, Lang::Es) => {
+                format!(
+                    "No existe el bot '{}'. Bots disponibles: {:?}",
+                    bot, available
+                )
+            }
+            (Message::DeprecatedUsage { old, new }, Lang::En) => {
+                format!("Warning: `{}` is deprecated, use `{}` instead", old, new)
+            }
+            (Message::DeprecatedUsage { old, new }, Lang::Es) => {
+                format!("Aviso: `{}` está obsoleto, usa `{}` en su lugar", old, new)
+            }
+            (Message::GameOver { winner }, Lang::En) => format!("Game over! Winner: {}", winner),
+            (Message::GameOver { winner }, Lang::Es) => {
+                format!("¡Fin de la partida! Ganador: {}", winner)
+            }
+            (Message::NextPlayer { player }, Lang::En) => {
+                format!("Game in progress, next player: {}", player)
+            }
+            (Message::NextPlayer { player }, Lang::Es) => {
+                format!("Partida en curso, siguiente jugador: {}", player)
+            }
+            (
+                Message::MoveRejected {
+                    number,
+                    token,
+                    error,
+                },
+                Lang::En,
+            ) => format!("Move {} ({}) rejected: {}", number, token, error),
+            (
+                Message::MoveRejected {
+                    number,
+                    token,
+                    error,
+                },
+                Lang::Es,
+            ) => format!("Movimiento {} ({}) rechazado: {}", number, token, error),
+            (Message::AlreadyOver, Lang::En) => "the game is already over".to_string(),
+            (Message::AlreadyOver, Lang::Es) => "la partida ya ha terminado".to_string(),
+            (Message::Prompt { player }, Lang::En) => {
+                format!(
+                    "Current player: {}, action (help = show commands)? ",
+                    player
+                )
+            }
+            (Message::Prompt { player }, Lang::Es) => {
+                format!(
+                    "Jugador actual: {}, ¿acción? (help = ver comandos) ",
+                    player
+                )
+            }
+            (Message::Interrupted, Lang::En) => "Interrupted".to_string(),
+            (Message::Interrupted, Lang::Es) => "Interrumpido".to_string(),
+            (Message::ReadError { error }, Lang::En) => format!("Error: {}", error),
+            (Message::ReadError { error }, Lang::Es) => format!("Error: {}", error),
+            (Message::Exiting, Lang::En) => "Exiting the game.".to_string(),
+            (Message::Exiting, Lang::Es) => "Saliendo de la partida.".to_string(),
+            (Message::NoCommand, Lang::En) => "No command entered.".to_string(),
+            (Message::NoCommand, Lang::Es) => "No se ha introducido ningún comando.".to_string(),
+            (Message::ParseError { error }, Lang::En) => {
+                format!("Error parsing command: {}", error)
+            }
+            (Message::ParseError { error }, Lang::Es) => {
+                format!("Error al interpretar el comando: {}", error)
+            }
+            (Message::FilenameRequired { command }, Lang::En) => {
+                format!("Filename required for {} command", command)
+            }
+            (Message::FilenameRequired { command }, Lang::Es) => {
+                format!("El comando {} necesita un nombre de fichero", command)
+            }
+            (Message::InvalidIndex, Lang::En) => "Invalid index (not a number)".to_string(),
+            (Message::InvalidIndex, Lang::Es) => "Índice no válido (no es un número)".to_string(),
+            (Message::IndexOutOfBounds { index, max }, Lang::En) => {
+                format!("Index out of bounds: {} > {}", index, max)
+            }
+            (Message::IndexOutOfBounds { index, max }, Lang::Es) => {
+                format!("Índice fuera del tablero: {} > {}", index, max)
+            }
+            (Message::CellRequired, Lang::En) => {
+                "Cell required for place command, e.g. place 4, place 2,1,1 or place b3".to_string()
+            }
+            (Message::CellRequired, Lang::Es) => {
+                "El comando place necesita una casilla, p. ej. place 4, place 2,1,1 o place b3"
+                    .to_string()
+            }
+            (Message::InvalidCoords, Lang::En) => {
+                "Invalid coordinates (expected three numbers x,y,z)".to_string()
+            }
+            (Message::InvalidCoords, Lang::Es) => {
+                "Coordenadas no válidas (se esperaban tres números x,y,z)".to_string()
+            }
+            (Message::CoordsOffBoard { coords }, Lang::En) => {
+                format!("Coordinates off the board: {}", coords)
+            }
+            (Message::CoordsOffBoard { coords }, Lang::Es) => {
+                format!("Coordenadas fuera del tablero: {}", coords)
+            }
+            (Message::InvalidCellName { name }, Lang::En) => format!(
+                "Invalid cell name {} (expected a column letter and a row number on the board, e.g. a1)",
+                name
+            ),
+            (Message::InvalidCellName { name }, Lang::Es) => format!(
+                "Nombre de casilla no válido {} (se esperaba la letra de la columna y el número de la fila en el tablero, p. ej. a1)",
+                name
+            ),
+            (Message::MoveError { error }, Lang::En) => format!("Error adding move: {}", error),
+            (Message::MoveError { error }, Lang::Es) => {
+                format!("Error al añadir el movimiento: {}", error)
+            }
+            (Message::ResignError { error }, Lang::En) => {
+                format!("Error adding resign move: {}", error)
+            }
+            (Message::ResignError { error }, Lang::Es) => {
+                format!("Error al abandonar la partida: {}", error)
+            }
+            (Message::BotMoveError { error }, Lang::En) => {
+                format!("Error adding bot move: {}", error)
+            }
+            (Message::BotMoveError { error }, Lang::Es) => {
+                format!("Error al añadir el movimiento del bot: {}", error)
+            }
+            (Message::Thinking { progress }, Lang::En) => {
+                let best = progress
+                    .best_move
+                    .map_or("-".to_string(), |c| c.to_string());
+                format!(
+                    "Thinking... {} iterations, depth {}, best so far {}",
+                    progress.iterations, progress.depth, best
+                )
+            }
+            (Message::Thinking { progress }, Lang::Es) => {
+                let best = progress
+                    .best_move
+                    .map_or("-".to_string(), |c| c.to_string());
+                format!(
+                    "Pensando... {} iteraciones, profundidad {}, mejor hasta ahora {}",
+                    progress.iterations, progress.depth, best
+                )
+            }
+            (Message::BotStats { stats }, Lang::En) => {
+                let mut text = format!("Bot stats: {:.2}s", stats.elapsed.as_secs_f64());
+                if let Some(nodes) = stats.nodes {
+                    text.push_str(&format!(", {} nodes", nodes));
+                }
+                if let (Some(before), Some(after)) = (stats.eval_before, stats.eval_after) {
+                    let (before, after) = (100.0 * before, 100.0 * after);
+                    text.push_str(&format!(", evaluation {:.1}% -> {:.1}%", before, after));
+                }
+                text
+            }
+            (Message::BotStats { stats }, Lang::Es) => {
+                let mut text = format!("Estadísticas del bot: {:.2}s", stats.elapsed.as_secs_f64());
+                if let Some(nodes) = stats.nodes {
+                    text.push_str(&format!(", {} nodos", nodes));
+                }
+                if let (Some(before), Some(after)) = (stats.eval_before, stats.eval_after) {
+                    let (before, after) = (100.0 * before, 100.0 * after);
+                    text.push_str(&format!(", evaluación {:.1}% -> {:.1}%", before, after));
+                }
+                text
+            }
+            (Message::NoBotMoves, Lang::En) => "No available moves for the bot.".to_string(),
+            (Message::NoBotMoves, Lang::Es) => {
+                "El bot no tiene movimientos disponibles.".to_string()
+            }
+            (Message::BotMoved { bot, cell }, Lang::En) => {
+                format!("{} played {}, your move", bot, cell)
+            }
+            (Message::BotMoved { bot, cell }, Lang::Es) => {
+                format!("{} jugó {}, te toca", bot, cell)
+            }
+            (Message::BotRetry { attempt, retries }, Lang::En) => {
+                format!("The bot did not answer, retrying ({}/{})", attempt, retries)
+            }
+            (Message::BotRetry { attempt, retries }, Lang::Es) => {
+                format!(
+                    "El bot no respondió, reintentando ({}/{})",
+                    attempt, retries
+                )
+            }
+            (Message::BotFallback { fallback }, Lang::En) => {
+                format!("The bot keeps failing, {} plays instead", fallback)
+            }
+            (Message::BotFallback { fallback }, Lang::Es) => {
+                format!("El bot sigue fallando, juega {} en su lugar", fallback)
+            }
+            (Message::BotWouldPlay { bot, coords }, Lang::En) => {
+                format!("{} would play {}", bot, coords)
+            }
+            (Message::BotWouldPlay { bot, coords }, Lang::Es) => {
+                format!("{} jugaría {}", bot, coords)
+            }
+            (Message::NoExplanation { bot }, Lang::En) => format!("({} gives no explanation)", bot),
+            (Message::NoExplanation { bot }, Lang::Es) => {
+                format!("({} no explica sus jugadas)", bot)
+            }
+            (Message::WinsWithPerfectPlay { player }, Lang::En) => {
+                format!("Player {} wins with perfect play", player)
+            }
+            (Message::WinsWithPerfectPlay { player }, Lang::Es) => {
+                format!("El jugador {} gana con juego perfecto", player)
+            }
+            (Message::WinningMoves { player, moves }, Lang::En) => {
+                format!(
+                    "Player {} wins with perfect play; winning moves: {}",
+                    player, moves
+                )
+            }
+            (Message::WinningMoves { player, moves }, Lang::Es) => {
+                format!(
+                    "El jugador {} gana con juego perfecto; jugadas ganadoras: {}",
+                    player, moves
+                )
+            }
+            (Message::FileValid { file }, Lang::En) => format!("{}: ok", file),
+            (Message::FileValid { file }, Lang::Es) => format!("{}: correcto", file),
+            (Message::FileInvalid { file, problems }, Lang::En) => {
+                format!("{}: {} problem(s)", file, problems)
+            }
+            (Message::FileInvalid { file, problems }, Lang::Es) => {
+                format!("{}: {} problema(s)", file, problems)
+            }
+            (Message::NoMovesYet, Lang::En) => "No moves have been played yet.".to_string(),
+            (Message::NoMovesYet, Lang::Es) => {
+                "Todavía no se ha jugado ningún movimiento.".to_string()
+            }
+            (Message::ViewingMove { number, total }, Lang::En) => format!(
+                "Viewing move {} of {} (back/forward to browse, a new move returns to the game)",
+                number, total
+            ),
+            (Message::ViewingMove { number, total }, Lang::Es) => format!(
+                "Viendo el movimiento {} de {} (back/forward para navegar, un nuevo movimiento vuelve a la partida)",
+                number, total
+            ),
+            (Message::EvalArgument, Lang::En) => "The eval command takes on or off".to_string(),
+            (Message::EvalArgument, Lang::Es) => "El comando eval admite on u off".to_string(),
+            (Message::Evaluation { evaluation }, Lang::En) => {
+                format!("Evaluation (random fills): {}", evaluation)
+            }
+            (Message::Evaluation { evaluation }, Lang::Es) => {
+                format!("Evaluación (rellenos al azar): {}", evaluation)
+            }
+            (Message::MoveNumberRequired, Lang::En) => {
+                "The show command needs a move number".to_string()
+            }
+            (Message::MoveNumberRequired, Lang::Es) => {
+                "El comando show necesita un número de movimiento".to_string()
+            }
+            (Message::MoveNumberOutOfRange { number, total }, Lang::En) => {
+                format!(
+                    "Move {} has not been played: the game has {} moves",
+                    number, total
+                )
+            }
+            (Message::MoveNumberOutOfRange { number, total }, Lang::Es) => {
+                format!(
+                    "El movimiento {} no se ha jugado: la partida tiene {} movimientos",
+                    number, total
+                )
+            }
+            (
+                Message::TakebackPrompt {
+                    requester,
+                    opponent,
+                },
+                Lang::En,
+            ) => format!(
+                "Player {} asks to take back their last move. Player {}, do you accept? (y/n) ",
+                requester, opponent
+            ),
+            (
+                Message::TakebackPrompt {
+                    requester,
+                    opponent,
+                },
+                Lang::Es,
+            ) => format!(
+                "El jugador {} pide deshacer su última jugada. Jugador {}, ¿aceptas? (s/n) ",
+                requester, opponent
+            ),
+            (Message::TakebackDeclined, Lang::En) => "Takeback declined.".to_string(),
+            (Message::TakebackDeclined, Lang::Es) => "Deshacer rechazado.".to_string(),
+            (Message::TeachGameOver, Lang::En) => {
+                "The game is over: once a chain connects the goal sides, no more stones are placed."
+                    .to_string()
+            }
+            (Message::TeachGameOver, Lang::Es) => {
+                "La partida ha terminado: cuando una cadena une los lados, no se colocan más piedras."
+                    .to_string()
+            }
+            (Message::TeachOffBoard { max }, Lang::En) => format!(
+                "That cell is not on the board: cells go from 0 to {}; show_idx shows them.",
+                max
+            ),
+            (Message::TeachOffBoard { max }, Lang::Es) => format!(
+                "Esa casilla no está en el tablero: van de 0 a {}; show_idx las muestra.",
+                max
+            ),
+            (Message::TeachNotYourTurn { player }, Lang::En) => format!(
+                "It is player {}'s turn: the players take turns, one stone each.",
+                player
+            ),
+            (Message::TeachNotYourTurn { player }, Lang::Es) => format!(
+                "Le toca al jugador {}: los jugadores se turnan, una piedra cada uno.",
+                player
+            ),
+            (Message::TeachOccupied { cell }, Lang::En) => format!(
+                "{} already has a stone: stones never move nor are captured, so play on an empty cell.",
+                cell
+            ),
+            (Message::TeachOccupied { cell }, Lang::Es) => format!(
+                "{} ya tiene una piedra: las piedras no se mueven ni se capturan, así que juega en una casilla vacía.",
+                cell
+            ),
+            (Message::TeachBlocked { cell }, Lang::En) => format!(
+                "{} is blocked by the rules of this variant: nobody can play there.",
+                cell
+            ),
+            (Message::TeachBlocked { cell }, Lang::Es) => format!(
+                "{} está bloqueada por las reglas de esta variante: nadie puede jugar ahí.",
+                cell
+            ),
+            (Message::TeachBridge { player, cells, links }, Lang::En) => format!(
+                "Bridge: player {} linked {} and {} through {} and {}. If the opponent takes one, take the other to stay connected.",
+                player, cells[0], cells[1], links[0], links[1]
+            ),
+            (Message::TeachBridge { player, cells, links }, Lang::Es) => format!(
+                "Puente: el jugador {} enlazó {} y {} a través de {} y {}. Si el rival ocupa una, ocupa la otra para seguir conectado.",
+                player, cells[0], cells[1], links[0], links[1]
+            ),
+            (
+                Message::TeachSide {
+                    player,
+                    sides_reached,
+                    touched,
+                    sides,
+                },
+                Lang::En,
+            ) => format!(
+                "Player {} reached {}: the chain touches {} of the {} sides it needs to win.",
+                player, sides_reached, touched, sides
+            ),
+            (
+                Message::TeachSide {
+                    player,
+                    sides_reached,
+                    touched,
+                    sides,
+                },
+                Lang::Es,
+            ) => format!(
+                "El jugador {} alcanzó {}: la cadena toca {} de los {} lados que necesita para ganar.",
+                player, sides_reached, touched, sides
+            ),
+            (Message::ConfirmGiveaway { cell }, Lang::En) => format!(
+                "After this move the opponent wins at once at {}. Play it anyway? (y/n) ",
+                cell
+            ),
+            (Message::ConfirmGiveaway { cell }, Lang::Es) => format!(
+                "Tras este movimiento el rival gana de inmediato en {}. ¿Jugarlo de todos modos? (s/n) ",
+                cell
+            ),
+            (Message::ConfirmExit, Lang::En) => {
+                "The game has unsaved moves. Exit anyway? (y/n) ".to_string()
+            }
+            (Message::ConfirmExit, Lang::Es) => {
+                "La partida tiene movimientos sin guardar. ¿Salir de todos modos? (s/n) "
+                    .to_string()
+            }
+            (Message::ConfirmLoad { file }, Lang::En) => format!(
+                "Loading {} discards the unsaved moves of this game. Load anyway? (y/n) ",
+                file
+            ),
+            (Message::ConfirmLoad { file }, Lang::Es) => format!(
+                "Cargar {} descarta los movimientos sin guardar de esta partida. ¿Cargar de todos modos? (s/n) ",
+                file
+            ),
+            (Message::ConfirmOverwrite { file }, Lang::En) => {
+                format!("{} already exists. Overwrite it? (y/n) ", file)
+            }
+            (Message::ConfirmOverwrite { file }, Lang::Es) => {
+                format!("{} ya existe. ¿Sobrescribirlo? (s/n) ", file)
+            }
+            (Message::Cancelled, Lang::En) => "Cancelled.".to_string(),
+            (Message::Cancelled, Lang::Es) => "Cancelado.".to_string(),
+            (Message::SaveOnInterrupt, Lang::En) => {
+                "The game has unsaved moves. File to save it to (empty to discard them): "
+                    .to_string()
+            }
+            (Message::SaveOnInterrupt, Lang::Es) => {
+                "La partida tiene movimientos sin guardar. Fichero donde guardarla (vacío para descartarlos): "
+                    .to_string()
+            }
+            (Message::GameSaved { file }, Lang::En) => format!("Game saved to {}", file),
+            (Message::GameSaved { file }, Lang::Es) => format!("Partida guardada en {}", file),
+            (Message::SaveError { error }, Lang::En) => format!("Error saving the game: {}", error),
+            (Message::SaveError { error }, Lang::Es) => {
+                format!("Error al guardar la partida: {}", error)
+            }
+            (Message::BotAcceptsTakeback { bot }, Lang::En) => {
+                format!("{} accepts the takeback.", bot)
+            }
+            (Message::BotAcceptsTakeback { bot }, Lang::Es) => {
+                format!("{} acepta deshacer la jugada.", bot)
+            }
+            (Message::TakenBack { moves }, Lang::En) => format!("{} move(s) taken back.", moves),
+            (Message::TakenBack { moves }, Lang::Es) => {
+                format!("Se han deshecho {} movimiento(s).", moves)
+            }
+            (Message::TakebackError { error }, Lang::En) => {
+                format!("Cannot take back: {}", error)
+            }
+            (Message::TakebackError { error }, Lang::Es) => {
+                format!("No se puede deshacer: {}", error)
+            }
+            (Message::CommentRequired, Lang::En) => {
+                "The comment command needs a glyph (! ? !! ?? !? ?!) or a text".to_string()
+            }
+            (Message::CommentRequired, Lang::Es) => {
+                "El comando comment necesita un signo (! ? !! ?? !? ?!) o un texto".to_string()
+            }
+            (
+                Message::OpeningSummary {
+                    name,
+                    size,
+                    moves,
+                    description,
+                },
+                Lang::En,
+            ) => format!(
+                "{:<12} size {}, {} move(s)  {}",
+                name, size, moves, description
+            ),
+            (
+                Message::OpeningSummary {
+                    name,
+                    size,
+                    moves,
+                    description,
+                },
+                Lang::Es,
+            ) => format!(
+                "{:<12} tamaño {}, {} movimiento(s)  {}",
+                name, size, moves, description
+            ),
+            (Message::OpeningNotFound { name }, Lang::En) => {
+                format!("Opening '{}' not found, see `gamey openings`", name)
+            }
+            (Message::OpeningNotFound { name }, Lang::Es) => {
+                format!(
+                    "No existe la apertura '{}', consulta `gamey openings`",
+                    name
+                )
+            }
+            (Message::OpeningExists { name }, Lang::En) => {
+                format!("There is already an opening named '{}'", name)
+            }
+            (Message::OpeningExists { name }, Lang::Es) => {
+                format!("Ya existe una apertura llamada '{}'", name)
+            }
+            (Message::OpeningIsBuiltin { name }, Lang::En) => {
+                format!("'{}' is a built-in opening and cannot be removed", name)
+            }
+            (Message::OpeningIsBuiltin { name }, Lang::Es) => {
+                format!("'{}' es una apertura incluida y no se puede borrar", name)
+            }
+            (Message::OpeningAdded { name, file }, Lang::En) => {
+                format!("Opening '{}' added to {}", name, file)
+            }
+            (Message::OpeningAdded { name, file }, Lang::Es) => {
+                format!("Apertura '{}' añadida a {}", name, file)
+            }
+            (Message::OpeningRemoved { name }, Lang::En) => {
+                format!("Opening '{}' removed", name)
+            }
+            (Message::OpeningRemoved { name }, Lang::Es) => {
+                format!("Apertura '{}' borrada", name)
+            }
+            (Message::Help, Lang::En) => HELP_EN.join("\n"),
+            (Message::Help, Lang::Es) => HELP_ES.join("\n"),
+        }
+    }
+}
+
+const HELP_EN: &[&str] = &[
+    "Available commands:",
+    "  <number>        - Place a piece at the specified index number",
+    "  place <cell>    - Place a piece at a cell given by index, x,y,z or name (b3)",
+    "  r, resign       - Resign from the game",
+    "  takeback        - Ask the opponent to take back your last move",
+    "  comment [g] <t> - Annotate the last move with a glyph g and a text t",
+    "  explain         - Show the bot's suggested move and why",
+    "  solve           - Show who wins with perfect play (small boards)",
+    "  history         - List the moves played so far",
+    "  describe        - Describe the board in words, for screen readers",
+    "  eval [on|off]   - Toggle an estimate of who is ahead after each move",
+    "  show <n>        - Show the board as it was after move n",
+    "  back            - Show the position one move earlier",
+    "  forward         - Show the position one move later",
+    "  show_coords     - Toggle showing coordinates on the board",
+    "  show_idx        - Toggle showing index numbers on the board",
+    "  show_colors     - Toggle showing colors on the board",
+    "  show_labels     - Toggle showing side labels and row/column guides",
+    "  show_last       - Toggle highlighting the last move",
+    "  show_numbers    - Toggle showing move numbers instead of players",
+    "  rotate          - Turn the board to bring another side to the bottom",
+    "  s, save <file>  - Save the current game state to a file",
+    "  l, load <file>  - Load a game state from a file",
+    "  dump [filename] - Print (or write) the full game state as JSON",
+    "  exit            - Exit the game",
+    "  help            - Show this help message",
+];
+
+const HELP_ES: &[&str] = &[
+    "Comandos disponibles:",
+    "  <número>        - Coloca una ficha en la casilla con ese índice",
+    "  place <c>       - Coloca una ficha en la casilla c: índice, x,y,z o nombre (b3)",
+    "  r, resign       - Abandona la partida",
+    "  takeback        - Pide al rival deshacer tu última jugada",
+    "  comment [s] <t> - Anota la última jugada con un signo s y un texto t",
+    "  explain         - Muestra la jugada que sugiere el bot y por qué",
+    "  solve           - Muestra quién gana con juego perfecto (tableros pequeños)",
+    "  history         - Lista los movimientos jugados",
+    "  describe        - Describe el tablero con palabras, para lectores de pantalla",
+    "  eval [on|off]   - Muestra u oculta quién va ganando tras cada jugada",
+    "  show <n>        - Muestra el tablero tras el movimiento n",
+    "  back            - Muestra la posición un movimiento antes",
+    "  forward         - Muestra la posición un movimiento después",
+    "  show_coords     - Muestra u oculta las coordenadas en el tablero",
+    "  show_idx        - Muestra u oculta los índices de las casillas",
+    "  show_colors     - Muestra u oculta los colores del tablero",
+    "  show_labels     - Muestra u oculta los lados y las guías de filas y columnas",
+    "  show_last       - Resalta o no la última jugada",
+    "  show_numbers    - Muestra el número de jugada en lugar del jugador",
+    "  rotate          - Gira el tablero para dejar otro lado abajo",
+    "  s, save <f>     - Guarda la partida en el fichero f",
+    "  l, load <f>     - Carga una partida desde el fichero f",
+    "  dump [fichero]  - Muestra (o escribe) el estado completo en JSON",
+    "  exit            - Sale de la partida",
+    "  help            - Muestra esta ayuda",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_messages_are_translated() {
+        let message = Message::GameOver {
+            winner: "1".to_string(),
+        };
+        assert_eq!(message.text(Lang::En), "Game over! Winner: 1");
+        assert_eq!(message.text(Lang::Es), "¡Fin de la partida! Ganador: 1");
+    }
+
+    #[test]
+    fn test_bot_stats_leave_out_unknown_values() {
+        let mut stats = MoveStats::timed(std::time::Duration::from_millis(1500));
+        let message = Message::BotStats { stats: &stats };
+        assert_eq!(message.text(Lang::En), "Bot stats: 1.50s");
+        stats.nodes = Some(200);
+        stats.eval_before = Some(0.5);
+        stats.eval_after = Some(0.625);
+        let message = Message::BotStats { stats: &stats };
+        assert_eq!(
+            message.text(Lang::En),
+            "Bot stats: 1.50s, 200 nodes, evaluation 50.0% -> 62.5%"
+        );
+    }
+
+    #[test]
+    fn test_yes_answers() {
+        assert!(Lang::En.is_yes("Yes"));
+        assert!(!Lang::En.is_yes("si"));
+        assert!(Lang::Es.is_yes(" sí "));
+        assert!(Lang::Es.is_yes("y"));
+        assert!(!Lang::Es.is_yes("no"));
+    }
+
+    #[test]
+    fn test_help_lists_the_same_commands() {
+        let command = |line: &&str| line.split_whitespace().next().map(str::to_string);
+        let en: Vec<_> = HELP_EN.iter().skip(2).filter_map(command).collect();
+        let es: Vec<_> = HELP_ES.iter().skip(2).filter_map(command).collect();
+        assert_eq!(en, es);
+        assert_eq!(HELP_EN.len(), HELP_ES.len());
+    }
+}
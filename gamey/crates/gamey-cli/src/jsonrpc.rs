@@ -0,0 +1,351 @@
+//! A JSON-RPC 2.0 protocol over stdin and stdout.
+//!
+//! With `serve --jsonrpc` the engine reads one request per line and answers
+//! each with one response line, so graders and GUIs can drive it as a
+//! subprocess without HTTP. The session holds a single game, created from
+//! the board options of the command line and replaced by `new_game`.
+//!
+//! # Methods
+//! - `new_game` - Start a new game; params `size`, and optionally
+//!   `topology`, `ruleset` and `handicap`
+//! - `move` - Play a stone for the player to move; params `coords` or `index`
+//! - `get_state` - Return the current game
+//! - `bot_move` - Let a bot play for the player to move; optional param `bot`
+//!
+//! Every method answers with `{"state": ...}`, a
+//! [`GameStateDump`](gamey_core::GameStateDump) of the game; `bot_move` also
+//! returns the `coords` the bot played. Engine errors carry their
+//! [`ErrorCode`] in the `data` of the error object.
+//!
+//! # Example
+//! ```text
+//! > {"jsonrpc": "2.0", "id": 1, "method": "move", "params": {"index": 4}}
+//! < {"jsonrpc":"2.0","id":1,"result":{"state":{...}}}
+//! ```
+
+use gamey_bots::YBotRegistry;
+use gamey_core::{BoardTopology, Coordinates, ErrorCode, GameY, GameYError, Movement, Ruleset};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use std::io::{BufRead, Write};
+
+/// The request could not be parsed as JSON.
+pub const PARSE_ERROR: i64 = -32700;
+/// The JSON is not a valid request object.
+pub const INVALID_REQUEST: i64 = -32600;
+/// The method does not exist.
+pub const METHOD_NOT_FOUND: i64 = -32601;
+/// The params do not match the method.
+pub const INVALID_PARAMS: i64 = -32602;
+/// The engine rejected the request, see the `data` of the error.
+pub const ENGINE_ERROR: i64 = -32000;
+
+/// A request line of the protocol.
+#[derive(Deserialize, Debug)]
+struct Request {
+    /// The protocol version, always "2.0".
+    jsonrpc: String,
+    /// The identifier echoed in the response; notifications have none.
+    #[serde(default)]
+    id: Value,
+    /// The method to call.
+    method: String,
+    /// The named params of the method.
+    #[serde(default)]
+    params: Value,
+}
+
+/// The error object of a failed call.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RpcError {
+    /// The JSON-RPC error code, e.g. [`METHOD_NOT_FOUND`].
+    pub code: i64,
+    /// A human-readable description of the error.
+    pub message: String,
+    /// For engine errors, the machine-readable [`ErrorCode`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<ErrorCode>,
+}
+
+impl RpcError {
+    fn new(code: i64, message: impl Into<String>) -> Self {
+        RpcError {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+
+    fn invalid_params(err: serde_json::Error) -> Self {
+        RpcError::new(INVALID_PARAMS, format!("Invalid params: {}", err))
+    }
+}
+
+impl From<GameYError> for RpcError {
+    fn from(err: GameYError) -> Self {
+        RpcError {
+            code: ENGINE_ERROR,
+            message: err.to_string(),
+            data: Some(err.code()),
+        }
+    }
+}
+
+/// Params of `new_game`.
+#[derive(Deserialize)]
+struct NewGameParams {
+    size: u32,
+    #[serde(default)]
+    topology: BoardTopology,
+    #[serde(default)]
+    ruleset: Ruleset,
+    #[serde(default)]
+    handicap: u32,
+}
+
+/// Params of `move`.
+#[derive(Deserialize)]
+struct MoveParams {
+    coords: Option<Coordinates>,
+    index: Option<u32>,
+}
+
+/// Params of `bot_move`.
+#[derive(Deserialize)]
+struct BotMoveParams {
+    bot: Option<String>,
+}
+
+/// A game driven through the JSON-RPC protocol.
+pub struct JsonRpcSession {
+    game: GameY,
+    bots: YBotRegistry,
+    default_bot: String,
+}
+
+impl JsonRpcSession {
+    /// Creates a session playing `game`, where `bot_move` uses the bots of
+    /// `bots` and `default_bot` unless the request names one.
+    pub fn new(game: GameY, bots: YBotRegistry, default_bot: &str) -> Self {
+        JsonRpcSession {
+            game,
+            bots,
+            default_bot: default_bot.to_string(),
+        }
+    }
+
+    /// Returns the game of the session.
+    pub fn game(&self) -> &GameY {
+        &self.game
+    }
+
+    /// Handles one request line and returns the response line, or `None`
+    /// for notifications (requests without an `id`) and blank lines.
+    pub fn handle_line(&mut self, line: &str) -> Option<String> {
+        if line.trim().is_empty() {
+            return None;
+        }
+        let value: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(err) => {
+                let error = RpcError::new(PARSE_ERROR, format!("Parse error: {}", err));
+                return Some(response(Value::Null, Err(error)));
+            }
+        };
+        let id = value.get("id").cloned().unwrap_or(Value::Null);
+        let request = match serde_json::from_value::<Request>(value) {
+            Ok(request) if request.jsonrpc == "2.0" => request,
+            Ok(_) => {
+                let error = RpcError::new(INVALID_REQUEST, "Invalid request: jsonrpc must be 2.0");
+                return Some(response(id, Err(error)));
+            }
+            Err(err) => {
+                let error = RpcError::new(INVALID_REQUEST, format!("Invalid request: {}", err));
+                return Some(response(id, Err(error)));
+            }
+        };
+        let result = self.call(&request.method, request.params);
+        (!request.id.is_null()).then(|| response(request.id, result))
+    }
+
+    /// Dispatches a call to the method it names.
+    fn call(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        let params = if params.is_null() { json!({}) } else { params };
+        match method {
+            "new_game" => {
+                let params: NewGameParams =
+                    serde_json::from_value(params).map_err(RpcError::invalid_params)?;
+                GameY::check_board_size(params.size)?;
+                let handicap = GameY::handicap_points(params.size, params.handicap)?;
+                self.game = GameY::with_variant(params.size, params.topology, params.ruleset)
+                    .with_handicap(&handicap)?;
+            }
+            "move" => {
+                let params: MoveParams =
+                    serde_json::from_value(params).map_err(RpcError::invalid_params)?;
+                let coords = match (params.coords, params.index) {
+                    (Some(coords), _) => coords,
+                    (None, Some(index)) if index < self.game.total_cells() => {
+                        self.game.cell_coords(index)
+                    }
+                    (None, Some(index)) => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            format!("Invalid params: index {} is off the board", index),
+                        ));
+                    }
+                    (None, None) => {
+                        return Err(RpcError::new(
+                            INVALID_PARAMS,
+                            "Invalid params: coords or index required",
+                        ));
+                    }
+                };
+                self.play(coords)?;
+            }
+            "get_state" => {}
+            "bot_move" => {
+                let params: BotMoveParams =
+                    serde_json::from_value(params).map_err(RpcError::invalid_params)?;
+                let name = params.bot.unwrap_or_else(|| self.default_bot.clone());
+                let bot = self.bots.find(&name).ok_or_else(|| RpcError {
+                    code: ENGINE_ERROR,
+                    message: format!("Bot not found: {}", name),
+                    data: Some(ErrorCode::BotNotFound),
+                })?;
+                let coords = bot.choose_move(&self.game).ok_or_else(|| RpcError {
+                    code: ENGINE_ERROR,
+                    message: "No valid moves available for the bot".to_string(),
+                    data: Some(ErrorCode::NoMoves),
+                })?;
+                self.play(coords)?;
+                return Ok(json!({ "coords": coords, "state": self.game.to_state() }));
+            }
+            _ => {
+                return Err(RpcError::new(
+                    METHOD_NOT_FOUND,
+                    format!("Method not found: {}", method),
+                ));
+            }
+        }
+        Ok(json!({ "state": self.game.to_state() }))
+    }
+
+    /// Plays a stone for the player to move.
+    fn play(&mut self, coords: Coordinates) -> Result<(), RpcError> {
+        let Some(player) = self.game.next_player() else {
+            return Err(RpcError {
+                code: ENGINE_ERROR,
+                message: "The game is already over".to_string(),
+                data: Some(ErrorCode::GameOver),
+            });
+        };
+        Ok(self.game.add_move(Movement::Placement { player, coords })?)
+    }
+}
+
+/// Serializes the response to a call.
+fn response(id: Value, result: Result<Value, RpcError>) -> String {
+    let body = match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err(error) => json!({ "jsonrpc": "2.0", "id": id, "error": error }),
+    };
+    body.to_string()
+}
+
+/// Serves the protocol until `input` ends, writing one line per response.
+pub fn serve(
+    session: &mut JsonRpcSession,
+    input: impl BufRead,
+    mut output: impl Write,
+) -> std::io::Result<()> {
+    for line in input.lines() {
+        if let Some(response) = session.handle_line(&line?) {
+            writeln!(output, "{}", response)?;
+            output.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::RandomBot;
+    use std::sync::Arc;
+
+    fn session() -> JsonRpcSession {
+        let bots = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        JsonRpcSession::new(GameY::new(3), bots, "random_bot")
+    }
+
+    fn call(session: &mut JsonRpcSession, request: Value) -> Value {
+        let line = session.handle_line(&request.to_string()).unwrap();
+        serde_json::from_str(&line).unwrap()
+    }
+
+    #[test]
+    fn test_play_a_game() {
+        let mut session = session();
+        let new_game =
+            json!({"jsonrpc": "2.0", "id": 1, "method": "new_game", "params": {"size": 4}});
+        let response = call(&mut session, new_game);
+        assert_eq!(response["id"], 1);
+        assert_eq!(response["result"]["state"]["size"], 4);
+
+        let play = json!({"jsonrpc": "2.0", "id": 2, "method": "move", "params": {"index": 0}});
+        let response = call(&mut session, play);
+        assert_eq!(
+            response["result"]["state"]["history"]
+                .as_array()
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let bot = json!({"jsonrpc": "2.0", "id": "b", "method": "bot_move"});
+        let response = call(&mut session, bot);
+        assert_eq!(response["id"], "b");
+        assert!(response["result"]["coords"].is_object());
+        assert_eq!(session.game().history().len(), 2);
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut session = session();
+        let response: Value = serde_json::from_str(&session.handle_line("{nope").unwrap()).unwrap();
+        assert_eq!(response["error"]["code"], PARSE_ERROR);
+
+        let unknown = json!({"jsonrpc": "2.0", "id": 1, "method": "undo"});
+        assert_eq!(
+            call(&mut session, unknown)["error"]["code"],
+            METHOD_NOT_FOUND
+        );
+
+        let play = json!({"jsonrpc": "2.0", "id": 2, "method": "move", "params": {"index": 0}});
+        call(&mut session, play.clone());
+        let response = call(&mut session, play);
+        assert_eq!(response["error"]["code"], ENGINE_ERROR);
+        assert_eq!(response["error"]["data"], "OCCUPIED");
+
+        let missing = json!({"jsonrpc": "2.0", "id": 3, "method": "move", "params": {}});
+        assert_eq!(call(&mut session, missing)["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_serve_skips_notifications() {
+        let mut session = session();
+        let input = concat!(
+            r#"{"jsonrpc": "2.0", "method": "move", "params": {"index": 0}}"#,
+            "\n\n",
+            r#"{"jsonrpc": "2.0", "id": 7, "method": "get_state"}"#,
+            "\n"
+        );
+        let mut output = Vec::new();
+        serve(&mut session, input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert_eq!(output.lines().count(), 1);
+        assert!(output.contains("\"id\":7"));
+        assert_eq!(session.game().history().len(), 1);
+    }
+}
@@ -0,0 +1,2732 @@
+//! Command-line interface for the Y game.
+//!
+//! This module provides the CLI application for playing Y games interactively.
+//! Every task is a subcommand of [`CliCommand`] with its own options, among
+//! them:
+//! - `play`: Two players take turns at the same terminal, or one plays
+//!   against a bot; the default
+//! - `serve`: Run as an HTTP server for bot API, or drive the engine through
+//!   stdin and stdout (see [`jsonrpc`])
+//! - `solve`, `replay`, `tournament`, `archive` and `convert`, which run
+//!   without the interactive prompt
+//!
+//! Messages are printed in English or Spanish, as chosen with `--lang` (see
+//! [`i18n`]). During a game, past positions can be browsed without
+//! changing the game (see [`history`]). Commands read `-` as stdin and
+//! write it as stdout (see [`stdio`]).
+
+use anyhow::Result;
+use clap::builder::BoolishValueParser;
+use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
+use gamey_archive::{ArchiveStats, ExportFormat, PositionIndex, export_to};
+use gamey_bots::{
+    BotSession, CancellationToken, Chaos, ChaosBot, Evaluation, MctsBot, MoveStats,
+    OpeningAnalysis, RandomBot, Review, SearchProgress, Solution, SolverBot, YBot, YBotRegistry,
+    solve, start_session,
+};
+use gamey_core::{
+    BoardTopology, Compression, Coordinates, DeterministicConfig, GameAction, GameFormat,
+    GameYError, Movement, RenderOptions, Ruleset, SaveOptions, Symmetry, game, move_list_tokens,
+};
+use gamey_core::{
+    GameStatus, GameY, Glyph, PlayerId, ValidationCode, ValidationIssue, YEN, validate_yen_json,
+};
+use gamey_tournament::{
+    Adjudication, Format, GameRecord, Sprt, Tournament, TournamentDir, TournamentReport,
+    TournamentSetup,
+};
+use rustyline::error::ReadlineError;
+use std::ffi::OsString;
+use std::fmt::Display;
+use std::io::IsTerminal;
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+pub mod config;
+pub mod history;
+pub mod i18n;
+pub mod jsonrpc;
+pub mod openings;
+pub mod prompt;
+pub mod stdio;
+pub mod teach;
+pub use config::*;
+pub use history::*;
+pub use i18n::*;
+pub use openings::*;
+pub use prompt::{Prompt, config_dir};
+
+/// Command-line arguments for the GameY application.
+#[derive(Parser, Debug)]
+#[command(author, version, about)]
+#[command(long_about = "GameY: A command-line implementation of the Game of Y.")]
+pub struct CliArgs {
+    /// Game to play: y (triangular board) or hex (rhombic board).
+    #[arg(short, long, global = true, default_value_t = BoardTopology::Y)]
+    pub game: BoardTopology,
+
+    /// Size of the board (length of one side), from 1 to 1024.
+    #[arg(short, long, global = true, default_value_t = 7)]
+    pub size: u32,
+
+    /// Number of handicap stones for the first player (0-4); the second player then moves first.
+    #[arg(long, global = true, default_value_t = 0)]
+    pub handicap: u32,
+
+    /// Variant to play: standard, master-y (corner cells blocked) or misere (connecting loses).
+    #[arg(long, global = true, default_value_t = Ruleset::Standard)]
+    pub ruleset: Ruleset,
+
+    /// Start from a named opening of the library, with its board options.
+    #[arg(long, global = true)]
+    pub opening: Option<String>,
+
+    /// File holding the openings added by the user, default ~/.gamey-openings.json.
+    #[arg(long, global = true)]
+    pub openings_file: Option<PathBuf>,
+
+    /// Keep the previous version of a saved game, with `.bak` appended to its name
+    #[arg(long, global = true)]
+    pub backup: bool,
+
+    /// Flush saved games to disk before going on, so they survive a power loss
+    #[arg(long, global = true)]
+    pub durable: bool,
+
+    /// Language of the messages: en (English) or es (Spanish).
+    #[arg(long, global = true, default_value_t = Lang::En)]
+    pub lang: Lang,
+
+    /// Seed every random choice, of the bots, playouts and tournaments, so
+    /// that a run can be repeated exactly.
+    #[arg(long, global = true)]
+    pub seed: Option<u64>,
+
+    /// Threads to split parallel work into with `--seed`; 1 by default.
+    #[arg(long, global = true, requires = "seed")]
+    pub threads: Option<usize>,
+
+    /// Only log errors.
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Log more details: -v for info messages, -vv for debug messages.
+    #[arg(short, long, global = true, action = ArgAction::Count)]
+    pub verbose: u8,
+
+    /// The command to run; `play` with its defaults when omitted.
+    #[command(subcommand)]
+    pub command: Option<CliCommand>,
+}
+
+/// Options of the `play` command, the interactive game at the terminal.
+#[derive(Parser, Debug, Clone, PartialEq)]
+pub struct PlayArgs {
+    /// Opponent: human (2-player at the same terminal) or computer (vs bot).
+    #[arg(short, long, default_value_t = Mode::Human)]
+    pub mode: Mode,
+
+    /// The bot to use (only used with --mode computer), default = random_bot
+    #[arg(short, long, default_value = "random_bot")]
+    pub bot: String,
+
+    /// Let the bot think during your turn (only used with --mode computer)
+    #[arg(long)]
+    pub ponder: bool,
+
+    /// Show the time, nodes and evaluation of each bot move (only used with --mode computer)
+    #[arg(long)]
+    pub show_bot_stats: bool,
+
+    /// Delay and fail the bot's moves to test resilience, e.g. latency=200,fail=0.1,timeout=0.05 (only used with --mode computer)
+    #[arg(long)]
+    pub chaos: Option<Chaos>,
+
+    /// Times a failed bot move is retried before the fallback bot plays (only used with --mode computer)
+    #[arg(long, default_value_t = 2)]
+    pub bot_retries: u32,
+
+    /// The bot that plays when the bot keeps failing (only used with --mode computer)
+    #[arg(long, default_value = "random_bot")]
+    pub fallback_bot: String,
+
+    /// Reject loaded games whose integrity digest is missing or does not match
+    #[arg(long)]
+    pub verify: bool,
+
+    /// Tournament key used to sign saved games and to verify loaded ones
+    #[arg(long)]
+    pub key: Option<String>,
+
+    /// Do not ask before exiting, loading or interrupting with unsaved moves, saving over an existing file, or, with --teach, a move that lets the opponent win
+    #[arg(short, long)]
+    pub yes: bool,
+
+    /// Teaching mode for beginners: explain illegal moves, point out bridges and the sides reached, and ask before a move that lets the opponent win at once
+    #[arg(long)]
+    pub teach: bool,
+}
+
+impl Default for PlayArgs {
+    /// The options of `gamey play` given without any.
+    fn default() -> Self {
+        PlayArgs::parse_from(["play"])
+    }
+}
+
+/// Options of the `serve` command, the bot server.
+#[derive(Parser, Debug, Clone, PartialEq)]
+pub struct ServeArgs {
+    /// Settings of the server; the options below override the file
+    #[arg(long, env = "GAMEY_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    /// Address to run the server on, default = 0.0.0.0
+    #[arg(long, env = "GAMEY_HOST")]
+    pub host: Option<String>,
+
+    /// Port to run the server on, default = 3000
+    #[arg(short, long, env = "GAMEY_PORT")]
+    pub port: Option<u16>,
+
+    /// Port to also answer over gRPC on (needs the grpc feature)
+    #[arg(long, env = "GAMEY_GRPC_PORT")]
+    pub grpc_port: Option<u16>,
+
+    /// Directory where the server persists hosted games
+    #[arg(long, env = "GAMEY_GAMES_DIR")]
+    pub games_dir: Option<PathBuf>,
+
+    /// Memory limits of the server's bots; defaults to ./bots.toml if present
+    #[arg(long, env = "GAMEY_BOTS_CONFIG")]
+    pub bots_config: Option<PathBuf>,
+
+    /// Limit board sizes, requests per client and searching bots for a classroom demo
+    #[arg(long, env = "GAMEY_DEMO", value_parser = BoolishValueParser::new())]
+    pub demo: bool,
+
+    /// Seconds a bot may search before the server request fails, default = 30
+    #[arg(long, env = "GAMEY_BOT_TIMEOUT")]
+    pub bot_timeout: Option<u64>,
+
+    /// Answers of deterministic bots the server caches, 0 to disable, default = 10000
+    #[arg(long, env = "GAMEY_MOVE_CACHE_ENTRIES")]
+    pub move_cache_entries: Option<usize>,
+
+    /// Key that enables the server's /admin endpoints, sent as a bearer token
+    #[arg(long, env = "GAMEY_ADMIN_KEY", hide_env_values = true)]
+    pub admin_key: Option<String>,
+
+    /// File where the server logs every move it serves, as JSON lines
+    #[arg(long, env = "GAMEY_AUDIT_LOG")]
+    pub audit_log: Option<PathBuf>,
+
+    /// Answer JSON-RPC requests read from stdin, one per line, instead of serving HTTP
+    #[arg(long)]
+    pub jsonrpc: bool,
+
+    /// The bot of JSON-RPC sessions (only used with --jsonrpc), default = random_bot
+    #[arg(short, long, default_value = "random_bot")]
+    pub bot: String,
+}
+
+impl ServeArgs {
+    /// Returns the `bots.toml` file configuring the server's bots: the one
+    /// given on the command line, or `bots.toml` in the current directory
+    /// if there is one.
+    pub fn bots_config(&self) -> Option<PathBuf> {
+        let default = PathBuf::from("bots.toml");
+        self.bots_config
+            .clone()
+            .or_else(|| default.is_file().then_some(default))
+    }
+}
+
+impl CliArgs {
+    /// Parses the command line like [`Parser::try_parse_from`], also taking
+    /// the forms of earlier versions, which `--help` no longer shows:
+    /// `--mode` without a command, where `--mode server` is now `serve`,
+    /// `--mode jsonrpc` is `serve --jsonrpc` and the other modes are
+    /// options of `play`, which drops the `--port` it never used; `--port`
+    /// without a mode, now `serve --port`; `--bot` without a mode, now
+    /// `play --bot`; and `play --moves`, now `replay --moves`.
+    ///
+    /// Returns the arguments and the deprecated form used, if any.
+    pub fn try_parse_legacy<I, T>(args: I) -> Result<(Self, Option<LegacyForm>), clap::Error>
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<OsString>,
+    {
+        let (args, deprecated) = upgrade_legacy_args(args.into_iter().map(Into::into).collect());
+        Ok((CliArgs::try_parse_from(args)?, deprecated))
+    }
+
+    /// Returns the tracing filter selected by `--quiet` and `-v`; warnings
+    /// are logged by default.
+    pub fn log_filter(&self) -> &'static str {
+        match (self.quiet, self.verbose) {
+            (true, _) => "error",
+            (false, 0) => "warn",
+            (false, 1) => "info",
+            (false, _) => "debug",
+        }
+    }
+
+    /// Returns the configuration making the run reproducible, if `--seed`
+    /// was given.
+    pub fn deterministic_config(&self) -> Option<DeterministicConfig> {
+        self.seed
+            .map(|seed| DeterministicConfig::new(seed).with_threads(self.threads.unwrap_or(1)))
+    }
+
+    /// Returns how games are saved, as set by `--backup` and `--durable`.
+    pub fn save_options(&self) -> SaveOptions {
+        SaveOptions::default()
+            .with_backup(self.backup)
+            .with_durable(self.durable)
+    }
+
+    /// Returns the file holding the user openings, see
+    /// [`default_openings_file`].
+    pub fn openings_file(&self) -> PathBuf {
+        self.openings_file
+            .clone()
+            .unwrap_or_else(default_openings_file)
+    }
+
+    /// Checks the options clap cannot validate on its own: that the bots
+    /// of the command exist and that the board options describe a game.
+    ///
+    /// Returns the message to show when they do not.
+    pub fn check(&self) -> Result<(), String> {
+        let bots = default_bots();
+        let names = match &self.command {
+            Some(CliCommand::Play(play)) => vec![&play.bot, &play.fallback_bot],
+            Some(CliCommand::Serve(serve)) => vec![&serve.bot],
+            _ => Vec::new(),
+        };
+        for bot in names {
+            if bots.find(bot).is_none() {
+                let message = Message::BotNotFound {
+                    bot,
+                    available: &bots.names(),
+                };
+                return Err(message.text(self.lang));
+            }
+        }
+        GameY::check_board_size(self.size).map_err(|e| e.to_string())?;
+        new_game(self).map(|_| ()).map_err(|e| e.to_string())
+    }
+}
+
+/// The exit status of the `gamey` binary, so scripts can tell failures apart.
+///
+/// Errors in the command-line syntax are reported by clap, which also exits
+/// with [`ExitStatus::InvalidArgs`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitStatus {
+    /// Everything went fine.
+    Success = 0,
+    /// The command line is invalid, e.g. an unknown bot or board size.
+    InvalidArgs = 2,
+    /// The engine rejected the game, e.g. an illegal move or an invalid
+    /// file, or any other error happened while running.
+    EngineError = 3,
+    /// The server could not listen on its port.
+    BindFailed = 4,
+}
+
+impl ExitStatus {
+    /// Returns the status for an error returned by one of the `run_*`
+    /// functions.
+    pub fn of(err: &anyhow::Error) -> ExitStatus {
+        match err.downcast_ref::<GameYError>() {
+            Some(GameYError::BindFailed { .. }) => ExitStatus::BindFailed,
+            _ => ExitStatus::EngineError,
+        }
+    }
+
+    /// Returns the numeric process exit code.
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+}
+
+impl From<ExitStatus> for std::process::ExitCode {
+    fn from(status: ExitStatus) -> Self {
+        std::process::ExitCode::from(status.code())
+    }
+}
+
+/// A form of the command line of an earlier version, see
+/// [`CliArgs::try_parse_legacy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LegacyForm {
+    /// The deprecated form, e.g. `--mode server`.
+    pub old: &'static str,
+    /// The form to use instead, e.g. `serve`.
+    pub new: &'static str,
+}
+
+impl LegacyForm {
+    const fn new(old: &'static str, new: &'static str) -> Self {
+        LegacyForm { old, new }
+    }
+}
+
+/// Rewrites a command line of an earlier version into the current commands,
+/// see [`CliArgs::try_parse_legacy`].
+fn upgrade_legacy_args(mut args: Vec<OsString>) -> (Vec<OsString>, Option<LegacyForm>) {
+    let text = |arg: &OsString| arg.to_str().unwrap_or_default().to_string();
+    let cli = CliArgs::command();
+    let command = args
+        .iter()
+        .enumerate()
+        .skip(1)
+        .find(|(_, arg)| cli.find_subcommand(text(arg)).is_some())
+        .map(|(at, _)| at);
+    if let Some(at) = command {
+        let moves = args[at + 1..]
+            .iter()
+            .map(text)
+            .any(|arg| arg == "--moves" || arg.starts_with("--moves="));
+        if text(&args[at]) == "play" && moves {
+            args[at] = "replay".into();
+            let form = LegacyForm::new("play --moves", "replay --moves");
+            return (args, Some(form));
+        }
+        return (args, None);
+    }
+    let Some(at) = position(&args, "--mode", "-m") else {
+        // Without a mode, the server options chose the server and the bot
+        // options the game against it
+        let (command, deprecated) = if position(&args, "--port", "-p").is_some() {
+            ("serve", LegacyForm::new("--port", "serve --port"))
+        } else if position(&args, "--bot", "-b").is_some() {
+            ("play", LegacyForm::new("--bot", "play --bot"))
+        } else {
+            return (args, None);
+        };
+        args.insert(1, command.into());
+        return (args, Some(deprecated));
+    };
+    let (mode, len) = match text(&args[at]).strip_prefix("--mode=") {
+        Some(mode) => (mode.to_string(), 1),
+        None => (args.get(at + 1).map(text).unwrap_or_default(), 2),
+    };
+    let (command, deprecated): (&[&str], _) = match mode.as_str() {
+        "server" => (&["serve"], LegacyForm::new("--mode server", "serve")),
+        "jsonrpc" => (
+            &["serve", "--jsonrpc"],
+            LegacyForm::new("--mode jsonrpc", "serve --jsonrpc"),
+        ),
+        "human" | "computer" => {
+            // The mode is still an option, of `play`, which never used the
+            // port of the server
+            if let Some(at) = position(&args, "--port", "-p") {
+                let len = if text(&args[at]).contains('=') { 1 } else { 2 };
+                args.drain(at..(at + len).min(args.len()));
+            }
+            args.insert(1, "play".into());
+            return (args, Some(LegacyForm::new("--mode", "play --mode")));
+        }
+        _ => return (args, None),
+    };
+    args.drain(at..(at + len).min(args.len()));
+    args.splice(1..1, command.iter().map(OsString::from));
+    (args, Some(deprecated))
+}
+
+/// Returns where the option with the given long and short names is in
+/// `args`, written as `--long value`, `--long=value` or `-s value`.
+fn position(args: &[OsString], long: &str, short: &str) -> Option<usize> {
+    args.iter()
+        .skip(1)
+        .position(|arg| {
+            let arg = arg.to_str().unwrap_or_default();
+            arg == long
+                || arg == short
+                || arg.strip_prefix(long).is_some_and(|v| v.starts_with('='))
+        })
+        .map(|at| at + 1)
+}
+
+/// The commands of `gamey`.
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum CliCommand {
+    /// Play a game at the terminal, against another human or a bot; the
+    /// default command.
+    Play(PlayArgs),
+    /// Run the bot server over HTTP, or answer JSON-RPC requests on stdin
+    /// and stdout.
+    Serve(ServeArgs),
+    /// List the bots that can play.
+    Bots,
+    /// Solve a position exactly: who wins with perfect play, and with
+    /// which moves; small Y boards only.
+    Solve {
+        /// The position, a YEN file, or `-` to read it from stdin; the
+        /// position set by the board options by default.
+        position: Option<PathBuf>,
+    },
+    /// Strictly validate YEN files and report every problem found.
+    Validate {
+        /// The YEN files to check; `-` reads one from stdin.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+    },
+    /// Convert game files between YEN, compact YEN, move lists and SGF,
+    /// writing each one to the output directory under the same name.
+    Convert {
+        /// The files to convert; `.gz` and `.zst` files are decompressed,
+        /// and `-` reads one from stdin.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// The format of the files: yen, compact-yen, moves or sgf; guessed
+        /// from the extension of every file by default. Move lists are
+        /// played on the board set by the board options.
+        #[arg(long)]
+        from: Option<GameFormat>,
+        /// The format to write: yen, compact-yen, moves or sgf.
+        #[arg(long)]
+        to: GameFormat,
+        /// The directory the converted files are written to, or `-` to
+        /// write them to stdout.
+        #[arg(long)]
+        out_dir: PathBuf,
+    },
+    /// Replay submitted games from scratch, checking that every move was
+    /// legal and that the claimed result is the one the engine finds, and
+    /// print the first discrepancy of each.
+    Verify {
+        /// The game files; `.gz` and `.zst` files are decompressed, and `-`
+        /// reads one from stdin.
+        #[arg(required = true)]
+        files: Vec<PathBuf>,
+        /// The format of the files: yen, compact-yen, moves or sgf; guessed
+        /// from the extension of every file by default. Move lists are
+        /// played on the board set by the board options.
+        #[arg(long)]
+        from: Option<GameFormat>,
+        /// The claimed result, e.g. B+ or W+R, for files that record none
+        /// or to check instead of the one they record.
+        #[arg(long)]
+        result: Option<String>,
+    },
+    /// Play a sequence of moves without the interactive prompt and print the result.
+    Replay {
+        /// Cell indices separated by spaces, played alternately from the
+        /// first player to move, optionally with glyphs (`4!`) and
+        /// `{comments}`; read from stdin when omitted.
+        #[arg(long)]
+        moves: Option<String>,
+        /// Print the final board before the result.
+        #[arg(long)]
+        print_final: bool,
+    },
+    /// List, show, add or remove the named openings; lists them by default.
+    Openings {
+        #[command(subcommand)]
+        action: Option<OpeningsAction>,
+    },
+    /// Play every pair of bots against each other and rank them; programs
+    /// given as `name=command` run in their own process for every move.
+    #[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
+    Tournament {
+        #[command(subcommand)]
+        action: Option<TournamentAction>,
+        /// A bot of gamey, or `name=command with args` for a program that
+        /// reads the position as YEN on stdin and writes its move as JSON
+        /// coordinates on stdout.
+        #[arg(long = "entrant", required = true)]
+        entrants: Vec<String>,
+        /// How the bots are paired.
+        #[arg(long, value_enum, default_value_t = TournamentFormat::RoundRobin)]
+        format: TournamentFormat,
+        /// Rounds of a Swiss tournament, by default enough to tell a single winner apart.
+        #[arg(long)]
+        rounds: Option<u32>,
+        /// Games every pairing plays, alternating who moves first.
+        #[arg(long, default_value_t = 2)]
+        games: u32,
+        /// CPU time of every move of a program, in milliseconds.
+        #[arg(long, default_value_t = 1000)]
+        move_time: u64,
+        /// Memory of every move of a program, in megabytes.
+        #[arg(long, default_value_t = 512)]
+        memory: u64,
+        /// Also write the report to this file: the standings as CSV if it
+        /// ends in `.csv`, every result as JSON otherwise; `-` writes the
+        /// JSON report to stdout instead of the standings.
+        #[arg(long)]
+        report: Option<PathBuf>,
+        /// Stop every match once a sequential probability ratio test tells
+        /// whether the first bot is stronger, e.g. `elo0=0,elo1=20`, with
+        /// optional error rates `alpha` and `beta` (0.05 by default).
+        #[arg(long)]
+        sprt: Option<Sprt>,
+        /// Save the tournament in this directory after every game, so that
+        /// it can be resumed if it is interrupted.
+        #[arg(long)]
+        dir: Option<PathBuf>,
+    },
+    /// Estimate the win rate of every first move by letting a bot play the
+    /// game out against itself, and rank the moves in a table and on the
+    /// board; symmetric moves are played out once.
+    AnalyzeOpenings {
+        /// The bot that plays the games out.
+        #[arg(long, default_value = "random_bot")]
+        bot: String,
+        /// Games played out after every first move.
+        #[arg(long, default_value_t = 1000)]
+        playouts: u32,
+    },
+    /// Estimate the winner of an unfinished position, such as a timed-out
+    /// correspondence game, by letting a bot play it out many times.
+    Adjudicate {
+        /// The position, a saved game; `-` reads it from stdin.
+        position: PathBuf,
+        /// The bot that plays the position out.
+        #[arg(long, default_value = "random_bot")]
+        bot: String,
+        /// Times the position is played out.
+        #[arg(long, default_value_t = 1000)]
+        playouts: u32,
+        /// The fraction of the playouts a player has to win to be awarded
+        /// the game, from 0.5 to 1.
+        #[arg(long, default_value_t = 0.75)]
+        threshold: f64,
+    },
+    /// Review every move of a game with a bot, flag the moves that dropped
+    /// the win rate of their player the most and print the accuracy of each
+    /// player.
+    Review {
+        /// The game, a saved game or move list; `.gz` and `.zst` files are
+        /// decompressed, and `-` reads it from stdin.
+        file: PathBuf,
+        /// The format of the game: yen, compact-yen, moves or sgf; guessed
+        /// from the extension by default. Move lists are played on the
+        /// board set by the board options.
+        #[arg(long)]
+        from: Option<GameFormat>,
+        /// The bot that chooses the alternatives and plays the moves out.
+        #[arg(long, default_value = "random_bot")]
+        bot: String,
+        /// Games played out after every move and its alternative.
+        #[arg(long, default_value_t = 100)]
+        playouts: u32,
+        /// The drop of win rate, from 0 to 1, above which a move is flagged
+        /// as a mistake.
+        #[arg(long, default_value_t = 0.2)]
+        threshold: f64,
+    },
+    /// Print statistics over an archive of games: game lengths, first
+    /// player win rate and swap frequency by board, and common opening
+    /// cells.
+    Stats {
+        /// The archive, a JSON Lines file with one YEN game per line, or
+        /// `-` to read it from stdin.
+        #[arg(long)]
+        games: PathBuf,
+        /// Also write the statistics as CSV files (`boards.csv`,
+        /// `lengths.csv` and `openings.csv`) in this directory, for plotting.
+        #[arg(long)]
+        csv: Option<PathBuf>,
+    },
+    /// Query an archive of games.
+    Archive {
+        #[command(subcommand)]
+        action: ArchiveAction,
+    },
+    /// Check that a bot server implements the bot API and report the
+    /// checks it fails.
+    #[cfg(feature = "client")]
+    Conformance {
+        /// The URL of the server, e.g. `http://localhost:3000`.
+        #[arg(long)]
+        url: String,
+        /// The bot of the server asked for moves.
+        #[arg(long, default_value = "random_bot")]
+        bot_id: String,
+    },
+    /// Follow a game hosted by a bot server, drawing the board again after
+    /// every move until the game ends.
+    #[cfg(feature = "client")]
+    Watch {
+        /// The URL of the server, e.g. `http://localhost:3000`.
+        #[arg(long)]
+        server: String,
+        /// The identifier of the game on the server. (`--game` is the shape
+        /// of the board.)
+        #[arg(long)]
+        game_id: String,
+        /// A bot of the server that comments every position with the move
+        /// it would play and why.
+        #[arg(long)]
+        bot_id: Option<String>,
+    },
+}
+
+/// What the `tournament` command does instead of starting a tournament.
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum TournamentAction {
+    /// Go on with a tournament saved with `--dir` where it stopped.
+    Resume {
+        /// The directory the tournament is saved in.
+        dir: PathBuf,
+    },
+}
+
+/// What the `archive` command looks up.
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum ArchiveAction {
+    /// List the games reaching a position, or a position symmetric to it,
+    /// and the moves played next with their results.
+    FindPosition {
+        /// The position, a YEN file, or `-` to read it from stdin.
+        position: PathBuf,
+        /// The archive, a JSON Lines file with one YEN game per line, or
+        /// `-` to read it from stdin.
+        #[arg(long)]
+        games: PathBuf,
+    },
+    /// Write one row per move of every game (game, move number, player,
+    /// cell index, result...) to a CSV or Parquet file, for data analysis.
+    Export {
+        /// The file to write, or `-` for stdout.
+        out: PathBuf,
+        /// The archive, a JSON Lines file with one YEN game per line, or
+        /// `-` to read it from stdin.
+        #[arg(long)]
+        games: PathBuf,
+        /// `csv`, or `parquet` if gamey was built with the `parquet`
+        /// feature; guessed from the extension of the file by default.
+        #[arg(long)]
+        format: Option<ExportFormat>,
+    },
+}
+
+/// The pairing formats of the `tournament` command, see [`Format`].
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum TournamentFormat {
+    /// Every bot plays every other bot.
+    RoundRobin,
+    /// Bots with similar scores play each other for some rounds.
+    Swiss,
+    /// Losers are out until one bot is left.
+    Elimination,
+}
+
+impl TournamentFormat {
+    /// Returns the format of a tournament between `entrants`, with the
+    /// given number of Swiss rounds or enough to tell a winner apart.
+    pub fn format(self, entrants: usize, rounds: Option<u32>) -> Format {
+        match self {
+            TournamentFormat::RoundRobin => Format::RoundRobin,
+            TournamentFormat::Swiss => match rounds {
+                Some(rounds) => Format::Swiss { rounds },
+                None => Format::swiss_for(entrants),
+            },
+            TournamentFormat::Elimination => Format::SingleElimination,
+        }
+    }
+}
+
+/// What the `openings` command does with the library.
+#[derive(Subcommand, Debug, Clone, PartialEq)]
+pub enum OpeningsAction {
+    /// List the openings with their board size and number of moves.
+    List,
+    /// Print the moves and the final board of an opening.
+    Show {
+        /// The name of the opening.
+        name: String,
+    },
+    /// Add an opening played with the board options from the move list.
+    Add {
+        /// The name of the new opening.
+        name: String,
+        /// Cell indices separated by spaces, as for the play command.
+        #[arg(long)]
+        moves: String,
+        /// What the opening is about.
+        #[arg(long, default_value = "")]
+        description: String,
+    },
+    /// Remove an opening added by the user.
+    Remove {
+        /// The name of the opening.
+        name: String,
+    },
+}
+
+/// The game mode determining how the game is played.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq)]
+pub enum Mode {
+    /// Play against a computer bot.
+    Computer,
+    /// Two humans playing at the same terminal.
+    Human,
+}
+
+impl Display for Mode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Mode::Computer => "computer",
+            Mode::Human => "human",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+/// Runs the interactive CLI game loop.
+///
+/// This function initializes the game described by the board options of
+/// `args` and runs the main game loop where players enter moves via the
+/// terminal, against the opponent set by `play`.
+pub fn run_cli_game(args: &CliArgs, play: &PlayArgs) -> Result<()> {
+    let lang = args.lang;
+    let mut state = CliState {
+        notifications: CliConfig::load_default().notifications,
+        teach: play.teach,
+        ..CliState::default()
+    };
+    let history = prompt::history_file();
+    let mut rl = prompt::new_prompt(&history)?;
+    let bots_registry = default_bots();
+    let bot: Arc<dyn YBot> = match bots_registry.find(&play.bot) {
+        Some(b) => b,
+        None => {
+            let message = Message::BotNotFound {
+                bot: &play.bot,
+                available: &bots_registry.names(),
+            };
+            anyhow::bail!(message.text(lang));
+        }
+    };
+    let bot = match &play.chaos {
+        Some(chaos) => Arc::new(ChaosBot::new(bot, chaos.clone())),
+        None => bot,
+    };
+    let session = Arc::new(Mutex::new(start_session(Arc::clone(&bot))));
+    let mut game = new_game(args)?;
+    loop {
+        print_board(&game, &mut state, lang)?;
+        let status = game.status();
+        match status {
+            GameStatus::Finished { winner } => {
+                let message = Message::GameOver {
+                    winner: winner.to_string(),
+                };
+                println!("{}", message.text(lang));
+                break;
+            }
+            GameStatus::Ongoing { next_player } => {
+                if let Some(requester) = state.takeback.take() {
+                    answer_takeback(&mut rl, &mut game, requester, lang);
+                    continue;
+                }
+                let prompt = Message::Prompt {
+                    player: next_player.to_string(),
+                }
+                .text(lang);
+                let pondering = (play.mode == Mode::Computer && play.ponder)
+                    .then(|| Pondering::start(Arc::clone(&session), game.clone()));
+                let readline = rl.readline(&prompt);
+                if let Some(pondering) = pondering {
+                    pondering.finish();
+                }
+                match readline {
+                    Err(ReadlineError::Interrupted) => {
+                        interrupt(|question| rl.readline(question), &game, &state, args, play);
+                        break;
+                    }
+                    Err(ReadlineError::Eof) => {
+                        println!("{}", Message::Exiting.text(lang));
+                        break;
+                    }
+                    Err(err) => {
+                        let message = Message::ReadError {
+                            error: format!("{:?}", err),
+                        };
+                        println!("{}", message.text(lang));
+                        continue;
+                    }
+                    Ok(realine) => {
+                        rl.add_history_entry(realine.as_str())?;
+                        if let Err(e) = prompt::save_history(&mut rl, &history) {
+                            tracing::warn!("Cannot save the history of commands: {}", e);
+                        }
+                        let command = parse_command_on(&realine, &game, lang);
+                        if !play.yes
+                            && let Some(question) = confirmation(&command, &game, &state)
+                            && !confirm(&mut rl, &question.text(lang), lang)
+                        {
+                            println!("{}", Message::Cancelled.text(lang));
+                            continue;
+                        }
+                        let off_board = match command {
+                            Command::Error { .. } if play.teach => {
+                                teach::off_board(&realine, &game)
+                            }
+                            _ => None,
+                        };
+                        let mut session = session.lock().unwrap_or_else(|e| e.into_inner());
+                        let flow = process_input(
+                            command,
+                            &mut game,
+                            &mut state,
+                            args,
+                            play,
+                            bot.as_ref(),
+                            session.as_mut(),
+                        )?;
+                        if let Some(lesson) = off_board {
+                            println!("{}", lesson.text(lang));
+                        }
+                        if flow.is_break() {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The state of the interactive game besides the game itself.
+#[derive(Default)]
+struct CliState {
+    /// How the board is drawn.
+    render_options: RenderOptions,
+    /// Which position of the game is drawn.
+    history: HistoryView,
+    /// The player whose takeback request waits for the opponent's answer.
+    takeback: Option<PlayerId>,
+    /// Whether the game changed since it was last saved or loaded.
+    unsaved: bool,
+    /// How the player is told that the bot has moved.
+    notifications: Notifications,
+    /// Whether an estimate of who is ahead is shown under the board.
+    eval: bool,
+    /// The last estimate shown, with the hash of its position, so that
+    /// redrawing the same position does not play the fills again.
+    evaluation: Option<(u64, Evaluation)>,
+    /// Whether the rules are explained to a beginner, see [`teach`].
+    teach: bool,
+}
+
+/// The random fills played to estimate who is ahead, see [`Evaluation`].
+const EVAL_PLAYOUTS: u32 = 200;
+
+/// Returns the question to ask before running `command`, if it discards
+/// unsaved moves, writes over an existing file or, in teaching mode, lets
+/// the opponent of the player to move in `game` win at once.
+fn confirmation<'a>(command: &'a Command, game: &GameY, state: &CliState) -> Option<Message<'a>> {
+    match command {
+        Command::Place { idx } if state.teach => {
+            let reply = teach::gives_away(game, game.cell_coords(*idx))?;
+            Some(Message::ConfirmGiveaway {
+                cell: game.cell_name(reply),
+            })
+        }
+        Command::Exit if state.unsaved => Some(Message::ConfirmExit),
+        Command::Load { filename } if state.unsaved => {
+            Some(Message::ConfirmLoad { file: filename })
+        }
+        Command::Save { filename }
+        | Command::Dump {
+            filename: Some(filename),
+        } if Path::new(filename).exists() => Some(Message::ConfirmOverwrite { file: filename }),
+        _ => None,
+    }
+}
+
+/// Handles a Ctrl-C at the prompt: unless `--yes` was given, offers to save
+/// the unsaved moves, reading the answer with `ask`.
+fn interrupt(
+    ask: impl FnOnce(&str) -> rustyline::Result<String>,
+    game: &GameY,
+    state: &CliState,
+    args: &CliArgs,
+    play: &PlayArgs,
+) {
+    println!("{}", Message::Interrupted.text(args.lang));
+    if state.unsaved && !play.yes {
+        save_on_interrupt(ask, game, args, play);
+    }
+}
+
+/// Offers to save a game interrupted with Ctrl-C to a file; an empty answer
+/// or another Ctrl-C leaves without saving.
+fn save_on_interrupt(
+    ask: impl FnOnce(&str) -> rustyline::Result<String>,
+    game: &GameY,
+    args: &CliArgs,
+    play: &PlayArgs,
+) {
+    let lang = args.lang;
+    let Ok(answer) = ask(&Message::SaveOnInterrupt.text(lang)) else {
+        return;
+    };
+    let filename = answer.trim();
+    if filename.is_empty() {
+        return;
+    }
+    match save_game(game, filename, args, play) {
+        Ok(()) => println!("{}", Message::GameSaved { file: filename }.text(lang)),
+        Err(e) => {
+            let message = Message::SaveError {
+                error: e.to_string(),
+            };
+            println!("{}", message.text(lang));
+        }
+    }
+}
+
+/// Saves `game` to `filename` with the save options of `args`, signed with
+/// the key of `play` if any.
+fn save_game(game: &GameY, filename: &str, args: &CliArgs, play: &PlayArgs) -> Result<()> {
+    let key = play.key.as_deref().map(str::as_bytes);
+    game.save_with_options(Path::new(filename), key, &args.save_options())?;
+    Ok(())
+}
+
+/// Asks `question` at the prompt and returns whether it was answered yes.
+fn confirm(rl: &mut Prompt, question: &str, lang: Lang) -> bool {
+    rl.readline(question)
+        .is_ok_and(|answer| lang.is_yes(&answer))
+}
+
+/// Prints the board of the position being displayed, noting when it is a
+/// past position rather than the live game.
+fn print_board(game: &GameY, state: &mut CliState, lang: Lang) -> Result<()> {
+    match state.history.shown() {
+        Some(number) => {
+            let past = game.position_after(number)?;
+            println!("{}", past.render(&state.render_options));
+            let message = Message::ViewingMove {
+                number,
+                total: game.move_count(),
+            };
+            println!("{}", message.text(lang));
+            print_evaluation(&past, state, lang);
+        }
+        None => {
+            println!("{}", game.render(&state.render_options));
+            print_evaluation(game, state, lang);
+        }
+    }
+    Ok(())
+}
+
+/// Prints the estimate of who is ahead in `shown` if `eval` is on and the
+/// game is not over.
+fn print_evaluation(shown: &GameY, state: &mut CliState, lang: Lang) {
+    if !state.eval {
+        return;
+    }
+    let hash = shown.zobrist_hash();
+    let evaluation = match state.evaluation {
+        Some((of, evaluation)) if of == hash => Some(evaluation),
+        _ => Evaluation::run(shown, EVAL_PLAYOUTS),
+    };
+    if let Some(evaluation) = evaluation {
+        state.evaluation = Some((hash, evaluation));
+        let message = Message::Evaluation {
+            evaluation: evaluation.to_string(),
+        };
+        println!("{}", message.text(lang));
+    }
+}
+
+/// Asks the opponent of `requester` whether they accept to take back the
+/// last move pair, and takes it back if they do.
+fn answer_takeback(rl: &mut Prompt, game: &mut GameY, requester: PlayerId, lang: Lang) {
+    let prompt = Message::TakebackPrompt {
+        requester: requester.to_string(),
+        opponent: requester.opponent().to_string(),
+    }
+    .text(lang);
+    let accepted = rl
+        .readline(&prompt)
+        .is_ok_and(|answer| lang.is_yes(&answer));
+    if accepted {
+        take_back(game, requester, lang);
+    } else {
+        println!("{}", Message::TakebackDeclined.text(lang));
+    }
+}
+
+/// Takes back the last move pair of `requester` and reports the result.
+fn take_back(game: &mut GameY, requester: PlayerId, lang: Lang) {
+    match game.take_back(requester) {
+        Ok(moves) => println!("{}", Message::TakenBack { moves }.text(lang)),
+        Err(e) => {
+            let message = Message::TakebackError {
+                error: e.to_string(),
+            };
+            println!("{}", message.text(lang));
+        }
+    }
+}
+
+/// Returns the bots available from the command line.
+fn default_bots() -> YBotRegistry {
+    YBotRegistry::new()
+        .with_bot(Arc::new(RandomBot))
+        .with_bot(Arc::new(MctsBot::default()))
+        .with_bot(Arc::new(SolverBot::default()))
+}
+
+/// Plays the tournament of `setup` and prints the report, also writing it
+/// to `report` if given: the standings as CSV for a `.csv` file, every
+/// result as JSON otherwise.
+///
+/// With `dir`, the tournament is saved there after every game, so that
+/// [`run_tournament_resume`] can go on with it.
+pub fn run_tournament(
+    setup: &TournamentSetup,
+    report: Option<&Path>,
+    dir: Option<&Path>,
+) -> Result<()> {
+    let _determinism = setup
+        .seed
+        .map(|seed| DeterministicConfig::new(seed).install());
+    let tournament = setup
+        .tournament(&default_bots())
+        .map_err(anyhow::Error::msg)?;
+    let start = GameY::try_from(setup.start.clone())?;
+    let result = match dir {
+        Some(dir) => {
+            let dir = TournamentDir::create(dir, setup)?;
+            play_saved_tournament(&tournament, &start, &dir, &[])?
+        }
+        None => tournament.run(|| start.clone()),
+    };
+    match report {
+        Some(file) if stdio::is_stdio(file) => {
+            eprintln!("{}", result);
+            println!("{}", serde_json::to_string_pretty(&result)?);
+        }
+        Some(file) => {
+            println!("{}", result);
+            let text = match file.extension() {
+                Some(extension) if extension == "csv" => result.standings_csv(),
+                _ => serde_json::to_string_pretty(&result)?,
+            };
+            std::fs::write(file, text)?;
+        }
+        None => println!("{}", result),
+    }
+    Ok(())
+}
+
+/// Goes on with the tournament saved in `dir` by `run_tournament`, replaying
+/// the games already played, and prints the report.
+pub fn run_tournament_resume(dir: &Path) -> Result<()> {
+    let (dir, setup, played) = TournamentDir::open(dir)?;
+    let _determinism = setup
+        .seed
+        .map(|seed| DeterministicConfig::new(seed).install());
+    let tournament = setup
+        .tournament(&default_bots())
+        .map_err(anyhow::Error::msg)?;
+    let start = GameY::try_from(setup.start)?;
+    println!(
+        "Resuming the tournament in {} after {} games",
+        dir.path().display(),
+        played.len()
+    );
+    let result = play_saved_tournament(&tournament, &start, &dir, &played)?;
+    println!("{}", result);
+    Ok(())
+}
+
+/// Plays the rest of a tournament saved in `dir` after the `played` games,
+/// saving every new game and the final report.
+fn play_saved_tournament(
+    tournament: &Tournament,
+    start: &GameY,
+    dir: &TournamentDir,
+    played: &[GameRecord],
+) -> Result<TournamentReport> {
+    let result = tournament.resume(|| start.clone(), played, |game| dir.record_game(game))?;
+    dir.write_report(&result)?;
+    Ok(result)
+}
+
+/// Prints the estimated win rate of every move of the player to move in
+/// the game of [`new_game`], played out `playouts` times by `bot`, as a
+/// ranked table and a heatmap of the board.
+pub fn run_analyze_openings(args: &CliArgs, bot: &str, playouts: u32) -> Result<()> {
+    let bots = default_bots();
+    let Some(bot) = bots.find(bot) else {
+        let message = Message::BotNotFound {
+            bot,
+            available: &bots.names(),
+        };
+        anyhow::bail!(message.text(args.lang));
+    };
+    let game = new_game(args)?;
+    let analysis = OpeningAnalysis::run(&game, bot.as_ref(), playouts)
+        .ok_or_else(|| anyhow::anyhow!("The game is over, there is no move to analyze"))?;
+    println!("{}", analysis);
+    print!("{}", analysis.heatmap());
+    Ok(())
+}
+
+/// Plays the position saved at `position` out `playouts` times with `bot`
+/// and prints who it is adjudicated to, with the confidence of the
+/// estimate; see [`Adjudication`].
+pub fn run_adjudicate(
+    args: &CliArgs,
+    position: &Path,
+    bot: &str,
+    playouts: u32,
+    threshold: f64,
+) -> Result<()> {
+    if !(0.5..=1.0).contains(&threshold) {
+        anyhow::bail!("The threshold must be between 0.5 and 1, not {}", threshold);
+    }
+    let bots = default_bots();
+    let Some(bot) = bots.find(bot) else {
+        let message = Message::BotNotFound {
+            bot,
+            available: &bots.names(),
+        };
+        anyhow::bail!(message.text(args.lang));
+    };
+    let game = stdio::load_game(position)?;
+    let adjudication = Adjudication::run(&game, bot.as_ref(), playouts, threshold)
+        .ok_or_else(|| anyhow::anyhow!("The game is over, there is nothing to adjudicate"))?;
+    println!("{}", adjudication);
+    Ok(())
+}
+
+/// Reviews every move of the game saved at `file` with `bot`, playing each
+/// out `playouts` times, and prints the moves that dropped the win rate of
+/// their player by more than `threshold` and the accuracy of each player;
+/// see [`Review`].
+pub fn run_review(
+    args: &CliArgs,
+    file: &Path,
+    from: Option<GameFormat>,
+    bot: &str,
+    playouts: u32,
+    threshold: f64,
+) -> Result<()> {
+    if !(0.0..=1.0).contains(&threshold) {
+        anyhow::bail!("The threshold must be between 0 and 1, not {}", threshold);
+    }
+    let bots = default_bots();
+    let Some(bot) = bots.find(bot) else {
+        let message = Message::BotNotFound {
+            bot,
+            available: &bots.names(),
+        };
+        anyhow::bail!(message.text(args.lang));
+    };
+    let game = read_game(file, from, &new_game(args)?)?;
+    print!("{}", Review::run(&game, bot.as_ref(), playouts, threshold));
+    Ok(())
+}
+
+/// Prints statistics over the games of the archive at `games`, and writes
+/// them as CSV files in `csv` if given.
+pub fn run_stats(games: &Path, csv: Option<&Path>) -> Result<()> {
+    let mut stats = ArchiveStats::new();
+    for archived in stdio::open_archive(games)? {
+        stats.add(&archived?.game);
+    }
+    print!("{}", stats);
+    if let Some(dir) = csv {
+        std::fs::create_dir_all(dir)?;
+        std::fs::write(dir.join("boards.csv"), stats.boards_csv())?;
+        std::fs::write(dir.join("lengths.csv"), stats.lengths_csv())?;
+        std::fs::write(dir.join("openings.csv"), stats.openings_csv()?)?;
+    }
+    Ok(())
+}
+
+/// Prints the games of the archive at `games` reaching the position saved
+/// at `position`, and the moves played next.
+pub fn run_find_position(position: &Path, games: &Path) -> Result<()> {
+    stdio::check_stdin_once(&[position, games])?;
+    let position = stdio::load_game(position)?;
+    let mut index = PositionIndex::new();
+    for archived in stdio::open_archive(games)? {
+        let archived = archived?;
+        index.add(archived.number, &archived.game);
+    }
+    print!("{}", index.find(&position));
+    Ok(())
+}
+
+/// Writes the moves of the games of the archive at `games` to `out` in
+/// `format`, or in the format its extension suggests.
+///
+/// When writing to stdout, the summary is printed to stderr.
+pub fn run_export(games: &Path, out: &Path, format: Option<ExportFormat>) -> Result<()> {
+    let format = format.unwrap_or_else(|| ExportFormat::from_path(out));
+    let output = stdio::create_output(out)?;
+    let name = out.display().to_string();
+    let written = export_to(stdio::open_archive(games)?, format, output, &name)?;
+    if stdio::is_stdio(out) {
+        eprintln!("Exported {} games", written);
+    } else {
+        println!("Exported {} games to {}", written, out.display());
+    }
+    Ok(())
+}
+
+/// Serves the JSON-RPC protocol of [`jsonrpc`] on stdin and stdout until
+/// stdin is closed, with `bot` as the bot of the session.
+pub fn run_jsonrpc(args: &CliArgs, bot: &str) -> Result<()> {
+    let mut session = jsonrpc::JsonRpcSession::new(new_game(args)?, default_bots(), bot);
+    jsonrpc::serve(
+        &mut session,
+        std::io::stdin().lock(),
+        std::io::stdout().lock(),
+    )?;
+    Ok(())
+}
+
+/// Creates the game described by the board options of the command line, or
+/// the position after the opening given with `--opening`.
+pub fn new_game(args: &CliArgs) -> Result<GameY> {
+    if let Some(name) = &args.opening {
+        let library = OpeningLibrary::load(&args.openings_file())?;
+        let opening = library
+            .find(name)
+            .ok_or_else(|| anyhow::anyhow!(Message::OpeningNotFound { name }.text(args.lang)))?;
+        return opening.game();
+    }
+    GameY::check_board_size(args.size)?;
+    let handicap = GameY::handicap_points(args.size, args.handicap)?;
+    Ok(game::GameY::with_variant(args.size, args.game, args.ruleset).with_handicap(&handicap)?)
+}
+
+/// Plays `moves` without the interactive prompt and prints the result.
+///
+/// Moves are read from stdin when `moves` is `None`. The final board is
+/// printed first if `print_final` is set. Returns true if every move was
+/// played, so callers can set the exit status.
+pub fn run_replay(args: &CliArgs, moves: Option<&str>, print_final: bool) -> Result<bool> {
+    let moves = match moves {
+        Some(moves) => moves.to_string(),
+        None => std::io::read_to_string(std::io::stdin())?,
+    };
+    let mut game = new_game(args)?;
+    let result = play_moves(&mut game, &moves, args.lang);
+    if print_final {
+        println!("{}", game.render(&RenderOptions::default()));
+    }
+    if let Err(message) = &result {
+        eprintln!("{}", message);
+    }
+    let message = match game.status() {
+        GameStatus::Finished { winner } => Message::GameOver {
+            winner: winner.to_string(),
+        },
+        GameStatus::Ongoing { next_player } => Message::NextPlayer {
+            player: next_player.to_string(),
+        },
+    };
+    println!("{}", message.text(args.lang));
+    Ok(result.is_ok())
+}
+
+/// Prints the names of the bots that can play, one per line.
+pub fn run_bots() {
+    for name in default_bots().names() {
+        println!("{}", name);
+    }
+}
+
+/// Solves the position saved at `position`, or the position of
+/// [`new_game`] if there is none, and prints who wins with perfect play.
+pub fn run_solve(args: &CliArgs, position: Option<&Path>) -> Result<()> {
+    let game = match position {
+        Some(position) => stdio::load_game(position)?,
+        None => new_game(args)?,
+    };
+    let solution = solve(&game)?;
+    println!("{}", solution_text(&game, &solution, args.lang));
+    Ok(())
+}
+
+/// Lists, shows, adds or removes openings of the library, see
+/// [`OpeningLibrary`].
+///
+/// New openings are played on the game of [`new_game`], so they can extend
+/// another opening given with `--opening`; the library file is only written
+/// when it changes.
+pub fn run_openings(args: &CliArgs, action: &OpeningsAction) -> Result<()> {
+    let lang = args.lang;
+    let file = args.openings_file();
+    let mut library = OpeningLibrary::load(&file)?;
+    match action {
+        OpeningsAction::List => {
+            for opening in library.openings() {
+                let message = Message::OpeningSummary {
+                    name: &opening.name,
+                    size: opening.position.size(),
+                    moves: opening.moves.len(),
+                    description: &opening.description,
+                };
+                println!("{}", message.text(lang));
+            }
+        }
+        OpeningsAction::Show { name } => {
+            let opening = library
+                .find(name)
+                .ok_or_else(|| anyhow::anyhow!(Message::OpeningNotFound { name }.text(lang)))?;
+            let game = opening.game()?;
+            for line in history_lines(&game) {
+                println!("{}", line);
+            }
+            println!("{}", game.render(&RenderOptions::default()));
+        }
+        OpeningsAction::Add {
+            name,
+            moves,
+            description,
+        } => {
+            let mut game = new_game(args)?;
+            play_moves(&mut game, moves, lang).map_err(anyhow::Error::msg)?;
+            library.add(Opening::from_game(name, description, &game)?, lang)?;
+            library.save(&file)?;
+            let message = Message::OpeningAdded {
+                name,
+                file: file.display().to_string(),
+            };
+            println!("{}", message.text(lang));
+        }
+        OpeningsAction::Remove { name } => {
+            library.remove(name, lang)?;
+            library.save(&file)?;
+            println!("{}", Message::OpeningRemoved { name }.text(lang));
+        }
+    }
+    Ok(())
+}
+
+/// Plays whitespace-separated cell indices on `game`, each by the player
+/// whose turn it is.
+///
+/// A move may end with a glyph judging it, as in `4!` or `7??`, and comments
+/// in braces, as in `{takes the corner}`, are attached after the last move
+/// played; both are kept as annotations of the game.
+///
+/// Stops at the first move that cannot be played, returning a message in
+/// the given language that names the move; the moves before it stay played.
+pub fn play_moves(game: &mut GameY, moves: &str, lang: Lang) -> Result<(), String> {
+    let mut number = 0;
+    for token in move_list_tokens(moves) {
+        if let Some(comment) = token.strip_prefix('{') {
+            let comment = comment.strip_suffix('}').unwrap_or(comment).trim();
+            game.add_annotation(None, comment);
+            continue;
+        }
+        number += 1;
+        let rejected = |error: String| {
+            Message::MoveRejected {
+                number,
+                token,
+                error,
+            }
+            .text(lang)
+        };
+        let Some(player) = game.next_player() else {
+            return Err(rejected(Message::AlreadyOver.text(lang)));
+        };
+        let (index, glyph) = token.split_at(token.find(['!', '?']).unwrap_or(token.len()));
+        let glyph = match glyph {
+            "" => None,
+            glyph => Some(glyph.parse::<Glyph>().map_err(rejected)?),
+        };
+        let idx = parse_idx_in(index, game.total_cells(), lang).map_err(rejected)?;
+        let coords = game.cell_coords(idx);
+        game.add_move(Movement::Placement { player, coords })
+            .map_err(|e| rejected(e.to_string()))?;
+        if let Some(glyph) = glyph {
+            game.add_annotation(Some(glyph), "");
+        }
+    }
+    Ok(())
+}
+
+/// A background thread that lets the bot think during the human's turn.
+struct Pondering {
+    stop: Arc<AtomicBool>,
+    handle: std::thread::JoinHandle<()>,
+}
+
+impl Pondering {
+    /// Starts pondering on `game` with the given session.
+    fn start(session: Arc<Mutex<Box<dyn BotSession>>>, game: GameY) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let flag = Arc::clone(&stop);
+        let handle = std::thread::spawn(move || {
+            let mut session = session.lock().unwrap_or_else(|e| e.into_inner());
+            session.ponder(&game, &flag);
+        });
+        Pondering { stop, handle }
+    }
+
+    /// Stops pondering and waits for the thread to release the session.
+    fn finish(self) {
+        self.stop.store(true, Ordering::Relaxed);
+        let _ = self.handle.join();
+    }
+}
+
+/// Converts every file to the format `to` in `out_dir`, reporting the
+/// files that cannot be converted and going on with the others.
+///
+/// With `-` as `out_dir`, the converted games are written to stdout one
+/// after the other, and the summary is printed to stderr.
+///
+/// Returns true if every file was converted, so callers can set the exit
+/// status.
+pub fn run_convert(
+    args: &CliArgs,
+    files: &[PathBuf],
+    from: Option<GameFormat>,
+    to: GameFormat,
+    out_dir: &Path,
+) -> Result<bool> {
+    let to_stdout = stdio::is_stdio(out_dir);
+    if !to_stdout {
+        std::fs::create_dir_all(out_dir)?;
+    }
+    let board = new_game(args)?;
+    let mut converted = 0;
+    for file in files {
+        match convert_file(file, from, to, out_dir, &board) {
+            Ok(out) => {
+                converted += 1;
+                tracing::info!("{} -> {}", file.display(), out.display());
+            }
+            Err(e) => eprintln!("{}: {}", file.display(), e),
+        }
+    }
+    let summary = format!("Converted {} of {} files", converted, files.len());
+    if to_stdout {
+        eprintln!("{}", summary);
+    } else {
+        println!("{} to {}", summary, out_dir.display());
+    }
+    Ok(converted == files.len())
+}
+
+/// Converts one file, returning the file written; stdin is named `stdin`.
+fn convert_file(
+    file: &Path,
+    from: Option<GameFormat>,
+    to: GameFormat,
+    out_dir: &Path,
+    board: &GameY,
+) -> Result<PathBuf> {
+    let from = from
+        .or_else(|| GameFormat::from_path(file))
+        .ok_or_else(|| anyhow::anyhow!("unknown format, set it with --from"))?;
+    let game = from.read(stdio::open_input(file)?, board)?;
+    if stdio::is_stdio(out_dir) {
+        to.write(&game, std::io::stdout().lock())?;
+        return Ok(out_dir.to_path_buf());
+    }
+
+    let mut name = match stdio::is_stdio(file) {
+        true => PathBuf::from("stdin"),
+        false => file
+            .file_name()
+            .map(PathBuf::from)
+            .ok_or_else(|| anyhow::anyhow!("not a file"))?,
+    };
+    if Compression::from_path(&name) != Compression::None {
+        name.set_extension("");
+    }
+    if GameFormat::from_path(&name).is_some() {
+        name.set_extension("");
+    }
+    let out = out_dir.join(name).with_extension(to.extension());
+    if out.exists()
+        && !stdio::is_stdio(file)
+        && std::fs::canonicalize(&out)? == std::fs::canonicalize(file)?
+    {
+        anyhow::bail!("the converted file would replace it");
+    }
+    let mut contents = Vec::new();
+    to.write(&game, &mut contents)?;
+    std::fs::write(&out, contents)?;
+    Ok(out)
+}
+
+/// Verifies each game file with [`verify_file`], printing the result it
+/// confirms or its first discrepancy.
+///
+/// Returns true if every game was verified, so callers can set the exit
+/// status.
+pub fn run_verify(
+    args: &CliArgs,
+    files: &[PathBuf],
+    from: Option<GameFormat>,
+    result: Option<&str>,
+) -> Result<bool> {
+    stdio::check_stdin_once(&files.iter().map(PathBuf::as_path).collect::<Vec<_>>())?;
+    let board = new_game(args)?;
+    let mut verified = 0;
+    for file in files {
+        match verify_file(file, from, result, &board) {
+            Ok(summary) => {
+                verified += 1;
+                println!("{}: {}", file.display(), summary);
+            }
+            Err(e) => println!("{}: {}", file.display(), e),
+        }
+    }
+    println!("Verified {} of {} games", verified, files.len());
+    Ok(verified == files.len())
+}
+
+/// Replays the game of `file` move by move on a fresh board and checks the
+/// claimed result, `claimed` or else the one recorded in the file, against
+/// the result the engine finds.
+///
+/// Returns a summary of the verified game, or the first discrepancy as the
+/// error: an illegal move, moves that do not lead to the recorded position,
+/// or a different result.
+pub fn verify_file(
+    file: &Path,
+    from: Option<GameFormat>,
+    claimed: Option<&str>,
+    board: &GameY,
+) -> Result<String> {
+    let game = read_game(file, from, board)?;
+    let moves = game.move_count();
+    let claimed = claimed.or(game.metadata().result.as_deref());
+    let found = game.result();
+    match (claimed, found) {
+        (None, None) => Ok(format!("{} legal moves, the game is not over", moves)),
+        (None, Some(found)) => Ok(format!(
+            "{} legal moves, no result claimed, {}",
+            moves, found
+        )),
+        (Some(claimed), None) => anyhow::bail!(
+            "the claimed result is {} but the game is not over after {} moves",
+            claimed,
+            moves
+        ),
+        (Some(claimed), Some(found)) if same_result(claimed, &found)? => {
+            Ok(format!("{} legal moves, result {} confirmed", moves, found))
+        }
+        (Some(claimed), Some(found)) => anyhow::bail!(
+            "the claimed result is {} but the engine finds {} after {} moves",
+            claimed,
+            found,
+            moves
+        ),
+    }
+}
+
+/// Reads the game of `file` in format `from`, or the format of its
+/// extension, replaying its moves on a fresh board; move lists are played
+/// on `board`.
+fn read_game(file: &Path, from: Option<GameFormat>, board: &GameY) -> Result<GameY> {
+    let from = from
+        .or_else(|| GameFormat::from_path(file))
+        .ok_or_else(|| anyhow::anyhow!("unknown format, set it with --from"))?;
+    let record = std::io::read_to_string(stdio::open_input(file)?)?;
+    Ok(match from {
+        GameFormat::Yen | GameFormat::CompactYen => replay_yen(&YEN::from_json(&record)?)?,
+        GameFormat::Moves => {
+            let mut game = board.clone();
+            game.play_move_list(&record)?;
+            game
+        }
+        GameFormat::Sgf => GameY::from_sgf(&record)?,
+    })
+}
+
+/// Replays the history of a YEN on an empty board of its variant, failing
+/// at the first move that cannot be played or if the moves do not lead to
+/// its layout.
+fn replay_yen(yen: &YEN) -> Result<GameY> {
+    GameY::check_board_size(yen.size())?;
+    let mut game = GameY::with_variant(yen.size(), yen.topology(), yen.ruleset())
+        .with_handicap(yen.handicap())?;
+    for (number, record) in yen.history().iter().enumerate() {
+        Movement::try_from(record.clone())
+            .and_then(|movement| game.add_move(movement))
+            .map_err(|e| anyhow::anyhow!("move {} is illegal: {}", number + 1, e))?;
+    }
+    if YEN::from(&game).layout() != yen.layout() {
+        anyhow::bail!(
+            "the {} moves lead to {} instead of the recorded layout {}",
+            yen.history().len(),
+            YEN::from(&game).layout(),
+            yen.layout()
+        );
+    }
+    game.set_metadata(yen.metadata().clone());
+    Ok(game)
+}
+
+/// Returns whether the claimed result, e.g. `b+r`, is the result `found` by
+/// the engine; a claim without a reason only names the winner.
+fn same_result(claimed: &str, found: &str) -> Result<bool> {
+    let claimed = claimed.trim().to_ascii_uppercase();
+    let Some((winner, reason)) = claimed
+        .split_once('+')
+        .filter(|(winner, _)| ["B", "W"].contains(winner))
+    else {
+        anyhow::bail!(
+            "invalid claimed result {}, expected e.g. B+ or W+R",
+            claimed
+        );
+    };
+    let reason = if reason == "RESIGN" { "R" } else { reason };
+    let (found_winner, found_reason) = found.split_once('+').unwrap_or((found, ""));
+    Ok(winner == found_winner && (reason.is_empty() || reason == found_reason))
+}
+
+/// Validates each YEN file, printing its problems with their codes.
+///
+/// Returns true if every file is valid, so callers can set the exit status.
+pub fn run_validate(files: &[PathBuf], lang: Lang) -> bool {
+    let mut all_valid = true;
+    for file in files {
+        let issues = validate_file(file);
+        if issues.is_empty() {
+            let message = Message::FileValid {
+                file: file.display().to_string(),
+            };
+            println!("{}", message.text(lang));
+            continue;
+        }
+        all_valid = false;
+        let message = Message::FileInvalid {
+            file: file.display().to_string(),
+            problems: issues.len(),
+        };
+        println!("{}", message.text(lang));
+        for issue in issues {
+            println!("  {}", issue);
+        }
+    }
+    all_valid
+}
+
+/// Strictly validates the YEN stored in a file, or read from stdin for `-`.
+///
+/// A file that cannot be read is reported as a single `INVALID_JSON` issue.
+pub fn validate_file(path: &Path) -> Vec<ValidationIssue> {
+    let json = match stdio::is_stdio(path) {
+        true => std::io::read_to_string(std::io::stdin()),
+        false => std::fs::read_to_string(path),
+    };
+    match json {
+        Ok(json) => validate_yen_json(&json),
+        Err(err) => vec![ValidationIssue::new(
+            ValidationCode::InvalidJson,
+            format!("cannot read file: {}", err),
+        )],
+    }
+}
+
+/// Runs a command of the player to move and updates game state, returning
+/// whether the game goes on.
+fn process_input(
+    command: Command,
+    game: &mut GameY,
+    state: &mut CliState,
+    args: &CliArgs,
+    play: &PlayArgs,
+    bot: &dyn YBot,
+    session: &mut dyn BotSession,
+) -> Result<ControlFlow<()>> {
+    let lang = args.lang;
+    let Some(player) = game.next_player() else {
+        return Ok(ControlFlow::Continue(()));
+    };
+    let moves = game.move_count();
+    match command {
+        Command::Place { idx } => {
+            state.history.live();
+            handle_place_command(game, idx, player, args, play, session, &state.notifications);
+        }
+        Command::Takeback => {
+            state.history.live();
+            if !game.history().iter().any(|m| m.player() == player) {
+                let error = GameYError::NothingToTakeBack { player }.to_string();
+                println!("{}", Message::TakebackError { error }.text(lang));
+            } else if play.mode == Mode::Computer {
+                let message = Message::BotAcceptsTakeback { bot: bot.name() };
+                println!("{}", message.text(lang));
+                take_back(game, player, lang);
+            } else {
+                state.takeback = Some(player);
+            }
+        }
+        Command::Comment { glyph, text } => {
+            game.add_annotation(glyph, text);
+            state.unsaved = true;
+        }
+        Command::Resign => {
+            state.history.live();
+            let movement = Movement::Action {
+                player,
+                action: GameAction::Resign,
+            };
+            apply_move(game, movement, lang, |error| Message::ResignError { error });
+        }
+        Command::Show3DCoords => {
+            state.render_options.show_3d_coords = !state.render_options.show_3d_coords;
+        }
+        Command::ShowIdx => {
+            state.render_options.show_idx = !state.render_options.show_idx;
+        }
+        Command::ShowColors => {
+            state.render_options.show_colors = !state.render_options.show_colors;
+        }
+        Command::ShowEdgeLabels => {
+            state.render_options.show_edge_labels = !state.render_options.show_edge_labels;
+        }
+        Command::HighlightLastMove => {
+            state.render_options.highlight_last_move = !state.render_options.highlight_last_move;
+        }
+        Command::ShowMoveNumbers => {
+            state.render_options.show_move_numbers = !state.render_options.show_move_numbers;
+        }
+        Command::Rotate => {
+            state.render_options.view = next_view(state.render_options.view, game.topology());
+        }
+        Command::Eval { on } => {
+            state.eval = on.unwrap_or(!state.eval);
+        }
+        Command::Describe => {
+            let shown = match state.history.shown() {
+                Some(number) => game.position_after(number)?,
+                None => game.clone(),
+            };
+            print!("{}", shown.describe());
+        }
+        Command::History => {
+            let lines = history_lines(game);
+            if lines.is_empty() {
+                println!("{}", Message::NoMovesYet.text(lang));
+            }
+            for line in lines {
+                println!("{}", line);
+            }
+        }
+        Command::ShowMove { number } => {
+            let total = game.move_count();
+            if number > total {
+                println!(
+                    "{}",
+                    Message::MoveNumberOutOfRange { number, total }.text(lang)
+                );
+            } else {
+                state.history.show(game, number);
+            }
+        }
+        Command::Back => state.history.back(game),
+        Command::Forward => state.history.forward(game),
+        Command::Explain => {
+            explain_bot_move(game, bot, lang);
+        }
+        Command::Solve => {
+            print_solution(game, lang);
+        }
+        Command::Help => {
+            println!("{}", Message::Help.text(lang));
+        }
+        Command::Exit => {
+            println!("{}", Message::Exiting.text(lang));
+            return Ok(ControlFlow::Break(()));
+        }
+        Command::None => {
+            println!("{}", Message::NoCommand.text(lang));
+        }
+        Command::Error { message } => {
+            println!("{}", Message::ParseError { error: message }.text(lang));
+        }
+        Command::Save { filename } => {
+            save_game(game, &filename, args, play)?;
+            state.unsaved = false;
+            tracing::info!("Game saved to {}", filename);
+        }
+        Command::Dump { filename } => {
+            let json = game.to_state_json()?;
+            match filename {
+                Some(filename) => {
+                    std::fs::write(&filename, json)?;
+                    tracing::info!("Game state dumped to {}", filename);
+                }
+                None => println!("{}", json),
+            }
+        }
+        Command::Load { filename } => {
+            let path = std::path::Path::new(&filename);
+            *game = if play.verify {
+                GameY::load_verified(path, play.key.as_deref().map(str::as_bytes))?
+            } else {
+                GameY::load_from_file(path)?
+            };
+            state.history.live();
+            state.unsaved = false;
+            tracing::info!("Game loaded from {}", filename);
+            return Ok(ControlFlow::Continue(()));
+        }
+    }
+    // Moves, resignations and takebacks change the history
+    if game.move_count() != moves {
+        state.unsaved = true;
+    }
+    Ok(ControlFlow::Continue(()))
+}
+
+/// Parses a user input string into a Command.
+///
+/// # Arguments
+/// * `input` - The raw input string from the user
+/// * `bound` - The upper bound for valid cell indices (total cells on board)
+///
+/// # Returns
+/// A `Command` variant representing the parsed action, with error messages
+/// in English; see [`parse_command_in`] for other languages.
+pub fn parse_command(input: &str, bound: u32) -> Command {
+    parse_command_in(input, bound, Lang::En)
+}
+
+/// Parses a user input string into a Command, writing error messages in
+/// the given language.
+pub fn parse_command_in(input: &str, bound: u32, lang: Lang) -> Command {
+    parse_command_with(input, lang, |part| parse_idx_in(part, bound, lang))
+}
+
+/// Parses a user input string into a Command for `game`, where cells can
+/// also be written as `x,y,z` coordinates or by name; see [`parse_cell_in`].
+pub fn parse_command_on(input: &str, game: &GameY, lang: Lang) -> Command {
+    parse_command_with(input, lang, |part| parse_cell_in(part, game, lang))
+}
+
+/// Parses a user input string into a Command, reading cells with
+/// `parse_cell`.
+fn parse_command_with(
+    input: &str,
+    lang: Lang,
+    parse_cell: impl Fn(&str) -> Result<u32, String>,
+) -> Command {
+    let place = |part: &str| match parse_cell(part) {
+        Ok(idx) => Command::Place { idx },
+        Err(error) => Command::Error {
+            message: Message::ParseError { error }.text(lang),
+        },
+    };
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    if parts.is_empty() {
+        return Command::None;
+    }
+    match parts[0] {
+        "save" | "s" => {
+            if parts.len() < 2 {
+                return Command::Error {
+                    message: Message::FilenameRequired { command: "save" }.text(lang),
+                };
+            }
+            Command::Save {
+                filename: parts[1].to_string(),
+            }
+        }
+        "load" | "l" => {
+            if parts.len() < 2 {
+                return Command::Error {
+                    message: Message::FilenameRequired { command: "load" }.text(lang),
+                };
+            }
+            Command::Load {
+                filename: parts[1].to_string(),
+            }
+        }
+        "dump" => Command::Dump {
+            filename: parts.get(1).map(|f| f.to_string()),
+        },
+        "show" => match parts.get(1).map(|n| n.parse::<usize>()) {
+            Some(Ok(number)) => Command::ShowMove { number },
+            _ => Command::Error {
+                message: Message::MoveNumberRequired.text(lang),
+            },
+        },
+        "history" => Command::History,
+        "back" => Command::Back,
+        "forward" => Command::Forward,
+        "resign" | "r" => Command::Resign,
+        "takeback" => Command::Takeback,
+        "comment" => {
+            let text = input.trim_start()["comment".len()..].trim();
+            let (glyph, text) = match text.split_once(char::is_whitespace) {
+                Some((first, rest)) => match first.parse::<Glyph>() {
+                    Ok(glyph) => (Some(glyph), rest.trim()),
+                    Err(_) => (None, text),
+                },
+                None => match text.parse::<Glyph>() {
+                    Ok(glyph) => (Some(glyph), ""),
+                    Err(_) => (None, text),
+                },
+            };
+            if glyph.is_none() && text.is_empty() {
+                return Command::Error {
+                    message: Message::CommentRequired.text(lang),
+                };
+            }
+            Command::Comment {
+                glyph,
+                text: text.to_string(),
+            }
+        }
+        "explain" => Command::Explain,
+        "solve" => Command::Solve,
+        "help" => Command::Help,
+        "exit" => Command::Exit,
+        "show_colors" => Command::ShowColors,
+        "show_coords" => Command::Show3DCoords,
+        "show_idx" => Command::ShowIdx,
+        "show_labels" => Command::ShowEdgeLabels,
+        "show_last" => Command::HighlightLastMove,
+        "show_numbers" => Command::ShowMoveNumbers,
+        "rotate" => Command::Rotate,
+        "describe" => Command::Describe,
+        "eval" => match parts.get(1).copied() {
+            None => Command::Eval { on: None },
+            Some("on") => Command::Eval { on: Some(true) },
+            Some("off") => Command::Eval { on: Some(false) },
+            Some(_) => Command::Error {
+                message: Message::EvalArgument.text(lang),
+            },
+        },
+        "place" => match parts.get(1) {
+            Some(part) => place(part),
+            None => Command::Error {
+                message: Message::CellRequired.text(lang),
+            },
+        },
+        str => place(str),
+    }
+}
+
+/// Represents a parsed CLI command.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    /// Place a piece at the given cell index.
+    Place { idx: u32 },
+    /// Resign from the game.
+    Resign,
+    /// Ask the opponent to take back the last move pair.
+    Takeback,
+    /// Annotate the last move with a glyph, a comment, or both.
+    Comment { glyph: Option<Glyph>, text: String },
+    /// Ask the bot for its suggested move and an explanation.
+    Explain,
+    /// Solve the position exactly (small boards only).
+    Solve,
+    /// List the moves played so far.
+    History,
+    /// Display the board as it was after the given number of moves.
+    ShowMove { number: usize },
+    /// Display the position one move earlier.
+    Back,
+    /// Display the position one move later.
+    Forward,
+    /// No command was entered (empty input).
+    None,
+    /// An error occurred while parsing the command.
+    Error { message: String },
+    /// Save the game to a file.
+    Save { filename: String },
+    /// Load a game from a file.
+    Load { filename: String },
+    /// Dump the full game state as JSON, to stdout or to a file.
+    Dump { filename: Option<String> },
+    /// Toggle display of 3D coordinates.
+    Show3DCoords,
+    /// Toggle display of colors.
+    ShowColors,
+    /// Toggle display of cell indices.
+    ShowIdx,
+    /// Toggle display of edge labels and row/column guides.
+    ShowEdgeLabels,
+    /// Toggle highlighting of the most recent placement.
+    HighlightLastMove,
+    /// Toggle display of move numbers instead of player symbols.
+    ShowMoveNumbers,
+    /// Turn the board as drawn, bringing another side to the bottom.
+    Rotate,
+    /// Describe the position in plain sentences, for screen readers.
+    Describe,
+    /// Show or hide the estimate of who is ahead, or toggle it if `on` is
+    /// `None`.
+    Eval { on: Option<bool> },
+    /// Exit the game.
+    Exit,
+    /// Show help message.
+    Help,
+}
+
+/// Returns the orientation the board is drawn in after `rotate`: the next
+/// rotation of the board, back to the unturned view after the last one.
+fn next_view(view: Symmetry, topology: BoardTopology) -> Symmetry {
+    match (topology, view) {
+        (BoardTopology::Y, Symmetry::Identity) => Symmetry::RotateClockwise,
+        (BoardTopology::Y, Symmetry::RotateClockwise) => Symmetry::RotateCounterclockwise,
+        (BoardTopology::Hex, Symmetry::Identity) => Symmetry::HalfTurn,
+        _ => Symmetry::Identity,
+    }
+}
+
+/// Parses a string as a cell index and validates it's within bounds.
+///
+/// # Arguments
+/// * `part` - The string to parse as a number
+/// * `bound` - The exclusive upper bound (index must be < bound)
+///
+/// # Returns
+/// * `Ok(index)` if parsing succeeds and index is valid
+/// * `Err(message)` if parsing fails or index is out of bounds, in English
+pub fn parse_idx(part: &str, bound: u32) -> Result<u32, String> {
+    parse_idx_in(part, bound, Lang::En)
+}
+
+/// Parses a string as a cell index like [`parse_idx`], writing error
+/// messages in the given language.
+pub fn parse_idx_in(part: &str, bound: u32, lang: Lang) -> Result<u32, String> {
+    let n = part
+        .parse::<u32>()
+        .map_err(|_| Message::InvalidIndex.text(lang))?;
+    if n >= bound {
+        return Err(Message::IndexOutOfBounds {
+            index: n,
+            max: bound - 1,
+        }
+        .text(lang));
+    }
+    Ok(n)
+}
+
+/// Parses a cell of `game` written as a linear index (`4`), as `x,y,z`
+/// coordinates (`2,1,1`) or by its algebraic name (`b3`, see
+/// [`GameY::cell_name`]), and returns its index.
+///
+/// # Returns
+/// * `Ok(index)` if `part` is a cell of the board
+/// * `Err(message)` explaining what is wrong for the format used, in `lang`
+pub fn parse_cell_in(part: &str, game: &GameY, lang: Lang) -> Result<u32, String> {
+    if part.contains(',') {
+        let values: Vec<u32> = part
+            .split(',')
+            .map(|value| value.trim().parse::<u32>())
+            .collect::<Result<_, _>>()
+            .map_err(|_| Message::InvalidCoords.text(lang))?;
+        let [x, y, z] = values[..] else {
+            return Err(Message::InvalidCoords.text(lang));
+        };
+        let coords = Coordinates::new(x, y, z);
+        let size = game.board_size();
+        if values.iter().any(|&v| v >= size) || !game.topology().contains(coords, size) {
+            return Err(Message::CoordsOffBoard { coords: part }.text(lang));
+        }
+        Ok(game.cell_index(coords))
+    } else if part.starts_with(|c: char| c.is_alphabetic()) {
+        let coords = game
+            .cell_by_name(part)
+            .map_err(|_| Message::InvalidCellName { name: part }.text(lang))?;
+        Ok(game.cell_index(coords))
+    } else {
+        parse_idx_in(part, game.total_cells(), lang)
+    }
+}
+
+/// Application logic for a Move command (Human + optional Bot response)
+fn handle_place_command(
+    game: &mut GameY,
+    idx: u32,
+    player: PlayerId,
+    args: &CliArgs,
+    play: &PlayArgs,
+    session: &mut dyn BotSession,
+    notifications: &Notifications,
+) {
+    let coords = game.cell_coords(idx);
+    let movement = Movement::Placement { player, coords };
+    let before = play.teach.then(|| game.clone());
+
+    if apply_move(game, movement, args.lang, |error| Message::MoveError {
+        error,
+    }) {
+        if let Some(before) = &before {
+            print_lessons(before, game, coords, args.lang);
+        }
+        // Only trigger bot if the human move was valid, mode is computer, and game isn't over
+        if play.mode == Mode::Computer && !game.check_game_over() {
+            trigger_bot_move(game, session, args, play, notifications);
+        }
+    } else if play.teach
+        && let Some(lesson) = teach::why_illegal(game, player, coords)
+    {
+        println!("{}", lesson.text(args.lang));
+    }
+}
+
+/// Prints what a beginner should notice about the stone placed at
+/// `coords`, see [`teach::lessons`].
+fn print_lessons(before: &GameY, game: &GameY, coords: Coordinates, lang: Lang) {
+    for lesson in teach::lessons(before, game, coords, lang) {
+        println!("{}", lesson);
+    }
+}
+
+/// AI logic extracted to its own function
+fn trigger_bot_move(
+    game: &mut GameY,
+    session: &mut dyn BotSession,
+    args: &CliArgs,
+    play: &PlayArgs,
+    notifications: &Notifications,
+) {
+    let lang = args.lang;
+    let mut choice = choose_bot_move(game, session, lang);
+    // A bot without moves in an ongoing game has failed, e.g. a remote bot
+    // that timed out: retry, then let the fallback bot play
+    let failed = |choice: &Option<_>| choice.is_none() && !game.available_cells().is_empty();
+    for attempt in 1..=play.bot_retries {
+        if !failed(&choice) {
+            break;
+        }
+        let message = Message::BotRetry {
+            attempt,
+            retries: play.bot_retries,
+        };
+        println!("{}", message.text(lang));
+        choice = choose_bot_move(game, session, lang);
+    }
+    if failed(&choice)
+        && let Some(fallback) = default_bots().find(&play.fallback_bot)
+    {
+        let message = Message::BotFallback {
+            fallback: fallback.name(),
+        };
+        println!("{}", message.text(lang));
+        choice = fallback.choose_move_with_stats(game);
+    }
+    if let Some((bot_coords, stats)) = choice {
+        if play.show_bot_stats {
+            println!("{}", Message::BotStats { stats: &stats }.text(lang));
+        }
+        // Assuming next_player() is safe to unwrap here because the game isn't over
+        if let Some(bot_player) = game.next_player() {
+            let bot_movement = Movement::Placement {
+                player: bot_player,
+                coords: bot_coords,
+            };
+            let before = play.teach.then(|| game.clone());
+            if apply_move(game, bot_movement, lang, |error| Message::BotMoveError {
+                error,
+            }) {
+                let message = Message::BotMoved {
+                    bot: &play.bot,
+                    cell: game.cell_name(bot_coords),
+                };
+                notifications.opponent_moved(&message.text(lang));
+                if let Some(before) = &before {
+                    print_lessons(before, game, bot_coords, lang);
+                }
+            }
+        }
+    } else {
+        println!("{}", Message::NoBotMoves.text(lang));
+    }
+}
+
+/// Asks the session for its move, showing the progress of the search on a
+/// terminal.
+fn choose_bot_move(
+    game: &GameY,
+    session: &mut dyn BotSession,
+    lang: Lang,
+) -> Option<(Coordinates, MoveStats)> {
+    // The thinking line is redrawn in place, so only show it on a terminal
+    let live = std::io::stderr().is_terminal();
+    let mut shown = 0;
+    let mut thinking = |progress: &SearchProgress| {
+        if live {
+            let line = Message::Thinking { progress }.text(lang);
+            eprint!("\r{:<width$}", line, width = shown);
+            shown = line.chars().count();
+        }
+    };
+    let choice = session.choose_move_with_progress(game, &mut thinking, &CancellationToken::new());
+    if shown > 0 {
+        eprint!("\r{:width$}\r", "", width = shown);
+    }
+    choice
+}
+
+/// Asks the bot which move it would play in the current position and why
+fn explain_bot_move(game: &GameY, bot: &dyn YBot, lang: Lang) {
+    match bot.choose_move_with_stats(game) {
+        Some((coords, stats)) => {
+            let message = Message::BotWouldPlay {
+                bot: bot.name(),
+                coords: coords.to_string(),
+            };
+            println!("{}", message.text(lang));
+            let explanation = stats.explanation.or_else(|| bot.explain_move(game, coords));
+            match explanation {
+                Some(explanation) => println!("  {}", explanation),
+                None => println!(
+                    "  {}",
+                    Message::NoExplanation { bot: bot.name() }.text(lang)
+                ),
+            }
+        }
+        None => println!("{}", Message::NoBotMoves.text(lang)),
+    }
+}
+
+/// Prints the exact solution of the position, or why it cannot be solved.
+fn print_solution(game: &GameY, lang: Lang) {
+    match solve(game) {
+        Ok(solution) => println!("{}", solution_text(game, &solution, lang)),
+        Err(e) => println!("{}", e),
+    }
+}
+
+/// Describes the solution of the position of `game`: who wins, and with
+/// which moves if the winner is to move.
+fn solution_text(game: &GameY, solution: &Solution, lang: Lang) -> String {
+    if solution.winning_moves.is_empty() {
+        let message = Message::WinsWithPerfectPlay {
+            player: solution.winner.to_string(),
+        };
+        return message.text(lang);
+    }
+    let moves: Vec<String> = solution
+        .winning_moves
+        .iter()
+        .map(|c| format!("{} ({})", game.cell_index(*c), c))
+        .collect();
+    let message = Message::WinningMoves {
+        player: solution.winner.to_string(),
+        moves: moves.join(", "),
+    };
+    message.text(lang)
+}
+
+/// Generic helper to apply a move and handle the Result printing
+/// Returns true if the move was successful
+fn apply_move(
+    game: &mut GameY,
+    movement: Movement,
+    lang: Lang,
+    error_msg: impl FnOnce(String) -> Message<'static>,
+) -> bool {
+    match game.add_move(movement) {
+        Ok(()) => true,
+        Err(e) => {
+            println!("{}", error_msg(e.to_string()).text(lang));
+            false
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mode_display_computer() {
+        assert_eq!(format!("{}", Mode::Computer), "computer");
+    }
+
+    #[test]
+    fn test_mode_display_human() {
+        assert_eq!(format!("{}", Mode::Human), "human");
+    }
+
+    #[test]
+    fn test_parse_idx_valid() {
+        assert_eq!(parse_idx("5", 10), Ok(5));
+        assert_eq!(parse_idx("0", 10), Ok(0));
+        assert_eq!(parse_idx("9", 10), Ok(9));
+    }
+
+    #[test]
+    fn test_parse_idx_out_of_bounds() {
+        let result = parse_idx("10", 10);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("out of bounds"));
+    }
+
+    #[test]
+    fn test_parse_idx_not_a_number() {
+        let result = parse_idx("abc", 10);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("not a number"));
+    }
+
+    #[test]
+    fn test_parse_idx_negative() {
+        let result = parse_idx("-1", 10);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_command_place() {
+        let cmd = parse_command("5", 10);
+        assert_eq!(cmd, Command::Place { idx: 5 });
+    }
+
+    #[test]
+    fn test_parse_command_resign() {
+        let cmd = parse_command("resign", 10);
+        assert_eq!(cmd, Command::Resign);
+    }
+
+    #[test]
+    fn test_parse_command_explain() {
+        let cmd = parse_command("explain", 10);
+        assert_eq!(cmd, Command::Explain);
+    }
+
+    #[test]
+    fn test_parse_command_help() {
+        let cmd = parse_command("help", 10);
+        assert_eq!(cmd, Command::Help);
+    }
+
+    #[test]
+    fn test_parse_command_exit() {
+        let cmd = parse_command("exit", 10);
+        assert_eq!(cmd, Command::Exit);
+    }
+
+    #[test]
+    fn test_parse_command_show_colors() {
+        let cmd = parse_command("show_colors", 10);
+        assert_eq!(cmd, Command::ShowColors);
+    }
+
+    #[test]
+    fn test_parse_command_show_coords() {
+        let cmd = parse_command("show_coords", 10);
+        assert_eq!(cmd, Command::Show3DCoords);
+    }
+
+    #[test]
+    fn test_parse_command_rotate() {
+        assert_eq!(parse_command("rotate", 10), Command::Rotate);
+    }
+
+    #[test]
+    fn test_parse_command_describe() {
+        assert_eq!(parse_command("describe", 10), Command::Describe);
+    }
+
+    #[test]
+    fn test_parse_command_eval() {
+        assert_eq!(parse_command("eval", 10), Command::Eval { on: None });
+        assert_eq!(
+            parse_command("eval on", 10),
+            Command::Eval { on: Some(true) }
+        );
+        assert_eq!(
+            parse_command("eval off", 10),
+            Command::Eval { on: Some(false) }
+        );
+        assert!(matches!(
+            parse_command("eval maybe", 10),
+            Command::Error { .. }
+        ));
+    }
+
+    #[test]
+    fn test_rotate_cycles_the_sides_at_the_bottom() {
+        let mut view = Symmetry::Identity;
+        let mut seen = Vec::new();
+        for _ in 0..3 {
+            view = next_view(view, BoardTopology::Y);
+            seen.push(view);
+        }
+        assert_eq!(
+            seen,
+            [
+                Symmetry::RotateClockwise,
+                Symmetry::RotateCounterclockwise,
+                Symmetry::Identity
+            ]
+        );
+        let turned = next_view(Symmetry::Identity, BoardTopology::Hex);
+        assert_eq!(turned, Symmetry::HalfTurn);
+        assert_eq!(next_view(turned, BoardTopology::Hex), Symmetry::Identity);
+        // A view kept from a game on another board starts over
+        assert_eq!(
+            next_view(Symmetry::RotateClockwise, BoardTopology::Hex),
+            Symmetry::Identity
+        );
+    }
+
+    #[test]
+    fn test_parse_command_show_idx() {
+        let cmd = parse_command("show_idx", 10);
+        assert_eq!(cmd, Command::ShowIdx);
+    }
+
+    #[test]
+    fn test_parse_command_save() {
+        let cmd = parse_command("save game.json", 10);
+        assert_eq!(
+            cmd,
+            Command::Save {
+                filename: "game.json".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_command_load() {
+        let cmd = parse_command("load game.json", 10);
+        assert_eq!(
+            cmd,
+            Command::Load {
+                filename: "game.json".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_command_save_no_filename() {
+        let cmd = parse_command("save", 10);
+        match cmd {
+            Command::Error { message } => {
+                assert!(message.contains("Filename required"));
+            }
+            _ => panic!("Expected Error command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_command_load_no_filename() {
+        let cmd = parse_command("load", 10);
+        match cmd {
+            Command::Error { message } => {
+                assert!(message.contains("Filename required"));
+            }
+            _ => panic!("Expected Error command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_command_empty() {
+        let cmd = parse_command("", 10);
+        assert_eq!(cmd, Command::None);
+    }
+
+    #[test]
+    fn test_parse_command_whitespace() {
+        let cmd = parse_command("   ", 10);
+        assert_eq!(cmd, Command::None);
+    }
+
+    #[test]
+    fn test_parse_command_invalid_number() {
+        let cmd = parse_command("abc", 10);
+        match cmd {
+            Command::Error { message } => {
+                assert!(message.contains("Error parsing"));
+            }
+            _ => panic!("Expected Error command"),
+        }
+    }
+
+    #[test]
+    fn test_parse_command_out_of_bounds() {
+        let cmd = parse_command("100", 10);
+        match cmd {
+            Command::Error { message } => {
+                assert!(message.contains("out of bounds"));
+            }
+            _ => panic!("Expected Error command"),
+        }
+    }
+
+    #[test]
+    fn test_command_debug() {
+        let cmd = Command::Place { idx: 5 };
+        let debug = format!("{:?}", cmd);
+        assert!(debug.contains("Place"));
+        assert!(debug.contains("5"));
+    }
+
+    #[test]
+    fn test_exit_status() {
+        assert_eq!(ExitStatus::Success.code(), 0);
+        assert_eq!(ExitStatus::InvalidArgs.code(), 2);
+        let err = anyhow::Error::from(GameYError::BindFailed {
+            address: "0.0.0.0:3000".to_string(),
+            message: "address in use".to_string(),
+        });
+        assert_eq!(ExitStatus::of(&err), ExitStatus::BindFailed);
+        assert_eq!(ExitStatus::of(&err).code(), 4);
+        let err = anyhow::Error::from(GameYError::InvalidBoardSize { size: 0 });
+        assert_eq!(ExitStatus::of(&err), ExitStatus::EngineError);
+    }
+
+    #[test]
+    fn test_confirmations() {
+        let dir = tempfile::tempdir().unwrap();
+        let existing = dir.path().join("game.yen");
+        std::fs::write(&existing, "{}").unwrap();
+        let existing = existing.display().to_string();
+        let new = dir.path().join("new.yen").display().to_string();
+        let mut state = CliState::default();
+        let mut game = GameY::new(2);
+
+        assert_eq!(confirmation(&Command::Exit, &game, &state), None);
+        let load = Command::Load {
+            filename: existing.clone(),
+        };
+        assert_eq!(confirmation(&load, &game, &state), None);
+        let save = Command::Save {
+            filename: existing.clone(),
+        };
+        assert_eq!(
+            confirmation(&save, &game, &state),
+            Some(Message::ConfirmOverwrite { file: &existing })
+        );
+        let save = Command::Save { filename: new };
+        assert_eq!(confirmation(&save, &game, &state), None);
+        let dump = Command::Dump {
+            filename: Some(existing.clone()),
+        };
+        assert!(confirmation(&dump, &game, &state).is_some());
+        assert_eq!(
+            confirmation(&Command::Dump { filename: None }, &game, &state),
+            None
+        );
+
+        state.unsaved = true;
+        assert_eq!(
+            confirmation(&Command::Exit, &game, &state),
+            Some(Message::ConfirmExit)
+        );
+        assert!(confirmation(&load, &game, &state).is_some());
+
+        // Teaching mode asks before a move that lets the opponent win
+        game.play_move_list("1").unwrap();
+        let place = Command::Place { idx: 0 };
+        assert_eq!(confirmation(&place, &game, &state), None);
+        state.teach = true;
+        assert_eq!(
+            confirmation(&place, &game, &state),
+            Some(Message::ConfirmGiveaway {
+                cell: game.cell_name(Coordinates::new(0, 1, 0))
+            })
+        );
+    }
+
+    #[test]
+    fn test_exit_returns_through_the_loop_and_moves_are_unsaved_until_saved() {
+        let args = CliArgs::parse_from(["gamey", "--size", "3"]);
+        let play = PlayArgs::default();
+        let bot = default_bots().find("random_bot").unwrap();
+        let mut session = start_session(Arc::clone(&bot));
+        let mut game = new_game(&args).unwrap();
+        let mut state = CliState::default();
+        let mut run = |command, game: &mut GameY, state: &mut CliState| {
+            process_input(
+                command,
+                game,
+                state,
+                &args,
+                &play,
+                bot.as_ref(),
+                session.as_mut(),
+            )
+            .unwrap()
+        };
+
+        assert!(run(Command::Help, &mut game, &mut state).is_continue());
+        assert!(!state.unsaved);
+        assert!(run(Command::Place { idx: 0 }, &mut game, &mut state).is_continue());
+        assert!(state.unsaved);
+
+        let dir = tempfile::tempdir().unwrap();
+        let filename = dir.path().join("game.yen").display().to_string();
+        assert!(run(Command::Save { filename }, &mut game, &mut state).is_continue());
+        assert!(!state.unsaved);
+        assert!(run(Command::Exit, &mut game, &mut state).is_break());
+    }
+
+    #[test]
+    fn test_interrupting_offers_to_save_a_game_that_loads_back() {
+        let args = CliArgs::parse_from(["gamey", "--size", "3"]);
+        let play = PlayArgs::default();
+        let bot = default_bots().find("random_bot").unwrap();
+        let mut session = start_session(Arc::clone(&bot));
+        let mut game = new_game(&args).unwrap();
+        let mut state = CliState::default();
+        let never = |_: &str| -> rustyline::Result<String> { panic!("nothing to save") };
+        interrupt(never, &game, &state, &args, &play);
+
+        let place = Command::Place { idx: 0 };
+        let flow = process_input(
+            place,
+            &mut game,
+            &mut state,
+            &args,
+            &play,
+            bot.as_ref(),
+            session.as_mut(),
+        );
+        assert!(flow.unwrap().is_continue());
+        assert!(state.unsaved);
+        let yes = PlayArgs {
+            yes: true,
+            ..PlayArgs::default()
+        };
+        interrupt(never, &game, &state, &args, &yes);
+
+        // Another Ctrl-C or an empty answer leaves without saving
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("game.yen");
+        let cancel = |_: &str| Err(ReadlineError::Interrupted);
+        interrupt(cancel, &game, &state, &args, &play);
+        interrupt(|_| Ok(" ".to_string()), &game, &state, &args, &play);
+        assert!(!file.exists());
+
+        let answer = file.display().to_string();
+        interrupt(|_| Ok(answer), &game, &state, &args, &play);
+        let loaded = GameY::load_from_file(&file).unwrap();
+        assert_eq!(loaded.move_count(), game.move_count());
+        assert_eq!(loaded.position(), game.position());
+    }
+
+    #[test]
+    fn test_save_game_signs_with_the_key() {
+        let args = CliArgs::parse_from(["gamey", "--size", "3"]);
+        let play = PlayArgs::parse_from(["play", "--key", "secret"]);
+        let game = new_game(&args).unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("game.yen");
+        save_game(&game, &file.display().to_string(), &args, &play).unwrap();
+        assert!(GameY::load_verified(&file, Some(b"secret")).is_ok());
+        assert!(GameY::load_verified(&file, Some(b"other")).is_err());
+    }
+}
+
@@ -0,0 +1,217 @@
+//! A library of named openings to start games from.
+//!
+//! Each opening is stored as the YEN of its starting position followed by
+//! the cell indices of its moves, so it keeps the board options it was made
+//! with. The library ships with a few lines on the default board and keeps
+//! the openings added by the user in a JSON file, see
+//! [`default_openings_file`].
+
+use crate::{Lang, Message};
+use anyhow::{Result, anyhow};
+use gamey_core::{Coordinates, GameY, Movement, YEN};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// A named opening: a starting position and the moves played from it.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Opening {
+    /// The name used to select the opening, e.g. `center-3`.
+    pub name: String,
+    /// A short description of the idea of the opening.
+    #[serde(default)]
+    pub description: String,
+    /// The position the moves are played from, usually an empty board.
+    pub position: YEN,
+    /// The cell indices of the moves, played alternately.
+    pub moves: Vec<u32>,
+}
+
+impl Opening {
+    /// Creates an opening from the moves of `game`, played from the position
+    /// it started at.
+    pub fn from_game(name: &str, description: &str, game: &GameY) -> Result<Self> {
+        let start = game.position_after(0)?;
+        let moves = game
+            .history()
+            .iter()
+            .filter_map(|movement| match movement {
+                Movement::Placement { coords, .. } => Some(game.cell_index(*coords)),
+                Movement::Action { .. } => None,
+            })
+            .collect();
+        Ok(Opening {
+            name: name.to_string(),
+            description: description.to_string(),
+            position: YEN::from(&start),
+            moves,
+        })
+    }
+
+    /// Returns the game after the moves of the opening.
+    pub fn game(&self) -> Result<GameY> {
+        let mut game = GameY::try_from(self.position.clone())?;
+        for &index in &self.moves {
+            if index >= game.total_cells() {
+                return Err(anyhow!("Cell index {} is off the board", index));
+            }
+            let player = game
+                .next_player()
+                .ok_or_else(|| anyhow!("The game is over before move {}", index))?;
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })?;
+        }
+        Ok(game)
+    }
+}
+
+/// The built-in openings and the ones added by the user.
+#[derive(Debug, Clone)]
+pub struct OpeningLibrary {
+    builtin: Vec<Opening>,
+    user: Vec<Opening>,
+}
+
+impl OpeningLibrary {
+    /// Loads the built-in openings and the user openings stored in `file`;
+    /// a missing file holds no openings.
+    pub fn load(file: &Path) -> Result<Self> {
+        let user = match std::fs::read_to_string(file) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| anyhow!("Invalid openings file {}: {}", file.display(), e))?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(anyhow!("Cannot read {}: {}", file.display(), e)),
+        };
+        Ok(OpeningLibrary {
+            builtin: builtin_openings(),
+            user,
+        })
+    }
+
+    /// Writes the user openings to `file`.
+    pub fn save(&self, file: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(&self.user)?;
+        std::fs::write(file, json).map_err(|e| anyhow!("Cannot write {}: {}", file.display(), e))
+    }
+
+    /// Returns every opening, built-in ones first.
+    pub fn openings(&self) -> impl Iterator<Item = &Opening> {
+        self.builtin.iter().chain(&self.user)
+    }
+
+    /// Returns whether `name` is a built-in opening.
+    pub fn is_builtin(&self, name: &str) -> bool {
+        self.builtin.iter().any(|opening| opening.name == name)
+    }
+
+    /// Finds an opening by name.
+    pub fn find(&self, name: &str) -> Option<&Opening> {
+        self.openings().find(|opening| opening.name == name)
+    }
+
+    /// Adds a user opening, rejecting names already in the library.
+    pub fn add(&mut self, opening: Opening, lang: Lang) -> Result<()> {
+        if self.find(&opening.name).is_some() {
+            let message = Message::OpeningExists {
+                name: &opening.name,
+            };
+            return Err(anyhow!(message.text(lang)));
+        }
+        self.user.push(opening);
+        Ok(())
+    }
+
+    /// Removes a user opening; built-in openings cannot be removed.
+    pub fn remove(&mut self, name: &str, lang: Lang) -> Result<()> {
+        if self.is_builtin(name) {
+            return Err(anyhow!(Message::OpeningIsBuiltin { name }.text(lang)));
+        }
+        let count = self.user.len();
+        self.user.retain(|opening| opening.name != name);
+        if self.user.len() == count {
+            return Err(anyhow!(Message::OpeningNotFound { name }.text(lang)));
+        }
+        Ok(())
+    }
+}
+
+/// Returns the file holding the user openings: `.gamey-openings.json` in
+/// the home directory, or in the current directory when there is no home.
+pub fn default_openings_file() -> PathBuf {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    home.unwrap_or_default().join(".gamey-openings.json")
+}
+
+/// Returns the openings shipped with the engine, all on the default board
+/// of size 7.
+pub fn builtin_openings() -> Vec<Opening> {
+    let line = |name: &str, description: &str, cells: &[(u32, u32, u32)]| {
+        let mut game = GameY::new(7);
+        for &(x, y, z) in cells {
+            let player = game.next_player().expect("openings do not end the game");
+            let coords = Coordinates::new(x, y, z);
+            game.add_move(Movement::Placement { player, coords })
+                .expect("openings only play empty cells");
+        }
+        Opening::from_game(name, description, &game).expect("openings start from a new game")
+    };
+    vec![
+        line("center-1", "Take the center of the board", &[(2, 2, 2)]),
+        line(
+            "center-3",
+            "Center, a reply next to it and a block towards the far side",
+            &[(2, 2, 2), (3, 2, 1), (2, 3, 1)],
+        ),
+        line(
+            "edge-1",
+            "Start on the middle of a side, leaving the center open",
+            &[(3, 3, 0)],
+        ),
+        line(
+            "corner-2",
+            "Take a corner and let the opponent have the center",
+            &[(6, 0, 0), (2, 2, 2)],
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_openings_are_playable() {
+        let openings = builtin_openings();
+        assert!(openings.iter().any(|opening| opening.name == "center-3"));
+        for opening in &openings {
+            let game = opening.game().unwrap();
+            assert_eq!(game.history().len(), opening.moves.len());
+            assert!(!game.check_game_over());
+        }
+    }
+
+    #[test]
+    fn test_user_openings_are_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("openings.json");
+        let mut library = OpeningLibrary::load(&file).unwrap();
+        let mut game = GameY::new(4);
+        for index in [3, 5] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        let opening = Opening::from_game("mine", "", &game).unwrap();
+        library.add(opening.clone(), Lang::En).unwrap();
+        assert!(library.add(opening, Lang::En).is_err());
+        assert!(library.remove("center-1", Lang::En).is_err());
+        library.save(&file).unwrap();
+
+        let mut library = OpeningLibrary::load(&file).unwrap();
+        let game = library.find("mine").unwrap().game().unwrap();
+        assert_eq!(game.board_size(), 4);
+        assert_eq!(game.history().len(), 2);
+        library.remove("mine", Lang::En).unwrap();
+        assert!(library.remove("mine", Lang::En).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! The prompt of the interactive game.
+//!
+//! Tab completes the command being typed, and the file name after `save`,
+//! `load` and `dump`. The commands entered are kept in a history file in
+//! the [`config_dir`], so they can be recalled with the arrow keys in later
+//! sessions.
+
+use anyhow::Result;
+use rustyline::completion::{Completer, FilenameCompleter, Pair};
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::history::DefaultHistory;
+use rustyline::validate::Validator;
+use rustyline::{Context, Editor, Helper};
+use std::path::{Path, PathBuf};
+
+/// The commands of the interactive game, as completed by the prompt; the
+/// short aliases `s`, `l` and `r` are left out.
+pub const COMMANDS: &[&str] = &[
+    "back",
+    "comment",
+    "describe",
+    "dump",
+    "eval",
+    "exit",
+    "explain",
+    "forward",
+    "help",
+    "history",
+    "load",
+    "place",
+    "resign",
+    "rotate",
+    "save",
+    "show",
+    "show_colors",
+    "show_coords",
+    "show_idx",
+    "show_labels",
+    "show_last",
+    "show_numbers",
+    "solve",
+    "takeback",
+];
+
+/// The commands whose argument is a file name.
+const FILE_COMMANDS: &[&str] = &["save", "s", "load", "l", "dump"];
+
+/// The line editor of the interactive game.
+pub type Prompt = Editor<CommandCompleter, DefaultHistory>;
+
+/// Completes commands and the file names they take.
+pub struct CommandCompleter {
+    files: FilenameCompleter,
+}
+
+impl CommandCompleter {
+    /// Creates a completer of the [`COMMANDS`].
+    pub fn new() -> Self {
+        CommandCompleter {
+            files: FilenameCompleter::new(),
+        }
+    }
+}
+
+impl Default for CommandCompleter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Completer for CommandCompleter {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let typed = line[..pos].trim_start();
+        let start = pos - typed.len();
+        match typed.split_once(char::is_whitespace) {
+            None => {
+                let commands = COMMANDS
+                    .iter()
+                    .filter(|command| command.starts_with(typed))
+                    .map(|command| Pair {
+                        display: command.to_string(),
+                        replacement: command.to_string(),
+                    })
+                    .collect();
+                Ok((start, commands))
+            }
+            Some((command, _)) if FILE_COMMANDS.contains(&command) => {
+                self.files.complete_path(line, pos)
+            }
+            Some(_) => Ok((pos, Vec::new())),
+        }
+    }
+}
+
+impl Hinter for CommandCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for CommandCompleter {}
+
+impl Validator for CommandCompleter {}
+
+impl Helper for CommandCompleter {}
+
+/// Returns a prompt completing commands, with the commands of previous
+/// sessions read from `history` if the file exists.
+pub fn new_prompt(history: &Path) -> Result<Prompt> {
+    let mut prompt = Prompt::new()?;
+    prompt.set_helper(Some(CommandCompleter::new()));
+    if history.is_file()
+        && let Err(e) = prompt.load_history(history)
+    {
+        tracing::warn!("Cannot read the history of commands: {}", e);
+    }
+    Ok(prompt)
+}
+
+/// Writes the commands entered at `prompt` to `history`, creating its
+/// directory if needed.
+pub fn save_history(prompt: &mut Prompt, history: &Path) -> Result<()> {
+    if let Some(dir) = history.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    prompt.save_history(history)?;
+    Ok(())
+}
+
+/// Returns the directory of the settings of gamey: `gamey` in
+/// `$XDG_CONFIG_HOME`, or in `.config` in the home directory, or in the
+/// current directory when there is no home.
+pub fn config_dir() -> PathBuf {
+    let config = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from);
+    let home = || {
+        std::env::var_os("HOME")
+            .map(|home| PathBuf::from(home).join(".config"))
+            .unwrap_or_default()
+    };
+    config.unwrap_or_else(home).join("gamey")
+}
+
+/// Returns the file the history of commands is kept in.
+pub fn history_file() -> PathBuf {
+    config_dir().join("history")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn complete(line: &str) -> (usize, Vec<String>) {
+        let history = DefaultHistory::new();
+        let (start, candidates) = CommandCompleter::new()
+            .complete(line, line.len(), &Context::new(&history))
+            .unwrap();
+        (
+            start,
+            candidates.into_iter().map(|c| c.replacement).collect(),
+        )
+    }
+
+    #[test]
+    fn test_complete_commands() {
+        assert_eq!(complete("ta"), (0, vec!["takeback".to_string()]));
+        assert_eq!(complete("  hi"), (2, vec!["history".to_string()]));
+        let (_, show) = complete("show_");
+        assert_eq!(show.len(), 6);
+        assert!(complete("xyz").1.is_empty());
+        // Arguments other than file names are not completed
+        assert!(complete("show 1").1.is_empty());
+    }
+
+    #[test]
+    fn test_complete_file_names() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("game.yen"), "{}").unwrap();
+        let typed = dir.path().join("ga").display().to_string();
+        for command in ["save", "l", "dump"] {
+            let line = format!("{} {}", command, typed);
+            let (start, files) = complete(&line);
+            assert_eq!(start, command.len() + 1);
+            assert_eq!(
+                files,
+                vec![dir.path().join("game.yen").display().to_string()]
+            );
+        }
+    }
+
+    #[test]
+    fn test_history_is_kept_across_prompts() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("config").join("history");
+        let mut prompt = new_prompt(&file).unwrap();
+        prompt.add_history_entry("save game.yen").unwrap();
+        save_history(&mut prompt, &file).unwrap();
+
+        let prompt = new_prompt(&file).unwrap();
+        let entries: Vec<&String> = prompt.history().iter().collect();
+        assert_eq!(entries, vec!["save game.yen"]);
+    }
+}
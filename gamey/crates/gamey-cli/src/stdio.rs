@@ -0,0 +1,84 @@
+//! `-` in place of a file name: commands read the position, game or
+//! archive from stdin and write their output to stdout, so they can be
+//! chained in shell pipelines:
+//!
+//! ```bash
+//! cat game.sgf | gamey convert - --from sgf --to yen --out-dir - > game.yen
+//! zcat selfplay.jsonl.gz | gamey archive export - --games - > moves.csv
+//! ```
+//!
+//! Stdin is not decompressed, as it has no extension to tell its
+//! compression by.
+
+use anyhow::Result;
+use gamey_archive::ArchiveReader;
+use gamey_core::{Compression, GameY};
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// Returns whether `path` stands for stdin or stdout.
+pub fn is_stdio(path: &Path) -> bool {
+    path.as_os_str() == "-"
+}
+
+/// Fails if more than one of `paths` is stdin, which can only be read once.
+pub fn check_stdin_once(paths: &[&Path]) -> Result<()> {
+    if paths.iter().filter(|path| is_stdio(path)).count() > 1 {
+        anyhow::bail!("Only one input can be read from stdin");
+    }
+    Ok(())
+}
+
+/// Opens the file at `path` for reading, decompressing it by extension, or
+/// stdin for `-`.
+pub fn open_input(path: &Path) -> Result<Box<dyn Read>> {
+    if is_stdio(path) {
+        return Ok(Box::new(std::io::stdin()));
+    }
+    let file = std::fs::File::open(path)?;
+    Ok(Compression::from_path(path).reader(file)?)
+}
+
+/// Creates the file at `path` for writing, or returns stdout for `-`.
+pub fn create_output(path: &Path) -> Result<Box<dyn Write + Send>> {
+    if is_stdio(path) {
+        return Ok(Box::new(std::io::stdout()));
+    }
+    Ok(Box::new(std::fs::File::create(path)?))
+}
+
+/// Loads the YEN game at `path`, or reads it from stdin for `-`.
+pub fn load_game(path: &Path) -> Result<GameY> {
+    if is_stdio(path) {
+        return Ok(GameY::read_yen(std::io::stdin())?);
+    }
+    Ok(GameY::load_from_file(path)?)
+}
+
+/// Opens the archive at `path`, or reads it from stdin for `-`.
+pub fn open_archive(path: &Path) -> Result<ArchiveReader<Box<dyn Read>>> {
+    if is_stdio(path) {
+        return Ok(ArchiveReader::new(Box::new(std::io::stdin()), "stdin"));
+    }
+    Ok(ArchiveReader::open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dash_is_stdio() {
+        assert!(is_stdio(Path::new("-")));
+        assert!(!is_stdio(Path::new("./-")));
+        assert!(!is_stdio(Path::new("game.yen")));
+    }
+
+    #[test]
+    fn test_stdin_is_read_once() {
+        let (stdin, file) = (Path::new("-"), Path::new("game.yen"));
+        assert!(check_stdin_once(&[stdin, file]).is_ok());
+        assert!(check_stdin_once(&[file, file]).is_ok());
+        assert!(check_stdin_once(&[stdin, stdin]).is_err());
+    }
+}
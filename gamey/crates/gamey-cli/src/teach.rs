@@ -0,0 +1,327 @@
+//! Explanations of the rules for beginners, shown with `--teach`.
+//!
+//! Teaching mode says why a move was rejected, points out the bridges and
+//! the sides reached by every placement, and asks before a move that lets
+//! the opponent win on their next move. The checks only look one move
+//! ahead, so they stay instant on every board size.
+
+use crate::i18n::{Lang, Message};
+use gamey_core::{
+    BoardTopology, Cell, Coordinates, GameStatus, GameY, Movement, PlayerId, Position,
+};
+
+/// Returns why `player` cannot place a stone at `coords` of `game`, or
+/// `None` if the move is legal.
+pub fn why_illegal(
+    game: &GameY,
+    player: PlayerId,
+    coords: Coordinates,
+) -> Option<Message<'static>> {
+    let next_player = match game.status() {
+        GameStatus::Finished { .. } => return Some(Message::TeachGameOver),
+        GameStatus::Ongoing { next_player } => *next_player,
+    };
+    if !game.topology().contains(coords, game.board_size()) {
+        return Some(Message::TeachOffBoard {
+            max: game.total_cells() - 1,
+        });
+    }
+    if next_player != player {
+        return Some(Message::TeachNotYourTurn {
+            player: next_player.to_string(),
+        });
+    }
+    let cell = game.cell_name(coords);
+    if game.is_occupied(coords) {
+        return Some(Message::TeachOccupied { cell });
+    }
+    if game.is_blocked(coords) {
+        return Some(Message::TeachBlocked { cell });
+    }
+    None
+}
+
+/// Returns the explanation for a cell typed as `input` that is not on the
+/// board of `game`: an index past the last one, or `x,y,z` coordinates
+/// outside the board.
+pub fn off_board(input: &str, game: &GameY) -> Option<Message<'static>> {
+    let parts: Vec<&str> = input.split_whitespace().collect();
+    let cell = match parts.as_slice() {
+        ["place", cell] | [cell] => *cell,
+        _ => return None,
+    };
+    let outside = if cell.contains(',') {
+        let values: Vec<u32> = cell
+            .split(',')
+            .map(|value| value.trim().parse::<u32>())
+            .collect::<Result<_, _>>()
+            .ok()?;
+        let [x, y, z] = values[..] else {
+            return None;
+        };
+        !game
+            .topology()
+            .contains(Coordinates::new(x, y, z), game.board_size())
+    } else {
+        cell.parse::<u32>().ok()? >= game.total_cells()
+    };
+    outside.then(|| Message::TeachOffBoard {
+        max: game.total_cells() - 1,
+    })
+}
+
+/// Returns what a beginner should notice about the stone just placed at
+/// `coords`, `before` being the game before the move: the bridges it makes
+/// and the sides its chain reached.
+pub fn lessons(before: &GameY, after: &GameY, coords: Coordinates, lang: Lang) -> Vec<String> {
+    let Some(player) = owner(&after.position(), after, coords) else {
+        return Vec::new();
+    };
+    let mut lessons: Vec<String> = bridges(after, coords)
+        .into_iter()
+        .map(|(other, [first, second])| {
+            Message::TeachBridge {
+                player: player.to_string(),
+                cells: [after.cell_name(coords), after.cell_name(other)],
+                links: [after.cell_name(first), after.cell_name(second)],
+            }
+            .text(lang)
+        })
+        .collect();
+
+    let sides = match after.topology() {
+        BoardTopology::Y => 3,
+        BoardTopology::Hex => 2,
+    };
+    let Some(group) = after
+        .groups(player)
+        .into_iter()
+        .find(|group| group.contains(coords))
+    else {
+        return lessons;
+    };
+    let touched = [
+        group.touches_side_a,
+        group.touches_side_b,
+        group.touches_side_c,
+    ];
+    let mut touched_before = [false; 3];
+    let neighbors = after.topology().neighbors(coords, after.board_size());
+    for old in before
+        .groups(player)
+        .iter()
+        .filter(|old| neighbors.iter().any(|&neighbor| old.contains(neighbor)))
+    {
+        touched_before[0] |= old.touches_side_a;
+        touched_before[1] |= old.touches_side_b;
+        touched_before[2] |= old.touches_side_c;
+    }
+    let reached: Vec<&str> = (0..sides)
+        .filter(|&side| touched[side] && !touched_before[side])
+        .map(|side| side_name(after.topology(), player, side, lang))
+        .collect();
+    if !reached.is_empty() {
+        let and = match lang {
+            Lang::En => " and ",
+            Lang::Es => " y ",
+        };
+        let message = Message::TeachSide {
+            player: player.to_string(),
+            sides_reached: reached.join(and),
+            touched: touched[..sides].iter().filter(|&&t| t).count(),
+            sides,
+        };
+        lessons.push(message.text(lang));
+    }
+    lessons
+}
+
+/// Returns the bridges the stone at `coords` makes with the other stones of
+/// its owner: stones it does not touch but shares two empty neighbors with,
+/// so that if the opponent takes one the other still connects them.
+///
+/// Each bridge is the other stone and the two shared cells, in cell index
+/// order.
+pub fn bridges(game: &GameY, coords: Coordinates) -> Vec<(Coordinates, [Coordinates; 2])> {
+    let position = game.position();
+    let Some(player) = owner(&position, game, coords) else {
+        return Vec::new();
+    };
+    let size = game.board_size();
+    let topology = game.topology();
+    let neighbors = topology.neighbors(coords, size);
+    let is_empty = |cell: Coordinates| !game.is_occupied(cell) && !game.is_blocked(cell);
+    let mut bridges: Vec<(Coordinates, [Coordinates; 2])> = Vec::new();
+    for &link in neighbors.iter().filter(|&&link| is_empty(link)) {
+        for other in topology.neighbors(link, size) {
+            let owned = owner(&position, game, other) == Some(player);
+            if other == coords
+                || !owned
+                || neighbors.contains(&other)
+                || bridges.iter().any(|(seen, _)| *seen == other)
+            {
+                continue;
+            }
+            let shared: Vec<Coordinates> = topology
+                .neighbors(other, size)
+                .into_iter()
+                .filter(|cell| neighbors.contains(cell))
+                .collect();
+            if let [first, second] = shared[..]
+                && is_empty(first)
+                && is_empty(second)
+            {
+                bridges.push((other, [first, second]));
+            }
+        }
+    }
+    bridges.sort_by_key(|(other, _)| game.cell_index(*other));
+    bridges
+}
+
+/// Returns a cell where the opponent wins at once if the player to move in
+/// `game` places a stone at `coords`; `None` if there is none or the move
+/// itself ends the game.
+pub fn gives_away(game: &GameY, coords: Coordinates) -> Option<Coordinates> {
+    let player = game.next_player()?;
+    let mut after = game.clone();
+    after
+        .add_move(Movement::Placement { player, coords })
+        .ok()?;
+    let opponent = after.next_player()?;
+    after
+        .available_cells_in_order()
+        .map(|index| after.cell_coords(index))
+        .find(|&reply| {
+            let mut reply_game = after.clone();
+            reply_game
+                .add_move(Movement::Placement {
+                    player: opponent,
+                    coords: reply,
+                })
+                .is_ok()
+                && matches!(reply_game.status(), GameStatus::Finished { winner } if *winner == opponent)
+        })
+}
+
+/// Returns the player whose stone is at `coords` in `position`, a position
+/// of `game`.
+fn owner(position: &Position, game: &GameY, coords: Coordinates) -> Option<PlayerId> {
+    match position.cell(game.cell_index(coords))? {
+        Cell::Occupied(player) => Some(player),
+        Cell::Empty => None,
+    }
+}
+
+/// Returns the name of goal side `side` of `player`, in the order of
+/// [`gamey_core::Group::touches_side_a`] and the others.
+fn side_name(topology: BoardTopology, player: PlayerId, side: usize, lang: Lang) -> &'static str {
+    match (topology, player.id() == 0, side, lang) {
+        (BoardTopology::Y, _, 0, Lang::En) => "side A at the bottom",
+        (BoardTopology::Y, _, 0, Lang::Es) => "el lado A de abajo",
+        (BoardTopology::Y, _, 1, Lang::En) => "side B at the left",
+        (BoardTopology::Y, _, 1, Lang::Es) => "el lado B de la izquierda",
+        (BoardTopology::Y, _, _, Lang::En) => "side C at the right",
+        (BoardTopology::Y, _, _, Lang::Es) => "el lado C de la derecha",
+        (BoardTopology::Hex, true, 0, Lang::En) => "the top edge",
+        (BoardTopology::Hex, true, 0, Lang::Es) => "el borde de arriba",
+        (BoardTopology::Hex, true, _, Lang::En) => "the bottom edge",
+        (BoardTopology::Hex, true, _, Lang::Es) => "el borde de abajo",
+        (BoardTopology::Hex, false, 0, Lang::En) => "the left edge",
+        (BoardTopology::Hex, false, 0, Lang::Es) => "el borde izquierdo",
+        (BoardTopology::Hex, false, _, Lang::En) => "the right edge",
+        (BoardTopology::Hex, false, _, Lang::Es) => "el borde derecho",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_why_illegal() {
+        let mut game = GameY::new(3);
+        game.play_move_list("0").unwrap();
+        let (zero, one) = (PlayerId::new(0), PlayerId::new(1));
+        let top = Coordinates::new(2, 0, 0);
+        assert_eq!(
+            why_illegal(&game, one, top),
+            Some(Message::TeachOccupied {
+                cell: game.cell_name(top)
+            })
+        );
+        assert_eq!(
+            why_illegal(&game, zero, Coordinates::new(1, 1, 0)),
+            Some(Message::TeachNotYourTurn {
+                player: "1".to_string()
+            })
+        );
+        assert_eq!(
+            why_illegal(&game, one, Coordinates::new(3, 0, 0)),
+            Some(Message::TeachOffBoard { max: 5 })
+        );
+        assert_eq!(why_illegal(&game, one, Coordinates::new(1, 1, 0)), None);
+
+        assert_eq!(
+            off_board("9", &game),
+            Some(Message::TeachOffBoard { max: 5 })
+        );
+        assert!(off_board("place 3,0,0", &game).is_some());
+        assert_eq!(off_board("4", &game), None);
+        assert_eq!(off_board("help", &game), None);
+    }
+
+    #[test]
+    fn test_bridges_and_sides() {
+        // Player 0 holds the center of a size 5 board and the cell two rows
+        // below it, which share two empty neighbors
+        let mut game = GameY::new(5);
+        game.play_move_list("4 0").unwrap();
+        let before = game.clone();
+        game.play_move_list("12").unwrap();
+        let placed = game.cell_coords(12);
+        let found = bridges(&game, placed);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, game.cell_coords(4));
+        assert_eq!(found[0].1, [game.cell_coords(7), game.cell_coords(8)]);
+
+        let told = lessons(&before, &game, placed, Lang::En);
+        assert_eq!(told.len(), 2);
+        assert!(told[0].starts_with("Bridge: player 0"));
+        assert!(told[1].contains("side A at the bottom"));
+
+        // A stone on the edge reaches a side
+        let mut edge = GameY::new(3);
+        let before = edge.clone();
+        edge.play_move_list("3").unwrap();
+        let told = lessons(&before, &edge, edge.cell_coords(3), Lang::En);
+        assert_eq!(
+            told,
+            vec![
+                "Player 0 reached side A at the bottom and side B at the left: \
+                 the chain touches 2 of the 3 sides it needs to win."
+            ]
+        );
+    }
+
+    #[test]
+    fn test_gives_away_an_immediate_win() {
+        // On a size 2 board every reply to a first move leaves the last
+        // cell to the first player
+        let mut game = GameY::new(2);
+        game.play_move_list("1").unwrap();
+        assert_eq!(
+            gives_away(&game, Coordinates::new(1, 0, 0)),
+            Some(Coordinates::new(0, 1, 0))
+        );
+        assert_eq!(
+            gives_away(&game, Coordinates::new(0, 1, 0)),
+            Some(Coordinates::new(1, 0, 0))
+        );
+        // Nothing is given away early on, nor by a winning move
+        let mut game = GameY::new(3);
+        game.play_move_list("4").unwrap();
+        assert_eq!(gives_away(&game, Coordinates::new(0, 0, 2)), None);
+        assert_eq!(gives_away(&GameY::new(1), Coordinates::new(0, 0, 0)), None);
+    }
+}
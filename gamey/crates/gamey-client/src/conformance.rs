@@ -0,0 +1,288 @@
+//! Contract tests for bot servers.
+//!
+//! Any server implementing the bot API (see `gamey-server`) can be checked
+//! with [`run_conformance`]: it sends a suite of requests and reports which
+//! answers follow the API, e.g. to grade the servers written for a course.
+//! The `gamey conformance --url http://...` command runs it from the
+//! terminal.
+//!
+//! The suite checks that the server:
+//! - answers `GET /status`,
+//! - chooses a legal move on an empty and on a partially filled board,
+//! - rejects invalid YEN with `INVALID_YEN` and malformed JSON with 400,
+//! - rejects an unknown API version with `UNSUPPORTED_API_VERSION`,
+//! - rejects an unknown bot with `BOT_NOT_FOUND`, and
+//! - answers a position without empty cells with an error rather than a move.
+
+use crate::{BotClient, ClientError};
+use gamey_core::{Coordinates, ErrorCode, GameY, Movement, PlayerId, YEN};
+use gamey_server::{ErrorResponse, MoveResponse, SUPPORTED_VERSION};
+use std::fmt::Display;
+
+/// The outcome of one check of the suite.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// What was checked.
+    pub name: &'static str,
+    /// Whether the server answered as the API requires.
+    pub passed: bool,
+    /// What the server answered, when it did not pass.
+    pub detail: Option<String>,
+}
+
+/// The outcomes of the whole suite against one server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// The server checked.
+    pub url: String,
+    /// The bot asked for moves.
+    pub bot_id: String,
+    /// The outcome of every check, in the order they ran.
+    pub checks: Vec<CheckResult>,
+}
+
+impl ConformanceReport {
+    /// Returns true if the server passed every check.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|check| check.passed)
+    }
+
+    /// Returns the number of checks passed.
+    pub fn passed_count(&self) -> usize {
+        self.checks.iter().filter(|check| check.passed).count()
+    }
+}
+
+impl Display for ConformanceReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Conformance of {} (bot {})", self.url, self.bot_id)?;
+        for check in &self.checks {
+            let mark = if check.passed { "PASS" } else { "FAIL" };
+            writeln!(f, "  {} {}", mark, check.name)?;
+            if let Some(detail) = &check.detail {
+                writeln!(f, "       {}", detail)?;
+            }
+        }
+        write!(
+            f,
+            "{}/{} checks passed",
+            self.passed_count(),
+            self.checks.len()
+        )
+    }
+}
+
+/// What a check expects from the server.
+type Outcome = Result<(), String>;
+
+/// Runs the suite against the server of `client`, asking `bot_id` for moves.
+///
+/// Failing checks do not stop the suite; the report lists all of them.
+pub async fn run_conformance(client: &BotClient, bot_id: &str) -> ConformanceReport {
+    let mut checks = Vec::new();
+    let mut check = |name, outcome: Outcome| {
+        checks.push(CheckResult {
+            name,
+            passed: outcome.is_ok(),
+            detail: outcome.err(),
+        });
+    };
+    check("GET /status answers", status(client).await);
+
+    let empty = GameY::new(5);
+    check(
+        "Chooses a legal move on an empty board",
+        legal_move(client, bot_id, &empty).await,
+    );
+    let mut started = GameY::new(5);
+    let moves = [
+        (0, Coordinates::new(4, 0, 0)),
+        (1, Coordinates::new(0, 4, 0)),
+        (0, Coordinates::new(1, 1, 2)),
+    ];
+    for (player, coords) in moves {
+        let player = PlayerId::new(player);
+        started
+            .add_move(Movement::Placement { player, coords })
+            .expect("the moves of the suite are legal");
+    }
+    check(
+        "Chooses a legal move on a partially filled board",
+        legal_move(client, bot_id, &started).await,
+    );
+
+    let invalid = YEN::new(3, 0, vec!['B', 'R'], "./..".to_string());
+    let body = serde_json::to_string(&invalid).expect("YEN serializes");
+    check(
+        "Rejects invalid YEN with INVALID_YEN",
+        error(
+            client,
+            SUPPORTED_VERSION,
+            bot_id,
+            body,
+            ErrorCode::InvalidYen,
+        )
+        .await,
+    );
+    check(
+        "Rejects malformed JSON with 400",
+        bad_request(client, bot_id).await,
+    );
+    let body = serde_json::to_string(&YEN::from(&empty)).expect("YEN serializes");
+    check(
+        "Rejects an unknown API version with UNSUPPORTED_API_VERSION",
+        error(
+            client,
+            "v0",
+            bot_id,
+            body.clone(),
+            ErrorCode::UnsupportedApiVersion,
+        )
+        .await,
+    );
+    check(
+        "Rejects an unknown bot with BOT_NOT_FOUND",
+        error(
+            client,
+            SUPPORTED_VERSION,
+            "no_such_bot_for_conformance",
+            body,
+            ErrorCode::BotNotFound,
+        )
+        .await,
+    );
+    check(
+        "Answers a full board with an error",
+        full_board(client, bot_id).await,
+    );
+
+    ConformanceReport {
+        url: client.base_url().to_string(),
+        bot_id: bot_id.to_string(),
+        checks,
+    }
+}
+
+async fn status(client: &BotClient) -> Outcome {
+    client.status().await.map_err(|e| e.to_string())
+}
+
+/// Expects a move of `bot_id` on an empty cell of `game`.
+async fn legal_move(client: &BotClient, bot_id: &str, game: &GameY) -> Outcome {
+    let yen = YEN::from(game);
+    let answer: MoveResponse = client
+        .choose(bot_id, &yen)
+        .await
+        .map_err(|e| e.to_string())?;
+    if answer.api_version != SUPPORTED_VERSION || answer.bot_id != bot_id {
+        return Err(format!(
+            "Answered for {} {} instead of {} {}",
+            answer.api_version, answer.bot_id, SUPPORTED_VERSION, bot_id
+        ));
+    }
+    let mut game = game.clone();
+    let player = game
+        .next_player()
+        .expect("the game of the suite is ongoing");
+    game.add_move(Movement::Placement {
+        player,
+        coords: answer.coords,
+    })
+    .map_err(|e| format!("Chose an illegal move {}: {}", answer.coords, e))
+}
+
+/// Expects the choose endpoint to fail with `code`, whatever the HTTP status
+/// of the answer.
+async fn error(
+    client: &BotClient,
+    api_version: &str,
+    bot_id: &str,
+    body: String,
+    code: ErrorCode,
+) -> Outcome {
+    let path = format!("{}/ybot/choose/{}", api_version, bot_id);
+    let (_, body) = post_raw(client, &path, body)
+        .await
+        .map_err(|e| e.to_string())?;
+    match serde_json::from_str::<ErrorResponse>(&body) {
+        Ok(err) if err.code == code => Ok(()),
+        Ok(err) => Err(format!(
+            "Answered {} instead of {}",
+            err.code.as_str(),
+            code.as_str()
+        )),
+        Err(e) => Err(format!("The error is not an ErrorResponse: {}", e)),
+    }
+}
+
+/// Expects a request whose body is not JSON to be rejected with 400.
+async fn bad_request(client: &BotClient, bot_id: &str) -> Outcome {
+    let path = format!("{}/ybot/choose/{}", SUPPORTED_VERSION, bot_id);
+    let (status, _) = post_raw(client, &path, "{\"size\": 3,".to_string())
+        .await
+        .map_err(|e| e.to_string())?;
+    match status {
+        400 | 422 => Ok(()),
+        status => Err(format!("Answered {} instead of 400", status)),
+    }
+}
+
+/// Expects a position without empty cells to be answered with an error:
+/// there is no move to choose.
+async fn full_board(client: &BotClient, bot_id: &str) -> Outcome {
+    let full = YEN::new(2, 1, vec!['B', 'R'], "B/BR".to_string());
+    match client.choose(bot_id, &full).await {
+        Ok(answer) => Err(format!("Chose {} on a full board", answer.coords)),
+        Err(ClientError::Server(_)) => Ok(()),
+        Err(e) => Err(e.to_string()),
+    }
+}
+
+/// Posts `body` as JSON to `path` and returns the status and body of the
+/// answer, whatever they are.
+async fn post_raw(
+    client: &BotClient,
+    path: &str,
+    body: String,
+) -> Result<(u16, String), ClientError> {
+    let url = format!("{}/{}", client.base_url, path);
+    let http_error = |e: reqwest::Error| ClientError::Http {
+        url: url.clone(),
+        message: e.to_string(),
+    };
+    let response = client
+        .http
+        .post(&url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .map_err(http_error)?;
+    let status = response.status().as_u16();
+    let body = response.text().await.map_err(http_error)?;
+    Ok((status, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_the_reference_server_conforms() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = gamey_server::create_router(gamey_server::create_default_state());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let report = run_conformance(&BotClient::new(&url), "random_bot").await;
+        assert!(report.passed(), "{}", report);
+        assert_eq!(report.checks.len(), 8);
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_servers_fail_every_check() {
+        let report = run_conformance(&BotClient::new("http://127.0.0.1:1"), "random_bot").await;
+        assert_eq!(report.passed_count(), 0);
+        assert!(report.to_string().ends_with("0/8 checks passed"));
+    }
+}
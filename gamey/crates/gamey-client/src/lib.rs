@@ -0,0 +1,448 @@
+//! HTTP client for gamey bot servers.
+//!
+//! [`BotClient`] calls the REST API of a bot server (see `gamey-server`) with
+//! typed requests and answers, so Rust programs do not need to build the
+//! URLs and JSON bodies by hand. Failed requests return the server's
+//! [`ErrorResponse`], with its machine-readable code.
+//!
+//! [`BotClient::watch_game`] follows a game hosted by the server as its
+//! moves are played.
+//!
+//! The [`conformance`] suite uses the client to check that a server
+//! implements the API.
+//!
+//! A [`RemoteBot`] plays the moves of a bot of another server, so a bot
+//! server can register its peers' bots (see [`remote_bot`]).
+//!
+//! To test how callers cope with slow or unreliable servers, a [`Chaos`]
+//! given to [`BotClient::with_chaos`] delays requests and makes some of them
+//! fail.
+//!
+//! The client speaks plain HTTP; build `reqwest` with one of its TLS
+//! features and pass the client to [`BotClient::with_http_client`] to reach
+//! servers behind HTTPS.
+//!
+//! # Example
+//! ```no_run
+//! use gamey_client::BotClient;
+//! use gamey_core::{GameY, YEN};
+//!
+//! # async fn run() -> Result<(), gamey_client::ClientError> {
+//! let client = BotClient::new("http://localhost:3000");
+//! let yen = YEN::from(&GameY::new(5));
+//! let answer = client.choose("random_bot", &yen).await?;
+//! println!("The bot plays {}", answer.coords);
+//! # Ok(())
+//! # }
+//! ```
+
+pub mod conformance;
+pub mod remote_bot;
+
+pub use remote_bot::RemoteBot;
+
+use gamey_bots::{Chaos, Fault};
+use gamey_core::{BoardTopology, GameMetadata, MoveRecord, Movement, YEN};
+use gamey_server::{
+    ErrorResponse, MoveResponse, SUPPORTED_VERSION,
+    games::{CreateGameRequest, GameExport, TurnEvent},
+};
+use serde::{Serialize, de::DeserializeOwned};
+
+/// Why a request to a bot server failed.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientError {
+    /// The server could not be reached, or its answer could not be read.
+    #[error("Request to {url} failed: {message}")]
+    Http {
+        /// The URL requested.
+        url: String,
+        /// What went wrong.
+        message: String,
+    },
+    /// The server answered with an error.
+    #[error("Server error {}: {}", .0.code.as_str(), .0.message)]
+    Server(ErrorResponse),
+}
+
+/// A client of the REST API of one bot server.
+#[derive(Debug, Clone)]
+pub struct BotClient {
+    http: reqwest::Client,
+    base_url: String,
+    chaos: Option<Chaos>,
+}
+
+impl BotClient {
+    /// Creates a client of the server at `base_url`, e.g.
+    /// `http://localhost:3000`.
+    pub fn new(base_url: &str) -> Self {
+        BotClient {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            chaos: None,
+        }
+    }
+
+    /// Sends the requests with the given `reqwest` client, e.g. one with
+    /// timeouts or TLS support.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Delays the requests and makes some of them fail as described by
+    /// `chaos`, to test callers against an unreliable server.
+    pub fn with_chaos(mut self, chaos: Chaos) -> Self {
+        self.chaos = Some(chaos);
+        self
+    }
+
+    /// Returns the URL of the server.
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    /// Checks that the server is up.
+    pub async fn status(&self) -> Result<(), ClientError> {
+        let url = format!("{}/status", self.base_url);
+        self.disturb(&url).await?;
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| http_error(&url, e))?;
+        if !response.status().is_success() {
+            return Err(ClientError::Http {
+                message: format!("The server answered {}", response.status()),
+                url,
+            });
+        }
+        Ok(())
+    }
+
+    /// Asks a bot for its move in a position.
+    ///
+    /// `POST /v1/ybot/choose/{bot_id}`
+    pub async fn choose(&self, bot_id: &str, yen: &YEN) -> Result<MoveResponse, ClientError> {
+        self.post(&format!("ybot/choose/{}", bot_id), yen).await
+    }
+
+    /// Asks a bot for its move in a position, with its explanation of why.
+    ///
+    /// `POST /v1/ybot/choose/{bot_id}?explain=true`
+    pub async fn explain(&self, bot_id: &str, yen: &YEN) -> Result<MoveResponse, ClientError> {
+        self.post(&format!("ybot/choose/{}?explain=true", bot_id), yen)
+            .await
+    }
+
+    /// Creates a game hosted by the server.
+    ///
+    /// `POST /v1/games`
+    pub async fn create_game(
+        &self,
+        size: u32,
+        topology: BoardTopology,
+    ) -> Result<GameExport, ClientError> {
+        let request = CreateGameRequest {
+            size,
+            topology,
+            metadata: GameMetadata::default(),
+        };
+        self.post("games", &request).await
+    }
+
+    /// Returns a game hosted by the server.
+    ///
+    /// `GET /v1/games/{game_id}`
+    pub async fn get_game(&self, game_id: &str) -> Result<GameExport, ClientError> {
+        let url = self.url(&format!("games/{}", game_id));
+        self.disturb(&url).await?;
+        let response = self.http.get(&url).send().await;
+        read(&url, response).await
+    }
+
+    /// Plays a move in a game hosted by the server.
+    ///
+    /// `POST /v1/games/{game_id}/moves`
+    pub async fn play_move(
+        &self,
+        game_id: &str,
+        movement: &Movement,
+    ) -> Result<GameExport, ClientError> {
+        let record = MoveRecord::from(movement);
+        self.post(&format!("games/{}/moves", game_id), &record)
+            .await
+    }
+
+    /// Follows a game hosted by the server: the returned [`GameWatch`] yields
+    /// its current state, then its state after every change until the game
+    /// ends.
+    ///
+    /// `GET /v1/games/{game_id}/events`
+    pub async fn watch_game(&self, game_id: &str) -> Result<GameWatch, ClientError> {
+        let url = self.url(&format!("games/{}/events", game_id));
+        self.disturb(&url).await?;
+        let response = self
+            .http
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| http_error(&url, e))?;
+        let status = response.status();
+        // Errors are answered as JSON instead of a stream of events
+        let is_json = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .is_some_and(|value| value.as_bytes().starts_with(b"application/json"));
+        if !status.is_success() || is_json {
+            let body = response.bytes().await.map_err(|e| http_error(&url, e))?;
+            return Err(server_error(&url, status, &body));
+        }
+        Ok(GameWatch {
+            url,
+            response,
+            buffer: Vec::new(),
+        })
+    }
+
+    /// Waits the latency of the chaos, if any, and fails the request to
+    /// `url` when it draws a fault.
+    async fn disturb(&self, url: &str) -> Result<(), ClientError> {
+        let Some(chaos) = &self.chaos else {
+            return Ok(());
+        };
+        tokio::time::sleep(chaos.latency).await;
+        let message = match chaos.draw() {
+            None => return Ok(()),
+            Some(Fault::Failure) => "Injected failure".to_string(),
+            Some(Fault::Timeout) => {
+                tokio::time::sleep(chaos.hang).await;
+                format!("Injected timeout after {:?}", chaos.hang)
+            }
+        };
+        Err(ClientError::Http {
+            url: url.to_string(),
+            message,
+        })
+    }
+
+    fn url(&self, path: &str) -> String {
+        format!("{}/{}/{}", self.base_url, SUPPORTED_VERSION, path)
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(
+        &self,
+        path: &str,
+        body: &B,
+    ) -> Result<T, ClientError> {
+        let url = self.url(path);
+        self.disturb(&url).await?;
+        let response = self.http.post(&url).json(body).send().await;
+        read(&url, response).await
+    }
+}
+
+/// The turn events of a game hosted by a server, read as they arrive; see
+/// [`BotClient::watch_game`].
+#[derive(Debug)]
+pub struct GameWatch {
+    url: String,
+    response: reqwest::Response,
+    buffer: Vec<u8>,
+}
+
+impl GameWatch {
+    /// Waits for the next state of the game, or returns `None` once the
+    /// server has ended the stream, after the game is over.
+    pub async fn next_turn(&mut self) -> Result<Option<TurnEvent>, ClientError> {
+        loop {
+            // Server-sent events end with a blank line
+            if let Some(end) = self.buffer.windows(2).position(|w| w == b"\n\n") {
+                let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+                if let Some(event) = self.parse_turn(&String::from_utf8_lossy(&block))? {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+            match self.response.chunk().await {
+                Ok(Some(chunk)) => self.buffer.extend_from_slice(&chunk),
+                Ok(None) => return Ok(None),
+                Err(e) => return Err(http_error(&self.url, e)),
+            }
+        }
+    }
+
+    /// Reads the turn event of one server-sent event, skipping other events
+    /// and the keep-alive comments.
+    fn parse_turn(&self, block: &str) -> Result<Option<TurnEvent>, ClientError> {
+        let mut name = None;
+        let mut data = Vec::new();
+        for line in block.lines() {
+            if let Some(value) = line.strip_prefix("event:") {
+                name = Some(value.trim());
+            } else if let Some(value) = line.strip_prefix("data:") {
+                data.push(value.strip_prefix(' ').unwrap_or(value));
+            }
+        }
+        if name != Some("turn") || data.is_empty() {
+            return Ok(None);
+        }
+        serde_json::from_str(&data.join("\n"))
+            .map(Some)
+            .map_err(|e| ClientError::Http {
+                url: self.url.clone(),
+                message: format!("Unexpected event: {}", e),
+            })
+    }
+}
+
+/// Asks the bot `bot_id` of the server at `url` for its move in a position;
+/// see [`BotClient::choose`].
+pub async fn choose(url: &str, bot_id: &str, yen: &YEN) -> Result<MoveResponse, ClientError> {
+    BotClient::new(url).choose(bot_id, yen).await
+}
+
+/// Reads the answer of a request: the expected body on success, the server's
+/// [`ErrorResponse`] otherwise. The server answers errors with `200 OK` too,
+/// so an error document is an error whatever the status.
+async fn read<T: DeserializeOwned>(
+    url: &str,
+    response: reqwest::Result<reqwest::Response>,
+) -> Result<T, ClientError> {
+    let response = response.map_err(|e| http_error(url, e))?;
+    let status = response.status();
+    let body = response.bytes().await.map_err(|e| http_error(url, e))?;
+    if let Ok(err) = serde_json::from_slice::<ErrorResponse>(&body) {
+        return Err(ClientError::Server(err));
+    }
+    if status.is_success() {
+        return serde_json::from_slice(&body).map_err(|e| ClientError::Http {
+            url: url.to_string(),
+            message: format!("Unexpected answer: {}", e),
+        });
+    }
+    Err(server_error(url, status, &body))
+}
+
+/// Reads the [`ErrorResponse`] of a failed request, if the server sent one.
+fn server_error(url: &str, status: reqwest::StatusCode, body: &[u8]) -> ClientError {
+    match serde_json::from_slice::<ErrorResponse>(body) {
+        Ok(err) => ClientError::Server(err),
+        Err(_) => ClientError::Http {
+            url: url.to_string(),
+            message: format!("The server answered {}", status),
+        },
+    }
+}
+
+fn http_error(url: &str, e: reqwest::Error) -> ClientError {
+    ClientError::Http {
+        url: url.to_string(),
+        message: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{Coordinates, ErrorCode, GameY, PlayerId};
+
+    /// Serves a default bot server on a free local port.
+    async fn serve() -> BotClient {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        let app = gamey_server::create_router(gamey_server::create_default_state());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        BotClient::new(&url)
+    }
+
+    #[tokio::test]
+    async fn test_choose_and_play_against_a_server() {
+        let client = serve().await;
+        client.status().await.unwrap();
+
+        let yen = YEN::from(&GameY::new(3));
+        let answer = client.choose("random_bot", &yen).await.unwrap();
+        assert_eq!(answer.bot_id, "random_bot");
+        assert!(answer.explanation.is_none());
+        let answer = client.explain("random_bot", &yen).await.unwrap();
+        assert!(answer.explanation.is_some());
+
+        let game = client.create_game(3, BoardTopology::Y).await.unwrap();
+        let movement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 0, 0),
+        };
+        let played = client.play_move(&game.game_id, &movement).await.unwrap();
+        assert_eq!(played.next_player, Some(1));
+        let fetched = client.get_game(&game.game_id).await.unwrap();
+        assert_eq!(fetched.history, played.history);
+    }
+
+    #[tokio::test]
+    async fn test_watch_game_until_it_ends() {
+        let client = serve().await;
+        let game = client.create_game(2, BoardTopology::Y).await.unwrap();
+        let mut watch = client.watch_game(&game.game_id).await.unwrap();
+        let current = watch.next_turn().await.unwrap().unwrap();
+        assert_eq!(current.next_player, Some(0));
+
+        for (player, x, y, z) in [(0, 1, 0, 0), (1, 0, 1, 0), (0, 0, 0, 1)] {
+            let movement = Movement::Placement {
+                player: PlayerId::new(player),
+                coords: Coordinates::new(x, y, z),
+            };
+            client.play_move(&game.game_id, &movement).await.unwrap();
+        }
+        let mut turns = Vec::new();
+        while let Some(turn) = watch.next_turn().await.unwrap() {
+            turns.push(turn);
+        }
+        assert_eq!(turns.len(), 3);
+        assert_eq!(turns[0].next_player, Some(1));
+        assert_eq!(turns[2].winner, Some(0));
+
+        let Err(ClientError::Server(err)) = client.watch_game("missing").await else {
+            panic!("expected a server error");
+        };
+        assert_eq!(err.code, ErrorCode::GameNotFound);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_keep_their_code() {
+        let client = serve().await;
+        let yen = YEN::from(&GameY::new(3));
+        let Err(ClientError::Server(err)) = client.choose("no_such_bot", &yen).await else {
+            panic!("expected a server error");
+        };
+        assert_eq!(err.code, ErrorCode::BotNotFound);
+
+        let unreachable = BotClient::new("http://127.0.0.1:1");
+        let err = unreachable.status().await.unwrap_err();
+        assert!(matches!(err, ClientError::Http { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_chaos_fails_requests() {
+        let chaos = Chaos {
+            failure_rate: 1.0,
+            ..Chaos::default()
+        };
+        let client = serve().await.with_chaos(chaos);
+        let Err(ClientError::Http { message, .. }) = client.status().await else {
+            panic!("expected an injected failure");
+        };
+        assert_eq!(message, "Injected failure");
+
+        let chaos = Chaos {
+            latency: std::time::Duration::from_millis(10),
+            ..Chaos::default()
+        };
+        let client = serve().await.with_chaos(chaos);
+        let start = std::time::Instant::now();
+        client.status().await.unwrap();
+        assert!(start.elapsed() >= std::time::Duration::from_millis(10));
+    }
+}
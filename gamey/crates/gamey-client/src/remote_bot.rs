@@ -0,0 +1,145 @@
+//! Bots of other bot servers.
+//!
+//! A [`RemoteBot`] chooses its moves by asking a bot of another server, so a
+//! bot server can offer the bots of its peers next to its own. Give
+//! [`RemoteBot::factory`] to
+//! [`AppState::with_remote_bots`](gamey_server::state::AppState::with_remote_bots)
+//! to register the remote bots listed in `bots.toml` (see
+//! [`gamey_server::bots_config`]).
+
+use crate::BotClient;
+use gamey_bots::YBot;
+use gamey_core::{Coordinates, GameY, YEN};
+use gamey_server::bots_config::{RemoteBotEntry, RemoteBotFactory};
+use gamey_server::state::DEFAULT_BOT_TIMEOUT;
+use std::sync::Arc;
+
+/// A bot that plays the moves of a bot of another server.
+#[derive(Debug, Clone)]
+pub struct RemoteBot {
+    name: String,
+    url: String,
+    bot_id: String,
+}
+
+impl RemoteBot {
+    /// Creates a bot named `name` that plays the moves of the bot `bot_id`
+    /// of the server at `url`.
+    pub fn new(name: &str, url: &str, bot_id: &str) -> Self {
+        RemoteBot {
+            name: name.to_string(),
+            url: url.to_string(),
+            bot_id: bot_id.to_string(),
+        }
+    }
+
+    /// Returns the factory that builds the remote bots of `bots.toml`.
+    pub fn factory() -> RemoteBotFactory {
+        Arc::new(|name: &str, entry: &RemoteBotEntry| {
+            let bot_id = entry.bot.as_deref().unwrap_or(name);
+            Arc::new(RemoteBot::new(name, &entry.url, bot_id)) as Arc<dyn YBot>
+        })
+    }
+
+    /// Returns the URL of the remote server.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Returns the name of the bot on the remote server.
+    pub fn bot_id(&self) -> &str {
+        &self.bot_id
+    }
+}
+
+impl YBot for RemoteBot {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Asks the remote bot for its move; returns `None` if the server cannot
+    /// be reached in [`DEFAULT_BOT_TIMEOUT`] or answers with an error.
+    fn choose_move(&self, board: &GameY) -> Option<Coordinates> {
+        let yen = YEN::from(board);
+        // Bots are called from async tasks as well as from blocking threads,
+        // so the request gets a thread and a runtime of its own
+        std::thread::scope(|scope| {
+            scope
+                .spawn(|| {
+                    let http = reqwest::Client::builder()
+                        .timeout(DEFAULT_BOT_TIMEOUT)
+                        .build()
+                        .ok()?;
+                    let client = BotClient::new(&self.url).with_http_client(http);
+                    let runtime = tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .ok()?;
+                    let answer = runtime.block_on(client.choose(&self.bot_id, &yen));
+                    answer.ok().map(|answer| answer.coords)
+                })
+                .join()
+                .ok()
+                .flatten()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_server::admin::ReloadResponse;
+
+    /// Serves `state` on a free local port and returns its URL.
+    async fn serve(state: gamey_server::state::AppState) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let app = gamey_server::create_router(state);
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        url
+    }
+
+    async fn reload(url: &str) -> Vec<String> {
+        let response = reqwest::Client::new()
+            .post(format!("{}/v1/admin/reload", url))
+            .bearer_auth("secret")
+            .send()
+            .await
+            .unwrap();
+        response.json::<ReloadResponse>().await.unwrap().bots
+    }
+
+    #[tokio::test]
+    async fn test_reload_registers_and_removes_remote_bots() {
+        let peer = serve(gamey_server::create_default_state()).await;
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bots.toml");
+        std::fs::write(&file, "").unwrap();
+        let state = gamey_server::create_default_state()
+            .with_bots_config(file.clone())
+            .with_admin_key("secret")
+            .with_remote_bots(RemoteBot::factory());
+        let url = serve(state).await;
+        assert!(!reload(&url).await.contains(&"peer_bot".to_string()));
+
+        let config = format!(
+            "[remote.peer_bot]\nurl = \"{}\"\nbot = \"random_bot\"\n",
+            peer
+        );
+        std::fs::write(&file, config).unwrap();
+        assert!(reload(&url).await.contains(&"peer_bot".to_string()));
+        let yen = YEN::from(&GameY::new(3));
+        let answer = BotClient::new(&url).choose("peer_bot", &yen).await.unwrap();
+        assert_eq!(answer.bot_id, "peer_bot");
+
+        std::fs::write(&file, "").unwrap();
+        assert!(!reload(&url).await.contains(&"peer_bot".to_string()));
+    }
+
+    #[test]
+    fn test_unreachable_servers_give_no_move() {
+        let bot = RemoteBot::new("peer_bot", "http://127.0.0.1:1", "random_bot");
+        assert_eq!(bot.bot_id(), "random_bot");
+        assert_eq!(bot.choose_move(&GameY::new(3)), None);
+    }
+}
@@ -0,0 +1,252 @@
+//! Transparent compression of saved games and archives.
+//!
+//! Files are compressed by extension: `.gz` with gzip and `.zst` with
+//! Zstandard, so `game.yen.gz` is a gzip compressed YEN file and
+//! `selfplay.jsonl.zst` a Zstandard compressed archive. Each format needs
+//! its feature, `gzip` or `zstd`; without it, such files are rejected with
+//! an error rather than read as garbage.
+//!
+//! Compressed streams may be concatenated, as happens when games are
+//! appended to a compressed archive, and are read back as one.
+
+use crate::GameYError;
+use std::io::{Read, Write};
+use std::path::Path;
+
+/// How a file is compressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Compression {
+    /// Not compressed.
+    None,
+    /// Gzip, for `.gz` files; needs the `gzip` feature.
+    Gzip,
+    /// Zstandard, for `.zst` files; needs the `zstd` feature.
+    Zstd,
+}
+
+impl Compression {
+    /// Returns the compression of the file at `path`, by its extension.
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("gz") => Compression::Gzip,
+            Some("zst") => Compression::Zstd,
+            _ => Compression::None,
+        }
+    }
+
+    /// Returns whether this build can read and write the compression.
+    pub fn is_available(self) -> bool {
+        match self {
+            Compression::None => true,
+            Compression::Gzip => cfg!(feature = "gzip"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+
+    fn unavailable(self) -> GameYError {
+        let (name, feature) = match self {
+            Compression::Gzip => ("gzip", "gzip"),
+            _ => ("Zstandard", "zstd"),
+        };
+        GameYError::IoError {
+            message: format!("Cannot read or write {} compressed files", name),
+            error: format!("gamey was built without the {} feature", feature),
+        }
+    }
+
+    /// Returns a reader decompressing `reader`.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the compression is not available.
+    pub fn reader<'a, R: Read + 'a>(self, reader: R) -> Result<Box<dyn Read + 'a>, GameYError> {
+        match self {
+            Compression::None => Ok(Box::new(reader)),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => Ok(Box::new(flate2::read::MultiGzDecoder::new(reader))),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let decoder = zstd::Decoder::new(reader).map_err(|e| GameYError::IoError {
+                    message: "Failed to start Zstandard decompression".to_string(),
+                    error: e.to_string(),
+                })?;
+                Ok(Box::new(decoder))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(self.unavailable()),
+        }
+    }
+
+    /// Returns a writer compressing into `writer`.
+    ///
+    /// The compressed stream is only complete once the writer is finished
+    /// with [`CompressedWriter::finish`], or dropped.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the compression is not available.
+    pub fn writer<W: Write>(self, writer: W) -> Result<CompressedWriter<W>, GameYError> {
+        let encoder = match self {
+            Compression::None => Encoder::Plain(writer),
+            #[cfg(feature = "gzip")]
+            Compression::Gzip => {
+                Encoder::Gzip(flate2::write::GzEncoder::new(writer, Default::default()))
+            }
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => {
+                let encoder = zstd::Encoder::new(writer, 0).map_err(|e| GameYError::IoError {
+                    message: "Failed to start Zstandard compression".to_string(),
+                    error: e.to_string(),
+                })?;
+                Encoder::Zstd(encoder)
+            }
+            #[allow(unreachable_patterns)]
+            _ => return Err(self.unavailable()),
+        };
+        Ok(CompressedWriter {
+            encoder: Some(encoder),
+        })
+    }
+}
+
+enum Encoder<W: Write> {
+    Plain(W),
+    #[cfg(feature = "gzip")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "zstd")]
+    Zstd(zstd::Encoder<'static, W>),
+}
+
+impl<W: Write> Encoder<W> {
+    // Without compression features there is a single variant
+    #[allow(clippy::infallible_destructuring_match)]
+    fn finish(self) -> std::io::Result<W> {
+        let mut writer = match self {
+            Encoder::Plain(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        };
+        writer.flush()?;
+        Ok(writer)
+    }
+}
+
+/// A writer compressing what is written to it, see [`Compression::writer`].
+pub struct CompressedWriter<W: Write> {
+    // Taken when finished
+    encoder: Option<Encoder<W>>,
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Completes the compressed stream and returns the underlying writer.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the end of the stream cannot be
+    /// written.
+    pub fn finish(mut self) -> Result<W, GameYError> {
+        let encoder = self.encoder.take().expect("the writer is not finished");
+        encoder.finish().map_err(|e| GameYError::IoError {
+            message: "Failed to finish compressed stream".to_string(),
+            error: e.to_string(),
+        })
+    }
+
+    fn encoder(&mut self) -> &mut dyn Write {
+        match self.encoder.as_mut().expect("the writer is not finished") {
+            Encoder::Plain(writer) => writer,
+            #[cfg(feature = "gzip")]
+            Encoder::Gzip(encoder) => encoder,
+            #[cfg(feature = "zstd")]
+            Encoder::Zstd(encoder) => encoder,
+        }
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.encoder().write(buf)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.encoder().flush()
+    }
+}
+
+impl<W: Write> Drop for CompressedWriter<W> {
+    fn drop(&mut self) {
+        if let Some(encoder) = self.encoder.take()
+            && let Err(e) = encoder.finish()
+        {
+            tracing::warn!("Failed to finish compressed stream: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(compression: Compression) -> Vec<u8> {
+        // Two streams one after the other, as after appending to a file
+        let mut file = Vec::new();
+        for text in ["first line\n", "second line\n"] {
+            let mut writer = compression.writer(&mut file).unwrap();
+            writer.write_all(text.as_bytes()).unwrap();
+            writer.finish().unwrap();
+        }
+        let mut read = String::new();
+        compression
+            .reader(file.as_slice())
+            .unwrap()
+            .read_to_string(&mut read)
+            .unwrap();
+        assert_eq!(read, "first line\nsecond line\n");
+        file
+    }
+
+    #[test]
+    fn test_compression_from_path() {
+        let of = |path: &str| Compression::from_path(Path::new(path));
+        assert_eq!(of("game.yen"), Compression::None);
+        assert_eq!(of("game.yen.gz"), Compression::Gzip);
+        assert_eq!(of("games/selfplay.jsonl.zst"), Compression::Zstd);
+        assert_eq!(of("gz"), Compression::None);
+    }
+
+    #[test]
+    fn test_uncompressed_round_trip() {
+        assert_eq!(
+            round_trip(Compression::None),
+            b"first line\nsecond line\n".to_vec()
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_gzip_round_trip() {
+        assert_eq!(&round_trip(Compression::Gzip)[..2], &[0x1f, 0x8b]);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_zstd_round_trip() {
+        assert_eq!(
+            &round_trip(Compression::Zstd)[..4],
+            &[0x28, 0xb5, 0x2f, 0xfd]
+        );
+    }
+
+    #[test]
+    fn test_unavailable_compression_is_an_error() {
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            assert_eq!(
+                compression.reader(&b""[..]).is_ok(),
+                compression.is_available()
+            );
+            assert_eq!(
+                compression.writer(Vec::new()).is_ok(),
+                compression.is_available()
+            );
+        }
+    }
+}
@@ -1,9 +1,12 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 /// Represents special game actions that are not regular piece placements.
 ///
 /// These actions allow players to perform non-placement moves during the game.
-#[derive(Debug, Clone, PartialEq, Eq)]
+/// They are serialized in lowercase, as `"swap"` and `"resign"`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum GameAction {
     /// The swap rule: allows the second player to swap colors after the first move.
     /// This is commonly used in games like Hex and Y to balance first-move advantage.
@@ -35,6 +38,19 @@ mod tests {
         assert_eq!(format!("{}", GameAction::Resign), "Resign");
     }
 
+    #[test]
+    fn test_wire_format() {
+        assert_eq!(
+            serde_json::to_string(&GameAction::Swap).unwrap(),
+            r#""swap""#
+        );
+        assert_eq!(
+            serde_json::from_str::<GameAction>(r#""resign""#).unwrap(),
+            GameAction::Resign
+        );
+        assert!(serde_json::from_str::<GameAction>(r#""Resign""#).is_err());
+    }
+
     #[test]
     fn test_equality() {
         assert_eq!(GameAction::Swap, GameAction::Swap);
@@ -0,0 +1,120 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A note attached to a point of the game history: a comment, a glyph
+/// judging the move just played, or both.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// The number of moves played when the note was made, 0 before the
+    /// first move.
+    pub move_number: usize,
+    /// The judgement of the move just played, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub glyph: Option<Glyph>,
+    /// The text of the note, empty for a glyph alone.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub text: String,
+}
+
+impl Annotation {
+    /// Creates a note made after `move_number` moves.
+    pub fn new(move_number: usize, text: impl Into<String>) -> Self {
+        Annotation {
+            move_number,
+            glyph: None,
+            text: text.into(),
+        }
+    }
+
+    /// Sets the glyph judging the move.
+    pub fn with_glyph(mut self, glyph: Glyph) -> Self {
+        self.glyph = Some(glyph);
+        self
+    }
+}
+
+/// A judgement of a move, written after it as in chess notation.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Glyph {
+    /// A good move, `!`.
+    #[serde(rename = "!")]
+    Good,
+    /// A mistake, `?`.
+    #[serde(rename = "?")]
+    Mistake,
+    /// A brilliant move, `!!`.
+    #[serde(rename = "!!")]
+    Brilliant,
+    /// A blunder, `??`.
+    #[serde(rename = "??")]
+    Blunder,
+    /// An interesting move, `!?`.
+    #[serde(rename = "!?")]
+    Interesting,
+    /// A dubious move, `?!`.
+    #[serde(rename = "?!")]
+    Dubious,
+}
+
+impl Display for Glyph {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            Glyph::Good => "!",
+            Glyph::Mistake => "?",
+            Glyph::Brilliant => "!!",
+            Glyph::Blunder => "??",
+            Glyph::Interesting => "!?",
+            Glyph::Dubious => "?!",
+        };
+        write!(f, "{}", s)
+    }
+}
+
+impl FromStr for Glyph {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "!" => Ok(Glyph::Good),
+            "?" => Ok(Glyph::Mistake),
+            "!!" => Ok(Glyph::Brilliant),
+            "??" => Ok(Glyph::Blunder),
+            "!?" => Ok(Glyph::Interesting),
+            "?!" => Ok(Glyph::Dubious),
+            _ => Err(format!(
+                "Unknown glyph '{}', expected one of: !, ?, !!, ??, !?, ?!",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_display_and_parse_roundtrip() {
+        for glyph in [
+            Glyph::Good,
+            Glyph::Mistake,
+            Glyph::Brilliant,
+            Glyph::Blunder,
+            Glyph::Interesting,
+            Glyph::Dubious,
+        ] {
+            assert_eq!(glyph.to_string().parse::<Glyph>(), Ok(glyph));
+        }
+        assert!("!!!".parse::<Glyph>().is_err());
+    }
+
+    #[test]
+    fn test_annotation_serde() {
+        let annotation = Annotation::new(3, "").with_glyph(Glyph::Blunder);
+        let json = serde_json::to_value(&annotation).unwrap();
+        assert_eq!(json, serde_json::json!({"move_number": 3, "glyph": "??"}));
+        let parsed: Annotation = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed, annotation);
+    }
+}
@@ -0,0 +1,287 @@
+use crate::{BoardTopology, PlayerId};
+use std::ops::{BitAnd, BitOr, Not};
+
+/// The largest number of cells of a board with a [`BitLayout`].
+///
+/// Such boards fit the padded grid of the layout in 256 bits: up to size 15
+/// for Y and size 11 for Hex.
+pub const BITBOARD_MAX_CELLS: u32 = 128;
+
+/// A set of cells of a small board, one bit per cell of a [`BitLayout`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    low: u128,
+    high: u128,
+}
+
+impl BitBoard {
+    /// Returns true if no bit is set.
+    pub fn is_empty(&self) -> bool {
+        self.low == 0 && self.high == 0
+    }
+
+    /// Returns the number of bits set.
+    pub fn count(&self) -> u32 {
+        self.low.count_ones() + self.high.count_ones()
+    }
+
+    /// Sets the given bit.
+    pub fn set(&mut self, bit: u32) {
+        match bit {
+            0..128 => self.low |= 1 << bit,
+            _ => self.high |= 1 << (bit - 128),
+        }
+    }
+
+    /// Returns true if the given bit is set.
+    pub fn contains(&self, bit: u32) -> bool {
+        match bit {
+            0..128 => self.low & 1 << bit != 0,
+            _ => self.high & 1 << (bit - 128) != 0,
+        }
+    }
+
+    /// Returns the set holding only the lowest bit of this one.
+    fn lowest(&self) -> BitBoard {
+        match self.low {
+            0 => BitBoard {
+                low: 0,
+                high: self.high & self.high.wrapping_neg(),
+            },
+            low => BitBoard {
+                low: low & low.wrapping_neg(),
+                high: 0,
+            },
+        }
+    }
+
+    /// Moves every bit `n` places up, `0 < n < 128`.
+    fn shift_up(self, n: u32) -> BitBoard {
+        BitBoard {
+            low: self.low << n,
+            high: self.high << n | self.low >> (128 - n),
+        }
+    }
+
+    /// Moves every bit `n` places down, `0 < n < 128`.
+    fn shift_down(self, n: u32) -> BitBoard {
+        BitBoard {
+            low: self.low >> n | self.high << (128 - n),
+            high: self.high >> n,
+        }
+    }
+}
+
+impl BitAnd for BitBoard {
+    type Output = BitBoard;
+
+    fn bitand(self, other: BitBoard) -> BitBoard {
+        BitBoard {
+            low: self.low & other.low,
+            high: self.high & other.high,
+        }
+    }
+}
+
+impl BitOr for BitBoard {
+    type Output = BitBoard;
+
+    fn bitor(self, other: BitBoard) -> BitBoard {
+        BitBoard {
+            low: self.low | other.low,
+            high: self.high | other.high,
+        }
+    }
+}
+
+impl Not for BitBoard {
+    type Output = BitBoard;
+
+    fn not(self) -> BitBoard {
+        BitBoard {
+            low: !self.low,
+            high: !self.high,
+        }
+    }
+}
+
+/// How the cells of a small board map to the bits of a [`BitBoard`], so that
+/// connections can be followed with shifts instead of cell by cell.
+///
+/// Row `r` of the board takes the bits from `r * width`, where the width is
+/// one more than the longest row. The unused bit at the end of every row
+/// keeps shifted stones from wrapping into the next row, so the neighbors of
+/// all cells at once are the set shifted by one step in each of the six
+/// directions.
+///
+/// # Example
+///
+/// ```
+/// use gamey_core::{BitBoard, BitLayout, BoardTopology, PlayerId};
+///
+/// let layout = BitLayout::new(BoardTopology::Y, 3).unwrap();
+/// let mut stones = BitBoard::default();
+/// // The left edge of the triangle touches all three sides
+/// for index in [0, 1, 3] {
+///     stones.set(layout.bit(index));
+/// }
+/// assert!(layout.connects_sides(stones, PlayerId::new(0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitLayout {
+    /// The bit of each cell, by index.
+    bits: Vec<u8>,
+    /// The bits of the cells of the board.
+    board: BitBoard,
+    /// For each player, the bits of the cells on each goal side.
+    sides: [[BitBoard; 3]; 2],
+    /// The distances in bits to the neighbors of a cell, in each direction.
+    steps: [u32; 3],
+}
+
+impl BitLayout {
+    /// Computes the layout of a board, or returns `None` if the board is
+    /// empty or has more than [`BITBOARD_MAX_CELLS`] cells.
+    pub fn new(topology: BoardTopology, board_size: u32) -> Option<Self> {
+        if board_size == 0 || topology.cell_count(board_size) > BITBOARD_MAX_CELLS {
+            return None;
+        }
+        let width = board_size + 1;
+        // On both boards the cells of the row below are `width` bits away.
+        // The remaining diagonal neighbors are down-right on a Y board and
+        // down-left on a Hex board.
+        let steps = match topology {
+            BoardTopology::Y => [1, width, width + 1],
+            BoardTopology::Hex => [1, width, board_size],
+        };
+        let mut layout = BitLayout {
+            bits: Vec::new(),
+            board: BitBoard::default(),
+            sides: Default::default(),
+            steps,
+        };
+        for (row, cells) in topology.rows(board_size).into_iter().enumerate() {
+            for (col, coords) in cells.into_iter().enumerate() {
+                let bit = row as u32 * width + col as u32;
+                layout.bits.push(bit as u8);
+                layout.board.set(bit);
+                for player in [0, 1] {
+                    let touched = topology.sides_touched(coords, board_size, PlayerId::new(player));
+                    for (side, touched) in touched.into_iter().enumerate() {
+                        if touched {
+                            layout.sides[player as usize][side].set(bit);
+                        }
+                    }
+                }
+            }
+        }
+        Some(layout)
+    }
+
+    /// Returns the bit of the cell with the given index.
+    ///
+    /// # Panics
+    /// Panics if the index is off the board.
+    pub fn bit(&self, index: u32) -> u32 {
+        self.bits[index as usize] as u32
+    }
+
+    /// Returns the cells adjacent to any of the given ones.
+    pub fn neighbors(&self, cells: BitBoard) -> BitBoard {
+        let around = self
+            .steps
+            .iter()
+            .fold(BitBoard::default(), |around, &step| {
+                around | cells.shift_up(step) | cells.shift_down(step)
+            });
+        around & self.board
+    }
+
+    /// Returns the stones connected to any of the `seeds`, the seeds
+    /// included.
+    pub fn flood_fill(&self, seeds: BitBoard, stones: BitBoard) -> BitBoard {
+        let mut reached = seeds & stones;
+        loop {
+            let grown = reached | (self.neighbors(reached) & stones);
+            if grown == reached {
+                return reached;
+            }
+            reached = grown;
+        }
+    }
+
+    /// Returns true if a group of the given stones of `player` touches every
+    /// goal side of that player.
+    pub fn connects_sides(&self, stones: BitBoard, player: PlayerId) -> bool {
+        let [side_a, side_b, side_c] = self.sides[player.id() as usize % 2];
+        let mut unexplored = stones & side_a;
+        while !unexplored.is_empty() {
+            let group = self.flood_fill(unexplored.lowest(), stones);
+            if !(group & side_b).is_empty() && !(group & side_c).is_empty() {
+                return true;
+            }
+            unexplored = unexplored & !group;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameStatus, GameY, Movement};
+    use rand::seq::SliceRandom;
+
+    #[test]
+    fn test_connects_sides_agrees_with_the_game() {
+        let mut rng = rand::rng();
+        for (topology, size) in [(BoardTopology::Y, 15), (BoardTopology::Hex, 11)] {
+            let layout = BitLayout::new(topology, size).unwrap();
+            for _ in 0..20 {
+                let mut game = GameY::with_topology(size, topology);
+                let mut cells: Vec<u32> = (0..game.total_cells()).collect();
+                cells.shuffle(&mut rng);
+                let mut stones = [BitBoard::default(); 2];
+                for index in cells {
+                    let Some(player) = game.next_player() else {
+                        break;
+                    };
+                    let coords = game.cell_coords(index);
+                    game.add_move(Movement::Placement { player, coords })
+                        .unwrap();
+                    stones[player.id() as usize].set(layout.bit(index));
+                    let won = matches!(game.status(), GameStatus::Finished { .. });
+                    assert_eq!(
+                        layout.connects_sides(stones[player.id() as usize], player),
+                        won
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_neighbors_match_the_topology() {
+        for topology in BoardTopology::all() {
+            let layout = BitLayout::new(topology, 5).unwrap();
+            for index in 0..topology.cell_count(5) {
+                let mut cell = BitBoard::default();
+                cell.set(layout.bit(index));
+                let mut expected = BitBoard::default();
+                let coords = topology.from_index(index, 5);
+                for neighbor in topology.neighbors(coords, 5) {
+                    expected.set(layout.bit(topology.to_index(neighbor, 5)));
+                }
+                assert_eq!(layout.neighbors(cell), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_only_small_boards_have_a_layout() {
+        assert!(BitLayout::new(BoardTopology::Y, 15).is_some());
+        assert!(BitLayout::new(BoardTopology::Y, 16).is_none());
+        assert!(BitLayout::new(BoardTopology::Hex, 11).is_some());
+        assert!(BitLayout::new(BoardTopology::Hex, 12).is_none());
+    }
+}
@@ -45,12 +45,12 @@ impl Coordinates {
     /// The index follows row-major order starting from the top of the triangle.
     /// For a board of size N, indices go from 0 to N*(N+1)/2 - 1.
     pub fn from_index(index: u32, board_size: u32) -> Self {
-        // As i = (r * (r + 1)) / 2
-        // r = floor((sqrt(8*i + 1) - 1) / 2)
-        let i_f = index as f64;
-        let r = (((8.0 * i_f + 1.0).sqrt() - 1.0) / 2.0).floor() as u32;
+        // Row r starts at index r * (r + 1) / 2, so the row of an index is
+        // r = floor((sqrt(8*i + 1) - 1) / 2), computed exactly with an
+        // integer square root
+        let r = (((8 * index as u64 + 1).isqrt() - 1) / 2) as u32;
 
-        let row_start_index = (r * (r + 1)) / 2;
+        let row_start_index = row_start(r);
         let c = index - row_start_index;
 
         let x = board_size - 1 - r;
@@ -60,16 +60,35 @@ impl Coordinates {
         Coordinates::new(x, y, z)
     }
 
+    /// Like [`Coordinates::from_index`], but returns `None` if the index is
+    /// past the last cell of a board of size `board_size`.
+    pub fn checked_from_index(index: u32, board_size: u32) -> Option<Self> {
+        let cells = u64::from(board_size) * (u64::from(board_size) + 1) / 2;
+        (u64::from(index) < cells).then(|| Coordinates::from_index(index, board_size))
+    }
+
     /// Converts these coordinates to a linear index.
     ///
     /// This is the inverse of `from_index`.
     pub fn to_index(&self, board_size: u32) -> u32 {
         let r = (board_size - 1) - self.x;
-        let row_start_index = (r * (r + 1)) / 2;
+        let row_start_index = row_start(r);
         let c = self.y;
         row_start_index + c
     }
 
+    /// Like [`Coordinates::to_index`], but returns `None` instead of
+    /// overflowing if the coordinates are not on a board of size
+    /// `board_size` or their index does not fit in a `u32`.
+    pub fn checked_to_index(&self, board_size: u32) -> Option<u32> {
+        let r = board_size.checked_sub(1)?.checked_sub(self.x)?;
+        if self.y > r {
+            return None;
+        }
+        let index = u64::from(r) * (u64::from(r) + 1) / 2 + u64::from(self.y);
+        u32::try_from(index).ok()
+    }
+
     /// Creates coordinates from a slice of 3 u32 values.
     ///
     /// Returns `None` if the slice does not have exactly 3 elements.
@@ -100,6 +119,12 @@ impl Coordinates {
     }
 }
 
+/// Returns the index of the first cell of row `r`, without overflowing on
+/// the largest boards whose indices fit in a `u32`.
+fn row_start(r: u32) -> u32 {
+    (r as u64 * (r as u64 + 1) / 2) as u32
+}
+
 impl From<Coordinates> for Vec<u32> {
     fn from(coords: Coordinates) -> Self {
         vec![coords.x, coords.y, coords.z]
@@ -184,6 +209,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_index_roundtrip_at_row_edges_of_large_boards() {
+        let board_size = 92_681;
+        for r in [0, 1, 2, 1_000, 65_535, 65_536, 92_679, 92_680] {
+            let first = row_start(r);
+            let last = first + r;
+            assert_eq!(Coordinates::from_index(first, board_size).y(), 0);
+            assert_eq!(Coordinates::from_index(last, board_size).z(), 0);
+            assert_eq!(
+                Coordinates::from_index(last, board_size).to_index(board_size),
+                last
+            );
+        }
+        // The bottom left corner of the largest board whose indices fit in
+        // a u32
+        assert_eq!(
+            Coordinates::from_index(row_start(92_680), board_size),
+            Coordinates::new(0, 0, 92_680)
+        );
+    }
+
+    #[test]
+    fn test_checked_conversions_at_the_limits() {
+        // The last cell of the largest board whose indices fit in a u32
+        let board_size = 92_681;
+        let last = Coordinates::new(0, 92_680, 0);
+        assert_eq!(last.checked_to_index(board_size), Some(4_294_930_220));
+        assert_eq!(
+            Coordinates::checked_from_index(4_294_930_220, board_size),
+            Some(last)
+        );
+        assert_eq!(
+            Coordinates::checked_from_index(4_294_930_221, board_size),
+            None
+        );
+        // One row more and the last cells cannot be indexed
+        assert_eq!(
+            Coordinates::new(0, 92_681, 0).checked_to_index(92_682),
+            None
+        );
+        // Coordinates off the board
+        assert_eq!(Coordinates::new(5, 0, 0).checked_to_index(5), None);
+        assert_eq!(Coordinates::new(0, 5, 0).checked_to_index(5), None);
+        assert_eq!(Coordinates::new(0, 0, 0).checked_to_index(0), None);
+        assert_eq!(
+            Coordinates::checked_from_index(9, 4),
+            Some(Coordinates::new(0, 3, 0))
+        );
+        assert_eq!(Coordinates::checked_from_index(10, 4), None);
+    }
+
     #[test]
     fn test_corner_touches_two_sides() {
         // Top corner touches sides B and C (y=0 and z=0)
@@ -243,6 +319,22 @@ mod tests {
                 "Coords {:?} did not roundtrip for board_size {}", coords, board_size);
         }
 
+        /// Property: The integer row lookup matches a search over the row
+        /// starts, up to the largest boards whose indices fit in a u32.
+        #[test]
+        fn prop_from_index_matches_row_search(board_size in 1u32..=92_681, idx_factor in 0.0f64..1.0) {
+            let total_cells = (board_size as u64 * (board_size as u64 + 1) / 2) as u32;
+            let idx = ((idx_factor * total_cells as f64) as u32).min(total_cells - 1);
+            let row = (0..board_size).rev().find(|&r| row_start(r) <= idx).unwrap();
+            let expected = Coordinates::new(
+                board_size - 1 - row,
+                idx - row_start(row),
+                row - (idx - row_start(row)),
+            );
+            prop_assert_eq!(Coordinates::from_index(idx, board_size), expected);
+            prop_assert_eq!(expected.to_index(board_size), idx);
+        }
+
         /// Property: All coordinate components are non-negative (ensured by u32).
         /// This test verifies the generated index is always within valid bounds.
         #[test]
@@ -0,0 +1,189 @@
+use crate::{BoardTopology, GameAction, GameStatus, GameY, Group, Movement, PlayerId};
+use std::fmt::Write;
+
+impl GameY {
+    /// Returns the position in plain sentences, one per line, for screen
+    /// readers and other places where the drawn board is of no use.
+    ///
+    /// The description gives the variant and whose turn it is, the last
+    /// move, the stones of each row from the top, and the groups of each
+    /// player with the sides they touch. Cells are given by their algebraic
+    /// names, see [`GameY::cell_name`].
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let mut game = GameY::new(3);
+    /// game.play_move_list("4").unwrap();
+    /// let description = game.describe();
+    /// assert!(description.contains("Last move: player 0 at b3."));
+    /// assert!(description.contains("Row 3: b3 player 0."));
+    /// ```
+    pub fn describe(&self) -> String {
+        let mut text = String::new();
+        let name = match self.topology() {
+            BoardTopology::Y => "Y",
+            BoardTopology::Hex => "Hex",
+        };
+        let moves = self.move_count();
+        let _ = writeln!(
+            text,
+            "Game of {}, size {}, {} rules, {} {} played.",
+            name,
+            self.board_size(),
+            self.ruleset(),
+            moves,
+            if moves == 1 { "move" } else { "moves" }
+        );
+        let _ = match self.status() {
+            GameStatus::Ongoing { next_player } => {
+                writeln!(text, "Player {} to move.", next_player)
+            }
+            GameStatus::Finished { winner } => writeln!(text, "Player {} won.", winner),
+        };
+        let _ = match self.last_move() {
+            Some(Movement::Placement { player, coords }) => writeln!(
+                text,
+                "Last move: player {} at {}.",
+                player,
+                self.cell_name(*coords)
+            ),
+            Some(Movement::Action { player, action }) => {
+                let verb = match action {
+                    GameAction::Swap => "swapped",
+                    GameAction::Resign => "resigned",
+                };
+                writeln!(text, "Last move: player {} {}.", player, verb)
+            }
+            None => writeln!(text, "No moves yet."),
+        };
+
+        for (row, cells) in self.topology().rows(self.board_size()).iter().enumerate() {
+            let stones: Vec<String> = cells
+                .iter()
+                .filter_map(|&coords| {
+                    let owner = match self.stone_at(coords) {
+                        Some((_, player)) => format!("player {}", player),
+                        None if self.is_blocked(coords) => "blocked".to_string(),
+                        None => return None,
+                    };
+                    Some(format!("{} {}", self.cell_name(coords), owner))
+                })
+                .collect();
+            let stones = if stones.is_empty() {
+                "empty".to_string()
+            } else {
+                stones.join(", ")
+            };
+            let _ = writeln!(text, "Row {}: {}.", row + 1, stones);
+        }
+
+        for player in [PlayerId::new(0), PlayerId::new(1)] {
+            let groups = self.groups(player);
+            let _ = match groups.len() {
+                0 => writeln!(text, "Player {} has no stones.", player),
+                1 => writeln!(text, "Player {} has 1 group:", player),
+                count => writeln!(text, "Player {} has {} groups:", player, count),
+            };
+            for group in &groups {
+                let _ = writeln!(text, "- {}.", self.describe_group(group));
+            }
+        }
+        text
+    }
+
+    /// Describes the stones of a group and the sides they touch.
+    fn describe_group(&self, group: &Group) -> String {
+        let cells: Vec<String> = group
+            .cells
+            .iter()
+            .map(|&coords| self.cell_name(coords))
+            .collect();
+        let stones = match group.size() {
+            1 => format!("1 stone at {}", cells[0]),
+            size => format!("{} stones at {}", size, cells.join(", ")),
+        };
+        let touched = [
+            group.touches_side_a,
+            group.touches_side_b,
+            group.touches_side_c,
+        ];
+        let sides: Vec<&str> = side_names(self.topology(), group.player)
+            .into_iter()
+            .zip(touched)
+            .filter_map(|(side, touched)| side.filter(|_| touched))
+            .collect();
+        match sides.as_slice() {
+            [] => format!("{}, touching no side", stones),
+            [side] => format!("{}, touching {}", stones, side),
+            [first @ .., last] => format!("{}, touching {} and {}", stones, first.join(", "), last),
+        }
+    }
+}
+
+/// Returns the names of the goal sides of `player`, in the order of
+/// [`Group::touches_side_a`] and the others; Hex has no third side.
+fn side_names(topology: BoardTopology, player: PlayerId) -> [Option<&'static str>; 3] {
+    match (topology, player.id()) {
+        (BoardTopology::Y, _) => [
+            Some("side A at the bottom"),
+            Some("side B at the left"),
+            Some("side C at the right"),
+        ],
+        (BoardTopology::Hex, 0) => [Some("the top edge"), Some("the bottom edge"), None],
+        (BoardTopology::Hex, _) => [Some("the left edge"), Some("the right edge"), None],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ruleset;
+
+    #[test]
+    fn test_describe_lists_rows_and_groups() {
+        let mut game = GameY::new(3);
+        // Player 0 takes the top corner and the cell below it, player 1 the
+        // bottom right corner
+        game.play_move_list("0 5 1").unwrap();
+        let description = game.describe();
+        let lines: Vec<&str> = description.lines().collect();
+        assert_eq!(
+            lines,
+            [
+                "Game of Y, size 3, standard rules, 3 moves played.",
+                "Player 1 to move.",
+                "Last move: player 0 at a2.",
+                "Row 1: a1 player 0.",
+                "Row 2: a2 player 0.",
+                "Row 3: c3 player 1.",
+                "Player 0 has 1 group:",
+                "- 2 stones at a1, a2, touching side B at the left and side C at the right.",
+                "Player 1 has 1 group:",
+                "- 1 stone at c3, touching side A at the bottom and side C at the right.",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_describe_empty_and_finished_games() {
+        let game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let description = game.describe();
+        assert!(description.contains("master-y rules, 0 moves played."));
+        assert!(description.contains("No moves yet."));
+        assert!(description.contains("Row 1: a1 blocked."));
+        assert!(description.contains("Row 2: empty."));
+        assert!(description.contains("Player 1 has no stones."));
+
+        let mut hex = GameY::with_topology(2, BoardTopology::Hex);
+        hex.play_move_list("0 1 2").unwrap();
+        let description = hex.describe();
+        assert!(description.contains("Player 0 won."));
+        assert!(
+            description
+                .contains("- 2 stones at a1, a2, touching the top edge and the bottom edge.")
+        );
+        assert!(description.contains("- 1 stone at b1, touching the right edge."));
+    }
+}
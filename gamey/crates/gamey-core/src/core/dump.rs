@@ -0,0 +1,107 @@
+use crate::{BoardTopology, Coordinates, GameYError, Group, MoveRecord, Ruleset, migrate};
+use serde::{Deserialize, Serialize};
+
+/// The current version of the [`GameStateDump`] format.
+pub const STATE_VERSION: u32 = 1;
+
+/// A complete snapshot of a game, as produced by
+/// [`GameY::to_state`](crate::GameY::to_state).
+///
+/// Unlike [`YEN`](crate::YEN), which only describes the position, the dump
+/// also carries the move history, the status, the available cells and the
+/// connected groups. Cells and groups are listed in cell index order, so
+/// equal games always produce the same dump.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GameStateDump {
+    /// The format version, see [`STATE_VERSION`].
+    pub version: u32,
+    /// The board size.
+    pub size: u32,
+    /// The shape of the board.
+    pub topology: BoardTopology,
+    /// The variant being played.
+    pub ruleset: Ruleset,
+    /// Every cell of the board, in index order.
+    pub board: Vec<CellDump>,
+    /// Handicap stones placed for player 0 before the first move.
+    pub handicap: Vec<Coordinates>,
+    /// Every move played so far, in order.
+    pub history: Vec<MoveRecord>,
+    /// Whether the game is ongoing or finished.
+    pub status: StatusDump,
+    /// Indices of the cells where a stone can still be placed, sorted.
+    pub available_cells: Vec<u32>,
+    /// The connected groups of stones.
+    pub groups: Vec<GroupDump>,
+}
+
+impl GameStateDump {
+    /// Parses a state dump, upgrading it from older format versions.
+    ///
+    /// # Errors
+    /// Returns `GameYError::UnsupportedVersion` for dumps written by a newer
+    /// version of the crate, or `GameYError::SerdeError` if the JSON is
+    /// malformed.
+    pub fn from_json(json: &str) -> Result<Self, GameYError> {
+        let mut value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| GameYError::SerdeError { error: e })?;
+        migrate(&mut value, "state dump", STATE_VERSION, &[])?;
+        serde_json::from_value(value).map_err(|e| GameYError::SerdeError { error: e })
+    }
+}
+
+/// A single cell in a [`GameStateDump`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CellDump {
+    /// The linear index of the cell.
+    pub index: u32,
+    /// The coordinates of the cell.
+    pub coords: Coordinates,
+    /// The player owning the stone on the cell, if any.
+    pub player: Option<u32>,
+    /// True if the ruleset keeps the cell empty.
+    pub blocked: bool,
+}
+
+/// The status of the game in a [`GameStateDump`].
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum StatusDump {
+    /// The game is still in progress.
+    Ongoing {
+        /// The player who has to move.
+        next_player: u32,
+    },
+    /// The game has ended.
+    Finished {
+        /// The player who won.
+        winner: u32,
+    },
+}
+
+/// A group of connected stones of one player in a [`GameStateDump`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GroupDump {
+    /// The player owning the group.
+    pub player: u32,
+    /// The cells of the group, in index order.
+    pub cells: Vec<Coordinates>,
+    /// True if the group touches the first goal side.
+    pub touches_side_a: bool,
+    /// True if the group touches the second goal side.
+    pub touches_side_b: bool,
+    /// True if the group touches the third goal side.
+    pub touches_side_c: bool,
+}
+
+impl From<Group> for GroupDump {
+    fn from(group: Group) -> Self {
+        GroupDump {
+            player: group.player.id(),
+            cells: group.cells,
+            touches_side_a: group.touches_side_a,
+            touches_side_b: group.touches_side_b,
+            touches_side_c: group.touches_side_c,
+        }
+    }
+}
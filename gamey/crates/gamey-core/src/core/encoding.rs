@@ -0,0 +1,176 @@
+use crate::{BoardTopology, Coordinates, GameY};
+
+/// The number of planes of [`GameY::encode_planes`].
+pub const ENCODING_PLANES: usize = 5;
+
+impl GameY {
+    /// Returns which cells the player to move may place a stone on, indexed
+    /// like the cells (see [`GameY::cell_index`]): `true` for the empty
+    /// cells that are not blocked, and `false` everywhere once the game is
+    /// over.
+    ///
+    /// The mask has [`GameY::total_cells`] entries, one per output of a
+    /// policy network, so that illegal moves can be masked out before the
+    /// softmax.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let mut game = GameY::new(3);
+    /// game.play_move_list("4").unwrap();
+    /// let mask = game.legal_move_mask();
+    /// assert_eq!(mask, vec![true, true, true, true, false, true]);
+    /// ```
+    pub fn legal_move_mask(&self) -> Vec<bool> {
+        let ongoing = self.next_player().is_some();
+        (0..self.total_cells())
+            .map(|index| ongoing && self.is_available(index))
+            .collect()
+    }
+
+    /// Returns the position as a stack of [`ENCODING_PLANES`] planes of
+    /// `size` × `size` values, flattened in row-major order: the value of
+    /// plane `p` at row `r` and column `c` is at `(p * size + r) * size + c`,
+    /// see [`GameY::encoding_shape`].
+    ///
+    /// Each cell sits at the row and column given by
+    /// [`GameY::grid_position`]. A Y board fills the lower left triangle of
+    /// the grid and a Hex board all of it. The planes are, in order:
+    ///
+    /// 0. 1 on the stones of the first player.
+    /// 1. 1 on the stones of the second player.
+    /// 2. 1 on the legal moves, as in [`GameY::legal_move_mask`].
+    /// 3. 1 on every cell of the board, blocked cells included, and 0 on
+    ///    the padding of a Y board.
+    /// 4. 1 on every cell of the board if the second player is to move, and
+    ///    0 otherwise.
+    ///
+    /// Every value is 0 or 1. The colours are not swapped for the player to
+    /// move, since the players of Hex connect different sides.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::{ENCODING_PLANES, GameY};
+    ///
+    /// let mut game = GameY::new(3);
+    /// game.play_move_list("4").unwrap();
+    /// let planes = game.encode_planes();
+    /// assert_eq!(game.encoding_shape(), [ENCODING_PLANES, 3, 3]);
+    /// assert_eq!(planes.len(), ENCODING_PLANES * 3 * 3);
+    /// // The stone of cell 4 is on row 2 and column 1 of the first plane
+    /// assert_eq!(game.grid_position(game.cell_coords(4)), (2, 1));
+    /// assert_eq!(planes[2 * 3 + 1], 1.0);
+    /// ```
+    pub fn encode_planes(&self) -> Vec<f32> {
+        let size = self.board_size() as usize;
+        let plane = size * size;
+        let mut planes = vec![0.0; ENCODING_PLANES * plane];
+        let second_to_move = self.next_player().is_some_and(|player| player.id() == 1);
+        let mask = self.legal_move_mask();
+        for index in 0..self.total_cells() {
+            let coords = self.cell_coords(index);
+            let (row, column) = self.grid_position(coords);
+            let cell = row as usize * size + column as usize;
+            if let Some((_, player)) = self.stone_at(coords) {
+                planes[player.id().min(1) as usize * plane + cell] = 1.0;
+            }
+            if mask[index as usize] {
+                planes[2 * plane + cell] = 1.0;
+            }
+            planes[3 * plane + cell] = 1.0;
+            if second_to_move {
+                planes[4 * plane + cell] = 1.0;
+            }
+        }
+        planes
+    }
+
+    /// Returns the shape of [`GameY::encode_planes`]: the planes, the rows
+    /// and the columns.
+    pub fn encoding_shape(&self) -> [usize; 3] {
+        let size = self.board_size() as usize;
+        [ENCODING_PLANES, size, size]
+    }
+
+    /// Returns the row and column of the cell at `coords` in the planes of
+    /// [`GameY::encode_planes`].
+    ///
+    /// Rows are counted from the top and columns from the left, as the board
+    /// is drawn by [`GameY::render`]: on a Y board row `r` holds `r + 1`
+    /// cells, on a Hex board every row holds `size` cells.
+    pub fn grid_position(&self, coords: Coordinates) -> (u32, u32) {
+        match self.topology() {
+            BoardTopology::Y => (self.board_size() - 1 - coords.x(), coords.y()),
+            BoardTopology::Hex => (coords.x(), coords.y()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ruleset;
+
+    /// Returns the value of plane `plane` at `index` of `game`.
+    fn value(game: &GameY, planes: &[f32], plane: usize, index: u32) -> f32 {
+        let size = game.board_size() as usize;
+        let (row, column) = game.grid_position(game.cell_coords(index));
+        planes[(plane * size + row as usize) * size + column as usize]
+    }
+
+    #[test]
+    fn test_planes_of_a_y_board() {
+        let mut game = GameY::new(4);
+        game.play_move_list("0 6").unwrap();
+        let planes = game.encode_planes();
+        assert_eq!(planes.len(), ENCODING_PLANES * 16);
+        assert_eq!(value(&game, &planes, 0, 0), 1.0);
+        assert_eq!(value(&game, &planes, 1, 6), 1.0);
+        assert_eq!(value(&game, &planes, 1, 0), 0.0);
+        // The board takes 10 of the 16 places of the grid, and every place
+        // of the board but the stones is legal
+        assert_eq!(planes[3 * 16..4 * 16].iter().sum::<f32>(), 10.0);
+        assert_eq!(planes[2 * 16..3 * 16].iter().sum::<f32>(), 8.0);
+        // The first player is to move
+        assert_eq!(planes[4 * 16..].iter().sum::<f32>(), 0.0);
+        // The top right corner of the grid is padding
+        assert_eq!(planes[3 * 16 + 3], 0.0);
+
+        game.play_move_list("9").unwrap();
+        let planes = game.encode_planes();
+        assert_eq!(planes[4 * 16..].iter().sum::<f32>(), 10.0);
+    }
+
+    #[test]
+    fn test_planes_of_a_hex_board() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        game.play_move_list("5").unwrap();
+        assert_eq!(game.grid_position(game.cell_coords(5)), (1, 2));
+        let planes = game.encode_planes();
+        assert_eq!(planes[5], 1.0);
+        assert_eq!(planes[3 * 9..4 * 9].iter().sum::<f32>(), 9.0);
+        assert_eq!(planes[4 * 9..].iter().sum::<f32>(), 9.0);
+    }
+
+    #[test]
+    fn test_mask_leaves_out_blocked_cells_and_finished_games() {
+        let game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let mask = game.legal_move_mask();
+        assert_eq!(mask.len(), 10);
+        for index in 0..10 {
+            let blocked = game.is_blocked(game.cell_coords(index));
+            assert_eq!(mask[index as usize], !blocked);
+        }
+        let planes = game.encode_planes();
+        let blocked = (0..10)
+            .find(|&index| game.is_blocked(game.cell_coords(index)))
+            .unwrap();
+        assert_eq!(value(&game, &planes, 2, blocked), 0.0);
+        assert_eq!(value(&game, &planes, 3, blocked), 1.0);
+
+        let mut game = GameY::new(1);
+        game.play_move_list("0").unwrap();
+        assert_eq!(game.legal_move_mask(), vec![false]);
+    }
+}
@@ -0,0 +1,2716 @@
+use crate::core::SetIdx;
+use crate::core::player_set::PlayerSet;
+use crate::{
+    Annotation, BitBoard, BoardGeometry, BoardTopology, CellDump, Compression, Coordinates,
+    GameAction, GameMetadata, GameStateDump, GameYError, Glyph, Group, GroupDump, MAX_BOARD_SIZE,
+    MoveRecord, Movement, PLAYER_COUNT, PlayerId, Position, RenderOptions, Ruleset, STATE_VERSION,
+    SaveOptions, Side, StatusDump, Symmetry, YEN, YEN_VERSION,
+};
+use rand::Rng;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::path::Path;
+use std::sync::Arc;
+
+/// A Result type alias for game operations that may fail with a `GameYError`.
+pub type Result<T> = std::result::Result<T, crate::GameYError>;
+
+/// Marks the cells missing from the available cells in their position table.
+const NOT_AVAILABLE: u32 = u32::MAX;
+
+/// The main game state for a Y game.
+///
+/// Y is a connection game played on a triangular board where players
+/// take turns placing pieces. The goal is to connect all three sides
+/// of the triangle with a single chain of connected pieces.
+#[derive(Debug, Clone)]
+pub struct GameY {
+    // Size of the board (length of one side of the triangular board).
+    board_size: u32,
+
+    // Shape of the board: the triangle of Y or the rhombus of Hex.
+    topology: BoardTopology,
+
+    // Cell tables of the board, shared by every game of the same shape.
+    geometry: Arc<BoardGeometry>,
+
+    // The union-find set and the player of the stone on each cell, by cell
+    // index.
+    board: Vec<Option<(SetIdx, PlayerId)>>,
+
+    status: GameStatus,
+
+    // History of moves made in the game.
+    history: Vec<Movement>,
+
+    // Notes attached to points of the history, in the order they were made.
+    annotations: Vec<Annotation>,
+
+    // The event, date, players and result of the game.
+    metadata: GameMetadata,
+
+    // Union-Find data structure to track connected components for each player
+    sets: Vec<PlayerSet>,
+
+    // Cells where a stone may still be placed, in no particular order so
+    // that one can be removed in constant time.
+    available_cells: Vec<u32>,
+
+    // Position of each cell in `available_cells`, or NOT_AVAILABLE.
+    available_pos: Vec<u32>,
+
+    // Stones placed for player 0 before the first move (handicap games).
+    handicap: Vec<Coordinates>,
+
+    // Variant being played.
+    ruleset: Ruleset,
+
+    // Cells where no stone may be placed under the ruleset.
+    blocked: Vec<Coordinates>,
+}
+
+/// A saved state of a game, taken with [`GameY::snapshot`] and put back
+/// with [`GameY::restore`].
+///
+/// Restoring copies the flat board, union-find and move arrays into the
+/// buffers the game already has, so a search can go back to its root many
+/// times without allocating, where assigning a clone would allocate every
+/// array again.
+///
+/// # Example
+///
+/// ```
+/// use gamey_core::{Coordinates, GameY, Movement, PlayerId};
+///
+/// let mut game = GameY::new(5);
+/// let root = game.snapshot();
+/// let movement = Movement::Placement {
+///     player: PlayerId::new(0),
+///     coords: Coordinates::new(2, 1, 1),
+/// };
+/// game.add_move(movement).unwrap();
+/// game.restore(&root);
+/// assert!(game.history().is_empty());
+/// assert_eq!(game.available_cells().len(), 15);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GameSnapshot {
+    game: GameY,
+}
+
+impl GameSnapshot {
+    /// Returns the saved game.
+    pub fn game(&self) -> &GameY {
+        &self.game
+    }
+}
+
+/// Represents the state of a single cell on the board.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Cell {
+    /// The cell has no piece.
+    Empty,
+    /// The cell is occupied by a piece belonging to the specified player.
+    Occupied(PlayerId),
+}
+
+impl GameY {
+    /// Creates a new game with the specified board size and number of players.
+    pub fn new(board_size: u32) -> Self {
+        GameY::with_ruleset(board_size, Ruleset::Standard)
+    }
+
+    /// Creates a new game with the specified board size played under the
+    /// given ruleset.
+    pub fn with_ruleset(board_size: u32, ruleset: Ruleset) -> Self {
+        GameY::with_variant(board_size, BoardTopology::Y, ruleset)
+    }
+
+    /// Creates a new game on a board of the given topology, e.g. a Hex
+    /// board with `BoardTopology::Hex`.
+    pub fn with_topology(board_size: u32, topology: BoardTopology) -> Self {
+        GameY::with_variant(board_size, topology, Ruleset::Standard)
+    }
+
+    /// Creates a new game with the given board topology and ruleset.
+    ///
+    /// # Panics
+    /// Panics if `board_size` is above [`MAX_BOARD_SIZE`]; sizes that come
+    /// from users should go through [`GameY::check_board_size`] first.
+    pub fn with_variant(board_size: u32, topology: BoardTopology, ruleset: Ruleset) -> Self {
+        assert!(
+            board_size <= MAX_BOARD_SIZE,
+            "board size {} is above the maximum of {}",
+            board_size,
+            MAX_BOARD_SIZE
+        );
+        let geometry = BoardGeometry::shared(topology, board_size);
+        let total_cells = geometry.cell_count();
+        let blocked = ruleset.blocked_cells(topology, board_size);
+        let blocked_idx: Vec<u32> = blocked
+            .iter()
+            .map(|c| topology.to_index(*c, board_size))
+            .collect();
+        let available_cells: Vec<u32> = (0..total_cells)
+            .filter(|idx| !blocked_idx.contains(idx))
+            .collect();
+        let mut available_pos = vec![NOT_AVAILABLE; total_cells as usize];
+        for (pos, &cell) in available_cells.iter().enumerate() {
+            available_pos[cell as usize] = pos as u32;
+        }
+        Self {
+            board_size,
+            topology,
+            geometry,
+            board: vec![None; total_cells as usize],
+            history: Vec::new(),
+            annotations: Vec::new(),
+            metadata: GameMetadata::default(),
+            sets: Vec::new(),
+            status: GameStatus::Ongoing {
+                next_player: PlayerId::new(0),
+            },
+            available_cells,
+            available_pos,
+            handicap: Vec::new(),
+            ruleset,
+            blocked,
+        }
+    }
+
+    /// Creates a handicap game where player 0 starts with the given stones
+    /// already on the board.
+    ///
+    /// Handicap stones are not part of the move history. When at least one
+    /// stone is given, player 1 makes the first move.
+    pub fn new_with_handicap(board_size: u32, stones: &[Coordinates]) -> Result<Self> {
+        GameY::new(board_size).with_handicap(stones)
+    }
+
+    /// Places handicap stones for player 0 on a freshly created game, which
+    /// allows combining handicaps with a ruleset.
+    pub fn with_handicap(mut self, stones: &[Coordinates]) -> Result<Self> {
+        self.place_handicap(stones)?;
+        Ok(self)
+    }
+
+    /// Places the handicap stones of player 0 on a fresh board.
+    fn place_handicap(&mut self, stones: &[Coordinates]) -> Result<()> {
+        let player = PlayerId::new(0);
+        for &coords in stones {
+            self.validate_placement(player, coords)?;
+            let cell_idx = self.cell_index(coords);
+            let set_idx = self.register_piece(player, cell_idx);
+            if self.connect_neighbors_and_check_win(cell_idx, player, set_idx) {
+                self.status = GameStatus::Finished {
+                    winner: self.connection_winner(player),
+                };
+            }
+        }
+        if !stones.is_empty() && !self.check_game_over() {
+            self.status = GameStatus::Ongoing {
+                next_player: PlayerId::new(1),
+            };
+        }
+        self.handicap = stones.to_vec();
+        Ok(())
+    }
+
+    /// Fails with [`GameYError::InvalidBoardSize`] unless a game can be
+    /// played on a board of size `board_size`: from 1 to [`MAX_BOARD_SIZE`].
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::{GameY, MAX_BOARD_SIZE};
+    ///
+    /// assert!(GameY::check_board_size(MAX_BOARD_SIZE).is_ok());
+    /// assert!(GameY::check_board_size(MAX_BOARD_SIZE + 1).is_err());
+    /// assert!(GameY::check_board_size(0).is_err());
+    /// ```
+    pub fn check_board_size(board_size: u32) -> Result<()> {
+        if board_size == 0 || board_size > MAX_BOARD_SIZE {
+            return Err(GameYError::InvalidBoardSize { size: board_size });
+        }
+        Ok(())
+    }
+
+    /// Returns the standard placement of `stones` handicap stones on a board.
+    ///
+    /// The first stone goes to the center of the board and the next ones
+    /// next to the middle of sides A, B and C, so at most four stones are
+    /// supported (fewer on very small boards).
+    pub fn handicap_points(board_size: u32, stones: u32) -> Result<Vec<Coordinates>> {
+        let m = board_size.saturating_sub(1);
+        let third = (m + 1) / 3;
+        let mut candidates = vec![Coordinates::new(third, third, m - 2 * third)];
+        if m >= 1 {
+            let half = (m - 1) / 2;
+            candidates.push(Coordinates::new(1, half, m - 1 - half));
+            candidates.push(Coordinates::new(half, 1, m - 1 - half));
+            candidates.push(Coordinates::new(half, m - 1 - half, 1));
+        }
+        let mut points: Vec<Coordinates> = Vec::new();
+        for c in candidates {
+            if !points.contains(&c) {
+                points.push(c);
+            }
+        }
+        if board_size == 0 || stones as usize > points.len() {
+            return Err(GameYError::InvalidHandicap {
+                stones,
+                board_size,
+                max: if board_size == 0 { 0 } else { points.len() as u32 },
+            });
+        }
+        points.truncate(stones as usize);
+        Ok(points)
+    }
+
+    /// Returns the handicap stones placed for player 0 before the first move.
+    pub fn handicap(&self) -> &[Coordinates] {
+        &self.handicap
+    }
+
+    /// Returns the shape of the board.
+    pub fn topology(&self) -> BoardTopology {
+        self.topology
+    }
+
+    /// Returns the coordinates of the cell with the given linear index.
+    pub fn cell_coords(&self, index: u32) -> Coordinates {
+        self.geometry.coords(index)
+    }
+
+    /// Returns the linear index of the cell at the given coordinates.
+    pub fn cell_index(&self, coords: Coordinates) -> u32 {
+        self.topology.to_index(coords, self.board_size)
+    }
+
+    /// Returns the ruleset this game is played under.
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// Returns true if no stone may ever be placed on the given cell.
+    pub fn is_blocked(&self, coords: Coordinates) -> bool {
+        self.blocked.contains(&coords)
+    }
+
+    /// Iterates over the stones on the board, in cell index order.
+    pub(crate) fn stones(&self) -> impl Iterator<Item = (Coordinates, PlayerId)> + '_ {
+        self.board.iter().enumerate().filter_map(|(index, cell)| {
+            cell.map(|(_, player)| (self.cell_coords(index as u32), player))
+        })
+    }
+
+    /// Returns the set and the player of the stone at the given coordinates,
+    /// or `None` if the cell is empty or off the board.
+    pub(crate) fn stone_at(&self, coords: Coordinates) -> Option<(SetIdx, PlayerId)> {
+        if !self.topology.contains(coords, self.board_size) {
+            return None;
+        }
+        self.board[self.cell_index(coords) as usize]
+    }
+
+    /// Returns true if a stone is at the given coordinates, and false if
+    /// the cell is empty or off the board.
+    ///
+    /// Lets callers check a move before playing it, instead of matching the
+    /// [`GameYError::Occupied`] returned by [`GameY::add_move`].
+    pub fn is_occupied(&self, coords: Coordinates) -> bool {
+        self.stone_at(coords).is_some()
+    }
+
+    /// Returns true if a stone is on the cell with the given index, like
+    /// [`GameY::is_occupied`].
+    pub fn is_occupied_idx(&self, index: u32) -> bool {
+        self.board
+            .get(index as usize)
+            .is_some_and(|cell| cell.is_some())
+    }
+
+    /// Returns the number of stones on the board, handicap stones included.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let mut game = GameY::new(3);
+    /// game.play_move_list("0 4").unwrap();
+    /// assert_eq!(game.occupied_count(), 2);
+    /// assert!(game.is_occupied_idx(4));
+    /// assert!(!game.is_occupied_idx(5));
+    /// ```
+    pub fn occupied_count(&self) -> u32 {
+        // Every cell is either blocked, available or occupied
+        self.total_cells() - self.blocked.len() as u32 - self.available_cells.len() as u32
+    }
+
+    /// Returns the current game status.
+    pub fn status(&self) -> &GameStatus {
+        &self.status
+    }
+
+    /// Returns true if the game has ended (has a winner).
+    pub fn check_game_over(&self) -> bool {
+        match self.status {
+            GameStatus::Ongoing { .. } => false,
+            GameStatus::Finished { winner: _ } => true,
+        }
+    }
+
+    /// Returns the moves played so far, in the order they were applied.
+    pub fn history(&self) -> &[Movement] {
+        &self.history
+    }
+
+    /// Saves the current state of the game, see [`GameSnapshot`].
+    pub fn snapshot(&self) -> GameSnapshot {
+        GameSnapshot { game: self.clone() }
+    }
+
+    /// Returns the current position, see [`Position`].
+    pub fn position(&self) -> Position {
+        let cells = self
+            .board
+            .iter()
+            .map(|stone| match stone {
+                Some((_, player)) => Cell::Occupied(*player),
+                None => Cell::Empty,
+            })
+            .collect();
+        let winner = self.winner();
+        Position::new(
+            self.board_size,
+            self.topology,
+            self.ruleset,
+            cells,
+            self.next_player(),
+            winner,
+        )
+    }
+
+    /// Creates a game in the given position, with no history.
+    pub fn from_position(position: &Position) -> GameY {
+        let mut game = GameY::with_variant(
+            position.board_size(),
+            position.topology(),
+            position.ruleset(),
+        );
+        for (cell_idx, cell) in position.cells().iter().enumerate() {
+            if let Cell::Occupied(player) = *cell {
+                let set_idx = game.register_piece(player, cell_idx as u32);
+                game.connect_neighbors_and_check_win(cell_idx as u32, player, set_idx);
+            }
+        }
+        game.status = match (position.winner(), position.next_player()) {
+            (Some(winner), _) => GameStatus::Finished { winner },
+            (None, next_player) => GameStatus::Ongoing {
+                next_player: next_player.unwrap_or(PlayerId::new(0)),
+            },
+        };
+        game
+    }
+
+    /// Puts the game back in the state saved in `snapshot`, reusing the
+    /// memory of its arrays.
+    ///
+    /// The snapshot may come from any game; the board, variant and handicap
+    /// are restored as well.
+    pub fn restore(&mut self, snapshot: &GameSnapshot) {
+        let saved = &snapshot.game;
+        self.board_size = saved.board_size;
+        self.topology = saved.topology;
+        if !Arc::ptr_eq(&self.geometry, &saved.geometry) {
+            self.geometry = Arc::clone(&saved.geometry);
+        }
+        self.board.clone_from(&saved.board);
+        self.status = saved.status.clone();
+        self.history.clone_from(&saved.history);
+        self.annotations.clone_from(&saved.annotations);
+        self.metadata.clone_from(&saved.metadata);
+        self.sets.clone_from(&saved.sets);
+        self.available_cells.clone_from(&saved.available_cells);
+        self.available_pos.clone_from(&saved.available_pos);
+        self.handicap.clone_from(&saved.handicap);
+        self.ruleset = saved.ruleset;
+        self.blocked.clone_from(&saved.blocked);
+    }
+
+    /// Returns the game as it was after the first `moves` moves of the
+    /// history, replayed on a fresh board with the same variant and handicap.
+    ///
+    /// Annotations made up to that point and the metadata are kept. Asking
+    /// for more moves than were played returns the current position.
+    pub fn position_after(&self, moves: usize) -> Result<GameY> {
+        let mut game = GameY::with_variant(self.board_size, self.topology, self.ruleset)
+            .with_handicap(&self.handicap)?;
+        for movement in self.history.iter().take(moves) {
+            game.add_move(movement.clone())?;
+        }
+        game.annotations = self
+            .annotations
+            .iter()
+            .filter(|annotation| annotation.move_number <= moves)
+            .cloned()
+            .collect();
+        game.metadata.clone_from(&self.metadata);
+        Ok(game)
+    }
+
+    /// Returns the event, date, players and result of the game.
+    pub fn metadata(&self) -> &GameMetadata {
+        &self.metadata
+    }
+
+    /// Sets the event, date, players and result of the game.
+    pub fn set_metadata(&mut self, metadata: GameMetadata) {
+        self.metadata = metadata;
+    }
+
+    /// Returns the notes attached to the history, in the order they were made.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Attaches a comment to the current point of the history.
+    pub fn annotate(&mut self, text: impl Into<String>) {
+        self.add_annotation(None, text);
+    }
+
+    /// Attaches a comment, a glyph judging the last move, or both to the
+    /// current point of the history.
+    pub fn add_annotation(&mut self, glyph: Option<Glyph>, text: impl Into<String>) {
+        let mut annotation = Annotation::new(self.history.len(), text);
+        annotation.glyph = glyph;
+        self.annotations.push(annotation);
+    }
+
+    /// Plays `moves` in order, attaching each annotation after the move it
+    /// was made after, as when restoring a saved game.
+    pub fn replay(
+        &mut self,
+        moves: impl IntoIterator<Item = Movement>,
+        annotations: impl IntoIterator<Item = Annotation>,
+    ) -> Result<()> {
+        let mut annotations = annotations.into_iter().peekable();
+        for movement in moves {
+            while let Some(annotation) =
+                annotations.next_if(|a| a.move_number <= self.history.len())
+            {
+                self.add_annotation(annotation.glyph, annotation.text);
+            }
+            self.add_move(movement)?;
+        }
+        for annotation in annotations {
+            self.add_annotation(annotation.glyph, annotation.text);
+        }
+        Ok(())
+    }
+
+    /// Takes back the last move of `player` together with every move played
+    /// after it, once both players have agreed, and records the takeback as
+    /// an annotation. Returns the number of moves taken back.
+    ///
+    /// Returns an error if `player` has not moved yet.
+    pub fn take_back(&mut self, player: PlayerId) -> Result<usize> {
+        let Some(index) = self
+            .history
+            .iter()
+            .rposition(|movement| movement.player() == player)
+        else {
+            return Err(GameYError::NothingToTakeBack { player });
+        };
+        let undone = self.history.len() - index;
+        let mut game = self.position_after(index)?;
+        game.annotate(format!(
+            "Takeback: player {} took back {} move(s)",
+            player, undone
+        ));
+        *self = game;
+        Ok(undone)
+    }
+
+    /// Returns the coordinates of the most recent placement, if any.
+    ///
+    /// Handicap stones are not moves, so they are never reported.
+    pub fn last_placement(&self) -> Option<Coordinates> {
+        self.history.iter().rev().find_map(|movement| match movement {
+            Movement::Placement { coords, .. } => Some(*coords),
+            Movement::Action { .. } => None,
+        })
+    }
+
+    /// Returns the indices of the cells where a stone may be placed, in
+    /// increasing order.
+    ///
+    /// The order only depends on the position, not on the order of the
+    /// moves that led to it, so that choices made from the list, e.g. by a
+    /// seeded bot, are reproducible.
+    pub fn available_cells(&self) -> Vec<u32> {
+        self.available_cells_in_order().collect()
+    }
+
+    /// Iterates over the available cell indices in increasing order, like
+    /// [`GameY::available_cells`] without collecting them.
+    pub fn available_cells_in_order(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.available_pos.len() as u32).filter(|&index| self.is_available(index))
+    }
+
+    /// Returns true if a stone may be placed on the cell with the given
+    /// index: it is on the board, empty and not blocked.
+    pub fn is_available(&self, index: u32) -> bool {
+        self.available_pos
+            .get(index as usize)
+            .is_some_and(|&pos| pos != NOT_AVAILABLE)
+    }
+
+    /// Returns the total number of cells on the board.
+    pub fn total_cells(&self) -> u32 {
+        self.topology.cell_count(self.board_size)
+    }
+
+    /// Checks if the movement is made by the correct player.
+    ///
+    /// Returns an error if it's not the specified player's turn.
+    pub fn check_player_turn(&self, movement: &Movement) -> Result<()> {
+        if let GameStatus::Ongoing { next_player } = self.status {
+            let player = match movement {
+                Movement::Placement { player, .. } => *player,
+                Movement::Action { player, .. } => *player,
+            };
+            if player != next_player {
+                return Err(GameYError::InvalidPlayerTurn {
+                    expected: next_player,
+                    found: player,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the player who should make the next move, or None if the game is over.
+    pub fn next_player(&self) -> Option<PlayerId> {
+        if let GameStatus::Ongoing { next_player } = self.status {
+            Some(next_player)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the winner, or `None` while the game is ongoing.
+    pub fn winner(&self) -> Option<PlayerId> {
+        match self.status {
+            GameStatus::Finished { winner } => Some(winner),
+            GameStatus::Ongoing { .. } => None,
+        }
+    }
+
+    /// Returns the number of moves played, actions included.
+    pub fn move_count(&self) -> usize {
+        self.history.len()
+    }
+
+    /// Returns the last move played, or `None` before the first one.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::{Coordinates, GameY, Movement, PlayerId};
+    ///
+    /// let mut game = GameY::new(1);
+    /// assert_eq!(game.last_move(), None);
+    /// game.play_move_list("0").unwrap();
+    /// assert_eq!(game.move_count(), 1);
+    /// assert_eq!(game.winner(), Some(PlayerId::new(0)));
+    /// assert!(matches!(
+    ///     game.last_move(),
+    ///     Some(Movement::Placement { coords, .. }) if *coords == Coordinates::new(0, 0, 0)
+    /// ));
+    /// ```
+    pub fn last_move(&self) -> Option<&Movement> {
+        self.history.last()
+    }
+
+    /// Loads a game state from a YEN format file.
+    ///
+    /// If the file carries a plain SHA-256 integrity digest that does not
+    /// match, a warning is logged; use [`GameY::load_verified`] to reject
+    /// such files instead.
+    ///
+    /// Files ending in `.gz` or `.zst` are decompressed; see
+    /// [`Compression`].
+    pub fn load_from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let filename = path.as_ref().display().to_string();
+        let yen = read_yen_document(open_file(path)?, &format!("file: {}", filename))?;
+        GameY::from_yen_lenient(yen, &filename)
+    }
+
+    /// Loads a game state from a YEN format file, rejecting it unless its
+    /// integrity digest is present and valid.
+    ///
+    /// `key` is the tournament key needed for files signed with
+    /// [`GameY::save_to_file_signed`].
+    pub fn load_verified<P: AsRef<Path>>(path: P, key: Option<&[u8]>) -> Result<Self> {
+        let filename = path.as_ref().display().to_string();
+        let yen = read_yen_document(open_file(path)?, &format!("file: {}", filename))?;
+        yen.verify_integrity(key)?;
+        GameY::try_from(yen)
+    }
+
+    /// Reads a game state in YEN format from `reader`, e.g. a network
+    /// stream or an in-memory buffer, like [`GameY::load_from_file`].
+    pub fn read_yen<R: std::io::Read>(reader: R) -> Result<Self> {
+        let yen = read_yen_document(reader, "YEN")?;
+        GameY::from_yen_lenient(yen, "YEN")
+    }
+
+    /// Reads a game state in YEN format from `reader`, rejecting it unless
+    /// its integrity digest is present and valid, like
+    /// [`GameY::load_verified`].
+    pub fn read_yen_verified<R: std::io::Read>(reader: R, key: Option<&[u8]>) -> Result<Self> {
+        let yen = read_yen_document(reader, "YEN")?;
+        yen.verify_integrity(key)?;
+        GameY::try_from(yen)
+    }
+
+    fn from_yen_lenient(yen: YEN, source: &str) -> Result<Self> {
+        if yen.integrity().is_some()
+            && let Err(e) = yen.verify_integrity(None)
+        {
+            tracing::warn!("{}: {}", source, e);
+        }
+        GameY::try_from(yen)
+    }
+
+    /// Writes the game state to `writer` in YEN format, with its history
+    /// and a SHA-256 integrity digest, as [`GameY::save_to_file`] does.
+    pub fn write_yen<W: std::io::Write>(&self, writer: W) -> Result<()> {
+        self.write_sealed_yen(writer, None)
+    }
+
+    /// Writes the game state to `writer` in YEN format, signed with an
+    /// HMAC-SHA-256 of the given tournament key.
+    pub fn write_yen_signed<W: std::io::Write>(&self, writer: W, key: &[u8]) -> Result<()> {
+        self.write_sealed_yen(writer, Some(key))
+    }
+
+    fn write_sealed_yen<W: std::io::Write>(&self, mut writer: W, key: Option<&[u8]>) -> Result<()> {
+        let history = self.history.iter().map(MoveRecord::from).collect();
+        let yen = YEN::from(self)
+            .with_history(history, self.annotations.clone())
+            .with_metadata(self.metadata.clone())
+            .seal(key)?;
+        serde_json::to_writer_pretty(&mut writer, &yen)
+            .map_err(|e| GameYError::SerdeError { error: e })?;
+        writer.flush().map_err(|e| GameYError::IoError {
+            message: "Failed to write YEN".to_string(),
+            error: e.to_string(),
+        })
+    }
+
+    /// Saves the game state to a file in YEN format, with a SHA-256
+    /// integrity digest.
+    ///
+    /// The file is replaced atomically; see [`SaveOptions`]. Files ending in
+    /// `.gz` or `.zst` are compressed; see [`Compression`].
+    pub fn save_to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.save_with_options(path, None, &SaveOptions::default())
+    }
+
+    /// Saves the game state to a file in YEN format, signed with an
+    /// HMAC-SHA-256 of the given tournament key.
+    pub fn save_to_file_signed<P: AsRef<Path>>(&self, path: P, key: &[u8]) -> Result<()> {
+        self.save_with_options(path, Some(key), &SaveOptions::default())
+    }
+
+    /// Saves the game state to a file in YEN format, signed with `key` if
+    /// given, keeping a backup of the previous save or flushing it to disk
+    /// as `options` say.
+    pub fn save_with_options<P: AsRef<Path>>(
+        &self,
+        path: P,
+        key: Option<&[u8]>,
+        options: &SaveOptions,
+    ) -> Result<()> {
+        let path = path.as_ref();
+        let mut writer = Compression::from_path(path).writer(Vec::new())?;
+        self.write_sealed_yen(&mut writer, key)?;
+        options.write(path, &writer.finish()?)
+    }
+
+    /// Returns a complete snapshot of the game: board, history, status,
+    /// available cells and connected groups.
+    pub fn to_state(&self) -> GameStateDump {
+        let board = (0..self.total_cells())
+            .map(|index| {
+                let coords = self.cell_coords(index);
+                CellDump {
+                    index,
+                    coords,
+                    player: self.stone_at(coords).map(|(_, p)| p.id()),
+                    blocked: self.is_blocked(coords),
+                }
+            })
+            .collect();
+        let status = StatusDump::from(&self.status);
+        let available_cells = self.available_cells_in_order().collect();
+        GameStateDump {
+            version: STATE_VERSION,
+            size: self.board_size,
+            topology: self.topology,
+            ruleset: self.ruleset,
+            board,
+            handicap: self.handicap.clone(),
+            history: self.history.iter().map(MoveRecord::from).collect(),
+            status,
+            available_cells,
+            groups: self.all_groups().into_iter().map(GroupDump::from).collect(),
+        }
+    }
+
+    /// Serializes [`GameY::to_state`] as pretty-printed JSON.
+    pub fn to_state_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(&self.to_state())
+            .map_err(|e| GameYError::SerdeError { error: e })
+    }
+
+    /// Returns the chains of connected stones of the given player.
+    ///
+    /// Groups are ordered by the index of their first cell, and list their
+    /// stones, the sides they touch and their liberties: the adjacent empty
+    /// cells where a stone may still be placed.
+    pub fn groups(&self, player: PlayerId) -> Vec<Group> {
+        self.all_groups()
+            .into_iter()
+            .filter(|group| group.player == player)
+            .collect()
+    }
+
+    /// Returns the minimum number of stones the player still has to place to
+    /// connect one of their groups to the given side.
+    ///
+    /// Paths may go through the player's own stones for free and through
+    /// empty cells at the cost of one stone each; opponent stones and
+    /// blocked cells are walls. The distance is 0 if a group already touches
+    /// the side.
+    ///
+    /// Returns `None` if the player has no stones on the board, or if every
+    /// path to the side is cut off.
+    pub fn connection_distance(&self, player: PlayerId, side: Side) -> Option<u32> {
+        let total = self.total_cells() as usize;
+        let mut distance: Vec<Option<u32>> = vec![None; total];
+        let mut queue = VecDeque::new();
+        for (index, cell) in self.board.iter().enumerate() {
+            if cell.is_some_and(|(_, owner)| owner == player) {
+                distance[index] = Some(0);
+                queue.push_front((index, 0));
+            }
+        }
+
+        // 0-1 BFS: own stones cost nothing, empty cells cost one stone
+        while let Some((index, dist)) = queue.pop_front() {
+            if distance[index] != Some(dist) {
+                continue;
+            }
+            if self.geometry.side_mask(index as u32, player) & 1 << side.index() != 0 {
+                return Some(dist);
+            }
+            for &next in self.geometry.neighbors(index as u32) {
+                let cost = match self.board[next as usize] {
+                    Some((_, owner)) if owner == player => 0,
+                    Some(_) => continue,
+                    None if self.is_blocked(self.cell_coords(next)) => continue,
+                    None => 1,
+                };
+                let next = next as usize;
+                let next_dist = dist + cost;
+                if distance[next].is_none_or(|d| next_dist < d) {
+                    distance[next] = Some(next_dist);
+                    if cost == 0 {
+                        queue.push_front((next, next_dist));
+                    } else {
+                        queue.push_back((next, next_dist));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Collects the stones of each union-find set, ordered by cell index.
+    fn all_groups(&self) -> Vec<Group> {
+        let stones = self.board.iter().enumerate().filter_map(|(index, cell)| {
+            cell.map(|(set_idx, player)| (self.cell_coords(index as u32), set_idx, player))
+        });
+
+        let mut roots: Vec<SetIdx> = Vec::new();
+        let mut groups: Vec<Group> = Vec::new();
+        for (coords, set_idx, player) in stones {
+            let root = self.root(set_idx);
+            match roots.iter().position(|&r| r == root) {
+                Some(pos) => groups[pos].cells.push(coords),
+                None => {
+                    let set = &self.sets[root];
+                    roots.push(root);
+                    groups.push(Group {
+                        player,
+                        cells: vec![coords],
+                        liberties: Vec::new(),
+                        touches_side_a: set.touches_side_a,
+                        touches_side_b: set.touches_side_b,
+                        touches_side_c: set.touches_side_c,
+                    });
+                }
+            }
+        }
+        for group in &mut groups {
+            group.liberties = self.liberties(&group.cells);
+        }
+        groups
+    }
+
+    /// Returns the empty, playable cells adjacent to any of the given ones,
+    /// ordered by cell index.
+    fn liberties(&self, cells: &[Coordinates]) -> Vec<Coordinates> {
+        let mut liberties: Vec<Coordinates> = cells
+            .iter()
+            .flat_map(|coords| self.get_neighbors(coords))
+            .filter(|n| self.stone_at(*n).is_none() && !self.is_blocked(*n))
+            .collect();
+        liberties.sort_unstable_by_key(|c| self.cell_index(*c));
+        liberties.dedup();
+        liberties
+    }
+
+    /// Adds a move to the game.
+    pub fn add_move(&mut self, movement: Movement) -> Result<()> {
+        let player = movement.player();
+        if player.id() >= PLAYER_COUNT {
+            return Err(GameYError::UnknownPlayer {
+                player,
+                players: PLAYER_COUNT,
+            });
+        }
+        match &movement {
+            Movement::Placement { player, coords } => {
+                self.handle_placement(*player, *coords)?;
+            }
+            Movement::Action { player, action } => {
+                self.handle_action(*player, action);
+            }
+        }
+        self.history.push(movement);
+        Ok(())
+    }
+
+    /// Fills the board with random stones, players alternating from the one
+    /// to move, until the game is decided, and returns the winner.
+    ///
+    /// This is the fast path of Monte Carlo playouts: stones go straight
+    /// onto the board and the union-find sets, without building [`Movement`]
+    /// values or recording them in the history, so the game should only be
+    /// used for its result afterwards. Returns the winner of a finished game
+    /// right away, and `None` only if the board fills up undecided.
+    ///
+    /// Boards of at most [`BITBOARD_MAX_CELLS`](crate::BITBOARD_MAX_CELLS)
+    /// cells without blocked cells are filled up completely without joining
+    /// any groups, and the winner is found once at the end with a
+    /// bit-parallel flood fill: a full board has exactly one connection, made
+    /// by the player who would have connected first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let mut game = GameY::new(11);
+    /// let winner = game.random_playout(&mut rand::rng());
+    /// assert!(winner.is_some());
+    /// assert!(game.check_game_over());
+    /// ```
+    pub fn random_playout<R: Rng + ?Sized>(&mut self, rng: &mut R) -> Option<PlayerId> {
+        if let GameStatus::Ongoing { next_player } = self.status
+            && self.blocked.is_empty()
+            && self.geometry.bit_layout().is_some()
+        {
+            return self.fill_and_flood(next_player, rng);
+        }
+        while let GameStatus::Ongoing { next_player } = self.status {
+            if self.available_cells.is_empty() {
+                return None;
+            }
+            let cell_idx = self.available_cells[rng.random_range(0..self.available_cells.len())];
+            let set_idx = self.register_piece(next_player, cell_idx);
+            if self.connect_neighbors_and_check_win(cell_idx, next_player, set_idx) {
+                self.status = GameStatus::Finished {
+                    winner: self.connection_winner(next_player),
+                };
+            } else {
+                self.status = GameStatus::Ongoing {
+                    next_player: next_player.opponent(),
+                };
+            }
+        }
+        self.winner()
+    }
+
+    /// Fills the remaining cells at random, players alternating from
+    /// `player`, and finds who connected with the [`BitLayout`] of the board.
+    ///
+    /// [`BitLayout`]: crate::BitLayout
+    fn fill_and_flood<R: Rng + ?Sized>(
+        &mut self,
+        mut player: PlayerId,
+        rng: &mut R,
+    ) -> Option<PlayerId> {
+        let geometry = Arc::clone(&self.geometry);
+        let layout = geometry.bit_layout()?;
+        let mut stones = [BitBoard::default(); 2];
+        for (index, stone) in self.board.iter().enumerate() {
+            if let Some((_, owner)) = stone {
+                stones[owner.id() as usize % 2].set(layout.bit(index as u32));
+            }
+        }
+        while !self.available_cells.is_empty() {
+            let cell_idx = self.available_cells[rng.random_range(0..self.available_cells.len())];
+            self.register_piece(player, cell_idx);
+            stones[player.id() as usize % 2].set(layout.bit(cell_idx));
+            player = player.opponent();
+        }
+        let connected = [PlayerId::new(0), PlayerId::new(1)]
+            .into_iter()
+            .find(|&player| layout.connects_sides(stones[player.id() as usize], player))?;
+        let winner = self.connection_winner(connected);
+        self.status = GameStatus::Finished { winner };
+        Some(winner)
+    }
+
+    /// Orchestrates the placement logic
+    fn handle_placement(&mut self, player: PlayerId, coords: Coordinates) -> Result<()> {
+        self.validate_placement(player, coords)?;
+
+        // Update board state (available cells, sets, board)
+        let cell_idx = self.cell_index(coords);
+        let set_idx = self.register_piece(player, cell_idx);
+
+        // Connect neighbors and determine if this move won the game
+        let won = self.connect_neighbors_and_check_win(cell_idx, player, set_idx);
+
+        self.update_status_after_placement(player, won);
+        Ok(())
+    }
+
+    /// Iterates over neighbors to union sets and checks for a win condition
+    fn connect_neighbors_and_check_win(
+        &mut self,
+        cell_idx: u32,
+        player: PlayerId,
+        current_set_idx: usize,
+    ) -> bool {
+        // Base win condition: The piece itself touches all required sides
+        let mut won = self.sets[current_set_idx].is_winning_configuration();
+
+        let geometry = Arc::clone(&self.geometry);
+        for &neighbor in geometry.neighbors(cell_idx) {
+            if let Some((neighbor_idx, neighbor_player)) = self.board[neighbor as usize]
+                && neighbor_player == player
+            {
+                // Union returns true if the merge resulted in a winning connection
+                let connection_won = self.union(current_set_idx, neighbor_idx);
+                won = won || connection_won;
+            }
+        }
+        won
+    }
+
+    /// Updates the game status (Finished vs Ongoing)
+    fn update_status_after_placement(&mut self, player: PlayerId, won: bool) {
+        if self.check_game_over() {
+            tracing::info!("Game was already over. Move ignored for status update.");
+        } else if won {
+            let winner = self.connection_winner(player);
+            tracing::debug!("Player {} wins the game!", winner);
+            self.status = GameStatus::Finished { winner };
+        } else {
+            // tracing::debug!("No win yet..."); // Optional debug
+            self.status = GameStatus::Ongoing {
+                next_player: player.opponent(),
+            };
+        }
+    }
+
+    /// Returns the winner once `player` has connected the sides: the player
+    /// themselves, or the opponent under misère rules.
+    fn connection_winner(&self, player: PlayerId) -> PlayerId {
+        if self.ruleset.is_misere() {
+            player.opponent()
+        } else {
+            player
+        }
+    }
+
+    /// Handles non-placement actions (Resign, Swap, etc.)
+    fn handle_action(&mut self, player: PlayerId, action: &GameAction) {
+        match action {
+            GameAction::Resign => {
+                self.status = GameStatus::Finished {
+                    winner: player.opponent(),
+                };
+            }
+            GameAction::Swap => {
+                self.status = GameStatus::Ongoing {
+                    next_player: player.opponent(),
+                };
+            }
+        }
+    }
+
+    /// Handles validation logic (Game Over checks and Occupancy)
+    fn validate_placement(&self, player: PlayerId, coords: Coordinates) -> Result<()> {
+        if self.check_game_over() {
+            tracing::info!("Game is already over. Move at {} could be ignored", coords);
+        }
+
+        self.check_coords(coords)?;
+
+        if self.is_blocked(coords) {
+            return Err(GameYError::BlockedCell {
+                coordinates: coords,
+                ruleset: self.ruleset,
+            });
+        }
+
+        if self.is_occupied(coords) {
+            return Err(GameYError::Occupied {
+                coordinates: coords,
+                player,
+            });
+        }
+        Ok(())
+    }
+
+    /// Checks that the coordinates denote a cell of this board, see
+    /// [`BoardTopology::check_coords`].
+    pub fn check_coords(&self, coords: Coordinates) -> Result<()> {
+        self.topology.check_coords(coords, self.board_size)
+    }
+
+    /// Updates internal data structures (Available cells, Sets, Board)
+    /// Returns the index of the newly created set.
+    fn register_piece(&mut self, player: PlayerId, cell_idx: u32) -> usize {
+        self.remove_available(cell_idx);
+
+        let set_idx = self.sets.len();
+        let [touches_side_a, touches_side_b, touches_side_c] =
+            self.geometry.sides_touched(cell_idx, player);
+        let new_set = PlayerSet {
+            parent: set_idx,
+            touches_side_a,
+            touches_side_b,
+            touches_side_c,
+        };
+        self.sets.push(new_set);
+        self.board[cell_idx as usize] = Some((set_idx, player));
+
+        set_idx
+    }
+
+    /// Removes a cell from the available cells, moving the last one into its
+    /// place.
+    fn remove_available(&mut self, cell_idx: u32) {
+        let pos = self.available_pos[cell_idx as usize];
+        if pos == NOT_AVAILABLE {
+            return;
+        }
+        self.available_cells.swap_remove(pos as usize);
+        if let Some(&moved) = self.available_cells.get(pos as usize) {
+            self.available_pos[moved as usize] = pos;
+        }
+        self.available_pos[cell_idx as usize] = NOT_AVAILABLE;
+    }
+
+    /// Returns the size of the board (length of one side of the triangle).
+    pub fn board_size(&self) -> u32 {
+        self.board_size
+    }
+
+    /// Returns the neighboring coordinates for a given cell.
+    fn get_neighbors(&self, coords: &Coordinates) -> Vec<Coordinates> {
+        let neighbors = self.geometry.neighbors(self.cell_index(*coords));
+        neighbors.iter().map(|&n| self.cell_coords(n)).collect()
+    }
+
+    /// Renders the current state of the board as a text string.
+    /// If `show_coordinates` is true, the coordinates of each cell will be displayed.
+    pub fn render(&self, options: &RenderOptions) -> String {
+        let mut result = String::new();
+        let coords_size = self.board_size.to_string().len();
+        let name = match self.topology {
+            BoardTopology::Y => "Y",
+            BoardTopology::Hex => "Hex",
+        };
+        let _ = writeln!(
+            result,
+            "--- Game of {} (Size {}) ---",
+            name, self.board_size
+        );
+
+        let move_numbers = if options.show_move_numbers {
+            self.move_numbers()
+        } else {
+            HashMap::new()
+        };
+        // Every symbol is padded to the widest move number to keep columns aligned
+        let symbol_width = if options.show_move_numbers {
+            self.history.len().max(1).to_string().len()
+        } else {
+            1
+        };
+        let indent_multiplier = self.get_indent_multiplier(options) + symbol_width as u32 / 2;
+        // A view of another board shape, e.g. kept after loading a game,
+        // draws the board unturned
+        let view = if options.view.applies_to(self.topology) {
+            options.view
+        } else {
+            Symmetry::Identity
+        };
+        let labels = EdgeLabels::for_view(self.topology, view);
+        let guide_width = self.board_size.saturating_sub(1).to_string().len();
+        // The cell drawn at each place of the board as viewed
+        let view = view.inverse();
+        let rows: Vec<Vec<Coordinates>> = self
+            .topology
+            .rows(self.board_size)
+            .into_iter()
+            .map(|row| {
+                row.into_iter()
+                    .map(|place| view.apply(place, self.board_size))
+                    .collect()
+            })
+            .collect();
+
+        // Each rendered row, with the visible column where each cell starts
+        let mut lines: Vec<(String, Vec<usize>)> = Vec::new();
+        for (row, cells) in rows.iter().enumerate() {
+            let mut line = String::new();
+            // The triangle widens downwards while the rhombus leans right
+            let level = match self.topology {
+                BoardTopology::Y => self.board_size - 1 - row as u32,
+                BoardTopology::Hex => row as u32,
+            };
+            if options.show_edge_labels {
+                // Row guide: distance from the bottom side in Y, row number in Hex
+                let guide = match (self.topology, cells.first()) {
+                    (BoardTopology::Y, Some(&first)) => side_distance(first, labels.bottom.side),
+                    (BoardTopology::Hex, Some(&first)) => first.x(),
+                    (_, None) => level,
+                };
+                let _ = write!(line, "{:>w$} ", guide, w = guide_width);
+            }
+            indent(&mut line, level * indent_multiplier);
+            if options.show_edge_labels {
+                let _ = write!(line, "{} ", labels.left.render(options));
+            }
+
+            let mut offsets = Vec::new();
+            for &coords in cells {
+                let cell_str = self.format_cell(
+                    coords,
+                    options,
+                    coords_size,
+                    move_numbers.get(&coords).copied(),
+                    symbol_width,
+                );
+                let bracketed = options.highlight_last_move
+                    && !options.show_colors
+                    && self.last_placement() == Some(coords);
+                // Brackets borrow one space on each side to keep columns aligned
+                if bracketed && line.ends_with(' ') {
+                    line.pop();
+                }
+                offsets.push(visible_width(&line));
+                let separator = if bracketed { "  " } else { "   " };
+                let _ = write!(line, "{}{}", cell_str, separator);
+            }
+            if options.show_edge_labels {
+                line.push_str(&labels.right.render(options));
+            }
+            lines.push((line, offsets));
+        }
+
+        if options.show_edge_labels
+            && let (Some(top), Some((_, offsets))) = (labels.top, lines.first())
+        {
+            result.push_str(&label_line(top, offsets, options));
+            result.push('\n');
+        }
+        for (line, _) in &lines {
+            result.push_str(line);
+            result.push('\n');
+            if options.show_idx || options.show_3d_coords {
+                result.push('\n');
+            }
+        }
+        if options.show_edge_labels
+            && let (Some((_, offsets)), Some(cells)) = (lines.last(), rows.last())
+        {
+            result.push_str(&label_line(labels.bottom, offsets, options));
+            result.push('\n');
+            // Column guide: distance from the left side in Y, column number in Hex
+            let mut guides = String::new();
+            for (&coords, &offset) in cells.iter().zip(offsets) {
+                let padding = offset.saturating_sub(guides.len());
+                indent(&mut guides, padding as u32);
+                let guide = match self.topology {
+                    BoardTopology::Y => side_distance(coords, labels.left.side),
+                    BoardTopology::Hex => coords.y(),
+                };
+                let _ = write!(guides, "{}", guide);
+            }
+            result.push_str(&guides);
+            result.push('\n');
+        }
+        result
+    }
+    /*pub fn render(&self, options: &RenderOptions) -> String {
+        let mut result = String::new();
+        let coords_size = self.board_size.to_string().len() as u32;
+
+        let _ = writeln!(result, "--- Game of Y (Size {}) ---", self.board_size);
+
+        for row in 0..self.board_size {
+            let x = self.board_size - 1 - row;
+
+            let indent_multiplier = match (options.show_3d_coords, options.show_idx) {
+                (true, true) => 8,
+                (true, false) => 4,
+                (false, true) => 4,
+                (false, false) => 2,
+            };
+
+            indent(&mut result, x * indent_multiplier);
+
+            for y in 0..=row {
+                let z = row - y;
+
+                let coords = Coordinates::new(x, y, z);
+                let player = self.stone_at(coords).map(|(_, p)| p);
+
+                let mut symbol = match player {
+                    Some(p) => format!("{}", p),
+                    None => ".".to_string(),
+                };
+
+                if options.show_3d_coords {
+                    symbol.push_str(
+                        format!(
+                            "({:0width$},{:0width$},{:0width$})",
+                            x,
+                            y,
+                            z,
+                            width = coords_size as usize
+                        )
+                        .as_str(),
+                    );
+                }
+                if options.show_idx {
+                    let idx = coords.to_index(self.board_size);
+                    symbol.push_str(format!("({}) ", idx).as_str());
+                }
+                if options.show_colors {
+                    match player {
+                        Some(p) if p.id() == 0 => {
+                            symbol = format!("\x1b[34m{}\x1b[0m", symbol); // Blue for player 0
+                        }
+                        Some(p) if p.id() == 1 => {
+                            symbol = format!("\x1b[31m{}\x1b[0m", symbol); // Red for player 1
+                        }
+                        _ => {}
+                    }
+                }
+
+                let _ = write!(result, "{}   ", symbol);
+            }
+            result.push('\n');
+            if options.show_idx || options.show_3d_coords {
+                result.push('\n');
+            }
+        }
+        result
+    }*/
+
+    fn get_indent_multiplier(&self, options: &RenderOptions) -> u32 {
+        match (options.show_3d_coords, options.show_idx) {
+            (true, true) => 8,
+            (true, false) => 4,
+            (false, true) => 4,
+            (false, false) => 2,
+        }
+    }
+
+    fn format_cell(
+        &self,
+        coords: Coordinates,
+        options: &RenderOptions,
+        width: usize,
+        move_number: Option<usize>,
+        symbol_width: usize,
+    ) -> String {
+        let player = self.stone_at(coords).map(|(_, p)| p);
+
+        // 1. Base symbol (move numbers replace the player for played stones)
+        let base = match (player, move_number) {
+            (Some(_), Some(n)) => n.to_string(),
+            (Some(p), None) => format!("{}", p),
+            (None, _) if self.is_blocked(coords) => "#".to_string(),
+            (None, _) => ".".to_string(),
+        };
+        let mut symbol = format!("{:>w$}", base, w = symbol_width);
+        let highlighted = options.highlight_last_move && self.last_placement() == Some(coords);
+        if highlighted {
+            symbol = if options.show_colors {
+                format!("\x1b[7m{}\x1b[27m", symbol) // Inverse video
+            } else {
+                format!("[{}]", symbol)
+            };
+        }
+
+        // 2. Append metadata (3D Coords / Index)
+        if options.show_3d_coords {
+            symbol.push_str(&format!(
+                "({:0w$},{:0w$},{:0w$})",
+                coords.x(),
+                coords.y(),
+                coords.z(),
+                w = width
+            ));
+        }
+        if options.show_idx {
+            let idx = self.cell_index(coords);
+            symbol.push_str(&format!("({}) ", idx));
+        }
+
+        // 3. Apply colors
+        if options.show_colors {
+            symbol = apply_player_color(symbol, player);
+        }
+
+        symbol
+    }
+
+    /// Maps each placed stone to its 1-based move number in the history.
+    fn move_numbers(&self) -> HashMap<Coordinates, usize> {
+        self.history
+            .iter()
+            .enumerate()
+            .filter_map(|(i, movement)| match movement {
+                Movement::Placement { coords, .. } => Some((*coords, i + 1)),
+                Movement::Action { .. } => None,
+            })
+            .collect()
+    }
+
+    /// Disjoint Set Union 'Find' without path compression, for read-only use
+    fn root(&self, mut i: SetIdx) -> SetIdx {
+        while self.sets[i].parent != i {
+            i = self.sets[i].parent;
+        }
+        i
+    }
+
+    /// Disjoint Set Union 'Find' with path compression
+    fn find(&mut self, i: SetIdx) -> SetIdx {
+        if self.sets[i].parent == i {
+            i
+        } else {
+            self.sets[i].parent = self.find(self.sets[i].parent);
+            self.sets[i].parent
+        }
+    }
+
+    /// Disjoint Set Union 'Union' operation
+    fn union(&mut self, i: SetIdx, j: SetIdx) -> bool {
+        let root_i = self.find(i);
+        let root_j = self.find(j);
+
+        if root_i != root_j {
+            self.sets[root_i].parent = root_j;
+            // Merge side properties
+            self.sets[root_j].touches_side_a |= self.sets[root_i].touches_side_a;
+            self.sets[root_j].touches_side_b |= self.sets[root_i].touches_side_b;
+            self.sets[root_j].touches_side_c |= self.sets[root_i].touches_side_c;
+            return self.sets[root_j].touches_side_a
+                && self.sets[root_j].touches_side_b
+                && self.sets[root_j].touches_side_c;
+        }
+        false
+    }
+}
+
+/// Opens the file at `path` for reading, decompressing it if its extension
+/// says so.
+fn open_file<P: AsRef<Path>>(path: P) -> Result<Box<dyn std::io::Read>> {
+    let path = path.as_ref();
+    let file = std::fs::File::open(path).map_err(|e| GameYError::IoError {
+        message: format!("Failed to read file: {}", path.display()),
+        error: e.to_string(),
+    })?;
+    Compression::from_path(path).reader(file)
+}
+
+fn read_yen_document<R: std::io::Read>(mut reader: R, source: &str) -> Result<YEN> {
+    let mut content = String::new();
+    reader
+        .read_to_string(&mut content)
+        .map_err(|e| GameYError::IoError {
+            message: format!("Failed to read {}", source),
+            error: e.to_string(),
+        })?;
+    YEN::from_json(&content)
+}
+
+fn indent(str: &mut String, level: u32) {
+    str.push_str(&" ".repeat(level as usize));
+}
+
+/// A label drawn along one edge of the rendered board.
+#[derive(Clone, Copy)]
+struct EdgeLabel {
+    text: char,
+    // Player whose goal the edge is, used to color the label
+    owner: Option<PlayerId>,
+    // Side of a Y board the edge is, 0 for A, 1 for B and 2 for C
+    side: usize,
+}
+
+impl EdgeLabel {
+    fn render(&self, options: &RenderOptions) -> String {
+        if options.show_colors {
+            apply_player_color(self.text.to_string(), self.owner)
+        } else {
+            self.text.to_string()
+        }
+    }
+}
+
+/// The labels drawn around the board when `show_edge_labels` is set.
+struct EdgeLabels {
+    top: Option<EdgeLabel>,
+    left: EdgeLabel,
+    right: EdgeLabel,
+    bottom: EdgeLabel,
+}
+
+impl EdgeLabels {
+    /// Returns the labels of the edges of a board drawn turned by `view`.
+    fn for_view(topology: BoardTopology, view: Symmetry) -> Self {
+        let side = |shown: usize| {
+            // A cell touching only the side drawn there, moved back
+            let mut place = [1, 2, 3];
+            place[shown] = 0;
+            let cell = view
+                .inverse()
+                .apply(Coordinates::new(place[0], place[1], place[2]), 0);
+            let side = (0..3)
+                .find(|&side| side_distance(cell, side) == 0)
+                .unwrap_or(shown);
+            EdgeLabel {
+                text: ['A', 'B', 'C'][side],
+                owner: None,
+                side,
+            }
+        };
+        let goal = |player| EdgeLabel {
+            text: char::from_digit(player, 10).unwrap_or('?'),
+            owner: Some(PlayerId::new(player)),
+            side: 0,
+        };
+        match topology {
+            // Unturned, side A is the bottom row, B the left edge and C the
+            // right edge
+            BoardTopology::Y => EdgeLabels {
+                top: None,
+                left: side(1),
+                right: side(2),
+                bottom: side(0),
+            },
+            // Each edge is labelled with the player who has to connect it,
+            // which the half-turn leaves in place
+            BoardTopology::Hex => EdgeLabels {
+                top: Some(goal(0)),
+                left: goal(1),
+                right: goal(1),
+                bottom: goal(0),
+            },
+        }
+    }
+}
+
+/// Returns the distance of a cell of a Y board from side `side`, 0 for A,
+/// 1 for B and 2 for C.
+fn side_distance(coords: Coordinates, side: usize) -> u32 {
+    match side {
+        0 => coords.x(),
+        1 => coords.y(),
+        _ => coords.z(),
+    }
+}
+
+/// Builds a line with the label under (or over) each cell of a row.
+fn label_line(label: EdgeLabel, offsets: &[usize], options: &RenderOptions) -> String {
+    let mut line = String::new();
+    let mut width = 0;
+    for &offset in offsets {
+        indent(&mut line, offset.saturating_sub(width) as u32);
+        line.push_str(&label.render(options));
+        width = offset + 1;
+    }
+    line
+}
+
+/// Returns the number of visible characters, skipping ANSI color codes.
+fn visible_width(str: &str) -> usize {
+    let mut width = 0;
+    let mut in_escape = false;
+    for c in str.chars() {
+        if in_escape {
+            in_escape = c != 'm';
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            width += 1;
+        }
+    }
+    width
+}
+
+impl TryFrom<YEN> for GameY {
+    type Error = GameYError;
+
+    fn try_from(game: YEN) -> Result<Self> {
+        if game.version() > YEN_VERSION {
+            return Err(GameYError::UnsupportedVersion {
+                format: "YEN".to_string(),
+                found: game.version(),
+                supported: YEN_VERSION,
+            });
+        }
+        GameY::check_board_size(game.size())?;
+        let mut ygame = GameY::with_variant(game.size(), game.topology(), game.ruleset());
+        ygame.place_handicap(game.handicap())?;
+        if !game.history().is_empty() {
+            let mut replayed = ygame.clone();
+            let moves = game.history().iter().cloned().map(Movement::try_from);
+            match moves
+                .collect::<Result<Vec<_>>>()
+                .and_then(|moves| replayed.replay(moves, game.annotations().to_vec()))
+            {
+                Ok(()) if YEN::from(&replayed).layout() == game.layout() => {
+                    replayed.metadata = game.metadata().clone();
+                    return Ok(replayed);
+                }
+                Ok(()) => {
+                    tracing::warn!("The move history does not lead to the layout, ignoring it")
+                }
+                Err(e) => tracing::warn!("Ignoring the move history: {}", e),
+            }
+        }
+        let board_rows = game.topology().rows(game.size());
+        let rows: Vec<&str> = game.layout().split('/').collect();
+        if rows.len() as u32 != game.size() {
+            return Err(GameYError::InvalidYENLayout {
+                expected: game.size(),
+                found: rows.len() as u32,
+            });
+        }
+        for (row, row_str) in rows.iter().enumerate() {
+            let cells: Vec<char> = row_str.chars().collect();
+            let expected = board_rows[row].len() as u32;
+            if cells.len() as u32 != expected {
+                return Err(GameYError::InvalidYENLayoutLine {
+                    expected,
+                    found: cells.len() as u32,
+                    line: row as u32,
+                });
+            }
+            for (col, cell) in cells.iter().enumerate() {
+                let coords = board_rows[row][col];
+                if ygame.handicap.contains(&coords) {
+                    if *cell != 'B' {
+                        return Err(GameYError::InvalidCharInLayout {
+                            char: *cell,
+                            row,
+                            col,
+                        });
+                    }
+                    continue;
+                }
+                if ygame.is_blocked(coords) {
+                    if *cell != '#' {
+                        return Err(GameYError::InvalidCharInLayout {
+                            char: *cell,
+                            row,
+                            col,
+                        });
+                    }
+                    continue;
+                }
+                match cell {
+                    'B' => {
+                        ygame.add_move(Movement::Placement {
+                            player: PlayerId::new(0),
+                            coords,
+                        })?;
+                    }
+                    'R' => {
+                        ygame.add_move(Movement::Placement {
+                            player: PlayerId::new(1),
+                            coords,
+                        })?;
+                    }
+                    '.' => {}
+                    _ => {
+                        return Err(GameYError::InvalidCharInLayout {
+                            char: *cell,
+                            row,
+                            col,
+                        });
+                    }
+                }
+            }
+        }
+        // A resignation leaves no trace in the layout, only in the result
+        let resigned = match game.result() {
+            Some("B+R") => Some(1),
+            Some("W+R") => Some(0),
+            _ => None,
+        };
+        if let Some(loser) = resigned
+            && !ygame.check_game_over()
+        {
+            ygame.add_move(Movement::Action {
+                player: PlayerId::new(loser),
+                action: GameAction::Resign,
+            })?;
+        }
+        ygame.annotations = game.annotations().to_vec();
+        ygame.metadata = game.metadata().clone();
+        Ok(ygame)
+    }
+}
+
+impl From<&GameY> for YEN {
+    fn from(game: &GameY) -> Self {
+        let size = game.board_size;
+        // Once the game is over the turn goes to whoever would have moved
+        // after the last move, whether it was a winning stone or a
+        // resignation
+        let turn = match (&game.status, game.last_move()) {
+            (GameStatus::Ongoing { next_player }, _) => next_player.id(),
+            (GameStatus::Finished { .. }, Some(last)) => last.player().opponent().id(),
+            (GameStatus::Finished { winner }, None) => winner.opponent().id(),
+        };
+        let players = vec!['B', 'R'];
+        let rows: Vec<String> = game
+            .topology
+            .rows(size)
+            .into_iter()
+            .map(|cells| {
+                cells
+                    .into_iter()
+                    .map(|coords| match game.stone_at(coords) {
+                        Some((_, player)) if player.id() == 0 => 'B',
+                        Some((_, player)) if player.id() == 1 => 'R',
+                        _ if game.is_blocked(coords) => '#',
+                        _ => '.',
+                    })
+                    .collect()
+            })
+            .collect();
+        YEN::new(size, turn, players, rows.join("/"))
+            .with_handicap(game.handicap.clone())
+            .with_ruleset(game.ruleset)
+            .with_topology(game.topology)
+            .with_result(game.result())
+    }
+}
+
+fn apply_player_color(symbol: String, player: Option<PlayerId>) -> String {
+    match player {
+        Some(p) if p.id() == 0 => format!("\x1b[34m{}\x1b[0m", symbol), // Blue
+        Some(p) if p.id() == 1 => format!("\x1b[31m{}\x1b[0m", symbol), // Red
+        _ => symbol,
+    }
+}
+
+/// Represents the current status of a game.
+#[derive(Debug, Clone)]
+pub enum GameStatus {
+    /// The game is still in progress with the specified player to move next.
+    Ongoing { next_player: PlayerId },
+    /// The game has ended with a winner.
+    Finished { winner: PlayerId },
+}
+
+impl From<&GameStatus> for StatusDump {
+    fn from(status: &GameStatus) -> Self {
+        match status {
+            GameStatus::Ongoing { next_player } => StatusDump::Ongoing {
+                next_player: next_player.id(),
+            },
+            GameStatus::Finished { winner } => StatusDump::Finished {
+                winner: winner.id(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_game_initialization() {
+        let game = GameY::new(7);
+        assert_eq!(game.board_size, 7);
+        assert_eq!(game.history.len(), 0);
+        match game.status {
+            GameStatus::Ongoing { next_player } => {
+                assert_eq!(next_player, PlayerId::new(0));
+            }
+            _ => panic!("Game should be ongoing"),
+        }
+    }
+
+    // Helper function to compare neighbor sets
+    fn assert_neighbors_match(actual: Vec<Coordinates>, expected: Vec<Coordinates>) {
+        let actual_set: HashSet<_> = actual.into_iter().collect();
+        let expected_set: HashSet<_> = expected.into_iter().collect();
+        assert_eq!(actual_set, expected_set);
+    }
+
+    #[test]
+    fn test_interior_cell_has_six_neighbors() {
+        let board = GameY::new(5);
+        let cell = Coordinates::new(2, 1, 1);
+
+        let neighbors = board.get_neighbors(&cell);
+
+        let expected = vec![
+            Coordinates::new(1, 2, 1),
+            Coordinates::new(1, 1, 2),
+            Coordinates::new(3, 0, 1),
+            Coordinates::new(2, 0, 2),
+            Coordinates::new(3, 1, 0),
+            Coordinates::new(2, 2, 0),
+        ];
+
+        assert_eq!(neighbors.len(), 6);
+        assert_neighbors_match(neighbors, expected);
+    }
+
+    #[test]
+    fn test_corner_cell_has_two_neighbors() {
+        let board = GameY::new(5);
+        let top_corner = Coordinates::new(4, 0, 0);
+
+        let neighbors = board.get_neighbors(&top_corner);
+
+        let expected = vec![Coordinates::new(3, 1, 0), Coordinates::new(3, 0, 1)];
+
+        assert_eq!(neighbors.len(), 2);
+        assert_neighbors_match(neighbors, expected);
+    }
+
+    #[test]
+    fn test_edge_cell_has_four_neighbors() {
+        let board = GameY::new(5);
+        let edge_cell = Coordinates::new(0, 2, 2);
+
+        let neighbors = board.get_neighbors(&edge_cell);
+
+        let expected = vec![
+            Coordinates::new(1, 1, 2),
+            Coordinates::new(0, 1, 3),
+            Coordinates::new(1, 2, 1),
+            Coordinates::new(0, 3, 1),
+        ];
+
+        assert_eq!(neighbors.len(), 4);
+        assert_neighbors_match(neighbors, expected);
+    }
+
+    #[test]
+    fn test_winning_condition() {
+        let mut game = GameY::new(3);
+
+        let moves = vec![
+            Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(0, 2, 0),
+            },
+            Movement::Placement {
+                player: PlayerId::new(1),
+                coords: Coordinates::new(2, 0, 0),
+            },
+            Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(0, 1, 1),
+            },
+            Movement::Placement {
+                player: PlayerId::new(1),
+                coords: Coordinates::new(1, 1, 0),
+            },
+            Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(0, 0, 2),
+            },
+        ];
+
+        for mv in moves {
+            game.add_move(mv).unwrap();
+        }
+
+        match game.status {
+            GameStatus::Finished { winner } => {
+                assert_eq!(winner, PlayerId::new(0));
+            }
+            _ => panic!("Game should be finished with a winner"),
+        }
+    }
+
+    #[test]
+    fn test_yen_conversion() {
+        let mut game = GameY::new(3);
+
+        let moves = vec![
+            Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(0, 2, 0),
+            },
+            Movement::Placement {
+                player: PlayerId::new(1),
+                coords: Coordinates::new(2, 0, 0),
+            },
+            Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(0, 1, 1),
+            },
+        ];
+
+        for mv in moves {
+            game.add_move(mv).unwrap();
+        }
+
+        let yen: YEN = (&game).into();
+        let loaded_game = GameY::try_from(yen.clone()).unwrap();
+
+        assert_eq!(game.board_size, loaded_game.board_size);
+        let yen_loaded: YEN = (&loaded_game).into();
+        assert_eq!(yen.layout(), yen_loaded.layout());
+    }
+
+    #[test]
+    fn test_yen_size_is_checked_before_allocating() {
+        let yen = YEN::new(MAX_BOARD_SIZE + 1, 0, vec!['B', 'R'], String::new());
+        assert!(matches!(
+            GameY::try_from(yen),
+            Err(GameYError::InvalidBoardSize { size }) if size == MAX_BOARD_SIZE + 1
+        ));
+        let yen = YEN::new(u32::MAX, 0, vec!['B', 'R'], String::new());
+        assert!(GameY::try_from(yen).is_err());
+    }
+
+    #[test]
+    fn test_moves_of_unknown_players_are_rejected() {
+        let mut game = GameY::new(3);
+        let result = game.add_move(Movement::Placement {
+            player: PlayerId::new(2),
+            coords: Coordinates::new(2, 0, 0),
+        });
+        assert!(matches!(
+            result,
+            Err(GameYError::UnknownPlayer { players: 2, .. })
+        ));
+        assert_eq!(game.move_count(), 0);
+        assert!(!game.is_occupied(Coordinates::new(2, 0, 0)));
+    }
+
+    #[test]
+    fn test_winner_and_last_move() {
+        let mut game = GameY::new(4);
+        game.play_move_list("0 6").unwrap();
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.move_count(), 2);
+        let resign = Movement::Action {
+            player: PlayerId::new(0),
+            action: GameAction::Resign,
+        };
+        game.add_move(resign.clone()).unwrap();
+        assert_eq!(game.winner(), Some(PlayerId::new(1)));
+        assert_eq!(game.last_move(), Some(&resign));
+        assert_eq!(game.move_count(), 3);
+    }
+
+    #[test]
+    fn test_occupancy_queries() {
+        let mut game = GameY::with_ruleset(5, Ruleset::MasterY);
+        assert_eq!(game.occupied_count(), 0);
+        let coords = game.cell_coords(game.available_cells()[0]);
+        assert!(!game.is_occupied(coords));
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords,
+        })
+        .unwrap();
+        assert!(game.is_occupied(coords));
+        assert!(game.is_occupied_idx(game.cell_index(coords)));
+        // Blocked cells and cells off the board hold no stone
+        let blocked = (0..game.total_cells())
+            .find(|&index| game.is_blocked(game.cell_coords(index)))
+            .unwrap();
+        assert!(!game.is_occupied_idx(blocked));
+        assert!(!game.is_occupied_idx(game.total_cells()));
+        assert!(!game.is_occupied(Coordinates::new(5, 0, 0)));
+
+        game.random_playout(&mut rand::rng());
+        let stones = (0..game.total_cells())
+            .filter(|&index| game.is_occupied_idx(index))
+            .count();
+        assert_eq!(game.occupied_count() as usize, stones);
+        assert_eq!(stones, game.stones().count());
+    }
+
+    #[test]
+    fn test_largest_board() {
+        let game = GameY::with_topology(MAX_BOARD_SIZE, BoardTopology::Hex);
+        let last = game.total_cells() - 1;
+        assert_eq!(last, 1_048_575);
+        assert_eq!(game.cell_index(game.cell_coords(last)), last);
+        let game = GameY::new(MAX_BOARD_SIZE);
+        let last = game.total_cells() - 1;
+        assert_eq!(
+            game.cell_coords(last),
+            Coordinates::new(0, MAX_BOARD_SIZE - 1, 0)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "above the maximum")]
+    fn test_board_above_the_maximum_panics() {
+        GameY::new(MAX_BOARD_SIZE + 1);
+    }
+
+    #[test]
+    fn test_yen_of_resigned_games() {
+        for resigner in [0, 1] {
+            let mut game = GameY::new(4);
+            game.play_move_list("0 6 3").unwrap();
+            if resigner == 0 {
+                game.play_move_list("9").unwrap();
+            }
+            game.add_move(Movement::Action {
+                player: PlayerId::new(resigner),
+                action: GameAction::Resign,
+            })
+            .unwrap();
+            let winner = PlayerId::new(resigner).opponent();
+            let yen = YEN::from(&game);
+            // The turn passes on after the resignation
+            assert_eq!(yen.turn(), winner.id());
+            assert_eq!(
+                yen.result(),
+                Some(if resigner == 0 { "W+R" } else { "B+R" })
+            );
+
+            let loaded = GameY::try_from(yen.clone()).unwrap();
+            assert_eq!(loaded.winner(), Some(winner));
+            assert_eq!(YEN::from(&loaded).turn(), yen.turn());
+            // Without the history the result still tells who resigned
+            let json = serde_json::to_value(&yen).unwrap();
+            let layout_only = YEN::new(yen.size(), yen.turn(), vec!['B', 'R'], yen.layout().into())
+                .with_result(yen.result().map(String::from));
+            let loaded = GameY::try_from(layout_only.clone()).unwrap();
+            assert_eq!(loaded.winner(), Some(winner));
+            assert_eq!(YEN::from(&loaded).turn(), yen.turn());
+            assert_eq!(json["result"], serde_json::json!(yen.result()));
+        }
+    }
+
+    #[test]
+    fn test_yen_turn_after_swap_and_win() {
+        let mut game = GameY::new(3);
+        game.play_move_list("4").unwrap();
+        game.add_move(Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Swap,
+        })
+        .unwrap();
+        let yen = YEN::from(&game);
+        assert_eq!(yen.turn(), 0);
+        assert_eq!(yen.result(), None);
+        assert!(!serde_json::to_string(&yen).unwrap().contains("result"));
+
+        let mut game = GameY::new(1);
+        game.play_move_list("0").unwrap();
+        let yen = YEN::from(&game);
+        assert_eq!((yen.turn(), yen.result()), (1, Some("B+")));
+        let loaded = GameY::try_from(yen).unwrap();
+        assert_eq!(loaded.winner(), Some(PlayerId::new(0)));
+    }
+
+    // Test loading a YEN representation of a finished game
+    #[test]
+    fn test_load_yen_end2() {
+        let yen_str = r#"{
+            "size": 2,
+            "turn": 0,
+            "players": ["B","R"],
+            "layout": "B/BB"
+        }"#;
+        let yen: YEN = serde_json::from_str(yen_str).unwrap();
+        let game = GameY::try_from(yen).unwrap();
+        match game.status {
+            GameStatus::Finished { winner } => {
+                assert_eq!(winner, PlayerId::new(0));
+            }
+            _ => panic!("Game should be finished with a winner"),
+        }
+    }
+
+    // Test loading a YEN representation of a finished game
+    #[test]
+    fn test_load_yen_end3() {
+        let yen_str = r#"{
+            "size": 3,
+            "turn": 0,
+            "players": ["B","R"],
+            "layout": "B/BB/BBR"
+        }"#;
+        let yen: YEN = serde_json::from_str(yen_str).unwrap();
+        let game = GameY::try_from(yen).unwrap();
+        match game.status {
+            GameStatus::Finished { winner } => {
+                assert_eq!(winner, PlayerId::new(0));
+            }
+            other => panic!("Game should be finished with a winner. Found: {:?}", other),
+        }
+    }
+
+    // Test loading a YEN representation of a finished game
+    #[test]
+    fn test_load_yen_single_full() {
+        let yen_str = r#"{
+            "size": 1,
+            "turn": 0,
+            "players": ["B","R"],
+            "layout": "B"
+        }"#;
+        let yen: YEN = serde_json::from_str(yen_str).unwrap();
+        let game = GameY::try_from(yen).unwrap();
+        match game.status {
+            GameStatus::Finished { winner } => {
+                assert_eq!(winner, PlayerId::new(0));
+            }
+            other => panic!("Game should be finished with a winner. Found {:?}", other),
+        }
+    }
+
+    // Test loading a YEN representation of a finished game
+    #[test]
+    fn test_load_yen_single_empty() {
+        let yen_str = r#"{
+            "size": 1,
+            "turn": 0,
+            "players": ["B","R"],
+            "layout": "."
+        }"#;
+        let yen: YEN = serde_json::from_str(yen_str).unwrap();
+        let game = GameY::try_from(yen).unwrap();
+        match game.status {
+            GameStatus::Ongoing { next_player } => {
+                assert_eq!(next_player, PlayerId::new(0));
+            }
+            _ => panic!("Game should be ongoing"),
+        }
+    }
+
+    #[test]
+    fn test_master_y_blocks_corners() {
+        let mut game = GameY::with_ruleset(5, Ruleset::MasterY);
+        assert_eq!(game.ruleset(), Ruleset::MasterY);
+        assert_eq!(game.available_cells().len(), 12);
+        let result = game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(4, 0, 0),
+        });
+        assert!(matches!(result, Err(GameYError::BlockedCell { .. })));
+    }
+
+    #[test]
+    fn test_master_y_renders_blocked_cells() {
+        let game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let rendered = game.render(&RenderOptions {
+            show_colors: false,
+            ..RenderOptions::default()
+        });
+        assert_eq!(rendered.matches('#').count(), 3);
+    }
+
+    #[test]
+    fn test_master_y_yen_roundtrip() {
+        let mut game = GameY::with_ruleset(4, Ruleset::MasterY);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 1, 1),
+        })
+        .unwrap();
+        let yen: YEN = (&game).into();
+        assert_eq!(yen.layout(), "#/../.B./#..#");
+        let loaded = GameY::try_from(yen).unwrap();
+        assert_eq!(loaded.ruleset(), Ruleset::MasterY);
+        assert_eq!(loaded.available_cells().len(), 6);
+    }
+
+    #[test]
+    fn test_blocked_char_rejected_in_standard_layout() {
+        let yen = YEN::new(2, 0, vec!['B', 'R'], "#/..".to_string());
+        assert!(matches!(
+            GameY::try_from(yen),
+            Err(GameYError::InvalidCharInLayout { char: '#', .. })
+        ));
+    }
+
+    #[test]
+    fn test_hex_player_0_wins_top_to_bottom() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        assert_eq!(game.available_cells().len(), 9);
+        for (player, row, col) in [(0, 0, 1), (1, 0, 0), (0, 1, 1), (1, 1, 0)] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords: Coordinates::new(row, col, 0),
+            })
+            .unwrap();
+        }
+        assert!(!game.check_game_over());
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 1, 0),
+        })
+        .unwrap();
+        assert!(matches!(
+            game.status(),
+            GameStatus::Finished { winner } if winner.id() == 0
+        ));
+    }
+
+    #[test]
+    fn test_hex_player_1_needs_left_to_right() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        // Player 1 fills the left column, which joins top and bottom
+        // (player 0's goal) but not the two sides of player 1
+        let moves = [
+            (0, 0, 2),
+            (1, 0, 0),
+            (0, 2, 2),
+            (1, 1, 0),
+            (0, 2, 1),
+            (1, 2, 0),
+        ];
+        for (player, row, col) in moves {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords: Coordinates::new(row, col, 0),
+            })
+            .unwrap();
+        }
+        assert!(!game.check_game_over());
+    }
+
+    #[test]
+    fn test_hex_rejects_y_coordinates() {
+        let game = GameY::with_topology(3, BoardTopology::Hex);
+        assert!(game.check_coords(Coordinates::new(2, 2, 0)).is_ok());
+        assert!(game.check_coords(Coordinates::new(3, 0, 0)).is_err());
+        assert!(game.check_coords(Coordinates::new(0, 1, 1)).is_err());
+    }
+
+    #[test]
+    fn test_hex_yen_roundtrip() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 2, 0),
+        })
+        .unwrap();
+        let yen: YEN = (&game).into();
+        assert_eq!(yen.layout(), ".../..B/...");
+        let loaded = GameY::try_from(yen).unwrap();
+        assert_eq!(loaded.topology(), BoardTopology::Hex);
+        assert_eq!(loaded.available_cells().len(), 8);
+    }
+
+    #[test]
+    fn test_misere_connection_loses() {
+        let mut game = GameY::with_ruleset(2, Ruleset::Misere);
+        for (player, coords) in [
+            (0, Coordinates::new(1, 0, 0)),
+            (1, Coordinates::new(0, 1, 0)),
+            (0, Coordinates::new(0, 0, 1)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        // Player 0 connected all three sides, so player 1 wins
+        assert!(matches!(
+            game.status(),
+            GameStatus::Finished { winner } if winner.id() == 1
+        ));
+    }
+
+    #[test]
+    fn test_misere_yen_roundtrip() {
+        let game = GameY::with_ruleset(3, Ruleset::Misere);
+        let yen: YEN = (&game).into();
+        assert_eq!(yen.ruleset(), Ruleset::Misere);
+        let loaded = GameY::try_from(yen).unwrap();
+        assert_eq!(loaded.ruleset(), Ruleset::Misere);
+    }
+
+    #[test]
+    fn test_visible_width_skips_colors() {
+        assert_eq!(visible_width("\x1b[34m0\x1b[0m   "), 4);
+    }
+
+    #[test]
+    fn test_render_edge_labels_on_y_board() {
+        let game = GameY::new(3);
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            show_colors: false,
+            show_edge_labels: true,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "2     B .   C");
+        assert_eq!(lines[2], "1   B .   .   C");
+        assert_eq!(lines[3], "0 B .   .   .   C");
+        assert_eq!(lines[4], "    A   A   A");
+        assert_eq!(lines[5], "    0   1   2");
+    }
+
+    #[test]
+    fn test_render_edge_labels_on_hex_board() {
+        let game = GameY::with_topology(2, BoardTopology::Hex);
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            show_colors: false,
+            show_edge_labels: true,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "    0   0");
+        assert_eq!(lines[2], "0 1 .   .   1");
+        assert_eq!(lines[3], "1   1 .   .   1");
+        assert_eq!(lines[4], "      0   0");
+    }
+
+    #[test]
+    fn test_render_turned_view_keeps_the_cells() {
+        let mut game = GameY::new(3);
+        game.play_move_list("0").unwrap();
+        let rendered = game.render(&RenderOptions {
+            show_colors: false,
+            show_edge_labels: true,
+            highlight_last_move: false,
+            view: Symmetry::RotateClockwise,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        // The top corner is drawn at the bottom right, with its index, and
+        // side C at the bottom
+        assert_eq!(lines[1], "2         A .(3)    B");
+        assert_eq!(lines[5], "0 A .(5)    .(2)    0(0)    B");
+        assert_eq!(lines[7], "    C       C       C");
+        assert_eq!(lines[8], "    0       1       2");
+
+        let hex = GameY::with_topology(2, BoardTopology::Hex);
+        let options = RenderOptions {
+            view: Symmetry::RotateClockwise,
+            ..RenderOptions::default()
+        };
+        // A view the board does not have draws it unturned
+        assert_eq!(hex.render(&options), hex.render(&RenderOptions::default()));
+    }
+
+    #[test]
+    fn test_last_placement_skips_actions() {
+        let mut game = GameY::new(3);
+        assert_eq!(game.last_placement(), None);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 1, 0),
+        })
+        .unwrap();
+        game.add_move(Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Swap,
+        })
+        .unwrap();
+        assert_eq!(game.last_placement(), Some(Coordinates::new(1, 1, 0)));
+    }
+
+    #[test]
+    fn test_render_highlights_last_move_with_brackets() {
+        let mut game = GameY::new(3);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 1, 0),
+        })
+        .unwrap();
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            show_colors: false,
+            highlight_last_move: true,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[2], "  .  [0]  ");
+    }
+
+    #[test]
+    fn test_render_highlights_last_move_with_inverse_video() {
+        let mut game = GameY::new(2);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(1),
+            coords: Coordinates::new(1, 0, 0),
+        })
+        .unwrap();
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            highlight_last_move: true,
+            ..RenderOptions::default()
+        });
+        assert!(rendered.contains("\x1b[31m\x1b[7m1\x1b[27m\x1b[0m"));
+    }
+
+    #[test]
+    fn test_render_move_numbers() {
+        let mut game = GameY::new(2);
+        for (player, coords) in [
+            (0, Coordinates::new(0, 1, 0)),
+            (1, Coordinates::new(1, 0, 0)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            show_colors: false,
+            highlight_last_move: false,
+            show_move_numbers: true,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[1], "  2   ");
+        assert_eq!(lines[2], ".   1   ");
+    }
+
+    #[test]
+    fn test_render_move_numbers_are_padded() {
+        let mut game = GameY::new(5);
+        for idx in 0..10 {
+            let player = game.next_player().unwrap();
+            game.add_move(Movement::Placement {
+                player,
+                coords: game.cell_coords(idx),
+            })
+            .unwrap();
+        }
+        let rendered = game.render(&RenderOptions {
+            show_idx: false,
+            show_colors: false,
+            highlight_last_move: false,
+            show_move_numbers: true,
+            ..RenderOptions::default()
+        });
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[4], "    7    8    9   10   ");
+        assert_eq!(lines[5], " .    .    .    .    .   ");
+    }
+
+    #[test]
+    fn test_state_dump_groups_and_status() {
+        let mut game = GameY::new(3);
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 1, 1)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        let state = game.to_state();
+        assert_eq!(state.board.len(), 6);
+        assert_eq!(state.board[0].player, Some(1));
+        assert_eq!(state.history.len(), 3);
+        assert_eq!(state.status, StatusDump::Ongoing { next_player: 1 });
+        assert_eq!(state.available_cells, vec![1, 2, 3]);
+        assert_eq!(state.groups.len(), 2);
+        assert_eq!(state.groups[0].player, 1);
+        assert_eq!(
+            state.groups[1].cells,
+            vec![Coordinates::new(0, 1, 1), Coordinates::new(0, 2, 0)]
+        );
+        assert!(state.groups[1].touches_side_a && state.groups[1].touches_side_c);
+        assert!(!state.groups[1].touches_side_b);
+    }
+
+    #[test]
+    fn test_groups_of_player() {
+        let mut game = GameY::new(3);
+        for (player, coords) in [
+            (0, Coordinates::new(0, 2, 0)),
+            (1, Coordinates::new(2, 0, 0)),
+            (0, Coordinates::new(0, 1, 1)),
+            (1, Coordinates::new(1, 1, 0)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        let groups = game.groups(PlayerId::new(0));
+        assert_eq!(groups.len(), 1);
+        let group = &groups[0];
+        assert_eq!(group.size(), 2);
+        assert!(group.contains(Coordinates::new(0, 1, 1)));
+        assert_eq!(group.sides_touched(), 2);
+        assert!(!group.is_winning());
+        assert_eq!(
+            group.liberties,
+            vec![Coordinates::new(1, 0, 1), Coordinates::new(0, 0, 2)]
+        );
+
+        let groups = game.groups(PlayerId::new(1));
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].liberties, vec![Coordinates::new(1, 0, 1)]);
+    }
+
+    #[test]
+    fn test_group_liberties_skip_blocked_cells() {
+        let mut game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let coords = Coordinates::new(2, 1, 0);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords,
+        })
+        .unwrap();
+        let group = &game.groups(PlayerId::new(0))[0];
+        assert!(!group.liberties.contains(&Coordinates::new(3, 0, 0)));
+        assert_eq!(group.liberties.len(), 3);
+    }
+
+    #[test]
+    fn test_connection_distance() {
+        let mut game = GameY::new(5);
+        assert_eq!(game.connection_distance(PlayerId::new(0), Side::A), None);
+
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 1, 1),
+        })
+        .unwrap();
+        let player = PlayerId::new(0);
+        assert_eq!(game.connection_distance(player, Side::A), Some(2));
+        assert_eq!(game.connection_distance(player, Side::B), Some(1));
+        assert_eq!(game.connection_distance(player, Side::C), Some(1));
+
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(3, 0, 1),
+        })
+        .unwrap();
+        assert_eq!(game.connection_distance(player, Side::B), Some(0));
+    }
+
+    #[test]
+    fn test_connection_distance_goes_around_opponent_stones() {
+        let mut game = GameY::new(3);
+        for (player, coords) in [
+            (0, Coordinates::new(2, 0, 0)),
+            (1, Coordinates::new(1, 1, 0)),
+            (1, Coordinates::new(1, 0, 1)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        assert_eq!(game.connection_distance(PlayerId::new(0), Side::A), None);
+        assert_eq!(game.connection_distance(PlayerId::new(1), Side::A), Some(1));
+    }
+
+    #[test]
+    fn test_state_json_roundtrip() {
+        let game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let json = game.to_state_json().unwrap();
+        let state: GameStateDump = serde_json::from_str(&json).unwrap();
+        assert_eq!(state, game.to_state());
+        assert_eq!(state.board.iter().filter(|c| c.blocked).count(), 3);
+    }
+
+    #[test]
+    fn test_position_after() {
+        let handicap = GameY::handicap_points(5, 1).unwrap();
+        let mut game = GameY::new(5).with_handicap(&handicap).unwrap();
+        for index in [0, 1, 3] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+
+        let start = game.position_after(0).unwrap();
+        assert!(start.history().is_empty());
+        assert_eq!(start.handicap(), game.handicap());
+        assert_eq!(start.next_player(), Some(PlayerId::new(1)));
+
+        let middle = game.position_after(2).unwrap();
+        assert_eq!(middle.history(), &game.history()[..2]);
+        assert_eq!(middle.last_placement(), Some(game.cell_coords(1)));
+
+        let end = game.position_after(10).unwrap();
+        assert_eq!(end.history(), game.history());
+        assert_eq!(end.zobrist_hash(), game.zobrist_hash());
+    }
+
+    #[test]
+    fn test_take_back() {
+        let mut game = GameY::new(4);
+        assert!(matches!(
+            game.take_back(PlayerId::new(0)),
+            Err(GameYError::NothingToTakeBack { .. })
+        ));
+        for index in [0, 1, 3] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game.annotate("opening");
+
+        // Player 1 takes back their move and player 0's reply
+        assert_eq!(game.take_back(PlayerId::new(1)).unwrap(), 2);
+        assert_eq!(game.history().len(), 1);
+        assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+        assert_eq!(game.available_cells().len(), 9);
+        assert_eq!(game.annotations().len(), 1);
+        assert_eq!(game.annotations()[0].move_number, 1);
+        assert!(game.annotations()[0].text.contains("took back 2 move(s)"));
+    }
+
+    #[test]
+    fn test_random_playout_matches_the_rules() {
+        let mut rng = rand::rng();
+        // Small boards take the bit-parallel path, size 16 does not
+        for (size, topology, ruleset) in [
+            (6, BoardTopology::Y, Ruleset::Standard),
+            (6, BoardTopology::Y, Ruleset::Misere),
+            (6, BoardTopology::Hex, Ruleset::Standard),
+            (16, BoardTopology::Y, Ruleset::Standard),
+            (16, BoardTopology::Y, Ruleset::Misere),
+        ] {
+            let mut game = GameY::with_variant(size, topology, ruleset);
+            let winner = game.random_playout(&mut rng).unwrap();
+            assert!(game.history().is_empty());
+            assert_eq!(game.next_player(), None);
+
+            // Placing the same stones through the rules finds the same winner
+            let (first, second): (Vec<_>, Vec<_>) =
+                game.stones().partition(|(_, player)| player.id() == 0);
+            let mut stones = [first, second];
+            let mut replay = GameY::with_variant(size, topology, ruleset);
+            while let Some(player) = replay.next_player() {
+                let (coords, _) = stones[player.id() as usize].pop().unwrap();
+                replay
+                    .add_move(Movement::Placement { player, coords })
+                    .unwrap();
+            }
+            assert!(matches!(replay.status(), GameStatus::Finished { winner: w } if *w == winner));
+        }
+
+        let mut finished = GameY::new(1);
+        let coords = Coordinates::new(0, 0, 0);
+        finished
+            .add_move(Movement::Placement {
+                player: PlayerId::new(0),
+                coords,
+            })
+            .unwrap();
+        assert_eq!(finished.random_playout(&mut rng), Some(PlayerId::new(0)));
+    }
+
+    #[test]
+    fn test_restore_puts_back_the_snapshot() {
+        let mut game = GameY::with_ruleset(5, Ruleset::MasterY);
+        for index in [4, 7] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        game.annotate("before the search");
+        let snapshot = game.snapshot();
+        let expected = game.to_state_json().unwrap();
+
+        game.random_playout(&mut rand::rng());
+        game.restore(&snapshot);
+        assert_eq!(game.to_state_json().unwrap(), expected);
+        assert_eq!(game.available_cells_in_order().count(), 10);
+
+        // The game goes on from the restored position as from the original
+        let player = game.next_player().unwrap();
+        let coords = game.cell_coords(game.available_cells()[0]);
+        game.add_move(Movement::Placement { player, coords })
+            .unwrap();
+        assert_eq!(game.history().len(), 3);
+
+        let mut other = GameY::with_topology(3, BoardTopology::Hex);
+        other.restore(&snapshot);
+        assert_eq!(other.to_state_json().unwrap(), expected);
+        assert_eq!(snapshot.game().history().len(), 2);
+    }
+
+    #[test]
+    fn test_available_cells_after_moves() {
+        let mut game = GameY::with_ruleset(4, Ruleset::MasterY);
+        for index in [1, 8, 4] {
+            let player = game.next_player().unwrap();
+            let coords = game.cell_coords(index);
+            game.add_move(Movement::Placement { player, coords })
+                .unwrap();
+        }
+        let in_order: Vec<u32> = game.available_cells_in_order().collect();
+        assert_eq!(in_order, vec![2, 3, 5, 7]);
+        let mut available = game.available_cells();
+        available.sort_unstable();
+        assert_eq!(available, in_order);
+        assert!(game.is_available(7));
+        assert!(!game.is_available(0));
+        assert!(!game.is_available(8));
+        assert!(!game.is_available(10));
+    }
+}
@@ -0,0 +1,202 @@
+use crate::{BitLayout, BoardTopology, Coordinates, PlayerId};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// The geometries computed so far, by topology and board size.
+type GeometryCache = Mutex<HashMap<(BoardTopology, u32), Arc<BoardGeometry>>>;
+
+/// Tables describing the cells of a board, computed once per topology and
+/// size and shared by every game played on such a board.
+///
+/// [`BoardTopology`] answers the same questions with arithmetic on every
+/// call; the geometry looks the answers up instead, which is what the hot
+/// loops of games and playouts need.
+///
+/// # Example
+///
+/// ```
+/// use gamey_core::{BoardGeometry, BoardTopology, Coordinates};
+///
+/// let geometry = BoardGeometry::shared(BoardTopology::Y, 3);
+/// assert_eq!(geometry.cell_count(), 6);
+/// assert_eq!(geometry.coords(0), Coordinates::new(2, 0, 0));
+/// assert_eq!(geometry.neighbors(0), &[1, 2]);
+/// ```
+#[derive(Debug, PartialEq, Eq)]
+pub struct BoardGeometry {
+    topology: BoardTopology,
+    board_size: u32,
+    /// The coordinates of each cell, by index.
+    coords: Vec<Coordinates>,
+    /// Where the neighbors of each cell start in `neighbors`; one entry per
+    /// cell plus one for the end.
+    neighbor_start: Vec<u32>,
+    /// The indices of the neighbors of every cell, one cell after another.
+    neighbors: Vec<u32>,
+    /// For each cell and player, the sides a stone there touches, as a mask
+    /// with bit `side.index()` set for each touched side.
+    side_masks: Vec<[u8; 2]>,
+    /// The bit of each cell on boards small enough for bit-parallel checks.
+    bit_layout: Option<BitLayout>,
+}
+
+impl BoardGeometry {
+    /// Computes the tables of a board.
+    pub fn new(topology: BoardTopology, board_size: u32) -> Self {
+        let cells = topology.cell_count(board_size);
+        let coords: Vec<Coordinates> = (0..cells)
+            .map(|index| topology.from_index(index, board_size))
+            .collect();
+        let mut neighbor_start = Vec::with_capacity(coords.len() + 1);
+        let mut neighbors = Vec::with_capacity(coords.len() * 6);
+        let mut side_masks = Vec::with_capacity(coords.len());
+        for &cell in &coords {
+            neighbor_start.push(neighbors.len() as u32);
+            neighbors.extend(
+                topology
+                    .neighbors(cell, board_size)
+                    .into_iter()
+                    .map(|neighbor| topology.to_index(neighbor, board_size)),
+            );
+            side_masks.push([0, 1].map(|player| {
+                let touched = topology.sides_touched(cell, board_size, PlayerId::new(player));
+                touched
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, touched)| **touched)
+                    .fold(0, |mask, (side, _)| mask | 1 << side)
+            }));
+        }
+        neighbor_start.push(neighbors.len() as u32);
+        BoardGeometry {
+            topology,
+            board_size,
+            coords,
+            neighbor_start,
+            neighbors,
+            side_masks,
+            bit_layout: BitLayout::new(topology, board_size),
+        }
+    }
+
+    /// Returns the geometry of a board, computing it the first time a board
+    /// of this topology and size is asked for.
+    pub fn shared(topology: BoardTopology, board_size: u32) -> Arc<BoardGeometry> {
+        static CACHE: OnceLock<GeometryCache> = OnceLock::new();
+        let cache = CACHE.get_or_init(Default::default);
+        let mut cache = cache.lock().unwrap_or_else(|e| e.into_inner());
+        Arc::clone(
+            cache
+                .entry((topology, board_size))
+                .or_insert_with(|| Arc::new(BoardGeometry::new(topology, board_size))),
+        )
+    }
+
+    /// Returns the shape of the board.
+    pub fn topology(&self) -> BoardTopology {
+        self.topology
+    }
+
+    /// Returns the size of the board.
+    pub fn board_size(&self) -> u32 {
+        self.board_size
+    }
+
+    /// Returns the number of cells of the board.
+    pub fn cell_count(&self) -> u32 {
+        self.coords.len() as u32
+    }
+
+    /// Returns the coordinates of the cell with the given index.
+    ///
+    /// Indices off the board are converted by the topology, as
+    /// [`BoardTopology::from_index`] does.
+    pub fn coords(&self, index: u32) -> Coordinates {
+        match self.coords.get(index as usize) {
+            Some(&coords) => coords,
+            None => self.topology.from_index(index, self.board_size),
+        }
+    }
+
+    /// Returns the indices of the cells adjacent to the given one, in
+    /// increasing order.
+    ///
+    /// # Panics
+    /// Panics if the index is off the board.
+    pub fn neighbors(&self, index: u32) -> &[u32] {
+        let start = self.neighbor_start[index as usize] as usize;
+        let end = self.neighbor_start[index as usize + 1] as usize;
+        &self.neighbors[start..end]
+    }
+
+    /// Returns the sides a stone of `player` on the given cell touches, as a
+    /// mask with bit [`Side::index`](crate::Side::index) set for each one.
+    ///
+    /// # Panics
+    /// Panics if the index is off the board.
+    pub fn side_mask(&self, index: u32, player: PlayerId) -> u8 {
+        self.side_masks[index as usize][player.id() as usize % 2]
+    }
+
+    /// Returns the layout of the board as a [`BitBoard`](crate::BitBoard), or
+    /// `None` if the board is too large, see
+    /// [`BITBOARD_MAX_CELLS`](crate::BITBOARD_MAX_CELLS).
+    pub fn bit_layout(&self) -> Option<&BitLayout> {
+        self.bit_layout.as_ref()
+    }
+
+    /// Returns which of the three goal sides a stone of `player` on the
+    /// given cell touches, like [`BoardTopology::sides_touched`].
+    ///
+    /// # Panics
+    /// Panics if the index is off the board.
+    pub fn sides_touched(&self, index: u32, player: PlayerId) -> [bool; 3] {
+        let mask = self.side_mask(index, player);
+        [0, 1, 2].map(|side| mask & 1 << side != 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tables_match_the_topology() {
+        for topology in BoardTopology::all() {
+            let geometry = BoardGeometry::new(topology, 6);
+            assert_eq!(geometry.cell_count(), topology.cell_count(6));
+            for index in 0..geometry.cell_count() {
+                let coords = geometry.coords(index);
+                assert_eq!(coords, topology.from_index(index, 6));
+                let neighbors: Vec<Coordinates> = geometry
+                    .neighbors(index)
+                    .iter()
+                    .map(|&n| geometry.coords(n))
+                    .collect();
+                assert_eq!(neighbors, topology.neighbors(coords, 6));
+                for player in [PlayerId::new(0), PlayerId::new(1)] {
+                    assert_eq!(
+                        geometry.sides_touched(index, player),
+                        topology.sides_touched(coords, 6, player)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shared_geometries_are_cached() {
+        let first = BoardGeometry::shared(BoardTopology::Hex, 4);
+        let second = BoardGeometry::shared(BoardTopology::Hex, 4);
+        assert!(Arc::ptr_eq(&first, &second));
+        let other = BoardGeometry::shared(BoardTopology::Y, 4);
+        assert!(!Arc::ptr_eq(&first, &other));
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let geometry = BoardGeometry::new(BoardTopology::Y, 0);
+        assert_eq!(geometry.cell_count(), 0);
+        assert!(geometry.bit_layout().is_none());
+    }
+}
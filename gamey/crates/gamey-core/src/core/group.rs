@@ -0,0 +1,50 @@
+use crate::{Coordinates, PlayerId};
+
+/// A chain of connected stones of one player, as returned by
+/// [`GameY::groups`](crate::GameY::groups).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Group {
+    /// The player owning the stones.
+    pub player: PlayerId,
+    /// The stones of the group, in cell index order.
+    pub cells: Vec<Coordinates>,
+    /// The empty cells adjacent to the group where a stone may be placed, in
+    /// cell index order.
+    pub liberties: Vec<Coordinates>,
+    /// True if the group touches the first goal side.
+    pub touches_side_a: bool,
+    /// True if the group touches the second goal side.
+    pub touches_side_b: bool,
+    /// True if the group touches the third goal side.
+    pub touches_side_c: bool,
+}
+
+impl Group {
+    /// Returns the number of stones in the group.
+    pub fn size(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Returns how many goal sides the group touches.
+    pub fn sides_touched(&self) -> usize {
+        [
+            self.touches_side_a,
+            self.touches_side_b,
+            self.touches_side_c,
+        ]
+        .iter()
+        .filter(|&&touched| touched)
+        .count()
+    }
+
+    /// Returns true if the group touches every goal side, that is, if it
+    /// decides the game.
+    pub fn is_winning(&self) -> bool {
+        self.sides_touched() == 3
+    }
+
+    /// Returns true if the group contains a stone at the given cell.
+    pub fn contains(&self, coords: Coordinates) -> bool {
+        self.cells.contains(&coords)
+    }
+}
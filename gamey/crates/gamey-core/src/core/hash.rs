@@ -0,0 +1,147 @@
+use crate::{BoardTopology, Coordinates, GameStatus, GameY, Ruleset, Symmetry};
+
+const VARIANT_SEED: u64 = 0x6761_6d65_795f_7661;
+const STONE_SEED: u64 = 0x6761_6d65_795f_7374;
+const SIDE_TO_MOVE_KEY: u64 = 0x9e6c_63d0_676a_9a99;
+
+impl GameY {
+    /// Returns the Zobrist hash of the position.
+    ///
+    /// The hash covers the variant (size, topology and ruleset), the stones
+    /// on the board and the player to move, but not the order in which the
+    /// stones were placed, so transpositions share a hash. The keys are
+    /// fixed, so hashes are stable across runs and can be stored.
+    pub fn zobrist_hash(&self) -> u64 {
+        self.hash_with(|coords| coords)
+    }
+
+    /// Returns the smallest [`GameY::zobrist_hash`] among the positions
+    /// equivalent to this one under the symmetries of the board.
+    ///
+    /// A Y board has six symmetries (rotations and reflections of the
+    /// triangle), a Hex board only the half-turn rotation, see
+    /// [`Symmetry::of`]. Symmetric positions therefore share their
+    /// canonical hash.
+    pub fn canonical_hash(&self) -> u64 {
+        let size = self.board_size();
+        Symmetry::of(self.topology())
+            .iter()
+            .map(|symmetry| self.hash_with(|coords| symmetry.apply(coords, size)))
+            .min()
+            .unwrap_or_default()
+    }
+
+    /// Hashes the position after moving every stone with `symmetry`.
+    fn hash_with(&self, symmetry: impl Fn(Coordinates) -> Coordinates) -> u64 {
+        let variant = (self.board_size() as u64)
+            | (topology_tag(self.topology()) << 32)
+            | (ruleset_tag(self.ruleset()) << 40);
+        let mut hash = splitmix64(VARIANT_SEED ^ variant);
+        for (coords, player) in self.stones() {
+            let index = self.cell_index(symmetry(coords)) as u64;
+            hash ^= splitmix64(STONE_SEED.wrapping_add(index * 2 + player.id() as u64));
+        }
+        if let GameStatus::Ongoing { next_player } = self.status()
+            && next_player.id() == 1
+        {
+            hash ^= SIDE_TO_MOVE_KEY;
+        }
+        hash
+    }
+}
+
+fn topology_tag(topology: BoardTopology) -> u64 {
+    match topology {
+        BoardTopology::Y => 0,
+        BoardTopology::Hex => 1,
+    }
+}
+
+fn ruleset_tag(ruleset: Ruleset) -> u64 {
+    match ruleset {
+        Ruleset::Standard => 0,
+        Ruleset::MasterY => 1,
+        Ruleset::Misere => 2,
+    }
+}
+
+/// The SplitMix64 finalizer, used to derive well-mixed fixed keys.
+pub(crate) fn splitmix64(seed: u64) -> u64 {
+    let mut z = seed.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Coordinates, GameY, Movement, PlayerId, Ruleset};
+
+    fn game_with(size: u32, moves: &[(u32, Coordinates)]) -> GameY {
+        let mut game = GameY::new(size);
+        for &(player, coords) in moves {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_transpositions_share_the_hash() {
+        let a = Coordinates::new(2, 1, 0);
+        let b = Coordinates::new(0, 1, 2);
+        let c = Coordinates::new(1, 1, 1);
+        let d = Coordinates::new(3, 0, 0);
+        let first = game_with(4, &[(0, a), (1, c), (0, b), (1, d)]);
+        let second = game_with(4, &[(0, b), (1, d), (0, a), (1, c)]);
+        assert_eq!(first.zobrist_hash(), second.zobrist_hash());
+    }
+
+    #[test]
+    fn test_hash_depends_on_stones_and_variant() {
+        let empty = GameY::new(4);
+        let one = game_with(4, &[(0, Coordinates::new(2, 1, 0))]);
+        let other = game_with(4, &[(0, Coordinates::new(1, 1, 1))]);
+        assert_ne!(empty.zobrist_hash(), one.zobrist_hash());
+        assert_ne!(one.zobrist_hash(), other.zobrist_hash());
+        assert_ne!(
+            GameY::new(4).zobrist_hash(),
+            GameY::with_ruleset(4, Ruleset::Misere).zobrist_hash()
+        );
+        assert_ne!(GameY::new(4).zobrist_hash(), GameY::new(5).zobrist_hash());
+    }
+
+    #[test]
+    fn test_symmetric_positions_share_the_canonical_hash() {
+        let one = game_with(4, &[(0, Coordinates::new(2, 1, 0))]);
+        let mirrored = game_with(4, &[(0, Coordinates::new(0, 1, 2))]);
+        let rotated = game_with(4, &[(0, Coordinates::new(1, 0, 2))]);
+        assert_ne!(one.zobrist_hash(), mirrored.zobrist_hash());
+        assert_eq!(one.canonical_hash(), mirrored.canonical_hash());
+        assert_eq!(one.canonical_hash(), rotated.canonical_hash());
+        let center = game_with(4, &[(0, Coordinates::new(1, 1, 1))]);
+        assert_ne!(one.canonical_hash(), center.canonical_hash());
+    }
+
+    #[test]
+    fn test_hex_canonical_hash_uses_the_half_turn() {
+        let hex = crate::BoardTopology::Hex;
+        let mut one = GameY::with_topology(3, hex);
+        one.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 1, 0),
+        })
+        .unwrap();
+        let mut rotated = GameY::with_topology(3, hex);
+        rotated
+            .add_move(Movement::Placement {
+                player: PlayerId::new(0),
+                coords: Coordinates::new(2, 1, 0),
+            })
+            .unwrap();
+        assert_eq!(one.canonical_hash(), rotated.canonical_hash());
+    }
+}
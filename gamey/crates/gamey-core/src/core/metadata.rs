@@ -0,0 +1,169 @@
+use crate::{GameAction, GameY, Movement, PlayerId};
+use serde::{Deserialize, Serialize};
+
+/// Facts about a game besides its moves: the event it was played at, when,
+/// by whom, and how it ended.
+///
+/// Every field is optional. Metadata is kept in saved games, SGF records,
+/// archives and hosted games, and is written only when it has something.
+///
+/// # Example
+/// ```
+/// use gamey_core::{GameMetadata, PlayerId};
+///
+/// let metadata = GameMetadata::default()
+///     .with_event("Club championship")
+///     .with_player(PlayerId::new(0), "Ada")
+///     .with_rating(PlayerId::new(0), 1840.0);
+/// assert_eq!(metadata.player_name(PlayerId::new(0)), Some("Ada"));
+/// assert_eq!(metadata.player_name(PlayerId::new(1)), None);
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct GameMetadata {
+    /// The name of the event, such as a tournament.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    /// The day the game was played, as `YYYY-MM-DD`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>,
+    /// The players, by player index.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub players: Vec<PlayerMetadata>,
+    /// The result as recorded, in SGF style: `B+` or `W+` for a win of the
+    /// first or second player, `B+R` for a win by resignation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+}
+
+/// The name and rating of a player of a game.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PlayerMetadata {
+    /// The name of the player.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// The rating of the player when the game was played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rating: Option<f64>,
+}
+
+impl GameMetadata {
+    /// Returns true if no field is set.
+    pub fn is_empty(&self) -> bool {
+        self.event.is_none()
+            && self.date.is_none()
+            && self.result.is_none()
+            && self
+                .players
+                .iter()
+                .all(|player| player.name.is_none() && player.rating.is_none())
+    }
+
+    /// Sets the name of the event.
+    pub fn with_event(mut self, event: impl Into<String>) -> Self {
+        self.event = Some(event.into());
+        self
+    }
+
+    /// Sets the day the game was played, as `YYYY-MM-DD`.
+    pub fn with_date(mut self, date: impl Into<String>) -> Self {
+        self.date = Some(date.into());
+        self
+    }
+
+    /// Sets the name of `player`.
+    pub fn with_player(mut self, player: PlayerId, name: impl Into<String>) -> Self {
+        self.player_mut(player).name = Some(name.into());
+        self
+    }
+
+    /// Sets the rating of `player`.
+    pub fn with_rating(mut self, player: PlayerId, rating: f64) -> Self {
+        self.player_mut(player).rating = Some(rating);
+        self
+    }
+
+    /// Sets the result as recorded.
+    pub fn with_result(mut self, result: impl Into<String>) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+
+    /// Returns the name of `player`, if known.
+    pub fn player_name(&self, player: PlayerId) -> Option<&str> {
+        self.players.get(player.id() as usize)?.name.as_deref()
+    }
+
+    /// Returns the rating of `player`, if known.
+    pub fn rating(&self, player: PlayerId) -> Option<f64> {
+        self.players.get(player.id() as usize)?.rating
+    }
+
+    /// Returns the entry of `player`, adding empty entries up to it.
+    pub fn player_mut(&mut self, player: PlayerId) -> &mut PlayerMetadata {
+        let index = player.id() as usize;
+        if self.players.len() <= index {
+            self.players.resize_with(index + 1, PlayerMetadata::default);
+        }
+        &mut self.players[index]
+    }
+}
+
+impl GameY {
+    /// Returns the result of a finished game as the engine determines it,
+    /// in the style of [`GameMetadata::result`]: `B+` or `W+` for a win of
+    /// the first or second player, with `R` appended when the loser
+    /// resigned. Returns `None` while the game is ongoing.
+    pub fn result(&self) -> Option<String> {
+        let winner = self.winner()?;
+        let resigned = matches!(
+            self.last_move(),
+            Some(Movement::Action {
+                action: GameAction::Resign,
+                ..
+            })
+        );
+        let color = if winner.id() == 0 { 'B' } else { 'W' };
+        Some(format!("{}+{}", color, if resigned { "R" } else { "" }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_metadata_is_not_serialized() {
+        let metadata = GameMetadata::default();
+        assert!(metadata.is_empty());
+        assert_eq!(serde_json::to_string(&metadata).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_players_are_indexed_by_id() {
+        let metadata = GameMetadata::default()
+            .with_player(PlayerId::new(1), "Grace")
+            .with_rating(PlayerId::new(1), 1500.0);
+        assert!(!metadata.is_empty());
+        assert_eq!(metadata.players.len(), 2);
+        assert_eq!(metadata.player_name(PlayerId::new(0)), None);
+        assert_eq!(metadata.player_name(PlayerId::new(1)), Some("Grace"));
+        assert_eq!(metadata.rating(PlayerId::new(1)), Some(1500.0));
+
+        let json = serde_json::to_string(&metadata).unwrap();
+        assert_eq!(json, r#"{"players":[{},{"name":"Grace","rating":1500.0}]}"#);
+        let read: GameMetadata = serde_json::from_str(&json).unwrap();
+        assert_eq!(read, metadata);
+    }
+
+    #[test]
+    fn test_result_of_the_game() {
+        let mut game = GameY::new(2);
+        assert_eq!(game.result(), None);
+        game.play_move_list("0 1 2").unwrap();
+        assert_eq!(game.result().as_deref(), Some("B+"));
+
+        let mut game = GameY::new(3);
+        game.play_move_list("0 resign").unwrap();
+        assert_eq!(game.result().as_deref(), Some("B+R"));
+    }
+}
@@ -0,0 +1,75 @@
+//! Core game types and logic for the Y game.
+//!
+//! This module contains the fundamental types for representing and playing Y:
+//! - [`Coordinates`]: Barycentric coordinates on the triangular board
+//! - [`GameY`]: The main game state and logic
+//! - [`GameStatus`]: Whether the game is ongoing or finished
+//! - [`GameStateDump`]: A complete JSON-friendly snapshot of a game
+//! - [`GameY::describe`]: The position in plain sentences, for screen readers
+//! - [`Group`]: A chain of connected stones of one player
+//! - [`GameY::zobrist_hash`] and [`GameY::canonical_hash`]: Position hashes
+//! - [`Player`] and [`PlayerId`]: Player representation
+//! - [`Position`]: An immutable, shareable position keyed by its stones
+//! - [`Movement`]: A move (placement or action) in the game
+//! - [`Annotation`] and [`Glyph`]: Comments and move judgements attached to
+//!   the game history
+//! - [`GameMetadata`]: The event, date, players and result of a game
+//! - [`GameAction`]: Special actions like swap or resign
+//! - [`RenderOptions`]: Configuration for board rendering
+//! - [`GameY::render_svg`]: The board as an SVG image
+//! - [`GameY::legal_move_mask`] and [`GameY::encode_planes`]: The input
+//!   encoding of the position for neural networks
+//! - [`SaveOptions`]: How games are written to files
+//! - [`Ruleset`]: The variant of Y being played
+//! - [`BoardTopology`]: The board shape, either Y or Hex
+//! - [`Symmetry`]: A rotation or reflection of the board
+//! - [`BoardGeometry`]: Cached cell tables of a board shape and size
+//! - [`BitBoard`] and [`BitLayout`]: Bit-parallel connection checks on small
+//!   boards
+//! - [`Side`]: One of the goal sides of the board
+
+pub mod action;
+pub mod annotation;
+pub mod bitboard;
+pub mod coord;
+pub mod describe;
+pub mod dump;
+pub mod encoding;
+pub mod game;
+pub mod geometry;
+pub mod group;
+pub mod hash;
+pub mod metadata;
+pub mod movement;
+pub mod player;
+mod player_set;
+mod position;
+pub mod render_options;
+pub mod ruleset;
+pub mod save_options;
+pub mod side;
+pub mod svg;
+pub mod symmetry;
+pub mod topology;
+
+pub use action::*;
+pub use annotation::*;
+pub use bitboard::*;
+pub use coord::*;
+pub use dump::*;
+pub use encoding::*;
+pub use game::*;
+pub use geometry::*;
+pub use group::*;
+pub use metadata::*;
+pub use movement::*;
+pub use player::*;
+pub use position::*;
+pub use render_options::*;
+pub use ruleset::*;
+pub use save_options::*;
+pub use side::*;
+pub use symmetry::*;
+pub use topology::*;
+
+type SetIdx = usize;
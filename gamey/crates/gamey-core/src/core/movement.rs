@@ -0,0 +1,218 @@
+use crate::{Coordinates, GameAction, GameYError, PlayerId};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// Represents a move that a player can make during the game.
+///
+/// A movement can either be placing a piece on the board at specific coordinates,
+/// or performing a special game action like swapping or resigning.
+///
+/// Movements are serialized as their [`MoveRecord`], e.g.
+/// `{"type":"placement","player":0,"coords":{"x":2,"y":0,"z":0}}` or
+/// `{"type":"action","player":1,"action":"swap"}`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(into = "MoveRecord", try_from = "MoveRecord")]
+pub enum Movement {
+    /// A piece placement on the board.
+    Placement {
+        /// The player making the placement.
+        player: PlayerId,
+        /// The coordinates where the piece is placed.
+        coords: Coordinates,
+    },
+    /// A special game action (not a piece placement).
+    Action {
+        /// The player performing the action.
+        player: PlayerId,
+        /// The action being performed.
+        action: GameAction,
+    },
+}
+
+impl Movement {
+    /// Returns the player making the move.
+    pub fn player(&self) -> PlayerId {
+        match self {
+            Movement::Placement { player, .. } | Movement::Action { player, .. } => *player,
+        }
+    }
+}
+
+impl Display for Movement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Movement::Placement { player, coords } => {
+                write!(f, "Player {} places at {}", player, coords)
+            }
+            Movement::Action { player, action } => {
+                write!(f, "Player {} performs action {}", player, action)
+            }
+        }
+    }
+}
+
+/// A serializable record of a single move in a game's history.
+///
+/// This is the wire format used for submitting moves and for exporting the
+/// history of a hosted game.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MoveRecord {
+    /// A stone placed on the board.
+    Placement {
+        /// The player placing the stone.
+        player: u32,
+        /// Where the stone is placed.
+        coords: Coordinates,
+    },
+    /// A non-placement action, either `"swap"` or `"resign"`.
+    Action {
+        /// The player performing the action.
+        player: u32,
+        /// The name of the action.
+        action: String,
+    },
+}
+
+impl From<&Movement> for MoveRecord {
+    fn from(movement: &Movement) -> Self {
+        match movement {
+            Movement::Placement { player, coords } => MoveRecord::Placement {
+                player: player.id(),
+                coords: *coords,
+            },
+            Movement::Action { player, action } => MoveRecord::Action {
+                player: player.id(),
+                action: action.to_string().to_lowercase(),
+            },
+        }
+    }
+}
+
+impl From<Movement> for MoveRecord {
+    fn from(movement: Movement) -> Self {
+        MoveRecord::from(&movement)
+    }
+}
+
+impl TryFrom<MoveRecord> for Movement {
+    type Error = GameYError;
+
+    fn try_from(record: MoveRecord) -> Result<Self, Self::Error> {
+        match record {
+            MoveRecord::Placement { player, coords } => Ok(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            }),
+            MoveRecord::Action { player, action } => {
+                let action = match action.as_str() {
+                    "swap" => GameAction::Swap,
+                    "resign" => GameAction::Resign,
+                    _ => return Err(GameYError::UnknownAction { action }),
+                };
+                Ok(Movement::Action {
+                    player: PlayerId::new(player),
+                    action,
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement_display() {
+        let movement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 2, 3),
+        };
+        assert_eq!(format!("{}", movement), "Player 0 places at (1, 2, 3)");
+    }
+
+    #[test]
+    fn test_action_swap_display() {
+        let movement = Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Swap,
+        };
+        assert_eq!(format!("{}", movement), "Player 1 performs action Swap");
+    }
+
+    #[test]
+    fn test_action_resign_display() {
+        let movement = Movement::Action {
+            player: PlayerId::new(0),
+            action: GameAction::Resign,
+        };
+        assert_eq!(format!("{}", movement), "Player 0 performs action Resign");
+    }
+
+    #[test]
+    fn test_clone() {
+        let movement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 2, 3),
+        };
+        let cloned = movement.clone();
+        assert_eq!(format!("{}", movement), format!("{}", cloned));
+    }
+
+    #[test]
+    fn test_move_record_roundtrip() {
+        let movement = Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Resign,
+        };
+        let record = MoveRecord::from(&movement);
+        assert_eq!(
+            record,
+            MoveRecord::Action {
+                player: 1,
+                action: "resign".to_string()
+            }
+        );
+        let back = Movement::try_from(record).unwrap();
+        assert_eq!(format!("{}", back), format!("{}", movement));
+    }
+
+    #[test]
+    fn test_movement_wire_format() {
+        let placement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(2, 0, 0),
+        };
+        let json = serde_json::to_string(&placement).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"placement","player":0,"coords":{"x":2,"y":0,"z":0}}"#
+        );
+        assert_eq!(serde_json::from_str::<Movement>(&json).unwrap(), placement);
+
+        let swap = Movement::Action {
+            player: PlayerId::new(1),
+            action: GameAction::Swap,
+        };
+        let json = serde_json::to_string(&swap).unwrap();
+        assert_eq!(json, r#"{"type":"action","player":1,"action":"swap"}"#);
+        assert_eq!(serde_json::from_str::<Movement>(&json).unwrap(), swap);
+
+        let pass = r#"{"type":"action","player":0,"action":"pass"}"#;
+        let err = serde_json::from_str::<Movement>(pass).unwrap_err();
+        assert!(err.to_string().contains("pass"));
+    }
+
+    #[test]
+    fn test_move_record_unknown_action() {
+        let record = MoveRecord::Action {
+            player: 0,
+            action: "pass".to_string(),
+        };
+        assert!(matches!(
+            Movement::try_from(record),
+            Err(GameYError::UnknownAction { .. })
+        ));
+    }
+}
@@ -1,5 +1,9 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
+/// The number of players of a game, numbered from 0.
+pub const PLAYER_COUNT: u32 = 2;
+
 /// Represents a player in the game with an identifier and a name.
 #[derive(Debug, Clone)]
 pub struct Player {
@@ -33,8 +37,11 @@ impl Display for Player {
 /// A unique identifier for a player.
 ///
 /// This is a lightweight wrapper around a `u32` that provides type safety
-/// for player identification throughout the game.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// for player identification throughout the game. It is serialized as the
+/// bare number. Any number is accepted here; a game rejects moves of
+/// players at or above [`PLAYER_COUNT`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct PlayerId(u32);
 
 impl PlayerId {
@@ -47,6 +54,15 @@ impl PlayerId {
     pub fn id(&self) -> u32 {
         self.0
     }
+
+    /// Returns the other player: 1 for player 0 and 0 for any other.
+    pub fn opponent(&self) -> PlayerId {
+        if self.0 == 0 {
+            PlayerId(1)
+        } else {
+            PlayerId(0)
+        }
+    }
 }
 
 impl Display for PlayerId {
@@ -65,6 +81,20 @@ mod tests {
         assert_eq!(id.id(), 42);
     }
 
+    #[test]
+    fn test_player_id_opponent() {
+        assert_eq!(PlayerId::new(0).opponent(), PlayerId::new(1));
+        assert_eq!(PlayerId::new(1).opponent(), PlayerId::new(0));
+    }
+
+    #[test]
+    fn test_player_id_serde() {
+        assert_eq!(serde_json::to_string(&PlayerId::new(1)).unwrap(), "1");
+        let id: PlayerId = serde_json::from_str("0").unwrap();
+        assert_eq!(id, PlayerId::new(0));
+        assert!(serde_json::from_str::<PlayerId>("-1").is_err());
+    }
+
     #[test]
     fn test_player_id_display() {
         let id = PlayerId::new(1);
@@ -0,0 +1,157 @@
+use crate::{BoardTopology, Cell, PlayerId, Ruleset};
+use std::sync::Arc;
+
+/// An immutable position of a game: the board, the stones on it and whose
+/// turn it is, without the history that led there.
+///
+/// Positions compare and hash by what is on the board, so games that reach
+/// the same stones by different move orders share a position. The cells are
+/// kept behind an `Arc`, so cloning a position is cheap and positions can be
+/// handed to other threads or used as keys of caches and transposition
+/// tables. Handicap stones are ordinary stones of player 0 here.
+///
+/// # Example
+///
+/// ```
+/// use gamey_core::{Coordinates, GameY, Movement, PlayerId};
+///
+/// let mut game = GameY::new(5);
+/// game.add_move(Movement::Placement {
+///     player: PlayerId::new(0),
+///     coords: Coordinates::new(2, 1, 1),
+/// })
+/// .unwrap();
+/// let position = game.position();
+/// assert_eq!(position.next_player(), Some(PlayerId::new(1)));
+///
+/// let copy = GameY::from_position(&position);
+/// assert_eq!(copy.position(), position);
+/// assert!(copy.history().is_empty());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    board_size: u32,
+    topology: BoardTopology,
+    ruleset: Ruleset,
+    cells: Arc<[Cell]>,
+    next_player: Option<PlayerId>,
+    winner: Option<PlayerId>,
+}
+
+impl Position {
+    pub(crate) fn new(
+        board_size: u32,
+        topology: BoardTopology,
+        ruleset: Ruleset,
+        cells: Arc<[Cell]>,
+        next_player: Option<PlayerId>,
+        winner: Option<PlayerId>,
+    ) -> Self {
+        Position {
+            board_size,
+            topology,
+            ruleset,
+            cells,
+            next_player,
+            winner,
+        }
+    }
+
+    /// Returns the size of the board.
+    pub fn board_size(&self) -> u32 {
+        self.board_size
+    }
+
+    /// Returns the shape of the board.
+    pub fn topology(&self) -> BoardTopology {
+        self.topology
+    }
+
+    /// Returns the variant being played.
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// Returns the contents of every cell, by cell index. Blocked cells are
+    /// empty.
+    pub fn cells(&self) -> &[Cell] {
+        &self.cells
+    }
+
+    /// Returns the contents of the cell with the given index, or `None` if
+    /// the index is off the board.
+    pub fn cell(&self, index: u32) -> Option<Cell> {
+        self.cells.get(index as usize).copied()
+    }
+
+    /// Returns the player to move, or `None` if the game is over.
+    pub fn next_player(&self) -> Option<PlayerId> {
+        self.next_player
+    }
+
+    /// Returns the winner, or `None` while the game is ongoing.
+    pub fn winner(&self) -> Option<PlayerId> {
+        self.winner
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameY, Movement};
+    use std::collections::HashSet;
+
+    fn play(game: &mut GameY, index: u32) {
+        let player = game.next_player().unwrap();
+        let coords = game.cell_coords(index);
+        game.add_move(Movement::Placement { player, coords })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_transpositions_share_a_position() {
+        let mut first = GameY::new(5);
+        let mut second = GameY::new(5);
+        for index in [3, 7, 12] {
+            play(&mut first, index);
+        }
+        for index in [12, 7, 3] {
+            play(&mut second, index);
+        }
+        assert_eq!(first.position(), second.position());
+        let positions: HashSet<Position> = [first.position(), second.position()].into();
+        assert_eq!(positions.len(), 1);
+
+        play(&mut second, 0);
+        assert_ne!(first.position(), second.position());
+    }
+
+    #[test]
+    fn test_from_position_restores_the_game() {
+        let mut game = GameY::with_topology(4, BoardTopology::Hex);
+        for index in [0, 5, 4, 9, 8, 13, 12] {
+            play(&mut game, index);
+        }
+        let position = game.position();
+        assert_eq!(position.winner(), Some(PlayerId::new(0)));
+        assert_eq!(position.cell(0), Some(Cell::Occupied(PlayerId::new(0))));
+        assert_eq!(position.cell(1), Some(Cell::Empty));
+        assert_eq!(position.cell(16), None);
+
+        let restored = GameY::from_position(&position);
+        assert_eq!(restored.next_player(), None);
+        assert_eq!(restored.available_cells().len(), 9);
+        assert_eq!(restored.groups(PlayerId::new(0)).len(), 1);
+        assert_eq!(restored.position(), position);
+    }
+
+    #[test]
+    fn test_positions_can_be_shared_between_threads() {
+        let position = Arc::new(GameY::new(4).position());
+        let shared = Arc::clone(&position);
+        let size = std::thread::spawn(move || shared.board_size())
+            .join()
+            .unwrap();
+        assert_eq!(size, 4);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::Symmetry;
+
+/// Configuration options for rendering the game board.
+///
+/// Controls what information is displayed when rendering the board to text.
+pub struct RenderOptions {
+    /// If true, show barycentric (x, y, z) coordinates for each cell.
+    pub show_3d_coords: bool,
+    /// If true, show the linear index for each cell.
+    pub show_idx: bool,
+    /// If true, use ANSI color codes to distinguish players.
+    pub show_colors: bool,
+    /// If true, label the edges of the board with the sides they stand for
+    /// and add row and column guides.
+    pub show_edge_labels: bool,
+    /// If true, mark the most recent placement (inverse video with colors,
+    /// brackets without) so it is easy to spot.
+    pub highlight_last_move: bool,
+    /// If true, print the move number of each stone instead of its player,
+    /// like a printed game diagram.
+    pub show_move_numbers: bool,
+    /// The orientation the board is drawn in, e.g. turned so that another
+    /// side is at the bottom. Cells keep their coordinates and indices.
+    pub view: Symmetry,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            show_3d_coords: false,
+            show_idx: true,
+            show_colors: true,
+            show_edge_labels: false,
+            highlight_last_move: true,
+            show_move_numbers: false,
+            view: Symmetry::Identity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = RenderOptions::default();
+        assert!(!options.show_3d_coords);
+        assert!(options.show_idx);
+        assert!(options.show_colors);
+        assert!(!options.show_edge_labels);
+        assert!(options.highlight_last_move);
+        assert!(!options.show_move_numbers);
+        assert_eq!(options.view, Symmetry::Identity);
+    }
+
+    #[test]
+    fn test_custom_options() {
+        let options = RenderOptions {
+            show_3d_coords: true,
+            show_idx: false,
+            show_colors: false,
+            show_edge_labels: true,
+            highlight_last_move: false,
+            show_move_numbers: true,
+            view: Symmetry::HalfTurn,
+        };
+        assert!(options.show_3d_coords);
+        assert!(!options.show_idx);
+        assert!(!options.show_colors);
+        assert!(options.show_edge_labels);
+        assert!(!options.highlight_last_move);
+        assert!(options.show_move_numbers);
+        assert_eq!(options.view, Symmetry::HalfTurn);
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{BoardTopology, Coordinates};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The variant of Y being played.
+///
+/// All variants are played by connecting the sides of the board. They differ
+/// in the board geometry, which each variant describes through
+/// [`Ruleset::blocked_cells`], and in who wins once a connection is made,
+/// see [`Ruleset::is_misere`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ruleset {
+    /// The classic game on a plain triangular board.
+    #[default]
+    Standard,
+    /// Master Y: the three corner cells are blocked by neutral stones, so the
+    /// corners become obtuse and no single cell touches two sides.
+    MasterY,
+    /// Misère (reverse Y): the player who connects the sides loses.
+    Misere,
+}
+
+impl Ruleset {
+    /// Returns every supported ruleset.
+    pub fn all() -> [Ruleset; 3] {
+        [Ruleset::Standard, Ruleset::MasterY, Ruleset::Misere]
+    }
+
+    /// Returns the cells where no player may place a stone on a board of
+    /// the given topology and size.
+    pub fn blocked_cells(&self, topology: BoardTopology, board_size: u32) -> Vec<Coordinates> {
+        match self {
+            Ruleset::Standard | Ruleset::Misere => Vec::new(),
+            // Master Y reshapes the corners of the triangle; Hex has none to block
+            Ruleset::MasterY if !topology.is_y() => Vec::new(),
+            // A smaller board would have no playable cell touching a side
+            Ruleset::MasterY if board_size < 4 => Vec::new(),
+            Ruleset::MasterY => {
+                let m = board_size - 1;
+                vec![
+                    Coordinates::new(m, 0, 0),
+                    Coordinates::new(0, m, 0),
+                    Coordinates::new(0, 0, m),
+                ]
+            }
+        }
+    }
+
+    /// Returns true if completing a connection loses the game instead of
+    /// winning it.
+    pub fn is_misere(&self) -> bool {
+        *self == Ruleset::Misere
+    }
+
+    /// Returns true for the classic ruleset.
+    pub fn is_standard(&self) -> bool {
+        *self == Ruleset::Standard
+    }
+}
+
+impl Display for Ruleset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Ruleset::Standard => write!(f, "standard"),
+            Ruleset::MasterY => write!(f, "master-y"),
+            Ruleset::Misere => write!(f, "misere"),
+        }
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "standard" => Ok(Ruleset::Standard),
+            "master-y" | "master_y" => Ok(Ruleset::MasterY),
+            "misere" => Ok(Ruleset::Misere),
+            _ => Err(format!(
+                "Unknown ruleset '{}', expected one of: standard, master-y, misere",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standard_has_no_blocked_cells() {
+        assert!(
+            Ruleset::Standard
+                .blocked_cells(BoardTopology::Y, 7)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_master_y_blocks_corners() {
+        let blocked = Ruleset::MasterY.blocked_cells(BoardTopology::Y, 5);
+        assert_eq!(blocked.len(), 3);
+        assert!(blocked.contains(&Coordinates::new(4, 0, 0)));
+        assert!(blocked.contains(&Coordinates::new(0, 4, 0)));
+        assert!(blocked.contains(&Coordinates::new(0, 0, 4)));
+    }
+
+    #[test]
+    fn test_master_y_small_board_has_no_blocked_cells() {
+        assert!(
+            Ruleset::MasterY
+                .blocked_cells(BoardTopology::Y, 3)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_only_misere_reverses_the_result() {
+        assert!(Ruleset::Misere.is_misere());
+        assert!(!Ruleset::Standard.is_misere());
+        assert!(Ruleset::Misere.blocked_cells(BoardTopology::Y, 5).is_empty());
+    }
+
+    #[test]
+    fn test_display_and_parse_roundtrip() {
+        for ruleset in Ruleset::all() {
+            assert_eq!(ruleset.to_string().parse::<Ruleset>(), Ok(ruleset));
+        }
+        assert!("hex".parse::<Ruleset>().is_err());
+    }
+
+    #[test]
+    fn test_serde_names() {
+        assert_eq!(
+            serde_json::to_string(&Ruleset::MasterY).unwrap(),
+            "\"master_y\""
+        );
+    }
+}
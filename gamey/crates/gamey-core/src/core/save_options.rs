@@ -0,0 +1,132 @@
+use crate::{GameYError, Result};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Configuration options for saving a game to a file.
+///
+/// Saves are always atomic: the game is written to a temporary file next to
+/// the target, which is then renamed over it, so a crash leaves either the
+/// previous save or the new one, never a truncated file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SaveOptions {
+    /// If true, keep the previous save next to the new one, with `.bak`
+    /// appended to its name.
+    pub backup: bool,
+    /// If true, flush the file and its directory to disk before returning,
+    /// so the save survives a power loss.
+    pub durable: bool,
+}
+
+impl SaveOptions {
+    /// Sets whether the previous save is kept as a `.bak` file.
+    pub fn with_backup(mut self, backup: bool) -> Self {
+        self.backup = backup;
+        self
+    }
+
+    /// Sets whether the save is flushed to disk before returning.
+    pub fn with_durable(mut self, durable: bool) -> Self {
+        self.durable = durable;
+        self
+    }
+
+    /// Returns the file the previous save of `path` is kept in.
+    pub fn backup_path(path: &Path) -> PathBuf {
+        let mut name = path.as_os_str().to_owned();
+        name.push(".bak");
+        PathBuf::from(name)
+    }
+
+    /// Writes `contents` to `path` atomically, as configured.
+    pub(crate) fn write(&self, path: &Path, contents: &[u8]) -> Result<()> {
+        let filename = path.display().to_string();
+        let failed = |e: std::io::Error| GameYError::IoError {
+            message: format!("Failed to write file: {}", filename),
+            error: e.to_string(),
+        };
+        let dir = match path.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let name = path.file_name().ok_or_else(|| {
+            failed(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "not a file name",
+            ))
+        })?;
+        let mut temp_name = std::ffi::OsString::from(".");
+        temp_name.push(name);
+        temp_name.push(format!(".{}.tmp", std::process::id()));
+        let temp = dir.join(temp_name);
+
+        let result = self.replace(path, &temp, dir, contents);
+        if result.is_err() {
+            let _ = fs::remove_file(&temp);
+        }
+        result.map_err(failed)
+    }
+
+    fn replace(
+        &self,
+        path: &Path,
+        temp: &Path,
+        dir: &Path,
+        contents: &[u8],
+    ) -> std::io::Result<()> {
+        let mut file = File::create(temp)?;
+        file.write_all(contents)?;
+        if self.durable {
+            file.sync_all()?;
+        }
+        drop(file);
+
+        if self.backup && path.is_file() {
+            let backup = Self::backup_path(path);
+            fs::copy(path, &backup)?;
+            if self.durable {
+                File::open(&backup)?.sync_all()?;
+            }
+        }
+        fs::rename(temp, path)?;
+        if self.durable {
+            sync_dir(dir)?;
+        }
+        Ok(())
+    }
+}
+
+/// Flushes the entries of `dir`, so that a rename in it is on disk.
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> std::io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+/// Directories cannot be opened as files on this platform; the rename is
+/// flushed with the file system.
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_options() {
+        let options = SaveOptions::default();
+        assert!(!options.backup);
+        assert!(!options.durable);
+        let options = options.with_backup(true).with_durable(true);
+        assert!(options.backup && options.durable);
+    }
+
+    #[test]
+    fn test_backup_path() {
+        assert_eq!(
+            SaveOptions::backup_path(Path::new("games/game.yen")),
+            PathBuf::from("games/game.yen.bak")
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// One of the three goal sides of the board.
+///
+/// On a Y board side A is where x = 0, side B where y = 0 and side C where
+/// z = 0. On a Hex board sides A and B are the two edges the player has to
+/// connect, and side C is reported as touched everywhere, see
+/// [`BoardTopology::sides_touched`](crate::BoardTopology::sides_touched).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Side {
+    /// The side where x = 0.
+    A,
+    /// The side where y = 0.
+    B,
+    /// The side where z = 0.
+    C,
+}
+
+impl Side {
+    /// Returns the three sides in order.
+    pub fn all() -> [Side; 3] {
+        [Side::A, Side::B, Side::C]
+    }
+
+    /// Returns the position of the side in the arrays returned by
+    /// [`BoardTopology::sides_touched`](crate::BoardTopology::sides_touched).
+    pub fn index(&self) -> usize {
+        match self {
+            Side::A => 0,
+            Side::B => 1,
+            Side::C => 2,
+        }
+    }
+}
+
+impl Display for Side {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Side::A => write!(f, "A"),
+            Side::B => write!(f, "B"),
+            Side::C => write!(f, "C"),
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use crate::{BoardTopology, GameY};
+use std::fmt::Write;
+
+/// The distance from the center of a cell to its corners, in SVG units.
+const CELL_RADIUS: f64 = 20.0;
+
+/// The colors of the stones of the first and the second player, as in the
+/// terminal.
+const STONE_COLORS: [&str; 2] = ["#2a5db0", "#c0392b"];
+
+impl GameY {
+    /// Returns the board as a standalone SVG image: a hexagon per cell, the
+    /// stones of the first player in blue and those of the second in red,
+    /// and the last placement ringed.
+    ///
+    /// The image is drawn like [`GameY::render`] with the default options,
+    /// so it can be embedded where text cannot, e.g. in an `<img>` element.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let mut game = GameY::new(3);
+    /// game.play_move_list("4").unwrap();
+    /// let svg = game.render_svg();
+    /// assert!(svg.starts_with("<svg"));
+    /// assert_eq!(svg.matches("<polygon").count(), 6);
+    /// assert_eq!(svg.matches("<circle").count(), 1);
+    /// ```
+    pub fn render_svg(&self) -> String {
+        let size = self.board_size();
+        let dx = CELL_RADIUS * 3f64.sqrt();
+        let dy = CELL_RADIUS * 1.5;
+        let last = self.last_placement();
+
+        let mut cells = String::new();
+        let (mut width, mut height) = (0.0f64, 0.0f64);
+        for (row, coords_of_row) in self.topology().rows(size).iter().enumerate() {
+            // The triangle widens downwards while the rhombus leans right
+            let shift = match self.topology() {
+                BoardTopology::Y => (size - 1 - row as u32) as f64 / 2.0,
+                BoardTopology::Hex => row as f64 / 2.0,
+            };
+            for (column, &coords) in coords_of_row.iter().enumerate() {
+                let cx = CELL_RADIUS + dx * (column as f64 + shift);
+                let cy = CELL_RADIUS + dy * row as f64;
+                let fill = if self.is_blocked(coords) {
+                    "#777777"
+                } else {
+                    "#e8dcc0"
+                };
+                let _ = writeln!(
+                    cells,
+                    r##"  <polygon points="{}" fill="{}" stroke="#8a7a5c"/>"##,
+                    hexagon(cx, cy),
+                    fill
+                );
+                if let Some((_, player)) = self.stone_at(coords) {
+                    let color = STONE_COLORS[player.id() as usize % STONE_COLORS.len()];
+                    let ring = if last == Some(coords) {
+                        r##" stroke="#f4d03f" stroke-width="3""##
+                    } else {
+                        ""
+                    };
+                    let _ = writeln!(
+                        cells,
+                        r#"  <circle cx="{:.1}" cy="{:.1}" r="{:.1}" fill="{}"{}/>"#,
+                        cx,
+                        cy,
+                        CELL_RADIUS * 0.7,
+                        color,
+                        ring
+                    );
+                }
+                width = width.max(cx + CELL_RADIUS * 2.0);
+                height = height.max(cy + CELL_RADIUS * 2.0);
+            }
+        }
+
+        let mut svg = String::new();
+        let _ = writeln!(
+            svg,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0:.0}" height="{1:.0}" viewBox="0 0 {0:.0} {1:.0}">"#,
+            width.ceil(),
+            height.ceil()
+        );
+        svg.push_str(&cells);
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+/// Returns the corners of the pointy-topped hexagon centered at `(cx, cy)`,
+/// as the points of an SVG polygon.
+fn hexagon(cx: f64, cy: f64) -> String {
+    (0..6)
+        .map(|corner| {
+            let angle = std::f64::consts::PI / 6.0 + corner as f64 * std::f64::consts::PI / 3.0;
+            format!(
+                "{:.1},{:.1}",
+                cx + CELL_RADIUS * angle.cos(),
+                cy + CELL_RADIUS * angle.sin()
+            )
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ruleset;
+
+    #[test]
+    fn test_render_svg_draws_every_cell_and_stone() {
+        let mut game = GameY::with_topology(3, BoardTopology::Hex);
+        game.play_move_list("0 4").unwrap();
+        let svg = game.render_svg();
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg""#));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polygon").count(), 9);
+        assert_eq!(svg.matches(STONE_COLORS[0]).count(), 1);
+        assert_eq!(svg.matches(STONE_COLORS[1]).count(), 1);
+        // Only the last placement is ringed
+        assert_eq!(svg.matches("stroke-width").count(), 1);
+        let ringed = svg.lines().find(|line| line.contains("stroke-width"));
+        assert!(ringed.unwrap().contains(STONE_COLORS[1]));
+    }
+
+    #[test]
+    fn test_render_svg_greys_blocked_cells() {
+        let game = GameY::with_ruleset(4, Ruleset::MasterY);
+        let svg = game.render_svg();
+        assert_eq!(svg.matches("<polygon").count(), 10);
+        assert!(svg.contains("#777777"));
+        assert!(!svg.contains("<circle"));
+    }
+}
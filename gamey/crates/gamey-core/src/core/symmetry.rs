@@ -0,0 +1,266 @@
+use crate::{BoardTopology, Coordinates, GameY, GameYError, Movement, Result};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A rotation or reflection of the board that maps every position to an
+/// equivalent one.
+///
+/// A Y board has six symmetries, the rotations and reflections of the
+/// triangle, which permute the coordinates of every cell. A Hex board only
+/// has the half-turn, since the other symmetries of the rhombus swap the
+/// sides the players connect. [`Symmetry::Identity`] applies to both.
+///
+/// Symmetries let records be normalized, training data be augmented and
+/// the board be shown from another side.
+///
+/// # Example
+/// ```
+/// use gamey_core::{Coordinates, Symmetry};
+///
+/// // The top corner of a board of size 5 goes to the bottom right one
+/// let top = Coordinates::new(4, 0, 0);
+/// assert_eq!(
+///     Symmetry::RotateClockwise.apply(top, 5),
+///     Coordinates::new(0, 4, 0)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Symmetry {
+    /// Leaves every cell in place.
+    #[default]
+    Identity,
+    /// Turns a Y board a third of a turn clockwise, so the top corner goes
+    /// to the bottom right.
+    RotateClockwise,
+    /// Turns a Y board a third of a turn counterclockwise, so the top
+    /// corner goes to the bottom left.
+    RotateCounterclockwise,
+    /// Mirrors a Y board about the axis through its top corner, swapping
+    /// left and right.
+    ReflectTop,
+    /// Mirrors a Y board about the axis through its bottom left corner.
+    ReflectLeft,
+    /// Mirrors a Y board about the axis through its bottom right corner.
+    ReflectRight,
+    /// Turns a Hex board half a turn.
+    HalfTurn,
+}
+
+impl Symmetry {
+    /// Returns the symmetries of a board of the given topology, starting
+    /// with [`Symmetry::Identity`].
+    pub fn of(topology: BoardTopology) -> &'static [Symmetry] {
+        match topology {
+            BoardTopology::Y => &[
+                Symmetry::Identity,
+                Symmetry::RotateClockwise,
+                Symmetry::RotateCounterclockwise,
+                Symmetry::ReflectTop,
+                Symmetry::ReflectLeft,
+                Symmetry::ReflectRight,
+            ],
+            BoardTopology::Hex => &[Symmetry::Identity, Symmetry::HalfTurn],
+        }
+    }
+
+    /// Returns true if the symmetry maps a board of the given topology onto
+    /// itself.
+    pub fn applies_to(&self, topology: BoardTopology) -> bool {
+        Symmetry::of(topology).contains(self)
+    }
+
+    /// Returns the symmetry undoing this one.
+    pub fn inverse(&self) -> Symmetry {
+        match self {
+            Symmetry::RotateClockwise => Symmetry::RotateCounterclockwise,
+            Symmetry::RotateCounterclockwise => Symmetry::RotateClockwise,
+            other => *other,
+        }
+    }
+
+    /// Returns the cell `coords` goes to on a board of size `board_size`.
+    ///
+    /// The coordinates must be those of a board the symmetry applies to,
+    /// see [`Symmetry::applies_to`].
+    pub fn apply(&self, coords: Coordinates, board_size: u32) -> Coordinates {
+        let (x, y, z) = (coords.x(), coords.y(), coords.z());
+        match self {
+            Symmetry::Identity => coords,
+            Symmetry::RotateClockwise => Coordinates::new(z, x, y),
+            Symmetry::RotateCounterclockwise => Coordinates::new(y, z, x),
+            Symmetry::ReflectTop => Coordinates::new(x, z, y),
+            Symmetry::ReflectLeft => Coordinates::new(y, x, z),
+            Symmetry::ReflectRight => Coordinates::new(z, y, x),
+            Symmetry::HalfTurn => {
+                let last = board_size.saturating_sub(1);
+                Coordinates::new(last - x, last - y, 0)
+            }
+        }
+    }
+}
+
+impl Display for Symmetry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Symmetry::Identity => write!(f, "identity"),
+            Symmetry::RotateClockwise => write!(f, "rotate-clockwise"),
+            Symmetry::RotateCounterclockwise => write!(f, "rotate-counterclockwise"),
+            Symmetry::ReflectTop => write!(f, "reflect-top"),
+            Symmetry::ReflectLeft => write!(f, "reflect-left"),
+            Symmetry::ReflectRight => write!(f, "reflect-right"),
+            Symmetry::HalfTurn => write!(f, "half-turn"),
+        }
+    }
+}
+
+impl FromStr for Symmetry {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.replace('_', "-").as_str() {
+            "identity" => Ok(Symmetry::Identity),
+            "rotate-clockwise" => Ok(Symmetry::RotateClockwise),
+            "rotate-counterclockwise" => Ok(Symmetry::RotateCounterclockwise),
+            "reflect-top" => Ok(Symmetry::ReflectTop),
+            "reflect-left" => Ok(Symmetry::ReflectLeft),
+            "reflect-right" => Ok(Symmetry::ReflectRight),
+            "half-turn" => Ok(Symmetry::HalfTurn),
+            _ => Err(format!(
+                "Unknown symmetry '{}', expected one of: identity, rotate-clockwise, \
+                 rotate-counterclockwise, reflect-top, reflect-left, reflect-right, half-turn",
+                s
+            )),
+        }
+    }
+}
+
+impl Movement {
+    /// Returns the move with its cell moved by `symmetry` on a board of
+    /// size `board_size`; actions are returned unchanged.
+    pub fn transformed(&self, symmetry: Symmetry, board_size: u32) -> Movement {
+        match self {
+            Movement::Placement { player, coords } => Movement::Placement {
+                player: *player,
+                coords: symmetry.apply(*coords, board_size),
+            },
+            Movement::Action { .. } => self.clone(),
+        }
+    }
+}
+
+impl GameY {
+    /// Returns the game with every stone moved by `symmetry`: the handicap
+    /// and the history are replayed transformed on a fresh board with the
+    /// same variant, keeping the annotations and the metadata.
+    ///
+    /// # Errors
+    /// Returns `GameYError::UnsupportedSymmetry` if the symmetry does not
+    /// apply to the board of the game.
+    pub fn transformed(&self, symmetry: Symmetry) -> Result<GameY> {
+        if !symmetry.applies_to(self.topology()) {
+            return Err(GameYError::UnsupportedSymmetry {
+                symmetry,
+                topology: self.topology(),
+            });
+        }
+        let size = self.board_size();
+        let handicap: Vec<Coordinates> = self
+            .handicap()
+            .iter()
+            .map(|&coords| symmetry.apply(coords, size))
+            .collect();
+        let mut game =
+            GameY::with_variant(size, self.topology(), self.ruleset()).with_handicap(&handicap)?;
+        game.replay(
+            self.history()
+                .iter()
+                .map(|movement| movement.transformed(symmetry, size)),
+            self.annotations().iter().cloned(),
+        )?;
+        game.set_metadata(self.metadata().clone());
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameStatus, PlayerId, Ruleset};
+
+    #[test]
+    fn test_symmetries_map_the_board_onto_itself() {
+        for topology in BoardTopology::all() {
+            let size = 5;
+            for &symmetry in Symmetry::of(topology) {
+                let mut cells: Vec<u32> = topology
+                    .rows(size)
+                    .into_iter()
+                    .flatten()
+                    .map(|coords| {
+                        let image = symmetry.apply(coords, size);
+                        assert!(topology.contains(image, size), "{}", symmetry);
+                        assert_eq!(symmetry.inverse().apply(image, size), coords);
+                        topology.to_index(image, size)
+                    })
+                    .collect();
+                cells.sort_unstable();
+                cells.dedup();
+                assert_eq!(cells.len() as u32, topology.cell_count(size));
+            }
+        }
+    }
+
+    #[test]
+    fn test_transformed_game_is_equivalent() {
+        let mut game = GameY::with_ruleset(5, Ruleset::MasterY);
+        game.play_move_list("4 7 12 swap").unwrap();
+        game.annotate("after the swap");
+        for &symmetry in Symmetry::of(BoardTopology::Y) {
+            let transformed = game.transformed(symmetry).unwrap();
+            assert_eq!(transformed.canonical_hash(), game.canonical_hash());
+            assert_eq!(transformed.history().len(), 4);
+            assert_eq!(transformed.annotations(), game.annotations());
+            assert_eq!(transformed.next_player(), game.next_player());
+            assert_eq!(
+                transformed
+                    .transformed(symmetry.inverse())
+                    .unwrap()
+                    .history(),
+                game.history()
+            );
+        }
+        let mirrored = game.transformed(Symmetry::ReflectTop).unwrap();
+        assert_eq!(
+            mirrored.history()[0],
+            game.history()[0].transformed(Symmetry::ReflectTop, 5)
+        );
+    }
+
+    #[test]
+    fn test_transformed_hex_game_keeps_the_winner() {
+        let mut game = GameY::with_topology(2, BoardTopology::Hex);
+        game.play_move_list("0 1 2").unwrap();
+        let turned = game.transformed(Symmetry::HalfTurn).unwrap();
+        assert!(matches!(
+            turned.status(),
+            GameStatus::Finished { winner } if *winner == PlayerId::new(0)
+        ));
+        assert!(matches!(
+            game.transformed(Symmetry::ReflectTop),
+            Err(GameYError::UnsupportedSymmetry { .. })
+        ));
+    }
+
+    #[test]
+    fn test_display_and_parse_roundtrip() {
+        for topology in BoardTopology::all() {
+            for &symmetry in Symmetry::of(topology) {
+                assert_eq!(symmetry.to_string().parse::<Symmetry>(), Ok(symmetry));
+            }
+        }
+        assert_eq!("half_turn".parse::<Symmetry>(), Ok(Symmetry::HalfTurn));
+        assert!("mirror".parse::<Symmetry>().is_err());
+    }
+}
@@ -0,0 +1,345 @@
+use crate::{Coordinates, GameYError, PlayerId};
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The largest board size a game can be created with.
+///
+/// A Hex board of this size has 1,048,576 cells and a Y board 524,800, far
+/// below the `u32` cell indices, so the arithmetic on indices cannot
+/// overflow and the cell tables of a game stay within a few tens of
+/// megabytes. See [`GameY::check_board_size`](crate::GameY::check_board_size).
+pub const MAX_BOARD_SIZE: u32 = 1024;
+
+/// The shape of the board and the connection goal played on it.
+///
+/// Both games are won by connecting sides of the board with a single chain
+/// of stones, so [`GameY`](crate::GameY) only needs the topology to tell it
+/// which cells exist, which cells are adjacent and which sides each cell
+/// touches.
+///
+/// Cells are always identified with [`Coordinates`]:
+/// - On a Y board they are the barycentric coordinates `(x, y, z)`.
+/// - On a Hex board they are `(row, column, 0)`, with rows counted from the
+///   top and columns from the left.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BoardTopology {
+    /// The triangular board of Y: a player wins by connecting all three sides.
+    #[default]
+    Y,
+    /// The rhombic board of Hex: player 0 connects the top and bottom rows,
+    /// player 1 connects the left and right columns.
+    Hex,
+}
+
+impl BoardTopology {
+    /// Returns every supported topology.
+    pub fn all() -> [BoardTopology; 2] {
+        [BoardTopology::Y, BoardTopology::Hex]
+    }
+
+    /// Returns true for the triangular board of Y.
+    pub fn is_y(&self) -> bool {
+        *self == BoardTopology::Y
+    }
+
+    /// Returns the number of cells on a board of the given size, saturating
+    /// at `u32::MAX` for sizes whose cells cannot all be indexed.
+    pub fn cell_count(&self, board_size: u32) -> u32 {
+        self.checked_cell_count(board_size).unwrap_or(u32::MAX)
+    }
+
+    /// Returns the number of cells on a board of the given size, or `None`
+    /// if it does not fit in a `u32`.
+    pub fn checked_cell_count(&self, board_size: u32) -> Option<u32> {
+        let size = u64::from(board_size);
+        let cells = match self {
+            BoardTopology::Y => size * (size + 1) / 2,
+            BoardTopology::Hex => size * size,
+        };
+        u32::try_from(cells).ok()
+    }
+
+    /// Returns true if the coordinates denote a cell of a board of the
+    /// given size.
+    pub fn contains(&self, coords: Coordinates, board_size: u32) -> bool {
+        match self {
+            BoardTopology::Y => {
+                board_size > 0 && coords.x() + coords.y() + coords.z() == board_size - 1
+            }
+            BoardTopology::Hex => {
+                coords.x() < board_size && coords.y() < board_size && coords.z() == 0
+            }
+        }
+    }
+
+    /// Checks that the coordinates denote a cell of a board of the given
+    /// size, naming the offending component otherwise.
+    ///
+    /// Each component must be below the board size and, together, they must
+    /// add up to `board_size - 1`. On a Hex board the third component must
+    /// be zero instead.
+    pub fn check_coords(&self, coords: Coordinates, board_size: u32) -> Result<(), GameYError> {
+        let max = board_size.saturating_sub(1);
+        if *self == BoardTopology::Hex {
+            if coords.z() != 0 {
+                return Err(GameYError::CoordOutOfRange {
+                    id_coord: 'z',
+                    coord: coords.z(),
+                    board_size,
+                });
+            }
+            if board_size > 0 && self.contains(coords, board_size) {
+                return Ok(());
+            }
+        }
+        for (id_coord, coord) in [('x', coords.x()), ('y', coords.y()), ('z', coords.z())] {
+            if coord > max {
+                return Err(GameYError::CoordOutOfRange {
+                    id_coord,
+                    coord,
+                    board_size,
+                });
+            }
+        }
+        if coords.x() + coords.y() + coords.z() != max {
+            return Err(GameYError::CoordOutOfRange {
+                id_coord: 'z',
+                coord: coords.z(),
+                board_size,
+            });
+        }
+        Ok(())
+    }
+
+    /// Converts a linear cell index into coordinates.
+    ///
+    /// Indices follow row-major order starting from the top of the board.
+    pub fn from_index(&self, index: u32, board_size: u32) -> Coordinates {
+        match self {
+            BoardTopology::Y => Coordinates::from_index(index, board_size),
+            BoardTopology::Hex => Coordinates::new(index / board_size, index % board_size, 0),
+        }
+    }
+
+    /// Converts coordinates into a linear cell index.
+    ///
+    /// This is the inverse of [`BoardTopology::from_index`].
+    pub fn to_index(&self, coords: Coordinates, board_size: u32) -> u32 {
+        match self {
+            BoardTopology::Y => coords.to_index(board_size),
+            BoardTopology::Hex => coords.x() * board_size + coords.y(),
+        }
+    }
+
+    /// Returns the cells of each row of the board, from top to bottom.
+    pub fn rows(&self, board_size: u32) -> Vec<Vec<Coordinates>> {
+        match self {
+            BoardTopology::Y => (0..board_size)
+                .map(|row| {
+                    let x = board_size - 1 - row;
+                    (0..=row).map(|y| Coordinates::new(x, y, row - y)).collect()
+                })
+                .collect(),
+            BoardTopology::Hex => (0..board_size)
+                .map(|row| {
+                    (0..board_size)
+                        .map(|col| Coordinates::new(row, col, 0))
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    /// Returns the cells adjacent to the given one, in cell index order.
+    pub fn neighbors(&self, coords: Coordinates, board_size: u32) -> Vec<Coordinates> {
+        let mut neighbors = Vec::new();
+        let x = coords.x();
+        let y = coords.y();
+        let z = coords.z();
+        match self {
+            BoardTopology::Y => {
+                if x > 0 {
+                    neighbors.push(Coordinates::new(x - 1, y + 1, z));
+                    neighbors.push(Coordinates::new(x - 1, y, z + 1));
+                }
+                if y > 0 {
+                    neighbors.push(Coordinates::new(x + 1, y - 1, z));
+                    neighbors.push(Coordinates::new(x, y - 1, z + 1));
+                }
+                if z > 0 {
+                    neighbors.push(Coordinates::new(x + 1, y, z - 1));
+                    neighbors.push(Coordinates::new(x, y + 1, z - 1));
+                }
+            }
+            BoardTopology::Hex => {
+                let last = board_size.saturating_sub(1);
+                if x > 0 {
+                    neighbors.push(Coordinates::new(x - 1, y, 0));
+                    if y < last {
+                        neighbors.push(Coordinates::new(x - 1, y + 1, 0));
+                    }
+                }
+                if y > 0 {
+                    neighbors.push(Coordinates::new(x, y - 1, 0));
+                }
+                if y < last {
+                    neighbors.push(Coordinates::new(x, y + 1, 0));
+                }
+                if x < last {
+                    neighbors.push(Coordinates::new(x + 1, y, 0));
+                    if y > 0 {
+                        neighbors.push(Coordinates::new(x + 1, y - 1, 0));
+                    }
+                }
+            }
+        }
+        neighbors.sort_by_key(|&neighbor| self.to_index(neighbor, board_size));
+        neighbors
+    }
+
+    /// Returns which of the three goal sides a stone of `player` on the
+    /// given cell touches.
+    ///
+    /// A chain wins once it touches all three. Hex only has two goal sides
+    /// per player, so the third one is always reported as touched.
+    pub fn sides_touched(
+        &self,
+        coords: Coordinates,
+        board_size: u32,
+        player: PlayerId,
+    ) -> [bool; 3] {
+        match self {
+            BoardTopology::Y => [
+                coords.touches_side_a(),
+                coords.touches_side_b(),
+                coords.touches_side_c(),
+            ],
+            BoardTopology::Hex => {
+                let last = board_size.saturating_sub(1);
+                let line = if player.id() == 0 {
+                    coords.x()
+                } else {
+                    coords.y()
+                };
+                [line == 0, line == last, true]
+            }
+        }
+    }
+}
+
+impl Display for BoardTopology {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BoardTopology::Y => write!(f, "y"),
+            BoardTopology::Hex => write!(f, "hex"),
+        }
+    }
+}
+
+impl FromStr for BoardTopology {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "y" => Ok(BoardTopology::Y),
+            "hex" => Ok(BoardTopology::Hex),
+            _ => Err(format!("Unknown game '{}', expected one of: y, hex", s)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cell_count() {
+        assert_eq!(BoardTopology::Y.cell_count(4), 10);
+        assert_eq!(BoardTopology::Hex.cell_count(4), 16);
+    }
+
+    #[test]
+    fn test_cell_count_of_huge_boards() {
+        assert_eq!(BoardTopology::Y.cell_count(MAX_BOARD_SIZE), 524_800);
+        assert_eq!(BoardTopology::Hex.cell_count(MAX_BOARD_SIZE), 1_048_576);
+        // The largest boards whose cells can be indexed, and the next ones
+        assert_eq!(
+            BoardTopology::Y.checked_cell_count(92_681),
+            Some(4_294_930_221)
+        );
+        assert_eq!(BoardTopology::Y.checked_cell_count(92_682), None);
+        assert_eq!(
+            BoardTopology::Hex.checked_cell_count(65_535),
+            Some(4_294_836_225)
+        );
+        assert_eq!(BoardTopology::Hex.checked_cell_count(65_536), None);
+        assert_eq!(BoardTopology::Hex.cell_count(u32::MAX), u32::MAX);
+    }
+
+    #[test]
+    fn test_hex_index_roundtrip() {
+        let hex = BoardTopology::Hex;
+        for idx in 0..hex.cell_count(5) {
+            let coords = hex.from_index(idx, 5);
+            assert!(hex.contains(coords, 5));
+            assert_eq!(hex.to_index(coords, 5), idx);
+        }
+    }
+
+    #[test]
+    fn test_hex_neighbors() {
+        let hex = BoardTopology::Hex;
+        assert_eq!(hex.neighbors(Coordinates::new(2, 2, 0), 5).len(), 6);
+        assert_eq!(hex.neighbors(Coordinates::new(0, 0, 0), 5).len(), 2);
+        assert_eq!(hex.neighbors(Coordinates::new(0, 4, 0), 5).len(), 3);
+    }
+
+    #[test]
+    fn test_neighbors_are_in_index_order() {
+        for topology in [BoardTopology::Y, BoardTopology::Hex] {
+            for idx in 0..topology.cell_count(5) {
+                let coords = topology.from_index(idx, 5);
+                let indices: Vec<u32> = topology
+                    .neighbors(coords, 5)
+                    .into_iter()
+                    .map(|c| topology.to_index(c, 5))
+                    .collect();
+                assert!(indices.is_sorted(), "{} {}: {:?}", topology, idx, indices);
+            }
+        }
+    }
+
+    #[test]
+    fn test_hex_sides_depend_on_player() {
+        let hex = BoardTopology::Hex;
+        let top_left = Coordinates::new(0, 0, 0);
+        assert_eq!(
+            hex.sides_touched(top_left, 3, PlayerId::new(0)),
+            [true, false, true]
+        );
+        let bottom_right = Coordinates::new(2, 2, 0);
+        assert_eq!(
+            hex.sides_touched(bottom_right, 3, PlayerId::new(1)),
+            [false, true, true]
+        );
+    }
+
+    #[test]
+    fn test_y_rows_match_indices() {
+        let y = BoardTopology::Y;
+        let cells: Vec<Coordinates> = y.rows(4).into_iter().flatten().collect();
+        for (idx, coords) in cells.iter().enumerate() {
+            assert_eq!(y.to_index(*coords, 4), idx as u32);
+        }
+    }
+
+    #[test]
+    fn test_display_and_parse_roundtrip() {
+        for topology in BoardTopology::all() {
+            assert_eq!(topology.to_string().parse::<BoardTopology>(), Ok(topology));
+        }
+        assert!("go".parse::<BoardTopology>().is_err());
+    }
+}
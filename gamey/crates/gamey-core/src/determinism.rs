@@ -0,0 +1,268 @@
+//! Reproducible randomness.
+//!
+//! Everything random in gamey, from the moves of the bots to the playouts
+//! of the analyses, draws from [`rng`]. By default that is the thread-local
+//! generator of `rand`, seeded from the operating system. While a
+//! [`DeterministicConfig`] is installed, every thread draws instead from its
+//! own stream, derived from the seed, so that a run repeated with the same
+//! seed makes the same choices:
+//! - the first thread to draw gets stream 0, the next one stream 1, and so
+//!   on, so runs that start threads in a fixed order are reproducible;
+//! - [`reseed`] restarts the stream of the current thread at a given
+//!   number, so that a piece of work such as a tournament game or the
+//!   playouts of one move gives the same result whatever ran before it and
+//!   on whichever thread;
+//! - [`worker_threads`] gives the fixed thread count of the configuration to
+//!   code that would otherwise use every core.
+//!
+//! Bots running in other processes and searches bounded by time rather
+//! than iterations are not covered.
+//!
+//! # Example
+//! ```
+//! use gamey_core::{DeterministicConfig, rng, reseed};
+//! use rand::Rng;
+//!
+//! let config = DeterministicConfig::new(42).install();
+//! reseed(7);
+//! let first: u32 = rng().random();
+//! reseed(7);
+//! assert_eq!(rng().random::<u32>(), first);
+//! drop(config);
+//! assert_eq!(DeterministicConfig::current(), None);
+//! ```
+
+use crate::core::hash::splitmix64;
+use rand::rngs::{StdRng, ThreadRng};
+use rand::{RngCore, SeedableRng};
+use std::cell::RefCell;
+use std::sync::RwLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// How many times a configuration was installed or restored, so that
+/// threads notice a new one, and the installed configuration.
+static CONFIG: RwLock<(u64, Option<DeterministicConfig>)> = RwLock::new((0, None));
+
+/// The stream of the next thread to draw under the installed configuration.
+static NEXT_STREAM: AtomicU64 = AtomicU64::new(0);
+
+thread_local! {
+    /// The installation the stream of the thread was derived from, and the
+    /// stream.
+    static STREAM: RefCell<Option<(u64, StdRng)>> = const { RefCell::new(None) };
+}
+
+/// Settings that make runs reproducible.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DeterministicConfig {
+    /// The seed every random stream is derived from.
+    pub seed: u64,
+    /// The number of threads parallel work is split into.
+    pub threads: usize,
+}
+
+impl DeterministicConfig {
+    /// Creates a configuration with `seed` and a single thread.
+    pub fn new(seed: u64) -> Self {
+        DeterministicConfig { seed, threads: 1 }
+    }
+
+    /// Sets the number of threads parallel work is split into; at least 1.
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Makes the whole process draw from streams derived from the seed
+    /// until the returned guard is dropped, which restores the
+    /// configuration installed before, if any. Streams are handed out from
+    /// 0 again on both occasions.
+    pub fn install(self) -> DeterminismGuard {
+        DeterminismGuard {
+            previous: replace(Some(self)),
+        }
+    }
+
+    /// Returns the installed configuration, if any.
+    pub fn current() -> Option<Self> {
+        installed().map(|(_, config)| config)
+    }
+}
+
+/// Keeps a [`DeterministicConfig`] installed; dropping it restores the
+/// configuration installed before.
+#[must_use = "the configuration is uninstalled when the guard is dropped"]
+#[derive(Debug)]
+pub struct DeterminismGuard {
+    previous: Option<DeterministicConfig>,
+}
+
+impl Drop for DeterminismGuard {
+    fn drop(&mut self) {
+        replace(self.previous);
+    }
+}
+
+/// Installs `config` in place of the current configuration, which it
+/// returns.
+fn replace(config: Option<DeterministicConfig>) -> Option<DeterministicConfig> {
+    let mut installed = CONFIG.write().unwrap_or_else(|e| e.into_inner());
+    let previous = installed.1;
+    *installed = (installed.0 + 1, config);
+    NEXT_STREAM.store(0, Ordering::SeqCst);
+    previous
+}
+
+fn installed() -> Option<(u64, DeterministicConfig)> {
+    let (generation, config) = *CONFIG.read().unwrap_or_else(|e| e.into_inner());
+    config.map(|config| (generation, config))
+}
+
+/// A source of randomness: the thread-local generator of `rand`, or a
+/// generator split off the seeded stream of the thread.
+pub enum GameRng {
+    /// Seeded by the operating system.
+    Thread(ThreadRng),
+    /// Derived from the seed of the [`DeterministicConfig`].
+    Seeded(Box<StdRng>),
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        match self {
+            GameRng::Thread(rng) => rng.next_u32(),
+            GameRng::Seeded(rng) => rng.next_u32(),
+        }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match self {
+            GameRng::Thread(rng) => rng.next_u64(),
+            GameRng::Seeded(rng) => rng.next_u64(),
+        }
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        match self {
+            GameRng::Thread(rng) => rng.fill_bytes(dest),
+            GameRng::Seeded(rng) => rng.fill_bytes(dest),
+        }
+    }
+}
+
+/// Returns a generator to draw from: the thread-local generator of `rand`,
+/// or, once a [`DeterministicConfig`] is installed, a generator seeded with
+/// the next value of the stream of the current thread.
+pub fn rng() -> GameRng {
+    let Some((generation, config)) = installed() else {
+        return GameRng::Thread(rand::rng());
+    };
+    STREAM.with_borrow_mut(|stream| {
+        if stream.as_ref().is_none_or(|(of, _)| *of != generation) {
+            let number = NEXT_STREAM.fetch_add(1, Ordering::SeqCst);
+            *stream = Some((generation, stream_rng(config.seed, number)));
+        }
+        let (_, master) = stream.as_mut().expect("the stream was just set");
+        GameRng::Seeded(Box::new(StdRng::seed_from_u64(master.next_u64())))
+    })
+}
+
+/// Restarts the stream of the current thread at stream number `stream`,
+/// if a [`DeterministicConfig`] is installed; does nothing otherwise.
+///
+/// Work that reseeds before it starts draws the same values whatever was
+/// drawn before it, e.g. when a tournament is resumed halfway.
+pub fn reseed(stream: u64) {
+    let Some((generation, config)) = installed() else {
+        return;
+    };
+    STREAM.with_borrow_mut(|current| {
+        // Keep the streams handed out to new threads apart from these
+        *current = Some((generation, stream_rng(config.seed ^ u64::MAX, stream)));
+    });
+}
+
+/// Returns the number of threads to split parallel work into: the
+/// threads of the installed [`DeterministicConfig`], or else the available
+/// parallelism.
+pub fn worker_threads() -> usize {
+    match DeterministicConfig::current() {
+        Some(config) => config.threads,
+        None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+    }
+}
+
+fn stream_rng(seed: u64, stream: u64) -> StdRng {
+    StdRng::seed_from_u64(splitmix64(seed ^ splitmix64(stream)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GameY;
+    use rand::Rng;
+    use std::sync::{Mutex, MutexGuard};
+
+    /// Keeps the tests that install a configuration, which is global, from
+    /// running at the same time.
+    fn lock() -> MutexGuard<'static, ()> {
+        static LOCK: Mutex<()> = Mutex::new(());
+        LOCK.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    #[test]
+    fn test_seeded_streams_are_reproducible() {
+        let _lock = lock();
+        let _config = DeterministicConfig::new(7).with_threads(0).install();
+        assert_eq!(
+            DeterministicConfig::current(),
+            Some(DeterministicConfig {
+                seed: 7,
+                threads: 1
+            })
+        );
+        assert_eq!(worker_threads(), 1);
+
+        let playout = || GameY::new(5).random_playout(&mut rng());
+        let draws = || (0..4).map(|_| rng().random::<u64>()).collect::<Vec<_>>();
+        reseed(3);
+        let (winner, first) = (playout(), draws());
+        reseed(3);
+        assert_eq!((playout(), draws()), (winner, first.clone()));
+        reseed(4);
+        assert_ne!(draws(), first);
+
+        // A thread started under the configuration draws from its own
+        // stream, and the same one again after a new installation
+        let spawned = || std::thread::spawn(|| rng().random::<u64>()).join().unwrap();
+        let value = {
+            let _config = DeterministicConfig::new(7).install();
+            spawned()
+        };
+        let _config = DeterministicConfig::new(7).install();
+        assert_eq!(spawned(), value);
+    }
+
+    #[test]
+    fn test_dropping_the_guard_restores_the_previous_config() {
+        let _lock = lock();
+        let outer = DeterministicConfig::new(1).install();
+        {
+            let _inner = DeterministicConfig::new(2).with_threads(3).install();
+            assert_eq!(worker_threads(), 3);
+        }
+        assert_eq!(
+            DeterministicConfig::current(),
+            Some(DeterministicConfig::new(1))
+        );
+        reseed(5);
+        let first: u64 = rng().random();
+        drop(outer);
+        assert_eq!(DeterministicConfig::current(), None);
+
+        // The streams of the restored configuration start over
+        let _again = DeterministicConfig::new(1).install();
+        reseed(5);
+        assert_eq!(rng().random::<u64>(), first);
+    }
+}
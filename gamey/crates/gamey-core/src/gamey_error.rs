@@ -0,0 +1,616 @@
+//! Error types for the GameY library.
+//!
+//! This module defines all error types that can occur during game operations,
+//! including I/O errors, parsing errors, and game rule violations.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use thiserror::Error;
+
+use crate::{BoardTopology, Coordinates, Movement, PlayerId, Ruleset, Symmetry};
+
+/// Errors that can occur during Y game operations.
+///
+/// This enum covers all possible error conditions including:
+/// - File I/O and serialization errors
+/// - Invalid game state or moves
+/// - YEN format parsing errors
+#[derive(Debug, Error)]
+pub enum GameYError {
+    /// An I/O operation failed.
+    #[error("I/O error {message}: {error}")]
+    IoError {
+        /// Description of the I/O operation that failed.
+        message: String,
+        /// The underlying error message.
+        error: String,
+    },
+
+    /// JSON serialization or deserialization failed.
+    #[error("Serde JSON error: {error}")]
+    SerdeError {
+        /// The underlying serde_json error.
+        error: serde_json::Error,
+    },
+
+    /// Wrong number of coordinates provided.
+    #[error("Invalid coordinates: expected {expected} coords, found {found}")]
+    BadCoordsNumber {
+        /// Expected number of coordinates (usually 3).
+        expected: usize,
+        /// Actual number of coordinates provided.
+        found: usize,
+    },
+
+    /// A coordinate value is outside the valid range for the board.
+    #[error("Coordinate {id_coord}={coord} is out of range for board size {board_size}")]
+    CoordOutOfRange {
+        /// Which coordinate is out of range ('x', 'y', or 'z').
+        id_coord: char,
+        /// The invalid coordinate value.
+        coord: u32,
+        /// The board size that defines the valid range.
+        board_size: u32,
+    },
+
+    /// Attempted to place a piece on an already occupied cell.
+    #[error("Player {player} tries to place a stone on an occupied position: {coordinates}")]
+    Occupied {
+        /// The coordinates of the occupied cell.
+        coordinates: Coordinates,
+        /// The player who attempted the placement.
+        player: PlayerId,
+    },
+
+    /// Attempted to place a piece on a cell the ruleset keeps empty.
+    #[error("Cell {coordinates} is blocked under the {ruleset} ruleset")]
+    BlockedCell {
+        /// The coordinates of the blocked cell.
+        coordinates: Coordinates,
+        /// The ruleset blocking the cell.
+        ruleset: Ruleset,
+    },
+
+    /// Invalid character found in a YEN layout string.
+    #[error("Invalid character '{char}' in layout at row {row}, column {col}")]
+    InvalidCharInLayout {
+        /// The invalid character.
+        char: char,
+        /// Row index where the character was found.
+        row: usize,
+        /// Column index where the character was found.
+        col: usize,
+    },
+
+    /// Attempted to make a move in a finished game.
+    #[error("Attempt to play movement {movement} in a finished game")]
+    GameOver {
+        /// The movement that was attempted.
+        movement: Movement,
+    },
+
+    /// Wrong player attempted to make a move.
+    #[error("Wrong player in movement: Expected player {expected}, found player {found}")]
+    InvalidPlayerTurn {
+        /// The player who should move.
+        expected: PlayerId,
+        /// The player who attempted to move.
+        found: PlayerId,
+    },
+
+    /// A movement names a player the game does not have.
+    #[error("Unknown player {player}, a game has {players} players")]
+    UnknownPlayer {
+        /// The player named by the movement.
+        player: PlayerId,
+        /// The number of players of the game.
+        players: u32,
+    },
+
+    /// Invalid number of players specified.
+    #[error("Invalid number of players: {num_players}, expected {expected}")]
+    InvalidNumPlayers {
+        /// The invalid number of players.
+        num_players: u32,
+        /// The expected number of players.
+        expected: u32,
+    },
+
+    /// YEN layout has wrong number of rows.
+    #[error("Invalid YEN layout: expected {expected} rows, found {found} rows")]
+    InvalidYENLayout {
+        /// Expected number of rows.
+        expected: u32,
+        /// Actual number of rows found.
+        found: u32,
+    },
+
+    /// A specific line in the YEN layout has wrong number of cells.
+    #[error("Invalid YEN layout line: expected {expected} rows, found {found} rows at line {line}")]
+    InvalidYENLayoutLine {
+        /// Expected number of cells in the line.
+        expected: u32,
+        /// Actual number of cells found.
+        found: u32,
+        /// The line number with the error.
+        line: u32,
+    },
+
+    /// The board size is not valid for a game.
+    #[error("Invalid board size: {size}")]
+    InvalidBoardSize {
+        /// The rejected board size.
+        size: u32,
+    },
+
+    /// The requested number of handicap stones cannot be placed.
+    #[error("Invalid handicap: {stones} stones requested, at most {max} supported on board size {board_size}")]
+    InvalidHandicap {
+        /// The number of stones requested.
+        stones: u32,
+        /// The board size.
+        board_size: u32,
+        /// The maximum number of stones for this board size.
+        max: u32,
+    },
+
+    /// A game action name could not be recognized.
+    #[error("Unknown game action: {action}")]
+    UnknownAction {
+        /// The unrecognized action name.
+        action: String,
+    },
+
+    /// No hosted game exists with the given identifier.
+    #[error("Game not found: {id}")]
+    GameNotFound {
+        /// The identifier that was looked up.
+        id: String,
+    },
+
+    /// No bot session exists with the given identifier.
+    #[error("Session not found: {id}")]
+    SessionNotFound {
+        /// The identifier that was looked up.
+        id: String,
+    },
+
+    /// A saved document was written with a format version this crate cannot read.
+    #[error("Unsupported {format} version {found}: this build reads versions up to {supported}")]
+    UnsupportedVersion {
+        /// The name of the format, e.g. "YEN".
+        format: String,
+        /// The version found in the document.
+        found: u32,
+        /// The newest version this build supports.
+        supported: u32,
+    },
+
+    /// An ASCII board diagram could not be parsed.
+    #[error("Invalid board diagram at line {line}: {message}")]
+    InvalidDiagram {
+        /// The 1-based line of the diagram with the problem (0 if none).
+        line: usize,
+        /// What is wrong with the diagram.
+        message: String,
+    },
+
+    /// A saved game failed its integrity check.
+    #[error("Integrity check failed: {reason}")]
+    IntegrityCheckFailed {
+        /// Why the check failed.
+        reason: String,
+    },
+
+    /// A takeback was asked for by a player who has no move to take back.
+    #[error("Player {player} has no move to take back")]
+    NothingToTakeBack {
+        /// The player who asked for the takeback.
+        player: PlayerId,
+    },
+
+    /// A takeback was answered but none is waiting for that player.
+    #[error("No takeback request is waiting for player {player}")]
+    NoTakebackRequest {
+        /// The player who answered.
+        player: PlayerId,
+    },
+
+    /// The solver cannot evaluate the position.
+    #[error("The position cannot be solved: {reason}")]
+    UnsupportedBySolver {
+        /// Why the position is out of the solver's reach.
+        reason: String,
+    },
+
+    /// A configuration file could not be read or parsed.
+    #[error("Invalid configuration {file}: {message}")]
+    InvalidConfig {
+        /// The configuration file.
+        file: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// A game of an archive could not be read.
+    #[error("Invalid game at line {line} of archive {file}: {message}")]
+    InvalidArchive {
+        /// The archive file.
+        file: String,
+        /// The line of the game, from 1.
+        line: usize,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// A move of a move list could not be played.
+    #[error("Invalid move {number} ({token}) in move list: {message}")]
+    InvalidMoveList {
+        /// The number of the move in the list, from 1.
+        number: usize,
+        /// The move as written.
+        token: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// An SGF document could not be read as a game.
+    #[error("Invalid SGF: {message}")]
+    InvalidSgf {
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// An algebraic cell name, such as `b3`, names no cell of the board.
+    #[error("Invalid cell name '{name}': {message}")]
+    InvalidCellName {
+        /// The name as written.
+        name: String,
+        /// What is wrong with it.
+        message: String,
+    },
+
+    /// A symmetry was applied to a board it does not map onto itself.
+    #[error("The {symmetry} symmetry does not apply to a {topology} board")]
+    UnsupportedSymmetry {
+        /// The symmetry asked for.
+        symmetry: Symmetry,
+        /// The shape of the board.
+        topology: BoardTopology,
+    },
+
+    /// The server could not listen on its address.
+    #[error("Failed to bind to {address}: {message}")]
+    BindFailed {
+        /// The address the server tried to listen on.
+        address: String,
+        /// Why binding failed.
+        message: String,
+    },
+
+    /// Server operation failed.
+    #[error("Server error: {message}")]
+    ServerError {
+        /// Description of what went wrong.
+        message: String,
+    },
+}
+
+impl GameYError {
+    /// Returns the machine-readable code of the error, so clients can tell
+    /// errors apart without matching on the message.
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            GameYError::IoError { .. } => ErrorCode::IoError,
+            GameYError::SerdeError { .. } => ErrorCode::InvalidJson,
+            GameYError::BadCoordsNumber { .. }
+            | GameYError::CoordOutOfRange { .. }
+            | GameYError::InvalidCellName { .. } => ErrorCode::InvalidCoordinates,
+            GameYError::Occupied { .. } => ErrorCode::Occupied,
+            GameYError::BlockedCell { .. } => ErrorCode::BlockedCell,
+            GameYError::InvalidCharInLayout { .. }
+            | GameYError::InvalidNumPlayers { .. }
+            | GameYError::InvalidYENLayout { .. }
+            | GameYError::InvalidYENLayoutLine { .. }
+            | GameYError::InvalidArchive { .. }
+            | GameYError::InvalidMoveList { .. }
+            | GameYError::InvalidSgf { .. } => ErrorCode::InvalidYen,
+            GameYError::GameOver { .. } => ErrorCode::GameOver,
+            GameYError::InvalidPlayerTurn { .. } => ErrorCode::InvalidPlayerTurn,
+            GameYError::UnknownPlayer { .. } => ErrorCode::UnknownPlayer,
+            GameYError::InvalidBoardSize { .. } => ErrorCode::InvalidBoardSize,
+            GameYError::InvalidHandicap { .. } => ErrorCode::InvalidHandicap,
+            GameYError::UnknownAction { .. } => ErrorCode::UnknownAction,
+            GameYError::GameNotFound { .. } => ErrorCode::GameNotFound,
+            GameYError::SessionNotFound { .. } => ErrorCode::SessionNotFound,
+            GameYError::UnsupportedVersion { .. } => ErrorCode::UnsupportedVersion,
+            GameYError::InvalidDiagram { .. } => ErrorCode::InvalidDiagram,
+            GameYError::IntegrityCheckFailed { .. } => ErrorCode::IntegrityCheckFailed,
+            GameYError::NothingToTakeBack { .. } => ErrorCode::NothingToTakeBack,
+            GameYError::NoTakebackRequest { .. } => ErrorCode::NoTakebackRequest,
+            GameYError::UnsupportedBySolver { .. } => ErrorCode::UnsupportedBySolver,
+            GameYError::InvalidConfig { .. } => ErrorCode::InvalidConfig,
+            GameYError::UnsupportedSymmetry { .. } => ErrorCode::UnsupportedSymmetry,
+            GameYError::BindFailed { .. } | GameYError::ServerError { .. } => {
+                ErrorCode::ServerError
+            }
+        }
+    }
+}
+
+/// A stable, machine-readable identifier for a kind of error.
+///
+/// Every [`GameYError`] maps to a code through [`GameYError::code`]; the
+/// bot server adds a few codes for errors that only exist at the API level.
+/// Codes are serialized in `SCREAMING_SNAKE_CASE`, e.g. `"BOT_NOT_FOUND"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    /// An I/O operation failed.
+    IoError,
+    /// A document is not valid JSON or does not have the expected fields.
+    InvalidJson,
+    /// Coordinates are malformed or outside the board.
+    InvalidCoordinates,
+    /// The cell is already occupied.
+    Occupied,
+    /// The cell is blocked by the ruleset.
+    BlockedCell,
+    /// A YEN document does not describe a valid position.
+    InvalidYen,
+    /// The game is already over.
+    GameOver,
+    /// It is not this player's turn.
+    InvalidPlayerTurn,
+    /// The player is not one of the players of the game.
+    UnknownPlayer,
+    /// The board size is not valid.
+    InvalidBoardSize,
+    /// The handicap cannot be placed.
+    InvalidHandicap,
+    /// The game action is not recognized.
+    UnknownAction,
+    /// No hosted game has the given identifier.
+    GameNotFound,
+    /// No bot session has the given identifier.
+    SessionNotFound,
+    /// A document was written with a newer format version.
+    UnsupportedVersion,
+    /// An ASCII board diagram could not be parsed.
+    InvalidDiagram,
+    /// A saved game failed its integrity check.
+    IntegrityCheckFailed,
+    /// The player has no move to take back.
+    NothingToTakeBack,
+    /// No takeback request is waiting for the player.
+    NoTakebackRequest,
+    /// The solver cannot evaluate the position.
+    UnsupportedBySolver,
+    /// A configuration file is not valid.
+    InvalidConfig,
+    /// The symmetry does not apply to the board.
+    UnsupportedSymmetry,
+    /// The requested API version is not supported.
+    UnsupportedApiVersion,
+    /// No bot is registered with the given identifier.
+    BotNotFound,
+    /// The bot found no move to play.
+    NoMoves,
+    /// An unexpected error happened on the server.
+    ServerError,
+    /// The bot did not choose a move in the time allowed.
+    Timeout,
+    /// The client has too many requests in progress.
+    TooManyRequests,
+    /// The server has too much work queued to accept more.
+    ServerBusy,
+    /// The request lacks valid credentials.
+    Unauthorized,
+    /// A webhook URL is malformed or points to an address the server does
+    /// not post to.
+    InvalidWebhook,
+}
+
+impl ErrorCode {
+    /// Returns the code as written in JSON, e.g. `"BOT_NOT_FOUND"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::IoError => "IO_ERROR",
+            ErrorCode::InvalidJson => "INVALID_JSON",
+            ErrorCode::InvalidCoordinates => "INVALID_COORDINATES",
+            ErrorCode::Occupied => "OCCUPIED",
+            ErrorCode::BlockedCell => "BLOCKED_CELL",
+            ErrorCode::InvalidYen => "INVALID_YEN",
+            ErrorCode::GameOver => "GAME_OVER",
+            ErrorCode::InvalidPlayerTurn => "INVALID_PLAYER_TURN",
+            ErrorCode::UnknownPlayer => "UNKNOWN_PLAYER",
+            ErrorCode::InvalidBoardSize => "INVALID_BOARD_SIZE",
+            ErrorCode::InvalidHandicap => "INVALID_HANDICAP",
+            ErrorCode::UnknownAction => "UNKNOWN_ACTION",
+            ErrorCode::GameNotFound => "GAME_NOT_FOUND",
+            ErrorCode::SessionNotFound => "SESSION_NOT_FOUND",
+            ErrorCode::UnsupportedVersion => "UNSUPPORTED_VERSION",
+            ErrorCode::InvalidDiagram => "INVALID_DIAGRAM",
+            ErrorCode::IntegrityCheckFailed => "INTEGRITY_CHECK_FAILED",
+            ErrorCode::NothingToTakeBack => "NOTHING_TO_TAKE_BACK",
+            ErrorCode::NoTakebackRequest => "NO_TAKEBACK_REQUEST",
+            ErrorCode::UnsupportedBySolver => "UNSUPPORTED_BY_SOLVER",
+            ErrorCode::InvalidConfig => "INVALID_CONFIG",
+            ErrorCode::UnsupportedSymmetry => "UNSUPPORTED_SYMMETRY",
+            ErrorCode::UnsupportedApiVersion => "UNSUPPORTED_API_VERSION",
+            ErrorCode::BotNotFound => "BOT_NOT_FOUND",
+            ErrorCode::NoMoves => "NO_MOVES",
+            ErrorCode::ServerError => "SERVER_ERROR",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::TooManyRequests => "TOO_MANY_REQUESTS",
+            ErrorCode::ServerBusy => "SERVER_BUSY",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::InvalidWebhook => "INVALID_WEBHOOK",
+        }
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_io_error_display() {
+        let err = GameYError::IoError {
+            message: "Failed to read".to_string(),
+            error: "file not found".to_string(),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("I/O error"));
+        assert!(msg.contains("Failed to read"));
+        assert!(msg.contains("file not found"));
+    }
+
+    #[test]
+    fn test_bad_coords_number_display() {
+        let err = GameYError::BadCoordsNumber {
+            expected: 3,
+            found: 2,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("expected 3"));
+        assert!(msg.contains("found 2"));
+    }
+
+    #[test]
+    fn test_coord_out_of_range_display() {
+        let err = GameYError::CoordOutOfRange {
+            id_coord: 'x',
+            coord: 10,
+            board_size: 5,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("x=10"));
+        assert!(msg.contains("board size 5"));
+    }
+
+    #[test]
+    fn test_occupied_display() {
+        let err = GameYError::Occupied {
+            coordinates: Coordinates::new(1, 2, 3),
+            player: PlayerId::new(0),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Player 0"));
+        assert!(msg.contains("occupied"));
+    }
+
+    #[test]
+    fn test_invalid_char_in_layout_display() {
+        let err = GameYError::InvalidCharInLayout {
+            char: 'X',
+            row: 1,
+            col: 2,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("'X'"));
+        assert!(msg.contains("row 1"));
+        assert!(msg.contains("column 2"));
+    }
+
+    #[test]
+    fn test_invalid_player_turn_display() {
+        let err = GameYError::InvalidPlayerTurn {
+            expected: PlayerId::new(0),
+            found: PlayerId::new(1),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Expected player 0"));
+        assert!(msg.contains("found player 1"));
+    }
+
+    #[test]
+    fn test_invalid_num_players_display() {
+        let err = GameYError::InvalidNumPlayers {
+            num_players: 3,
+            expected: 2,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("3"));
+        assert!(msg.contains("expected 2"));
+    }
+
+    #[test]
+    fn test_invalid_yen_layout_display() {
+        let err = GameYError::InvalidYENLayout {
+            expected: 5,
+            found: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("expected 5"));
+        assert!(msg.contains("found 3"));
+    }
+
+    #[test]
+    fn test_invalid_yen_layout_line_display() {
+        let err = GameYError::InvalidYENLayoutLine {
+            expected: 4,
+            found: 2,
+            line: 3,
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("expected 4"));
+        assert!(msg.contains("found 2"));
+        assert!(msg.contains("line 3"));
+    }
+
+    #[test]
+    fn test_server_error_display() {
+        let err = GameYError::ServerError {
+            message: "Failed to bind to port 3000".to_string(),
+        };
+        let msg = format!("{}", err);
+        assert!(msg.contains("Server error"));
+        assert!(msg.contains("Failed to bind to port 3000"));
+    }
+
+    #[test]
+    fn test_game_not_found_display() {
+        let err = GameYError::GameNotFound {
+            id: "abc".to_string(),
+        };
+        assert_eq!(format!("{}", err), "Game not found: abc");
+    }
+
+    #[test]
+    fn test_error_is_debug() {
+        let err = GameYError::IoError {
+            message: "test".to_string(),
+            error: "error".to_string(),
+        };
+        let debug = format!("{:?}", err);
+        assert!(debug.contains("IoError"));
+    }
+
+    #[test]
+    fn test_error_codes() {
+        let err = GameYError::GameNotFound {
+            id: "abc".to_string(),
+        };
+        assert_eq!(err.code(), ErrorCode::GameNotFound);
+        assert_eq!(serde_json::to_value(err.code()).unwrap(), "GAME_NOT_FOUND");
+        assert_eq!(ErrorCode::BotNotFound.to_string(), "BOT_NOT_FOUND");
+        let err = GameYError::InvalidYENLayout {
+            expected: 3,
+            found: 2,
+        };
+        assert_eq!(err.code(), ErrorCode::InvalidYen);
+        let err = GameYError::UnknownPlayer {
+            player: PlayerId::new(2),
+            players: 2,
+        };
+        assert_eq!(err.code(), ErrorCode::UnknownPlayer);
+        assert_eq!(err.to_string(), "Unknown player 2, a game has 2 players");
+    }
+}
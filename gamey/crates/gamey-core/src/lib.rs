@@ -0,0 +1,39 @@
+//! The rules engine of GameY.
+//!
+//! This crate holds everything needed to play and store games of Y and Hex,
+//! without the bots, the server or the command-line interface, so it builds
+//! with a small set of dependencies.
+//!
+//! # Modules
+//!
+//! - [`core`]: Core game types including board, coordinates, and game logic
+//! - [`notation`]: Game notation formats (YEN)
+//! - [`compression`]: Gzip and Zstandard compressed files
+//! - [`determinism`]: Seeded randomness for reproducible runs
+//! - [`gamey_error`]: Error types for the library
+//! - [`testing`]: Random game generators and invariant checkers for fuzzing
+//!
+//! # Example
+//!
+//! ```
+//! use gamey_core::{GameY, Coordinates, Movement, PlayerId};
+//!
+//! let mut game = GameY::new(5);
+//! let movement = Movement::Placement {
+//!     player: PlayerId::new(0),
+//!     coords: Coordinates::new(2, 1, 1),
+//! };
+//! game.add_move(movement).unwrap();
+//! ```
+
+pub mod compression;
+pub mod core;
+pub mod determinism;
+pub mod gamey_error;
+pub mod notation;
+pub mod testing;
+pub use compression::*;
+pub use core::*;
+pub use determinism::*;
+pub use gamey_error::*;
+pub use notation::*;
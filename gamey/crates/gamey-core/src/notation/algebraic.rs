@@ -0,0 +1,143 @@
+//! Algebraic cell names: the letter of the column and the number of the
+//! row of a cell, as in `b3`.
+//!
+//! Rows are numbered from 1 at the top of the board as rendered, and the
+//! cells of each row are lettered from `a` at its left, so `a1` is the top
+//! corner of a Y board and the top left corner of a Hex board, where names
+//! follow the usual Hex notation. Past `z`, columns go on with `aa`, `ab`...
+
+use crate::{BoardTopology, Coordinates, GameY, GameYError};
+
+impl GameY {
+    /// Returns the algebraic name of the cell at `coords`.
+    pub fn cell_name(&self, coords: Coordinates) -> String {
+        let (row, column) = match self.topology() {
+            BoardTopology::Y => (self.board_size() - 1 - coords.x(), coords.y()),
+            BoardTopology::Hex => (coords.x(), coords.y()),
+        };
+        format!("{}{}", column_letters(column), row + 1)
+    }
+
+    /// Returns the cell with the algebraic name `name`, in either case.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidCellName` if `name` is not a column
+    /// letter followed by a row number, or names no cell of the board.
+    pub fn cell_by_name(&self, name: &str) -> Result<Coordinates, GameYError> {
+        let invalid = |message: &str| GameYError::InvalidCellName {
+            name: name.to_string(),
+            message: message.to_string(),
+        };
+        let lower = name.trim().to_ascii_lowercase();
+        let digits = lower.find(|c: char| !c.is_ascii_lowercase());
+        let (letters, number) = lower.split_at(digits.unwrap_or(lower.len()));
+        if letters.is_empty() || number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(invalid(
+                "expected a column letter and a row number, e.g. a1",
+            ));
+        }
+        let column = letters
+            .bytes()
+            .try_fold(0u32, |column, letter| {
+                column
+                    .checked_mul(26)?
+                    .checked_add((letter - b'a') as u32 + 1)
+            })
+            .ok_or_else(|| invalid("the column is off the board"))?
+            - 1;
+        let row = match number.parse::<u32>() {
+            Ok(row) if (1..=self.board_size()).contains(&row) => row - 1,
+            _ => return Err(invalid("the row is off the board")),
+        };
+        let size = self.board_size();
+        match self.topology() {
+            BoardTopology::Y if column <= row => {
+                Ok(Coordinates::new(size - 1 - row, column, row - column))
+            }
+            BoardTopology::Hex if column < size => Ok(Coordinates::new(row, column, 0)),
+            _ => Err(invalid("the column is off the board")),
+        }
+    }
+}
+
+/// Returns the letters of the column numbered `column` from 0.
+fn column_letters(column: u32) -> String {
+    let mut letters = Vec::new();
+    let mut rest = column + 1;
+    while rest > 0 {
+        rest -= 1;
+        letters.push(b'a' + (rest % 26) as u8);
+        rest /= 26;
+    }
+    letters.reverse();
+    String::from_utf8(letters).expect("letters are ASCII")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_cell_name_round_trips() {
+        for game in [GameY::new(5), GameY::with_topology(4, BoardTopology::Hex)] {
+            for index in 0..game.total_cells() {
+                let coords = game.cell_coords(index);
+                let name = game.cell_name(coords);
+                assert_eq!(game.cell_by_name(&name).unwrap(), coords, "{}", name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cell_names_follow_the_rows() {
+        let game = GameY::new(3);
+        // Index order is row-major from the top
+        let names: Vec<String> = (0..6)
+            .map(|i| game.cell_name(game.cell_coords(i)))
+            .collect();
+        assert_eq!(names, ["a1", "a2", "b2", "a3", "b3", "c3"]);
+        assert_eq!(game.cell_by_name("B3").unwrap(), game.cell_coords(4));
+
+        let hex = GameY::with_topology(3, BoardTopology::Hex);
+        assert_eq!(hex.cell_by_name("c1").unwrap(), Coordinates::new(0, 2, 0));
+    }
+
+    #[test]
+    fn test_invalid_cell_names() {
+        let game = GameY::new(3);
+        for name in [
+            "",
+            "a",
+            "3",
+            "b1",
+            "a0",
+            "a4",
+            "1a",
+            "a1b",
+            "é1",
+            "zzzzzzzzz1",
+        ] {
+            assert!(
+                matches!(
+                    game.cell_by_name(name),
+                    Err(GameYError::InvalidCellName { .. })
+                ),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn test_columns_past_z() {
+        assert_eq!(column_letters(0), "a");
+        assert_eq!(column_letters(25), "z");
+        assert_eq!(column_letters(26), "aa");
+        assert_eq!(column_letters(27), "ab");
+        let game = GameY::with_topology(30, BoardTopology::Hex);
+        assert_eq!(
+            game.cell_by_name("ab1").unwrap(),
+            Coordinates::new(0, 27, 0)
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{BoardTopology, GameY, GameYError, Ruleset, YEN};
+
+impl GameY {
+    /// Parses a position from an ASCII diagram, such as the output of
+    /// [`GameY::render`], so tests and bug reports can embed readable boards.
+    ///
+    /// Each non-empty line is a row of the board, from top to bottom, with
+    /// cells separated by whitespace. A cell is `B` or `0` for player 0, `R`
+    /// or `1` for player 1, `.` for an empty cell and `#` for a cell blocked
+    /// by Master Y. Whatever follows the symbol of a cell (the indices or
+    /// coordinates printed by `render`) is ignored, as are color codes, the
+    /// brackets around the last move and the `--- Game of ... ---` header.
+    /// Diagrams rendered with edge labels or move numbers are not supported.
+    ///
+    /// The board is a Hex board if the header says so, or if every row has
+    /// the same number of cells; otherwise it is a Y board.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::GameY;
+    ///
+    /// let game = GameY::from_ascii(
+    ///     "
+    ///         B
+    ///       .   R
+    ///     .   .   B
+    ///     ",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(game.board_size(), 3);
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidDiagram` if the rows do not form a board,
+    /// or any error of loading the equivalent [`YEN`] position.
+    pub fn from_ascii(diagram: &str) -> Result<GameY, GameYError> {
+        let mut topology = None;
+        let mut rows: Vec<String> = Vec::new();
+        for (line_no, line) in strip_ansi(diagram).lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with("---") {
+                if line.contains("Game of Hex") {
+                    topology = Some(BoardTopology::Hex);
+                }
+                continue;
+            }
+            let mut row = String::new();
+            for token in line.split_whitespace() {
+                let symbol = token.trim_start_matches('[').chars().next();
+                let cell = match symbol {
+                    Some('B' | '0') => 'B',
+                    Some('R' | '1') => 'R',
+                    Some('.') => '.',
+                    Some('#') => '#',
+                    // Index or coordinate annotations printed on their own
+                    Some('(') => continue,
+                    _ => {
+                        return Err(GameYError::InvalidDiagram {
+                            line: line_no + 1,
+                            message: format!("unexpected cell '{}'", token),
+                        });
+                    }
+                };
+                row.push(cell);
+            }
+            rows.push(row);
+        }
+
+        let size = rows.len() as u32;
+        if size == 0 {
+            return Err(GameYError::InvalidDiagram {
+                line: 0,
+                message: "the diagram has no rows".to_string(),
+            });
+        }
+        let topology = topology.unwrap_or_else(|| {
+            if size > 1 && rows.iter().all(|r| r.len() == rows[0].len()) {
+                BoardTopology::Hex
+            } else {
+                BoardTopology::Y
+            }
+        });
+        let ruleset = if rows.iter().any(|r| r.contains('#')) {
+            Ruleset::MasterY
+        } else {
+            Ruleset::Standard
+        };
+        let stones = |symbol| {
+            rows.iter()
+                .flat_map(|r| r.chars())
+                .filter(|&c| c == symbol)
+                .count()
+        };
+        let turn = if stones('B') > stones('R') { 1 } else { 0 };
+        let yen = YEN::new(size, turn, vec!['B', 'R'], rows.join("/"))
+            .with_topology(topology)
+            .with_ruleset(ruleset);
+        GameY::try_from(yen)
+    }
+}
+
+/// Removes ANSI escape sequences such as the color codes used by `render`.
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut in_escape = false;
+    for c in text.chars() {
+        if in_escape {
+            in_escape = !c.is_ascii_alphabetic();
+        } else if c == '\x1b' {
+            in_escape = true;
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Coordinates, Movement, PlayerId, RenderOptions};
+
+    fn sample_game() -> GameY {
+        let mut game = GameY::new(4);
+        for (player, coords) in [
+            (0, Coordinates::new(1, 1, 1)),
+            (1, Coordinates::new(3, 0, 0)),
+            (0, Coordinates::new(0, 2, 1)),
+        ] {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(player),
+                coords,
+            })
+            .unwrap();
+        }
+        game
+    }
+
+    fn layout(game: &GameY) -> String {
+        YEN::from(game).layout().to_string()
+    }
+
+    #[test]
+    fn test_parses_letter_diagram() {
+        let game = GameY::from_ascii(
+            "
+                R
+              .   .
+            .   B   .
+          .   .   B   .
+            ",
+        )
+        .unwrap();
+        assert_eq!(layout(&game), layout(&sample_game()));
+    }
+
+    #[test]
+    fn test_roundtrips_default_render() {
+        let game = sample_game();
+        let parsed = GameY::from_ascii(&game.render(&RenderOptions::default())).unwrap();
+        assert_eq!(layout(&parsed), layout(&game));
+    }
+
+    #[test]
+    fn test_roundtrips_render_with_coordinates() {
+        let game = sample_game();
+        let options = RenderOptions {
+            show_3d_coords: true,
+            show_colors: false,
+            ..RenderOptions::default()
+        };
+        let parsed = GameY::from_ascii(&game.render(&options)).unwrap();
+        assert_eq!(layout(&parsed), layout(&game));
+    }
+
+    #[test]
+    fn test_detects_hex_boards() {
+        let game = GameY::from_ascii("B .\n . R").unwrap();
+        assert_eq!(game.topology(), BoardTopology::Hex);
+        assert_eq!(layout(&game), "B./.R");
+    }
+
+    #[test]
+    fn test_detects_master_y() {
+        let game = GameY::from_ascii("#\n. .\n. . .\n# . . #").unwrap();
+        assert_eq!(game.ruleset(), Ruleset::MasterY);
+    }
+
+    #[test]
+    fn test_rejects_unknown_symbols() {
+        assert!(matches!(
+            GameY::from_ascii("X\n. ."),
+            Err(GameYError::InvalidDiagram { line: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn test_rejects_malformed_rows() {
+        assert!(GameY::from_ascii(".\n. . .").is_err());
+        assert!(GameY::from_ascii("\n\n").is_err());
+    }
+}
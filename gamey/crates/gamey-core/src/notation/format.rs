@@ -0,0 +1,166 @@
+//! The file formats games are stored in, to read and write games whatever
+//! their format.
+
+use crate::{Compression, GameY, GameYError, MoveRecord, YEN};
+use std::fmt::Display;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// A format of game files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameFormat {
+    /// YEN with the move history and an integrity digest, pretty printed,
+    /// as [`GameY::save_to_file`] writes it; `.yen` files.
+    Yen,
+    /// YEN with the move history on a single line, as in archives.
+    CompactYen,
+    /// A move list, see [`moves`](crate::notation::moves); `.ymn` files.
+    Moves,
+    /// A Smart Game Format record, see [`sgf`](crate::notation::sgf);
+    /// `.sgf` files.
+    Sgf,
+}
+
+impl GameFormat {
+    /// Returns the format of the file at `path` by its extension, after
+    /// any compression extension, or `None` if the extension is unknown.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let path = match Compression::from_path(path) {
+            Compression::None => path,
+            _ => Path::new(path.file_stem()?),
+        };
+        match path.extension()?.to_str()? {
+            "yen" | "json" => Some(GameFormat::Yen),
+            "ymn" => Some(GameFormat::Moves),
+            "sgf" => Some(GameFormat::Sgf),
+            _ => None,
+        }
+    }
+
+    /// Returns the extension of files in the format.
+    pub fn extension(self) -> &'static str {
+        match self {
+            GameFormat::Yen | GameFormat::CompactYen => "yen",
+            GameFormat::Moves => "ymn",
+            GameFormat::Sgf => "sgf",
+        }
+    }
+
+    /// Reads a game in the format from `reader`. Move lists do not
+    /// describe the board, so they are played on `board`.
+    ///
+    /// # Errors
+    /// Returns the error of reading or parsing the game.
+    pub fn read<R: Read>(self, mut reader: R, board: &GameY) -> Result<GameY, GameYError> {
+        match self {
+            GameFormat::Yen | GameFormat::CompactYen => GameY::read_yen(reader),
+            GameFormat::Moves => {
+                let mut game = board.clone();
+                game.read_moves(reader)?;
+                Ok(game)
+            }
+            GameFormat::Sgf => {
+                let mut sgf = String::new();
+                reader
+                    .read_to_string(&mut sgf)
+                    .map_err(|e| GameYError::IoError {
+                        message: "Failed to read SGF".to_string(),
+                        error: e.to_string(),
+                    })?;
+                GameY::from_sgf(&sgf)
+            }
+        }
+    }
+
+    /// Writes `game` in the format to `writer`.
+    ///
+    /// # Errors
+    /// Returns the error of writing the game.
+    pub fn write<W: Write>(self, game: &GameY, mut writer: W) -> Result<(), GameYError> {
+        let failed = |e: std::io::Error| GameYError::IoError {
+            message: format!("Failed to write {}", self),
+            error: e.to_string(),
+        };
+        match self {
+            GameFormat::Yen => game.write_yen(writer),
+            GameFormat::CompactYen => {
+                let history = game.history().iter().map(MoveRecord::from).collect();
+                let yen = YEN::from(game)
+                    .with_history(history, game.annotations().to_vec())
+                    .with_metadata(game.metadata().clone());
+                serde_json::to_writer(&mut writer, &yen)
+                    .map_err(|e| GameYError::SerdeError { error: e })?;
+                writeln!(writer).map_err(failed)
+            }
+            GameFormat::Moves => game.write_moves(writer),
+            GameFormat::Sgf => writeln!(writer, "{}", game.to_sgf()?).map_err(failed),
+        }
+    }
+}
+
+impl Display for GameFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GameFormat::Yen => write!(f, "yen"),
+            GameFormat::CompactYen => write!(f, "compact-yen"),
+            GameFormat::Moves => write!(f, "moves"),
+            GameFormat::Sgf => write!(f, "sgf"),
+        }
+    }
+}
+
+impl FromStr for GameFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "yen" => Ok(GameFormat::Yen),
+            "compact-yen" | "compact_yen" => Ok(GameFormat::CompactYen),
+            "moves" | "ymn" => Ok(GameFormat::Moves),
+            "sgf" => Ok(GameFormat::Sgf),
+            _ => Err(format!(
+                "Unknown game format '{}', expected one of: yen, compact-yen, moves, sgf",
+                s
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_from_path() {
+        let of = |path: &str| GameFormat::from_path(Path::new(path));
+        assert_eq!(of("game.yen"), Some(GameFormat::Yen));
+        assert_eq!(of("games/game.sgf.gz"), Some(GameFormat::Sgf));
+        assert_eq!(of("game.ymn.zst"), Some(GameFormat::Moves));
+        assert_eq!(of("game.txt"), None);
+        assert_eq!(of("game"), None);
+    }
+
+    #[test]
+    fn test_every_format_round_trips() {
+        let mut game = GameY::new(4);
+        game.play_move_list("0 5! 2 {a reply}").unwrap();
+        for format in [
+            GameFormat::Yen,
+            GameFormat::CompactYen,
+            GameFormat::Moves,
+            GameFormat::Sgf,
+        ] {
+            assert_eq!(format.to_string().parse::<GameFormat>(), Ok(format));
+            let mut written = Vec::new();
+            format.write(&game, &mut written).unwrap();
+            if format == GameFormat::CompactYen {
+                assert_eq!(written.iter().filter(|&&b| b == b'\n').count(), 1);
+            }
+            let read = format.read(written.as_slice(), &GameY::new(4)).unwrap();
+            assert_eq!(read.history(), game.history(), "{}", format);
+            assert_eq!(read.annotations(), game.annotations(), "{}", format);
+        }
+        assert!("pgn".parse::<GameFormat>().is_err());
+    }
+}
@@ -0,0 +1,120 @@
+use crate::GameYError;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// The algorithm used to compute an [`Integrity`] digest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IntegrityAlgorithm {
+    /// A plain SHA-256 hash: detects accidental or careless edits.
+    Sha256,
+    /// An HMAC-SHA-256 keyed with a tournament key: only key holders can
+    /// produce a valid digest.
+    HmacSha256,
+}
+
+/// An integrity digest attached to a saved game.
+///
+/// The digest covers the canonical JSON form of the saved document without
+/// its integrity field, so any change to the position or its metadata is
+/// detected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Integrity {
+    /// How the digest was computed.
+    pub algorithm: IntegrityAlgorithm,
+    /// The digest, as lowercase hexadecimal.
+    pub digest: String,
+}
+
+impl Integrity {
+    /// Computes the digest of `content`, keyed with `key` if one is given.
+    pub fn compute(content: &[u8], key: Option<&[u8]>) -> Self {
+        match key {
+            Some(key) => {
+                let mut mac =
+                    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+                mac.update(content);
+                Integrity {
+                    algorithm: IntegrityAlgorithm::HmacSha256,
+                    digest: to_hex(&mac.finalize().into_bytes()),
+                }
+            }
+            None => Integrity {
+                algorithm: IntegrityAlgorithm::Sha256,
+                digest: to_hex(&Sha256::digest(content)),
+            },
+        }
+    }
+
+    /// Checks that this digest matches `content`.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IntegrityCheckFailed` if the content was
+    /// modified, or if the digest is keyed and no key was given.
+    pub fn verify(&self, content: &[u8], key: Option<&[u8]>) -> Result<(), GameYError> {
+        let key = match (self.algorithm, key) {
+            (IntegrityAlgorithm::Sha256, _) => None,
+            (IntegrityAlgorithm::HmacSha256, Some(key)) => Some(key),
+            (IntegrityAlgorithm::HmacSha256, None) => {
+                return Err(GameYError::IntegrityCheckFailed {
+                    reason: "the game is signed with a tournament key but no key was given"
+                        .to_string(),
+                });
+            }
+        };
+        if Integrity::compute(content, key).digest != self.digest {
+            return Err(GameYError::IntegrityCheckFailed {
+                reason: "the digest does not match, the game was modified".to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, b| {
+        let _ = write!(hex, "{:02x}", b);
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_known_digest() {
+        let integrity = Integrity::compute(b"abc", None);
+        assert_eq!(integrity.algorithm, IntegrityAlgorithm::Sha256);
+        assert_eq!(
+            integrity.digest,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_verify_detects_changes() {
+        let integrity = Integrity::compute(b"content", None);
+        assert!(integrity.verify(b"content", None).is_ok());
+        assert!(integrity.verify(b"c0ntent", None).is_err());
+    }
+
+    #[test]
+    fn test_hmac_requires_the_right_key() {
+        let integrity = Integrity::compute(b"content", Some(b"key"));
+        assert_eq!(integrity.algorithm, IntegrityAlgorithm::HmacSha256);
+        assert!(integrity.verify(b"content", Some(b"key")).is_ok());
+        assert!(integrity.verify(b"content", Some(b"other")).is_err());
+        assert!(integrity.verify(b"content", None).is_err());
+    }
+
+    #[test]
+    fn test_algorithm_names() {
+        assert_eq!(
+            serde_json::to_string(&IntegrityAlgorithm::HmacSha256).unwrap(),
+            "\"hmac-sha256\""
+        );
+    }
+}
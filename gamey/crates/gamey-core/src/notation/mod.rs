@@ -0,0 +1,42 @@
+//! Game notation formats for serializing and deserializing Y game states.
+//!
+//! This module provides notation formats for representing Y game positions
+//! in a compact, portable way. Currently supported:
+//!
+//! - [`YEN`]: Y Exchange Notation - a JSON-based format inspired by chess FEN
+//! - ASCII diagrams, as printed by [`GameY::render`](crate::GameY::render),
+//!   read with [`GameY::from_ascii`](crate::GameY::from_ascii)
+//! - [`moves`]: move lists, the moves of a game as cell indices, read with
+//!   [`GameY::read_moves`](crate::GameY::read_moves) and written with
+//!   [`GameY::write_moves`](crate::GameY::write_moves)
+//! - [`sgf`]: Smart Game Format records, written with
+//!   [`GameY::to_sgf`](crate::GameY::to_sgf) and read with
+//!   [`GameY::from_sgf`](crate::GameY::from_sgf)
+//! - [`algebraic`]: cell names such as `b3`, written with
+//!   [`GameY::cell_name`](crate::GameY::cell_name) and read with
+//!   [`GameY::cell_by_name`](crate::GameY::cell_by_name)
+//!
+//! [`GameFormat`] reads and writes games in any of these formats.
+//!
+//! [`YEN::validate`] checks a YEN strictly and reports every problem with a
+//! machine-readable [`ValidationCode`].
+//!
+//! Saved formats carry a `version` field; [`versioning`] upgrades documents
+//! written by older versions of the crate. Saved games may also carry an
+//! [`Integrity`] digest to detect tampering.
+
+pub mod algebraic;
+pub mod ascii;
+pub mod format;
+pub mod integrity;
+pub mod moves;
+pub mod sgf;
+pub mod validation;
+pub mod versioning;
+pub mod yen;
+pub use format::GameFormat;
+pub use integrity::*;
+pub use moves::move_list_tokens;
+pub use validation::*;
+pub use versioning::*;
+pub use yen::*;
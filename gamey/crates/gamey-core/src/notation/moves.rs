@@ -0,0 +1,192 @@
+//! Move lists: the moves of a game as whitespace-separated cell indices.
+//!
+//! A move may end with a glyph judging it, as in `4!` or `7??`, and
+//! comments in braces, as in `{takes the corner}`, are attached after the
+//! last move played. `swap` and `resign` stand for the actions. A move list
+//! does not describe the board, so it is played on a game set up
+//! beforehand, e.g. `0 5! {a strong reply} 3 resign`.
+
+use crate::{GameAction, GameY, GameYError, Glyph, Movement};
+use std::io::{Read, Write};
+
+impl GameY {
+    /// Plays the move list read from `reader` on this game, each move by
+    /// the player whose turn it is, keeping glyphs and comments as
+    /// annotations.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the list cannot be read, or
+    /// `GameYError::InvalidMoveList` naming the first move that cannot be
+    /// played; the moves before it stay played.
+    pub fn read_moves<R: Read>(&mut self, mut reader: R) -> Result<(), GameYError> {
+        let mut moves = String::new();
+        reader
+            .read_to_string(&mut moves)
+            .map_err(|e| GameYError::IoError {
+                message: "Failed to read move list".to_string(),
+                error: e.to_string(),
+            })?;
+        self.play_move_list(&moves)
+    }
+
+    /// Plays a move list on this game, like [`GameY::read_moves`].
+    pub fn play_move_list(&mut self, moves: &str) -> Result<(), GameYError> {
+        let mut number = 0;
+        for token in move_list_tokens(moves) {
+            if let Some(comment) = token.strip_prefix('{') {
+                let comment = comment.strip_suffix('}').unwrap_or(comment).trim();
+                self.add_annotation(None, comment);
+                continue;
+            }
+            number += 1;
+            let rejected = |message: String| GameYError::InvalidMoveList {
+                number,
+                token: token.to_string(),
+                message,
+            };
+            let Some(player) = self.next_player() else {
+                return Err(rejected("the game is over".to_string()));
+            };
+            let (name, glyph) = token.split_at(token.find(['!', '?']).unwrap_or(token.len()));
+            let glyph = match glyph {
+                "" => None,
+                glyph => Some(glyph.parse::<Glyph>().map_err(rejected)?),
+            };
+            let movement = match name.to_ascii_lowercase().as_str() {
+                "swap" => Movement::Action {
+                    player,
+                    action: GameAction::Swap,
+                },
+                "resign" => Movement::Action {
+                    player,
+                    action: GameAction::Resign,
+                },
+                index => match index.parse::<u32>() {
+                    Ok(index) if index < self.total_cells() => Movement::Placement {
+                        player,
+                        coords: self.cell_coords(index),
+                    },
+                    _ => {
+                        return Err(rejected(format!(
+                            "expected a cell index below {}, swap or resign",
+                            self.total_cells()
+                        )));
+                    }
+                },
+            };
+            self.add_move(movement)
+                .map_err(|e| rejected(e.to_string()))?;
+            if let Some(glyph) = glyph {
+                self.add_annotation(Some(glyph), "");
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the moves of the game to `writer` as a move list, on one
+    /// line.
+    ///
+    /// An annotation holding both a glyph and a comment is written as the
+    /// glyph after its move followed by the comment, so it is read back as
+    /// two annotations. Closing braces in comments are written as `)`.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the list cannot be written.
+    pub fn write_moves<W: Write>(&self, mut writer: W) -> Result<(), GameYError> {
+        writeln!(writer, "{}", self.to_move_list()).map_err(|e| GameYError::IoError {
+            message: "Failed to write move list".to_string(),
+            error: e.to_string(),
+        })
+    }
+
+    /// Returns the moves of the game as a move list, like
+    /// [`GameY::write_moves`] without the final newline.
+    pub fn to_move_list(&self) -> String {
+        let mut tokens: Vec<String> = Vec::new();
+        let mut annotations = self.annotations().iter().peekable();
+        for played in 0..=self.history().len() {
+            if let Some(movement) = played.checked_sub(1).map(|i| &self.history()[i]) {
+                tokens.push(match movement {
+                    Movement::Placement { coords, .. } => self.cell_index(*coords).to_string(),
+                    Movement::Action { action, .. } => action.to_string().to_ascii_lowercase(),
+                });
+            }
+            while let Some(annotation) = annotations.next_if(|a| a.move_number <= played) {
+                if let (Some(glyph), Some(last)) = (annotation.glyph, tokens.last_mut())
+                    && played > 0
+                    && !last.ends_with(['!', '?'])
+                {
+                    last.push_str(&glyph.to_string());
+                }
+                if !annotation.text.is_empty() {
+                    tokens.push(format!("{{{}}}", annotation.text.replace('}', ")")));
+                }
+            }
+        }
+        tokens.join(" ")
+    }
+}
+
+/// Splits a move list into moves and `{...}` comments, which may contain
+/// spaces; an unclosed comment runs to the end of the list.
+pub fn move_list_tokens(moves: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut rest = moves.trim_start();
+    while !rest.is_empty() {
+        let end = if rest.starts_with('{') {
+            rest.find('}').map_or(rest.len(), |end| end + 1)
+        } else {
+            rest.find(|c: char| c.is_whitespace() || c == '{')
+                .unwrap_or(rest.len())
+        };
+        tokens.push(&rest[..end]);
+        rest = rest[end..].trim_start();
+    }
+    tokens
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_list_round_trip() {
+        let mut game = GameY::new(3);
+        game.read_moves("{opening} 0 swap 4!? {a strong reply} 3 resign".as_bytes())
+            .unwrap();
+        assert_eq!(game.history().len(), 5);
+        assert_eq!(game.annotations().len(), 3);
+        assert!(matches!(
+            game.history()[4],
+            Movement::Action {
+                action: GameAction::Resign,
+                ..
+            }
+        ));
+
+        let mut written = Vec::new();
+        game.write_moves(&mut written).unwrap();
+        assert_eq!(
+            String::from_utf8(written).unwrap(),
+            "{opening} 0 swap 4!? {a strong reply} 3 resign\n"
+        );
+        let mut replayed = GameY::new(3);
+        replayed.play_move_list(&game.to_move_list()).unwrap();
+        assert_eq!(replayed.history(), game.history());
+        assert_eq!(replayed.annotations(), game.annotations());
+    }
+
+    #[test]
+    fn test_invalid_move_is_named() {
+        let mut game = GameY::new(3);
+        match game.play_move_list("0 {fine} 0") {
+            Err(GameYError::InvalidMoveList { number, token, .. }) => {
+                assert_eq!((number, token.as_str()), (2, "0"));
+            }
+            other => panic!("expected an invalid move list, got {:?}", other),
+        }
+        assert_eq!(game.history().len(), 1);
+        assert!(game.play_move_list("6").is_err());
+        assert!(game.play_move_list("1?x").is_err());
+    }
+}
@@ -0,0 +1,463 @@
+//! Smart Game Format (SGF) game records.
+//!
+//! Games are written as a single line of play in the style of the Hex SGF
+//! files of HexGui and Little Golem: `B` moves are player 0 and `W` moves
+//! player 1, cells are a letter for the column within the row and a letter
+//! for the row, from the top (`a` to `z`, then `A` to `Z`), and the actions
+//! are `swap-pieces` and `resign`. Hex boards are marked `GM[11]`; gamey
+//! also records the board shape in `TP` (`y` or `hex`) and the ruleset in
+//! `RU`. Handicap stones are `AB` setup stones of the root node, comments
+//! are `C` and glyphs the move annotations `TE`, `BM`, `IT` and `DO`. The
+//! [`GameMetadata`] are the root properties `EV`, `DT`, `PB`, `PW`, `BR`,
+//! `WR` and `RE`.
+//!
+//! Reading follows the main line of the first game of a file and ignores
+//! the properties it does not know.
+
+use crate::{
+    BoardTopology, Coordinates, GameAction, GameMetadata, GameY, GameYError, Glyph, Movement,
+    PlayerId, Ruleset,
+};
+
+type Chars<'a> = std::iter::Peekable<std::str::Chars<'a>>;
+
+/// The cell letters, enough for boards of size 52.
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl GameY {
+    /// Returns the game as an SGF record.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidSgf` if the board is too large to be
+    /// written with SGF letters.
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::{Coordinates, GameY, Movement, PlayerId};
+    ///
+    /// let mut game = GameY::new(3);
+    /// let coords = Coordinates::new(2, 0, 0);
+    /// game.add_move(Movement::Placement { player: PlayerId::new(0), coords }).unwrap();
+    /// let sgf = game.to_sgf().unwrap();
+    /// assert!(sgf.starts_with("(;FF[4]"));
+    /// assert!(sgf.ends_with("SZ[3]TP[y]RU[standard];B[aa])"));
+    /// assert_eq!(GameY::from_sgf(&sgf).unwrap().history(), game.history());
+    /// ```
+    pub fn to_sgf(&self) -> Result<String, GameYError> {
+        if self.board_size() as usize > LETTERS.len() {
+            return Err(GameYError::InvalidSgf {
+                message: format!(
+                    "boards of size {} are larger than SGF supports",
+                    self.board_size()
+                ),
+            });
+        }
+        let points = Points::new(self);
+        let mut sgf = format!("(;FF[4]CA[UTF-8]AP[gamey:{}]", env!("CARGO_PKG_VERSION"));
+        if self.topology() == BoardTopology::Hex {
+            sgf.push_str("GM[11]");
+        }
+        sgf.push_str(&format!(
+            "SZ[{}]TP[{}]RU[{}]",
+            self.board_size(),
+            self.topology(),
+            self.ruleset()
+        ));
+        if !self.handicap().is_empty() {
+            sgf.push_str(&format!("HA[{}]AB", self.handicap().len()));
+            for &coords in self.handicap() {
+                sgf.push_str(&format!("[{}]", points.name(coords)));
+            }
+        }
+        let metadata = self.metadata();
+        let text = [("EV", &metadata.event), ("DT", &metadata.date)];
+        for (property, value) in text {
+            if let Some(value) = value {
+                sgf.push_str(&format!("{}[{}]", property, escape(value)));
+            }
+        }
+        for (id, name, rating) in [(0, "PB", "BR"), (1, "PW", "WR")] {
+            let player = PlayerId::new(id);
+            if let Some(value) = metadata.player_name(player) {
+                sgf.push_str(&format!("{}[{}]", name, escape(value)));
+            }
+            if let Some(value) = metadata.rating(player) {
+                sgf.push_str(&format!("{}[{}]", rating, value));
+            }
+        }
+        if let Some(result) = self.result().or_else(|| metadata.result.clone()) {
+            sgf.push_str(&format!("RE[{}]", escape(&result)));
+        }
+        self.push_annotations(&mut sgf, 0);
+        for (played, movement) in self.history().iter().enumerate() {
+            let (player, value) = match movement {
+                Movement::Placement { player, coords } => (*player, points.name(*coords)),
+                Movement::Action { player, action } => (
+                    *player,
+                    match action {
+                        GameAction::Swap => "swap-pieces".to_string(),
+                        GameAction::Resign => "resign".to_string(),
+                    },
+                ),
+            };
+            let color = if player.id() == 0 { 'B' } else { 'W' };
+            sgf.push_str(&format!(";{}[{}]", color, value));
+            self.push_annotations(&mut sgf, played + 1);
+        }
+        sgf.push(')');
+        Ok(sgf)
+    }
+
+    fn push_annotations(&self, sgf: &mut String, move_number: usize) {
+        let mut comments = Vec::new();
+        for annotation in self
+            .annotations()
+            .iter()
+            .filter(|a| a.move_number == move_number)
+        {
+            if let Some(glyph) = annotation.glyph {
+                sgf.push_str(match glyph {
+                    Glyph::Good => "TE[1]",
+                    Glyph::Brilliant => "TE[2]",
+                    Glyph::Mistake => "BM[1]",
+                    Glyph::Blunder => "BM[2]",
+                    Glyph::Interesting => "IT[]",
+                    Glyph::Dubious => "DO[]",
+                });
+            }
+            if !annotation.text.is_empty() {
+                comments.push(annotation.text.as_str());
+            }
+        }
+        if !comments.is_empty() {
+            sgf.push_str(&format!("C[{}]", escape(&comments.join("\n"))));
+        }
+    }
+
+    /// Reads a game from an SGF record, such as one written by
+    /// [`GameY::to_sgf`].
+    ///
+    /// Several comments of one node are read back as a single comment.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidSgf` if the record is malformed or
+    /// describes no game of Y or Hex, or the error of the first move that
+    /// cannot be played.
+    pub fn from_sgf(sgf: &str) -> Result<GameY, GameYError> {
+        let nodes = main_line(sgf)?;
+        let root = nodes
+            .first()
+            .ok_or_else(|| invalid("the record has no node"))?;
+        let size = match root.value("SZ") {
+            Some(size) => size
+                .split(':')
+                .next()
+                .and_then(|s| s.trim().parse::<u32>().ok())
+                .ok_or_else(|| invalid(format!("invalid board size '{}'", size)))?,
+            None => return Err(invalid("the record has no board size (SZ)")),
+        };
+        if size == 0 || size as usize > LETTERS.len() {
+            return Err(GameYError::InvalidBoardSize { size });
+        }
+        let topology = match (root.value("TP"), root.value("GM")) {
+            (Some(topology), _) => topology.parse().map_err(invalid)?,
+            (None, Some("11")) => BoardTopology::Hex,
+            (None, Some(game)) if game != "1" => BoardTopology::Y,
+            (None, Some(_)) => return Err(invalid("the record is a game of Go")),
+            (None, None) => BoardTopology::Y,
+        };
+        let ruleset = match root.value("RU") {
+            Some(ruleset) => ruleset.parse().unwrap_or_else(|_| {
+                tracing::warn!("Unknown SGF ruleset '{}', playing standard", ruleset);
+                Ruleset::Standard
+            }),
+            None => Ruleset::Standard,
+        };
+        let mut game = GameY::with_variant(size, topology, ruleset);
+        let points = Points::new(&game);
+        let handicap = root
+            .values("AB")
+            .map(|name| points.coords(name))
+            .collect::<Result<Vec<_>, _>>()?;
+        game = game.with_handicap(&handicap)?;
+        game.set_metadata(root_metadata(root));
+
+        for node in &nodes {
+            for (color, id) in [("B", 0), ("W", 1)] {
+                let Some(value) = node.value(color) else {
+                    continue;
+                };
+                let player = PlayerId::new(id);
+                let movement = match value {
+                    "swap-pieces" | "swap" => Movement::Action {
+                        player,
+                        action: GameAction::Swap,
+                    },
+                    "resign" => Movement::Action {
+                        player,
+                        action: GameAction::Resign,
+                    },
+                    name => Movement::Placement {
+                        player,
+                        coords: points.coords(name)?,
+                    },
+                };
+                game.add_move(movement)?;
+            }
+            let glyph = match (node.value("TE"), node.value("BM")) {
+                (Some("2"), _) => Some(Glyph::Brilliant),
+                (Some(_), _) => Some(Glyph::Good),
+                (_, Some("2")) => Some(Glyph::Blunder),
+                (_, Some(_)) => Some(Glyph::Mistake),
+                _ if node.value("IT").is_some() => Some(Glyph::Interesting),
+                _ if node.value("DO").is_some() => Some(Glyph::Dubious),
+                _ => None,
+            };
+            // A glyph judges the move of its node
+            let moved = node.value("B").is_some() || node.value("W").is_some();
+            let glyph = glyph.filter(|_| moved);
+            let comment = node.value("C").unwrap_or_default();
+            if glyph.is_some() || !comment.is_empty() {
+                game.add_annotation(glyph, comment);
+            }
+        }
+        Ok(game)
+    }
+}
+
+/// Reads the metadata of a game from the properties of its root node.
+fn root_metadata(root: &Node) -> GameMetadata {
+    let text = |property| root.value(property).map(str::to_string);
+    let mut metadata = GameMetadata {
+        event: text("EV"),
+        date: text("DT"),
+        result: text("RE"),
+        ..GameMetadata::default()
+    };
+    for (id, name, rating) in [(0, "PB", "BR"), (1, "PW", "WR")] {
+        let player = PlayerId::new(id);
+        if let Some(value) = root.value(name) {
+            metadata.player_mut(player).name = Some(value.to_string());
+        }
+        if let Some(value) = root.value(rating).and_then(|r| r.trim().parse().ok()) {
+            metadata.player_mut(player).rating = Some(value);
+        }
+    }
+    metadata
+}
+
+/// Names the cells of a board with SGF letters.
+struct Points {
+    rows: Vec<Vec<Coordinates>>,
+}
+
+impl Points {
+    fn new(game: &GameY) -> Self {
+        Points {
+            rows: game.topology().rows(game.board_size()),
+        }
+    }
+
+    fn name(&self, coords: Coordinates) -> String {
+        for (row, cells) in self.rows.iter().enumerate() {
+            if let Some(col) = cells.iter().position(|&c| c == coords) {
+                return format!("{}{}", LETTERS[col] as char, LETTERS[row] as char);
+            }
+        }
+        String::new()
+    }
+
+    fn coords(&self, name: &str) -> Result<Coordinates, GameYError> {
+        let letter = |c: u8| LETTERS.iter().position(|&l| l == c);
+        match name.as_bytes() {
+            &[col, row] => letter(row)
+                .and_then(|row| self.rows.get(row))
+                .zip(letter(col))
+                .and_then(|(cells, col)| cells.get(col).copied()),
+            _ => None,
+        }
+        .ok_or_else(|| invalid(format!("'{}' is not a cell of the board", name)))
+    }
+}
+
+/// A node of an SGF record: its properties and their values.
+struct Node {
+    properties: Vec<(String, Vec<String>)>,
+}
+
+impl Node {
+    fn values<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a str> {
+        self.properties
+            .iter()
+            .filter(move |(id, _)| id == name)
+            .flat_map(|(_, values)| values.iter().map(String::as_str))
+    }
+
+    fn value(&self, name: &str) -> Option<&str> {
+        self.values(name).next()
+    }
+}
+
+/// Returns the nodes of the main line of the first game tree of `sgf`.
+fn main_line(sgf: &str) -> Result<Vec<Node>, GameYError> {
+    let mut chars = sgf.chars().peekable();
+    while chars.next_if(|&c| c != '(').is_some() {}
+    if chars.next() != Some('(') {
+        return Err(invalid("the record does not start with '('"));
+    }
+    game_tree(&mut chars)
+}
+
+/// Reads a game tree whose `(` was just read, returning the nodes of its
+/// main line: its own nodes and those of its first variation.
+fn game_tree(chars: &mut Chars<'_>) -> Result<Vec<Node>, GameYError> {
+    let mut nodes = Vec::new();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match chars.next() {
+            Some(';') => nodes.push(read_node(chars)?),
+            Some('(') => {
+                nodes.extend(game_tree(chars)?);
+                loop {
+                    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+                    if chars.next_if_eq(&'(').is_none() {
+                        break;
+                    }
+                    game_tree(chars)?;
+                }
+            }
+            Some(')') => return Ok(nodes),
+            Some(c) => return Err(invalid(format!("unexpected '{}'", c))),
+            None => return Err(invalid("the record is not closed with ')'")),
+        }
+    }
+}
+
+fn read_node(chars: &mut Chars<'_>) -> Result<Node, GameYError> {
+    let mut properties = Vec::new();
+    loop {
+        while chars.next_if(|c| c.is_whitespace()).is_some() {}
+        let mut id = String::new();
+        while let Some(c) = chars.next_if(|c| c.is_ascii_alphabetic()) {
+            id.push(c);
+        }
+        if id.is_empty() {
+            return Ok(Node { properties });
+        }
+        let mut values = Vec::new();
+        loop {
+            while chars.next_if(|c| c.is_whitespace()).is_some() {}
+            if chars.next_if_eq(&'[').is_none() {
+                break;
+            }
+            let mut value = String::new();
+            loop {
+                match chars.next() {
+                    Some('\\') => {
+                        // A soft line break is dropped, anything else kept
+                        match chars.next() {
+                            Some('\n') | None => {}
+                            Some(c) => value.push(c),
+                        }
+                    }
+                    Some(']') => break,
+                    Some(c) => value.push(c),
+                    None => return Err(invalid(format!("unterminated value of {}", id))),
+                }
+            }
+            values.push(value);
+        }
+        if values.is_empty() {
+            return Err(invalid(format!("property {} has no value", id)));
+        }
+        properties.push((id, values));
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace(']', "\\]")
+}
+
+fn invalid(message: impl Into<String>) -> GameYError {
+    GameYError::InvalidSgf {
+        message: message.into(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sgf_round_trip() {
+        let mut game = GameY::with_topology(4, BoardTopology::Hex)
+            .with_handicap(&[Coordinates::new(1, 1, 0)])
+            .unwrap();
+        game.annotate("Handicap game");
+        game.play_move_list("0 {takes the corner} 15?? 3!").unwrap();
+        game.add_move(Movement::Action {
+            player: PlayerId::new(0),
+            action: GameAction::Resign,
+        })
+        .unwrap();
+
+        let sgf = game.to_sgf().unwrap();
+        assert!(sgf.contains("GM[11]SZ[4]TP[hex]RU[standard]HA[1]AB[bb]RE[W+R]"));
+        assert!(sgf.contains(";W[aa]C[takes the corner];B[dd]BM[2];W[da]TE[1];B[resign])"));
+        let read = GameY::from_sgf(&sgf).unwrap();
+        assert_eq!(read.topology(), BoardTopology::Hex);
+        assert_eq!(read.handicap(), game.handicap());
+        assert_eq!(read.history(), game.history());
+        assert_eq!(read.annotations(), game.annotations());
+    }
+
+    #[test]
+    fn test_read_foreign_records() {
+        // Variations past the first are skipped, escapes and unknown
+        // properties are handled
+        let sgf = "(;GM[11]SZ[3]PB[Alice]C[a \\\\ b \\] c]\n  (;B[aa];W[swap-pieces])(;B[cc]))";
+        let game = GameY::from_sgf(sgf).unwrap();
+        assert_eq!(game.topology(), BoardTopology::Hex);
+        assert_eq!(game.history().len(), 2);
+        assert_eq!(game.annotations()[0].text, "a \\ b ] c");
+        assert_eq!(game.metadata().player_name(PlayerId::new(0)), Some("Alice"));
+        // Y is the default shape
+        assert_eq!(
+            GameY::from_sgf("(;SZ[2];B[aa])").unwrap().topology(),
+            BoardTopology::Y
+        );
+    }
+
+    #[test]
+    fn test_metadata_round_trip() {
+        let mut game = GameY::new(3);
+        let metadata = GameMetadata::default()
+            .with_event("Open [final]")
+            .with_date("2026-03-14")
+            .with_player(PlayerId::new(0), "Ada")
+            .with_player(PlayerId::new(1), "Grace")
+            .with_rating(PlayerId::new(1), 1650.5)
+            .with_result("W+T");
+        game.set_metadata(metadata.clone());
+
+        let sgf = game.to_sgf().unwrap();
+        assert!(sgf.contains("EV[Open [final\\]]DT[2026-03-14]PB[Ada]PW[Grace]WR[1650.5]RE[W+T]"));
+        assert_eq!(GameY::from_sgf(&sgf).unwrap().metadata(), &metadata);
+    }
+
+    #[test]
+    fn test_invalid_records() {
+        for sgf in [
+            "",
+            "(;SZ[3]",
+            "(;B[aa])",
+            "(;SZ[0])",
+            "(;GM[1]SZ[19])",
+            "(;SZ[3];B[zz])",
+            "(;SZ[3];B[aa];W[aa])",
+            "(;SZ[3]C[unterminated)",
+        ] {
+            assert!(GameY::from_sgf(sgf).is_err(), "{}", sgf);
+        }
+    }
+}
@@ -0,0 +1,534 @@
+//! Strict validation of YEN documents.
+//!
+//! Loading a YEN with [`GameY::try_from`](crate::GameY) stops at the first
+//! problem and only checks what it needs to rebuild the board. [`YEN::validate`]
+//! instead checks everything a well-formed position must satisfy and reports
+//! every problem at once, each with a stable [`ValidationCode`]:
+//!
+//! - the layout has one row per board row, each of the right length
+//! - the layout only uses `B`, `R`, `.` and `#`, with `#` exactly on the
+//!   cells blocked by the ruleset and `B` on every handicap cell
+//! - both players have a plausible number of stones and `turn` names the
+//!   player whose move it is
+//! - the position can be reached in a game, that is, no stone was placed
+//!   after the game had already been decided
+//!
+//! The checks assume the game was played by placing stones only, as the
+//! engine does not record passes or swaps in YEN.
+
+use crate::{BoardTopology, Coordinates, GameYError, MAX_BOARD_SIZE, PlayerId, YEN, YEN_VERSION};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+
+/// A machine-readable code identifying the kind of problem found in a YEN.
+///
+/// Codes are serialized in `SCREAMING_SNAKE_CASE` and are stable across
+/// versions, so clients may match on them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ValidationCode {
+    /// The document is not valid JSON or lacks required fields.
+    InvalidJson,
+    /// The document was written by a newer version of the format.
+    UnsupportedVersion,
+    /// The board size is zero or above [`MAX_BOARD_SIZE`].
+    InvalidSize,
+    /// The player symbols are not `["B", "R"]`.
+    InvalidPlayers,
+    /// The `turn` field does not name one of the two players.
+    InvalidTurn,
+    /// The layout does not have one row per board row.
+    LayoutRowCount,
+    /// A layout row does not have as many cells as the board row.
+    LayoutRowLength,
+    /// The layout contains a character that is not a cell symbol.
+    InvalidChar,
+    /// A blocked cell is not marked `#`, or a playable cell is.
+    BlockedCell,
+    /// A handicap stone is off the board, repeated, blocked or missing from
+    /// the layout.
+    InvalidHandicap,
+    /// The number of stones of each player cannot occur in a game.
+    StoneParity,
+    /// The `turn` field does not match the number of stones on the board.
+    TurnMismatch,
+    /// The position cannot be reached because play went on after the game
+    /// was decided.
+    UnreachablePosition,
+}
+
+impl ValidationCode {
+    /// Returns the code as written in JSON, e.g. `"STONE_PARITY"`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ValidationCode::InvalidJson => "INVALID_JSON",
+            ValidationCode::UnsupportedVersion => "UNSUPPORTED_VERSION",
+            ValidationCode::InvalidSize => "INVALID_SIZE",
+            ValidationCode::InvalidPlayers => "INVALID_PLAYERS",
+            ValidationCode::InvalidTurn => "INVALID_TURN",
+            ValidationCode::LayoutRowCount => "LAYOUT_ROW_COUNT",
+            ValidationCode::LayoutRowLength => "LAYOUT_ROW_LENGTH",
+            ValidationCode::InvalidChar => "INVALID_CHAR",
+            ValidationCode::BlockedCell => "BLOCKED_CELL",
+            ValidationCode::InvalidHandicap => "INVALID_HANDICAP",
+            ValidationCode::StoneParity => "STONE_PARITY",
+            ValidationCode::TurnMismatch => "TURN_MISMATCH",
+            ValidationCode::UnreachablePosition => "UNREACHABLE_POSITION",
+        }
+    }
+}
+
+impl Display for ValidationCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A single problem found while validating a YEN.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValidationIssue {
+    /// What kind of problem this is.
+    pub code: ValidationCode,
+    /// A human-readable description of the problem.
+    pub message: String,
+}
+
+impl ValidationIssue {
+    /// Creates an issue with the given code and message.
+    pub fn new(code: ValidationCode, message: impl Into<String>) -> Self {
+        ValidationIssue {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.code, self.message)
+    }
+}
+
+impl YEN {
+    /// Checks the document strictly and returns every problem found.
+    ///
+    /// An empty result means the YEN describes a position that can occur in
+    /// a game. Checks that depend on the layout shape are skipped when the
+    /// shape is wrong, and reachability is only checked once the stones
+    /// themselves are valid.
+    pub fn validate(&self) -> Vec<ValidationIssue> {
+        let mut issues = Vec::new();
+        if self.version() > YEN_VERSION {
+            issues.push(ValidationIssue::new(
+                ValidationCode::UnsupportedVersion,
+                format!(
+                    "version {} is newer than the supported version {}",
+                    self.version(),
+                    YEN_VERSION
+                ),
+            ));
+        }
+        if self.size() == 0 || self.size() > MAX_BOARD_SIZE {
+            issues.push(ValidationIssue::new(
+                ValidationCode::InvalidSize,
+                format!(
+                    "the board size must be from 1 to {}, found {}",
+                    MAX_BOARD_SIZE,
+                    self.size()
+                ),
+            ));
+            return issues;
+        }
+        if self.players() != ['B', 'R'] {
+            issues.push(ValidationIssue::new(
+                ValidationCode::InvalidPlayers,
+                format!("players must be [\"B\", \"R\"], found {:?}", self.players()),
+            ));
+        }
+        if self.turn() > 1 {
+            issues.push(ValidationIssue::new(
+                ValidationCode::InvalidTurn,
+                format!("turn must be 0 or 1, found {}", self.turn()),
+            ));
+        }
+
+        let topology = self.topology();
+        let size = self.size();
+        let blocked: HashSet<Coordinates> = self
+            .ruleset()
+            .blocked_cells(topology, size)
+            .into_iter()
+            .collect();
+        let mut handicap = HashSet::new();
+        for &coords in self.handicap() {
+            let problem = if !topology.contains(coords, size) {
+                Some("is outside the board")
+            } else if blocked.contains(&coords) {
+                Some("is on a blocked cell")
+            } else if !handicap.insert(coords) {
+                Some("is listed twice")
+            } else {
+                None
+            };
+            if let Some(problem) = problem {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::InvalidHandicap,
+                    format!("handicap stone at {} {}", coords, problem),
+                ));
+            }
+        }
+
+        let Some(stones) = self.read_stones(&blocked, &handicap, &mut issues) else {
+            return issues;
+        };
+        let count = |player: u32| {
+            stones
+                .iter()
+                .filter(|(coords, p)| p.id() == player && !handicap.contains(coords))
+                .count()
+        };
+        let (blue, red) = (count(0), count(1));
+        // After the handicap the second player moves first
+        let (first, second) = if handicap.is_empty() {
+            (blue, red)
+        } else {
+            (red, blue)
+        };
+        let first_mover = if handicap.is_empty() { 0 } else { 1 };
+        let last_mover = if first == second + 1 {
+            first_mover
+        } else if first == second {
+            1 - first_mover
+        } else {
+            issues.push(ValidationIssue::new(
+                ValidationCode::StoneParity,
+                format!(
+                    "B has {} and R has {} stones besides the handicap, \
+                     which cannot happen when players alternate",
+                    blue, red
+                ),
+            ));
+            return issues;
+        };
+
+        let connected: Vec<u32> = (0..2)
+            .filter(|&player| has_connection(topology, size, &stones, player, None))
+            .collect();
+        match connected.as_slice() {
+            [] => {
+                let next = 1 - last_mover;
+                if self.turn() <= 1 && self.turn() != next {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::TurnMismatch,
+                        format!(
+                            "turn is {} but the stones on the board say it is {}'s move",
+                            self.turn(),
+                            self.players().get(next as usize).unwrap_or(&'?')
+                        ),
+                    ));
+                }
+            }
+            [player] => {
+                let player = *player;
+                if player != last_mover {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::UnreachablePosition,
+                        format!(
+                            "player {} connects the sides but did not make the last move",
+                            player
+                        ),
+                    ));
+                } else if !stones.iter().any(|(coords, p)| {
+                    p.id() == player
+                        && !handicap.contains(coords)
+                        && !has_connection(topology, size, &stones, player, Some(*coords))
+                }) {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::UnreachablePosition,
+                        format!(
+                            "player {} already connected the sides before their last move",
+                            player
+                        ),
+                    ));
+                }
+            }
+            _ => issues.push(ValidationIssue::new(
+                ValidationCode::UnreachablePosition,
+                "both players connect the sides",
+            )),
+        }
+        issues
+    }
+
+    /// Reads the stones of the layout, recording problems with its shape and
+    /// characters. Returns `None` if the stones cannot be trusted.
+    fn read_stones(
+        &self,
+        blocked: &HashSet<Coordinates>,
+        handicap: &HashSet<Coordinates>,
+        issues: &mut Vec<ValidationIssue>,
+    ) -> Option<HashMap<Coordinates, PlayerId>> {
+        let board_rows = self.topology().rows(self.size());
+        let rows: Vec<&str> = self.layout().split('/').collect();
+        let before = issues.len();
+        if rows.len() != board_rows.len() {
+            issues.push(ValidationIssue::new(
+                ValidationCode::LayoutRowCount,
+                format!(
+                    "the layout has {} rows, expected {}",
+                    rows.len(),
+                    board_rows.len()
+                ),
+            ));
+        }
+        for (row, (row_str, board_row)) in rows.iter().zip(&board_rows).enumerate() {
+            let found = row_str.chars().count();
+            if found != board_row.len() {
+                issues.push(ValidationIssue::new(
+                    ValidationCode::LayoutRowLength,
+                    format!(
+                        "row {} has {} cells, expected {}",
+                        row,
+                        found,
+                        board_row.len()
+                    ),
+                ));
+            }
+        }
+        if issues.len() > before {
+            return None;
+        }
+
+        let mut stones = HashMap::new();
+        for (row, (row_str, board_row)) in rows.iter().zip(&board_rows).enumerate() {
+            for (col, (cell, &coords)) in row_str.chars().zip(board_row).enumerate() {
+                let at = format!("row {}, column {}", row, col);
+                match cell {
+                    'B' | 'R' | '.' | '#' => {}
+                    _ => {
+                        issues.push(ValidationIssue::new(
+                            ValidationCode::InvalidChar,
+                            format!("invalid character '{}' at {}", cell, at),
+                        ));
+                        continue;
+                    }
+                }
+                if handicap.contains(&coords) && cell != 'B' {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::InvalidHandicap,
+                        format!("handicap cell at {} holds '{}' instead of 'B'", at, cell),
+                    ));
+                } else if blocked.contains(&coords) && cell != '#' {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::BlockedCell,
+                        format!("blocked cell at {} holds '{}' instead of '#'", at, cell),
+                    ));
+                } else if !blocked.contains(&coords) && cell == '#' {
+                    issues.push(ValidationIssue::new(
+                        ValidationCode::BlockedCell,
+                        format!("'#' at {} marks a cell that is not blocked", at),
+                    ));
+                }
+                match cell {
+                    'B' => {
+                        stones.insert(coords, PlayerId::new(0));
+                    }
+                    'R' => {
+                        stones.insert(coords, PlayerId::new(1));
+                    }
+                    _ => {}
+                }
+            }
+        }
+        (issues.len() == before).then_some(stones)
+    }
+}
+
+/// Returns true if `player` has a chain touching every goal side, ignoring
+/// the stone at `without` if given.
+fn has_connection(
+    topology: BoardTopology,
+    size: u32,
+    stones: &HashMap<Coordinates, PlayerId>,
+    player: u32,
+    without: Option<Coordinates>,
+) -> bool {
+    let owner = PlayerId::new(player);
+    let is_own = |coords: &Coordinates| {
+        Some(*coords) != without && stones.get(coords).is_some_and(|p| p.id() == player)
+    };
+    let mut seen = HashSet::new();
+    for start in stones.keys().filter(|coords| is_own(coords)) {
+        if !seen.insert(*start) {
+            continue;
+        }
+        let mut sides = [false; 3];
+        let mut stack = vec![*start];
+        while let Some(coords) = stack.pop() {
+            let touched = topology.sides_touched(coords, size, owner);
+            for (side, touches) in sides.iter_mut().zip(touched) {
+                *side |= touches;
+            }
+            for neighbor in topology.neighbors(coords, size) {
+                if is_own(&neighbor) && seen.insert(neighbor) {
+                    stack.push(neighbor);
+                }
+            }
+        }
+        if sides.iter().all(|&side| side) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Parses and strictly validates a YEN document given as JSON text.
+///
+/// Documents from older format versions are upgraded first, as with
+/// [`YEN::from_json`]. Problems that prevent parsing are reported as a
+/// single [`ValidationCode::InvalidJson`] or
+/// [`ValidationCode::UnsupportedVersion`] issue.
+pub fn validate_yen_json(json: &str) -> Vec<ValidationIssue> {
+    match YEN::from_json(json) {
+        Ok(yen) => yen.validate(),
+        Err(GameYError::UnsupportedVersion {
+            found, supported, ..
+        }) => vec![ValidationIssue::new(
+            ValidationCode::UnsupportedVersion,
+            format!(
+                "version {} is not supported, the newest supported version is {}",
+                found, supported
+            ),
+        )],
+        Err(err) => vec![ValidationIssue::new(
+            ValidationCode::InvalidJson,
+            err.to_string(),
+        )],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{GameY, Movement, Ruleset};
+
+    fn codes(yen: YEN) -> Vec<ValidationCode> {
+        yen.validate().into_iter().map(|issue| issue.code).collect()
+    }
+
+    fn yen(size: u32, turn: u32, layout: &str) -> YEN {
+        YEN::new(size, turn, vec!['B', 'R'], layout.to_string())
+    }
+
+    #[test]
+    fn test_positions_from_games_are_valid() {
+        let mut game = GameY::new(4);
+        for (i, index) in [0, 4, 7, 9].into_iter().enumerate() {
+            game.add_move(Movement::Placement {
+                player: PlayerId::new(i as u32 % 2),
+                coords: Coordinates::from_index(index, 4),
+            })
+            .unwrap();
+            assert!(YEN::from(&game).validate().is_empty());
+        }
+        assert!(YEN::from(&GameY::new(4)).validate().is_empty());
+        let master = GameY::with_ruleset(5, Ruleset::MasterY);
+        assert!(YEN::from(&master).validate().is_empty());
+        let handicap = GameY::new_with_handicap(5, &GameY::handicap_points(5, 2).unwrap()).unwrap();
+        assert!(YEN::from(&handicap).validate().is_empty());
+    }
+
+    #[test]
+    fn test_reports_every_layout_problem() {
+        assert_eq!(
+            codes(yen(3, 0, "B/BR/.R")),
+            vec![ValidationCode::LayoutRowLength]
+        );
+        assert_eq!(
+            codes(yen(3, 0, "B/BR")),
+            vec![ValidationCode::LayoutRowCount]
+        );
+        let issues = yen(3, 0, "X/B#/.RZ").validate();
+        let found: Vec<_> = issues.iter().map(|issue| issue.code).collect();
+        assert_eq!(
+            found,
+            vec![
+                ValidationCode::InvalidChar,
+                ValidationCode::BlockedCell,
+                ValidationCode::InvalidChar
+            ]
+        );
+        assert!(issues[0].message.contains("row 0, column 0"));
+    }
+
+    #[test]
+    fn test_stone_parity_and_turn() {
+        assert_eq!(
+            codes(yen(3, 1, "B/B./...")),
+            vec![ValidationCode::StoneParity]
+        );
+        assert_eq!(
+            codes(yen(3, 1, "R/../...")),
+            vec![ValidationCode::StoneParity]
+        );
+        assert_eq!(
+            codes(yen(3, 0, "B/../...")),
+            vec![ValidationCode::TurnMismatch]
+        );
+        assert_eq!(
+            codes(yen(3, 2, "B/../...")),
+            vec![ValidationCode::InvalidTurn]
+        );
+        assert!(codes(yen(3, 1, "B/../...")).is_empty());
+        assert!(codes(yen(3, 0, "./../...")).is_empty());
+        assert_eq!(
+            codes(yen(3, 1, "./../...")),
+            vec![ValidationCode::TurnMismatch]
+        );
+    }
+
+    #[test]
+    fn test_header_problems() {
+        let players = YEN::new(2, 0, vec!['X', 'O'], "./..".to_string());
+        assert_eq!(codes(players), vec![ValidationCode::InvalidPlayers]);
+        assert_eq!(codes(yen(0, 0, "")), vec![ValidationCode::InvalidSize]);
+        assert_eq!(
+            codes(yen(MAX_BOARD_SIZE + 1, 0, "")),
+            vec![ValidationCode::InvalidSize]
+        );
+        let handicap = yen(3, 0, "./../...").with_handicap(vec![Coordinates::new(5, 0, 0)]);
+        assert_eq!(codes(handicap), vec![ValidationCode::InvalidHandicap]);
+    }
+
+    #[test]
+    fn test_unreachable_positions() {
+        // A finished game where the winner made the last move
+        assert!(codes(yen(2, 1, "B/BR")).is_empty());
+        // B connects the sides but R moved last
+        assert_eq!(
+            codes(yen(3, 0, "B/BR/BRR")),
+            vec![ValidationCode::UnreachablePosition]
+        );
+        // Every B stone can be removed and B stays connected
+        let hex = yen(4, 1, "BRRB/BRRB/BRRB/BR.B").with_topology(BoardTopology::Hex);
+        assert_eq!(codes(hex), vec![ValidationCode::UnreachablePosition]);
+    }
+
+    #[test]
+    fn test_validate_yen_json() {
+        let issues = validate_yen_json("{\"size\": 3");
+        assert_eq!(issues[0].code, ValidationCode::InvalidJson);
+        let issues = validate_yen_json(
+            r#"{"version": 99, "size": 1, "turn": 0, "players": ["B", "R"], "layout": "."}"#,
+        );
+        assert_eq!(issues[0].code, ValidationCode::UnsupportedVersion);
+        let issues =
+            validate_yen_json(r#"{"size": 1, "turn": 0, "players": ["B", "R"], "layout": "."}"#);
+        assert!(issues.is_empty());
+        assert_eq!(
+            serde_json::to_value(ValidationCode::StoneParity).unwrap(),
+            "STONE_PARITY"
+        );
+    }
+}
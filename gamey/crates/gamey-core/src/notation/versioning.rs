@@ -0,0 +1,86 @@
+use crate::GameYError;
+use serde_json::Value;
+
+/// A step that upgrades a JSON document from one version to the next.
+pub type Migration = fn(&mut Value);
+
+/// Upgrades a versioned JSON document in place to the `current` version.
+///
+/// The version is read from the top-level `version` field; documents without
+/// it are taken to be version 1, which predates versioning. `migrations[i]`
+/// upgrades a document from version `i + 1` to version `i + 2`, so there must
+/// be `current - 1` of them. On success the `version` field holds `current`.
+///
+/// # Errors
+/// Returns `GameYError::UnsupportedVersion` if the document was written by a
+/// newer version of the crate, or has an invalid version number.
+pub fn migrate(
+    value: &mut Value,
+    format: &str,
+    current: u32,
+    migrations: &[Migration],
+) -> Result<(), GameYError> {
+    debug_assert_eq!(migrations.len() as u32 + 1, current);
+    let found = match value.get("version") {
+        None => 1,
+        Some(v) => v.as_u64().and_then(|v| u32::try_from(v).ok()).unwrap_or(0),
+    };
+    if found == 0 || found > current {
+        return Err(GameYError::UnsupportedVersion {
+            format: format.to_string(),
+            found,
+            supported: current,
+        });
+    }
+    for migration in &migrations[(found - 1) as usize..] {
+        migration(value);
+    }
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), Value::from(current));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn add_flag(value: &mut Value) {
+        value["flag"] = json!(true);
+    }
+
+    #[test]
+    fn test_unversioned_documents_are_migrated() {
+        let mut value = json!({ "a": 1 });
+        migrate(&mut value, "test", 2, &[add_flag]).unwrap();
+        assert_eq!(value, json!({ "a": 1, "flag": true, "version": 2 }));
+    }
+
+    #[test]
+    fn test_current_documents_are_untouched() {
+        let mut value = json!({ "a": 1, "version": 2 });
+        migrate(&mut value, "test", 2, &[add_flag]).unwrap();
+        assert_eq!(value, json!({ "a": 1, "version": 2 }));
+    }
+
+    #[test]
+    fn test_future_versions_are_rejected() {
+        let mut value = json!({ "version": 3 });
+        let err = migrate(&mut value, "test", 2, &[add_flag]).unwrap_err();
+        assert!(matches!(
+            err,
+            GameYError::UnsupportedVersion {
+                found: 3,
+                supported: 2,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_invalid_versions_are_rejected() {
+        let mut value = json!({ "version": "two" });
+        assert!(migrate(&mut value, "test", 2, &[add_flag]).is_err());
+    }
+}
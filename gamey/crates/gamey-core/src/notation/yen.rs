@@ -0,0 +1,708 @@
+use crate::{
+    Annotation, BoardTopology, Coordinates, GameMetadata, GameY, GameYError, Integrity, Migration,
+    MoveRecord, PLAYER_COUNT, PlayerId, Ruleset, migrate,
+};
+use serde::{Deserialize, Serialize};
+
+/// The current version of the YEN format.
+///
+/// Version 1 is the original format, which had no `version` field. Version 2
+/// adds the `version` field itself and the optional `handicap`, `ruleset` and
+/// `topology` fields.
+pub const YEN_VERSION: u32 = 2;
+
+/// Upgrades from each older YEN version, see [`migrate`].
+const YEN_MIGRATIONS: [Migration; 1] = [
+    // Version 1 positions are standard Y games, which version 2 reads as is
+    |_| {},
+];
+
+/// Y Exchange Notation (YEN) - a compact format for representing Y game states.
+///
+/// YEN is inspired by FEN (Forsyth-Edwards Notation) used in chess. It provides
+/// a simple JSON-serializable format for storing and exchanging game positions.
+///
+/// # Format
+/// - `version`: The format version, see [`YEN_VERSION`]
+/// - `size`: The board size (length of one side of the triangle)
+/// - `turn`: Which player's turn it is (0 or 1)
+/// - `players`: Character symbols for each player (e.g., ['B', 'R'] for Blue/Red)
+/// - `layout`: A compact string where rows are separated by '/', and cells are
+///   represented by player symbols or '.' for empty cells
+/// - `handicap` (optional): Cells holding handicap stones of the first player,
+///   placed before the first move. These cells also appear in the layout.
+/// - `ruleset` (optional): The variant being played, omitted for standard Y.
+///   Cells blocked by the ruleset are written as '#' in the layout.
+/// - `topology` (optional): `"hex"` for a Hex position, omitted for Y. Hex
+///   layouts have `size` rows of `size` cells each.
+/// - `result` (optional): The result of a finished game, `B+` or `W+` for a
+///   win of the first or second player with `R` appended for a resignation,
+///   see [`GameY::result`]. On a finished game `turn` is the player who
+///   would have moved after the last move.
+/// - `history` (optional): The moves that led to the position, written by
+///   saved games so that loading replays them in order. A history that
+///   does not lead to the layout is ignored.
+/// - `annotations` (optional): Comments and glyphs attached to the history.
+/// - `metadata` (optional): The event, date, players and result of the game,
+///   see [`GameMetadata`].
+/// - `integrity` (optional): A digest of the rest of the document, see
+///   [`YEN::seal`].
+///
+/// # Example
+/// ```json
+/// {
+///   "size": 3,
+///   "turn": 0,
+///   "players": ["B", "R"],
+///   "layout": "B/BR/.R."
+/// }
+/// ```
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct YEN {
+    /// The format version; YEN sent without one is taken to be current.
+    #[serde(default = "current_yen_version")]
+    version: u32,
+    /// The board size (length of one side of the triangle).
+    size: u32,
+    /// The index of the player whose turn it is (0-indexed).
+    turn: u32,
+    /// Character symbols representing each player.
+    players: Vec<char>,
+    /// A compact string representation of the board.
+    ///
+    /// Rows are separated by '/', with cells represented by player symbols
+    /// or '.' for empty cells. Example: "B/..R/.B.R"
+    layout: String,
+    /// Handicap stones of the first player, placed before the first move.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    handicap: Vec<Coordinates>,
+    /// The variant being played.
+    #[serde(default, skip_serializing_if = "Ruleset::is_standard")]
+    ruleset: Ruleset,
+    /// The shape of the board.
+    #[serde(default, skip_serializing_if = "BoardTopology::is_y")]
+    topology: BoardTopology,
+    /// The result of the game, if it is finished.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<String>,
+    /// The moves that led to the position, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<MoveRecord>,
+    /// Comments and glyphs attached to the history.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    annotations: Vec<Annotation>,
+    /// The event, date, players and result of the game.
+    #[serde(default, skip_serializing_if = "GameMetadata::is_empty")]
+    metadata: GameMetadata,
+    /// A digest of the rest of the document.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    integrity: Option<Integrity>,
+}
+
+impl YEN {
+    /// Creates a new YEN representation.
+    ///
+    /// # Arguments
+    /// * `size` - The board size
+    /// * `turn` - Index of the player to move (0 or 1)
+    /// * `players` - Character symbols for each player
+    /// * `layout` - The board layout string
+    pub fn new(size: u32, turn: u32, players: Vec<char>, layout: String) -> Self {
+        YEN {
+            version: YEN_VERSION,
+            size,
+            turn,
+            players,
+            layout,
+            handicap: Vec::new(),
+            ruleset: Ruleset::Standard,
+            topology: BoardTopology::Y,
+            result: None,
+            history: Vec::new(),
+            annotations: Vec::new(),
+            metadata: GameMetadata::default(),
+            integrity: None,
+        }
+    }
+
+    /// Creates a YEN representation like [`YEN::new`], checking that it
+    /// describes a standard Y board.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidBoardSize` for sizes no game can be
+    /// played on, `GameYError::InvalidNumPlayers` unless there is a symbol
+    /// for each player, `GameYError::UnknownPlayer` if `turn` has no
+    /// symbol, `GameYError::InvalidYENLayout` or
+    /// `GameYError::InvalidYENLayoutLine` if the layout does not fit the
+    /// board, and `GameYError::InvalidCharInLayout` for cells that are
+    /// neither '.' nor a player symbol.
+    pub fn try_new(
+        size: u32,
+        turn: u32,
+        players: Vec<char>,
+        layout: String,
+    ) -> Result<Self, GameYError> {
+        GameY::check_board_size(size)?;
+        if players.len() as u32 != PLAYER_COUNT {
+            return Err(GameYError::InvalidNumPlayers {
+                num_players: players.len() as u32,
+                expected: PLAYER_COUNT,
+            });
+        }
+        let yen = YEN::new(size, turn, players, layout);
+        yen.symbol(PlayerId::new(turn))?;
+        let rows: Vec<&str> = yen.layout.split('/').collect();
+        if rows.len() as u32 != size {
+            return Err(GameYError::InvalidYENLayout {
+                expected: size,
+                found: rows.len() as u32,
+            });
+        }
+        for (row, cells) in rows.iter().enumerate() {
+            let found = cells.chars().count() as u32;
+            if found != row as u32 + 1 {
+                return Err(GameYError::InvalidYENLayoutLine {
+                    expected: row as u32 + 1,
+                    found,
+                    line: row as u32,
+                });
+            }
+            if let Some((col, char)) = cells
+                .chars()
+                .enumerate()
+                .find(|(_, cell)| *cell != '.' && !yen.players.contains(cell))
+            {
+                return Err(GameYError::InvalidCharInLayout { char, row, col });
+            }
+        }
+        Ok(yen)
+    }
+
+    /// Creates the YEN of an empty standard Y board of the given size, the
+    /// first player to move. Stones are added with [`YEN::with_move`].
+    ///
+    /// Other boards are best written from a game, e.g.
+    /// `YEN::from(&GameY::with_topology(size, BoardTopology::Hex))`.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidBoardSize` for sizes no game can be
+    /// played on, see [`GameY::check_board_size`].
+    ///
+    /// # Example
+    /// ```
+    /// use gamey_core::{Coordinates, PlayerId, YEN};
+    ///
+    /// let yen = YEN::empty(3)
+    ///     .unwrap()
+    ///     .with_move(Coordinates::new(2, 0, 0), PlayerId::new(0))
+    ///     .unwrap()
+    ///     .with_move(Coordinates::new(0, 1, 1), PlayerId::new(1))
+    ///     .unwrap();
+    /// assert_eq!(yen.layout(), "B/../.R.");
+    /// assert_eq!(yen.turn(), 0);
+    /// ```
+    pub fn empty(size: u32) -> Result<Self, GameYError> {
+        GameY::check_board_size(size)?;
+        let rows: Vec<String> = (1..=size).map(|cells| ".".repeat(cells as usize)).collect();
+        Ok(YEN::new(size, 0, vec!['B', 'R'], rows.join("/")))
+    }
+
+    /// Places a stone of `player` at `coords` and gives the turn to the
+    /// other player; use [`YEN::set_turn`] to give it to someone else.
+    ///
+    /// # Errors
+    /// Returns `GameYError::CoordOutOfRange` if the cell is not on the
+    /// board, `GameYError::UnknownPlayer` if `player` has no symbol,
+    /// `GameYError::Occupied` or `GameYError::BlockedCell` if the cell is
+    /// not empty, and `GameYError::InvalidYENLayout` or
+    /// `GameYError::InvalidYENLayoutLine` if the layout does not fit the
+    /// board.
+    pub fn with_move(mut self, coords: Coordinates, player: PlayerId) -> Result<Self, GameYError> {
+        self.topology.check_coords(coords, self.size)?;
+        let symbol = self.symbol(player)?;
+        let mut rows: Vec<Vec<char>> = self
+            .layout
+            .split('/')
+            .map(|row| row.chars().collect())
+            .collect();
+        if rows.len() as u32 != self.size {
+            return Err(GameYError::InvalidYENLayout {
+                expected: self.size,
+                found: rows.len() as u32,
+            });
+        }
+        let (row, col) = match self.topology {
+            BoardTopology::Y => (self.size - 1 - coords.x(), coords.y()),
+            BoardTopology::Hex => (coords.x(), coords.y()),
+        };
+        let expected = match self.topology {
+            BoardTopology::Y => row + 1,
+            BoardTopology::Hex => self.size,
+        };
+        let cells = &mut rows[row as usize];
+        if cells.len() as u32 != expected {
+            return Err(GameYError::InvalidYENLayoutLine {
+                expected,
+                found: cells.len() as u32,
+                line: row,
+            });
+        }
+        match cells[col as usize] {
+            '.' => cells[col as usize] = symbol,
+            '#' => {
+                return Err(GameYError::BlockedCell {
+                    coordinates: coords,
+                    ruleset: self.ruleset,
+                });
+            }
+            _ => {
+                return Err(GameYError::Occupied {
+                    coordinates: coords,
+                    player,
+                });
+            }
+        }
+        let rows: Vec<String> = rows.into_iter().map(String::from_iter).collect();
+        self.layout = rows.join("/");
+        self.turn = player.opponent().id();
+        Ok(self)
+    }
+
+    /// Gives the turn to `player`.
+    ///
+    /// # Errors
+    /// Returns `GameYError::UnknownPlayer` if `player` has no symbol.
+    pub fn set_turn(&mut self, player: PlayerId) -> Result<(), GameYError> {
+        self.symbol(player)?;
+        self.turn = player.id();
+        Ok(())
+    }
+
+    /// Returns the layout symbol of `player`.
+    fn symbol(&self, player: PlayerId) -> Result<char, GameYError> {
+        self.players
+            .get(player.id() as usize)
+            .copied()
+            .ok_or(GameYError::UnknownPlayer {
+                player,
+                players: self.players.len() as u32,
+            })
+    }
+
+    /// Parses a YEN document, upgrading it from older format versions.
+    ///
+    /// Unlike plain deserialization, a document without a `version` field is
+    /// treated as version 1, as written before versioning existed.
+    ///
+    /// # Errors
+    /// Returns `GameYError::UnsupportedVersion` for documents written by a
+    /// newer version of the crate, or `GameYError::SerdeError` if the JSON
+    /// is malformed.
+    pub fn from_json(json: &str) -> Result<Self, GameYError> {
+        let mut value: serde_json::Value =
+            serde_json::from_str(json).map_err(|e| GameYError::SerdeError { error: e })?;
+        migrate(&mut value, "YEN", YEN_VERSION, &YEN_MIGRATIONS)?;
+        serde_json::from_value(value).map_err(|e| GameYError::SerdeError { error: e })
+    }
+
+    /// Returns the format version of this document.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Attaches an integrity digest of the document, an HMAC keyed with `key`
+    /// if one is given or a plain SHA-256 hash otherwise.
+    ///
+    /// The digest covers the canonical JSON form of the whole document
+    /// (minus the digest), including the move history when there is one.
+    pub fn seal(mut self, key: Option<&[u8]>) -> Result<Self, GameYError> {
+        self.integrity = None;
+        let content = self.canonical_content()?;
+        self.integrity = Some(Integrity::compute(&content, key));
+        Ok(self)
+    }
+
+    /// Checks the integrity digest of the document.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IntegrityCheckFailed` if the document has no
+    /// digest, if it was modified after sealing, or if it was sealed with a
+    /// key and `key` does not match.
+    pub fn verify_integrity(&self, key: Option<&[u8]>) -> Result<(), GameYError> {
+        let integrity = self
+            .integrity
+            .as_ref()
+            .ok_or_else(|| GameYError::IntegrityCheckFailed {
+                reason: "the game has no integrity field".to_string(),
+            })?;
+        integrity.verify(&self.canonical_content()?, key)
+    }
+
+    /// Returns the integrity digest, if the document was sealed.
+    pub fn integrity(&self) -> Option<&Integrity> {
+        self.integrity.as_ref()
+    }
+
+    fn canonical_content(&self) -> Result<Vec<u8>, GameYError> {
+        let unsealed = YEN {
+            integrity: None,
+            ..self.clone()
+        };
+        serde_json::to_vec(&unsealed).map_err(|e| GameYError::SerdeError { error: e })
+    }
+
+    /// Sets the handicap stones of the first player.
+    pub fn with_handicap(mut self, handicap: Vec<Coordinates>) -> Self {
+        self.handicap = handicap;
+        self
+    }
+
+    /// Sets the ruleset of the game.
+    pub fn with_ruleset(mut self, ruleset: Ruleset) -> Self {
+        self.ruleset = ruleset;
+        self
+    }
+
+    /// Sets the shape of the board.
+    pub fn with_topology(mut self, topology: BoardTopology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Sets the result of a finished game, e.g. `"W+R"`; see
+    /// [`GameY::result`].
+    pub fn with_result(mut self, result: Option<String>) -> Self {
+        self.result = result;
+        self
+    }
+
+    /// Sets the moves that led to the position and the annotations made
+    /// on them.
+    pub fn with_history(mut self, history: Vec<MoveRecord>, annotations: Vec<Annotation>) -> Self {
+        self.history = history;
+        self.annotations = annotations;
+        self
+    }
+
+    /// Sets the event, date, players and result of the game.
+    pub fn with_metadata(mut self, metadata: GameMetadata) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Returns the result of the game, or `None` if it is not finished.
+    pub fn result(&self) -> Option<&str> {
+        self.result.as_deref()
+    }
+
+    /// Returns the moves that led to the position, empty if unknown.
+    pub fn history(&self) -> &[MoveRecord] {
+        &self.history
+    }
+
+    /// Returns the comments and glyphs attached to the history.
+    pub fn annotations(&self) -> &[Annotation] {
+        &self.annotations
+    }
+
+    /// Returns the event, date, players and result of the game.
+    pub fn metadata(&self) -> &GameMetadata {
+        &self.metadata
+    }
+
+    /// Returns the board layout string.
+    pub fn layout(&self) -> &str {
+        &self.layout
+    }
+
+    /// Returns the board size.
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    /// Returns the index of the player whose turn it is.
+    pub fn turn(&self) -> u32 {
+        self.turn
+    }
+
+    /// Returns the player symbols.
+    pub fn players(&self) -> &[char] {
+        &self.players
+    }
+
+    /// Returns the handicap stones of the first player.
+    pub fn handicap(&self) -> &[Coordinates] {
+        &self.handicap
+    }
+
+    /// Returns the ruleset of the game.
+    pub fn ruleset(&self) -> Ruleset {
+        self.ruleset
+    }
+
+    /// Returns the shape of the board.
+    pub fn topology(&self) -> BoardTopology {
+        self.topology
+    }
+}
+
+fn current_yen_version() -> u32 {
+    YEN_VERSION
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "B/BR/.R.".to_string());
+        assert_eq!(yen.size(), 3);
+        assert_eq!(yen.turn(), 0);
+        assert_eq!(yen.layout(), "B/BR/.R.");
+        assert_eq!(yen.players(), &['B', 'R']);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "B/BR/.R.".to_string());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"size\":3"));
+        assert!(json.contains("\"turn\":0"));
+        assert!(json.contains("\"layout\":\"B/BR/.R.\""));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{
+            "size": 3,
+            "turn": 1,
+            "players": ["B", "R"],
+            "layout": "B/BR/.R."
+        }"#;
+        let yen: YEN = serde_json::from_str(json).unwrap();
+        assert_eq!(yen.size(), 3);
+        assert_eq!(yen.turn(), 1);
+        assert_eq!(yen.layout(), "B/BR/.R.");
+        assert_eq!(yen.players(), &['B', 'R']);
+    }
+
+    #[test]
+    fn test_builder() {
+        assert_eq!(YEN::empty(3).unwrap().layout(), "./../...");
+        assert!(matches!(
+            YEN::empty(0),
+            Err(GameYError::InvalidBoardSize { size: 0 })
+        ));
+        let mut yen = YEN::empty(3)
+            .unwrap()
+            .with_move(Coordinates::new(1, 1, 0), PlayerId::new(1))
+            .unwrap();
+        assert_eq!(yen.layout(), "./.R/...");
+        assert_eq!(yen.turn(), 0);
+        yen.set_turn(PlayerId::new(1)).unwrap();
+        assert_eq!(yen.turn(), 1);
+        assert!(matches!(
+            yen.set_turn(PlayerId::new(2)),
+            Err(GameYError::UnknownPlayer { .. })
+        ));
+        // The YEN loads as the game it describes
+        let game = GameY::try_from(yen.clone()).unwrap();
+        assert!(game.is_occupied(Coordinates::new(1, 1, 0)));
+
+        let occupied = yen
+            .clone()
+            .with_move(Coordinates::new(1, 1, 0), PlayerId::new(0));
+        assert!(matches!(occupied, Err(GameYError::Occupied { .. })));
+        let off_board = yen
+            .clone()
+            .with_move(Coordinates::new(3, 0, 0), PlayerId::new(0));
+        assert!(matches!(off_board, Err(GameYError::CoordOutOfRange { .. })));
+        let unknown = yen.with_move(Coordinates::new(2, 0, 0), PlayerId::new(2));
+        assert!(matches!(unknown, Err(GameYError::UnknownPlayer { .. })));
+    }
+
+    #[test]
+    fn test_checked_constructor() {
+        let yen = YEN::try_new(3, 1, vec!['B', 'R'], "B/.R/...".to_string()).unwrap();
+        assert_eq!(yen.layout(), "B/.R/...");
+        assert_eq!(yen.turn(), 1);
+
+        let checked = |size, turn, players: &[char], layout: &str| {
+            YEN::try_new(size, turn, players.to_vec(), layout.to_string())
+        };
+        assert!(matches!(
+            checked(0, 0, &['B', 'R'], ""),
+            Err(GameYError::InvalidBoardSize { size: 0 })
+        ));
+        assert!(matches!(
+            checked(2, 0, &['B'], "./.."),
+            Err(GameYError::InvalidNumPlayers { num_players: 1, .. })
+        ));
+        assert!(matches!(
+            checked(2, 2, &['B', 'R'], "./.."),
+            Err(GameYError::UnknownPlayer { .. })
+        ));
+        assert!(matches!(
+            checked(3, 0, &['B', 'R'], "./.."),
+            Err(GameYError::InvalidYENLayout {
+                expected: 3,
+                found: 2
+            })
+        ));
+        assert!(matches!(
+            checked(2, 0, &['B', 'R'], "./..."),
+            Err(GameYError::InvalidYENLayoutLine { line: 1, .. })
+        ));
+        assert!(matches!(
+            checked(2, 0, &['B', 'R'], "./.X"),
+            Err(GameYError::InvalidCharInLayout {
+                char: 'X',
+                row: 1,
+                col: 1
+            })
+        ));
+    }
+
+    #[test]
+    fn test_builder_on_other_boards() {
+        let hex = YEN::from(&GameY::with_topology(2, BoardTopology::Hex))
+            .with_move(Coordinates::new(1, 0, 0), PlayerId::new(0))
+            .unwrap();
+        assert_eq!(hex.layout(), "../B.");
+        let master = YEN::from(&GameY::with_ruleset(4, Ruleset::MasterY));
+        let blocked = master
+            .layout()
+            .split('/')
+            .enumerate()
+            .find_map(|(row, cells)| Some((row as u32, cells.find('#')? as u32)))
+            .unwrap();
+        let coords = Coordinates::new(3 - blocked.0, blocked.1, blocked.0 - blocked.1);
+        assert!(matches!(
+            master.with_move(coords, PlayerId::new(0)),
+            Err(GameYError::BlockedCell { .. })
+        ));
+    }
+
+    #[test]
+    fn test_handicap_omitted_when_empty() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(!json.contains("handicap"));
+    }
+
+    #[test]
+    fn test_handicap_roundtrip() {
+        let yen = YEN::new(3, 1, vec!['B', 'R'], "./B./...".to_string())
+            .with_handicap(vec![Coordinates::new(1, 0, 1)]);
+        let json = serde_json::to_string(&yen).unwrap();
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.handicap(), &[Coordinates::new(1, 0, 1)]);
+    }
+
+    #[test]
+    fn test_ruleset_defaults_to_standard() {
+        let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(!json.contains("ruleset"));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.ruleset(), Ruleset::Standard);
+    }
+
+    #[test]
+    fn test_ruleset_roundtrip() {
+        let yen = YEN::new(4, 0, vec!['B', 'R'], "#/../.../#..#".to_string())
+            .with_ruleset(Ruleset::MasterY);
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"ruleset\":\"master_y\""));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.ruleset(), Ruleset::MasterY);
+    }
+
+    #[test]
+    fn test_topology_roundtrip() {
+        let yen = YEN::new(2, 0, vec!['B', 'R'], "../..".to_string())
+            .with_topology(BoardTopology::Hex);
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains("\"topology\":\"hex\""));
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.topology(), BoardTopology::Hex);
+    }
+
+    #[test]
+    fn test_version_is_serialized() {
+        let yen = YEN::new(1, 0, vec!['B', 'R'], ".".to_string());
+        let json = serde_json::to_string(&yen).unwrap();
+        assert!(json.contains(&format!("\"version\":{}", YEN_VERSION)));
+    }
+
+    #[test]
+    fn test_from_json_upgrades_unversioned_files() {
+        let json = r#"{"size": 2, "turn": 0, "players": ["B", "R"], "layout": "./.."}"#;
+        let yen = YEN::from_json(json).unwrap();
+        assert_eq!(yen.version(), YEN_VERSION);
+        assert_eq!(yen.layout(), "./..");
+    }
+
+    #[test]
+    fn test_from_json_rejects_future_versions() {
+        let json = r#"{"version": 99, "size": 1, "turn": 0, "players": ["B", "R"], "layout": "."}"#;
+        assert!(matches!(
+            YEN::from_json(json),
+            Err(GameYError::UnsupportedVersion { found: 99, .. })
+        ));
+    }
+
+    #[test]
+    fn test_seal_and_verify() {
+        let yen = YEN::new(2, 0, vec!['B', 'R'], "B/..".to_string())
+            .seal(None)
+            .unwrap();
+        assert!(yen.verify_integrity(None).is_ok());
+
+        let json = serde_json::to_string(&yen).unwrap();
+        let tampered: YEN = serde_json::from_str(&json.replace("B/..", "B/R.")).unwrap();
+        assert!(tampered.verify_integrity(None).is_err());
+    }
+
+    #[test]
+    fn test_unsealed_documents_fail_verification() {
+        let yen = YEN::new(1, 0, vec!['B', 'R'], ".".to_string());
+        assert!(yen.integrity().is_none());
+        assert!(yen.verify_integrity(None).is_err());
+    }
+
+    #[test]
+    fn test_clone() {
+        let yen = YEN::new(5, 0, vec!['B', 'R'], "./.././.../.....".to_string());
+        let cloned = yen.clone();
+        assert_eq!(yen.size(), cloned.size());
+        assert_eq!(yen.turn(), cloned.turn());
+        assert_eq!(yen.layout(), cloned.layout());
+    }
+
+    #[test]
+    fn test_empty_board() {
+        let yen = YEN::new(2, 0, vec!['B', 'R'], "./../..".to_string());
+        assert_eq!(yen.size(), 2);
+        assert_eq!(yen.layout(), "./../..");
+    }
+
+    #[test]
+    fn test_single_cell_board() {
+        let yen = YEN::new(1, 0, vec!['B', 'R'], ".".to_string());
+        assert_eq!(yen.size(), 1);
+        assert_eq!(yen.layout(), ".");
+    }
+
+    #[test]
+    fn test_roundtrip_serialization() {
+        let original = YEN::new(4, 1, vec!['B', 'R'], "B/.R/BBR/....".to_string());
+        let json = serde_json::to_string(&original).unwrap();
+        let restored: YEN = serde_json::from_str(&json).unwrap();
+        assert_eq!(original.size(), restored.size());
+        assert_eq!(original.turn(), restored.turn());
+        assert_eq!(original.layout(), restored.layout());
+        assert_eq!(original.players(), restored.players());
+    }
+}
@@ -0,0 +1,348 @@
+//! Helpers for fuzzing the engine and code built on top of it.
+//!
+//! This module provides generators of random legal game trajectories and
+//! checkers for the invariants every [`GameY`] must uphold. Bot authors can
+//! use them to exercise their integrations with many different positions:
+//!
+//! ```
+//! use gamey_core::testing::{check_trajectory, random_trajectory};
+//! use gamey_core::GameY;
+//!
+//! let mut rng = rand::rng();
+//! let start = GameY::new(5);
+//! let moves = random_trajectory(&start, usize::MAX, &mut rng);
+//! check_trajectory(&start, &moves).unwrap();
+//! ```
+
+use crate::{
+    BoardTopology, Coordinates, GameY, GameYError, Movement, PlayerId, Ruleset, StatusDump, YEN,
+};
+use rand::Rng;
+use rand::prelude::IndexedRandom;
+use std::collections::{HashMap, HashSet};
+
+/// A broken engine invariant, found by one of the checkers of this module.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Invariant '{invariant}' violated: {details}")]
+pub struct InvariantViolation {
+    /// The name of the invariant that does not hold.
+    pub invariant: &'static str,
+    /// What was found to be wrong.
+    pub details: String,
+}
+
+impl InvariantViolation {
+    fn new(invariant: &'static str, details: impl Into<String>) -> Self {
+        InvariantViolation {
+            invariant,
+            details: details.into(),
+        }
+    }
+}
+
+/// Generates a random sequence of legal placements starting from `game`.
+///
+/// Players alternate, starting with the player to move, and the trajectory
+/// stops when the game is over, when the board is full or after `max_moves`
+/// placements. `game` itself is not modified.
+pub fn random_trajectory<R: Rng + ?Sized>(
+    game: &GameY,
+    max_moves: usize,
+    rng: &mut R,
+) -> Vec<Movement> {
+    let mut game = game.clone();
+    let mut moves = Vec::new();
+    while moves.len() < max_moves {
+        let Some(player) = game.next_player() else {
+            break;
+        };
+        let Some(&cell) = game.available_cells().choose(rng) else {
+            break;
+        };
+        let movement = Movement::Placement {
+            player,
+            coords: game.cell_coords(cell),
+        };
+        game.add_move(movement.clone())
+            .expect("available cells accept placements");
+        moves.push(movement);
+    }
+    moves
+}
+
+/// Plays a random game of the given variant and returns the final position.
+///
+/// See [`random_trajectory`] for how moves are chosen.
+pub fn random_game<R: Rng + ?Sized>(
+    board_size: u32,
+    topology: BoardTopology,
+    ruleset: Ruleset,
+    max_moves: usize,
+    rng: &mut R,
+) -> GameY {
+    let mut game = GameY::with_variant(board_size, topology, ruleset);
+    for movement in random_trajectory(&game, max_moves, rng) {
+        game.add_move(movement)
+            .expect("random trajectories are legal");
+    }
+    game
+}
+
+/// Replays `moves` from `start`, checking every invariant after each move.
+///
+/// # Errors
+/// Returns the first violation found, or a violation named `legal_moves` if
+/// the engine rejects one of the moves.
+pub fn check_trajectory(start: &GameY, moves: &[Movement]) -> Result<(), InvariantViolation> {
+    let mut game = start.clone();
+    check_invariants(&game)?;
+    for (i, movement) in moves.iter().enumerate() {
+        game.add_move(movement.clone()).map_err(|e| {
+            InvariantViolation::new("legal_moves", format!("move {} was rejected: {}", i + 1, e))
+        })?;
+        check_invariants(&game)?;
+    }
+    Ok(())
+}
+
+/// Checks every invariant of this module on `game`.
+///
+/// # Errors
+/// Returns the first violation found.
+pub fn check_invariants(game: &GameY) -> Result<(), InvariantViolation> {
+    check_available_cells(game)?;
+    check_status(game)?;
+    check_yen_roundtrip(game)
+}
+
+/// Checks that the available cells are exactly the empty cells that are not
+/// blocked by the ruleset, without duplicates.
+///
+/// # Errors
+/// Returns a violation named `available_cells` otherwise.
+pub fn check_available_cells(game: &GameY) -> Result<(), InvariantViolation> {
+    let state = game.to_state();
+    let violation = |details: String| InvariantViolation::new("available_cells", details);
+
+    let available: HashSet<u32> = game.available_cells().iter().copied().collect();
+    if available.len() != game.available_cells().len() {
+        return Err(violation(
+            "the available cells contain duplicates".to_string(),
+        ));
+    }
+    for cell in &state.board {
+        let free = cell.player.is_none() && !cell.blocked;
+        if free != available.contains(&cell.index) || free != game.is_available(cell.index) {
+            return Err(violation(format!(
+                "cell {} at {} is {} but {} available",
+                cell.index,
+                cell.coords,
+                if free { "free" } else { "occupied or blocked" },
+                if free { "not" } else { "still" },
+            )));
+        }
+    }
+    if let Some(index) = available.iter().find(|&&i| i >= game.total_cells()) {
+        return Err(violation(format!("cell {} is off the board", index)));
+    }
+    Ok(())
+}
+
+/// Checks that the groups tracked by the engine match the connected
+/// components of the board, and that the status agrees with them: a game is
+/// finished exactly when some player has a chain touching every goal side.
+///
+/// # Errors
+/// Returns a violation named `status` otherwise.
+pub fn check_status(game: &GameY) -> Result<(), InvariantViolation> {
+    let violation = |details: String| InvariantViolation::new("status", details);
+    let state = game.to_state();
+    let stones: HashMap<Coordinates, u32> = state
+        .board
+        .iter()
+        .filter_map(|cell| cell.player.map(|p| (cell.coords, p)))
+        .collect();
+
+    let mut components = connected_components(game, &stones);
+    let mut groups: Vec<Vec<Coordinates>> = state.groups.iter().map(|g| g.cells.clone()).collect();
+    for cells in components.iter_mut().chain(groups.iter_mut()) {
+        cells.sort_unstable_by_key(|c| game.cell_index(*c));
+    }
+    components.sort_unstable_by_key(|cells| game.cell_index(cells[0]));
+    groups.sort_unstable_by_key(|cells| game.cell_index(cells[0]));
+    if components != groups {
+        return Err(violation(format!(
+            "the engine tracks {} groups but the board has {} connected components",
+            groups.len(),
+            components.len()
+        )));
+    }
+
+    let mut connected = None;
+    for group in &state.groups {
+        let player = PlayerId::new(group.player);
+        let mut sides = [false; 3];
+        for coords in &group.cells {
+            let touched = game
+                .topology()
+                .sides_touched(*coords, game.board_size(), player);
+            for (side, t) in sides.iter_mut().zip(touched) {
+                *side |= t;
+            }
+        }
+        let tracked = [
+            group.touches_side_a,
+            group.touches_side_b,
+            group.touches_side_c,
+        ];
+        if sides != tracked {
+            return Err(violation(format!(
+                "the group at {} touches sides {:?} but the engine tracks {:?}",
+                group.cells[0], sides, tracked
+            )));
+        }
+        if sides.iter().all(|&s| s) {
+            connected = Some(group.player);
+        }
+    }
+
+    match (state.status, connected) {
+        (StatusDump::Ongoing { .. }, None) => Ok(()),
+        (StatusDump::Ongoing { .. }, Some(player)) => Err(violation(format!(
+            "player {} connected the goal sides but the game is ongoing",
+            player
+        ))),
+        (StatusDump::Finished { winner }, None) => Err(violation(format!(
+            "player {} won but nobody connected the goal sides",
+            winner
+        ))),
+        (StatusDump::Finished { winner }, Some(player)) => {
+            let expected = if game.ruleset().is_misere() {
+                1 - player
+            } else {
+                player
+            };
+            if winner == expected {
+                Ok(())
+            } else {
+                Err(violation(format!(
+                    "player {} connected the goal sides under {} but player {} won",
+                    player,
+                    game.ruleset(),
+                    winner
+                )))
+            }
+        }
+    }
+}
+
+/// Checks that converting the game to [`YEN`] and back yields the same
+/// position and the same status.
+///
+/// The `turn` field is not compared: loading a YEN document replays its
+/// stones in layout order, so the player to move is not preserved yet.
+///
+/// # Errors
+/// Returns a violation named `yen_roundtrip` otherwise.
+pub fn check_yen_roundtrip(game: &GameY) -> Result<(), InvariantViolation> {
+    let violation = |details: String| InvariantViolation::new("yen_roundtrip", details);
+    let yen = YEN::from(game);
+    let reloaded = GameY::try_from(yen.clone())
+        .map_err(|e: GameYError| violation(format!("the YEN of the game does not load: {}", e)))?;
+    let again = YEN::from(&reloaded);
+    let json = |yen: &YEN| {
+        let mut value = serde_json::to_value(yen).unwrap_or_default();
+        if let Some(object) = value.as_object_mut() {
+            object.remove("turn");
+        }
+        value.to_string()
+    };
+    if json(&yen) != json(&again) {
+        return Err(violation(format!(
+            "{} was reloaded as {}",
+            json(&yen),
+            json(&again)
+        )));
+    }
+    let winner = |state: StatusDump| match state {
+        StatusDump::Finished { winner } => Some(winner),
+        StatusDump::Ongoing { .. } => None,
+    };
+    if winner(game.to_state().status) != winner(reloaded.to_state().status) {
+        return Err(violation(format!(
+            "the game status changed when reloading {}",
+            json(&yen)
+        )));
+    }
+    Ok(())
+}
+
+/// Computes the connected components of same-player stones by flood fill,
+/// independently of the engine's union-find.
+fn connected_components(game: &GameY, stones: &HashMap<Coordinates, u32>) -> Vec<Vec<Coordinates>> {
+    let mut seen: HashSet<Coordinates> = HashSet::new();
+    let mut components = Vec::new();
+    for (&start, &player) in stones {
+        if !seen.insert(start) {
+            continue;
+        }
+        let mut component = vec![start];
+        let mut pending = vec![start];
+        while let Some(coords) = pending.pop() {
+            for neighbor in game.topology().neighbors(coords, game.board_size()) {
+                if stones.get(&neighbor) == Some(&player) && seen.insert(neighbor) {
+                    component.push(neighbor);
+                    pending.push(neighbor);
+                }
+            }
+        }
+        components.push(component);
+    }
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+
+    #[test]
+    fn test_random_games_uphold_invariants() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for topology in BoardTopology::all() {
+            for ruleset in Ruleset::all() {
+                for size in 1..=6 {
+                    let start = GameY::with_variant(size, topology, ruleset);
+                    let moves = random_trajectory(&start, usize::MAX, &mut rng);
+                    check_trajectory(&start, &moves).unwrap();
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_trajectory_stops_at_game_over() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let game = random_game(5, BoardTopology::Y, Ruleset::Standard, usize::MAX, &mut rng);
+        assert!(game.check_game_over());
+    }
+
+    #[test]
+    fn test_random_trajectory_respects_max_moves() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let moves = random_trajectory(&GameY::new(7), 4, &mut rng);
+        assert_eq!(moves.len(), 4);
+    }
+
+    #[test]
+    fn test_illegal_moves_are_reported() {
+        let coords = Coordinates::new(1, 0, 0);
+        let placement = Movement::Placement {
+            player: PlayerId::new(0),
+            coords,
+        };
+        let err = check_trajectory(&GameY::new(2), &[placement.clone(), placement]).unwrap_err();
+        assert_eq!(err.invariant, "legal_moves");
+    }
+}
@@ -0,0 +1,44 @@
+//! Generates the gRPC service of the `grpc` feature.
+//!
+//! The messages are written by hand in `src/grpc.rs` with prost's derive, so
+//! building does not need `protoc`; `proto/gamey.proto` describes the same
+//! service for clients in other languages, and a test of `src/grpc.rs`
+//! checks that their field numbers agree.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "grpc")]
+    compile_grpc();
+}
+
+#[cfg(feature = "grpc")]
+fn compile_grpc() {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    let method = |name: &str, route_name: &str, input: &str, output: &str| {
+        Method::builder()
+            .name(name)
+            .route_name(route_name)
+            .input_type(format!("crate::grpc::proto::{}", input))
+            .output_type(format!("crate::grpc::proto::{}", output))
+            .codec_path("tonic_prost::ProstCodec")
+    };
+    let service = Service::builder()
+        .name("BotService")
+        .package("gamey.v1")
+        .comment("The bots of the server, see `proto/gamey.proto`.")
+        .method(
+            method("choose", "Choose", "ChooseRequest", "MoveProto")
+                .comment("Asks a bot for a move in a position.")
+                .build(),
+        )
+        .method(
+            method("play", "Play", "PlayRequest", "MoveProto")
+                .comment("Plays a game against a bot, one reply per request.")
+                .client_streaming()
+                .server_streaming()
+                .build(),
+        )
+        .build();
+    Builder::new().compile(&[service]);
+}
@@ -0,0 +1,408 @@
+//! Endpoints to inspect and manage a running server.
+//!
+//! The admin endpoints need the admin key of the server (see
+//! [`ServerConfig::admin_key`](crate::server_config::ServerConfig::admin_key)),
+//! sent as `Authorization: Bearer <key>`. Without an admin key they are
+//! disabled; either way, unauthorized requests fail with `UNAUTHORIZED`.
+//!
+//! The bots can be reconfigured without restarting the server, e.g. to
+//! raise their limits between the rounds of a tournament: edit the
+//! `bots.toml` file the server was started with and reload it. Requests that
+//! are already searching finish with the old bots, open sessions keep
+//! theirs, and the cached answers are dropped since the new bots may answer
+//! differently. Remote bots added to or removed from the file are
+//! registered or dropped by the reload as well.
+//!
+//! # Endpoints
+//! - `POST /{api_version}/admin/reload` - Rebuild the bots from `bots.toml`
+//! - `GET /{api_version}/admin/games` - List the hosted games and the open
+//!   bot sessions
+//! - `DELETE /{api_version}/admin/games/{game_id}` - Delete a hosted game
+//! - `DELETE /{api_version}/admin/sessions/{session_id}` - Close a bot
+//!   session, e.g. one whose bot is stuck
+//! - `GET /{api_version}/admin/bots` - How long each bot takes to answer
+//! - `GET /{api_version}/admin/config` - The settings the server runs with
+
+use crate::{
+    audit::AuditConfig,
+    bots_config::BotsConfig,
+    check_api_version,
+    demo::DemoLimits,
+    error::{ErrorContext, ErrorResponse},
+    games::GameSummary,
+    sessions::SessionSummary,
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Request, State},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use gamey_core::{ErrorCode, GameYError};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// The number of recent searches of each bot the latency percentiles are
+/// computed over.
+pub const LATENCY_SAMPLES: usize = 256;
+
+/// The answer times of one bot.
+#[derive(Default)]
+struct Latency {
+    searches: u64,
+    timeouts: u64,
+    total: Duration,
+    max: Duration,
+    /// The latest answer times, oldest first.
+    recent: VecDeque<Duration>,
+}
+
+/// How long the bots take to answer, recorded for every search the server
+/// runs.
+#[derive(Default)]
+pub struct BotLatencies {
+    bots: Mutex<HashMap<String, Latency>>,
+}
+
+impl BotLatencies {
+    /// Creates an empty record.
+    pub fn new() -> Self {
+        BotLatencies::default()
+    }
+
+    /// Records a search of `bot_id` that took `elapsed`, or was stopped
+    /// after it if it `timed_out`.
+    pub fn record(&self, bot_id: &str, elapsed: Duration, timed_out: bool) {
+        let mut bots = self.bots.lock().unwrap_or_else(|e| e.into_inner());
+        let latency = bots.entry(bot_id.to_string()).or_default();
+        latency.searches += 1;
+        latency.timeouts += timed_out as u64;
+        latency.total += elapsed;
+        latency.max = latency.max.max(elapsed);
+        if latency.recent.len() == LATENCY_SAMPLES {
+            latency.recent.pop_front();
+        }
+        latency.recent.push_back(elapsed);
+    }
+
+    /// Returns the statistics of every bot that searched, ordered by bot.
+    pub fn stats(&self) -> Vec<BotLatencyStats> {
+        let bots = self.bots.lock().unwrap_or_else(|e| e.into_inner());
+        let mut stats: Vec<BotLatencyStats> = bots
+            .iter()
+            .map(|(bot_id, latency)| {
+                let mut recent: Vec<Duration> = latency.recent.iter().copied().collect();
+                recent.sort_unstable();
+                let percentile = |p: usize| millis(recent[(recent.len() - 1) * p / 100]);
+                BotLatencyStats {
+                    bot_id: bot_id.clone(),
+                    searches: latency.searches,
+                    timeouts: latency.timeouts,
+                    mean_ms: millis(latency.total) / latency.searches as f64,
+                    p50_ms: percentile(50),
+                    p95_ms: percentile(95),
+                    max_ms: millis(latency.max),
+                }
+            })
+            .collect();
+        stats.sort_by(|a, b| a.bot_id.cmp(&b.bot_id));
+        stats
+    }
+}
+
+fn millis(duration: Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// The answer times of a bot, see [`BotLatencies`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotLatencyStats {
+    /// The bot.
+    pub bot_id: String,
+    /// The searches run since the server started.
+    pub searches: u64,
+    /// The searches stopped because they took too long.
+    pub timeouts: u64,
+    /// The mean time of all searches, in milliseconds.
+    pub mean_ms: f64,
+    /// The median time of the last [`LATENCY_SAMPLES`] searches.
+    pub p50_ms: f64,
+    /// The 95th percentile of the time of the last [`LATENCY_SAMPLES`]
+    /// searches.
+    pub p95_ms: f64,
+    /// The longest search since the server started.
+    pub max_ms: f64,
+}
+
+/// Middleware that rejects the requests without the admin key of the
+/// server.
+pub async fn require_admin_key(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let given = request
+        .headers()
+        .get(AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let message = match (state.admin_key(), given) {
+        (Some(key), Some(given)) if same_key(key, given) => return next.run(request).await,
+        (Some(_), _) => "A valid admin key is required",
+        (None, _) => "The admin endpoints are disabled: the server has no admin key",
+    };
+    ErrorResponse::error(ErrorCode::Unauthorized, message, None, None).into_response()
+}
+
+/// Compares two keys in a time that does not depend on where they differ.
+fn same_key(key: &str, given: &str) -> bool {
+    key.len() == given.len()
+        && key
+            .bytes()
+            .zip(given.bytes())
+            .fold(0, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+/// Path parameters of the admin endpoints.
+#[derive(Deserialize)]
+pub struct AdminParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+}
+
+/// Path parameters of the admin endpoints about one game.
+#[derive(Deserialize)]
+pub struct AdminGameParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The identifier of the game.
+    game_id: String,
+}
+
+/// Path parameters of the admin endpoints about one session.
+#[derive(Deserialize)]
+pub struct AdminSessionParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The identifier of the session.
+    session_id: String,
+}
+
+/// Response of the reload endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReloadResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The bots available after the reload.
+    pub bots: Vec<String>,
+}
+
+/// Handler that rebuilds the bot registry from the server's `bots.toml`, or
+/// with the default limits if the server was started without one.
+///
+/// If the file cannot be read or is not valid, the server keeps its bots
+/// and the request fails.
+///
+/// # Route
+/// `POST /{api_version}/admin/reload`
+pub async fn reload_bots(
+    State(state): State<AppState>,
+    Path(params): Path<AdminParams>,
+) -> Result<Json<ReloadResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let config = match state.bots_config() {
+        Some(file) => BotsConfig::load(file).context(&params.api_version, None)?,
+        None => BotsConfig::default(),
+    };
+    state.replace_bots(config.registry_with_remote(state.remote_bots()));
+    state.move_cache().clear();
+    tracing::info!("Reloaded the bots");
+    Ok(Json(ReloadResponse {
+        api_version: params.api_version,
+        bots: state.bots().names(),
+    }))
+}
+
+/// Response of the endpoint listing the games.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GamesResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The games hosted by the server.
+    pub games: Vec<GameSummary>,
+    /// The open bot sessions.
+    pub sessions: Vec<SessionSummary>,
+}
+
+/// Handler that lists the hosted games and the open bot sessions.
+///
+/// # Route
+/// `GET /{api_version}/admin/games`
+pub async fn list_games(
+    State(state): State<AppState>,
+    Path(params): Path<AdminParams>,
+) -> Result<Json<GamesResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    Ok(Json(GamesResponse {
+        api_version: params.api_version,
+        games: state.games().summaries(),
+        sessions: state.sessions().summaries(),
+    }))
+}
+
+/// Response of the endpoints that delete a game or a session.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DeletedResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The identifier of the deleted game or session.
+    pub id: String,
+}
+
+/// Handler that deletes a hosted game.
+///
+/// # Route
+/// `DELETE /{api_version}/admin/games/{game_id}`
+pub async fn delete_game(
+    State(state): State<AppState>,
+    Path(params): Path<AdminGameParams>,
+) -> Result<Json<DeletedResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    state
+        .games()
+        .remove(&params.game_id)
+        .context(&params.api_version, None)?;
+    tracing::info!("Deleted game {}", params.game_id);
+    Ok(Json(DeletedResponse {
+        api_version: params.api_version,
+        id: params.game_id,
+    }))
+}
+
+/// Handler that closes a bot session. A bot still searching for the session
+/// finishes its search, but its move is not played.
+///
+/// # Route
+/// `DELETE /{api_version}/admin/sessions/{session_id}`
+pub async fn delete_session(
+    State(state): State<AppState>,
+    Path(params): Path<AdminSessionParams>,
+) -> Result<Json<DeletedResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    if !state.sessions().close(&params.session_id) {
+        let err = GameYError::SessionNotFound {
+            id: params.session_id,
+        };
+        return Err(err).context(&params.api_version, None);
+    }
+    tracing::info!("Closed session {}", params.session_id);
+    Ok(Json(DeletedResponse {
+        api_version: params.api_version,
+        id: params.session_id,
+    }))
+}
+
+/// Response of the bot statistics endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct BotStatsResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The answer times of every bot that searched.
+    pub bots: Vec<BotLatencyStats>,
+}
+
+/// Handler that reports how long each bot takes to answer.
+///
+/// # Route
+/// `GET /{api_version}/admin/bots`
+pub async fn bot_stats(
+    State(state): State<AppState>,
+    Path(params): Path<AdminParams>,
+) -> Result<Json<BotStatsResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    Ok(Json(BotStatsResponse {
+        api_version: params.api_version,
+        bots: state.bot_latencies().stats(),
+    }))
+}
+
+/// Response of the configuration endpoint: the settings the server runs
+/// with. The admin key is never shown.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ConfigResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The bots available.
+    pub bots: Vec<String>,
+    /// The `bots.toml` file the bots are reloaded from, if any.
+    pub bots_config: Option<PathBuf>,
+    /// The directory the hosted games are persisted in, if any.
+    pub games_dir: Option<PathBuf>,
+    /// How long a bot may search before its request fails, in seconds.
+    pub bot_timeout_secs: f64,
+    /// The number of answers of deterministic bots cached.
+    pub move_cache_entries: usize,
+    /// The limits of demo mode, if the server runs in it.
+    pub demo: Option<DemoLimits>,
+    /// Where the moves served are logged, if they are.
+    pub audit: Option<AuditConfig>,
+}
+
+/// Handler that dumps the settings the server runs with.
+///
+/// # Route
+/// `GET /{api_version}/admin/config`
+pub async fn show_config(
+    State(state): State<AppState>,
+    Path(params): Path<AdminParams>,
+) -> Result<Json<ConfigResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    Ok(Json(ConfigResponse {
+        api_version: params.api_version,
+        bots: state.bots().names(),
+        bots_config: state.bots_config().map(PathBuf::from),
+        games_dir: state.games().dir().map(PathBuf::from),
+        bot_timeout_secs: state.bot_timeout().as_secs_f64(),
+        move_cache_entries: state.move_cache().capacity(),
+        demo: state.demo().map(|demo| demo.limits()),
+        audit: state.audit_log().map(|log| log.config().clone()),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latencies_are_kept_per_bot() {
+        let latencies = BotLatencies::new();
+        for ms in 1..=100 {
+            latencies.record("mcts_bot", Duration::from_millis(ms), ms == 100);
+        }
+        latencies.record("random_bot", Duration::from_millis(2), false);
+
+        let stats = latencies.stats();
+        assert_eq!(stats.len(), 2);
+        let mcts = &stats[0];
+        assert_eq!((mcts.searches, mcts.timeouts), (100, 1));
+        assert!((mcts.mean_ms - 50.5).abs() < 1e-6);
+        assert!((mcts.p50_ms - 50.0).abs() < 1e-6);
+        assert!((mcts.p95_ms - 95.0).abs() < 1e-6);
+        assert!((mcts.max_ms - 100.0).abs() < 1e-6);
+        assert_eq!(stats[1].bot_id, "random_bot");
+    }
+
+    #[test]
+    fn test_keys_must_match_exactly() {
+        assert!(same_key("secret", "secret"));
+        assert!(!same_key("secret", "secreT"));
+        assert!(!same_key("secret", "secret2"));
+        assert!(!same_key("secret", ""));
+    }
+}
@@ -0,0 +1,331 @@
+//! Append-only audit log of the moves served.
+//!
+//! When enabled (see [`AuditConfig`]), every request that asks a bot for a
+//! move or plays a move adds one JSON line to the log: when it happened,
+//! who asked, the position it started from, the moves sent and returned,
+//! and the error if it failed. Requests rejected before a position was
+//! loaded, e.g. with invalid YEN, are not logged. The log settles disputes
+//! in tournaments and doubles as a dataset of positions and answers.
+//!
+//! The log is rotated by size: once the file would grow past
+//! [`AuditConfig::max_bytes`], it is renamed to `<file>.1`, older files
+//! move one number up, and only [`AuditConfig::keep`] old files are kept.
+
+use axum::{
+    extract::{ConnectInfo, FromRequestParts},
+    http::request::Parts,
+};
+use gamey_core::{Coordinates, ErrorCode, GameYError};
+use serde::{Deserialize, Serialize};
+use std::convert::Infallible;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::net::{IpAddr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// The size at which the audit log is rotated by default, 10 MiB.
+pub const DEFAULT_AUDIT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+
+/// The number of rotated audit logs kept by default.
+pub const DEFAULT_AUDIT_KEEP: usize = 5;
+
+/// Where the audit log is written and how it is rotated.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct AuditConfig {
+    /// The file the log is appended to.
+    pub path: PathBuf,
+    /// The size the file may reach before it is rotated.
+    #[serde(default = "default_max_bytes")]
+    pub max_bytes: u64,
+    /// The number of rotated files kept; 0 deletes the log when it is full.
+    #[serde(default = "default_keep")]
+    pub keep: usize,
+}
+
+fn default_max_bytes() -> u64 {
+    DEFAULT_AUDIT_MAX_BYTES
+}
+
+fn default_keep() -> usize {
+    DEFAULT_AUDIT_KEEP
+}
+
+impl AuditConfig {
+    /// Logs to `path` with the default rotation settings.
+    pub fn new(path: PathBuf) -> Self {
+        AuditConfig {
+            path,
+            max_bytes: DEFAULT_AUDIT_MAX_BYTES,
+            keep: DEFAULT_AUDIT_KEEP,
+        }
+    }
+}
+
+/// What kind of request an audit entry records.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditedRequest {
+    /// A bot was asked for a move in a position, see
+    /// [`choose`](crate::choose).
+    Choose,
+    /// A move was sent to a bot session, see [`sessions`](crate::sessions).
+    SessionMove,
+    /// A move was played in a hosted game, see [`games`](crate::games).
+    GameMove,
+}
+
+/// One line of the audit log.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct AuditEntry {
+    /// When the request was answered, in milliseconds since the Unix epoch.
+    pub timestamp_ms: u64,
+    /// What was requested.
+    pub request: AuditedRequest,
+    /// The IP address of the client, if known.
+    pub client: Option<IpAddr>,
+    /// The bot asked for a move, if any.
+    pub bot_id: Option<String>,
+    /// The session or hosted game, if any.
+    pub id: Option<String>,
+    /// The Zobrist hash of the position the request started from.
+    pub position_hash: String,
+    /// The move sent by the client, if any.
+    pub sent: Option<Coordinates>,
+    /// The move the bot returned, if any.
+    pub returned: Option<Coordinates>,
+    /// Why the request failed, if it did.
+    pub error: Option<ErrorCode>,
+}
+
+impl AuditEntry {
+    /// Starts the entry of a request about the position with the given
+    /// hash, timestamped now.
+    pub fn new(request: AuditedRequest, client: Option<IpAddr>, position_hash: u64) -> Self {
+        let timestamp_ms = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        AuditEntry {
+            timestamp_ms,
+            request,
+            client,
+            bot_id: None,
+            id: None,
+            position_hash: format!("{:016x}", position_hash),
+            sent: None,
+            returned: None,
+            error: None,
+        }
+    }
+
+    /// Sets the bot asked for a move.
+    pub fn with_bot(mut self, bot_id: &str) -> Self {
+        self.bot_id = Some(bot_id.to_string());
+        self
+    }
+
+    /// Sets the session or hosted game.
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(id.to_string());
+        self
+    }
+
+    /// Sets the move sent by the client.
+    pub fn with_sent(mut self, sent: Option<Coordinates>) -> Self {
+        self.sent = sent;
+        self
+    }
+
+    /// Sets the move returned, or the code of the error of a failed
+    /// request.
+    pub fn with_outcome(mut self, outcome: Result<Option<Coordinates>, ErrorCode>) -> Self {
+        match outcome {
+            Ok(returned) => self.returned = returned,
+            Err(code) => self.error = Some(code),
+        }
+        self
+    }
+}
+
+/// The open audit log file and its size.
+struct Output {
+    file: File,
+    size: u64,
+}
+
+/// An append-only JSONL log of the moves served, shared by all requests.
+pub struct AuditLog {
+    config: AuditConfig,
+    output: Mutex<Output>,
+}
+
+impl AuditLog {
+    /// Opens the log for appending, creating the file if needed.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be opened.
+    pub fn open(config: AuditConfig) -> Result<Self, GameYError> {
+        let output = open_output(&config.path)?;
+        Ok(AuditLog {
+            config,
+            output: Mutex::new(output),
+        })
+    }
+
+    /// Returns where the log is written and how it is rotated.
+    pub fn config(&self) -> &AuditConfig {
+        &self.config
+    }
+
+    /// Appends an entry, rotating the log first if it is full.
+    ///
+    /// Failures are reported in the server's log but do not fail the
+    /// request being audited.
+    pub fn record(&self, entry: &AuditEntry) {
+        if let Err(e) = self.append(entry) {
+            tracing::warn!("Cannot write the audit log: {}", e);
+        }
+    }
+
+    fn append(&self, entry: &AuditEntry) -> Result<(), GameYError> {
+        let mut line =
+            serde_json::to_vec(entry).map_err(|e| GameYError::SerdeError { error: e })?;
+        line.push(b'\n');
+        let mut output = self.output.lock().unwrap_or_else(|e| e.into_inner());
+        if output.size > 0 && output.size + line.len() as u64 > self.config.max_bytes {
+            self.rotate()?;
+            *output = open_output(&self.config.path)?;
+        }
+        output
+            .file
+            .write_all(&line)
+            .map_err(|e| io_error(&self.config.path, e))?;
+        output.size += line.len() as u64;
+        Ok(())
+    }
+
+    /// Renames the full log to `<file>.1`, moving the older ones up and
+    /// dropping the oldest.
+    fn rotate(&self) -> Result<(), GameYError> {
+        let path = &self.config.path;
+        if self.config.keep == 0 {
+            return std::fs::remove_file(path).map_err(|e| io_error(path, e));
+        }
+        for n in (1..self.config.keep).rev() {
+            let from = rotated(path, n);
+            if from.exists() {
+                std::fs::rename(&from, rotated(path, n + 1)).map_err(|e| io_error(&from, e))?;
+            }
+        }
+        std::fs::rename(path, rotated(path, 1)).map_err(|e| io_error(path, e))
+    }
+}
+
+/// Returns the name of the `n`th rotated log, e.g. `audit.jsonl.1`.
+pub fn rotated(path: &Path, n: usize) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", n));
+    PathBuf::from(name)
+}
+
+fn open_output(path: &Path) -> Result<Output, GameYError> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| io_error(path, e))?;
+    let size = file.metadata().map_err(|e| io_error(path, e))?.len();
+    Ok(Output { file, size })
+}
+
+fn io_error(path: &Path, e: std::io::Error) -> GameYError {
+    GameYError::IoError {
+        message: format!("Failed to write the audit log {}", path.display()),
+        error: e.to_string(),
+    }
+}
+
+/// The IP address of the client of a request, if it came through a
+/// connection (requests sent straight to the router, e.g. in tests, have
+/// none).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ClientAddr(pub Option<IpAddr>);
+
+impl<S: Send + Sync> FromRequestParts<S> for ClientAddr {
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Infallible> {
+        let info = parts.extensions.get::<ConnectInfo<SocketAddr>>();
+        Ok(ClientAddr(info.map(|info| info.0.ip())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(n: u32) -> AuditEntry {
+        AuditEntry::new(AuditedRequest::Choose, None, n as u64)
+            .with_bot("random_bot")
+            .with_outcome(Ok(Some(Coordinates::new(n, 0, 0))))
+    }
+
+    fn lines(path: &Path) -> Vec<AuditEntry> {
+        std::fs::read_to_string(path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_entries_are_appended_as_json_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let log = AuditLog::open(AuditConfig::new(path.clone())).unwrap();
+        let first = entry(1);
+        log.record(&first);
+        let failed = AuditEntry::new(AuditedRequest::GameMove, None, 2)
+            .with_id("g1")
+            .with_sent(Some(Coordinates::new(0, 1, 0)))
+            .with_outcome(Err(ErrorCode::GameNotFound));
+        log.record(&failed);
+        drop(log);
+
+        // Reopening appends to the same file
+        let log = AuditLog::open(AuditConfig::new(path.clone())).unwrap();
+        log.record(&entry(3));
+        let entries = lines(&path);
+        assert_eq!(entries, vec![first, failed, entries[2].clone()]);
+        assert_eq!(entries[2].position_hash, "0000000000000003");
+    }
+
+    #[test]
+    fn test_full_logs_are_rotated() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        let line_len = serde_json::to_vec(&entry(0)).unwrap().len() as u64 + 1;
+        let config = AuditConfig {
+            path: path.clone(),
+            max_bytes: 2 * line_len,
+            keep: 2,
+        };
+        let log = AuditLog::open(config).unwrap();
+        for n in 0..7 {
+            log.record(&entry(n));
+        }
+
+        let returned = |path: &Path| -> Vec<u32> {
+            lines(path)
+                .iter()
+                .map(|entry| entry.returned.unwrap().x())
+                .collect()
+        };
+        assert_eq!(returned(&path), vec![6]);
+        assert_eq!(returned(&rotated(&path, 1)), vec![4, 5]);
+        assert_eq!(returned(&rotated(&path, 2)), vec![2, 3]);
+        assert!(!rotated(&path, 3).exists());
+    }
+}
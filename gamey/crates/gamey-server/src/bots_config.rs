@@ -0,0 +1,218 @@
+//! Per-bot settings of the bot server, read from `bots.toml`.
+//!
+//! A shared server cannot let a single search take all of its memory, so
+//! the search bots it registers are bounded by default: see
+//! [`DEFAULT_MAX_NODES`] and [`DEFAULT_MAX_TABLE_ENTRIES`]. Each bot can
+//! override the defaults in a table named after it:
+//!
+//! ```toml
+//! [mcts_bot]
+//! max_nodes = 200000
+//!
+//! [solver_bot]
+//! max_nodes = 100000          # nodes of the MCTS fallback
+//! max_table_entries = 500000  # positions in the transposition table
+//! ```
+//!
+//! Bots served by other bot servers are registered under the `remote`
+//! table, by the name they take on this server; `bot` names the bot on the
+//! remote server and defaults to the same name:
+//!
+//! ```toml
+//! [remote.classmate_bot]
+//! url = "http://10.0.0.7:3000"
+//! bot = "mcts_bot"
+//! ```
+//!
+//! The server does not speak to other servers itself: it builds remote bots
+//! with the [`RemoteBotFactory`] given to
+//! [`AppState::with_remote_bots`](crate::AppState::with_remote_bots), such
+//! as the one of the `gamey-client` crate. Without one, remote entries are
+//! skipped with a warning.
+
+use gamey_bots::{DEFAULT_SOLVER_EMPTY_CELLS, MctsBot, RandomBot, SolverBot, YBot, YBotRegistry};
+use gamey_core::GameYError;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+
+/// The default node limit of the search trees of the server's bots.
+pub const DEFAULT_MAX_NODES: usize = 1_000_000;
+
+/// The default number of positions the server's solver keeps in its
+/// transposition table.
+pub const DEFAULT_MAX_TABLE_ENTRIES: usize = 4_000_000;
+
+/// The memory limits of one bot.
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct BotLimits {
+    /// The largest number of nodes of the search tree.
+    pub max_nodes: Option<usize>,
+    /// The largest number of positions in the transposition table.
+    pub max_table_entries: Option<usize>,
+}
+
+/// A bot served by another bot server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct RemoteBotEntry {
+    /// The URL of the remote server, e.g. `http://10.0.0.7:3000`.
+    pub url: String,
+    /// The name of the bot on the remote server; by default, the name of
+    /// the entry.
+    pub bot: Option<String>,
+}
+
+/// Builds the bot of a remote entry, given the name it takes on this
+/// server.
+pub type RemoteBotFactory = Arc<dyn Fn(&str, &RemoteBotEntry) -> Arc<dyn YBot> + Send + Sync>;
+
+/// The settings of every bot, keyed by bot name, and the remote bots.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct BotsConfig {
+    /// The bots of other servers, keyed by the name they take here.
+    #[serde(default)]
+    remote: HashMap<String, RemoteBotEntry>,
+    #[serde(flatten)]
+    bots: HashMap<String, BotLimits>,
+}
+
+impl BotsConfig {
+    /// Parses a configuration in the format of `bots.toml`.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Reads a configuration file.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be read and
+    /// `GameYError::InvalidConfig` if it is not a valid configuration.
+    pub fn load(file: &Path) -> Result<Self, GameYError> {
+        let text = std::fs::read_to_string(file).map_err(|e| GameYError::IoError {
+            message: format!("Failed to read {}", file.display()),
+            error: e.to_string(),
+        })?;
+        BotsConfig::parse(&text).map_err(|e| GameYError::InvalidConfig {
+            file: file.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Returns the limits configured for `bot`, if any.
+    pub fn limits(&self, bot: &str) -> BotLimits {
+        self.bots.get(bot).copied().unwrap_or_default()
+    }
+
+    /// Returns the node limit of `bot`, [`DEFAULT_MAX_NODES`] unless
+    /// configured.
+    pub fn max_nodes(&self, bot: &str) -> usize {
+        self.limits(bot).max_nodes.unwrap_or(DEFAULT_MAX_NODES)
+    }
+
+    /// Returns the transposition table limit of `bot`,
+    /// [`DEFAULT_MAX_TABLE_ENTRIES`] unless configured.
+    pub fn max_table_entries(&self, bot: &str) -> usize {
+        self.limits(bot)
+            .max_table_entries
+            .unwrap_or(DEFAULT_MAX_TABLE_ENTRIES)
+    }
+
+    /// Returns the remote bots, keyed by the name they take on this server.
+    pub fn remote(&self) -> &HashMap<String, RemoteBotEntry> {
+        &self.remote
+    }
+
+    /// Builds the standard bots of the server with their limits.
+    pub fn registry(&self) -> YBotRegistry {
+        let mcts = |bot: &str| MctsBot::default().with_max_nodes(self.max_nodes(bot));
+        let solver = SolverBot::new(DEFAULT_SOLVER_EMPTY_CELLS, mcts("solver_bot"))
+            .with_max_table_entries(self.max_table_entries("solver_bot"));
+        YBotRegistry::new()
+            .with_bot(Arc::new(RandomBot))
+            .with_bot(Arc::new(mcts("mcts_bot")))
+            .with_bot(Arc::new(solver))
+    }
+
+    /// Builds the standard bots like [`BotsConfig::registry`], and the
+    /// remote bots with `connect`. Without a factory the remote bots are
+    /// skipped.
+    pub fn registry_with_remote(&self, connect: Option<&RemoteBotFactory>) -> YBotRegistry {
+        let mut registry = self.registry();
+        let mut remote: Vec<_> = self.remote.iter().collect();
+        remote.sort_by_key(|(name, _)| *name);
+        for (name, entry) in remote {
+            match connect {
+                Some(connect) => registry = registry.with_bot(connect(name, entry)),
+                None => tracing::warn!(
+                    "Skipping the remote bot {name}: this server cannot reach other servers"
+                ),
+            }
+        }
+        registry
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limits_fall_back_to_the_defaults() {
+        let config = BotsConfig::parse("[mcts_bot]\nmax_nodes = 500\n").unwrap();
+        assert_eq!(config.max_nodes("mcts_bot"), 500);
+        assert_eq!(config.limits("mcts_bot").max_table_entries, None);
+        assert_eq!(
+            config.max_table_entries("mcts_bot"),
+            DEFAULT_MAX_TABLE_ENTRIES
+        );
+        assert_eq!(config.max_nodes("solver_bot"), DEFAULT_MAX_NODES);
+        assert_eq!(config.registry().names().len(), 3);
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings() {
+        assert!(BotsConfig::parse("[mcts_bot]\nmax_ram = 5\n").is_err());
+        assert!(BotsConfig::parse("[mcts_bot]\nmax_nodes = -1\n").is_err());
+    }
+
+    #[test]
+    fn test_load_reports_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("bots.toml");
+        std::fs::write(&file, "mcts_bot = 3").unwrap();
+        let err = BotsConfig::load(&file).unwrap_err();
+        assert!(matches!(err, GameYError::InvalidConfig { .. }));
+        assert!(BotsConfig::load(&dir.path().join("missing.toml")).is_err());
+    }
+
+    #[test]
+    fn test_remote_bots_are_built_by_the_factory() {
+        let text =
+            "[mcts_bot]\nmax_nodes = 500\n\n[remote.far_bot]\nurl = \"http://10.0.0.7:3000\"\n";
+        let config = BotsConfig::parse(text).unwrap();
+        assert_eq!(config.max_nodes("mcts_bot"), 500);
+        let entry = &config.remote()["far_bot"];
+        assert_eq!(entry.url, "http://10.0.0.7:3000");
+        assert_eq!(entry.bot, None);
+        assert_eq!(config.registry_with_remote(None).names().len(), 3);
+
+        let built = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let names = Arc::clone(&built);
+        let connect: RemoteBotFactory = Arc::new(move |name: &str, entry: &RemoteBotEntry| {
+            names
+                .lock()
+                .unwrap()
+                .push((name.to_string(), entry.url.clone()));
+            Arc::new(RandomBot) as Arc<dyn YBot>
+        });
+        config.registry_with_remote(Some(&connect));
+        assert_eq!(
+            *built.lock().unwrap(),
+            vec![("far_bot".to_string(), "http://10.0.0.7:3000".to_string())]
+        );
+        assert!(BotsConfig::parse("[remote.far_bot]\nhost = \"x\"\n").is_err());
+    }
+}
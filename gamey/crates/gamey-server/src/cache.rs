@@ -0,0 +1,294 @@
+//! Caching of the moves chosen by deterministic bots.
+//!
+//! Classes tend to send the same test positions over and over. Bots that
+//! always answer the same in the same position (see
+//! [`YBot::is_deterministic`](gamey_bots::YBot::is_deterministic)) have
+//! their answers kept in a [`MoveCache`], so repeated requests return
+//! without searching again.
+//!
+//! Clients can opt out per request with a `Cache-Control` header:
+//! `no-cache` searches again (and stores the new answer), `no-store` also
+//! keeps the answer out of the cache. Answers of the choose endpoint carry an
+//! `X-Cache: HIT` or `X-Cache: MISS` header when the cache was consulted.
+//!
+//! # Endpoints
+//! - `GET /{api_version}/ybot/cache` - The cache statistics
+
+use crate::{check_api_version, error::ErrorResponse, state::AppState};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{HeaderMap, header::CACHE_CONTROL},
+};
+use gamey_core::{Coordinates, Position};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// The number of answers the server caches by default.
+pub const DEFAULT_MOVE_CACHE_ENTRIES: usize = 10_000;
+
+/// What a cached answer was asked for.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    /// The position the bot was asked about.
+    pub position: Position,
+    /// The bot that answered.
+    pub bot_id: String,
+    /// Whether the answer includes an explanation.
+    pub explain: bool,
+}
+
+/// A cached answer of a bot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedMove {
+    /// The move the bot chose.
+    pub coords: Coordinates,
+    /// The explanation of the move, if it was asked for.
+    pub explanation: Option<String>,
+}
+
+/// How the client allows the cache to be used, from its `Cache-Control`
+/// header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CachePolicy {
+    /// A cached answer may be returned.
+    pub read: bool,
+    /// A new answer may be stored.
+    pub write: bool,
+}
+
+impl CachePolicy {
+    /// Reads the policy from the headers of a request.
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut policy = CachePolicy {
+            read: true,
+            write: true,
+        };
+        let directives = headers
+            .get_all(CACHE_CONTROL)
+            .iter()
+            .filter_map(|value| value.to_str().ok())
+            .flat_map(|value| value.split(','));
+        for directive in directives {
+            match directive.trim().to_ascii_lowercase().as_str() {
+                "no-cache" => policy.read = false,
+                "no-store" => {
+                    policy.read = false;
+                    policy.write = false;
+                }
+                _ => {}
+            }
+        }
+        policy
+    }
+}
+
+/// The entries of the cache and their order of use.
+#[derive(Default)]
+struct Entries {
+    moves: HashMap<CacheKey, (CachedMove, u64)>,
+    /// The keys by the time they were last used, oldest first.
+    by_use: BTreeMap<u64, CacheKey>,
+    clock: u64,
+}
+
+/// A least recently used cache of bot answers, shared by all requests.
+pub struct MoveCache {
+    capacity: usize,
+    entries: Mutex<Entries>,
+    hits: AtomicU64,
+    misses: AtomicU64,
+}
+
+impl MoveCache {
+    /// Creates a cache holding at most `capacity` answers; 0 disables it.
+    pub fn new(capacity: usize) -> Self {
+        MoveCache {
+            capacity,
+            entries: Mutex::new(Entries::default()),
+            hits: AtomicU64::new(0),
+            misses: AtomicU64::new(0),
+        }
+    }
+
+    /// Returns the largest number of answers kept.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns true if answers are cached at all.
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Returns the cached answer for `key`, counting a hit or a miss.
+    pub fn get(&self, key: &CacheKey) -> Option<CachedMove> {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let now = entries.clock;
+        let Some((cached, used)) = entries.moves.get_mut(key) else {
+            self.misses.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        let previous = std::mem::replace(used, now);
+        let cached = cached.clone();
+        if let Some(key) = entries.by_use.remove(&previous) {
+            entries.by_use.insert(now, key);
+        }
+        self.hits.fetch_add(1, Ordering::Relaxed);
+        Some(cached)
+    }
+
+    /// Stores an answer, dropping the least recently used ones if the cache
+    /// is full.
+    pub fn insert(&self, key: CacheKey, cached: CachedMove) {
+        if !self.is_enabled() {
+            return;
+        }
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let now = entries.clock;
+        if let Some((_, used)) = entries.moves.insert(key.clone(), (cached, now)) {
+            entries.by_use.remove(&used);
+        }
+        entries.by_use.insert(now, key);
+        while entries.moves.len() > self.capacity {
+            let Some((_, oldest)) = entries.by_use.pop_first() else {
+                break;
+            };
+            entries.moves.remove(&oldest);
+        }
+    }
+
+    /// Drops every cached answer, e.g. after the bots were reconfigured.
+    /// The statistics are kept.
+    pub fn clear(&self) {
+        let mut entries = self.lock();
+        entries.moves.clear();
+        entries.by_use.clear();
+    }
+
+    /// Returns the statistics of the cache.
+    pub fn stats(&self) -> CacheStats {
+        CacheStats {
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+            entries: self.lock().moves.len(),
+            capacity: self.capacity,
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// The statistics of a [`MoveCache`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CacheStats {
+    /// Requests answered from the cache.
+    pub hits: u64,
+    /// Requests for deterministic bots that had to search.
+    pub misses: u64,
+    /// Answers currently cached.
+    pub entries: usize,
+    /// The largest number of answers cached.
+    pub capacity: usize,
+}
+
+/// Response of the cache statistics endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CacheStatsResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The statistics of the move cache.
+    #[serde(flatten)]
+    pub stats: CacheStats,
+}
+
+/// Path parameters of the cache endpoint.
+#[derive(Deserialize)]
+pub struct CacheParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+}
+
+/// Handler that reports how well the move cache works.
+///
+/// # Route
+/// `GET /{api_version}/ybot/cache`
+pub async fn cache_stats(
+    State(state): State<AppState>,
+    Path(params): Path<CacheParams>,
+) -> Result<Json<CacheStatsResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    Ok(Json(CacheStatsResponse {
+        api_version: params.api_version,
+        stats: state.move_cache().stats(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+    use gamey_core::GameY;
+
+    fn key(size: u32) -> CacheKey {
+        CacheKey {
+            position: GameY::new(size).position(),
+            bot_id: "bot".to_string(),
+            explain: false,
+        }
+    }
+
+    fn answer(x: u32) -> CachedMove {
+        CachedMove {
+            coords: Coordinates::new(x, 0, 0),
+            explanation: None,
+        }
+    }
+
+    #[test]
+    fn test_least_recently_used_answers_are_dropped() {
+        let cache = MoveCache::new(2);
+        cache.insert(key(1), answer(0));
+        cache.insert(key(2), answer(1));
+        assert_eq!(cache.get(&key(1)), Some(answer(0)));
+        cache.insert(key(3), answer(2));
+
+        assert_eq!(cache.get(&key(2)), None);
+        assert_eq!(cache.get(&key(1)), Some(answer(0)));
+        assert_eq!(cache.get(&key(3)), Some(answer(2)));
+        let stats = cache.stats();
+        assert_eq!((stats.hits, stats.misses, stats.entries), (3, 1, 2));
+    }
+
+    #[test]
+    fn test_disabled_cache_stores_nothing() {
+        let cache = MoveCache::new(0);
+        cache.insert(key(1), answer(0));
+        assert_eq!(cache.get(&key(1)), None);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_policy_follows_cache_control() {
+        let mut headers = HeaderMap::new();
+        let policy = CachePolicy::from_headers(&headers);
+        assert!(policy.read && policy.write);
+
+        headers.insert(
+            CACHE_CONTROL,
+            HeaderValue::from_static("max-age=0, No-Cache"),
+        );
+        let policy = CachePolicy::from_headers(&headers);
+        assert!(!policy.read && policy.write);
+
+        headers.insert(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+        let policy = CachePolicy::from_headers(&headers);
+        assert!(!policy.read && !policy.write);
+    }
+}
@@ -0,0 +1,217 @@
+//! Posts of the hosted games to chat channels.
+//!
+//! When enabled (see [`ChatConfig`]), the [`ChatNotifier`] observes every
+//! hosted game and posts to Slack or Discord incoming webhooks whenever it
+//! becomes a player's turn or a game ends, with the board drawn as text and
+//! a link to its image (see [`games`](crate::games)) if the public URL of
+//! the server is known.
+//!
+//! Chat webhooks must be `https://` URLs, as those of Slack and Discord are;
+//! a configuration with any other URL is rejected when it is loaded.
+
+use crate::SUPPORTED_VERSION;
+use crate::games::{GameObserver, TurnEvent, spawn_post};
+use gamey_core::{GameY, RenderOptions};
+use serde::{Deserialize, Deserializer, Serialize, de::Error};
+
+/// The longest message Discord accepts; longer boards are left out and only
+/// linked.
+const DISCORD_MAX_LENGTH: usize = 2000;
+
+/// The chat webhooks told of the hosted games.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq, Default)]
+#[serde(default, deny_unknown_fields)]
+pub struct ChatConfig {
+    /// The URL the server is reached at from the chat, e.g.
+    /// `https://gamey.example.org`, to link the image of the board; if
+    /// `None`, the messages have no link.
+    pub public_url: Option<String>,
+    /// The webhooks posted to.
+    pub webhooks: Vec<ChatWebhook>,
+}
+
+/// An incoming webhook of a chat channel.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct ChatWebhook {
+    /// The `https://` URL of the webhook.
+    #[serde(deserialize_with = "https_url")]
+    pub url: String,
+    /// The chat service, which decides the format of the messages.
+    pub service: ChatService,
+    /// If true, only post the results of finished games.
+    #[serde(default)]
+    pub results_only: bool,
+}
+
+/// Reads the URL of a chat webhook, rejecting any but `https://` ones so
+/// that messages never leave the server unencrypted.
+fn https_url<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let url = String::deserialize(deserializer)?;
+    if url.starts_with("https://") {
+        Ok(url)
+    } else {
+        Err(D::Error::custom(format!(
+            "chat webhook URL must start with https://: {}",
+            url
+        )))
+    }
+}
+
+/// A chat service with incoming webhooks.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChatService {
+    /// Slack, whose messages are `{"text": ...}`.
+    Slack,
+    /// Discord, whose messages are `{"content": ...}`.
+    Discord,
+}
+
+impl ChatService {
+    /// Returns the JSON body that posts `text` to a webhook of the service.
+    pub fn payload(&self, text: &str) -> serde_json::Value {
+        match self {
+            ChatService::Slack => serde_json::json!({ "text": text }),
+            ChatService::Discord => serde_json::json!({ "content": text }),
+        }
+    }
+}
+
+/// Posts the turns and results of the hosted games to the chat webhooks of
+/// a [`ChatConfig`].
+pub struct ChatNotifier {
+    config: ChatConfig,
+}
+
+impl ChatNotifier {
+    /// Creates a notifier posting to the webhooks of `config`.
+    pub fn new(config: ChatConfig) -> Self {
+        ChatNotifier { config }
+    }
+
+    /// Returns the message posted to a webhook of `service` for `event`:
+    /// what happened, the board and the link to its image.
+    pub fn message(&self, event: &TurnEvent, service: ChatService) -> String {
+        let news = match (event.winner, event.takeback_requested_by, event.next_player) {
+            (Some(winner), _, _) => format!("player {} won", winner),
+            (None, Some(player), _) => format!("player {} asks to take back a move", player),
+            (None, None, Some(player)) => format!("player {} to move", player),
+            (None, None, None) => "the game changed".to_string(),
+        };
+        let mut message = format!("Game {}: {}", event.game_id, news);
+        let link = self.config.public_url.as_ref().map(|url| {
+            format!(
+                "{}/{}/games/{}/board.svg",
+                url.trim_end_matches('/'),
+                SUPPORTED_VERSION,
+                event.game_id
+            )
+        });
+        let board = GameY::try_from(event.yen.clone()).ok().map(|game| {
+            let options = RenderOptions {
+                show_idx: false,
+                show_colors: false,
+                ..RenderOptions::default()
+            };
+            format!("```\n{}```", game.render(&options))
+        });
+        let board_fits = |board: &String| {
+            service != ChatService::Discord
+                || message.len() + board.len() + link.as_ref().map_or(0, |l| l.len()) + 2
+                    <= DISCORD_MAX_LENGTH
+        };
+        if let Some(board) = board.filter(board_fits) {
+            message.push('\n');
+            message.push_str(&board);
+        }
+        if let Some(link) = link {
+            message.push('\n');
+            message.push_str(&link);
+        }
+        message
+    }
+}
+
+impl GameObserver for ChatNotifier {
+    fn observe(&self, event: &TurnEvent) {
+        for webhook in &self.config.webhooks {
+            if webhook.results_only && event.winner.is_none() {
+                continue;
+            }
+            let text = self.message(event, webhook.service);
+            spawn_post(&webhook.url, webhook.service.payload(&text).to_string());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::YEN;
+
+    fn event(next_player: Option<u32>, winner: Option<u32>) -> TurnEvent {
+        let mut game = GameY::new(3);
+        game.play_move_list("0").unwrap();
+        TurnEvent {
+            game_id: "g1".to_string(),
+            next_player,
+            winner,
+            yen: YEN::from(&game),
+            takeback_requested_by: None,
+        }
+    }
+
+    #[test]
+    fn test_message_shows_the_board_and_links_its_image() {
+        let notifier = ChatNotifier::new(ChatConfig {
+            public_url: Some("https://gamey.example.org/".to_string()),
+            webhooks: Vec::new(),
+        });
+        let message = notifier.message(&event(Some(1), None), ChatService::Slack);
+        let lines: Vec<&str> = message.lines().collect();
+        assert_eq!(lines[0], "Game g1: player 1 to move");
+        assert_eq!(lines[1], "```");
+        assert!(message.contains("Game of Y (Size 3)"));
+        assert!(!message.contains('\x1b'));
+        assert_eq!(
+            lines.last(),
+            Some(&"https://gamey.example.org/v1/games/g1/board.svg")
+        );
+
+        let message = ChatNotifier::new(ChatConfig::default())
+            .message(&event(None, Some(0)), ChatService::Discord);
+        assert!(message.starts_with("Game g1: player 0 won\n```"));
+        assert!(message.ends_with("```"));
+    }
+
+    #[test]
+    fn test_payload_per_service() {
+        assert_eq!(
+            ChatService::Slack.payload("hi"),
+            serde_json::json!({ "text": "hi" })
+        );
+        assert_eq!(
+            ChatService::Discord.payload("hi"),
+            serde_json::json!({ "content": "hi" })
+        );
+        let config: ChatConfig = toml::from_str(
+            "[[webhooks]]\nurl = \"https://hooks.slack.com/services/T000/B000/XXXX\"\nservice = \"slack\"\nresults_only = true\n",
+        )
+        .unwrap();
+        assert_eq!(config.webhooks[0].service, ChatService::Slack);
+        assert!(config.webhooks[0].results_only);
+        assert!(
+            toml::from_str::<ChatConfig>("[[webhooks]]\nurl = \"x\"\nservice = \"irc\"\n").is_err()
+        );
+    }
+
+    #[test]
+    fn test_plain_http_webhooks_are_rejected() {
+        let error = toml::from_str::<ChatConfig>(
+            "[[webhooks]]\nurl = \"http://relay/slack\"\nservice = \"slack\"\n",
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains("must start with https://"));
+    }
+}
@@ -0,0 +1,412 @@
+use crate::{
+    SUPPORTED_VERSION,
+    audit::{AuditEntry, AuditedRequest, ClientAddr},
+    cache::{CacheKey, CachePolicy, CachedMove},
+    check_api_version,
+    demo::{bot_worker, check_board_size},
+    error::{ErrorContext, ErrorResponse},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue},
+    response::sse::{Event, Sse},
+};
+use futures_util::Stream;
+use gamey_bots::{CancellationToken, YBot};
+use gamey_core::{Coordinates, ErrorCode, GameY, YEN};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::task::JoinError;
+
+/// Path parameters extracted from the choose endpoint URL.
+#[derive(Deserialize)]
+pub struct ChooseParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The identifier of the bot to use for move selection.
+    bot_id: String,
+}
+
+impl ChooseParams {
+    /// The parameters of a request to `bot_id` in the current API version,
+    /// for callers that do not come through the router.
+    pub fn new(bot_id: &str) -> Self {
+        ChooseParams {
+            api_version: SUPPORTED_VERSION.to_string(),
+            bot_id: bot_id.to_string(),
+        }
+    }
+}
+
+/// Query parameters accepted by the choose endpoint.
+#[derive(Deserialize, Default)]
+pub struct ChooseQuery {
+    /// If true, the response includes the bot's explanation of its move.
+    #[serde(default)]
+    explain: bool,
+}
+
+/// Response returned by the choose endpoint on success.
+///
+/// Contains the bot's chosen move coordinates along with context
+/// about which API version and bot were used.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MoveResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The bot that selected this move.
+    pub bot_id: String,
+    /// The coordinates where the bot chooses to place its piece.
+    pub coords: Coordinates,
+    /// Why the bot chose this move, when requested with `?explain=true`
+    /// and supported by the bot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub explanation: Option<String>,
+}
+
+/// Handler for the bot move selection endpoint.
+///
+/// This endpoint accepts a game state in YEN format and returns the
+/// coordinates of the bot's chosen move.
+///
+/// # Route
+/// `POST /{api_version}/ybot/choose/{bot_id}[?explain=true]`
+///
+/// # Request Body
+/// A JSON object in YEN format representing the current game state.
+///
+/// # Response
+/// On success, returns a `MoveResponse` with the chosen coordinates.
+/// On failure, returns an `ErrorResponse` with details about what went wrong.
+///
+/// The search stops when the client disconnects, and fails with a
+/// `TIMEOUT` error after [`AppState::bot_timeout`].
+///
+/// Answers of deterministic bots are cached, unless the request opts out
+/// with a `Cache-Control` header (see [`cache`](crate::cache)).
+#[axum::debug_handler]
+pub async fn choose(
+    State(state): State<AppState>,
+    Path(params): Path<ChooseParams>,
+    Query(query): Query<ChooseQuery>,
+    ClientAddr(client): ClientAddr,
+    headers: HeaderMap,
+    Json(yen): Json<YEN>,
+) -> Result<(HeaderMap, Json<MoveResponse>), ErrorResponse> {
+    let (game_y, bot) = game_and_bot(&state, &params, yen)?;
+    let position_hash = game_y.zobrist_hash();
+    let bot_id = params.bot_id.clone();
+    let result = answer(&state, params, query, &headers, game_y, bot).await;
+    if let Some(log) = state.audit_log() {
+        let outcome = result.as_ref().map(|(_, response)| Some(response.coords));
+        let entry = AuditEntry::new(AuditedRequest::Choose, client, position_hash)
+            .with_bot(&bot_id)
+            .with_outcome(outcome.map_err(|e| e.code));
+        log.record(&entry);
+    }
+    result
+}
+
+/// Answers a choose request from the cache or by running the bot.
+async fn answer(
+    state: &AppState,
+    params: ChooseParams,
+    query: ChooseQuery,
+    headers: &HeaderMap,
+    game_y: GameY,
+    bot: Arc<dyn YBot>,
+) -> Result<(HeaderMap, Json<MoveResponse>), ErrorResponse> {
+    let cache = state.move_cache();
+    let policy = CachePolicy::from_headers(headers);
+    let key = (bot.is_deterministic() && cache.is_enabled()).then(|| CacheKey {
+        position: game_y.position(),
+        bot_id: params.bot_id.clone(),
+        explain: query.explain,
+    });
+    let mut response_headers = HeaderMap::new();
+    if let Some(key) = &key {
+        let cached = policy.read.then(|| cache.get(key)).flatten();
+        let status = ["MISS", "HIT"][cached.is_some() as usize];
+        response_headers.insert("x-cache", HeaderValue::from_static(status));
+        if let Some(cached) = cached {
+            let response = MoveResponse {
+                api_version: params.api_version,
+                bot_id: params.bot_id,
+                coords: cached.coords,
+                explanation: cached.explanation,
+            };
+            return Ok((response_headers, Json(response)));
+        }
+    }
+    let worker = bot_worker(state)
+        .await
+        .context(&params.api_version, Some(&params.bot_id))?;
+    // Dropping the guard stops the search: when this handler returns, times
+    // out, or is dropped because the client went away
+    let cancel = CancellationToken::new();
+    let _guard = cancel.clone().drop_guard();
+    let search = tokio::task::spawn_blocking(move || {
+        let _worker = worker;
+        let choice = bot.choose_move_with_progress(&game_y, &mut |_| {}, &cancel);
+        let explanation = choice
+            .as_ref()
+            .filter(|_| query.explain && !cancel.is_cancelled())
+            .and_then(|(coords, stats)| {
+                // The search that chose the move may already explain it
+                stats
+                    .explanation
+                    .clone()
+                    .or_else(|| bot.explain_move(&game_y, *coords))
+            });
+        (choice, explanation)
+    });
+    let timeout = state.bot_timeout();
+    let started = Instant::now();
+    let result = tokio::time::timeout(timeout, search).await;
+    let latencies = state.bot_latencies();
+    latencies.record(&params.bot_id, started.elapsed(), result.is_err());
+    let (choice, explanation) = result
+        .map_err(|_| timed_out(&params, timeout))?
+        .map_err(|e| bot_failed(&params, e))?;
+    // Handle the case where the bot has no valid moves
+    let (coords, _) = choice.ok_or_else(|| no_moves(&params))?;
+    if let Some(key) = key
+        && policy.write
+    {
+        let explanation = explanation.clone();
+        let cached = CachedMove {
+            coords,
+            explanation,
+        };
+        cache.insert(key, cached);
+    }
+    let response = MoveResponse {
+        api_version: params.api_version,
+        bot_id: params.bot_id,
+        coords,
+        explanation,
+    };
+    Ok((response_headers, Json(response)))
+}
+
+/// Handler that streams the progress of the bot's search as server-sent
+/// events, so clients can show the best move found so far.
+///
+/// # Route
+/// `POST /{api_version}/ybot/choose/{bot_id}/stream`
+///
+/// # Request Body
+/// A JSON object in YEN format representing the current game state.
+///
+/// # Response
+/// A stream of `progress` events carrying a
+/// [`SearchProgress`](gamey_bots::SearchProgress), ended by a `move` event
+/// carrying a [`MoveResponse`] or an `error` event carrying an
+/// [`ErrorResponse`]. Requests that cannot start a search fail before the
+/// stream starts, as with the choose endpoint.
+///
+/// The search stops when the client disconnects, and ends with a `TIMEOUT`
+/// error event after [`AppState::bot_timeout`].
+pub async fn choose_stream(
+    State(state): State<AppState>,
+    Path(params): Path<ChooseParams>,
+    ClientAddr(client): ClientAddr,
+    Json(yen): Json<YEN>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ErrorResponse> {
+    let (game_y, bot) = game_and_bot(&state, &params, yen)?;
+    let audit = state.audit_log().map(|log| {
+        let entry = AuditEntry::new(AuditedRequest::Choose, client, game_y.zobrist_hash());
+        (log, entry.with_bot(&params.bot_id))
+    });
+    let worker = bot_worker(&state)
+        .await
+        .context(&params.api_version, Some(&params.bot_id))?;
+    let (events, receiver) = tokio::sync::mpsc::unbounded_channel();
+    let cancel = CancellationToken::new();
+    let search = {
+        let (events, cancel) = (events.clone(), cancel.clone());
+        tokio::task::spawn_blocking(move || {
+            let _worker = worker;
+            let mut report = |progress: &_| {
+                let event = Event::default().event("progress").json_data(progress);
+                // The stream was dropped: the client went away
+                if events.send(event).is_err() {
+                    cancel.cancel();
+                }
+            };
+            bot.choose_move_with_progress(&game_y, &mut report, &cancel)
+        })
+    };
+    let timeout = state.bot_timeout();
+    let latencies = state.bot_latencies();
+    tokio::spawn(async move {
+        let error = |error: ErrorResponse| Event::default().event("error").json_data(error);
+        let started = Instant::now();
+        let result = tokio::time::timeout(timeout, search).await;
+        latencies.record(&params.bot_id, started.elapsed(), result.is_err());
+        let outcome = match result {
+            Ok(Ok(Some((coords, _)))) => Ok(coords),
+            Ok(Ok(None)) => Err(no_moves(&params)),
+            Ok(Err(e)) => Err(bot_failed(&params, e)),
+            Err(_) => {
+                cancel.cancel();
+                Err(timed_out(&params, timeout))
+            }
+        };
+        if let Some((log, entry)) = audit {
+            let returned = outcome.as_ref().map(|coords| Some(*coords));
+            log.record(&entry.with_outcome(returned.map_err(|e| e.code)));
+        }
+        let event = match outcome {
+            Ok(coords) => Event::default().event("move").json_data(MoveResponse {
+                api_version: params.api_version.clone(),
+                bot_id: params.bot_id.clone(),
+                coords,
+                explanation: None,
+            }),
+            Err(e) => error(e),
+        };
+        let _ = events.send(event);
+    });
+    let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+        let event = receiver.recv().await?;
+        Some((event, receiver))
+    });
+    Ok(Sse::new(stream))
+}
+
+/// Loads the position of a choose request and finds the requested bot.
+fn game_and_bot(
+    state: &AppState,
+    params: &ChooseParams,
+    yen: YEN,
+) -> Result<(GameY, Arc<dyn YBot>), ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let game_y = GameY::try_from(yen)
+        .map_err(ErrorResponse::invalid_yen)
+        .context(&params.api_version, Some(&params.bot_id))?;
+    check_board_size(state, game_y.board_size())
+        .context(&params.api_version, Some(&params.bot_id))?;
+    let bot = state.bots().find(&params.bot_id).ok_or_else(|| {
+        ErrorResponse::error(
+            ErrorCode::BotNotFound,
+            &format!(
+                "Bot not found: {}, available bots: [{}]",
+                params.bot_id,
+                state.bots().names().join(", ")
+            ),
+            Some(params.api_version.clone()),
+            Some(params.bot_id.clone()),
+        )
+    })?;
+    Ok((game_y, bot))
+}
+
+/// The error of a bot that did not choose a move in time.
+fn timed_out(params: &ChooseParams, timeout: Duration) -> ErrorResponse {
+    ErrorResponse::error(
+        ErrorCode::Timeout,
+        &format!("The bot did not choose a move within {:?}", timeout),
+        Some(params.api_version.clone()),
+        Some(params.bot_id.clone()),
+    )
+}
+
+/// The error of a bot whose search panicked.
+fn bot_failed(params: &ChooseParams, err: JoinError) -> ErrorResponse {
+    ErrorResponse::error(
+        ErrorCode::ServerError,
+        &format!("The bot failed: {}", err),
+        Some(params.api_version.clone()),
+        Some(params.bot_id.clone()),
+    )
+}
+
+/// The error of a bot that has no valid move to play.
+fn no_moves(params: &ChooseParams) -> ErrorResponse {
+    ErrorResponse::error(
+        ErrorCode::NoMoves,
+        "No valid moves available for the bot",
+        Some(params.api_version.clone()),
+        Some(params.bot_id.clone()),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_response_creation() {
+        let response = MoveResponse {
+            api_version: "v1".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(1, 2, 3),
+            explanation: None,
+        };
+        assert_eq!(response.api_version, "v1");
+        assert_eq!(response.bot_id, "random");
+        assert_eq!(response.coords, Coordinates::new(1, 2, 3));
+    }
+
+    #[test]
+    fn test_move_response_serialize() {
+        let response = MoveResponse {
+            api_version: "v1".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(1, 2, 3),
+            explanation: None,
+        };
+        let json = serde_json::to_string(&response).unwrap();
+        assert!(json.contains("\"api_version\":\"v1\""));
+        assert!(json.contains("\"bot_id\":\"random\""));
+        assert!(!json.contains("explanation"));
+    }
+
+    #[test]
+    fn test_move_response_deserialize() {
+        let json = r#"{"api_version":"v1","bot_id":"test","coords":{"x":0,"y":1,"z":2}}"#;
+        let response: MoveResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(response.api_version, "v1");
+        assert_eq!(response.bot_id, "test");
+    }
+
+    #[test]
+    fn test_move_response_clone() {
+        let response = MoveResponse {
+            api_version: "v1".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(0, 0, 0),
+            explanation: None,
+        };
+        let cloned = response.clone();
+        assert_eq!(response, cloned);
+    }
+
+    #[test]
+    fn test_move_response_equality() {
+        let r1 = MoveResponse {
+            api_version: "v1".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(1, 1, 1),
+            explanation: None,
+        };
+        let r2 = MoveResponse {
+            api_version: "v1".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(1, 1, 1),
+            explanation: None,
+        };
+        let r3 = MoveResponse {
+            api_version: "v2".to_string(),
+            bot_id: "random".to_string(),
+            coords: Coordinates::new(1, 1, 1),
+            explanation: None,
+        };
+        assert_eq!(r1, r2);
+        assert_ne!(r1, r3);
+    }
+}
@@ -0,0 +1,280 @@
+//! Demo mode, for running the server on a small machine in front of a
+//! classroom.
+//!
+//! A whole class pointing its clients at one server can easily ask for more
+//! searches than a small VM can run. In demo mode the server:
+//! - rejects boards larger than [`DemoLimits::max_board_size`] with an
+//!   `INVALID_BOARD_SIZE` error,
+//! - answers `TOO_MANY_REQUESTS` to a client that already has
+//!   [`DemoLimits::max_requests_per_client`] requests in progress, and
+//! - runs bots on at most [`DemoLimits::workers`] threads at a time; up to
+//!   [`DemoLimits::max_queued`] searches wait for a free worker, and any more
+//!   are answered `SERVER_BUSY`.
+//!
+//! Clients are told apart by their IP address.
+
+use crate::{error::ErrorResponse, state::AppState};
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use gamey_core::ErrorCode;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// The limits of a server running in demo mode.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct DemoLimits {
+    /// The largest board size accepted.
+    pub max_board_size: u32,
+    /// The largest number of requests a client may have in progress.
+    pub max_requests_per_client: usize,
+    /// The largest number of bots searching at the same time.
+    pub workers: usize,
+    /// The largest number of searches waiting for a worker.
+    pub max_queued: usize,
+}
+
+impl Default for DemoLimits {
+    fn default() -> Self {
+        DemoLimits {
+            max_board_size: 11,
+            max_requests_per_client: 2,
+            workers: 2,
+            max_queued: 16,
+        }
+    }
+}
+
+/// The shared state of demo mode: the requests in progress of every client
+/// and the pool of bot workers.
+pub struct Demo {
+    limits: DemoLimits,
+    clients: Mutex<HashMap<IpAddr, usize>>,
+    workers: Arc<Semaphore>,
+    queued: AtomicUsize,
+}
+
+impl Demo {
+    /// Creates the state of demo mode with the given limits.
+    pub fn new(limits: DemoLimits) -> Self {
+        Demo {
+            limits,
+            clients: Mutex::new(HashMap::new()),
+            workers: Arc::new(Semaphore::new(limits.workers)),
+            queued: AtomicUsize::new(0),
+        }
+    }
+
+    /// Returns the limits being enforced.
+    pub fn limits(&self) -> DemoLimits {
+        self.limits
+    }
+
+    /// Fails if a board of the given size is too large for demo mode.
+    pub fn check_board_size(&self, size: u32) -> Result<(), ErrorResponse> {
+        if size <= self.limits.max_board_size {
+            return Ok(());
+        }
+        Err(ErrorResponse::error(
+            ErrorCode::InvalidBoardSize,
+            &format!(
+                "Board size {} is too large, this demo server accepts up to size {}",
+                size, self.limits.max_board_size
+            ),
+            None,
+            None,
+        ))
+    }
+
+    /// Counts a new request of `client`, or returns `None` if the client
+    /// already has as many requests in progress as allowed. The request
+    /// stops counting when the returned slot is dropped.
+    pub fn enter(self: &Arc<Self>, client: IpAddr) -> Option<ClientSlot> {
+        let mut clients = self.lock();
+        let requests = clients.entry(client).or_insert(0);
+        if *requests >= self.limits.max_requests_per_client {
+            return None;
+        }
+        *requests += 1;
+        Some(ClientSlot {
+            demo: Arc::clone(self),
+            client,
+        })
+    }
+
+    /// Waits for a free bot worker. Fails with `SERVER_BUSY` if too many
+    /// searches are already waiting.
+    ///
+    /// The worker is free again when the permit is dropped, so it should be
+    /// moved into the task running the bot.
+    pub async fn worker(&self) -> Result<OwnedSemaphorePermit, ErrorResponse> {
+        if let Ok(permit) = Arc::clone(&self.workers).try_acquire_owned() {
+            return Ok(permit);
+        }
+        let _waiting = Waiting::enter(&self.queued, self.limits.max_queued).ok_or_else(|| {
+            ErrorResponse::error(
+                ErrorCode::ServerBusy,
+                "The server is busy, please try again later",
+                None,
+                None,
+            )
+        })?;
+        Arc::clone(&self.workers)
+            .acquire_owned()
+            .await
+            .map_err(|e| ErrorResponse::error(ErrorCode::ServerError, &e.to_string(), None, None))
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<IpAddr, usize>> {
+        self.clients.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// A request in progress of a client, see [`Demo::enter`].
+pub struct ClientSlot {
+    demo: Arc<Demo>,
+    client: IpAddr,
+}
+
+impl Drop for ClientSlot {
+    fn drop(&mut self) {
+        let mut clients = self.demo.lock();
+        if let Some(requests) = clients.get_mut(&self.client) {
+            *requests -= 1;
+            if *requests == 0 {
+                clients.remove(&self.client);
+            }
+        }
+    }
+}
+
+/// A search waiting for a worker, counted while it waits.
+struct Waiting<'a>(&'a AtomicUsize);
+
+impl<'a> Waiting<'a> {
+    fn enter(queued: &'a AtomicUsize, max: usize) -> Option<Self> {
+        queued
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()?;
+        Some(Waiting(queued))
+    }
+}
+
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Middleware that rejects the requests of clients with too many requests
+/// in progress.
+///
+/// The client is the IP address of the connection; requests that did not
+/// come through a connection (e.g. in tests) all count as the same client.
+pub async fn limit_clients(
+    State(demo): State<Arc<Demo>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let client = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED), |info| info.0.ip());
+    let Some(_slot) = demo.enter(client) else {
+        return ErrorResponse::error(
+            ErrorCode::TooManyRequests,
+            &format!(
+                "Too many requests in progress, at most {} per client",
+                demo.limits.max_requests_per_client
+            ),
+            None,
+            None,
+        )
+        .into_response();
+    };
+    next.run(request).await
+}
+
+/// Fails if a board of the given size is too large for the server, which
+/// only limits board sizes in demo mode.
+pub(crate) fn check_board_size(state: &AppState, size: u32) -> Result<(), ErrorResponse> {
+    match state.demo() {
+        Some(demo) => demo.check_board_size(size),
+        None => Ok(()),
+    }
+}
+
+/// Waits for a free bot worker in demo mode; other servers run every
+/// search right away.
+pub(crate) async fn bot_worker(
+    state: &AppState,
+) -> Result<Option<OwnedSemaphorePermit>, ErrorResponse> {
+    match state.demo() {
+        Some(demo) => demo.worker().await.map(Some),
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits() -> DemoLimits {
+        DemoLimits {
+            max_board_size: 5,
+            max_requests_per_client: 2,
+            workers: 1,
+            max_queued: 1,
+        }
+    }
+
+    #[test]
+    fn test_clients_are_limited_separately() {
+        let demo = Arc::new(Demo::new(limits()));
+        let alice = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let bob = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let first = demo.enter(alice).unwrap();
+        let _second = demo.enter(alice).unwrap();
+        assert!(demo.enter(alice).is_none());
+        assert!(demo.enter(bob).is_some());
+
+        drop(first);
+        assert!(demo.enter(alice).is_some());
+    }
+
+    #[test]
+    fn test_large_boards_are_rejected() {
+        let demo = Demo::new(limits());
+        assert!(demo.check_board_size(5).is_ok());
+        let err = demo.check_board_size(6).unwrap_err();
+        assert_eq!(err.code, ErrorCode::InvalidBoardSize);
+    }
+
+    #[tokio::test]
+    async fn test_searches_beyond_the_queue_are_rejected() {
+        let demo = Arc::new(Demo::new(limits()));
+        let running = demo.worker().await.unwrap();
+        let queued = {
+            let demo = Arc::clone(&demo);
+            tokio::spawn(async move { demo.worker().await.map(|_| ()) })
+        };
+        while demo.queued.load(Ordering::Acquire) == 0 {
+            tokio::task::yield_now().await;
+        }
+        let err = demo.worker().await.unwrap_err();
+        assert_eq!(err.code, ErrorCode::ServerBusy);
+
+        drop(running);
+        assert!(queued.await.unwrap().is_ok());
+        assert_eq!(demo.queued.load(Ordering::Acquire), 0);
+    }
+}
@@ -0,0 +1,239 @@
+use axum::{Json, http::StatusCode, response::IntoResponse};
+use gamey_core::{ErrorCode, GameYError};
+use serde::{Deserialize, Serialize};
+
+/// A structured error response returned by the bot server API.
+///
+/// This type is serialized to JSON and returned when API requests fail.
+/// It includes context about which API version and bot were involved, and a
+/// machine-readable [`ErrorCode`] clients can branch on.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ErrorResponse {
+    /// The API version that was requested, if available.
+    pub api_version: Option<String>,
+    /// The bot ID that was requested, if available.
+    pub bot_id: Option<String>,
+    /// What kind of error happened.
+    pub code: ErrorCode,
+    /// A human-readable error message describing what went wrong.
+    pub message: String,
+}
+
+impl ErrorResponse {
+    /// Creates a new error response with the given message and optional context.
+    ///
+    /// # Arguments
+    /// * `code` - The kind of error
+    /// * `message` - A description of the error
+    /// * `api_version` - The API version from the request, if known
+    /// * `bot_id` - The bot ID from the request, if known
+    pub fn error(
+        code: ErrorCode,
+        message: &str,
+        api_version: Option<String>,
+        bot_id: Option<String>,
+    ) -> Self {
+        Self {
+            bot_id,
+            api_version,
+            code,
+            message: message.to_string(),
+        }
+    }
+
+    /// Creates the response for a YEN position the engine could not load.
+    pub fn invalid_yen(err: GameYError) -> Self {
+        ErrorResponse::error(
+            ErrorCode::InvalidYen,
+            &format!("Invalid YEN format: {}", err),
+            None,
+            None,
+        )
+    }
+
+    /// Returns the HTTP status the kind of error stands for.
+    ///
+    /// Unknown resources map to 404, requests without valid credentials to
+    /// 401, moves that clash with the state of the game to 409, clients with
+    /// too many requests in progress to 429, server failures to 500, bots
+    /// running out of time and busy servers to 503 and anything else the
+    /// client sent wrong to 400.
+    ///
+    /// The HTTP API still answers every error with `200 OK` and the error as
+    /// the body, so clients tell errors apart by their [`ErrorCode`]; the
+    /// status classifies errors for other transports, such as the gRPC
+    /// service.
+    pub fn status(&self) -> StatusCode {
+        match self.code {
+            ErrorCode::BotNotFound | ErrorCode::GameNotFound | ErrorCode::SessionNotFound => {
+                StatusCode::NOT_FOUND
+            }
+            ErrorCode::Occupied
+            | ErrorCode::BlockedCell
+            | ErrorCode::GameOver
+            | ErrorCode::InvalidPlayerTurn
+            | ErrorCode::NothingToTakeBack
+            | ErrorCode::NoTakebackRequest
+            | ErrorCode::NoMoves => StatusCode::CONFLICT,
+            ErrorCode::IoError | ErrorCode::InvalidConfig | ErrorCode::ServerError => {
+                StatusCode::INTERNAL_SERVER_ERROR
+            }
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            ErrorCode::Timeout | ErrorCode::ServerBusy => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// Converts an engine error into a response without request context, so
+/// handlers can use `?` on engine results; see [`ErrorContext`] to keep the
+/// API version and bot of the request.
+impl From<GameYError> for ErrorResponse {
+    fn from(err: GameYError) -> Self {
+        ErrorResponse::error(err.code(), &err.to_string(), None, None)
+    }
+}
+
+/// Answers with the error as JSON and `200 OK`, the status the API has
+/// always answered errors with.
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> axum::response::Response {
+        (StatusCode::OK, Json(self)).into_response()
+    }
+}
+
+/// Attaches the context of a request to the error of a result.
+///
+/// # Example
+/// ```
+/// use gamey_core::GameYError;
+/// use gamey_server::{ErrorContext, ErrorResponse};
+///
+/// let result: Result<(), GameYError> = Err(GameYError::GameNotFound { id: "g1".into() });
+/// let err: ErrorResponse = result.context("v1", None).unwrap_err();
+/// assert_eq!(err.api_version.as_deref(), Some("v1"));
+/// ```
+pub trait ErrorContext<T> {
+    /// Converts the error into an [`ErrorResponse`] for the given API version
+    /// and bot, keeping any context the error already had.
+    fn context(self, api_version: &str, bot_id: Option<&str>) -> Result<T, ErrorResponse>;
+}
+
+impl<T, E: Into<ErrorResponse>> ErrorContext<T> for Result<T, E> {
+    fn context(self, api_version: &str, bot_id: Option<&str>) -> Result<T, ErrorResponse> {
+        self.map_err(|err| {
+            let mut response: ErrorResponse = err.into();
+            response
+                .api_version
+                .get_or_insert_with(|| api_version.to_string());
+            if let Some(bot_id) = bot_id {
+                response.bot_id.get_or_insert_with(|| bot_id.to_string());
+            }
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_with_all_fields() {
+        let err = ErrorResponse::error(
+            ErrorCode::ServerError,
+            "Something went wrong",
+            Some("v1".to_string()),
+            Some("random".to_string()),
+        );
+        assert_eq!(err.message, "Something went wrong");
+        assert_eq!(err.api_version, Some("v1".to_string()));
+        assert_eq!(err.bot_id, Some("random".to_string()));
+    }
+
+    #[test]
+    fn test_error_with_no_context() {
+        let err = ErrorResponse::error(ErrorCode::ServerError, "Generic error", None, None);
+        assert_eq!(err.message, "Generic error");
+        assert_eq!(err.api_version, None);
+        assert_eq!(err.bot_id, None);
+    }
+
+    #[test]
+    fn test_error_with_partial_context() {
+        let err = ErrorResponse::error(
+            ErrorCode::UnsupportedApiVersion,
+            "Version error",
+            Some("v2".to_string()),
+            None,
+        );
+        assert_eq!(err.message, "Version error");
+        assert_eq!(err.api_version, Some("v2".to_string()));
+        assert_eq!(err.bot_id, None);
+    }
+
+    #[test]
+    fn test_serialize() {
+        let err = ErrorResponse::error(
+            ErrorCode::BotNotFound,
+            "Test error",
+            Some("v1".to_string()),
+            Some("bot1".to_string()),
+        );
+        let json = serde_json::to_string(&err).unwrap();
+        assert!(json.contains("\"message\":\"Test error\""));
+        assert!(json.contains("\"api_version\":\"v1\""));
+        assert!(json.contains("\"bot_id\":\"bot1\""));
+        assert!(json.contains("\"code\":\"BOT_NOT_FOUND\""));
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let json = r#"{"api_version":"v1","bot_id":"random","code":"BOT_NOT_FOUND","message":"error msg"}"#;
+        let err: ErrorResponse = serde_json::from_str(json).unwrap();
+        assert_eq!(err.message, "error msg");
+        assert_eq!(err.api_version, Some("v1".to_string()));
+        assert_eq!(err.bot_id, Some("random".to_string()));
+        assert_eq!(err.code, ErrorCode::BotNotFound);
+    }
+
+    #[test]
+    fn test_clone() {
+        let err = ErrorResponse::error(
+            ErrorCode::ServerError,
+            "Clone test",
+            Some("v1".to_string()),
+            None,
+        );
+        let cloned = err.clone();
+        assert_eq!(err, cloned);
+    }
+
+    #[test]
+    fn test_from_game_error() {
+        let err = ErrorResponse::from(GameYError::GameOver {
+            movement: gamey_core::Movement::Action {
+                player: gamey_core::PlayerId::new(0),
+                action: gamey_core::GameAction::Resign,
+            },
+        });
+        assert_eq!(err.code, ErrorCode::GameOver);
+        assert_eq!(err.status(), StatusCode::CONFLICT);
+        assert_eq!(err.api_version, None);
+    }
+
+    #[test]
+    fn test_context_keeps_existing_fields() {
+        let result: Result<(), ErrorResponse> = Err(ErrorResponse::error(
+            ErrorCode::UnsupportedApiVersion,
+            "Version error",
+            Some("v2".to_string()),
+            None,
+        ));
+        let err = result.context("v1", Some("bot")).unwrap_err();
+        assert_eq!(err.api_version, Some("v2".to_string()));
+        assert_eq!(err.bot_id, Some("bot".to_string()));
+        assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+    }
+}
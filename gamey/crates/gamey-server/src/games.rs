@@ -0,0 +1,1354 @@
+//! Correspondence games hosted by the bot server.
+//!
+//! Unlike the stateless choose endpoint, these games live on the server and
+//! may last for days: every move is submitted on its own request, the game
+//! can be persisted to disk after each change, and registered webhooks are
+//! notified whenever it becomes a player's turn.
+//!
+//! # Endpoints
+//! - `POST /{api_version}/games` - Create a new game
+//! - `GET /{api_version}/games/{game_id}` - Export the game (YEN + history)
+//! - `GET /{api_version}/games/{game_id}/state` - Dump the full game state
+//! - `GET /{api_version}/games/{game_id}/board.svg` - The board as an image
+//! - `GET /{api_version}/games/{game_id}/board.txt` - The board as plain text
+//! - `POST /{api_version}/games/{game_id}/moves` - Play a move
+//! - `POST /{api_version}/games/{game_id}/takeback` - Ask the opponent to
+//!   take back the last move pair
+//! - `POST /{api_version}/games/{game_id}/takeback/answer` - Accept or
+//!   decline the opponent's takeback request
+//! - `POST /{api_version}/games/{game_id}/webhooks` - Register a turn webhook
+//!   (only URLs whose host resolves to public addresses are accepted)
+//! - `GET /{api_version}/games/{game_id}/events` - Follow the game as
+//!   server-sent events
+
+use crate::{
+    audit::{AuditEntry, AuditedRequest, ClientAddr},
+    check_api_version,
+    demo::check_board_size,
+    error::{ErrorContext, ErrorResponse},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::header,
+    response::{
+        IntoResponse, Response,
+        sse::{Event, KeepAlive, Sse},
+    },
+};
+use futures_util::Stream;
+use gamey_core::{
+    Annotation, BoardTopology, ErrorCode, GameMetadata, GameStateDump, GameY, GameYError,
+    MoveRecord, Movement, PlayerId, RenderOptions, YEN, migrate,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// How many turn events a slow watcher may fall behind before it misses
+/// some; it only needs the latest one to redraw the game.
+const WATCH_CAPACITY: usize = 64;
+
+/// A webhook registered on a hosted game.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Webhook {
+    /// The `http://` or `https://` URL that receives a JSON `POST` for each
+    /// event; its host must resolve to public addresses only (see
+    /// [`check_webhook_url`]).
+    pub url: String,
+    /// If set, only notify when it is this player's turn (or the game ends).
+    #[serde(default)]
+    pub player: Option<u32>,
+}
+
+/// The payload delivered to webhooks after a move has been played.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TurnEvent {
+    /// The game that changed.
+    pub game_id: String,
+    /// The player who has to move now, if the game is still ongoing.
+    pub next_player: Option<u32>,
+    /// The winner, if the game has finished.
+    pub winner: Option<u32>,
+    /// The current position.
+    pub yen: YEN,
+    /// The player waiting for the opponent to answer a takeback request.
+    #[serde(default)]
+    pub takeback_requested_by: Option<u32>,
+}
+
+impl TurnEvent {
+    /// Returns true if the given webhook should receive this event: its
+    /// player has to move or to answer a takeback, or the game has ended.
+    pub fn concerns(&self, webhook: &Webhook) -> bool {
+        match webhook.player {
+            Some(player) => {
+                self.winner.is_some()
+                    || self.next_player == Some(player)
+                    || self
+                        .takeback_requested_by
+                        .is_some_and(|requester| requester != player)
+            }
+            None => true,
+        }
+    }
+}
+
+/// A hook that tells players when it is their turn.
+///
+/// The default implementation is [`WebhookNotifier`]; other transports (for
+/// example e-mail) can be plugged into the [`GameStore`] by implementing this
+/// trait.
+pub trait TurnNotifier: Send + Sync {
+    /// Delivers `event` to `webhook`. Implementations must not block.
+    fn notify(&self, webhook: &Webhook, event: &TurnEvent);
+}
+
+/// Delivers turn events as JSON `POST` requests in a background task.
+pub struct WebhookNotifier;
+
+impl TurnNotifier for WebhookNotifier {
+    fn notify(&self, webhook: &Webhook, event: &TurnEvent) {
+        match serde_json::to_string(event) {
+            Ok(body) => spawn_post(&webhook.url, body),
+            Err(e) => tracing::warn!("Cannot serialize turn event: {}", e),
+        }
+    }
+}
+
+/// Something told of every change of the games hosted by a [`GameStore`],
+/// e.g. the [`ChatNotifier`](crate::chat::ChatNotifier).
+///
+/// Unlike a [`TurnNotifier`], which delivers events to the webhooks
+/// registered on each game, an observer sees the events of all games.
+pub trait GameObserver: Send + Sync {
+    /// Receives the new state of a game after a change. Implementations
+    /// must not block.
+    fn observe(&self, event: &TurnEvent);
+}
+
+/// How long a webhook may take to accept the connection.
+const WEBHOOK_CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// How long a webhook may take to answer a post, connection included.
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Returns the HTTP client shared by every webhook post, which speaks
+/// HTTPS and gives up on endpoints slower than [`WEBHOOK_TIMEOUT`].
+fn webhook_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .connect_timeout(WEBHOOK_CONNECT_TIMEOUT)
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .expect("the webhook HTTP client is valid")
+    })
+}
+
+/// Sends `body` to `url` with [`post_json`] in a background task, logging
+/// failures.
+pub(crate) fn spawn_post(url: &str, body: String) {
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        tracing::warn!("No async runtime available to notify {}", url);
+        return;
+    };
+    let url = url.to_string();
+    handle.spawn(async move {
+        if let Err(e) = post_json(&url, body).await {
+            tracing::warn!("Webhook {} failed: {}", url, e);
+        }
+    });
+}
+
+/// Sends a JSON `POST` request to an `http://` or `https://` URL, failing
+/// unless it is answered with a success status.
+async fn post_json(url: &str, body: String) -> Result<(), String> {
+    webhook_client()
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .body(body)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
+}
+
+/// Checks that `url` may be registered as a webhook: an `http://` or
+/// `https://` URL whose host only resolves to public addresses, so that
+/// clients cannot make the server post to itself or to its private network.
+///
+/// # Errors
+/// Returns an `INVALID_WEBHOOK` error naming the URL otherwise.
+pub async fn check_webhook_url(url: &str) -> Result<(), ErrorResponse> {
+    let invalid = |reason: &str| {
+        ErrorResponse::error(
+            ErrorCode::InvalidWebhook,
+            &format!("Invalid webhook URL {}: {}", url, reason),
+            None,
+            None,
+        )
+    };
+    let parsed = reqwest::Url::parse(url).map_err(|e| invalid(&e.to_string()))?;
+    if !matches!(parsed.scheme(), "http" | "https") {
+        return Err(invalid("only http:// and https:// are supported"));
+    }
+    let host = parsed.host_str().ok_or_else(|| invalid("it has no host"))?;
+    let addresses: Vec<IpAddr> = match host.trim_matches(['[', ']']).parse::<IpAddr>() {
+        Ok(ip) => vec![ip],
+        Err(_) => {
+            let port = parsed.port_or_known_default().unwrap_or(80);
+            tokio::net::lookup_host((host, port))
+                .await
+                .map_err(|e| invalid(&format!("cannot resolve {}: {}", host, e)))?
+                .map(|address| address.ip())
+                .collect()
+        }
+    };
+    if addresses.is_empty() || !addresses.iter().all(|ip| is_public(*ip)) {
+        return Err(invalid("its host does not resolve to public addresses"));
+    }
+    Ok(())
+}
+
+/// Returns true unless `ip` is a loopback, private, link-local, shared,
+/// unspecified, broadcast or multicast address.
+fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, ..] = ip.octets();
+            !(ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_multicast()
+                || a == 0
+                || (a == 100 && b & 0xc0 == 64))
+        }
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public(IpAddr::V4(ip)),
+            None => {
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_unique_local()
+                    || ip.is_unicast_link_local()
+                    || ip.is_multicast())
+            }
+        },
+    }
+}
+
+/// A game hosted by the server together with its registered webhooks.
+#[derive(Clone)]
+struct HostedGame {
+    game: GameY,
+    webhooks: Vec<Webhook>,
+    // The player whose takeback request waits for the opponent's answer.
+    takeback: Option<PlayerId>,
+}
+
+/// The current version of the on-disk format of hosted games.
+const STORED_GAME_VERSION: u32 = 1;
+
+/// The on-disk representation of a hosted game.
+#[derive(Serialize, Deserialize)]
+struct StoredGame {
+    version: u32,
+    id: String,
+    size: u32,
+    #[serde(default)]
+    topology: BoardTopology,
+    history: Vec<MoveRecord>,
+    webhooks: Vec<Webhook>,
+    #[serde(default)]
+    annotations: Vec<Annotation>,
+    #[serde(default)]
+    takeback: Option<u32>,
+    #[serde(default)]
+    metadata: GameMetadata,
+}
+
+/// A snapshot of a hosted game, as returned by the games endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct GameExport {
+    /// The identifier of the game.
+    pub game_id: String,
+    /// The current position.
+    pub yen: YEN,
+    /// Every move played so far, in order.
+    pub history: Vec<MoveRecord>,
+    /// The player who has to move, if the game is still ongoing.
+    pub next_player: Option<u32>,
+    /// The winner, if the game has finished.
+    pub winner: Option<u32>,
+    /// Notes attached to the history, such as accepted takebacks.
+    #[serde(default)]
+    pub annotations: Vec<Annotation>,
+    /// The player waiting for the opponent to answer a takeback request.
+    #[serde(default)]
+    pub takeback_requested_by: Option<u32>,
+    /// The event, date, players and result of the game.
+    #[serde(default)]
+    pub metadata: GameMetadata,
+}
+
+impl GameExport {
+    fn new(game_id: &str, hosted: &HostedGame) -> Self {
+        let game = &hosted.game;
+        GameExport {
+            game_id: game_id.to_string(),
+            yen: game.into(),
+            history: game.history().iter().map(MoveRecord::from).collect(),
+            next_player: game.next_player().map(|player| player.id()),
+            winner: game.winner().map(|player| player.id()),
+            annotations: game.annotations().to_vec(),
+            takeback_requested_by: hosted.takeback.map(|player| player.id()),
+            metadata: game.metadata().clone(),
+        }
+    }
+
+    fn turn_event(&self) -> TurnEvent {
+        TurnEvent {
+            game_id: self.game_id.clone(),
+            next_player: self.next_player,
+            winner: self.winner,
+            yen: self.yen.clone(),
+            takeback_requested_by: self.takeback_requested_by,
+        }
+    }
+}
+
+/// A short description of a hosted game, as listed by the admin endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct GameSummary {
+    /// The identifier of the game.
+    pub game_id: String,
+    /// The size of the board.
+    pub size: u32,
+    /// The shape of the board.
+    pub topology: BoardTopology,
+    /// The number of moves played.
+    pub moves: usize,
+    /// The player who has to move, if the game is still ongoing.
+    pub next_player: Option<u32>,
+    /// The winner, if the game has finished.
+    pub winner: Option<u32>,
+}
+
+impl GameSummary {
+    fn new(game_id: &str, game: &GameY) -> Self {
+        GameSummary {
+            game_id: game_id.to_string(),
+            size: game.board_size(),
+            topology: game.topology(),
+            moves: game.move_count(),
+            next_player: game.next_player().map(|player| player.id()),
+            winner: game.winner().map(|player| player.id()),
+        }
+    }
+}
+
+/// Storage for the games hosted by the server.
+///
+/// Games are kept in memory and, when the store is opened on a directory,
+/// written to `<dir>/<game_id>.json` after every change so that they survive
+/// server restarts.
+pub struct GameStore {
+    games: Mutex<HashMap<String, HostedGame>>,
+    dir: Option<PathBuf>,
+    notifier: Arc<dyn TurnNotifier>,
+    observers: Vec<Arc<dyn GameObserver>>,
+    watchers: broadcast::Sender<TurnEvent>,
+}
+
+impl GameStore {
+    /// Creates an empty store that keeps games only in memory.
+    pub fn in_memory() -> Self {
+        GameStore {
+            games: Mutex::new(HashMap::new()),
+            dir: None,
+            notifier: Arc::new(WebhookNotifier),
+            observers: Vec::new(),
+            watchers: broadcast::channel(WATCH_CAPACITY).0,
+        }
+    }
+
+    /// Opens a persistent store in `dir`, loading any games saved there.
+    ///
+    /// The directory is created if it does not exist. Files that cannot be
+    /// read or replayed are logged and skipped.
+    pub fn open<P: Into<PathBuf>>(dir: P) -> Result<Self, GameYError> {
+        let dir = dir.into();
+        let dir_name = dir.display().to_string();
+        std::fs::create_dir_all(&dir).map_err(|e| GameYError::IoError {
+            message: format!("Failed to create games directory: {}", dir_name),
+            error: e.to_string(),
+        })?;
+        let entries = std::fs::read_dir(&dir).map_err(|e| GameYError::IoError {
+            message: format!("Failed to read games directory: {}", dir_name),
+            error: e.to_string(),
+        })?;
+        let mut games = HashMap::new();
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_some_and(|ext| ext == "json") {
+                // One damaged file must not keep the other games offline
+                match load_stored_game(&path) {
+                    Ok((id, hosted)) => {
+                        games.insert(id, hosted);
+                    }
+                    Err(e) => tracing::warn!("Skipping game file {}: {}", path.display(), e),
+                }
+            }
+        }
+        Ok(GameStore {
+            games: Mutex::new(games),
+            dir: Some(dir),
+            notifier: Arc::new(WebhookNotifier),
+            observers: Vec::new(),
+            watchers: broadcast::channel(WATCH_CAPACITY).0,
+        })
+    }
+
+    /// Replaces the notifier used to deliver turn events.
+    pub fn with_notifier(mut self, notifier: Arc<dyn TurnNotifier>) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    /// Adds an observer told of every change of every game.
+    pub fn with_observer(mut self, observer: Arc<dyn GameObserver>) -> Self {
+        self.observers.push(observer);
+        self
+    }
+
+    /// Creates a new game with the given board size and shape and returns
+    /// its export.
+    pub fn create(&self, size: u32, topology: BoardTopology) -> Result<GameExport, GameYError> {
+        self.create_with_metadata(size, topology, GameMetadata::default())
+    }
+
+    /// Creates a new game like [`GameStore::create`], recording the event,
+    /// date, players and result of `metadata`.
+    pub fn create_with_metadata(
+        &self,
+        size: u32,
+        topology: BoardTopology,
+        metadata: GameMetadata,
+    ) -> Result<GameExport, GameYError> {
+        GameY::check_board_size(size)?;
+        let id = format!("{:016x}", rand::random::<u64>());
+        let mut game = GameY::with_topology(size, topology);
+        game.set_metadata(metadata);
+        let hosted = HostedGame {
+            game,
+            webhooks: Vec::new(),
+            takeback: None,
+        };
+        let export = GameExport::new(&id, &hosted);
+        self.persist(&id, &hosted)?;
+        self.lock().insert(id, hosted);
+        Ok(export)
+    }
+
+    /// Returns the export of a game, or `None` if it does not exist.
+    pub fn get(&self, id: &str) -> Option<GameExport> {
+        self.lock()
+            .get(id)
+            .map(|hosted| GameExport::new(id, hosted))
+    }
+
+    /// Returns the full state dump of a game, or `None` if it does not exist.
+    pub fn state(&self, id: &str) -> Option<GameStateDump> {
+        self.lock().get(id).map(|hosted| hosted.game.to_state())
+    }
+
+    /// Returns the board of a game drawn by `draw`, or `None` if the game
+    /// does not exist.
+    pub fn draw<T>(&self, id: &str, draw: impl FnOnce(&GameY) -> T) -> Option<T> {
+        self.lock().get(id).map(|hosted| draw(&hosted.game))
+    }
+
+    /// Returns the identifiers of all hosted games.
+    pub fn ids(&self) -> Vec<String> {
+        self.lock().keys().cloned().collect()
+    }
+
+    /// Returns a summary of every hosted game, ordered by identifier.
+    pub fn summaries(&self) -> Vec<GameSummary> {
+        let mut summaries: Vec<GameSummary> = self
+            .lock()
+            .iter()
+            .map(|(id, hosted)| GameSummary::new(id, &hosted.game))
+            .collect();
+        summaries.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+        summaries
+    }
+
+    /// Returns the directory the games are persisted in, if any.
+    pub fn dir(&self) -> Option<&std::path::Path> {
+        self.dir.as_deref()
+    }
+
+    /// Removes a game, and deletes its file if the store is persistent.
+    pub fn remove(&self, id: &str) -> Result<(), GameYError> {
+        if self.lock().remove(id).is_none() {
+            return Err(GameYError::GameNotFound { id: id.to_string() });
+        }
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        let path = dir.join(format!("{}.json", id));
+        std::fs::remove_file(&path).map_err(|e| GameYError::IoError {
+            message: format!("Failed to delete file: {}", path.display()),
+            error: e.to_string(),
+        })
+    }
+
+    /// Returns the Zobrist hash of the current position of a game, or `None`
+    /// if it does not exist.
+    pub fn position_hash(&self, id: &str) -> Option<u64> {
+        self.lock().get(id).map(|hosted| hosted.game.zobrist_hash())
+    }
+
+    /// Plays a move in a hosted game and notifies the interested webhooks.
+    ///
+    /// The move is rejected if the game is over or it is not the mover's turn.
+    /// A pending takeback request lapses once a move is played.
+    pub fn play(&self, id: &str, movement: Movement) -> Result<GameExport, GameYError> {
+        let mut games = self.lock();
+        let hosted = games
+            .get_mut(id)
+            .ok_or_else(|| GameYError::GameNotFound { id: id.to_string() })?;
+        if hosted.game.check_game_over() {
+            return Err(GameYError::GameOver { movement });
+        }
+        hosted.game.check_player_turn(&movement)?;
+        if let Movement::Placement { player, coords } = movement
+            && hosted.game.is_occupied(coords)
+        {
+            return Err(GameYError::Occupied {
+                coordinates: coords,
+                player,
+            });
+        }
+        let mut updated = hosted.clone();
+        updated.game.add_move(movement)?;
+        updated.takeback = None;
+        self.commit(id, hosted, updated)
+    }
+
+    /// Asks the opponent of `player` to take back the last move of `player`
+    /// and the moves played after it; see [`GameStore::answer_takeback`].
+    pub fn request_takeback(&self, id: &str, player: PlayerId) -> Result<GameExport, GameYError> {
+        let mut games = self.lock();
+        let hosted = games
+            .get_mut(id)
+            .ok_or_else(|| GameYError::GameNotFound { id: id.to_string() })?;
+        if !hosted
+            .game
+            .history()
+            .iter()
+            .any(|movement| movement.player() == player)
+        {
+            return Err(GameYError::NothingToTakeBack { player });
+        }
+        let mut updated = hosted.clone();
+        updated.takeback = Some(player);
+        self.commit(id, hosted, updated)
+    }
+
+    /// Answers the takeback request of the opponent of `player`.
+    ///
+    /// An accepted takeback undoes the moves and is recorded as an
+    /// annotation of the game; a declined one leaves the game unchanged.
+    pub fn answer_takeback(
+        &self,
+        id: &str,
+        player: PlayerId,
+        accept: bool,
+    ) -> Result<GameExport, GameYError> {
+        let mut games = self.lock();
+        let hosted = games
+            .get_mut(id)
+            .ok_or_else(|| GameYError::GameNotFound { id: id.to_string() })?;
+        let requester = match hosted.takeback {
+            Some(requester) if requester != player => requester,
+            _ => return Err(GameYError::NoTakebackRequest { player }),
+        };
+        let mut updated = hosted.clone();
+        if accept {
+            updated.game.take_back(requester)?;
+        }
+        updated.takeback = None;
+        self.commit(id, hosted, updated)
+    }
+
+    /// Persists the new state of a game and, once it is saved, swaps it in
+    /// and notifies it; on failure the game is left as it was.
+    fn commit(
+        &self,
+        id: &str,
+        hosted: &mut HostedGame,
+        updated: HostedGame,
+    ) -> Result<GameExport, GameYError> {
+        self.persist(id, &updated)?;
+        *hosted = updated;
+        Ok(self.notify(id, hosted))
+    }
+
+    /// Delivers the new state of a game to its interested webhooks, the
+    /// observers and the watchers and returns its export.
+    fn notify(&self, id: &str, hosted: &HostedGame) -> GameExport {
+        let export = GameExport::new(id, hosted);
+        let event = export.turn_event();
+        for webhook in hosted.webhooks.iter().filter(|w| event.concerns(w)) {
+            self.notifier.notify(webhook, &event);
+        }
+        for observer in &self.observers {
+            observer.observe(&event);
+        }
+        // Nobody may be watching
+        let _ = self.watchers.send(event);
+        export
+    }
+
+    /// Returns the current state of a game and a receiver of the turn events
+    /// of every game from then on, or `None` if the game does not exist.
+    ///
+    /// The receiver gets the events of all games; watchers keep those whose
+    /// [`TurnEvent::game_id`] is the one they follow.
+    pub fn watch(&self, id: &str) -> Option<(TurnEvent, broadcast::Receiver<TurnEvent>)> {
+        // Subscribing under the lock means no move falls between the two
+        let games = self.lock();
+        let hosted = games.get(id)?;
+        Some((
+            GameExport::new(id, hosted).turn_event(),
+            self.watchers.subscribe(),
+        ))
+    }
+
+    /// Registers a webhook on a game and returns all of its webhooks.
+    pub fn add_webhook(&self, id: &str, webhook: Webhook) -> Result<Vec<Webhook>, GameYError> {
+        let mut games = self.lock();
+        let hosted = games
+            .get_mut(id)
+            .ok_or_else(|| GameYError::GameNotFound { id: id.to_string() })?;
+        if hosted.webhooks.contains(&webhook) {
+            return Ok(hosted.webhooks.clone());
+        }
+        let mut updated = hosted.clone();
+        updated.webhooks.push(webhook);
+        self.persist(id, &updated)?;
+        *hosted = updated;
+        Ok(hosted.webhooks.clone())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, HostedGame>> {
+        self.games.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn persist(&self, id: &str, hosted: &HostedGame) -> Result<(), GameYError> {
+        let Some(dir) = &self.dir else {
+            return Ok(());
+        };
+        let stored = StoredGame {
+            version: STORED_GAME_VERSION,
+            id: id.to_string(),
+            size: hosted.game.board_size(),
+            topology: hosted.game.topology(),
+            history: hosted.game.history().iter().map(MoveRecord::from).collect(),
+            webhooks: hosted.webhooks.clone(),
+            annotations: hosted.game.annotations().to_vec(),
+            takeback: hosted.takeback.map(|player| player.id()),
+            metadata: hosted.game.metadata().clone(),
+        };
+        let json = serde_json::to_string_pretty(&stored)
+            .map_err(|e| GameYError::SerdeError { error: e })?;
+        let path = dir.join(format!("{}.json", id));
+        std::fs::write(&path, json).map_err(|e| GameYError::IoError {
+            message: format!("Failed to write file: {}", path.display()),
+            error: e.to_string(),
+        })
+    }
+}
+
+impl Default for GameStore {
+    fn default() -> Self {
+        GameStore::in_memory()
+    }
+}
+
+/// Loads a stored game by replaying its history on an empty board.
+fn load_stored_game(path: &std::path::Path) -> Result<(String, HostedGame), GameYError> {
+    let content = std::fs::read_to_string(path).map_err(|e| GameYError::IoError {
+        message: format!("Failed to read file: {}", path.display()),
+        error: e.to_string(),
+    })?;
+    let mut value: serde_json::Value =
+        serde_json::from_str(&content).map_err(|e| GameYError::SerdeError { error: e })?;
+    migrate(&mut value, "stored game", STORED_GAME_VERSION, &[])?;
+    let stored: StoredGame =
+        serde_json::from_value(value).map_err(|e| GameYError::SerdeError { error: e })?;
+    let moves = stored
+        .history
+        .into_iter()
+        .map(Movement::try_from)
+        .collect::<Result<Vec<_>, _>>()?;
+    GameY::check_board_size(stored.size)?;
+    let mut game = GameY::with_topology(stored.size, stored.topology);
+    game.replay(moves, stored.annotations)?;
+    game.set_metadata(stored.metadata);
+    Ok((
+        stored.id,
+        HostedGame {
+            game,
+            webhooks: stored.webhooks,
+            takeback: stored.takeback.map(PlayerId::new),
+        },
+    ))
+}
+
+/// Path parameters for the games collection endpoint.
+#[derive(Deserialize)]
+pub struct GamesParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+}
+
+/// Path parameters for endpoints that operate on a single game.
+#[derive(Deserialize)]
+pub struct GameParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The identifier of the hosted game.
+    game_id: String,
+}
+
+/// Request body for creating a game.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct CreateGameRequest {
+    /// The board size of the new game.
+    pub size: u32,
+    /// The shape of the board, Y unless stated otherwise.
+    #[serde(default)]
+    pub topology: BoardTopology,
+    /// The event, date, players and result of the game, if known.
+    #[serde(default)]
+    pub metadata: GameMetadata,
+}
+
+/// Handler that creates a new hosted game.
+///
+/// # Route
+/// `POST /{api_version}/games`
+pub async fn create_game(
+    State(state): State<AppState>,
+    Path(params): Path<GamesParams>,
+    Json(request): Json<CreateGameRequest>,
+) -> Result<Json<GameExport>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    check_board_size(&state, request.size).context(&params.api_version, None)?;
+    state
+        .games()
+        .create_with_metadata(request.size, request.topology, request.metadata)
+        .map(Json)
+        .context(&params.api_version, None)
+}
+
+/// Handler that exports a hosted game as YEN plus its move history.
+///
+/// # Route
+/// `GET /{api_version}/games/{game_id}`
+pub async fn get_game(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+) -> Result<Json<GameExport>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    state
+        .games()
+        .get(&params.game_id)
+        .map(Json)
+        .ok_or(GameYError::GameNotFound { id: params.game_id })
+        .context(&params.api_version, None)
+}
+
+/// Handler that dumps the full state of a hosted game, including its
+/// connected groups and available cells.
+///
+/// # Route
+/// `GET /{api_version}/games/{game_id}/state`
+pub async fn get_game_state(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+) -> Result<Json<GameStateDump>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    state
+        .games()
+        .state(&params.game_id)
+        .map(Json)
+        .ok_or(GameYError::GameNotFound { id: params.game_id })
+        .context(&params.api_version, None)
+}
+
+/// Handler that draws the board of a hosted game as an SVG image, e.g. for
+/// an `<img>` element or a chat message that can only embed images.
+///
+/// # Route
+/// `GET /{api_version}/games/{game_id}/board.svg`
+pub async fn get_board_svg(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+) -> Result<Response, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let svg = state
+        .games()
+        .draw(&params.game_id, GameY::render_svg)
+        .ok_or(GameYError::GameNotFound { id: params.game_id })
+        .context(&params.api_version, None)?;
+    Ok(board_response("image/svg+xml", svg))
+}
+
+/// Handler that draws the board of a hosted game as plain text, like the
+/// terminal game without colors.
+///
+/// # Route
+/// `GET /{api_version}/games/{game_id}/board.txt`
+pub async fn get_board_text(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+) -> Result<Response, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let options = RenderOptions {
+        show_colors: false,
+        ..RenderOptions::default()
+    };
+    let text = state
+        .games()
+        .draw(&params.game_id, |game| game.render(&options))
+        .ok_or(GameYError::GameNotFound { id: params.game_id })
+        .context(&params.api_version, None)?;
+    Ok(board_response("text/plain; charset=utf-8", text))
+}
+
+/// Answers with a drawn board that changes with every move, so it must not
+/// be cached.
+fn board_response(content_type: &'static str, board: String) -> Response {
+    (
+        [
+            (header::CONTENT_TYPE, content_type),
+            (header::CACHE_CONTROL, "no-cache"),
+        ],
+        board,
+    )
+        .into_response()
+}
+
+/// Handler that plays a move in a hosted game.
+///
+/// # Route
+/// `POST /{api_version}/games/{game_id}/moves`
+pub async fn play_move(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+    ClientAddr(client): ClientAddr,
+    Json(record): Json<MoveRecord>,
+) -> Result<Json<GameExport>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let movement = Movement::try_from(record).context(&params.api_version, None)?;
+    let games = state.games();
+    let audit = state.audit_log().and_then(|log| {
+        let position_hash = games.position_hash(&params.game_id)?;
+        let sent = match &movement {
+            Movement::Placement { coords, .. } => Some(*coords),
+            Movement::Action { .. } => None,
+        };
+        let entry = AuditEntry::new(AuditedRequest::GameMove, client, position_hash)
+            .with_id(&params.game_id)
+            .with_sent(sent);
+        Some((log, entry))
+    });
+    let played = games.play(&params.game_id, movement);
+    if let Some((log, entry)) = audit {
+        let outcome = played.as_ref().map(|_| None);
+        log.record(&entry.with_outcome(outcome.map_err(GameYError::code)));
+    }
+    played.map(Json).context(&params.api_version, None)
+}
+
+/// Request body for asking for a takeback.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TakebackRequest {
+    /// The player who wants to take back their last move.
+    pub player: u32,
+}
+
+/// Request body for answering a takeback request.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TakebackAnswer {
+    /// The player answering, the opponent of the one who asked.
+    pub player: u32,
+    /// Whether the takeback is accepted.
+    pub accept: bool,
+}
+
+/// Handler that asks the opponent to take back the last move pair.
+///
+/// # Route
+/// `POST /{api_version}/games/{game_id}/takeback`
+pub async fn request_takeback(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+    Json(request): Json<TakebackRequest>,
+) -> Result<Json<GameExport>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    state
+        .games()
+        .request_takeback(&params.game_id, PlayerId::new(request.player))
+        .map(Json)
+        .context(&params.api_version, None)
+}
+
+/// Handler that accepts or declines the opponent's takeback request.
+///
+/// # Route
+/// `POST /{api_version}/games/{game_id}/takeback/answer`
+pub async fn answer_takeback(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+    Json(answer): Json<TakebackAnswer>,
+) -> Result<Json<GameExport>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    state
+        .games()
+        .answer_takeback(&params.game_id, PlayerId::new(answer.player), answer.accept)
+        .map(Json)
+        .context(&params.api_version, None)
+}
+
+/// Handler that registers a webhook notified whenever it is a player's turn.
+///
+/// Webhooks pointing to the server itself or to a private network are
+/// rejected, see [`check_webhook_url`].
+///
+/// # Route
+/// `POST /{api_version}/games/{game_id}/webhooks`
+pub async fn add_webhook(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+    Json(webhook): Json<Webhook>,
+) -> Result<Json<Vec<Webhook>>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    check_webhook_url(&webhook.url)
+        .await
+        .context(&params.api_version, None)?;
+    state
+        .games()
+        .add_webhook(&params.game_id, webhook)
+        .map(Json)
+        .context(&params.api_version, None)
+}
+
+/// Handler that follows a hosted game as server-sent `turn` events.
+///
+/// The first event is the current state of the game and another one follows
+/// every change, each a [`TurnEvent`]. The stream ends once the game has a
+/// winner.
+///
+/// # Route
+/// `GET /{api_version}/games/{game_id}/events`
+pub async fn watch_game(
+    State(state): State<AppState>,
+    Path(params): Path<GameParams>,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let (current, receiver) = state
+        .games()
+        .watch(&params.game_id)
+        .ok_or(GameYError::GameNotFound {
+            id: params.game_id.clone(),
+        })
+        .context(&params.api_version, None)?;
+    let game_id = params.game_id;
+    let stream = futures_util::stream::unfold(
+        (Some(current), receiver, false),
+        move |(mut next, mut receiver, finished)| {
+            let game_id = game_id.clone();
+            async move {
+                if finished {
+                    return None;
+                }
+                while next.is_none() {
+                    match receiver.recv().await {
+                        Ok(event) if event.game_id == game_id => next = Some(event),
+                        Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => return None,
+                    }
+                }
+                let event = next?;
+                let finished = event.winner.is_some();
+                let sse = Event::default().event("turn").json_data(&event);
+                Some((sse, (None, receiver, finished)))
+            }
+        },
+    );
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::{Coordinates, GameAction, PlayerId};
+
+    /// A notifier that records every event it is asked to deliver.
+    #[derive(Default)]
+    struct RecordingNotifier {
+        events: Mutex<Vec<(String, TurnEvent)>>,
+    }
+
+    impl TurnNotifier for RecordingNotifier {
+        fn notify(&self, webhook: &Webhook, event: &TurnEvent) {
+            self.events
+                .lock()
+                .unwrap()
+                .push((webhook.url.clone(), event.clone()));
+        }
+    }
+
+    fn placement(player: u32, x: u32, y: u32, z: u32) -> Movement {
+        Movement::Placement {
+            player: PlayerId::new(player),
+            coords: Coordinates::new(x, y, z),
+        }
+    }
+
+    #[test]
+    fn test_create_and_play() {
+        let store = GameStore::in_memory();
+        let created = store.create(3, BoardTopology::Y).unwrap();
+        assert_eq!(created.next_player, Some(0));
+
+        let export = store.play(&created.game_id, placement(0, 2, 0, 0)).unwrap();
+        assert_eq!(export.history.len(), 1);
+        assert_eq!(export.next_player, Some(1));
+        assert_eq!(export.yen.layout(), "B/../...");
+    }
+
+    #[test]
+    fn test_play_rejects_wrong_turn() {
+        let store = GameStore::in_memory();
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        let result = store.play(&id, placement(1, 2, 0, 0));
+        assert!(matches!(result, Err(GameYError::InvalidPlayerTurn { .. })));
+        assert!(store.get(&id).unwrap().history.is_empty());
+    }
+
+    #[test]
+    fn test_play_rejects_occupied_cell() {
+        let store = GameStore::in_memory();
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        store.play(&id, placement(0, 2, 0, 0)).unwrap();
+        let result = store.play(&id, placement(1, 2, 0, 0));
+        assert!(matches!(result, Err(GameYError::Occupied { .. })));
+        assert_eq!(store.get(&id).unwrap().history.len(), 1);
+    }
+
+    #[test]
+    fn test_play_rejects_finished_game() {
+        let store = GameStore::in_memory();
+        let id = store.create(1, BoardTopology::Y).unwrap().game_id;
+        store.play(&id, placement(0, 0, 0, 0)).unwrap();
+        let result = store.play(
+            &id,
+            Movement::Action {
+                player: PlayerId::new(1),
+                action: GameAction::Resign,
+            },
+        );
+        assert!(matches!(result, Err(GameYError::GameOver { .. })));
+    }
+
+    #[test]
+    fn test_unknown_game() {
+        let store = GameStore::in_memory();
+        assert!(store.get("missing").is_none());
+        assert!(matches!(
+            store.play("missing", placement(0, 0, 0, 0)),
+            Err(GameYError::GameNotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn test_webhooks_notified_on_player_turn() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let store = GameStore::in_memory().with_notifier(notifier.clone());
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        let hook = |url: &str, player| Webhook {
+            url: url.to_string(),
+            player,
+        };
+        store.add_webhook(&id, hook("http://all", None)).unwrap();
+        store.add_webhook(&id, hook("http://p0", Some(0))).unwrap();
+        store.add_webhook(&id, hook("http://p1", Some(1))).unwrap();
+
+        store.play(&id, placement(0, 2, 0, 0)).unwrap();
+
+        let events = notifier.events.lock().unwrap();
+        let urls: Vec<&str> = events.iter().map(|(url, _)| url.as_str()).collect();
+        assert_eq!(urls, vec!["http://all", "http://p1"]);
+        assert_eq!(events[0].1.next_player, Some(1));
+    }
+
+    #[tokio::test]
+    async fn test_post_json_checks_the_answer() {
+        use axum::{Router, http::StatusCode, routing::post};
+
+        let app = Router::new()
+            .route("/ok", post(|| async { StatusCode::NO_CONTENT }))
+            .route(
+                "/fail",
+                post(|| async { StatusCode::INTERNAL_SERVER_ERROR }),
+            );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let url = |path: &str| format!("http://{}{}", address, path);
+        assert_eq!(post_json(&url("/ok"), "{}".to_string()).await, Ok(()));
+        assert!(post_json(&url("/fail"), "{}".to_string()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_webhooks_must_point_to_public_hosts() {
+        for url in ["http://203.0.113.7/turn", "https://[2001:db8::1]:8443/turn"] {
+            assert_eq!(check_webhook_url(url).await, Ok(()), "{}", url);
+        }
+        for url in [
+            "http://localhost:9/turn",
+            "http://127.0.0.1/turn",
+            "http://10.1.2.3/turn",
+            "http://192.168.0.1/turn",
+            "http://169.254.169.254/latest/meta-data",
+            "http://100.64.0.1/turn",
+            "http://0.0.0.0/turn",
+            "http://[::1]/turn",
+            "http://[fd00::1]/turn",
+            "http://[::ffff:127.0.0.1]/turn",
+            "ftp://203.0.113.7/turn",
+            "not a url",
+        ] {
+            let error = check_webhook_url(url).await.unwrap_err();
+            assert_eq!(error.code, ErrorCode::InvalidWebhook, "{}", url);
+        }
+    }
+
+    /// An observer that records every event it sees.
+    #[derive(Default)]
+    struct RecordingObserver {
+        events: Mutex<Vec<TurnEvent>>,
+    }
+
+    impl GameObserver for RecordingObserver {
+        fn observe(&self, event: &TurnEvent) {
+            self.events.lock().unwrap().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_observers_see_every_game() {
+        let observer = Arc::new(RecordingObserver::default());
+        let store = GameStore::in_memory().with_observer(observer.clone());
+        let first = store.create(3, BoardTopology::Y).unwrap().game_id;
+        let second = store.create(3, BoardTopology::Y).unwrap().game_id;
+
+        store.play(&first, placement(0, 2, 0, 0)).unwrap();
+        store.play(&second, placement(0, 2, 0, 0)).unwrap();
+        store.request_takeback(&second, PlayerId::new(0)).unwrap();
+
+        let events = observer.events.lock().unwrap();
+        let games: Vec<&str> = events.iter().map(|e| e.game_id.as_str()).collect();
+        assert_eq!(games, [&first, &second, &second]);
+        assert_eq!(events[2].takeback_requested_by, Some(0));
+    }
+
+    #[test]
+    fn test_watchers_follow_their_game() {
+        let store = GameStore::in_memory();
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        let other = store.create(3, BoardTopology::Y).unwrap().game_id;
+        assert!(store.watch("missing").is_none());
+
+        let (current, mut receiver) = store.watch(&id).unwrap();
+        assert_eq!(current.game_id, id);
+        assert_eq!(current.next_player, Some(0));
+
+        store.play(&other, placement(0, 2, 0, 0)).unwrap();
+        store.play(&id, placement(0, 0, 2, 0)).unwrap();
+        let events: Vec<TurnEvent> = std::iter::from_fn(|| receiver.try_recv().ok()).collect();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].game_id, other);
+        assert_eq!(events[1].game_id, id);
+        assert_eq!(events[1].yen.layout(), "./../..B");
+    }
+
+    #[test]
+    fn test_persistent_store_reloads_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = {
+            let store = GameStore::open(dir.path()).unwrap();
+            let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+            store.play(&id, placement(0, 2, 0, 0)).unwrap();
+            store
+                .add_webhook(
+                    &id,
+                    Webhook {
+                        url: "http://example".to_string(),
+                        player: None,
+                    },
+                )
+                .unwrap();
+            id
+        };
+
+        let reopened = GameStore::open(dir.path()).unwrap();
+        let export = reopened.get(&id).unwrap();
+        assert_eq!(export.history.len(), 1);
+        assert_eq!(export.next_player, Some(1));
+        assert_eq!(export.yen.layout(), "B/../...");
+    }
+
+    #[test]
+    fn test_takeback_negotiation() {
+        let notifier = Arc::new(RecordingNotifier::default());
+        let store = GameStore::in_memory().with_notifier(notifier.clone());
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        let p0 = Webhook {
+            url: "http://p0".to_string(),
+            player: Some(0),
+        };
+        store.add_webhook(&id, p0).unwrap();
+        assert!(matches!(
+            store.request_takeback(&id, PlayerId::new(0)),
+            Err(GameYError::NothingToTakeBack { .. })
+        ));
+        store.play(&id, placement(0, 2, 0, 0)).unwrap();
+        store.play(&id, placement(1, 1, 1, 0)).unwrap();
+
+        let export = store.request_takeback(&id, PlayerId::new(1)).unwrap();
+        assert_eq!(export.takeback_requested_by, Some(1));
+        assert_eq!(notifier.events.lock().unwrap().len(), 2);
+        assert!(matches!(
+            store.answer_takeback(&id, PlayerId::new(1), true),
+            Err(GameYError::NoTakebackRequest { .. })
+        ));
+
+        let export = store.answer_takeback(&id, PlayerId::new(0), true).unwrap();
+        assert_eq!(export.history.len(), 1);
+        assert_eq!(export.next_player, Some(1));
+        assert_eq!(export.takeback_requested_by, None);
+        assert_eq!(export.annotations.len(), 1);
+        assert_eq!(export.annotations[0].move_number, 1);
+    }
+
+    #[test]
+    fn test_declined_and_lapsed_takebacks() {
+        let store = GameStore::in_memory();
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        store.play(&id, placement(0, 2, 0, 0)).unwrap();
+        store.request_takeback(&id, PlayerId::new(0)).unwrap();
+        let export = store.answer_takeback(&id, PlayerId::new(1), false).unwrap();
+        assert_eq!(export.history.len(), 1);
+        assert!(export.annotations.is_empty());
+
+        store.request_takeback(&id, PlayerId::new(0)).unwrap();
+        let export = store.play(&id, placement(1, 1, 1, 0)).unwrap();
+        assert_eq!(export.takeback_requested_by, None);
+    }
+
+    #[test]
+    fn test_persistent_store_keeps_takebacks() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = {
+            let store = GameStore::open(dir.path()).unwrap();
+            let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+            store.play(&id, placement(0, 2, 0, 0)).unwrap();
+            store.play(&id, placement(1, 1, 1, 0)).unwrap();
+            store.request_takeback(&id, PlayerId::new(1)).unwrap();
+            store.answer_takeback(&id, PlayerId::new(0), true).unwrap();
+            store.play(&id, placement(1, 1, 0, 1)).unwrap();
+            store.request_takeback(&id, PlayerId::new(1)).unwrap();
+            id
+        };
+
+        let reopened = GameStore::open(dir.path()).unwrap();
+        let export = reopened.get(&id).unwrap();
+        assert_eq!(export.history.len(), 2);
+        assert_eq!(export.annotations.len(), 1);
+        assert_eq!(export.annotations[0].move_number, 1);
+        assert_eq!(export.takeback_requested_by, Some(1));
+    }
+
+    #[test]
+    fn test_persistent_store_keeps_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let metadata = GameMetadata::default()
+            .with_event("Ladder")
+            .with_player(PlayerId::new(0), "Ada");
+        let id = {
+            let store = GameStore::open(dir.path()).unwrap();
+            let export = store
+                .create_with_metadata(3, BoardTopology::Y, metadata.clone())
+                .unwrap();
+            assert_eq!(export.metadata, metadata);
+            store.play(&export.game_id, placement(0, 2, 0, 0)).unwrap();
+            export.game_id
+        };
+
+        let reopened = GameStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.get(&id).unwrap().metadata, metadata);
+    }
+
+    #[test]
+    fn test_failed_persist_keeps_the_game() {
+        let dir = tempfile::tempdir().unwrap();
+        let games = dir.path().join("games");
+        let store = GameStore::open(&games).unwrap();
+        let id = store.create(3, BoardTopology::Y).unwrap().game_id;
+        store.play(&id, placement(0, 2, 0, 0)).unwrap();
+        std::fs::remove_dir_all(&games).unwrap();
+
+        assert!(matches!(
+            store.play(&id, placement(1, 1, 1, 0)),
+            Err(GameYError::IoError { .. })
+        ));
+        assert!(store.request_takeback(&id, PlayerId::new(0)).is_err());
+        let webhook = Webhook {
+            url: "http://example".to_string(),
+            player: None,
+        };
+        assert!(store.add_webhook(&id, webhook).is_err());
+        let export = store.get(&id).unwrap();
+        assert_eq!(export.history.len(), 1);
+        assert_eq!(export.next_player, Some(1));
+        assert_eq!(export.takeback_requested_by, None);
+    }
+
+    #[test]
+    fn test_open_skips_unreadable_games() {
+        let dir = tempfile::tempdir().unwrap();
+        let id = {
+            let store = GameStore::open(dir.path()).unwrap();
+            store.create(3, BoardTopology::Y).unwrap().game_id
+        };
+        std::fs::write(dir.path().join("corrupt.json"), "{ not json").unwrap();
+
+        let reopened = GameStore::open(dir.path()).unwrap();
+        assert_eq!(reopened.ids(), vec![id]);
+    }
+}
@@ -0,0 +1,577 @@
+//! gRPC interface of the bot server, behind the `grpc` feature.
+//!
+//! Clients that evaluate many positions in a batch pay for the HTTP/JSON
+//! round-trips of the REST API; the gRPC service answers the same requests
+//! over one HTTP/2 connection. `proto/gamey.proto` describes it for clients
+//! in other languages:
+//! - `Choose(ChooseRequest) -> MoveProto` asks a bot for a move, like the
+//!   choose endpoint (see [`choose`](crate::choose)).
+//! - `Play(stream PlayRequest) -> stream MoveProto` plays a whole game against
+//!   a bot, like a bot session (see [`sessions`](crate::sessions)) that lasts
+//!   as long as the stream.
+//!
+//! Both run through the handlers of the REST API, so timeouts, the move
+//! cache, demo limits and the audit log apply the same. Errors are returned
+//! as gRPC statuses whose message starts with the [`ErrorCode`] of the REST
+//! API.
+
+use crate::{
+    MoveResponse,
+    audit::ClientAddr,
+    choose::{self, ChooseParams, ChooseQuery},
+    error::ErrorResponse,
+    sessions::{self, NewSessionParams, SessionMoveRequest, SessionParams},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, Query, State},
+    http::{HeaderMap, StatusCode},
+};
+use futures_util::Stream;
+use gamey_core::{BoardTopology, Coordinates, ErrorCode, GameYError, YEN};
+use proto::{
+    ChooseRequest, CoordinatesProto, MoveProto, PlayRequest, YenProto,
+    bot_service_server::{BotService, BotServiceServer},
+};
+use std::net::IpAddr;
+use std::pin::Pin;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tonic::{Request, Response, Status, Streaming, transport::server::TcpIncoming};
+
+/// The messages and the generated client and server of the service.
+pub mod proto {
+    /// A position in YEN, without history or annotations.
+    #[derive(Clone, PartialEq, Eq, ::prost::Message)]
+    pub struct YenProto {
+        /// The length of one side of the board.
+        #[prost(uint32, tag = "1")]
+        pub size: u32,
+        /// The index of the player to move.
+        #[prost(uint32, tag = "2")]
+        pub turn: u32,
+        /// The symbol of each player, e.g. "BR".
+        #[prost(string, tag = "3")]
+        pub players: String,
+        /// The rows of the board separated by '/', '.' for empty cells.
+        #[prost(string, tag = "4")]
+        pub layout: String,
+        /// "y" or "hex"; empty means "y".
+        #[prost(string, tag = "5")]
+        pub topology: String,
+    }
+
+    /// The barycentric coordinates of a cell.
+    #[derive(Clone, Copy, PartialEq, Eq, ::prost::Message)]
+    pub struct CoordinatesProto {
+        #[prost(uint32, tag = "1")]
+        pub x: u32,
+        #[prost(uint32, tag = "2")]
+        pub y: u32,
+        #[prost(uint32, tag = "3")]
+        pub z: u32,
+    }
+
+    /// Request of `Choose`.
+    #[derive(Clone, PartialEq, Eq, ::prost::Message)]
+    pub struct ChooseRequest {
+        /// The bot to ask, e.g. "random_bot".
+        #[prost(string, tag = "1")]
+        pub bot_id: String,
+        /// The position to choose a move in.
+        #[prost(message, optional, tag = "2")]
+        pub position: Option<YenProto>,
+    }
+
+    /// A move chosen by a bot.
+    #[derive(Clone, PartialEq, Eq, ::prost::Message)]
+    pub struct MoveProto {
+        /// The bot that chose the move.
+        #[prost(string, tag = "1")]
+        pub bot_id: String,
+        /// The cell the bot plays.
+        #[prost(message, optional, tag = "2")]
+        pub coords: Option<CoordinatesProto>,
+    }
+
+    /// The bot and position a `Play` stream starts with.
+    #[derive(Clone, PartialEq, Eq, ::prost::Message)]
+    pub struct StartGame {
+        /// The bot to play against.
+        #[prost(string, tag = "1")]
+        pub bot_id: String,
+        /// The position the game starts from.
+        #[prost(message, optional, tag = "2")]
+        pub position: Option<YenProto>,
+    }
+
+    /// Request of `Play`: the first one starts the game, and any may carry
+    /// the opponent's move.
+    #[derive(Clone, PartialEq, Eq, ::prost::Message)]
+    pub struct PlayRequest {
+        /// Only in the first request.
+        #[prost(message, optional, tag = "1")]
+        pub start: Option<StartGame>,
+        /// The opponent's last move, if any.
+        #[prost(message, optional, tag = "2")]
+        pub opponent_move: Option<CoordinatesProto>,
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/gamey.v1.BotService.rs"));
+}
+
+impl From<&YEN> for YenProto {
+    fn from(yen: &YEN) -> Self {
+        YenProto {
+            size: yen.size(),
+            turn: yen.turn(),
+            players: yen.players().iter().collect(),
+            layout: yen.layout().to_string(),
+            topology: yen.topology().to_string(),
+        }
+    }
+}
+
+impl TryFrom<YenProto> for YEN {
+    type Error = Status;
+
+    fn try_from(proto: YenProto) -> Result<Self, Status> {
+        let topology = match proto.topology.as_str() {
+            "" => BoardTopology::Y,
+            name => name.parse().map_err(|e: String| {
+                let err = ErrorResponse::error(ErrorCode::InvalidYen, &e, None, None);
+                Status::from(err)
+            })?,
+        };
+        let yen = YEN::new(
+            proto.size,
+            proto.turn,
+            proto.players.chars().collect(),
+            proto.layout,
+        );
+        Ok(yen.with_topology(topology))
+    }
+}
+
+impl From<Coordinates> for CoordinatesProto {
+    fn from(coords: Coordinates) -> Self {
+        CoordinatesProto {
+            x: coords.x(),
+            y: coords.y(),
+            z: coords.z(),
+        }
+    }
+}
+
+impl From<CoordinatesProto> for Coordinates {
+    fn from(coords: CoordinatesProto) -> Self {
+        Coordinates::new(coords.x, coords.y, coords.z)
+    }
+}
+
+impl From<MoveResponse> for MoveProto {
+    fn from(response: MoveResponse) -> Self {
+        MoveProto {
+            bot_id: response.bot_id,
+            coords: Some(response.coords.into()),
+        }
+    }
+}
+
+/// Converts an error of the REST API into the gRPC status of the same kind,
+/// e.g. `NOT_FOUND` for an unknown bot or `DEADLINE_EXCEEDED` for a bot that
+/// ran out of time.
+impl From<ErrorResponse> for Status {
+    fn from(err: ErrorResponse) -> Self {
+        let message = format!("{}: {}", err.code.as_str(), err.message);
+        match (err.code, err.status()) {
+            (ErrorCode::Timeout, _) => Status::deadline_exceeded(message),
+            (_, StatusCode::NOT_FOUND) => Status::not_found(message),
+            (_, StatusCode::CONFLICT) => Status::failed_precondition(message),
+            (_, StatusCode::UNAUTHORIZED) => Status::unauthenticated(message),
+            (_, StatusCode::TOO_MANY_REQUESTS) => Status::resource_exhausted(message),
+            (_, StatusCode::SERVICE_UNAVAILABLE) => Status::unavailable(message),
+            (_, StatusCode::INTERNAL_SERVER_ERROR) => Status::internal(message),
+            _ => Status::invalid_argument(message),
+        }
+    }
+}
+
+/// Fails with `INVALID_ARGUMENT` for a request that lacks a position.
+fn required_position(position: Option<YenProto>) -> Result<YEN, Status> {
+    let err = ErrorResponse::error(ErrorCode::InvalidYen, "A position is required", None, None);
+    position.ok_or_else(|| Status::from(err))?.try_into()
+}
+
+/// The gRPC service, answering with the bots of the server.
+pub struct GrpcBots {
+    state: AppState,
+}
+
+impl GrpcBots {
+    /// Creates the service for the given server state.
+    pub fn new(state: AppState) -> Self {
+        GrpcBots { state }
+    }
+}
+
+/// The stream of replies of `Play`.
+type MoveStream = Pin<Box<dyn Stream<Item = Result<MoveProto, Status>> + Send>>;
+
+#[tonic::async_trait]
+impl BotService for GrpcBots {
+    async fn choose(&self, request: Request<ChooseRequest>) -> Result<Response<MoveProto>, Status> {
+        let client = request.remote_addr().map(|addr| addr.ip());
+        let request = request.into_inner();
+        let yen = required_position(request.position)?;
+        let (_, Json(response)) = choose::choose(
+            State(self.state.clone()),
+            Path(ChooseParams::new(&request.bot_id)),
+            Query(ChooseQuery::default()),
+            ClientAddr(client),
+            HeaderMap::new(),
+            Json(yen),
+        )
+        .await?;
+        Ok(Response::new(response.into()))
+    }
+
+    type PlayStream = MoveStream;
+
+    async fn play(
+        &self,
+        request: Request<Streaming<PlayRequest>>,
+    ) -> Result<Response<MoveStream>, Status> {
+        let client = request.remote_addr().map(|addr| addr.ip());
+        let requests = request.into_inner();
+        let (replies, receiver) = mpsc::channel(1);
+        tokio::spawn(play_game(self.state.clone(), client, requests, replies));
+        let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+            let reply = receiver.recv().await?;
+            Some((reply, receiver))
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+}
+
+/// Plays the game of a `Play` stream in a bot session, closed when either
+/// side ends the stream or a request fails.
+async fn play_game(
+    state: AppState,
+    client: Option<IpAddr>,
+    mut requests: Streaming<PlayRequest>,
+    replies: mpsc::Sender<Result<MoveProto, Status>>,
+) {
+    let mut session: Option<SessionParams> = None;
+    loop {
+        let reply = match requests.message().await {
+            Ok(Some(request)) => play_turn(&state, client, &mut session, request).await,
+            Ok(None) => break,
+            Err(status) => Err(status),
+        };
+        let failed = reply.is_err();
+        if replies.send(reply).await.is_err() || failed {
+            break;
+        }
+    }
+    if let Some(params) = session {
+        let _ = sessions::close_session(State(state), Path(params)).await;
+    }
+}
+
+/// Answers one request of a `Play` stream, starting the session first if it
+/// is the first request.
+async fn play_turn(
+    state: &AppState,
+    client: Option<IpAddr>,
+    session: &mut Option<SessionParams>,
+    request: PlayRequest,
+) -> Result<MoveProto, Status> {
+    let params = match (session.as_ref(), request.start) {
+        (None, Some(start)) => {
+            let yen = required_position(start.position)?;
+            let Json(created) = sessions::create_session(
+                State(state.clone()),
+                Path(NewSessionParams::new(&start.bot_id)),
+                Json(yen),
+            )
+            .await?;
+            session.insert(SessionParams::new(&created.bot_id, &created.session_id))
+        }
+        (None, None) => {
+            return Err(Status::invalid_argument(
+                "The first request must start the game",
+            ));
+        }
+        (Some(_), Some(_)) => {
+            return Err(Status::invalid_argument("The game has already started"));
+        }
+        (Some(params), None) => params,
+    };
+    let Json(response) = sessions::session_move(
+        State(state.clone()),
+        Path(params.clone()),
+        ClientAddr(client),
+        Json(SessionMoveRequest {
+            coords: request.opponent_move.map(Coordinates::from),
+        }),
+    )
+    .await?;
+    Ok(response.into())
+}
+
+/// Serves the gRPC service on `listener` until the server fails.
+///
+/// # Errors
+/// Returns `GameYError::ServerError` if the server fails while running.
+pub async fn serve(state: AppState, listener: TcpListener) -> Result<(), GameYError> {
+    tonic::transport::Server::builder()
+        .add_service(BotServiceServer::new(GrpcBots::new(state)))
+        .serve_with_incoming(TcpIncoming::from(listener))
+        .await
+        .map_err(|e| GameYError::ServerError {
+            message: format!("gRPC server error: {}", e),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_core::GameY;
+
+    #[test]
+    fn test_positions_round_trip() {
+        let mut game = GameY::new(4);
+        game.add_move(gamey_core::Movement::Placement {
+            player: gamey_core::PlayerId::new(0),
+            coords: Coordinates::new(3, 0, 0),
+        })
+        .unwrap();
+        let yen = YEN::from(&game);
+        let proto = YenProto::from(&yen);
+        assert_eq!(proto.topology, "y");
+        let back = YEN::try_from(proto).unwrap();
+        assert_eq!(GameY::try_from(back).unwrap().position(), game.position());
+    }
+
+    #[test]
+    fn test_errors_map_to_grpc_codes() {
+        let status = |code| Status::from(ErrorResponse::error(code, "message", None, None));
+        assert_eq!(status(ErrorCode::BotNotFound).code(), tonic::Code::NotFound);
+        assert_eq!(
+            status(ErrorCode::Timeout).code(),
+            tonic::Code::DeadlineExceeded
+        );
+        assert_eq!(
+            status(ErrorCode::InvalidYen).code(),
+            tonic::Code::InvalidArgument
+        );
+        assert_eq!(
+            status(ErrorCode::NoMoves).code(),
+            tonic::Code::FailedPrecondition
+        );
+        assert_eq!(
+            status(ErrorCode::BotNotFound).message(),
+            "BOT_NOT_FOUND: message"
+        );
+        let yen = YenProto {
+            topology: "square".to_string(),
+            ..YenProto::default()
+        };
+        assert!(YEN::try_from(yen).is_err());
+    }
+
+    /// Returns the fields of each message of `proto/gamey.proto`, as
+    /// (message, field, type, number).
+    fn proto_fields() -> Vec<(String, String, String, u32)> {
+        let mut fields = Vec::new();
+        let mut message = None;
+        for line in include_str!("../proto/gamey.proto").lines() {
+            let line = line.trim();
+            if let Some(name) = line.strip_prefix("message ") {
+                message = name.strip_suffix(" {").map(str::to_string);
+            } else if line == "}" {
+                message = None;
+            } else if let (Some(message), Some(field)) = (&message, line.strip_suffix(';')) {
+                let words: Vec<&str> = field.split_whitespace().collect();
+                let [kind, name, "=", number] = words[..] else {
+                    panic!("unexpected field: {}", line);
+                };
+                let number = number.parse().unwrap();
+                fields.push((message.clone(), name.to_string(), kind.to_string(), number));
+            }
+        }
+        fields
+    }
+
+    /// Encodes a default message with the fields `set` sets.
+    fn encode_with<M: prost::Message + Default>(set: impl FnOnce(&mut M)) -> Vec<u8> {
+        let mut message = M::default();
+        set(&mut message);
+        message.encode_to_vec()
+    }
+
+    #[test]
+    fn test_messages_match_the_proto_file() {
+        use proto::StartGame;
+
+        let fields = proto_fields();
+        let mut checked = 0;
+        // A message with only one field set encodes that field's key first:
+        // its number and wire type
+        let mut check = |field: &str, bytes: Vec<u8>| {
+            let (_, _, kind, number) = fields
+                .iter()
+                .find(|(m, f, _, _)| format!("{}.{}", m, f) == field)
+                .unwrap_or_else(|| panic!("{} is not in the proto file", field));
+            // Varints are wire type 0, strings and messages are
+            // length-delimited, wire type 2
+            let wire_type = if kind == "uint32" { 0 } else { 2 };
+            assert_eq!(u32::from(bytes[0]), number << 3 | wire_type, "{}", field);
+            checked += 1;
+        };
+        let text = || "a".to_string();
+        check("YenProto.size", encode_with(|m: &mut YenProto| m.size = 1));
+        check("YenProto.turn", encode_with(|m: &mut YenProto| m.turn = 1));
+        check(
+            "YenProto.players",
+            encode_with(|m: &mut YenProto| m.players = text()),
+        );
+        check(
+            "YenProto.layout",
+            encode_with(|m: &mut YenProto| m.layout = text()),
+        );
+        check(
+            "YenProto.topology",
+            encode_with(|m: &mut YenProto| m.topology = text()),
+        );
+        check(
+            "CoordinatesProto.x",
+            encode_with(|m: &mut CoordinatesProto| m.x = 1),
+        );
+        check(
+            "CoordinatesProto.y",
+            encode_with(|m: &mut CoordinatesProto| m.y = 1),
+        );
+        check(
+            "CoordinatesProto.z",
+            encode_with(|m: &mut CoordinatesProto| m.z = 1),
+        );
+        check(
+            "ChooseRequest.bot_id",
+            encode_with(|m: &mut ChooseRequest| m.bot_id = text()),
+        );
+        check(
+            "ChooseRequest.position",
+            encode_with(|m: &mut ChooseRequest| m.position = Some(YenProto::default())),
+        );
+        check(
+            "MoveProto.bot_id",
+            encode_with(|m: &mut MoveProto| m.bot_id = text()),
+        );
+        check(
+            "MoveProto.coords",
+            encode_with(|m: &mut MoveProto| m.coords = Some(CoordinatesProto::default())),
+        );
+        check(
+            "StartGame.bot_id",
+            encode_with(|m: &mut StartGame| m.bot_id = text()),
+        );
+        check(
+            "StartGame.position",
+            encode_with(|m: &mut StartGame| m.position = Some(YenProto::default())),
+        );
+        check(
+            "PlayRequest.start",
+            encode_with(|m: &mut PlayRequest| m.start = Some(StartGame::default())),
+        );
+        check(
+            "PlayRequest.opponent_move",
+            encode_with(|m: &mut PlayRequest| m.opponent_move = Some(CoordinatesProto::default())),
+        );
+        assert_eq!(checked, fields.len(), "fields of the proto file");
+    }
+
+    #[tokio::test]
+    async fn test_choose_and_play_over_grpc() {
+        use proto::{StartGame, bot_service_client::BotServiceClient};
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(serve(crate::create_default_state(), listener));
+        let mut client = BotServiceClient::connect(format!("http://{}", addr))
+            .await
+            .unwrap();
+
+        let position = YenProto::from(&YEN::from(&GameY::new(3)));
+        let reply = client
+            .choose(ChooseRequest {
+                bot_id: "random_bot".to_string(),
+                position: Some(position.clone()),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(reply.bot_id, "random_bot");
+        assert!(reply.coords.is_some());
+        let err = client
+            .choose(ChooseRequest {
+                bot_id: "no_such_bot".to_string(),
+                position: Some(position.clone()),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        // The bot moves first, then answers the opponent's move
+        let start = PlayRequest {
+            start: Some(StartGame {
+                bot_id: "random_bot".to_string(),
+                position: Some(position),
+            }),
+            opponent_move: None,
+        };
+        let (requests, receiver) = mpsc::channel(2);
+        requests.send(start).await.unwrap();
+        let stream = futures_util::stream::unfold(receiver, |mut receiver| async move {
+            let request = receiver.recv().await?;
+            Some((request, receiver))
+        });
+        let mut replies = client.play(stream).await.unwrap().into_inner();
+        let first: Coordinates = replies
+            .message()
+            .await
+            .unwrap()
+            .unwrap()
+            .coords
+            .unwrap()
+            .into();
+        let mut game = GameY::new(3);
+        game.add_move(gamey_core::Movement::Placement {
+            player: gamey_core::PlayerId::new(0),
+            coords: first,
+        })
+        .unwrap();
+        let free = game.available_cells()[0];
+        let opponent_move = Coordinates::from_index(free, 3);
+        requests
+            .send(PlayRequest {
+                start: None,
+                opponent_move: Some(opponent_move.into()),
+            })
+            .await
+            .unwrap();
+        let second: Coordinates = replies
+            .message()
+            .await
+            .unwrap()
+            .unwrap()
+            .coords
+            .unwrap()
+            .into();
+        assert!(second != first && second != opponent_move);
+        drop(requests);
+        assert!(replies.message().await.unwrap().is_none());
+    }
+}
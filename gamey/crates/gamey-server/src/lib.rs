@@ -0,0 +1,334 @@
+//! HTTP server for Y game bots.
+//!
+//! This module provides an Axum-based REST API for querying Y game bots.
+//! The server exposes endpoints for checking bot status and requesting moves.
+//!
+//! # Endpoints
+//! - `GET /status` - Health check endpoint
+//! - `POST /{api_version}/ybot/choose/{bot_id}` - Request a move from a bot
+//! - `POST /{api_version}/ybot/choose/{bot_id}/stream` - Follow the bot's
+//!   search as server-sent events
+//! - `/{api_version}/ybot/session/...` - Stateful bot sessions (see [`sessions`])
+//! - `/{api_version}/games/...` - Correspondence games hosted by the server
+//!   (see [`games`])
+//! - `/{api_version}/position/...` - Analysis of a single position (see
+//!   [`position`])
+//! - `GET /{api_version}/ybot/cache` - Statistics of the cached answers of
+//!   deterministic bots (see [`cache`])
+//! - `/{api_version}/admin/...` - Inspect and manage the server, with its
+//!   admin key (see [`admin`])
+//!
+//! With the `grpc` feature, the server can also answer the choose and
+//! session requests over gRPC on a port of its own (see `grpc`). With the
+//! `webui` feature, it serves a page at `/` to play against its bots from a
+//! browser (see `webui`).
+//!
+//! # Configuration
+//! The address, timeouts and other settings of the server can be read from a
+//! `server.toml` file (see [`server_config`]). The memory limits of the
+//! search bots can be set per bot in a `bots.toml` file (see
+//! [`bots_config`]). A server for a classroom can run in demo mode,
+//! which limits board sizes, requests per client and searching bots (see
+//! [`demo`]). Every move served can be written to a rotating audit log (see
+//! [`audit`]), and the turns and results of hosted games posted to chat
+//! channels (see [`chat`]).
+//!
+//! # Errors
+//! Failed requests answer with an [`ErrorResponse`] and `200 OK`; its
+//! machine-readable code tells the errors apart, e.g. `BOT_NOT_FOUND` for an
+//! unknown bot.
+//!
+//! # Example
+//! ```no_run
+//! use gamey_server::{run_bot_server, server_config::ServerConfig};
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     if let Err(e) = run_bot_server(ServerConfig::default()).await {
+//!         eprintln!("Server error: {}", e);
+//!     }
+//! }
+//! ```
+
+pub mod admin;
+pub mod audit;
+pub mod bots_config;
+pub mod cache;
+pub mod chat;
+pub mod choose;
+pub mod demo;
+pub mod error;
+pub mod games;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod position;
+pub mod server_config;
+pub mod sessions;
+pub mod state;
+pub mod version;
+#[cfg(feature = "webui")]
+pub mod webui;
+use axum::response::IntoResponse;
+use std::net::SocketAddr;
+use std::sync::Arc;
+pub use choose::MoveResponse;
+pub use error::{ErrorContext, ErrorResponse};
+pub use version::*;
+
+use crate::{
+    audit::AuditLog,
+    bots_config::{BotsConfig, RemoteBotFactory},
+    chat::ChatNotifier,
+    games::GameStore,
+    server_config::ServerConfig,
+    state::AppState,
+};
+use gamey_core::GameYError;
+
+/// Creates the Axum router with the given state.
+///
+/// This is useful for testing the API without binding to a network port.
+/// In demo mode, the requests of every client are limited as set in the
+/// state.
+pub fn create_router(state: AppState) -> axum::Router {
+    let router = axum::Router::new()
+        .route("/status", axum::routing::get(status))
+        .route(
+            "/{api_version}/ybot/choose/{bot_id}",
+            axum::routing::post(choose::choose),
+        )
+        .route(
+            "/{api_version}/ybot/choose/{bot_id}/stream",
+            axum::routing::post(choose::choose_stream),
+        )
+        .route(
+            "/{api_version}/ybot/cache",
+            axum::routing::get(cache::cache_stats),
+        )
+        .route(
+            "/{api_version}/ybot/session/{bot_id}",
+            axum::routing::post(sessions::create_session),
+        )
+        .route(
+            "/{api_version}/ybot/session/{bot_id}/{session_id}/move",
+            axum::routing::post(sessions::session_move),
+        )
+        .route(
+            "/{api_version}/ybot/session/{bot_id}/{session_id}",
+            axum::routing::delete(sessions::close_session),
+        )
+        .route("/{api_version}/games", axum::routing::post(games::create_game))
+        .route(
+            "/{api_version}/games/{game_id}",
+            axum::routing::get(games::get_game),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/state",
+            axum::routing::get(games::get_game_state),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/board.svg",
+            axum::routing::get(games::get_board_svg),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/board.txt",
+            axum::routing::get(games::get_board_text),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/moves",
+            axum::routing::post(games::play_move),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/takeback",
+            axum::routing::post(games::request_takeback),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/takeback/answer",
+            axum::routing::post(games::answer_takeback),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/webhooks",
+            axum::routing::post(games::add_webhook),
+        )
+        .route(
+            "/{api_version}/games/{game_id}/events",
+            axum::routing::get(games::watch_game),
+        )
+        .route(
+            "/{api_version}/position/hash",
+            axum::routing::post(position::hash_position),
+        )
+        .route(
+            "/{api_version}/position/validate",
+            axum::routing::post(position::validate_position),
+        )
+        .route(
+            "/{api_version}/position/move",
+            axum::routing::post(position::apply_move),
+        );
+    let admin = axum::Router::new()
+        .route(
+            "/{api_version}/admin/reload",
+            axum::routing::post(admin::reload_bots),
+        )
+        .route(
+            "/{api_version}/admin/games",
+            axum::routing::get(admin::list_games),
+        )
+        .route(
+            "/{api_version}/admin/games/{game_id}",
+            axum::routing::delete(admin::delete_game),
+        )
+        .route(
+            "/{api_version}/admin/sessions/{session_id}",
+            axum::routing::delete(admin::delete_session),
+        )
+        .route(
+            "/{api_version}/admin/bots",
+            axum::routing::get(admin::bot_stats),
+        )
+        .route(
+            "/{api_version}/admin/config",
+            axum::routing::get(admin::show_config),
+        )
+        .route_layer(axum::middleware::from_fn_with_state(
+            state.clone(),
+            admin::require_admin_key,
+        ));
+    let router = router.merge(admin);
+    #[cfg(feature = "webui")]
+    let router = router
+        .route("/", axum::routing::get(webui::index))
+        .route("/webui/bots", axum::routing::get(webui::bots))
+        .route("/webui/{file}", axum::routing::get(webui::webui_file));
+    let router = match state.demo() {
+        Some(demo) => router.layer(axum::middleware::from_fn_with_state(
+            demo,
+            demo::limit_clients,
+        )),
+        None => router,
+    };
+    router.with_state(state)
+}
+
+/// Creates the default application state with the standard bot registry.
+///
+/// The default state includes the `RandomBot`, which selects moves randomly,
+/// the `MctsBot`, which searches with Monte Carlo Tree Search, and the
+/// `SolverBot`, which plays perfectly once few cells remain empty. The
+/// search bots use the default memory limits of [`bots_config`].
+pub fn create_default_state() -> AppState {
+    create_state_with_config(&BotsConfig::default())
+}
+
+/// Creates the application state with the standard bots, limited as set in
+/// `config`.
+pub fn create_state_with_config(config: &BotsConfig) -> AppState {
+    AppState::new(config.registry())
+}
+
+/// Starts the bot server with the given settings.
+///
+/// This function blocks until the server is shut down.
+///
+/// # Errors
+/// Returns `GameYError::BindFailed` if the address cannot be bound (e.g.,
+/// port already in use, permission denied), and `GameYError::ServerError` if
+/// the server encounters an error while running, or a gRPC port is set but
+/// the server was built without the `grpc` feature.
+///
+/// Returns `GameYError::IoError` if the games directory or the bots
+/// configuration cannot be loaded, and `GameYError::InvalidConfig` if the
+/// bots configuration is not valid.
+pub async fn run_bot_server(config: ServerConfig) -> Result<(), GameYError> {
+    run_bot_server_with_remote_bots(config, None).await
+}
+
+/// Starts the bot server like [`run_bot_server`], building the remote bots
+/// of its `bots.toml` with `remote_bots` (see [`bots_config`]).
+///
+/// # Errors
+/// The same as [`run_bot_server`].
+pub async fn run_bot_server_with_remote_bots(
+    config: ServerConfig,
+    remote_bots: Option<RemoteBotFactory>,
+) -> Result<(), GameYError> {
+    #[cfg(not(feature = "grpc"))]
+    if config.grpc_port.is_some() {
+        return Err(GameYError::ServerError {
+            message: "gRPC is not available, build gamey with the `grpc` feature".to_string(),
+        });
+    }
+    let bots = match &config.bots_config {
+        Some(file) => BotsConfig::load(file)?,
+        None => BotsConfig::default(),
+    };
+    let mut state = AppState::new(bots.registry_with_remote(remote_bots.as_ref()))
+        .with_bot_timeout(config.bot_timeout())
+        .with_move_cache(config.move_cache_entries);
+    if let Some(connect) = remote_bots {
+        state = state.with_remote_bots(connect);
+    }
+    if let Some(file) = config.bots_config.clone() {
+        state = state.with_bots_config(file);
+    }
+    let mut games = match config.games_dir.clone() {
+        Some(dir) => GameStore::open(dir)?,
+        None => GameStore::in_memory(),
+    };
+    if let Some(chat) = config.chat.clone() {
+        games = games.with_observer(Arc::new(ChatNotifier::new(chat)));
+    }
+    state = state.with_games(games);
+    if let Some(limits) = config.demo {
+        state = state.with_demo(limits);
+    }
+    if let Some(key) = &config.admin_key {
+        state = state.with_admin_key(key);
+    }
+    if let Some(audit) = config.audit.clone() {
+        state = state.with_audit_log(AuditLog::open(audit)?);
+    }
+    let app = create_router(state.clone());
+
+    let addr = config.address();
+    let listener = bind(&addr).await?;
+
+    println!("Server mode: Listening on http://{}", addr);
+    #[cfg(feature = "webui")]
+    println!("Server mode: Play in a browser at http://{}/", addr);
+    // The address of each connection tells the clients of demo mode apart
+    let app = app.into_make_service_with_connect_info::<SocketAddr>();
+    let rest = async {
+        axum::serve(listener, app)
+            .await
+            .map_err(|e| GameYError::ServerError {
+                message: format!("Server error: {}", e),
+            })
+    };
+    #[cfg(feature = "grpc")]
+    if let Some(port) = config.grpc_port {
+        let addr = format!("{}:{}", config.host, port);
+        let listener = bind(&addr).await?;
+        println!("Server mode: gRPC listening on {}", addr);
+        tokio::try_join!(rest, grpc::serve(state, listener))?;
+        return Ok(());
+    }
+    rest.await
+}
+
+async fn bind(addr: &str) -> Result<tokio::net::TcpListener, GameYError> {
+    tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| GameYError::BindFailed {
+            address: addr.to_string(),
+            message: e.to_string(),
+        })
+}
+
+/// Health check endpoint handler.
+///
+/// Returns "OK" to indicate the server is running.
+pub async fn status() -> impl IntoResponse {
+    "OK"
+}
@@ -0,0 +1,172 @@
+//! Stateless endpoints that analyze a single position sent as YEN.
+//!
+//! # Endpoints
+//! - `POST /{api_version}/position/hash` - Hash a position for deduplication
+//! - `POST /{api_version}/position/validate` - Strictly validate positions
+//! - `POST /{api_version}/position/move` - Play a move in a position
+
+use crate::{
+    check_api_version,
+    error::{ErrorContext, ErrorResponse},
+};
+use axum::{Json, extract::Path};
+use gamey_core::{
+    GameY, GameYError, Movement, StatusDump, ValidationIssue, YEN, validate_yen_json,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Path parameters of the position endpoints.
+#[derive(Deserialize)]
+pub struct PositionParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+}
+
+/// Response of the hash endpoint.
+///
+/// Hashes are 64-bit values written as 16 lowercase hexadecimal digits, so
+/// clients without 64-bit integers can store them exactly.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct HashResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The Zobrist hash of the position, see [`GameY::zobrist_hash`].
+    pub hash: String,
+    /// The hash shared by all symmetric versions of the position, see
+    /// [`GameY::canonical_hash`].
+    pub canonical_hash: String,
+}
+
+/// Response of the validate endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidateResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// One result per submitted document, in request order.
+    pub results: Vec<ValidationResult>,
+}
+
+/// The outcome of validating one YEN document.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ValidationResult {
+    /// True if no problem was found.
+    pub valid: bool,
+    /// Every problem found, each with a machine-readable code.
+    pub issues: Vec<ValidationIssue>,
+}
+
+/// Request body of the move endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApplyMoveRequest {
+    /// The position to play the move in.
+    pub yen: YEN,
+    /// The move to play, e.g.
+    /// `{"type": "placement", "player": 0, "coords": {"x": 2, "y": 0, "z": 0}}`.
+    pub movement: Movement,
+}
+
+/// Response of the move endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApplyMoveResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The position after the move.
+    pub yen: YEN,
+    /// Whether the game goes on, and who moves or won.
+    pub status: StatusDump,
+}
+
+/// Handler that hashes a position, so that external databases deduplicate
+/// transposed and symmetric positions the same way the engine does.
+///
+/// # Route
+/// `POST /{api_version}/position/hash`
+///
+/// # Request Body
+/// A JSON object in YEN format.
+pub async fn hash_position(
+    Path(params): Path<PositionParams>,
+    Json(yen): Json<YEN>,
+) -> Result<Json<HashResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let game = GameY::try_from(yen)
+        .map_err(ErrorResponse::invalid_yen)
+        .context(&params.api_version, None)?;
+    Ok(Json(HashResponse {
+        api_version: params.api_version,
+        hash: format!("{:016x}", game.zobrist_hash()),
+        canonical_hash: format!("{:016x}", game.canonical_hash()),
+    }))
+}
+
+/// Handler that strictly validates positions and reports every problem
+/// found, see [`YEN::validate`].
+///
+/// Invalid positions are not an error of the request: each one gets a
+/// result listing its issues.
+///
+/// # Route
+/// `POST /{api_version}/position/validate`
+///
+/// # Request Body
+/// A JSON object in YEN format, or an array of them to validate a batch.
+pub async fn validate_position(
+    Path(params): Path<PositionParams>,
+    Json(body): Json<Value>,
+) -> Result<Json<ValidateResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let documents = match body {
+        Value::Array(documents) => documents,
+        document => vec![document],
+    };
+    let results = documents
+        .into_iter()
+        .map(|document| {
+            let issues = validate_yen_json(&document.to_string());
+            ValidationResult {
+                valid: issues.is_empty(),
+                issues,
+            }
+        })
+        .collect();
+    Ok(Json(ValidateResponse {
+        api_version: params.api_version,
+        results,
+    }))
+}
+
+/// Handler that plays a move in a position and returns the position after
+/// it, so that clients can leave every rule to the server without opening a
+/// session.
+///
+/// The move is checked like a move of a hosted game: it is rejected if the
+/// game is over, if it is not the mover's turn or if the cell cannot be
+/// played, each with the error code of the engine error.
+///
+/// # Route
+/// `POST /{api_version}/position/move`
+///
+/// # Request Body
+/// An [`ApplyMoveRequest`]: the position in YEN format and the move.
+pub async fn apply_move(
+    Path(params): Path<PositionParams>,
+    Json(request): Json<ApplyMoveRequest>,
+) -> Result<Json<ApplyMoveResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let mut game = GameY::try_from(request.yen)
+        .map_err(ErrorResponse::invalid_yen)
+        .context(&params.api_version, None)?;
+    let movement = request.movement;
+    if game.check_game_over() {
+        return Err(GameYError::GameOver { movement }).context(&params.api_version, None);
+    }
+    game.check_player_turn(&movement)
+        .and_then(|()| game.add_move(movement))
+        .context(&params.api_version, None)?;
+    Ok(Json(ApplyMoveResponse {
+        api_version: params.api_version,
+        yen: YEN::from(&game),
+        status: StatusDump::from(game.status()),
+    }))
+}
@@ -0,0 +1,183 @@
+//! Settings of the bot server, read from `server.toml`.
+//!
+//! Every setting is optional and falls back to its default; the `gamey`
+//! binary lets command-line options and `GAMEY_*` environment variables
+//! override the file. Paths are relative to the working directory of the
+//! server.
+//!
+//! ```toml
+//! host = "127.0.0.1"
+//! port = 8080
+//! # Also answer over gRPC, with the `grpc` feature
+//! grpc_port = 50051
+//! games_dir = "/var/lib/gamey/games"
+//! bots_config = "/etc/gamey/bots.toml"
+//! bot_timeout_secs = 10
+//! move_cache_entries = 50000
+//! admin_key = "change-me"
+//!
+//! # Run in demo mode, see `demo`; missing limits keep their defaults
+//! [demo]
+//! max_board_size = 9
+//!
+//! # Log every move served, see `audit`
+//! [audit]
+//! path = "/var/log/gamey/audit.jsonl"
+//! max_bytes = 52428800
+//! keep = 10
+//!
+//! # Post turns and results of hosted games to chat channels, see `chat`
+//! [chat]
+//! public_url = "https://gamey.example.org"
+//! [[chat.webhooks]]
+//! url = "https://hooks.slack.com/services/T000/B000/XXXX"
+//! service = "slack"
+//! results_only = true
+//! ```
+
+use crate::{
+    audit::AuditConfig, cache::DEFAULT_MOVE_CACHE_ENTRIES, chat::ChatConfig, demo::DemoLimits,
+    state::DEFAULT_BOT_TIMEOUT,
+};
+use gamey_core::GameYError;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The address the server listens on by default, every interface.
+pub const DEFAULT_HOST: &str = "0.0.0.0";
+
+/// The port the server listens on by default.
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// The settings of the bot server.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct ServerConfig {
+    /// The address to listen on.
+    pub host: String,
+    /// The TCP port to listen on.
+    pub port: u16,
+    /// The TCP port the gRPC service listens on, on the same address; if
+    /// `None`, the server only answers over HTTP.
+    pub grpc_port: Option<u16>,
+    /// Directory where hosted games are persisted; if `None`, hosted games
+    /// only live in memory.
+    pub games_dir: Option<PathBuf>,
+    /// A `bots.toml` file with the limits of the bots (see
+    /// [`bots_config`](crate::bots_config)); if `None`, the bots use the
+    /// default limits.
+    pub bots_config: Option<PathBuf>,
+    /// How long a bot may search before its request fails, in seconds.
+    pub bot_timeout_secs: u64,
+    /// The number of answers of deterministic bots cached; 0 disables the
+    /// cache.
+    pub move_cache_entries: usize,
+    /// The limits of demo mode (see [`demo`](crate::demo)); if `None`, the
+    /// server is not limited.
+    pub demo: Option<DemoLimits>,
+    /// The key required by the admin endpoints (see [`admin`](crate::admin));
+    /// if `None`, they are disabled.
+    pub admin_key: Option<String>,
+    /// Where the moves served are logged (see [`audit`](crate::audit)); if
+    /// `None`, they are not.
+    pub audit: Option<AuditConfig>,
+    /// The chat webhooks told of the hosted games (see
+    /// [`chat`](crate::chat)); if `None`, no chat is.
+    pub chat: Option<ChatConfig>,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            host: DEFAULT_HOST.to_string(),
+            port: DEFAULT_PORT,
+            grpc_port: None,
+            games_dir: None,
+            bots_config: None,
+            bot_timeout_secs: DEFAULT_BOT_TIMEOUT.as_secs(),
+            move_cache_entries: DEFAULT_MOVE_CACHE_ENTRIES,
+            demo: None,
+            admin_key: None,
+            audit: None,
+            chat: None,
+        }
+    }
+}
+
+impl ServerConfig {
+    /// Parses a configuration in the format of `server.toml`.
+    pub fn parse(text: &str) -> Result<Self, toml::de::Error> {
+        toml::from_str(text)
+    }
+
+    /// Reads a configuration file.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the file cannot be read and
+    /// `GameYError::InvalidConfig` if it is not a valid configuration.
+    pub fn load(file: &Path) -> Result<Self, GameYError> {
+        let text = std::fs::read_to_string(file).map_err(|e| GameYError::IoError {
+            message: format!("Failed to read {}", file.display()),
+            error: e.to_string(),
+        })?;
+        ServerConfig::parse(&text).map_err(|e| GameYError::InvalidConfig {
+            file: file.display().to_string(),
+            message: e.to_string(),
+        })
+    }
+
+    /// Returns the address to listen on, e.g. `0.0.0.0:3000`.
+    pub fn address(&self) -> String {
+        format!("{}:{}", self.host, self.port)
+    }
+
+    /// Returns how long a bot may search before its request fails.
+    pub fn bot_timeout(&self) -> Duration {
+        Duration::from_secs(self.bot_timeout_secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_settings_keep_their_defaults() {
+        let config = ServerConfig::parse("port = 8080\n[demo]\nworkers = 4\n").unwrap();
+        assert_eq!(config.address(), "0.0.0.0:8080");
+        assert_eq!(config.bot_timeout(), DEFAULT_BOT_TIMEOUT);
+        let demo = config.demo.unwrap();
+        assert_eq!(demo.workers, 4);
+        assert_eq!(demo.max_board_size, DemoLimits::default().max_board_size);
+        assert_eq!(ServerConfig::parse("").unwrap(), ServerConfig::default());
+
+        let config = ServerConfig::parse("[audit]\npath = \"audit.jsonl\"\n").unwrap();
+        assert_eq!(config.audit, Some(AuditConfig::new("audit.jsonl".into())));
+
+        let config = ServerConfig::parse(
+            "[chat]\n[[chat.webhooks]]\nurl = \"https://relay/hook\"\nservice = \"discord\"\n",
+        )
+        .unwrap();
+        let chat = config.chat.unwrap();
+        assert_eq!(chat.public_url, None);
+        assert_eq!(chat.webhooks[0].url, "https://relay/hook");
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings() {
+        assert!(ServerConfig::parse("prot = 8080\n").is_err());
+        assert!(ServerConfig::parse("port = 70000\n").is_err());
+        assert!(ServerConfig::parse("[demo]\nmax_clients = 1\n").is_err());
+    }
+
+    #[test]
+    fn test_load_reports_the_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("server.toml");
+        std::fs::write(&file, "port = \"http\"").unwrap();
+        let err = ServerConfig::load(&file).unwrap_err();
+        assert!(matches!(err, GameYError::InvalidConfig { .. }));
+        assert!(ServerConfig::load(&dir.path().join("missing.toml")).is_err());
+    }
+}
@@ -0,0 +1,421 @@
+//! Stateful bot sessions exposed by the bot server.
+//!
+//! A session binds a bot to one game. After creating it from a YEN position,
+//! clients only send the opponent's last move and receive the bot's reply, so
+//! bots that keep state between moves (see [`BotSession`]) can reuse their
+//! earlier work.
+//!
+//! # Endpoints
+//! - `POST /{api_version}/ybot/session/{bot_id}` - Create a session
+//! - `POST /{api_version}/ybot/session/{bot_id}/{session_id}/move` - Play the
+//!   opponent's move (if any) and get the bot's reply
+//! - `DELETE /{api_version}/ybot/session/{bot_id}/{session_id}` - Close a session
+
+use crate::{
+    MoveResponse, SUPPORTED_VERSION,
+    audit::{AuditEntry, AuditedRequest, ClientAddr},
+    check_api_version,
+    demo::{bot_worker, check_board_size},
+    error::{ErrorContext, ErrorResponse},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Path, State},
+};
+use gamey_bots::{BotSession, start_session};
+use gamey_core::{Coordinates, ErrorCode, GameY, GameYError, Movement, PlayerId, YEN};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, TryLockError};
+use std::time::Instant;
+
+/// A bot session together with the game it follows.
+struct SessionEntry {
+    bot_id: String,
+    game: GameY,
+    session: Box<dyn BotSession>,
+}
+
+/// A short description of an open session, as listed by the admin
+/// endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionSummary {
+    /// The identifier of the session.
+    pub session_id: String,
+    /// True if the bot is choosing a move; the other fields are then unknown.
+    pub searching: bool,
+    /// The bot playing the session.
+    pub bot_id: Option<String>,
+    /// The size of the board.
+    pub size: Option<u32>,
+    /// The number of moves played.
+    pub moves: Option<usize>,
+}
+
+/// The open bot sessions of the server.
+#[derive(Default)]
+pub struct SessionStore {
+    sessions: Mutex<HashMap<String, Arc<Mutex<SessionEntry>>>>,
+}
+
+impl SessionStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        SessionStore::default()
+    }
+
+    /// Opens a session of `bot_id` on `game` and returns its identifier.
+    pub fn open(&self, bot_id: &str, session: Box<dyn BotSession>, game: GameY) -> String {
+        let id = format!("{:016x}", rand::random::<u64>());
+        let entry = SessionEntry {
+            bot_id: bot_id.to_string(),
+            game,
+            session,
+        };
+        self.lock().insert(id.clone(), Arc::new(Mutex::new(entry)));
+        id
+    }
+
+    /// Closes a session. Returns false if it did not exist.
+    pub fn close(&self, id: &str) -> bool {
+        self.lock().remove(id).is_some()
+    }
+
+    /// Returns a summary of every open session, ordered by identifier.
+    ///
+    /// Sessions whose bot is searching are listed without waiting for it.
+    pub fn summaries(&self) -> Vec<SessionSummary> {
+        let sessions = self.lock();
+        let mut summaries: Vec<SessionSummary> = sessions
+            .iter()
+            .map(|(id, entry)| {
+                let entry = match entry.try_lock() {
+                    Ok(entry) => Some(entry),
+                    Err(TryLockError::Poisoned(e)) => Some(e.into_inner()),
+                    Err(TryLockError::WouldBlock) => None,
+                };
+                SessionSummary {
+                    session_id: id.clone(),
+                    searching: entry.is_none(),
+                    bot_id: entry.as_ref().map(|entry| entry.bot_id.clone()),
+                    size: entry.as_ref().map(|entry| entry.game.board_size()),
+                    moves: entry.as_ref().map(|entry| entry.game.move_count()),
+                }
+            })
+            .collect();
+        summaries.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        summaries
+    }
+
+    /// Returns the number of open sessions.
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Returns true if there are no open sessions.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Applies the opponent's move (if any) and returns the bot's reply,
+    /// which is also applied to the session's game.
+    ///
+    /// Returns `Ok(None)` when the bot has no move to play, for example
+    /// because the opponent's move ended the game.
+    pub fn play(
+        &self,
+        id: &str,
+        bot_id: &str,
+        opponent_move: Option<Coordinates>,
+    ) -> Result<Option<Coordinates>, GameYError> {
+        let not_found = || GameYError::SessionNotFound { id: id.to_string() };
+        let entry = self.lock().get(id).cloned().ok_or_else(not_found)?;
+        let mut entry = entry.lock().unwrap_or_else(|e| e.into_inner());
+        if entry.bot_id != bot_id {
+            return Err(not_found());
+        }
+        let SessionEntry { game, session, .. } = &mut *entry;
+
+        if let Some(coords) = opponent_move {
+            place_for_next_player(game, coords)?;
+        }
+        if game.check_game_over() {
+            return Ok(None);
+        }
+        let Some(coords) = session.choose_move(game) else {
+            return Ok(None);
+        };
+        place_for_next_player(game, coords)?;
+        Ok(Some(coords))
+    }
+
+    /// Returns the Zobrist hash of the current position of a session, or
+    /// `None` if it does not exist. Waits if the bot is searching.
+    pub fn position_hash(&self, id: &str) -> Option<u64> {
+        let entry = self.lock().get(id).cloned()?;
+        let entry = entry.lock().unwrap_or_else(|e| e.into_inner());
+        Some(entry.game.zobrist_hash())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Arc<Mutex<SessionEntry>>>> {
+        self.sessions.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Places a stone for the player whose turn it is.
+fn place_for_next_player(game: &mut GameY, coords: Coordinates) -> Result<(), GameYError> {
+    let Some(player) = game.next_player() else {
+        let player = PlayerId::new((game.move_count() % 2) as u32);
+        return Err(GameYError::GameOver {
+            movement: Movement::Placement { player, coords },
+        });
+    };
+    game.add_move(Movement::Placement { player, coords })
+}
+
+/// Path parameters for creating a session.
+#[derive(Deserialize)]
+pub struct NewSessionParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The bot that will play in the session.
+    bot_id: String,
+}
+
+impl NewSessionParams {
+    /// The parameters of a new session of `bot_id` in the current API
+    /// version, for callers that do not come through the router.
+    pub fn new(bot_id: &str) -> Self {
+        NewSessionParams {
+            api_version: SUPPORTED_VERSION.to_string(),
+            bot_id: bot_id.to_string(),
+        }
+    }
+}
+
+/// Path parameters for endpoints that operate on an existing session.
+#[derive(Deserialize, Clone)]
+pub struct SessionParams {
+    /// The API version (e.g., "v1").
+    api_version: String,
+    /// The bot playing in the session.
+    bot_id: String,
+    /// The identifier of the session.
+    session_id: String,
+}
+
+impl SessionParams {
+    /// The parameters of requests about a session in the current API
+    /// version, for callers that do not come through the router.
+    pub fn new(bot_id: &str, session_id: &str) -> Self {
+        SessionParams {
+            api_version: SUPPORTED_VERSION.to_string(),
+            bot_id: bot_id.to_string(),
+            session_id: session_id.to_string(),
+        }
+    }
+}
+
+/// Response returned when a session is created or closed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SessionResponse {
+    /// The API version used for this request.
+    pub api_version: String,
+    /// The bot playing in the session.
+    pub bot_id: String,
+    /// The identifier to use in subsequent move requests.
+    pub session_id: String,
+}
+
+/// Request body of the session move endpoint.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct SessionMoveRequest {
+    /// The opponent's last move, or `None` when the bot moves first.
+    #[serde(default)]
+    pub coords: Option<Coordinates>,
+}
+
+/// Handler that creates a bot session bound to the given position.
+///
+/// # Route
+/// `POST /{api_version}/ybot/session/{bot_id}`
+pub async fn create_session(
+    State(state): State<AppState>,
+    Path(params): Path<NewSessionParams>,
+    Json(yen): Json<YEN>,
+) -> Result<Json<SessionResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let game = GameY::try_from(yen)
+        .map_err(ErrorResponse::invalid_yen)
+        .context(&params.api_version, Some(&params.bot_id))?;
+    check_board_size(&state, game.board_size())
+        .context(&params.api_version, Some(&params.bot_id))?;
+    let Some(bot) = state.bots().find(&params.bot_id) else {
+        return Err(ErrorResponse::error(
+            ErrorCode::BotNotFound,
+            &format!("Bot not found: {}", params.bot_id),
+            Some(params.api_version),
+            Some(params.bot_id),
+        ));
+    };
+    let session_id = state
+        .sessions()
+        .open(&params.bot_id, start_session(bot), game);
+    Ok(Json(SessionResponse {
+        api_version: params.api_version,
+        bot_id: params.bot_id,
+        session_id,
+    }))
+}
+
+/// Handler that sends the opponent's move to a session and returns the bot's reply.
+///
+/// # Route
+/// `POST /{api_version}/ybot/session/{bot_id}/{session_id}/move`
+pub async fn session_move(
+    State(state): State<AppState>,
+    Path(params): Path<SessionParams>,
+    ClientAddr(client): ClientAddr,
+    Json(request): Json<SessionMoveRequest>,
+) -> Result<Json<MoveResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    let _worker = bot_worker(&state)
+        .await
+        .context(&params.api_version, Some(&params.bot_id))?;
+    let sessions = state.sessions();
+    let audit = state.audit_log().and_then(|log| {
+        let position_hash = sessions.position_hash(&params.session_id)?;
+        let entry = AuditEntry::new(AuditedRequest::SessionMove, client, position_hash)
+            .with_bot(&params.bot_id)
+            .with_id(&params.session_id)
+            .with_sent(request.coords);
+        Some((log, entry))
+    });
+    let started = Instant::now();
+    let reply = sessions.play(&params.session_id, &params.bot_id, request.coords);
+    if reply.as_ref().is_ok_and(Option::is_some) {
+        let latencies = state.bot_latencies();
+        latencies.record(&params.bot_id, started.elapsed(), false);
+    }
+    if let Some((log, entry)) = audit {
+        let outcome = reply.as_ref().map(|coords| *coords);
+        log.record(&entry.with_outcome(outcome.map_err(GameYError::code)));
+    }
+    let reply = reply.context(&params.api_version, Some(&params.bot_id))?;
+    let Some(coords) = reply else {
+        return Err(ErrorResponse::error(
+            ErrorCode::NoMoves,
+            "No valid moves available for the bot",
+            Some(params.api_version),
+            Some(params.bot_id),
+        ));
+    };
+    Ok(Json(MoveResponse {
+        api_version: params.api_version,
+        bot_id: params.bot_id,
+        coords,
+        explanation: None,
+    }))
+}
+
+/// Handler that closes a session.
+///
+/// # Route
+/// `DELETE /{api_version}/ybot/session/{bot_id}/{session_id}`
+pub async fn close_session(
+    State(state): State<AppState>,
+    Path(params): Path<SessionParams>,
+) -> Result<Json<SessionResponse>, ErrorResponse> {
+    check_api_version(&params.api_version)?;
+    if !state.sessions().close(&params.session_id) {
+        return Err(GameYError::SessionNotFound {
+            id: params.session_id,
+        })
+        .context(&params.api_version, Some(&params.bot_id));
+    }
+    Ok(Json(SessionResponse {
+        api_version: params.api_version,
+        bot_id: params.bot_id,
+        session_id: params.session_id,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::{MctsBot, RandomBot};
+
+    #[test]
+    fn test_open_and_close() {
+        let store = SessionStore::new();
+        let id = store.open(
+            "random_bot",
+            start_session(Arc::new(RandomBot)),
+            GameY::new(3),
+        );
+        assert_eq!(store.len(), 1);
+        assert!(store.close(&id));
+        assert!(!store.close(&id));
+        assert!(store.is_empty());
+    }
+
+    #[test]
+    fn test_play_applies_both_moves() {
+        let store = SessionStore::new();
+        let id = store.open(
+            "mcts_bot",
+            start_session(Arc::new(MctsBot::new(50))),
+            GameY::new(4),
+        );
+
+        let first = store.play(&id, "mcts_bot", None).unwrap().unwrap();
+        let opponent = (0..10)
+            .map(|idx| Coordinates::from_index(idx, 4))
+            .find(|c| *c != first)
+            .unwrap();
+        let second = store
+            .play(&id, "mcts_bot", Some(opponent))
+            .unwrap()
+            .unwrap();
+        assert_ne!(second, first);
+        assert_ne!(second, opponent);
+    }
+
+    #[test]
+    fn test_play_rejects_occupied_opponent_move() {
+        let store = SessionStore::new();
+        let id = store.open(
+            "random_bot",
+            start_session(Arc::new(RandomBot)),
+            GameY::new(3),
+        );
+        let first = store.play(&id, "random_bot", None).unwrap().unwrap();
+        let result = store.play(&id, "random_bot", Some(first));
+        assert!(matches!(result, Err(GameYError::Occupied { .. })));
+    }
+
+    #[test]
+    fn test_play_with_wrong_bot_is_not_found() {
+        let store = SessionStore::new();
+        let id = store.open(
+            "random_bot",
+            start_session(Arc::new(RandomBot)),
+            GameY::new(3),
+        );
+        let result = store.play(&id, "mcts_bot", None);
+        assert!(matches!(result, Err(GameYError::SessionNotFound { .. })));
+    }
+
+    #[test]
+    fn test_play_after_winning_opponent_move_returns_none() {
+        let store = SessionStore::new();
+        let id = store.open(
+            "random_bot",
+            start_session(Arc::new(RandomBot)),
+            GameY::new(1),
+        );
+        let result = store.play(&id, "random_bot", Some(Coordinates::new(0, 0, 0)));
+        assert_eq!(result.unwrap(), None);
+    }
+}
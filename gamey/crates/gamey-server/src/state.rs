@@ -0,0 +1,245 @@
+use crate::{
+    admin::BotLatencies,
+    audit::AuditLog,
+    bots_config::RemoteBotFactory,
+    cache::{DEFAULT_MOVE_CACHE_ENTRIES, MoveCache},
+    demo::{Demo, DemoLimits},
+    games::GameStore,
+    sessions::SessionStore,
+};
+use gamey_bots::YBotRegistry;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+/// How long a bot may search before the request fails by default.
+pub const DEFAULT_BOT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Shared application state for the bot server.
+///
+/// This struct holds the bot registry, the hosted games, the open bot
+/// sessions and the cached bot answers, and is shared across all request handlers
+/// via Axum's state extraction. It uses `Arc` internally to allow cheap cloning
+/// for concurrent request handling.
+///
+/// The bot registry can be replaced while the server runs (see
+/// [`AppState::replace_bots`]); requests already running keep the bots they
+/// started with.
+#[derive(Clone)]
+pub struct AppState {
+    /// The registry of available bots, shared by all clones of the state and
+    /// replaced as a whole on reload.
+    bots: Arc<RwLock<Arc<YBotRegistry>>>,
+    /// The `bots.toml` file the registry is reloaded from, if any.
+    bots_config: Option<PathBuf>,
+    /// Builds the remote bots of `bots.toml`, if the server can reach other
+    /// servers.
+    remote_bots: Option<RemoteBotFactory>,
+    /// The correspondence games hosted by the server.
+    games: Arc<GameStore>,
+    /// The stateful bot sessions currently open.
+    sessions: Arc<SessionStore>,
+    /// The answers of deterministic bots, by position.
+    move_cache: Arc<MoveCache>,
+    /// How long a bot may search before its request fails.
+    bot_timeout: Duration,
+    /// The limits of demo mode, if the server runs in it.
+    demo: Option<Arc<Demo>>,
+    /// The key required by the admin endpoints; without one they are
+    /// disabled.
+    admin_key: Option<Arc<str>>,
+    /// How long each bot takes to answer.
+    bot_latencies: Arc<BotLatencies>,
+    /// The log of the moves served, if auditing is enabled.
+    audit_log: Option<Arc<AuditLog>>,
+}
+
+impl AppState {
+    /// Creates a new application state with the given bot registry.
+    ///
+    /// Hosted games are kept in memory; use [`AppState::with_games`] to
+    /// provide a persistent store.
+    pub fn new(bots: YBotRegistry) -> Self {
+        Self {
+            bots: Arc::new(RwLock::new(Arc::new(bots))),
+            bots_config: None,
+            remote_bots: None,
+            games: Arc::new(GameStore::in_memory()),
+            sessions: Arc::new(SessionStore::new()),
+            move_cache: Arc::new(MoveCache::new(DEFAULT_MOVE_CACHE_ENTRIES)),
+            bot_timeout: DEFAULT_BOT_TIMEOUT,
+            demo: None,
+            admin_key: None,
+            bot_latencies: Arc::new(BotLatencies::new()),
+            audit_log: None,
+        }
+    }
+
+    /// Sets the `bots.toml` file the registry is reloaded from (see
+    /// [`admin`](crate::admin)); without one, reloading restores the default
+    /// limits.
+    pub fn with_bots_config(mut self, file: PathBuf) -> Self {
+        self.bots_config = Some(file);
+        self
+    }
+
+    /// Sets how the remote bots of `bots.toml` are built when the registry
+    /// is reloaded (see [`bots_config`](crate::bots_config)).
+    pub fn with_remote_bots(mut self, connect: RemoteBotFactory) -> Self {
+        self.remote_bots = Some(connect);
+        self
+    }
+
+    /// Replaces the store used for hosted games.
+    pub fn with_games(mut self, games: GameStore) -> Self {
+        self.games = Arc::new(games);
+        self
+    }
+
+    /// Replaces the move cache with an empty one holding at most `capacity`
+    /// answers; 0 disables caching.
+    pub fn with_move_cache(mut self, capacity: usize) -> Self {
+        self.move_cache = Arc::new(MoveCache::new(capacity));
+        self
+    }
+
+    /// Replaces how long a bot may search before its request fails, see
+    /// [`DEFAULT_BOT_TIMEOUT`].
+    pub fn with_bot_timeout(mut self, timeout: Duration) -> Self {
+        self.bot_timeout = timeout;
+        self
+    }
+
+    /// Runs the server in demo mode with the given limits (see
+    /// [`demo`](crate::demo)).
+    pub fn with_demo(mut self, limits: DemoLimits) -> Self {
+        self.demo = Some(Arc::new(Demo::new(limits)));
+        self
+    }
+
+    /// Enables the admin endpoints, for requests with the given key (see
+    /// [`admin`](crate::admin)).
+    pub fn with_admin_key(mut self, key: &str) -> Self {
+        self.admin_key = Some(key.into());
+        self
+    }
+
+    /// Logs every move served to the given audit log (see
+    /// [`audit`](crate::audit)).
+    pub fn with_audit_log(mut self, log: AuditLog) -> Self {
+        self.audit_log = Some(Arc::new(log));
+        self
+    }
+
+    /// Returns the key required by the admin endpoints, if they are enabled.
+    pub fn admin_key(&self) -> Option<&str> {
+        self.admin_key.as_deref()
+    }
+
+    /// Returns how long a bot may search before its request fails.
+    pub fn bot_timeout(&self) -> Duration {
+        self.bot_timeout
+    }
+
+    /// Returns a clone of the Arc-wrapped bot registry.
+    pub fn bots(&self) -> Arc<YBotRegistry> {
+        Arc::clone(&self.bots.read().unwrap_or_else(|e| e.into_inner()))
+    }
+
+    /// Replaces the bot registry for the following requests, in this state
+    /// and all of its clones.
+    pub fn replace_bots(&self, bots: YBotRegistry) {
+        *self.bots.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(bots);
+    }
+
+    /// Returns the `bots.toml` file the registry is reloaded from, if any.
+    pub fn bots_config(&self) -> Option<&Path> {
+        self.bots_config.as_deref()
+    }
+
+    /// Returns how the remote bots of `bots.toml` are built, if the server
+    /// can reach other servers.
+    pub fn remote_bots(&self) -> Option<&RemoteBotFactory> {
+        self.remote_bots.as_ref()
+    }
+
+    /// Returns a clone of the Arc-wrapped game store.
+    pub fn games(&self) -> Arc<GameStore> {
+        Arc::clone(&self.games)
+    }
+
+    /// Returns a clone of the Arc-wrapped session store.
+    pub fn sessions(&self) -> Arc<SessionStore> {
+        Arc::clone(&self.sessions)
+    }
+
+    /// Returns a clone of the Arc-wrapped move cache.
+    pub fn move_cache(&self) -> Arc<MoveCache> {
+        Arc::clone(&self.move_cache)
+    }
+
+    /// Returns a clone of the Arc-wrapped record of bot answer times.
+    pub fn bot_latencies(&self) -> Arc<BotLatencies> {
+        Arc::clone(&self.bot_latencies)
+    }
+
+    /// Returns the state of demo mode, or `None` if the server does not run
+    /// in it.
+    pub fn demo(&self) -> Option<Arc<Demo>> {
+        self.demo.clone()
+    }
+
+    /// Returns the audit log, or `None` if the moves served are not logged.
+    pub fn audit_log(&self) -> Option<Arc<AuditLog>> {
+        self.audit_log.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::RandomBot;
+
+    #[test]
+    fn test_new_state() {
+        let registry = YBotRegistry::new();
+        let state = AppState::new(registry);
+        assert!(state.bots().names().is_empty());
+    }
+
+    #[test]
+    fn test_state_with_bot() {
+        let registry = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        let state = AppState::new(registry);
+        assert!(state.bots().names().contains(&"random_bot".to_string()));
+    }
+
+    #[test]
+    fn test_state_clone() {
+        let registry = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        let state = AppState::new(registry);
+        let cloned = state.clone();
+        // Both should reference the same underlying data
+        assert_eq!(state.bots().names(), cloned.bots().names());
+    }
+
+    #[test]
+    fn test_replaced_bots_are_seen_by_clones() {
+        let state = AppState::new(YBotRegistry::new());
+        let cloned = state.clone();
+        let before = state.bots();
+        cloned.replace_bots(YBotRegistry::new().with_bot(Arc::new(RandomBot)));
+        assert_eq!(state.bots().names(), vec!["random_bot".to_string()]);
+        assert!(before.names().is_empty());
+    }
+
+    #[test]
+    fn test_bots_arc_clone() {
+        let registry = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        let state = AppState::new(registry);
+        let bots1 = state.bots();
+        let bots2 = state.bots();
+        // Both Arcs should point to the same registry
+        assert_eq!(bots1.names(), bots2.names());
+    }
+}
@@ -1,4 +1,5 @@
 use crate::error::ErrorResponse;
+use gamey_core::ErrorCode;
 
 /// The currently supported API version.
 pub const SUPPORTED_VERSION: &str = "v1";
@@ -14,7 +15,7 @@ pub const SUPPORTED_VERSION: &str = "v1";
 ///
 /// # Example
 /// ```
-/// use gamey::check_api_version;
+/// use gamey_server::check_api_version;
 ///
 /// assert!(check_api_version("v1").is_ok());
 /// assert!(check_api_version("v2").is_err());
@@ -22,6 +23,7 @@ pub const SUPPORTED_VERSION: &str = "v1";
 pub fn check_api_version(version: &str) -> Result<(), ErrorResponse> {
     if version != SUPPORTED_VERSION {
         Err(ErrorResponse::error(
+            ErrorCode::UnsupportedApiVersion,
             &format!(
                 "Unsupported API version: {}. Supported version is {}",
                 version, SUPPORTED_VERSION
@@ -0,0 +1,106 @@
+//! A web page to play against the bots of the server from a browser, with
+//! the `webui` feature.
+//!
+//! The page, its script and its styles are embedded in the binary from the
+//! `webui` directory of the crate. The page draws the board as SVG and plays
+//! through the games and choose endpoints, so the server needs nothing else.
+//!
+//! # Endpoints
+//! - `GET /` - The page
+//! - `GET /webui/bots` - The bots the page offers as opponents
+//! - `GET /webui/{file}` - The script and styles of the page
+
+use crate::state::AppState;
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::{StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use include_dir::{Dir, include_dir};
+
+static ASSETS: Dir<'static> = include_dir!("$CARGO_MANIFEST_DIR/webui");
+
+/// Handler that serves the page.
+///
+/// # Route
+/// `GET /`
+pub async fn index() -> Response {
+    asset("index.html")
+}
+
+/// Handler that serves a file of the page.
+///
+/// # Route
+/// `GET /webui/{file}`
+pub async fn webui_file(Path(file): Path<String>) -> Response {
+    asset(&file)
+}
+
+/// Handler that lists the bots of the server, sorted by name.
+///
+/// # Route
+/// `GET /webui/bots`
+pub async fn bots(State(state): State<AppState>) -> Json<Vec<String>> {
+    let mut names = state.bots().names();
+    names.sort();
+    Json(names)
+}
+
+/// Answers with an embedded file, or 404 if there is none at `path`.
+fn asset(path: &str) -> Response {
+    match ASSETS.get_file(path) {
+        Some(file) => (
+            [(header::CONTENT_TYPE, content_type(path))],
+            file.contents(),
+        )
+            .into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Returns the media type of an embedded file by its extension.
+fn content_type(path: &str) -> &'static str {
+    match path.rsplit_once('.').map(|(_, extension)| extension) {
+        Some("html") => "text/html; charset=utf-8",
+        Some("js") => "text/javascript; charset=utf-8",
+        Some("css") => "text/css; charset=utf-8",
+        Some("svg") => "image/svg+xml",
+        _ => "application/octet-stream",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_serves_the_embedded_page() {
+        let response = index().await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "text/html; charset=utf-8"
+        );
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let page = String::from_utf8(body.to_vec()).unwrap();
+        assert!(page.contains("webui/app.js"));
+
+        let script = webui_file(Path("app.js".to_string())).await;
+        assert_eq!(
+            script.headers()[header::CONTENT_TYPE],
+            "text/javascript; charset=utf-8"
+        );
+        let missing = webui_file(Path("secret.toml".to_string())).await;
+        assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_lists_the_bots() {
+        let Json(names) = bots(State(crate::create_default_state())).await;
+        assert!(names.contains(&"random_bot".to_string()));
+        assert!(names.is_sorted());
+    }
+}
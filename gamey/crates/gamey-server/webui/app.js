@@ -0,0 +1,195 @@
+// Plays a game hosted by the server against one of its bots: the human's
+// moves and the bot's answers, asked of the choose endpoint, are both played
+// through the games endpoints.
+"use strict";
+
+const API = "v1";
+const SVG = "http://www.w3.org/2000/svg";
+const RADIUS = 20;
+
+const form = document.getElementById("new-game");
+const board = document.getElementById("board");
+const statusLine = document.getElementById("status");
+
+let game = null;
+let human = 0;
+let bot = null;
+let thinking = false;
+
+async function request(method, path, body) {
+  const response = await fetch(path, {
+    method,
+    headers: body === undefined ? {} : { "content-type": "application/json" },
+    body: body === undefined ? undefined : JSON.stringify(body),
+  });
+  const answer = await response.json();
+  // Errors of the API come with a code, whatever the status
+  if (!response.ok || answer.code !== undefined) {
+    throw new Error(answer.message || response.statusText);
+  }
+  return answer;
+}
+
+async function loadBots() {
+  const names = await request("GET", "webui/bots");
+  const select = form.elements.bot;
+  for (const name of names) {
+    const option = document.createElement("option");
+    option.value = option.textContent = name;
+    select.appendChild(option);
+  }
+  select.value = names.includes("mcts_bot") ? "mcts_bot" : names[0];
+}
+
+// The cells of a position, each with its coordinates, its place on the
+// drawing and its content: "." when empty, the player's index, or "#" when
+// blocked.
+function cells(yen) {
+  const rows = yen.layout.split("/");
+  const size = yen.size;
+  const hex = yen.topology === "hex";
+  const dx = RADIUS * Math.sqrt(3);
+  const dy = RADIUS * 1.5;
+  const result = [];
+  rows.forEach((row, r) => {
+    [...row].forEach((symbol, c) => {
+      const coords = hex
+        ? { x: r, y: c, z: 0 }
+        : { x: size - 1 - r, y: c, z: r - c };
+      const shift = hex ? r / 2 : (size - 1 - r) / 2;
+      const index = yen.players.indexOf(symbol);
+      result.push({
+        coords,
+        cx: RADIUS + dx * (c + shift),
+        cy: RADIUS + dy * r,
+        content: symbol === "." ? "." : index >= 0 ? index : "#",
+      });
+    });
+  });
+  return result;
+}
+
+function hexagon(cx, cy) {
+  const points = [];
+  for (let i = 0; i < 6; i++) {
+    const angle = Math.PI / 6 + (i * Math.PI) / 3;
+    points.push(`${cx + RADIUS * Math.cos(angle)},${cy + RADIUS * Math.sin(angle)}`);
+  }
+  return points.join(" ");
+}
+
+function sameCell(a, b) {
+  return a.x === b.x && a.y === b.y && a.z === b.z;
+}
+
+function draw() {
+  board.replaceChildren();
+  if (!game) {
+    return;
+  }
+  const last = game.history.filter((move) => move.type === "placement").pop();
+  const playable = game.next_player === human && !thinking;
+  let width = 0;
+  let height = 0;
+  for (const cell of cells(game.yen)) {
+    const polygon = document.createElementNS(SVG, "polygon");
+    polygon.setAttribute("points", hexagon(cell.cx, cell.cy));
+    polygon.classList.add("cell");
+    if (cell.content === "#") {
+      polygon.classList.add("blocked");
+    } else if (cell.content === "." && playable) {
+      polygon.classList.add("playable");
+      polygon.addEventListener("click", () => play(cell.coords));
+    }
+    board.appendChild(polygon);
+    if (cell.content === 0 || cell.content === 1) {
+      const stone = document.createElementNS(SVG, "circle");
+      stone.setAttribute("cx", cell.cx);
+      stone.setAttribute("cy", cell.cy);
+      stone.setAttribute("r", RADIUS * 0.7);
+      stone.classList.add("stone", `player-${cell.content}`);
+      if (last && sameCell(last.coords, cell.coords)) {
+        stone.classList.add("last");
+      }
+      board.appendChild(stone);
+    }
+    width = Math.max(width, cell.cx + RADIUS * 2);
+    height = Math.max(height, cell.cy + RADIUS * 2);
+  }
+  board.setAttribute("viewBox", `0 0 ${width} ${height}`);
+}
+
+function report() {
+  if (game.winner !== null && game.winner !== undefined) {
+    statusLine.textContent = game.winner === human ? "You won!" : `${bot} won.`;
+  } else if (thinking) {
+    statusLine.textContent = `${bot} is thinking...`;
+  } else {
+    statusLine.textContent = "Your move: click a cell.";
+  }
+}
+
+function show(error) {
+  statusLine.textContent = `Error: ${error.message}`;
+}
+
+async function botMove() {
+  thinking = true;
+  draw();
+  report();
+  try {
+    const answer = await request("POST", `${API}/ybot/choose/${bot}`, game.yen);
+    game = await request("POST", `${API}/games/${game.game_id}/moves`, {
+      type: "placement",
+      player: game.next_player,
+      coords: answer.coords,
+    });
+    thinking = false;
+    draw();
+    report();
+  } catch (error) {
+    thinking = false;
+    draw();
+    show(error);
+  }
+}
+
+async function play(coords) {
+  try {
+    game = await request("POST", `${API}/games/${game.game_id}/moves`, {
+      type: "placement",
+      player: human,
+      coords,
+    });
+  } catch (error) {
+    show(error);
+    return;
+  }
+  draw();
+  report();
+  if (game.next_player !== null && game.next_player !== undefined) {
+    await botMove();
+  }
+}
+
+form.addEventListener("submit", async (event) => {
+  event.preventDefault();
+  bot = form.elements.bot.value;
+  human = Number(form.elements.human.value);
+  try {
+    game = await request("POST", `${API}/games`, {
+      size: Number(form.elements.size.value),
+      topology: form.elements.topology.value,
+    });
+  } catch (error) {
+    show(error);
+    return;
+  }
+  draw();
+  report();
+  if (game.next_player !== human) {
+    await botMove();
+  }
+});
+
+loadBots().catch(show);
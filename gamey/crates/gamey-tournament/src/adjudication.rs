@@ -0,0 +1,223 @@
+//! Deciding unfinished games.
+//!
+//! When a correspondence game is stopped before its end, e.g. because a
+//! player ran out of time, [`Adjudication::run`] lets a bot play the
+//! position out against itself many times and awards the game to a player
+//! who wins at least a threshold fraction of the playouts. The playouts are
+//! a match between the two players of the position, so [`MatchStats`] gives
+//! the confidence of the estimate: the interval of the win rate and the
+//! probability that the leader is really ahead.
+//!
+//! The playouts are shared by the worker threads, each playout drawing from
+//! its own random stream, so under a [`gamey_core::DeterministicConfig`]
+//! the adjudication is the same whatever the number of threads.
+
+use crate::MatchStats;
+use gamey_bots::YBot;
+use gamey_core::{GameStatus, GameY, Movement, PlayerId, reseed, worker_threads};
+use std::fmt::Display;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// The estimated winner of an unfinished position.
+#[derive(Debug, Clone)]
+pub struct Adjudication {
+    /// The bot that played the position out.
+    pub bot: String,
+    /// The playouts won by the first player (wins) and by the second
+    /// player (losses); playouts the bot gave up are not counted.
+    pub stats: MatchStats,
+    /// The fraction of the playouts a player has to win to be awarded the
+    /// game.
+    pub threshold: f64,
+}
+
+impl Adjudication {
+    /// Plays `game` out `playouts` times with `bot` playing both sides, in
+    /// parallel, and awards it to a player who wins at least `threshold` of
+    /// the playouts.
+    ///
+    /// Returns `None` if the game is over.
+    pub fn run(game: &GameY, bot: &dyn YBot, playouts: u32, threshold: f64) -> Option<Self> {
+        game.next_player()?;
+        let stats = Mutex::new(MatchStats::default());
+        let next = AtomicU32::new(0);
+        std::thread::scope(|scope| {
+            for _ in 0..worker_threads().min(playouts as usize) {
+                scope.spawn(|| {
+                    loop {
+                        let playout = next.fetch_add(1, Ordering::Relaxed);
+                        if playout >= playouts {
+                            break;
+                        }
+                        reseed(u64::from(playout));
+                        let winner = play_out(game, bot);
+                        let mut stats = stats.lock().unwrap_or_else(|e| e.into_inner());
+                        match winner.map(|winner| winner.id()) {
+                            Some(0) => stats.wins += 1,
+                            Some(_) => stats.losses += 1,
+                            None => {}
+                        }
+                    }
+                });
+            }
+        });
+        Some(Adjudication {
+            bot: bot.name().to_string(),
+            stats: stats.into_inner().unwrap_or_else(|e| e.into_inner()),
+            threshold,
+        })
+    }
+
+    /// Returns the fraction of the counted playouts won by `player`.
+    pub fn win_rate(&self, player: PlayerId) -> f64 {
+        match player.id() {
+            0 => self.stats.score(),
+            _ => 1.0 - self.stats.score(),
+        }
+    }
+
+    /// Returns the player who won the most playouts.
+    pub fn leader(&self) -> PlayerId {
+        PlayerId::new(u32::from(self.stats.wins < self.stats.losses))
+    }
+
+    /// Returns the probability that the leader is really ahead, the
+    /// likelihood of superiority of the playouts.
+    pub fn confidence(&self) -> f64 {
+        match self.leader().id() {
+            0 => self.stats.los(),
+            _ => 1.0 - self.stats.los(),
+        }
+    }
+
+    /// Returns the player awarded the game, or `None` if neither player
+    /// won enough of the playouts and the game stays undecided.
+    pub fn winner(&self) -> Option<PlayerId> {
+        let leader = self.leader();
+        (self.stats.games() > 0 && self.win_rate(leader) >= self.threshold).then_some(leader)
+    }
+}
+
+impl Display for Adjudication {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let leader = self.leader();
+        let (low, high) = match leader.id() {
+            0 => self.stats.score_interval(),
+            _ => {
+                let (low, high) = self.stats.score_interval();
+                (1.0 - high, 1.0 - low)
+            }
+        };
+        writeln!(
+            f,
+            "Played out {} times by {}: player 0 won {}, player 1 won {}",
+            self.stats.games(),
+            self.bot,
+            self.stats.wins,
+            self.stats.losses
+        )?;
+        writeln!(
+            f,
+            "Player {} wins {:.1}% [{:.1}%, {:.1}%], ahead with confidence {:.1}%",
+            leader,
+            100.0 * self.win_rate(leader),
+            100.0 * low,
+            100.0 * high,
+            100.0 * self.confidence()
+        )?;
+        match self.winner() {
+            Some(winner) => write!(
+                f,
+                "Adjudicated to player {} (threshold {:.1}%)",
+                winner,
+                100.0 * self.threshold
+            ),
+            None => write!(
+                f,
+                "Undecided: no player reaches the threshold of {:.1}%",
+                100.0 * self.threshold
+            ),
+        }
+    }
+}
+
+/// Lets `bot` play both sides of `game` until it ends, returning the
+/// winner; `None` if the bot gave up.
+fn play_out(game: &GameY, bot: &dyn YBot) -> Option<PlayerId> {
+    let mut game = game.clone();
+    loop {
+        let player = match game.status() {
+            GameStatus::Ongoing { next_player } => *next_player,
+            GameStatus::Finished { winner } => return Some(*winner),
+        };
+        let coords = bot.choose_move(&game)?;
+        game.add_move(Movement::Placement { player, coords }).ok()?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::RandomBot;
+    use gamey_core::Coordinates;
+
+    #[test]
+    fn test_won_position_is_adjudicated() {
+        // Player 0 holds the top and needs one of the two cells of the
+        // bottom row, which player 1 cannot both take
+        let mut game = GameY::new(2);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(1, 0, 0),
+        })
+        .unwrap();
+        let adjudication = Adjudication::run(&game, &RandomBot, 50, 0.75).unwrap();
+        assert_eq!(adjudication.stats.games(), 50);
+        assert_eq!(adjudication.stats.wins, 50);
+        assert_eq!(adjudication.leader(), PlayerId::new(0));
+        assert_eq!(adjudication.winner(), Some(PlayerId::new(0)));
+        assert!(adjudication.confidence() > 0.99);
+        let text = adjudication.to_string();
+        assert!(text.contains("player 0 won 50, player 1 won 0"), "{}", text);
+        assert!(text.ends_with("Adjudicated to player 0 (threshold 75.0%)"));
+    }
+
+    #[test]
+    fn test_threshold_leaves_close_games_undecided() {
+        let adjudication = Adjudication {
+            bot: "random_bot".to_string(),
+            stats: MatchStats { wins: 4, losses: 6 },
+            threshold: 0.75,
+        };
+        assert_eq!(adjudication.leader(), PlayerId::new(1));
+        assert!((adjudication.win_rate(PlayerId::new(1)) - 0.6).abs() < 1e-9);
+        assert!(adjudication.confidence() > 0.5);
+        assert_eq!(adjudication.winner(), None);
+        assert!(adjudication.to_string().contains("Undecided"));
+    }
+
+    #[test]
+    fn test_seeded_adjudication_does_not_depend_on_threads() {
+        let _lock = crate::lock_determinism();
+        let wins = |threads| {
+            let _config = gamey_core::DeterministicConfig::new(3)
+                .with_threads(threads)
+                .install();
+            let adjudication = Adjudication::run(&GameY::new(5), &RandomBot, 40, 0.6).unwrap();
+            adjudication.stats
+        };
+        assert_eq!(wins(1), wins(3));
+    }
+
+    #[test]
+    fn test_finished_game_is_not_adjudicated() {
+        let mut game = GameY::new(1);
+        game.add_move(Movement::Placement {
+            player: PlayerId::new(0),
+            coords: Coordinates::new(0, 0, 0),
+        })
+        .unwrap();
+        assert!(Adjudication::run(&game, &RandomBot, 5, 0.5).is_none());
+    }
+}
@@ -0,0 +1,586 @@
+//! Tournaments between bots.
+//!
+//! A tournament pairs its [`Entrant`]s round by round in a [`Format`]:
+//! round-robin, Swiss or single elimination (see [`schedule`]). Every
+//! pairing is a match of a few games in which who moves first alternates,
+//! the entrant that moved first less often so far starting. Entrants are either
+//! bots of this workspace or third-party programs run as [`ProcessBot`]s,
+//! whose moves are sandboxed in separate processes: a process bot that goes
+//! over its per-move [`Budget`], crashes or plays an illegal move loses the
+//! game and is disqualified, forfeiting its remaining games. The
+//! [`TournamentReport`] ranks the entrants and lists the results with what
+//! the moves of every entrant cost (see [`Accounting`]).
+//!
+//! Matches can stop early once a [`Sprt`] has decided which entrant is
+//! stronger, and the report of a match between two entrants tells how
+//! significant its result is (see [`stats`]).
+//!
+//! Unfinished games, such as timed-out correspondence games, can be decided
+//! by letting a bot play them out (see [`Adjudication`]).
+//!
+//! Long tournaments can be saved game by game in a [`TournamentDir`] and
+//! resumed after a crash with [`Tournament::resume`], which replays the
+//! recorded games instead of playing them again.
+//!
+//! Every game draws from its own random stream (see [`gamey_core::reseed`]),
+//! so under a [`gamey_core::DeterministicConfig`] a tournament between bots
+//! of this workspace is played again move for move from the same seed, also
+//! when it is resumed; only the measured times differ.
+//!
+//! # Example
+//! ```
+//! use gamey_bots::RandomBot;
+//! use gamey_core::GameY;
+//! use gamey_tournament::{Entrant, Tournament};
+//! use std::sync::Arc;
+//!
+//! let entrants = vec![Entrant::Bot(Arc::new(RandomBot)), Entrant::Bot(Arc::new(RandomBot))];
+//! let report = Tournament::new(entrants).run(|| GameY::new(3));
+//! assert_eq!(report.games.len(), 2);
+//! ```
+
+pub mod adjudication;
+pub mod report;
+pub mod sandbox;
+pub mod schedule;
+pub mod state;
+pub mod stats;
+pub use adjudication::*;
+pub use report::*;
+pub use sandbox::*;
+pub use schedule::*;
+pub use state::*;
+pub use stats::*;
+
+use gamey_bots::{YBot, YBotRegistry};
+use gamey_core::{Coordinates, GameStatus, GameY, GameYError, Movement, reseed};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// A player of a tournament.
+pub enum Entrant {
+    /// A bot running in this process; it is trusted, so its moves are timed
+    /// but not limited.
+    Bot(Arc<dyn YBot>),
+    /// A program run for every move within the budget of the tournament.
+    Process(ProcessBot),
+}
+
+impl Entrant {
+    /// Parses an entrant given on the command line: `name=command with args`
+    /// for a process bot, or the name of one of `bots`.
+    pub fn parse(spec: &str, bots: &YBotRegistry) -> Result<Self, String> {
+        if spec.contains('=') {
+            return ProcessBot::parse(spec)
+                .map(Entrant::Process)
+                .ok_or_else(|| {
+                    format!("Expected name=command for a process bot, found '{}'", spec)
+                });
+        }
+        bots.find(spec).map(Entrant::Bot).ok_or_else(|| {
+            format!(
+                "Bot not found: {}, available bots: [{}]",
+                spec,
+                bots.names().join(", ")
+            )
+        })
+    }
+
+    /// Returns the name of the entrant.
+    pub fn name(&self) -> &str {
+        match self {
+            Entrant::Bot(bot) => bot.name(),
+            Entrant::Process(bot) => bot.name(),
+        }
+    }
+
+    /// Chooses a move in `game` and returns what it cost.
+    pub fn play(
+        &self,
+        game: &GameY,
+        budget: &Budget,
+    ) -> Result<(Coordinates, MoveUsage), Violation> {
+        match self {
+            Entrant::Bot(bot) => {
+                let start = Instant::now();
+                let coords = bot.choose_move(game).ok_or_else(|| Violation::Crashed {
+                    message: "the bot found no move".to_string(),
+                })?;
+                let elapsed = start.elapsed();
+                let usage = MoveUsage {
+                    wall_time: elapsed,
+                    cpu_time: elapsed,
+                    peak_memory_kb: None,
+                };
+                Ok((coords, usage))
+            }
+            Entrant::Process(bot) => bot.play(game, budget),
+        }
+    }
+}
+
+/// A tournament between entrants, played with [`Tournament::run`].
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    format: Format,
+    games_per_pairing: u32,
+    budget: Budget,
+    sprt: Option<Sprt>,
+}
+
+impl Tournament {
+    /// Creates a round-robin tournament in which every pairing plays two
+    /// games, one moving first each, within the default [`Budget`].
+    pub fn new(entrants: Vec<Entrant>) -> Self {
+        Tournament {
+            entrants,
+            format: Format::RoundRobin,
+            games_per_pairing: 2,
+            budget: Budget::default(),
+            sprt: None,
+        }
+    }
+
+    /// Sets how the entrants are paired.
+    pub fn with_format(mut self, format: Format) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Sets the number of games every pairing plays; who moves first
+    /// alternates. Elimination matches that end tied get a deciding game.
+    pub fn with_games_per_pairing(mut self, games: u32) -> Self {
+        self.games_per_pairing = games;
+        self
+    }
+
+    /// Sets the budget of every move of the process bots.
+    pub fn with_budget(mut self, budget: Budget) -> Self {
+        self.budget = budget;
+        self
+    }
+
+    /// Stops every match as soon as `sprt` decides whether the first entrant
+    /// of the pairing is stronger; the games per pairing are then the most
+    /// a match plays.
+    pub fn with_sprt(mut self, sprt: Sprt) -> Self {
+        self.sprt = Some(sprt);
+        self
+    }
+
+    /// Plays every round, starting each game from a game created by
+    /// `new_game`, and returns the report.
+    pub fn run(&self, new_game: impl Fn() -> GameY) -> TournamentReport {
+        self.resume(new_game, &[], |_| Ok(()))
+            .expect("there are no games to replay")
+    }
+
+    /// Goes on with a tournament of which the games in `played` were
+    /// already played, as recorded in a previous report, and returns the
+    /// report of the whole tournament.
+    ///
+    /// The recorded games are replayed in order through the schedule
+    /// instead of being played again, so the pairings, who moves first and
+    /// the standings come out as if the tournament had never stopped.
+    /// `on_game` is called with every game played after them, e.g. to save
+    /// it.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IntegrityCheckFailed` if a recorded game does
+    /// not match the schedule of this tournament, and any error returned by
+    /// `on_game`, which stops the tournament.
+    pub fn resume(
+        &self,
+        new_game: impl Fn() -> GameY,
+        played: &[GameRecord],
+        mut on_game: impl FnMut(&GameRecord) -> Result<(), GameYError>,
+    ) -> Result<TournamentReport, GameYError> {
+        let mut progress = Progress {
+            standings: self
+                .entrants
+                .iter()
+                .map(|entrant| Standing::new(entrant.name()))
+                .collect(),
+            games: Vec::new(),
+            played,
+            on_game: &mut on_game,
+        };
+        let mut opponents = vec![Vec::new(); self.entrants.len()];
+        let mut scheduler = Scheduler::new(self.format, self.entrants.len());
+        let mut rounds = 0;
+        loop {
+            let scores: Vec<u32> = progress.standings.iter().map(|s| s.wins).collect();
+            let Some(round) = scheduler.next_round(&scores) else {
+                break;
+            };
+            rounds += 1;
+            if self.format.scores_byes() {
+                for &bye in &round.byes {
+                    progress.standings[bye].wins += 1;
+                    progress.standings[bye].byes += 1;
+                }
+            }
+            for &pairing in &round.pairings {
+                let winner = self.play_match(pairing, rounds, &mut progress, &new_game)?;
+                scheduler.record_match(pairing, winner);
+                let loser = if winner == pairing[0] {
+                    pairing[1]
+                } else {
+                    pairing[0]
+                };
+                if self.format == Format::SingleElimination {
+                    progress.standings[loser].eliminated_in = Some(rounds);
+                }
+                opponents[pairing[0]].push(pairing[1]);
+                opponents[pairing[1]].push(pairing[0]);
+            }
+        }
+        if progress.games.len() < played.len() {
+            return Err(GameYError::IntegrityCheckFailed {
+                reason: format!(
+                    "{} games were recorded but the tournament has only {}",
+                    played.len(),
+                    progress.games.len()
+                ),
+            });
+        }
+        let mut standings = progress.standings;
+        for (entrant, met) in opponents.iter().enumerate() {
+            standings[entrant].buchholz = met.iter().map(|&other| standings[other].wins).sum();
+        }
+        Ok(TournamentReport {
+            format: self.format,
+            rounds,
+            budget: BudgetReport::from(&self.budget),
+            sprt: self.sprt,
+            standings,
+            games: progress.games,
+        })
+    }
+
+    /// Plays the games of a match in `round` and returns the entrant who
+    /// won more of them; ties go to the first of `pairing`, except in
+    /// elimination tournaments, which play deciding games. With a [`Sprt`],
+    /// the match stops once the test concludes.
+    ///
+    /// The entrant that moved first less often so far starts, and then they
+    /// alternate.
+    fn play_match(
+        &self,
+        pairing: [usize; 2],
+        round: u32,
+        progress: &mut Progress,
+        new_game: &impl Fn() -> GameY,
+    ) -> Result<usize, GameYError> {
+        let standings = &progress.standings;
+        let mut seats = pairing;
+        if standings[pairing[1]].games_first < standings[pairing[0]].games_first {
+            seats.swap(0, 1);
+        }
+        let mut wins = [0; 2];
+        let mut played = 0;
+        loop {
+            let tied = wins[0] == wins[1];
+            let both_out = pairing
+                .iter()
+                .all(|&e| progress.standings[e].disqualified.is_some());
+            let decide = self.format == Format::SingleElimination && tied && !both_out;
+            let stats = MatchStats {
+                wins: wins[0],
+                losses: wins[1],
+            };
+            let significant = self.sprt.is_some_and(|sprt| sprt.result(&stats).is_some());
+            if (played >= self.games_per_pairing || significant) && !decide {
+                break;
+            }
+            if let Some(winner) = self.play_game(seats, round, progress, new_game)? {
+                wins[usize::from(seats[winner] == pairing[1])] += 1;
+            }
+            seats.swap(0, 1);
+            played += 1;
+        }
+        Ok(if wins[1] > wins[0] {
+            pairing[1]
+        } else {
+            pairing[0]
+        })
+    }
+
+    /// Plays, or replays if it was recorded, one game between the entrants
+    /// at `seats`, the first of them moving for player 0, and returns the
+    /// player who won. The game is forfeited if one of them is
+    /// disqualified.
+    fn play_game(
+        &self,
+        seats: [usize; 2],
+        round: u32,
+        progress: &mut Progress,
+        new_game: &impl Fn() -> GameY,
+    ) -> Result<Option<usize>, GameYError> {
+        let players = seats.map(|seat| self.entrants[seat].name().to_string());
+        let number = progress.games.len();
+        let recorded = progress.played.get(number);
+        if let Some(recorded) = recorded
+            && (recorded.round != round || recorded.players != players)
+        {
+            return Err(GameYError::IntegrityCheckFailed {
+                reason: format!(
+                    "game {} was recorded as {} against {} in round {}, but the schedule has {} against {} in round {}",
+                    number + 1,
+                    recorded.players[0],
+                    recorded.players[1],
+                    recorded.round,
+                    players[0],
+                    players[1],
+                    round
+                ),
+            });
+        }
+        let standings = &mut progress.standings;
+        let disqualified = seats.map(|seat| standings[seat].disqualified.is_some());
+        let record = if disqualified[0] || disqualified[1] {
+            // Nobody scores when both were disqualified
+            let winner =
+                (disqualified[0] != disqualified[1]).then_some(if disqualified[0] { 1 } else { 0 });
+            GameRecord {
+                round,
+                players,
+                winner,
+                moves: 0,
+                forfeit: Some("disqualified".to_string()),
+                violation: None,
+                accounting: Default::default(),
+            }
+        } else {
+            let record = match recorded {
+                Some(recorded) => recorded.clone(),
+                None => {
+                    reseed(number as u64);
+                    self.play_out(seats, round, players, new_game)
+                }
+            };
+            standings[seats[0]].games_first += 1;
+            for (seat, accounting) in seats.iter().zip(&record.accounting) {
+                standings[*seat].accounting.merge(accounting);
+            }
+            if let (Some(winner), Some(violation)) = (record.winner, &record.violation) {
+                standings[seats[1 - winner]].disqualified = Some(violation.clone());
+            }
+            record
+        };
+        if let Some(winner) = record.winner {
+            standings[seats[winner]].wins += 1;
+            standings[seats[1 - winner]].losses += 1;
+        }
+        if recorded.is_none() {
+            (progress.on_game)(&record)?;
+        }
+        let winner = record.winner;
+        progress.games.push(record);
+        Ok(winner)
+    }
+
+    /// Plays one game between the entrants at `seats` to its end, or until
+    /// one of them breaks the rules.
+    fn play_out(
+        &self,
+        seats: [usize; 2],
+        round: u32,
+        players: [String; 2],
+        new_game: &impl Fn() -> GameY,
+    ) -> GameRecord {
+        let mut accounting: [Accounting; 2] = Default::default();
+        let mut violation = None;
+        let mut game = new_game();
+        let winner = loop {
+            let player = match game.status() {
+                GameStatus::Ongoing { next_player } => *next_player,
+                GameStatus::Finished { winner } => break winner.id() as usize,
+            };
+            let id = player.id() as usize;
+            let entrant = &self.entrants[seats[id]];
+            if let Err(broken) = self.play_move(entrant, &mut game, &mut accounting[id]) {
+                violation = Some(broken);
+                break 1 - id;
+            }
+        };
+        let forfeit = violation.as_ref().map(|violation| {
+            let loser = &self.entrants[seats[1 - winner]];
+            format!("{} {}", loser.name(), violation)
+        });
+        GameRecord {
+            round,
+            players,
+            winner: Some(winner),
+            moves: game.move_count(),
+            forfeit,
+            violation,
+            accounting,
+        }
+    }
+
+    /// Lets `entrant` play the next move of `game`, adding what it cost to
+    /// `accounting`, illegal moves included.
+    fn play_move(
+        &self,
+        entrant: &Entrant,
+        game: &mut GameY,
+        accounting: &mut Accounting,
+    ) -> Result<(), Violation> {
+        let player = game.next_player().expect("the game is ongoing");
+        let (coords, usage) = entrant.play(game, &self.budget)?;
+        accounting.record(&usage);
+        game.add_move(Movement::Placement { player, coords })
+            .map_err(|e| Violation::IllegalMove {
+                coords,
+                message: e.to_string(),
+            })
+    }
+}
+
+/// The state of a tournament being played.
+struct Progress<'a> {
+    standings: Vec<Standing>,
+    games: Vec<GameRecord>,
+    /// The games recorded before the tournament was resumed.
+    played: &'a [GameRecord],
+    /// Called with every game played, not replayed.
+    on_game: &'a mut dyn FnMut(&GameRecord) -> Result<(), GameYError>,
+}
+
+/// Keeps the tests that install a [`DeterministicConfig`], which is global
+/// to the process, from running at the same time.
+///
+/// [`DeterministicConfig`]: gamey_core::DeterministicConfig
+#[cfg(test)]
+fn lock_determinism() -> std::sync::MutexGuard<'static, ()> {
+    static LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    LOCK.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::RandomBot;
+
+    fn random_bot() -> Entrant {
+        Entrant::Bot(Arc::new(RandomBot))
+    }
+
+    #[test]
+    fn test_every_pair_plays() {
+        let tournament = Tournament::new(vec![random_bot(), random_bot(), random_bot()])
+            .with_games_per_pairing(4);
+        let report = tournament.run(|| GameY::new(4));
+        assert_eq!(report.games.len(), 12);
+        let wins: u32 = report.standings.iter().map(|s| s.wins).sum();
+        assert_eq!(wins, 12);
+        assert!(report.standings.iter().all(|s| s.wins + s.losses == 8));
+        assert!(report.games.iter().all(|g| g.forfeit.is_none()));
+    }
+
+    #[test]
+    fn test_seeded_tournaments_are_replayed_exactly() {
+        let _lock = crate::lock_determinism();
+        let _config = gamey_core::DeterministicConfig::new(11).install();
+        let tournament =
+            Tournament::new(vec![random_bot(), random_bot()]).with_games_per_pairing(8);
+        let outcomes = |report: &TournamentReport| -> Vec<(Option<usize>, usize)> {
+            report.games.iter().map(|g| (g.winner, g.moves)).collect()
+        };
+        let first = tournament.run(|| GameY::new(6));
+        assert_eq!(
+            outcomes(&tournament.run(|| GameY::new(6))),
+            outcomes(&first)
+        );
+        let resumed = tournament
+            .resume(|| GameY::new(6), &first.games[..3], |_| Ok(()))
+            .unwrap();
+        assert_eq!(outcomes(&resumed), outcomes(&first));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_violations_disqualify() {
+        let slow = ProcessBot::new("slow_bot", "sh", &["-c", "sleep 5"]);
+        let budget = Budget {
+            wall_time: std::time::Duration::from_millis(100),
+            ..Budget::default()
+        };
+        let report = Tournament::new(vec![random_bot(), Entrant::Process(slow)])
+            .with_games_per_pairing(3)
+            .with_budget(budget)
+            .run(|| GameY::new(3));
+        let slow = &report.standings[1];
+        assert_eq!(
+            slow.disqualified,
+            Some(Violation::WallTime { limit_ms: 100 })
+        );
+        assert_eq!((slow.wins, slow.losses), (0, 3));
+        assert_eq!(report.games[2].forfeit.as_deref(), Some("disqualified"));
+        assert_eq!(report.ranking()[0].name, "random_bot");
+        assert!(report.to_string().contains("slow_bot was disqualified"));
+    }
+
+    #[test]
+    fn test_formats() {
+        let entrants = || (0..5).map(|_| random_bot()).collect();
+        let swiss = Tournament::new(entrants())
+            .with_format(Format::Swiss { rounds: 3 })
+            .with_games_per_pairing(1)
+            .run(|| GameY::new(3));
+        assert_eq!(swiss.rounds, 3);
+        assert_eq!(swiss.games.len(), 6);
+        let byes: u32 = swiss.standings.iter().map(|s| s.byes).sum();
+        assert_eq!(byes, 3);
+
+        let elimination = Tournament::new(entrants())
+            .with_format(Format::SingleElimination)
+            .with_games_per_pairing(2)
+            .run(|| GameY::new(3));
+        assert_eq!(elimination.rounds, 3);
+        let champions: Vec<_> = elimination
+            .standings
+            .iter()
+            .filter(|s| s.eliminated_in.is_none())
+            .collect();
+        assert_eq!(champions.len(), 1);
+        assert_eq!(elimination.ranking()[0], champions[0]);
+    }
+
+    #[test]
+    fn test_sprt_stops_matches_early() {
+        let report = Tournament::new(vec![random_bot(), random_bot()])
+            .with_games_per_pairing(1000)
+            .with_sprt(Sprt::new(0.0, 400.0))
+            .run(|| GameY::new(3));
+        assert!(report.games.len() < 1000);
+        let stats = report.comparison().unwrap();
+        assert_eq!(stats.games() as usize, report.games.len());
+        assert!(report.sprt.unwrap().result(&stats).is_some());
+        assert!(report.to_string().contains("SPRT (elo0=0,elo1=400"));
+    }
+
+    #[test]
+    fn test_who_moves_first_is_balanced() {
+        let entrants = (0..4).map(|_| random_bot()).collect();
+        let report = Tournament::new(entrants)
+            .with_games_per_pairing(1)
+            .run(|| GameY::new(3));
+        let mut first: Vec<u32> = report.standings.iter().map(|s| s.games_first).collect();
+        first.sort();
+        assert_eq!(first, vec![1, 1, 2, 2]);
+    }
+
+    #[test]
+    fn test_parse_entrants() {
+        let bots = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        assert_eq!(
+            Entrant::parse("random_bot", &bots).unwrap().name(),
+            "random_bot"
+        );
+        assert_eq!(Entrant::parse("mine=./bot", &bots).unwrap().name(), "mine");
+        assert!(Entrant::parse("no_such_bot", &bots).is_err());
+        assert!(Entrant::parse("=./bot", &bots).is_err());
+    }
+}
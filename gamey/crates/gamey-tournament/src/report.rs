@@ -0,0 +1,278 @@
+//! The results of a tournament.
+//!
+//! A [`TournamentReport`] prints as a table, serializes to JSON with every
+//! game, and exports its standings as CSV for spreadsheets (see
+//! [`TournamentReport::standings_csv`]).
+
+use crate::{Accounting, Budget, Format, MatchStats, Sprt, Violation};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::fmt::Display;
+use std::time::Duration;
+
+/// The results of one entrant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Standing {
+    /// The name of the entrant.
+    pub name: String,
+    /// Games won, including forfeits and byes.
+    pub wins: u32,
+    /// Games lost, including forfeits.
+    pub losses: u32,
+    /// Rounds without an opponent that counted as a win.
+    pub byes: u32,
+    /// Games played moving for the first player.
+    pub games_first: u32,
+    /// The sum of the wins of the opponents met, to break ties.
+    pub buchholz: u32,
+    /// The round in which the entrant was knocked out of an elimination
+    /// tournament.
+    pub eliminated_in: Option<u32>,
+    /// Why the entrant was disqualified, if it was.
+    pub disqualified: Option<Violation>,
+    /// What its moves cost.
+    pub accounting: Accounting,
+}
+
+impl Standing {
+    pub(crate) fn new(name: &str) -> Self {
+        Standing {
+            name: name.to_string(),
+            wins: 0,
+            losses: 0,
+            byes: 0,
+            games_first: 0,
+            buchholz: 0,
+            eliminated_in: None,
+            disqualified: None,
+            accounting: Accounting::default(),
+        }
+    }
+}
+
+/// The result of one game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// The round the game belongs to, from 1.
+    pub round: u32,
+    /// The entrants playing player 0 and player 1.
+    pub players: [String; 2],
+    /// The player who won, 0 or 1, if anybody did.
+    pub winner: Option<usize>,
+    /// The number of moves played.
+    pub moves: usize,
+    /// Why the game was forfeited, if it was.
+    pub forfeit: Option<String>,
+    /// The rule broken by the loser, which disqualified it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub violation: Option<Violation>,
+    /// What the moves of player 0 and player 1 cost in this game.
+    #[serde(default)]
+    pub accounting: [Accounting; 2],
+}
+
+/// The budget of a tournament, in the units of its report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetReport {
+    /// CPU time per move, in milliseconds.
+    pub cpu_ms: u64,
+    /// Wall time per move, in milliseconds.
+    pub wall_ms: u64,
+    /// Peak resident memory per move, in megabytes.
+    pub memory_mb: u64,
+}
+
+impl From<&Budget> for BudgetReport {
+    fn from(budget: &Budget) -> Self {
+        BudgetReport {
+            cpu_ms: budget.cpu_time.as_millis() as u64,
+            wall_ms: budget.wall_time.as_millis() as u64,
+            memory_mb: budget.memory_mb,
+        }
+    }
+}
+
+impl From<&BudgetReport> for Budget {
+    fn from(budget: &BudgetReport) -> Self {
+        Budget {
+            cpu_time: Duration::from_millis(budget.cpu_ms),
+            wall_time: Duration::from_millis(budget.wall_ms),
+            memory_mb: budget.memory_mb,
+        }
+    }
+}
+
+/// The results of a tournament.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TournamentReport {
+    /// How the entrants were paired.
+    pub format: Format,
+    /// The number of rounds played.
+    pub rounds: u32,
+    /// The budget of every move of the process bots.
+    pub budget: BudgetReport,
+    /// The test that stopped matches early, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprt: Option<Sprt>,
+    /// The results of every entrant, in the order they entered.
+    pub standings: Vec<Standing>,
+    /// Every game, in the order they were played.
+    pub games: Vec<GameRecord>,
+}
+
+impl TournamentReport {
+    /// Returns the standings from first to last: by the round they were
+    /// knocked out in, in elimination tournaments, then by wins and by
+    /// Buchholz score. Disqualified entrants come last.
+    pub fn ranking(&self) -> Vec<&Standing> {
+        let mut ranking: Vec<&Standing> = self.standings.iter().collect();
+        ranking.sort_by_key(|s| {
+            (
+                s.disqualified.is_some(),
+                Reverse(s.eliminated_in.unwrap_or(u32::MAX)),
+                Reverse(s.wins),
+                Reverse(s.buchholz),
+            )
+        });
+        ranking
+    }
+
+    /// Returns the wins and losses of the first entrant against the second
+    /// when the tournament was a match between two entrants.
+    pub fn comparison(&self) -> Option<MatchStats> {
+        match self.standings.as_slice() {
+            [first, _] => Some(MatchStats {
+                wins: first.wins,
+                losses: first.losses,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Returns the ranking as CSV, with a header line.
+    pub fn standings_csv(&self) -> String {
+        let mut csv = String::from(
+            "rank,name,wins,losses,byes,games_first,buchholz,eliminated_in,moves,cpu_ms,max_move_cpu_ms,peak_memory_kb,disqualified\n",
+        );
+        for (rank, standing) in self.ranking().into_iter().enumerate() {
+            let accounting = &standing.accounting;
+            let optional = |value: Option<u64>| value.map_or(String::new(), |v| v.to_string());
+            let disqualified = standing
+                .disqualified
+                .as_ref()
+                .map_or(String::new(), |v| v.to_string());
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{},{},{},{},{},{}\n",
+                rank + 1,
+                csv_field(&standing.name),
+                standing.wins,
+                standing.losses,
+                standing.byes,
+                standing.games_first,
+                standing.buchholz,
+                optional(standing.eliminated_in.map(u64::from)),
+                accounting.moves,
+                accounting.cpu_ms,
+                accounting.max_move_cpu_ms,
+                optional(accounting.peak_memory_kb),
+                csv_field(&disqualified)
+            ));
+        }
+        csv
+    }
+}
+
+/// Quotes a CSV field when it holds a separator, a quote or a line break.
+fn csv_field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+impl Display for TournamentReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Format: {}, {} rounds played", self.format, self.rounds)?;
+        writeln!(
+            f,
+            "{:<20} {:>5} {:>6} {:>5} {:>6} {:>10} {:>10} {:>10}",
+            "Bot", "Wins", "Losses", "First", "Moves", "CPU/move", "Max CPU", "Peak mem"
+        )?;
+        for standing in self.ranking() {
+            let accounting = &standing.accounting;
+            let memory = accounting
+                .peak_memory_kb
+                .map_or("-".to_string(), |kb| format!("{} MB", kb.div_ceil(1024)));
+            writeln!(
+                f,
+                "{:<20} {:>5} {:>6} {:>5} {:>6} {:>7} ms {:>7} ms {:>10}",
+                standing.name,
+                standing.wins,
+                standing.losses,
+                standing.games_first,
+                accounting.moves,
+                accounting.mean_move_cpu_ms(),
+                accounting.max_move_cpu_ms,
+                memory
+            )?;
+        }
+        for standing in &self.standings {
+            if let Some(violation) = &standing.disqualified {
+                writeln!(f, "{} was disqualified: it {}", standing.name, violation)?;
+            }
+        }
+        if let Some(stats) = self.comparison() {
+            let [first, second] = [&self.standings[0].name, &self.standings[1].name];
+            writeln!(f, "{} vs {}: {}", first, second, stats)?;
+            if let Some(sprt) = &self.sprt {
+                let (lower, upper) = sprt.bounds();
+                let result = sprt
+                    .result(&stats)
+                    .map_or("inconclusive".to_string(), |result| result.to_string());
+                writeln!(
+                    f,
+                    "SPRT ({}): LLR {:.2} [{:.2}, {:.2}], {}",
+                    sprt,
+                    sprt.llr(&stats),
+                    lower,
+                    upper,
+                    result
+                )?;
+            }
+        }
+        write!(
+            f,
+            "Budget per move: {} ms CPU, {} ms wall time, {} MB",
+            self.budget.cpu_ms, self.budget.wall_ms, self.budget.memory_mb
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_standings_csv() {
+        let mut winner = Standing::new("mcts, tuned");
+        winner.wins = 2;
+        let mut loser = Standing::new("random_bot");
+        loser.losses = 2;
+        loser.disqualified = Some(Violation::WallTime { limit_ms: 100 });
+        let report = TournamentReport {
+            format: Format::RoundRobin,
+            rounds: 1,
+            budget: BudgetReport::from(&Budget::default()),
+            sprt: None,
+            standings: vec![loser, winner],
+            games: Vec::new(),
+        };
+        let csv = report.standings_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("rank,name,wins"));
+        assert_eq!(lines[1], "1,\"mcts, tuned\",2,0,0,0,0,,0,0,0,,");
+        assert!(lines[2].ends_with(",did not answer within 100 ms"));
+    }
+}
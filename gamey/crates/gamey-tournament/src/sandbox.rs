@@ -0,0 +1,448 @@
+//! Bots running as separate processes, with per-move resource accounting.
+//!
+//! A [`ProcessBot`] is a program written in any language: for every move it
+//! is started anew, reads the position as YEN JSON on stdin and writes the
+//! coordinates of its move as JSON on stdout, e.g. `{"x":2,"y":0,"z":0}`.
+//!
+//! Running each move in its own process lets the tournament measure what it
+//! costs and stop it when it goes over its [`Budget`]: the process is killed
+//! once its wall time runs out, or on Linux as soon as its resident memory
+//! grows past the limit, and its CPU time and peak memory are checked when
+//! it exits. On Unix the CPU time and peak memory come from `wait4`; on
+//! other platforms only the wall time is known.
+
+use gamey_core::{Coordinates, GameY, YEN};
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::mpsc;
+use std::time::{Duration, Instant};
+
+/// How often a running process is checked.
+const POLL_INTERVAL: Duration = Duration::from_millis(2);
+
+/// The resources a bot may use for one move.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Budget {
+    /// CPU time, user and system, of the process.
+    pub cpu_time: Duration,
+    /// Time from starting the process until it answers, including start-up.
+    pub wall_time: Duration,
+    /// Peak resident memory of the process, in megabytes.
+    pub memory_mb: u64,
+}
+
+impl Budget {
+    /// Creates a budget of `cpu_time` per move and `memory_mb` megabytes,
+    /// with twice the CPU time, plus one second to start, as wall time.
+    pub fn new(cpu_time: Duration, memory_mb: u64) -> Self {
+        Budget {
+            cpu_time,
+            wall_time: cpu_time * 2 + Duration::from_secs(1),
+            memory_mb,
+        }
+    }
+}
+
+impl Default for Budget {
+    /// One second of CPU time and 512 MB per move.
+    fn default() -> Self {
+        Budget::new(Duration::from_secs(1), 512)
+    }
+}
+
+/// Why a bot lost a game and was disqualified.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Violation {
+    /// The move took more CPU time than the budget.
+    #[error("used {used_ms} ms of CPU time, more than the {limit_ms} ms allowed")]
+    CpuTime {
+        /// CPU time used, in milliseconds.
+        used_ms: u64,
+        /// CPU time allowed, in milliseconds.
+        limit_ms: u64,
+    },
+    /// The bot did not answer within the wall time of the budget.
+    #[error("did not answer within {limit_ms} ms")]
+    WallTime {
+        /// Wall time allowed, in milliseconds.
+        limit_ms: u64,
+    },
+    /// The process used more memory than the budget.
+    #[error("used {used_mb} MB of memory, more than the {limit_mb} MB allowed")]
+    Memory {
+        /// Peak resident memory, in megabytes.
+        used_mb: u64,
+        /// Memory allowed, in megabytes.
+        limit_mb: u64,
+    },
+    /// The bot could not be run or failed without answering.
+    #[error("failed: {message}")]
+    Crashed {
+        /// What went wrong.
+        message: String,
+    },
+    /// The bot answered something that is not a move.
+    #[error("answered {answer:?}, which is not a move")]
+    InvalidAnswer {
+        /// The answer, trimmed.
+        answer: String,
+    },
+    /// The bot chose a move the rules do not allow.
+    #[error("played the illegal move {coords}: {message}")]
+    IllegalMove {
+        /// The move chosen.
+        coords: Coordinates,
+        /// Why the engine rejected it.
+        message: String,
+    },
+}
+
+/// What one move cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MoveUsage {
+    /// Time from starting the bot until it answered.
+    pub wall_time: Duration,
+    /// CPU time used, when known; the wall time for bots in this process.
+    pub cpu_time: Duration,
+    /// Peak resident memory in kilobytes, when known.
+    pub peak_memory_kb: Option<u64>,
+}
+
+/// What the moves of one bot cost over a tournament.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Accounting {
+    /// Moves answered, illegal ones included.
+    pub moves: u64,
+    /// Total CPU time, in milliseconds.
+    pub cpu_ms: u64,
+    /// CPU time of the most expensive move, in milliseconds.
+    pub max_move_cpu_ms: u64,
+    /// Total wall time, in milliseconds.
+    pub wall_ms: u64,
+    /// Peak resident memory of any move in kilobytes, when known.
+    pub peak_memory_kb: Option<u64>,
+}
+
+impl Accounting {
+    /// Adds the cost of one move.
+    pub fn record(&mut self, usage: &MoveUsage) {
+        let cpu_ms = usage.cpu_time.as_millis() as u64;
+        self.moves += 1;
+        self.cpu_ms += cpu_ms;
+        self.max_move_cpu_ms = self.max_move_cpu_ms.max(cpu_ms);
+        self.wall_ms += usage.wall_time.as_millis() as u64;
+        if let Some(kb) = usage.peak_memory_kb {
+            self.peak_memory_kb = Some(self.peak_memory_kb.map_or(kb, |peak| peak.max(kb)));
+        }
+    }
+
+    /// Adds the costs counted by `other`.
+    pub fn merge(&mut self, other: &Accounting) {
+        self.moves += other.moves;
+        self.cpu_ms += other.cpu_ms;
+        self.max_move_cpu_ms = self.max_move_cpu_ms.max(other.max_move_cpu_ms);
+        self.wall_ms += other.wall_ms;
+        self.peak_memory_kb = self.peak_memory_kb.max(other.peak_memory_kb);
+    }
+
+    /// Returns the average CPU time per move, in milliseconds.
+    pub fn mean_move_cpu_ms(&self) -> u64 {
+        self.cpu_ms.checked_div(self.moves).unwrap_or(0)
+    }
+}
+
+/// A bot run as a separate process for every move, see the [module
+/// documentation](self) for its protocol.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessBot {
+    name: String,
+    program: String,
+    args: Vec<String>,
+}
+
+impl ProcessBot {
+    /// Creates a bot called `name` that runs `program` with `args`.
+    pub fn new(name: &str, program: &str, args: &[&str]) -> Self {
+        ProcessBot {
+            name: name.to_string(),
+            program: program.to_string(),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    /// Parses `name=command with args`, splitting the command on spaces.
+    pub fn parse(spec: &str) -> Option<Self> {
+        let (name, command) = spec.split_once('=')?;
+        let mut words = command.split_whitespace();
+        let program = words.next()?;
+        let args: Vec<&str> = words.collect();
+        let name = name.trim();
+        (!name.is_empty()).then(|| ProcessBot::new(name, program, &args))
+    }
+
+    /// Returns the name of the bot.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Runs the bot on `game` within `budget` and returns its move with
+    /// what it cost.
+    ///
+    /// The move is not checked against the rules; see
+    /// [`Violation::IllegalMove`].
+    pub fn play(
+        &self,
+        game: &GameY,
+        budget: &Budget,
+    ) -> Result<(Coordinates, MoveUsage), Violation> {
+        let input = serde_json::to_string(&YEN::from(game)).map_err(crashed)?;
+        let start = Instant::now();
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|e| crashed(format!("cannot run {}: {}", self.program, e)))?;
+        // A bot that does not read its input must not block the tournament
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+        let mut stdout = child.stdout.take().expect("stdout is piped");
+        let (sender, output) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut text = String::new();
+            let _ = stdout.read_to_string(&mut text);
+            let _ = sender.send(text);
+        });
+
+        let exit = wait_within(&mut child, budget, start)?;
+        let usage = MoveUsage {
+            wall_time: start.elapsed(),
+            cpu_time: exit.cpu_time.unwrap_or(start.elapsed()),
+            peak_memory_kb: exit.peak_memory_kb,
+        };
+        check_usage(&usage, budget)?;
+        if !exit.success {
+            return Err(crashed("the process exited with an error"));
+        }
+        // The output is complete once the process exits, unless it left
+        // children holding the pipe
+        let answer = output
+            .recv_timeout(Duration::from_millis(100))
+            .unwrap_or_default();
+        let coords = serde_json::from_str(answer.trim()).map_err(|_| Violation::InvalidAnswer {
+            answer: answer.trim().to_string(),
+        })?;
+        Ok((coords, usage))
+    }
+}
+
+/// How a process ended.
+struct Exit {
+    success: bool,
+    cpu_time: Option<Duration>,
+    peak_memory_kb: Option<u64>,
+}
+
+fn crashed(message: impl ToString) -> Violation {
+    Violation::Crashed {
+        message: message.to_string(),
+    }
+}
+
+/// Checks what an answered move cost against the budget.
+fn check_usage(usage: &MoveUsage, budget: &Budget) -> Result<(), Violation> {
+    if usage.cpu_time > budget.cpu_time {
+        return Err(Violation::CpuTime {
+            used_ms: usage.cpu_time.as_millis() as u64,
+            limit_ms: budget.cpu_time.as_millis() as u64,
+        });
+    }
+    match usage.peak_memory_kb {
+        Some(kb) if kb > budget.memory_mb * 1024 => Err(Violation::Memory {
+            used_mb: kb / 1024,
+            limit_mb: budget.memory_mb,
+        }),
+        _ => Ok(()),
+    }
+}
+
+/// Waits for `child` to exit, killing it when it runs out of wall time or,
+/// where it can be sampled, memory.
+#[cfg(unix)]
+fn wait_within(child: &mut Child, budget: &Budget, start: Instant) -> Result<Exit, Violation> {
+    let pid = child.id() as libc::pid_t;
+    loop {
+        let mut status = 0;
+        // SAFETY: rusage is plain data, for which all zeroes is valid
+        let mut usage: libc::rusage = unsafe { std::mem::zeroed() };
+        // SAFETY: pid is our unreaped child and the pointers are valid
+        let waited = unsafe { libc::wait4(pid, &mut status, libc::WNOHANG, &mut usage) };
+        if waited == pid {
+            return Ok(Exit {
+                success: libc::WIFEXITED(status) && libc::WEXITSTATUS(status) == 0,
+                cpu_time: Some(timeval(usage.ru_utime) + timeval(usage.ru_stime)),
+                peak_memory_kb: Some(max_rss_kb(&usage)),
+            });
+        }
+        if waited < 0 {
+            return Err(crashed(std::io::Error::last_os_error()));
+        }
+        let violation = if start.elapsed() > budget.wall_time {
+            Some(Violation::WallTime {
+                limit_ms: budget.wall_time.as_millis() as u64,
+            })
+        } else {
+            resident_kb(pid)
+                .filter(|kb| *kb > budget.memory_mb * 1024)
+                .map(|kb| Violation::Memory {
+                    used_mb: kb / 1024,
+                    limit_mb: budget.memory_mb,
+                })
+        };
+        if let Some(violation) = violation {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(violation);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Waits for `child` to exit, killing it when it runs out of wall time.
+#[cfg(not(unix))]
+fn wait_within(child: &mut Child, budget: &Budget, start: Instant) -> Result<Exit, Violation> {
+    loop {
+        if let Some(status) = child.try_wait().map_err(crashed)? {
+            return Ok(Exit {
+                success: status.success(),
+                cpu_time: None,
+                peak_memory_kb: None,
+            });
+        }
+        if start.elapsed() > budget.wall_time {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Violation::WallTime {
+                limit_ms: budget.wall_time.as_millis() as u64,
+            });
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(unix)]
+fn timeval(time: libc::timeval) -> Duration {
+    Duration::from_secs(time.tv_sec as u64) + Duration::from_micros(time.tv_usec as u64)
+}
+
+/// Returns the peak resident memory reported by `wait4`, in kilobytes.
+#[cfg(unix)]
+fn max_rss_kb(usage: &libc::rusage) -> u64 {
+    let max_rss = usage.ru_maxrss as u64;
+    // macOS reports bytes, the other Unix systems kilobytes
+    if cfg!(target_os = "macos") {
+        max_rss / 1024
+    } else {
+        max_rss
+    }
+}
+
+/// Returns the current resident memory of a running process in kilobytes,
+/// where the platform makes it cheap to read.
+#[cfg(unix)]
+fn resident_kb(pid: libc::pid_t) -> Option<u64> {
+    let status = std::fs::read_to_string(format!("/proc/{}/status", pid)).ok()?;
+    let line = status.lines().find(|line| line.starts_with("VmRSS:"))?;
+    line.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    /// A bot that runs `script` with `sh`.
+    fn shell_bot(script: &str) -> ProcessBot {
+        ProcessBot::new("shell_bot", "sh", &["-c", script])
+    }
+
+    #[test]
+    fn test_parse_process_bot() {
+        let bot = ProcessBot::parse("mine=python3 bot.py --fast").unwrap();
+        assert_eq!(
+            bot,
+            ProcessBot::new("mine", "python3", &["bot.py", "--fast"])
+        );
+        assert_eq!(ProcessBot::parse("python3 bot.py"), None);
+        assert_eq!(ProcessBot::parse("mine="), None);
+    }
+
+    #[test]
+    fn test_process_bot_answers() {
+        let bot = shell_bot(r#"cat > /dev/null; echo '{"x":2,"y":0,"z":0}'"#);
+        let (coords, usage) = bot.play(&GameY::new(3), &Budget::default()).unwrap();
+        assert_eq!(coords, Coordinates::new(2, 0, 0));
+        assert!(usage.peak_memory_kb.is_some());
+    }
+
+    #[test]
+    fn test_process_bot_violations() {
+        let game = GameY::new(3);
+        let budget = Budget {
+            wall_time: Duration::from_millis(200),
+            ..Budget::default()
+        };
+        let slow = shell_bot("sleep 5");
+        assert_eq!(
+            slow.play(&game, &budget).unwrap_err(),
+            Violation::WallTime { limit_ms: 200 }
+        );
+
+        let busy = shell_bot(
+            r#"i=0; while [ $i -lt 200000 ]; do i=$((i+1)); done; echo '{"x":2,"y":0,"z":0}'"#,
+        );
+        let budget = Budget {
+            cpu_time: Duration::from_millis(1),
+            ..Budget::default()
+        };
+        assert!(matches!(
+            busy.play(&game, &budget),
+            Err(Violation::CpuTime { limit_ms: 1, .. })
+        ));
+
+        let chatty = shell_bot("echo hello");
+        assert_eq!(
+            chatty.play(&game, &Budget::default()).unwrap_err(),
+            Violation::InvalidAnswer {
+                answer: "hello".to_string()
+            }
+        );
+        let failing = shell_bot("exit 3");
+        assert!(matches!(
+            failing.play(&game, &Budget::default()),
+            Err(Violation::Crashed { .. })
+        ));
+    }
+
+    #[test]
+    fn test_accounting() {
+        let mut accounting = Accounting::default();
+        accounting.record(&MoveUsage {
+            wall_time: Duration::from_millis(30),
+            cpu_time: Duration::from_millis(20),
+            peak_memory_kb: Some(1000),
+        });
+        accounting.record(&MoveUsage {
+            wall_time: Duration::from_millis(10),
+            cpu_time: Duration::from_millis(40),
+            peak_memory_kb: None,
+        });
+        assert_eq!(accounting.moves, 2);
+        assert_eq!(accounting.cpu_ms, 60);
+        assert_eq!(accounting.max_move_cpu_ms, 40);
+        assert_eq!(accounting.mean_move_cpu_ms(), 30);
+        assert_eq!(accounting.peak_memory_kb, Some(1000));
+    }
+}
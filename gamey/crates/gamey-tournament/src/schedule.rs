@@ -0,0 +1,300 @@
+//! Pairing formats of tournaments.
+//!
+//! A [`Scheduler`] hands out the pairings of a tournament one round at a
+//! time, since Swiss and elimination pairings depend on the results of the
+//! rounds before:
+//! - [`Format::RoundRobin`] pairs everybody with everybody once, with the
+//!   circle method so that every entrant plays once per round,
+//! - [`Format::Swiss`] pairs entrants with similar scores who have not met
+//!   yet, for a fixed number of rounds,
+//! - [`Format::SingleElimination`] reseeds the entrants still in every
+//!   round, the best seed against the worst, until one is left.
+//!
+//! Who moves first is decided when the games are played, see
+//! [`Tournament`](crate::Tournament).
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// How the entrants of a tournament are paired.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "snake_case")]
+pub enum Format {
+    /// Everybody plays everybody.
+    RoundRobin,
+    /// Entrants with similar scores play each other for some rounds.
+    Swiss {
+        /// The number of rounds.
+        rounds: u32,
+    },
+    /// Losers of a match are out, until one entrant is left.
+    SingleElimination,
+}
+
+impl Format {
+    /// Returns the Swiss format with enough rounds to tell a single winner
+    /// apart among `entrants`: the base 2 logarithm, rounded up.
+    pub fn swiss_for(entrants: usize) -> Self {
+        let rounds = entrants.max(2).next_power_of_two().trailing_zeros();
+        Format::Swiss { rounds }
+    }
+
+    /// Returns true if an entrant left without an opponent for a round wins
+    /// a game, as in Swiss tournaments; in elimination it goes through to
+    /// the next round instead.
+    pub fn scores_byes(&self) -> bool {
+        matches!(self, Format::Swiss { .. })
+    }
+}
+
+impl Display for Format {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Format::RoundRobin => write!(f, "round-robin"),
+            Format::Swiss { rounds } => write!(f, "swiss ({} rounds)", rounds),
+            Format::SingleElimination => write!(f, "single elimination"),
+        }
+    }
+}
+
+/// The pairings of one round, as indices of the entrants.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Round {
+    /// The pairs of entrants playing each other.
+    pub pairings: Vec<[usize; 2]>,
+    /// The entrants left without an opponent.
+    pub byes: Vec<usize>,
+}
+
+/// Hands out the rounds of a tournament in a [`Format`].
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    format: Format,
+    entrants: usize,
+    rounds_played: u32,
+    /// Whether each pair of entrants has met.
+    met: Vec<Vec<bool>>,
+    /// Whether each entrant had a bye.
+    had_bye: Vec<bool>,
+    /// The entrants still in an elimination tournament, by seed.
+    alive: Vec<usize>,
+}
+
+impl Scheduler {
+    /// Creates the scheduler of a tournament between `entrants`, seeded in
+    /// their order.
+    pub fn new(format: Format, entrants: usize) -> Self {
+        Scheduler {
+            format,
+            entrants,
+            rounds_played: 0,
+            met: vec![vec![false; entrants]; entrants],
+            had_bye: vec![false; entrants],
+            alive: (0..entrants).collect(),
+        }
+    }
+
+    /// Returns the format of the tournament.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the pairings of the next round, or `None` once the
+    /// tournament is over.
+    ///
+    /// `scores` holds the points of every entrant so far, used to pair
+    /// Swiss rounds.
+    pub fn next_round(&mut self, scores: &[u32]) -> Option<Round> {
+        let round = match self.format {
+            Format::RoundRobin => self.round_robin()?,
+            Format::Swiss { rounds } if self.rounds_played < rounds => self.swiss(scores),
+            Format::Swiss { .. } => return None,
+            Format::SingleElimination => self.elimination()?,
+        };
+        for &[a, b] in &round.pairings {
+            self.met[a][b] = true;
+            self.met[b][a] = true;
+        }
+        for &bye in &round.byes {
+            self.had_bye[bye] = true;
+        }
+        self.rounds_played += 1;
+        Some(round)
+    }
+
+    /// Records the winner of a match, needed to go on with elimination
+    /// tournaments.
+    pub fn record_match(&mut self, pairing: [usize; 2], winner: usize) {
+        if self.format == Format::SingleElimination {
+            let loser = if winner == pairing[0] {
+                pairing[1]
+            } else {
+                pairing[0]
+            };
+            self.alive.retain(|&entrant| entrant != loser);
+        }
+    }
+
+    /// The next round of the circle method: the first entrant stays put and
+    /// the others rotate, with a bye in place of the missing entrant when
+    /// their number is odd.
+    fn round_robin(&self) -> Option<Round> {
+        let slots = self.entrants + self.entrants % 2;
+        if self.entrants < 2 || self.rounds_played as usize >= slots - 1 {
+            return None;
+        }
+        let rotation = self.rounds_played as usize;
+        let others = slots - 1;
+        let at = |slot: usize| match slot {
+            0 => 0,
+            _ => 1 + (slot - 1 + rotation) % others,
+        };
+        let mut round = Round::default();
+        for slot in 0..slots / 2 {
+            let (a, b) = (at(slot), at(slots - 1 - slot));
+            match (a < self.entrants, b < self.entrants) {
+                (true, true) => round.pairings.push([a, b]),
+                (true, false) => round.byes.push(a),
+                (false, true) => round.byes.push(b),
+                (false, false) => {}
+            }
+        }
+        Some(round)
+    }
+
+    /// Pairs every entrant, from the highest score down, with the best
+    /// placed entrant it has not met, if the others can then be paired
+    /// without rematches too; the lowest placed entrant without a bye yet
+    /// gets one when their number is odd.
+    fn swiss(&self, scores: &[u32]) -> Round {
+        let mut order: Vec<usize> = (0..self.entrants).collect();
+        order.sort_by_key(|&entrant| std::cmp::Reverse(scores[entrant]));
+        let mut round = Round::default();
+        if order.len() % 2 == 1 {
+            let bye = order
+                .iter()
+                .rposition(|&entrant| !self.had_bye[entrant])
+                .unwrap_or(order.len() - 1);
+            round.byes.push(order.remove(bye));
+        }
+        round.pairings = self.pair_without_rematches(&order).unwrap_or_else(|| {
+            // Everybody met already: pair in order
+            order.chunks(2).map(|pair| [pair[0], pair[1]]).collect()
+        });
+        round
+    }
+
+    /// Pairs `order`, the best placed first, so that nobody meets again,
+    /// or returns `None` if that is impossible.
+    fn pair_without_rematches(&self, order: &[usize]) -> Option<Vec<[usize; 2]>> {
+        let Some((&first, rest)) = order.split_first() else {
+            return Some(Vec::new());
+        };
+        rest.iter().enumerate().find_map(|(i, &opponent)| {
+            if self.met[first][opponent] {
+                return None;
+            }
+            let mut others = rest.to_vec();
+            others.remove(i);
+            let mut pairings = self.pair_without_rematches(&others)?;
+            pairings.insert(0, [first, opponent]);
+            Some(pairings)
+        })
+    }
+
+    /// Pairs the best seed still in with the worst, the top seeds getting
+    /// byes in the first round so that the later rounds are full.
+    fn elimination(&self) -> Option<Round> {
+        if self.alive.len() < 2 {
+            return None;
+        }
+        let byes = self.alive.len().next_power_of_two() - self.alive.len();
+        let mut round = Round {
+            byes: self.alive[..byes].to_vec(),
+            ..Round::default()
+        };
+        let playing = &self.alive[byes..];
+        for i in 0..playing.len() / 2 {
+            round
+                .pairings
+                .push([playing[i], playing[playing.len() - 1 - i]]);
+        }
+        Some(round)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs the scheduler to the end, letting the lower index win every
+    /// match, and returns the rounds.
+    fn play_out(format: Format, entrants: usize) -> Vec<Round> {
+        let mut scheduler = Scheduler::new(format, entrants);
+        let mut scores = vec![0; entrants];
+        let mut rounds = Vec::new();
+        while let Some(round) = scheduler.next_round(&scores) {
+            for &pairing in &round.pairings {
+                let winner = pairing[0].min(pairing[1]);
+                scores[winner] += 1;
+                scheduler.record_match(pairing, winner);
+            }
+            rounds.push(round);
+        }
+        rounds
+    }
+
+    #[test]
+    fn test_round_robin_pairs_everybody_once() {
+        for entrants in [2, 3, 4, 7] {
+            let rounds = play_out(Format::RoundRobin, entrants);
+            let mut met = vec![vec![0; entrants]; entrants];
+            for round in &rounds {
+                let mut playing: Vec<usize> = round.pairings.iter().flatten().copied().collect();
+                playing.extend(&round.byes);
+                playing.sort();
+                assert_eq!(playing, (0..entrants).collect::<Vec<_>>());
+                for &[a, b] in &round.pairings {
+                    met[a][b] += 1;
+                    met[b][a] += 1;
+                }
+            }
+            for (a, row) in met.iter().enumerate() {
+                for (b, &count) in row.iter().enumerate() {
+                    assert_eq!(count, if a == b { 0 } else { 1 });
+                }
+            }
+        }
+        assert!(play_out(Format::RoundRobin, 1).is_empty());
+    }
+
+    #[test]
+    fn test_swiss_avoids_rematches_and_repeated_byes() {
+        let rounds = play_out(Format::Swiss { rounds: 3 }, 5);
+        assert_eq!(rounds.len(), 3);
+        let mut pairs: Vec<[usize; 2]> = rounds
+            .iter()
+            .flat_map(|round| round.pairings.iter().map(|&[a, b]| [a.min(b), a.max(b)]))
+            .collect();
+        let played = pairs.len();
+        pairs.sort();
+        pairs.dedup();
+        assert_eq!(pairs.len(), played);
+        let mut byes: Vec<usize> = rounds.iter().flat_map(|round| round.byes.clone()).collect();
+        byes.sort();
+        byes.dedup();
+        assert_eq!(byes.len(), 3);
+        assert_eq!(Format::swiss_for(5), Format::Swiss { rounds: 3 });
+        assert_eq!(Format::swiss_for(8), Format::Swiss { rounds: 3 });
+    }
+
+    #[test]
+    fn test_elimination_until_one_is_left() {
+        let rounds = play_out(Format::SingleElimination, 6);
+        assert_eq!(rounds.len(), 3);
+        assert_eq!(rounds[0].byes, vec![0, 1]);
+        assert_eq!(rounds[0].pairings, vec![[2, 5], [3, 4]]);
+        assert_eq!(rounds[1].pairings, vec![[0, 3], [1, 2]]);
+        assert_eq!(rounds[2].pairings, vec![[0, 1]]);
+    }
+}
@@ -0,0 +1,288 @@
+//! Tournaments saved game by game, so that they can be resumed.
+//!
+//! A [`TournamentDir`] holds:
+//! - `tournament.json`: the [`TournamentSetup`], written when the
+//!   tournament starts,
+//! - `games.jsonl`: one [`GameRecord`] per line, appended after every game,
+//! - `report.json`: the [`TournamentReport`], once the tournament is over.
+//!
+//! The pairings are not saved: the schedule is deterministic, so
+//! [`Tournament::resume`](crate::Tournament::resume) finds them again by
+//! replaying the recorded games.
+
+use crate::{
+    Budget, BudgetReport, Entrant, Format, GameRecord, Sprt, Tournament, TournamentReport,
+};
+use gamey_bots::YBotRegistry;
+use gamey_core::{GameYError, YEN};
+use serde::{Deserialize, Serialize};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+const SETUP_FILE: &str = "tournament.json";
+const GAMES_FILE: &str = "games.jsonl";
+const REPORT_FILE: &str = "report.json";
+
+/// Everything needed to play a tournament again from its start.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TournamentSetup {
+    /// The entrants as given on the command line, see
+    /// [`Entrant::parse`](crate::Entrant::parse).
+    pub entrants: Vec<String>,
+    /// How the entrants are paired.
+    pub format: Format,
+    /// The number of games every pairing plays.
+    pub games_per_pairing: u32,
+    /// The budget of every move of the process bots.
+    pub budget: BudgetReport,
+    /// The test that stops matches early, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sprt: Option<Sprt>,
+    /// The seed of the [`DeterministicConfig`](gamey_core::DeterministicConfig)
+    /// the tournament is played under, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// The position every game starts from.
+    pub start: YEN,
+}
+
+impl TournamentSetup {
+    /// Creates the tournament, finding the entrants among `bots`.
+    pub fn tournament(&self, bots: &YBotRegistry) -> Result<Tournament, String> {
+        let entrants = self
+            .entrants
+            .iter()
+            .map(|spec| Entrant::parse(spec, bots))
+            .collect::<Result<Vec<_>, String>>()?;
+        let tournament = Tournament::new(entrants)
+            .with_format(self.format)
+            .with_games_per_pairing(self.games_per_pairing)
+            .with_budget(Budget::from(&self.budget));
+        Ok(match self.sprt {
+            Some(sprt) => tournament.with_sprt(sprt),
+            None => tournament,
+        })
+    }
+}
+
+/// A directory where a tournament is saved as it is played.
+#[derive(Debug, Clone)]
+pub struct TournamentDir {
+    path: PathBuf,
+}
+
+impl TournamentDir {
+    /// Starts saving a new tournament in `path`, creating the directory if
+    /// needed.
+    ///
+    /// # Errors
+    /// Returns `GameYError::InvalidConfig` if the directory already holds a
+    /// tournament, which should be resumed instead, and
+    /// `GameYError::IoError` if the files cannot be written.
+    pub fn create(path: &Path, setup: &TournamentSetup) -> Result<Self, GameYError> {
+        let dir = TournamentDir {
+            path: path.to_path_buf(),
+        };
+        let setup_file = dir.file(SETUP_FILE);
+        if setup_file.exists() {
+            return Err(GameYError::InvalidConfig {
+                file: setup_file.display().to_string(),
+                message: "a tournament was already started here, resume it instead".to_string(),
+            });
+        }
+        std::fs::create_dir_all(path).map_err(|e| io_error("create", path, e))?;
+        File::create(dir.file(GAMES_FILE)).map_err(|e| io_error("create", path, e))?;
+        let json =
+            serde_json::to_string_pretty(setup).map_err(|e| GameYError::SerdeError { error: e })?;
+        std::fs::write(&setup_file, json).map_err(|e| io_error("write", &setup_file, e))?;
+        Ok(dir)
+    }
+
+    /// Opens the tournament saved in `path`, returning its setup and the
+    /// games played so far.
+    ///
+    /// A last game cut short by a crash while it was being written is
+    /// dropped, and played again when the tournament is resumed.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the files cannot be read and
+    /// `GameYError::InvalidConfig` if they are not valid.
+    pub fn open(path: &Path) -> Result<(Self, TournamentSetup, Vec<GameRecord>), GameYError> {
+        let dir = TournamentDir {
+            path: path.to_path_buf(),
+        };
+        let setup_file = dir.file(SETUP_FILE);
+        let text =
+            std::fs::read_to_string(&setup_file).map_err(|e| io_error("read", &setup_file, e))?;
+        let setup = serde_json::from_str(&text).map_err(|e| GameYError::InvalidConfig {
+            file: setup_file.display().to_string(),
+            message: e.to_string(),
+        })?;
+
+        let games_file = dir.file(GAMES_FILE);
+        let text =
+            std::fs::read_to_string(&games_file).map_err(|e| io_error("read", &games_file, e))?;
+        let mut games = Vec::new();
+        let mut complete = 0;
+        for (number, line) in text.split_inclusive('\n').enumerate() {
+            if !line.ends_with('\n') {
+                break;
+            }
+            let game = serde_json::from_str(line).map_err(|e| GameYError::InvalidConfig {
+                file: games_file.display().to_string(),
+                message: format!("line {}: {}", number + 1, e),
+            })?;
+            games.push(game);
+            complete += line.len();
+        }
+        if complete < text.len() {
+            OpenOptions::new()
+                .write(true)
+                .open(&games_file)
+                .and_then(|file| file.set_len(complete as u64))
+                .map_err(|e| io_error("repair", &games_file, e))?;
+        }
+        Ok((dir, setup, games))
+    }
+
+    /// Returns the directory.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends a game to the saved ones, making sure it reached the disk.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the game cannot be written.
+    pub fn record_game(&self, game: &GameRecord) -> Result<(), GameYError> {
+        let games_file = self.file(GAMES_FILE);
+        let mut line = serde_json::to_vec(game).map_err(|e| GameYError::SerdeError { error: e })?;
+        line.push(b'\n');
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(&games_file)
+            .map_err(|e| io_error("open", &games_file, e))?;
+        file.write_all(&line)
+            .and_then(|_| file.sync_data())
+            .map_err(|e| io_error("write", &games_file, e))
+    }
+
+    /// Saves the report of the finished tournament.
+    ///
+    /// # Errors
+    /// Returns `GameYError::IoError` if the report cannot be written.
+    pub fn write_report(&self, report: &TournamentReport) -> Result<(), GameYError> {
+        let report_file = self.file(REPORT_FILE);
+        let json = serde_json::to_string_pretty(report)
+            .map_err(|e| GameYError::SerdeError { error: e })?;
+        std::fs::write(&report_file, json).map_err(|e| io_error("write", &report_file, e))
+    }
+
+    fn file(&self, name: &str) -> PathBuf {
+        self.path.join(name)
+    }
+}
+
+fn io_error(action: &str, path: &Path, e: std::io::Error) -> GameYError {
+    GameYError::IoError {
+        message: format!("Failed to {} {}", action, path.display()),
+        error: e.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gamey_bots::RandomBot;
+    use gamey_core::GameY;
+    use std::sync::Arc;
+
+    fn setup() -> TournamentSetup {
+        TournamentSetup {
+            entrants: vec!["random_bot".to_string(); 3],
+            format: Format::RoundRobin,
+            games_per_pairing: 2,
+            budget: BudgetReport::from(&Budget::default()),
+            sprt: None,
+            seed: None,
+            start: YEN::from(&GameY::new(3)),
+        }
+    }
+
+    fn tournament() -> Tournament {
+        let bots = YBotRegistry::new().with_bot(Arc::new(RandomBot));
+        setup().tournament(&bots).unwrap()
+    }
+
+    #[test]
+    fn test_resume_replays_the_recorded_games() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = TournamentDir::create(temp.path(), &setup()).unwrap();
+        assert!(TournamentDir::create(temp.path(), &setup()).is_err());
+
+        // The tournament is interrupted after its fourth game
+        let mut saved = 0;
+        let interrupted = tournament().resume(
+            || GameY::new(3),
+            &[],
+            |game| {
+                if saved == 4 {
+                    return Err(GameYError::ServerError {
+                        message: "interrupted".to_string(),
+                    });
+                }
+                saved += 1;
+                dir.record_game(game)
+            },
+        );
+        assert!(interrupted.is_err());
+
+        let (dir, loaded, played) = TournamentDir::open(temp.path()).unwrap();
+        assert_eq!(loaded.entrants, setup().entrants);
+        assert_eq!(loaded.start.layout(), setup().start.layout());
+        assert_eq!(played.len(), 4);
+        let report = tournament()
+            .resume(|| GameY::new(3), &played, |game| dir.record_game(game))
+            .unwrap();
+        assert_eq!(report.games.len(), 6);
+        assert_eq!(report.games[..4], played[..]);
+        let wins: u32 = report.standings.iter().map(|s| s.wins).sum();
+        assert_eq!(wins, 6);
+        let first: u32 = report.standings.iter().map(|s| s.games_first).sum();
+        assert_eq!(first, 6);
+        let (_, _, played) = TournamentDir::open(temp.path()).unwrap();
+        assert_eq!(played, report.games);
+    }
+
+    #[test]
+    fn test_open_drops_a_game_cut_short() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = TournamentDir::create(temp.path(), &setup()).unwrap();
+        let report = tournament().run(|| GameY::new(3));
+        dir.record_game(&report.games[0]).unwrap();
+        let mut file = OpenOptions::new()
+            .append(true)
+            .open(temp.path().join(GAMES_FILE))
+            .unwrap();
+        file.write_all(b"{\"round\":1,\"pla").unwrap();
+
+        let (dir, _, played) = TournamentDir::open(temp.path()).unwrap();
+        assert_eq!(played, report.games[..1]);
+        dir.record_game(&report.games[1]).unwrap();
+        let (_, _, played) = TournamentDir::open(temp.path()).unwrap();
+        assert_eq!(played, report.games[..2]);
+    }
+
+    #[test]
+    fn test_resume_rejects_games_of_another_tournament() {
+        let report = tournament().run(|| GameY::new(3));
+        let mut played = report.games.clone();
+        played[0].players[0] = "mcts_bot".to_string();
+        let result = tournament().resume(|| GameY::new(3), &played, |_| Ok(()));
+        assert!(matches!(
+            result,
+            Err(GameYError::IntegrityCheckFailed { .. })
+        ));
+    }
+}
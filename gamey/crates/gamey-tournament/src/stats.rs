@@ -0,0 +1,329 @@
+//! How significant the result of a match between two bots is.
+//!
+//! A win count alone does not tell a real improvement from luck.
+//! [`MatchStats`] gives the score of a match with its 95% confidence
+//! interval, the Elo difference it implies and the likelihood of
+//! superiority (LOS), the probability that the first bot is the stronger.
+//! A [`Sprt`], a sequential probability ratio test, decides after every game
+//! whether the match has shown that the first bot is stronger by some Elo
+//! margin or not, so that matches can stop as soon as they have.
+//!
+//! Games of Y cannot be drawn, so every game is a win or a loss.
+
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The normal quantile of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.959_964;
+
+/// The games won and lost by the first bot of a match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct MatchStats {
+    /// Games won by the first bot.
+    pub wins: u32,
+    /// Games won by the second bot.
+    pub losses: u32,
+}
+
+impl MatchStats {
+    /// Returns the number of games.
+    pub fn games(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Returns the fraction of the games won by the first bot, or one half
+    /// before any game.
+    pub fn score(&self) -> f64 {
+        match self.games() {
+            0 => 0.5,
+            games => f64::from(self.wins) / f64::from(games),
+        }
+    }
+
+    /// Returns the 95% confidence interval of the score, a Wilson score
+    /// interval, which stays within 0 and 1 even for one-sided results.
+    pub fn score_interval(&self) -> (f64, f64) {
+        let n = f64::from(self.games());
+        if n == 0.0 {
+            return (0.0, 1.0);
+        }
+        let p = self.score();
+        let z2 = Z_95 * Z_95;
+        let center = (p + z2 / (2.0 * n)) / (1.0 + z2 / n);
+        let margin = Z_95 / (1.0 + z2 / n) * (p * (1.0 - p) / n + z2 / (4.0 * n * n)).sqrt();
+        (center - margin, center + margin)
+    }
+
+    /// Returns the Elo difference between the bots that the score implies,
+    /// infinite if one of them won every game.
+    pub fn elo(&self) -> f64 {
+        elo_from_score(self.score())
+    }
+
+    /// Returns the 95% confidence interval of [`MatchStats::elo`].
+    pub fn elo_interval(&self) -> (f64, f64) {
+        let (low, high) = self.score_interval();
+        (elo_from_score(low), elo_from_score(high))
+    }
+
+    /// Returns the likelihood of superiority: the probability that the
+    /// first bot is the stronger one, given the wins and losses.
+    pub fn los(&self) -> f64 {
+        if self.games() == 0 {
+            return 0.5;
+        }
+        let wins = f64::from(self.wins);
+        let losses = f64::from(self.losses);
+        0.5 * (1.0 + erf((wins - losses) / (2.0 * (wins + losses)).sqrt()))
+    }
+}
+
+impl Display for MatchStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (low, high) = self.score_interval();
+        let (elo_low, elo_high) = self.elo_interval();
+        write!(
+            f,
+            "+{} -{}, score {:.1}% [{:.1}%, {:.1}%], Elo {:+.0} [{:+.0}, {:+.0}], LOS {:.1}%",
+            self.wins,
+            self.losses,
+            100.0 * self.score(),
+            100.0 * low,
+            100.0 * high,
+            self.elo(),
+            elo_low,
+            elo_high,
+            100.0 * self.los()
+        )
+    }
+}
+
+/// The Elo difference at which the stronger player scores `score`.
+fn elo_from_score(score: f64) -> f64 {
+    -400.0 * (1.0 / score - 1.0).log10()
+}
+
+/// The score expected of a player `elo` points stronger than its opponent.
+fn score_from_elo(elo: f64) -> f64 {
+    1.0 / (1.0 + 10f64.powf(-elo / 400.0))
+}
+
+/// The error function, with an absolute error below 1.5e-7 (Abramowitz and
+/// Stegun, formula 7.1.26).
+fn erf(x: f64) -> f64 {
+    let t = 1.0 / (1.0 + 0.327_591_1 * x.abs());
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736
+                + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let y = 1.0 - poly * (-x * x).exp();
+    if x < 0.0 { -y } else { y }
+}
+
+/// What a [`Sprt`] concluded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SprtResult {
+    /// H0 was accepted: the first bot is not [`Sprt::elo1`] stronger.
+    NotStronger,
+    /// H1 was accepted: the first bot is [`Sprt::elo1`] stronger rather
+    /// than [`Sprt::elo0`].
+    Stronger,
+}
+
+impl Display for SprtResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SprtResult::NotStronger => write!(f, "H0 accepted"),
+            SprtResult::Stronger => write!(f, "H1 accepted"),
+        }
+    }
+}
+
+/// A sequential probability ratio test of whether the first bot of a match
+/// is stronger than the second one.
+///
+/// The test weighs the hypothesis H0 that the first bot is
+/// [`Sprt::elo0`] Elo stronger against H1 that it is [`Sprt::elo1`]
+/// stronger, and stops once the log-likelihood ratio of the results crosses
+/// the bound given by the error rates. Written on the command line as
+/// comma-separated `key=value` pairs, e.g. `elo0=0,elo1=20,alpha=0.05`;
+/// both error rates are 0.05 by default.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Sprt {
+    /// The Elo difference of H0.
+    pub elo0: f64,
+    /// The Elo difference of H1, above `elo0`.
+    pub elo1: f64,
+    /// The probability of accepting H1 when H0 holds.
+    pub alpha: f64,
+    /// The probability of accepting H0 when H1 holds.
+    pub beta: f64,
+}
+
+impl Sprt {
+    /// Creates a test of `elo0` against `elo1` with error rates of 5%.
+    pub fn new(elo0: f64, elo1: f64) -> Self {
+        Sprt {
+            elo0,
+            elo1,
+            alpha: 0.05,
+            beta: 0.05,
+        }
+    }
+
+    /// Returns the log-likelihood ratio of H1 against H0 given `stats`.
+    pub fn llr(&self, stats: &MatchStats) -> f64 {
+        let p0 = score_from_elo(self.elo0);
+        let p1 = score_from_elo(self.elo1);
+        f64::from(stats.wins) * (p1 / p0).ln()
+            + f64::from(stats.losses) * ((1.0 - p1) / (1.0 - p0)).ln()
+    }
+
+    /// Returns the bounds of the log-likelihood ratio below which H0 is
+    /// accepted and above which H1 is.
+    pub fn bounds(&self) -> (f64, f64) {
+        (
+            (self.beta / (1.0 - self.alpha)).ln(),
+            ((1.0 - self.beta) / self.alpha).ln(),
+        )
+    }
+
+    /// Returns the conclusion of the test given `stats`, or `None` while
+    /// the match should go on.
+    pub fn result(&self, stats: &MatchStats) -> Option<SprtResult> {
+        let llr = self.llr(stats);
+        let (lower, upper) = self.bounds();
+        if llr >= upper {
+            Some(SprtResult::Stronger)
+        } else if llr <= lower {
+            Some(SprtResult::NotStronger)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for Sprt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut sprt = Sprt::new(0.0, 0.0);
+        let mut elo1 = None;
+        for pair in s.split(',').map(str::trim).filter(|p| !p.is_empty()) {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| format!("Expected key=value in SPRT, found '{}'", pair))?;
+            let number = || {
+                value
+                    .parse::<f64>()
+                    .ok()
+                    .filter(|number| number.is_finite())
+                    .ok_or_else(|| format!("Invalid number for {}: '{}'", key, value))
+            };
+            let rate = || match number()? {
+                rate if rate > 0.0 && rate < 0.5 => Ok(rate),
+                _ => Err(format!("Invalid error rate for {}: '{}'", key, value)),
+            };
+            match key {
+                "elo0" => sprt.elo0 = number()?,
+                "elo1" => elo1 = Some(number()?),
+                "alpha" => sprt.alpha = rate()?,
+                "beta" => sprt.beta = rate()?,
+                _ => {
+                    return Err(format!(
+                        "Unknown SPRT setting '{}', expected elo0, elo1, alpha or beta",
+                        key
+                    ));
+                }
+            }
+        }
+        sprt.elo1 = elo1.ok_or("The SPRT needs elo1, the Elo difference to detect")?;
+        if sprt.elo1 <= sprt.elo0 {
+            return Err("elo1 must be greater than elo0".to_string());
+        }
+        Ok(sprt)
+    }
+}
+
+impl Display for Sprt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "elo0={},elo1={},alpha={},beta={}",
+            self.elo0, self.elo1, self.alpha, self.beta
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64, tolerance: f64) -> bool {
+        (a - b).abs() < tolerance
+    }
+
+    #[test]
+    fn test_match_stats() {
+        let even = MatchStats {
+            wins: 50,
+            losses: 50,
+        };
+        assert_eq!(even.score(), 0.5);
+        assert!(close(even.elo(), 0.0, 1e-9));
+        assert!(close(even.los(), 0.5, 1e-9));
+        let (low, high) = even.score_interval();
+        assert!(close(low, 0.404, 0.001) && close(high, 0.596, 0.001));
+
+        let better = MatchStats {
+            wins: 60,
+            losses: 40,
+        };
+        assert!(close(better.elo(), 70.4, 0.1));
+        assert!(close(better.los(), 0.977, 0.001));
+        let (low, high) = better.elo_interval();
+        assert!(low < better.elo() && better.elo() < high);
+
+        let sweep = MatchStats { wins: 5, losses: 0 };
+        assert_eq!(sweep.elo(), f64::INFINITY);
+        assert!(sweep.elo_interval().0.is_finite());
+        assert_eq!(MatchStats::default().los(), 0.5);
+        assert!(sweep.to_string().starts_with("+5 -0, score 100.0%"));
+    }
+
+    #[test]
+    fn test_sprt() {
+        let sprt = Sprt::new(0.0, 50.0);
+        let (lower, upper) = sprt.bounds();
+        assert!(close(lower, -2.944, 0.001) && close(upper, 2.944, 0.001));
+        assert_eq!(sprt.result(&MatchStats::default()), None);
+        assert_eq!(sprt.llr(&MatchStats::default()), 0.0);
+        let crushing = MatchStats {
+            wins: 40,
+            losses: 10,
+        };
+        assert_eq!(sprt.result(&crushing), Some(SprtResult::Stronger));
+        let losing = MatchStats {
+            wins: 10,
+            losses: 40,
+        };
+        assert_eq!(sprt.result(&losing), Some(SprtResult::NotStronger));
+        let close_match = MatchStats { wins: 6, losses: 5 };
+        assert_eq!(sprt.result(&close_match), None);
+    }
+
+    #[test]
+    fn test_parse_sprt() {
+        let sprt: Sprt = "elo0=0, elo1=20,alpha=0.01".parse().unwrap();
+        assert_eq!(sprt.elo1, 20.0);
+        assert_eq!((sprt.alpha, sprt.beta), (0.01, 0.05));
+        assert_eq!(sprt.to_string().parse::<Sprt>().unwrap(), sprt);
+        assert!("elo0=0".parse::<Sprt>().is_err());
+        assert!("elo0=10,elo1=5".parse::<Sprt>().is_err());
+        assert!("elo1=5,alpha=0.7".parse::<Sprt>().is_err());
+        assert!("elo1=5,gamma=1".parse::<Sprt>().is_err());
+        assert!("elo1".parse::<Sprt>().is_err());
+    }
+}
@@ -3,14 +3,25 @@
 //! Y is a connection game played on a triangular board where two players
 //! compete to connect all three sides of the triangle with their pieces.
 //!
+//! This crate re-exports the crates of the workspace under a single name:
+//! `gamey-core` (the rules engine and notations), `gamey-bots`,
+//! `gamey-tournament`, `gamey-archive`, `gamey-server`, `gamey-cli` and
+//! `gamey-client`. Consumers who only need the engine can depend on
+//! `gamey-core` directly.
+//!
 //! # Modules
 //!
 //! - [`core`]: Core game types including board, coordinates, and game logic
 //! - [`bot`]: Bot implementations for computer opponents
-//! - [`bot_server`]: HTTP server for bot API
-//! - [`cli`]: Command-line interface for interactive play
+//! - [`tournament`]: Tournaments between bots, including sandboxed programs
+//! - [`archive`]: Archives of finished games and statistics over them
+//! - [`bot_server`]: HTTP server for bot API (feature `server`)
+//! - [`cli`]: Command-line interface for interactive play (feature `cli`)
+//! - `client`: HTTP client for bot servers (feature `client`)
+//! - `conformance`: Contract tests for bot servers (feature `client`)
 //! - [`notation`]: Game notation formats (YEN)
 //! - [`gamey_error`]: Error types for the library
+//! - [`testing`]: Random game generators and invariant checkers for fuzzing
 //!
 //! # Example
 //!
@@ -27,16 +38,41 @@
 //! };
 //! game.add_move(movement).unwrap();
 //! ```
+//!
+//! # Features
+//!
+//! Both features are enabled by default. Without them, the engine, the bots
+//! and the notation formats build with no dependency on tokio, axum, clap or
+//! rustyline, so they can be used from WASM or embedded targets:
+//!
+//! - `cli`: the interactive terminal game
+//! - `server`: the HTTP bot server
+//!
+//! The optional `client` feature adds a typed client of the REST API of bot
+//! servers, and `grpc` a gRPC interface to the bot server. `gzip` and `zstd`
+//! read and write compressed saves and archives, and `parquet` exports
+//! archives as Parquet.
+//!
+//! ```toml
+//! gamey = { version = "0.1", default-features = false }
+//! ```
+
+pub use gamey_archive as archive;
+pub use gamey_bots as bot;
+#[cfg(feature = "cli")]
+pub use gamey_cli as cli;
+#[cfg(feature = "client")]
+pub use gamey_client as client;
+#[cfg(feature = "client")]
+pub use gamey_client::conformance;
+pub use gamey_core::{core, gamey_error, notation, testing};
+#[cfg(feature = "server")]
+pub use gamey_server as bot_server;
+pub use gamey_tournament as tournament;
 
-pub mod bot;
-pub mod cli;
-pub mod core;
-pub mod gamey_error;
-pub mod notation;
-pub mod bot_server;
-pub use bot::*;
-pub use cli::*;
-pub use core::*;
-pub use gamey_error::*;
-pub use notation::*;
-pub use bot_server::*;
+pub use gamey_bots::*;
+#[cfg(feature = "cli")]
+pub use gamey_cli::*;
+pub use gamey_core::*;
+#[cfg(feature = "server")]
+pub use gamey_server::*;
@@ -1,10 +1,15 @@
 //! GameY binary entry point.
 //!
-//! This is the main executable for the GameY application. It supports three modes:
+//! This is the main executable for the GameY application. Every task is a
+//! subcommand with its own options, while the board options (`--size`,
+//! `--game`, `--ruleset`...) and logging options are shared by all:
 //!
-//! - **Human mode** (default): Two players take turns at the terminal
-//! - **Computer mode**: Play against a bot
-//! - **Server mode**: Run as an HTTP server exposing the bot API
+//! - **play** (default): Two players take turns at the terminal, or one
+//!   plays against a bot
+//! - **serve**: Run as an HTTP server exposing the bot API, or answer
+//!   JSON-RPC requests read line by line from stdin
+//! - **bots**, **solve**, **replay**, **tournament**, **archive**,
+//!   **convert** and more, see `gamey help`
 //!
 //! # Usage
 //!
@@ -13,31 +18,357 @@
 //! gamey
 //!
 //! # Play against the random bot
-//! gamey --mode computer
+//! gamey play --mode computer
+//!
+//! # Play against a slow, unreliable bot; the random bot steps in when it fails
+//! gamey play --mode computer --bot mcts_bot --chaos latency=500,fail=0.2 --fallback-bot random_bot
 //!
 //! # Start the bot server on port 3000
-//! gamey --mode server --port 3000
+//! gamey serve --port 3000
+//!
+//! # Serve a classroom from a small machine
+//! gamey serve --demo
+//!
+//! # Start the server with the settings of a file, as in a container
+//! GAMEY_CONFIG=/etc/gamey/server.toml GAMEY_PORT=8080 gamey serve
+//!
+//! # Drive the engine over JSON-RPC from another program
+//! gamey serve --jsonrpc --bot mcts_bot
+//!
+//! # The bots that can play, and who wins a small board with perfect play
+//! gamey bots
+//! gamey solve --size 4
+//!
+//! # Check saved positions
+//! gamey validate game1.yen game2.yen
+//!
+//! # Convert a collection of games to SGF
+//! gamey convert --from yen --to sgf games/*.yen --out-dir sgf/
+//!
+//! # Check a submitted game move by move against the result it claims
+//! gamey verify game.ymn --size 7 --result B+
+//!
+//! # Play a scripted game and print the final board
+//! gamey replay --moves "4 7 2 9" --size 5 --print-final
+//!
+//! # Start from a named opening, or save your own
+//! gamey play --opening center-3 --mode computer
+//! gamey openings add my-line --moves "12 5 19" --description "Low center"
+//!
+//! # Rank bots, including a program limited to 500 ms of CPU per move
+//! gamey tournament --entrant mcts_bot --entrant "mine=python3 bot.py" --move-time 500
+//!
+//! # A Swiss tournament whose standings are saved for a spreadsheet
+//! gamey tournament --format swiss --rounds 4 --entrant a=./a --entrant b=./b --report standings.csv
+//!
+//! # A long tournament saved after every game, and resumed if it stops
+//! gamey tournament --entrant a=./a --entrant b=./b --games 100 --dir league
+//! gamey tournament resume league
+//!
+//! # Test whether a new version is stronger, stopping once it is clear
+//! gamey tournament --entrant new=./new --entrant old=./old --games 2000 --sprt elo0=0,elo1=20
+//!
+//! # Rank the first moves of a size 7 board by the win rate mcts_bot gets
+//! gamey analyze-openings --size 7 --bot mcts_bot --playouts 200
+//!
+//! # Decide a timed-out game by who wins 80% of its playouts
+//! gamey adjudicate pos.yen --bot mcts_bot --playouts 500 --threshold 0.8
+//!
+//! # The mistakes of a finished game and the accuracy of each player
+//! gamey review game.ymn --bot mcts_bot --playouts 50
+//!
+//! # Statistics over an archive of games, also as CSV for plotting
+//! gamey stats --games archive.jsonl --csv stats/
+//!
+//! # The same tournament, move for move, every time it is run
+//! gamey tournament --entrant random_bot --entrant mcts_bot --seed 42
+//!
+//! # Games of an archive reaching a position, and the moves played next
+//! gamey archive find-position pos.yen --games archive.jsonl
+//!
+//! # One row per move of an archive, for pandas or Polars (feature `parquet`)
+//! gamey archive export --format parquet out.parquet --games archive.jsonl
+//!
+//! # Check that a bot server implements the API (feature `client`)
+//! gamey conformance --url http://localhost:3000
+//!
+//! # Follow a game of a server live, with the comments of one of its bots
+//! # (feature `client`)
+//! gamey watch --server http://localhost:3000 --game-id 1a2b3c --bot-id mcts_bot
 //! ```
+//!
+//! # Exit codes
+//!
+//! - `0`: normal termination
+//! - `2`: invalid command-line arguments
+//! - `3`: engine error, e.g. a rejected move or an invalid YEN file
+//! - `4`: the server could not bind its port
+//!
+//! The server options can also be set with `GAMEY_*` environment variables
+//! (see `gamey serve --help`) or a `--config` file; options on the command line
+//! win over the environment, and both over the file.
+//!
+//! `--quiet` only logs errors and `-v`/`-vv` add info and debug messages;
+//! logs are written to stderr.
 
-use clap::Parser;
-use gamey::{self, CliArgs, Mode, run_bot_server, run_cli_game};
-use tracing_subscriber::prelude::*;
+use gamey::bot_server::{audit::AuditConfig, server_config::ServerConfig};
+use gamey::tournament::{Budget, BudgetReport, TournamentSetup};
+use gamey::{
+    self, ArchiveAction, CliArgs, CliCommand, DeterministicConfig, ExitStatus, GameYError,
+    LegacyForm, Message, OpeningsAction, PlayArgs, ServeArgs, TournamentAction, YEN, new_game,
+    run_adjudicate, run_analyze_openings, run_bot_server_with_remote_bots, run_bots, run_cli_game,
+    run_convert, run_export, run_find_position, run_jsonrpc, run_openings, run_replay, run_review,
+    run_solve, run_stats, run_tournament, run_tournament_resume, run_validate, run_verify,
+};
+use std::process::ExitCode;
+use std::time::Duration;
+use tracing_subscriber::{EnvFilter, prelude::*};
 
 /// Main entry point for the GameY application.
 ///
-/// Parses command-line arguments and runs either the CLI game or the HTTP server
-/// depending on the selected mode.
+/// Parses command-line arguments and runs the selected command, the CLI game
+/// by default.
 #[tokio::main]
-async fn main() {
-    tracing_subscriber::registry().init();
-    let args = CliArgs::parse();
+async fn main() -> ExitCode {
+    let (args, deprecated) =
+        CliArgs::try_parse_legacy(std::env::args_os()).unwrap_or_else(|e| e.exit());
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(EnvFilter::new(args.log_filter()))
+        .init();
+    if let Some(LegacyForm { old, new }) = deprecated {
+        eprintln!("{}", Message::DeprecatedUsage { old, new }.text(args.lang));
+    }
+    if let Err(message) = args.check() {
+        eprintln!("Error: {}", message);
+        return ExitStatus::InvalidArgs.into();
+    }
+    let _determinism = args
+        .deterministic_config()
+        .map(DeterministicConfig::install);
+    run(args).await.into()
+}
+
+/// Runs the command selected by `args` and returns the exit status.
+async fn run(args: CliArgs) -> ExitStatus {
+    let result = match &args.command {
+        None => run_cli_game(&args, &PlayArgs::default()).map(|_| true),
+        Some(CliCommand::Play(play)) => run_cli_game(&args, play).map(|_| true),
+        Some(CliCommand::Serve(serve)) if serve.jsonrpc => {
+            run_jsonrpc(&args, &serve.bot).map(|_| true)
+        }
+        Some(CliCommand::Serve(serve)) => run_server(serve).await.map(|_| true).map_err(Into::into),
+        Some(CliCommand::Bots) => {
+            run_bots();
+            Ok(true)
+        }
+        Some(CliCommand::Solve { position }) => run_solve(&args, position.as_deref()).map(|_| true),
+        Some(CliCommand::Validate { files }) => Ok(run_validate(files, args.lang)),
+        Some(CliCommand::Convert {
+            files,
+            from,
+            to,
+            out_dir,
+        }) => run_convert(&args, files, *from, *to, out_dir),
+        Some(CliCommand::Verify {
+            files,
+            from,
+            result,
+        }) => run_verify(&args, files, *from, result.as_deref()),
+        Some(CliCommand::Replay { moves, print_final }) => {
+            run_replay(&args, moves.as_deref(), *print_final)
+        }
+        Some(CliCommand::Openings { action }) => {
+            let action = action.clone().unwrap_or(OpeningsAction::List);
+            run_openings(&args, &action).map(|_| true)
+        }
+        Some(CliCommand::Tournament {
+            action: Some(TournamentAction::Resume { dir }),
+            ..
+        }) => run_tournament_resume(dir).map(|_| true),
+        Some(CliCommand::Tournament {
+            action: None,
+            entrants,
+            format,
+            rounds,
+            games,
+            move_time,
+            memory,
+            report,
+            sprt,
+            dir,
+        }) => {
+            let budget = Budget::new(Duration::from_millis(*move_time), *memory);
+            let setup = new_game(&args).map(|start| TournamentSetup {
+                entrants: entrants.clone(),
+                format: format.format(entrants.len(), *rounds),
+                games_per_pairing: *games,
+                budget: BudgetReport::from(&budget),
+                sprt: *sprt,
+                seed: args.seed,
+                start: YEN::from(&start),
+            });
+            setup
+                .and_then(|setup| run_tournament(&setup, report.as_deref(), dir.as_deref()))
+                .map(|_| true)
+        }
+        Some(CliCommand::AnalyzeOpenings { bot, playouts }) => {
+            run_analyze_openings(&args, bot, *playouts).map(|_| true)
+        }
+        Some(CliCommand::Adjudicate {
+            position,
+            bot,
+            playouts,
+            threshold,
+        }) => run_adjudicate(&args, position, bot, *playouts, *threshold).map(|_| true),
+        Some(CliCommand::Review {
+            file,
+            from,
+            bot,
+            playouts,
+            threshold,
+        }) => run_review(&args, file, *from, bot, *playouts, *threshold).map(|_| true),
+        Some(CliCommand::Stats { games, csv }) => run_stats(games, csv.as_deref()).map(|_| true),
+        Some(CliCommand::Archive {
+            action: ArchiveAction::FindPosition { position, games },
+        }) => run_find_position(position, games).map(|_| true),
+        Some(CliCommand::Archive {
+            action: ArchiveAction::Export { out, games, format },
+        }) => run_export(games, out, *format).map(|_| true),
+        #[cfg(feature = "client")]
+        Some(CliCommand::Conformance { url, bot_id }) => Ok(run_conformance(url, bot_id).await),
+        #[cfg(feature = "client")]
+        Some(CliCommand::Watch {
+            server,
+            game_id,
+            bot_id,
+        }) => Ok(run_watch(server, game_id, bot_id.as_deref()).await),
+    };
+    match result {
+        Ok(true) => ExitStatus::Success,
+        Ok(false) => ExitStatus::EngineError,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitStatus::of(&e)
+        }
+    }
+}
+
+/// Runs the conformance suite against the server at `url` and prints the
+/// report.
+///
+/// Returns true if the server passed every check.
+#[cfg(feature = "client")]
+async fn run_conformance(url: &str, bot_id: &str) -> bool {
+    let client = gamey::client::BotClient::new(url);
+    let report = gamey::conformance::run_conformance(&client, bot_id).await;
+    println!("{}", report);
+    report.passed()
+}
+
+/// Follows the game `game_id` of the server at `server`, drawing the board
+/// after every move, and the comment of `bot_id` on it if given.
+///
+/// Returns false if the game could not be followed to its end.
+#[cfg(feature = "client")]
+async fn run_watch(server: &str, game_id: &str, bot_id: Option<&str>) -> bool {
+    use gamey::{GameY, RenderOptions};
+    use std::io::IsTerminal;
 
-    if args.mode == Mode::Server {
-        if let Err(e) = run_bot_server(args.port).await {
+    let client = gamey::client::BotClient::new(server);
+    let mut watch = match client.watch_game(game_id).await {
+        Ok(watch) => watch,
+        Err(e) => {
             eprintln!("Error: {}", e);
-            std::process::exit(1);
+            return false;
+        }
+    };
+    let live = std::io::stdout().is_terminal();
+    loop {
+        let turn = match watch.next_turn().await {
+            Ok(Some(turn)) => turn,
+            Ok(None) => return true,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return false;
+            }
+        };
+        let game = match GameY::try_from(turn.yen.clone()) {
+            Ok(game) => game,
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return false;
+            }
+        };
+        if live {
+            // Draw over the previous board
+            print!("\x1b[2J\x1b[H");
+        }
+        println!("{}", game.render(&RenderOptions::default()));
+        match (turn.winner, turn.next_player) {
+            (Some(winner), _) => println!("Player {} won.", winner),
+            (None, Some(player)) => println!("Player {} to move.", player),
+            (None, None) => {}
+        }
+        if let (Some(bot_id), None) = (bot_id, turn.winner) {
+            match client.explain(bot_id, &turn.yen).await {
+                Ok(answer) => println!(
+                    "{} would play {}: {}",
+                    bot_id,
+                    game.cell_name(answer.coords),
+                    answer.explanation.unwrap_or_default()
+                ),
+                Err(e) => eprintln!("No comment from {}: {}", bot_id, e),
+            }
+        }
+    }
+}
+
+/// Runs the bot server with the settings of the `--config` file, overridden
+/// by the options given on the command line or in the environment.
+async fn run_server(args: &ServeArgs) -> Result<(), GameYError> {
+    let mut config = match &args.config {
+        Some(file) => ServerConfig::load(file)?,
+        None => ServerConfig::default(),
+    };
+    if let Some(host) = &args.host {
+        config.host = host.clone();
+    }
+    if let Some(port) = args.port {
+        config.port = port;
+    }
+    if let Some(port) = args.grpc_port {
+        config.grpc_port = Some(port);
+    }
+    if let Some(dir) = &args.games_dir {
+        config.games_dir = Some(dir.clone());
+    }
+    if args.bots_config.is_some() || config.bots_config.is_none() {
+        config.bots_config = args.bots_config();
+    }
+    if let Some(seconds) = args.bot_timeout {
+        config.bot_timeout_secs = seconds;
+    }
+    if let Some(entries) = args.move_cache_entries {
+        config.move_cache_entries = entries;
+    }
+    if args.demo {
+        config.demo.get_or_insert_with(Default::default);
+    }
+    if let Some(key) = &args.admin_key {
+        config.admin_key = Some(key.clone());
+    }
+    if let Some(path) = &args.audit_log {
+        match &mut config.audit {
+            // Keep the rotation settings of the file
+            Some(audit) => audit.path = path.clone(),
+            None => config.audit = Some(AuditConfig::new(path.clone())),
         }
-    } else {
-        run_cli_game().expect("End CLI game");
     }
+    // Remote bots in bots.toml are reached with the HTTP client
+    #[cfg(feature = "client")]
+    let remote_bots = Some(gamey::client::RemoteBot::factory());
+    #[cfg(not(feature = "client"))]
+    let remote_bots = None;
+    run_bot_server_with_remote_bots(config, remote_bots).await
 }
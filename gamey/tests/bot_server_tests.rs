@@ -2,9 +2,13 @@ use axum::{
     body::Body,
     http::{Request, StatusCode},
 };
-use gamey::{YBotRegistry, YEN, create_default_state, create_router, state::AppState, RandomBot, MoveResponse, ErrorResponse};
+use gamey::{
+    Coordinates, ErrorCode, ErrorResponse, MoveResponse, PlayerId, RandomBot, YBotRegistry, YEN,
+    create_default_state, create_router, state::AppState,
+};
 use http_body_util::BodyExt;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tower::ServiceExt;
 
 /// Helper to create a test app with the default state
@@ -75,6 +79,28 @@ async fn test_choose_endpoint_with_valid_request() {
     // Coordinates should be valid (we can't predict exactly which one the random bot picks)
 }
 
+#[tokio::test]
+async fn test_choose_endpoint_with_built_position() {
+    // Only one cell is left for the bot, whose turn the builders gave it
+    let mut yen = YEN::empty(2)
+        .unwrap()
+        .with_move(Coordinates::new(1, 0, 0), PlayerId::new(0))
+        .unwrap()
+        .with_move(Coordinates::new(0, 1, 0), PlayerId::new(1))
+        .unwrap();
+    yen.set_turn(PlayerId::new(1)).unwrap();
+
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/ybot/choose/random_bot",
+        Some(serde_json::to_value(&yen).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["coords"], serde_json::json!({"x": 0, "y": 0, "z": 1}));
+}
+
 #[tokio::test]
 async fn test_choose_endpoint_with_partially_filled_board() {
     let app = test_app();
@@ -103,6 +129,69 @@ async fn test_choose_endpoint_with_partially_filled_board() {
     assert_eq!(move_response.bot_id, "random_bot");
 }
 
+#[tokio::test]
+async fn test_choose_stream_endpoint_reports_progress() {
+    let app = test_app();
+    let yen = YEN::new(4, 0, vec!['B', 'R'], "./../.../....".to_string());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/ybot/choose/mcts_bot/stream")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&yen).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        mime::TEXT_EVENT_STREAM.as_ref()
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let events: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+    assert_eq!(events.len(), 11);
+    assert!(events[..10].iter().all(|&event| event == "progress"));
+    assert_eq!(events[10], "move");
+    let last_data = body.lines().rfind(|line| line.starts_with("data: ")).unwrap();
+    let move_response: MoveResponse = serde_json::from_str(&last_data[6..]).unwrap();
+    assert_eq!(move_response.bot_id, "mcts_bot");
+}
+
+#[tokio::test]
+async fn test_choose_endpoint_times_out_and_stops_the_search() {
+    let bots = YBotRegistry::new().with_bot(Arc::new(gamey::MctsBot::new(u32::MAX)));
+    let state = AppState::new(bots).with_bot_timeout(Duration::from_millis(50));
+    let app = test_app_with_state(state);
+    let yen = YEN::from(&gamey::GameY::new(7));
+
+    let start = Instant::now();
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/ybot/choose/mcts_bot")
+                .header("content-type", "application/json")
+                .body(Body::from(serde_json::to_string(&yen).unwrap()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(start.elapsed() < Duration::from_secs(5));
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.code, ErrorCode::Timeout);
+}
+
 // ============================================================================
 // Choose endpoint tests - Error cases
 // ============================================================================
@@ -131,6 +220,7 @@ async fn test_choose_endpoint_with_invalid_api_version() {
     let error_response: ErrorResponse = serde_json::from_slice(&body).unwrap();
 
     assert!(error_response.message.contains("Unsupported API version"));
+    assert_eq!(error_response.code, ErrorCode::UnsupportedApiVersion);
     assert_eq!(error_response.api_version, Some("v2".to_string()));
 }
 
@@ -159,6 +249,7 @@ async fn test_choose_endpoint_with_unknown_bot() {
 
     assert!(error_response.message.contains("Bot not found"));
     assert!(error_response.message.contains("unknown_bot"));
+    assert_eq!(error_response.code, ErrorCode::BotNotFound);
     assert_eq!(error_response.bot_id, Some("unknown_bot".to_string()));
 }
 
@@ -232,6 +323,75 @@ async fn test_choose_with_custom_bot_registry() {
     assert_eq!(response.status(), StatusCode::OK);
 }
 
+/// A deterministic bot that plays the first free cell and counts its moves.
+struct CountingBot(std::sync::atomic::AtomicUsize);
+
+impl gamey::YBot for CountingBot {
+    fn name(&self) -> &str {
+        "counting_bot"
+    }
+
+    fn choose_move(&self, board: &gamey::GameY) -> Option<gamey::Coordinates> {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        let first = board.available_cells_in_order().next()?;
+        Some(board.cell_coords(first))
+    }
+
+    fn is_deterministic(&self) -> bool {
+        true
+    }
+}
+
+#[tokio::test]
+async fn test_choose_caches_answers_of_deterministic_bots() {
+    let bot = Arc::new(CountingBot(Default::default()));
+    let state = AppState::new(YBotRegistry::new().with_bot(bot.clone()));
+    let app = test_app_with_state(state);
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+    let choose = |cache_control: Option<&str>| {
+        let mut request = Request::builder()
+            .method("POST")
+            .uri("/v1/ybot/choose/counting_bot")
+            .header("content-type", "application/json");
+        if let Some(value) = cache_control {
+            request = request.header("cache-control", value);
+        }
+        let request = request
+            .body(Body::from(serde_json::to_string(&yen).unwrap()))
+            .unwrap();
+        app.clone().oneshot(request)
+    };
+
+    let first = choose(None).await.unwrap();
+    assert_eq!(first.headers()["x-cache"], "MISS");
+    let second = choose(None).await.unwrap();
+    assert_eq!(second.headers()["x-cache"], "HIT");
+    let body = second.into_body().collect().await.unwrap().to_bytes();
+    let move_response: MoveResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(move_response.bot_id, "counting_bot");
+    assert_eq!(bot.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+    // Opting out searches again
+    let third = choose(Some("no-cache")).await.unwrap();
+    assert_eq!(third.status(), StatusCode::OK);
+    assert_eq!(bot.0.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/ybot/cache")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let stats: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(stats["hits"], 1);
+    assert_eq!(stats["misses"], 1);
+    assert_eq!(stats["entries"], 1);
+}
+
 #[tokio::test]
 async fn test_choose_with_empty_bot_registry() {
     // Create an empty registry
@@ -318,3 +478,826 @@ async fn test_get_on_choose_endpoint_returns_method_not_allowed() {
 
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
+
+// ============================================================================
+// Hosted games tests
+// ============================================================================
+
+/// Sends a JSON request to the app and returns the status and parsed body.
+async fn send_json(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    body: Option<serde_json::Value>,
+) -> (StatusCode, serde_json::Value) {
+    let request = Request::builder()
+        .method(method)
+        .uri(uri)
+        .header("content-type", "application/json");
+    let request = match body {
+        Some(body) => request.body(Body::from(body.to_string())).unwrap(),
+        None => request.body(Body::empty()).unwrap(),
+    };
+    let response = app.oneshot(request).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_create_play_and_export_game() {
+    let app = test_app();
+
+    let (status, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    assert_eq!(created["next_player"], 0);
+
+    let (_, played) = send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 2, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+    assert_eq!(played["next_player"], 1);
+
+    let (status, exported) =
+        send_json(app, "GET", &format!("/v1/games/{}", game_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(exported["yen"]["layout"], "B/../...");
+    assert_eq!(exported["history"][0]["type"], "placement");
+    assert_eq!(exported["history"][0]["player"], 0);
+}
+
+#[tokio::test]
+async fn test_watch_game_streams_turns_until_the_end() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 1 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 0, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+
+    // The game is over, so the stream ends after its current state
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri(format!("/v1/games/{}/events", game_id))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["content-type"],
+        mime::TEXT_EVENT_STREAM.as_ref()
+    );
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let body = String::from_utf8(body.to_vec()).unwrap();
+    let events: Vec<&str> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("event: "))
+        .collect();
+    assert_eq!(events, ["turn"]);
+    let data = body
+        .lines()
+        .find_map(|line| line.strip_prefix("data: "))
+        .unwrap();
+    let event: serde_json::Value = serde_json::from_str(data).unwrap();
+    assert_eq!(event["game_id"], game_id.as_str());
+    assert_eq!(event["winner"], 0);
+
+    let (_, body) = send_json(test_app(), "GET", "/v1/games/missing/events", None).await;
+    assert_eq!(body["code"], "GAME_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_board_images_of_a_game() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 2, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+
+    let get = |path: String| {
+        let app = app.clone();
+        async move {
+            let response = app
+                .oneshot(Request::builder().uri(path).body(Body::empty()).unwrap())
+                .await
+                .unwrap();
+            let status = response.status();
+            let content_type = response.headers()["content-type"].clone();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (
+                status,
+                content_type,
+                String::from_utf8(body.to_vec()).unwrap(),
+            )
+        }
+    };
+    let (status, content_type, svg) = get(format!("/v1/games/{}/board.svg", game_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "image/svg+xml");
+    assert_eq!(svg.matches("<polygon").count(), 6);
+    assert_eq!(svg.matches("<circle").count(), 1);
+
+    let (status, content_type, text) = get(format!("/v1/games/{}/board.txt", game_id)).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(content_type, "text/plain; charset=utf-8");
+    assert!(text.contains("Game of Y (Size 3)"));
+    assert!(!text.contains('\x1b'));
+
+    let (status, _, text) = get("/v1/games/missing/board.svg".to_string()).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(text.contains("GAME_NOT_FOUND"));
+}
+
+#[tokio::test]
+async fn test_create_and_play_hex_game() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3, "topology": "hex" })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    assert_eq!(created["yen"]["topology"], "hex");
+    assert_eq!(created["yen"]["layout"], ".../.../...");
+
+    let (_, played) = send_json(
+        app,
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 1, "y": 1, "z": 0 }
+        })),
+    )
+    .await;
+    assert_eq!(played["yen"]["layout"], ".../.B./...");
+}
+
+#[tokio::test]
+async fn test_play_move_out_of_turn_returns_error() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap();
+
+    let (_, body) = send_json(
+        app,
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 1,
+            "coords": { "x": 2, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+    let error: ErrorResponse = serde_json::from_value(body).unwrap();
+    assert!(error.message.contains("Wrong player"));
+    assert_eq!(error.code, ErrorCode::InvalidPlayerTurn);
+}
+
+#[tokio::test]
+async fn test_register_webhook() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 4 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap();
+
+    let (status, hooks) = send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/webhooks", game_id),
+        Some(serde_json::json!({ "url": "http://203.0.113.7/turn", "player": 1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(hooks[0]["url"], "http://203.0.113.7/turn");
+    assert_eq!(hooks[0]["player"], 1);
+
+    let (status, body) = send_json(
+        app,
+        "POST",
+        &format!("/v1/games/{}/webhooks", game_id),
+        Some(serde_json::json!({ "url": "http://localhost:9/turn" })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "INVALID_WEBHOOK");
+    assert_eq!(body["api_version"], "v1");
+}
+
+#[tokio::test]
+async fn test_get_game_state() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 2, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+
+    let (status, body) =
+        send_json(app, "GET", &format!("/v1/games/{}/state", game_id), None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"]["state"], "ongoing");
+    assert_eq!(body["status"]["next_player"], 1);
+    assert_eq!(body["board"][0]["player"], 0);
+    assert_eq!(body["available_cells"].as_array().unwrap().len(), 5);
+    assert_eq!(body["groups"][0]["cells"][0]["x"], 2);
+}
+
+#[tokio::test]
+async fn test_takeback_endpoints() {
+    let app = test_app();
+    let (_, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/games",
+        Some(serde_json::json!({ "size": 3 })),
+    )
+    .await;
+    let game_id = created["game_id"].as_str().unwrap().to_string();
+    send_json(
+        app.clone(),
+        "POST",
+        &format!("/v1/games/{}/moves", game_id),
+        Some(serde_json::json!({
+            "type": "placement",
+            "player": 0,
+            "coords": { "x": 2, "y": 0, "z": 0 }
+        })),
+    )
+    .await;
+
+    let takeback = format!("/v1/games/{}/takeback", game_id);
+    let (status, body) = send_json(
+        app.clone(),
+        "POST",
+        &takeback,
+        Some(serde_json::json!({ "player": 1 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "NOTHING_TO_TAKE_BACK");
+
+    let (status, body) = send_json(
+        app.clone(),
+        "POST",
+        &takeback,
+        Some(serde_json::json!({ "player": 0 })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["takeback_requested_by"], 0);
+
+    let (status, body) = send_json(
+        app,
+        "POST",
+        &format!("{}/answer", takeback),
+        Some(serde_json::json!({ "player": 1, "accept": true })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["history"].as_array().unwrap().len(), 0);
+    assert_eq!(body["next_player"], 0);
+    assert_eq!(body["annotations"][0]["move_number"], 0);
+}
+
+#[tokio::test]
+async fn test_get_unknown_game_returns_error() {
+    let (_, body) = send_json(test_app(), "GET", "/v1/games/missing", None).await;
+    let error: ErrorResponse = serde_json::from_value(body).unwrap();
+    assert!(error.message.contains("Game not found"));
+}
+
+#[tokio::test]
+async fn test_choose_endpoint_with_explanation() {
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/ybot/choose/random_bot?explain=true",
+        Some(serde_json::to_value(&yen).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let move_response: MoveResponse = serde_json::from_value(body).unwrap();
+    assert!(move_response.explanation.unwrap().contains("random"));
+}
+
+#[tokio::test]
+async fn test_choose_endpoint_without_explanation_by_default() {
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+    let (_, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/ybot/choose/random_bot",
+        Some(serde_json::to_value(&yen).unwrap()),
+    )
+    .await;
+    assert!(body.get("explanation").is_none());
+}
+
+// ============================================================================
+// Bot session tests
+// ============================================================================
+
+#[tokio::test]
+async fn test_bot_session_plays_incrementally() {
+    let app = test_app();
+    let yen = YEN::new(3, 0, vec!['B', 'R'], "./../...".to_string());
+
+    let (status, created) = send_json(
+        app.clone(),
+        "POST",
+        "/v1/ybot/session/random_bot",
+        Some(serde_json::to_value(&yen).unwrap()),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let session_id = created["session_id"].as_str().unwrap().to_string();
+    let move_uri = format!("/v1/ybot/session/random_bot/{}/move", session_id);
+
+    // The bot opens the game
+    let (_, first) = send_json(app.clone(), "POST", &move_uri, Some(serde_json::json!({}))).await;
+    let first: MoveResponse = serde_json::from_value(first).unwrap();
+
+    // Replaying the bot's own move as the opponent's is rejected
+    let (_, body) = send_json(
+        app.clone(),
+        "POST",
+        &move_uri,
+        Some(serde_json::json!({ "coords": first.coords })),
+    )
+    .await;
+    let error: ErrorResponse = serde_json::from_value(body).unwrap();
+    assert!(error.message.contains("occupied"));
+    assert_eq!(error.code, ErrorCode::Occupied);
+
+    let (status, closed) = send_json(
+        app,
+        "DELETE",
+        &format!("/v1/ybot/session/random_bot/{}", session_id),
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(closed["session_id"], session_id);
+}
+
+#[tokio::test]
+async fn test_bot_session_unknown_session() {
+    let (_, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/ybot/session/random_bot/missing/move",
+        Some(serde_json::json!({})),
+    )
+    .await;
+    let error: ErrorResponse = serde_json::from_value(body).unwrap();
+    assert!(error.message.contains("Session not found"));
+    assert_eq!(error.code, ErrorCode::SessionNotFound);
+}
+
+#[tokio::test]
+async fn test_hash_position() {
+    let yen = |layout: &str| {
+        serde_json::json!({
+            "size": 3,
+            "turn": 1,
+            "players": ["B", "R"],
+            "layout": layout
+        })
+    };
+    let (status, left) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/hash",
+        Some(yen("B/../...")),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(left["hash"].as_str().unwrap().len(), 16);
+
+    // Reflecting the board moves the stone to another corner
+    let (_, right) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/hash",
+        Some(yen("./../B..")),
+    )
+    .await;
+    assert_ne!(left["hash"], right["hash"]);
+    assert_eq!(left["canonical_hash"], right["canonical_hash"]);
+}
+
+#[tokio::test]
+async fn test_hash_position_rejects_invalid_yen() {
+    let (_, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/hash",
+        Some(serde_json::json!({
+            "size": 3,
+            "turn": 0,
+            "players": ["B", "R"],
+            "layout": "B/.."
+        })),
+    )
+    .await;
+    assert!(body["message"].as_str().unwrap().contains("Invalid YEN"));
+    assert_eq!(body["code"], "INVALID_YEN");
+}
+
+#[tokio::test]
+async fn test_validate_position_reports_all_issues() {
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/validate",
+        Some(serde_json::json!([
+            {"size": 3, "turn": 1, "players": ["B", "R"], "layout": "B/../..."},
+            {"size": 3, "turn": 0, "players": ["B", "R"], "layout": "X/B#/.RZ"},
+            {"size": 3, "turn": 0, "players": ["B", "R"], "layout": "B/BR/BRR"}
+        ])),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    let results = body["results"].as_array().unwrap();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0]["valid"], true);
+    assert_eq!(results[1]["valid"], false);
+    let codes: Vec<&str> = results[1]["issues"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|issue| issue["code"].as_str().unwrap())
+        .collect();
+    assert_eq!(codes, ["INVALID_CHAR", "BLOCKED_CELL", "INVALID_CHAR"]);
+    assert_eq!(results[2]["issues"][0]["code"], "UNREACHABLE_POSITION");
+}
+
+#[tokio::test]
+async fn test_validate_position_accepts_a_single_document() {
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/validate",
+        Some(serde_json::json!({"size": 2, "players": ["B", "R"], "layout": "./.."})),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["results"][0]["issues"][0]["code"], "INVALID_JSON");
+}
+
+#[tokio::test]
+async fn test_apply_move_to_a_position() {
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/move",
+        Some(serde_json::json!({
+            "yen": YEN::empty(3).unwrap(),
+            "movement": {"type": "placement", "player": 0, "coords": {"x": 2, "y": 0, "z": 0}}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["api_version"], "v1");
+    assert_eq!(body["yen"]["layout"], "B/../...");
+    assert_eq!(body["yen"]["turn"], 1);
+    assert_eq!(
+        body["status"],
+        serde_json::json!({"state": "ongoing", "next_player": 1})
+    );
+
+    // The second player resigns the position returned
+    let (status, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/move",
+        Some(serde_json::json!({
+            "yen": body["yen"],
+            "movement": {"type": "action", "player": 1, "action": "resign"}
+        })),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["status"],
+        serde_json::json!({"state": "finished", "winner": 0})
+    );
+    assert_eq!(body["yen"]["result"], "B+R");
+}
+
+#[tokio::test]
+async fn test_apply_move_rejects_illegal_moves() {
+    let yen = YEN::empty(3)
+        .unwrap()
+        .with_move(Coordinates::new(2, 0, 0), PlayerId::new(0))
+        .unwrap();
+    let placement = |player: u32, x: u32, y: u32, z: u32| {
+        serde_json::json!({
+            "yen": yen,
+            "movement": {"type": "placement", "player": player, "coords": {"x": x, "y": y, "z": z}}
+        })
+    };
+    for (request, expected) in [
+        (placement(1, 2, 0, 0), "OCCUPIED"),
+        (placement(0, 1, 1, 0), "INVALID_PLAYER_TURN"),
+        (placement(1, 3, 0, 0), "INVALID_COORDINATES"),
+    ] {
+        let (status, body) =
+            send_json(test_app(), "POST", "/v1/position/move", Some(request)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["code"], expected);
+        assert_eq!(body["api_version"], "v1");
+    }
+
+    let (_, body) = send_json(
+        test_app(),
+        "POST",
+        "/v1/position/move",
+        Some(serde_json::json!({
+            "yen": {"size": 1, "turn": 1, "players": ["B", "R"], "layout": "B"},
+            "movement": {"type": "action", "player": 1, "action": "swap"}
+        })),
+    )
+    .await;
+    assert_eq!(body["code"], "GAME_OVER");
+}
+
+#[tokio::test]
+async fn test_engine_errors_answer_their_code() {
+    let (status, body) = send_json(test_app(), "GET", "/v1/games/missing", None).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "GAME_NOT_FOUND");
+    assert_eq!(body["api_version"], "v1");
+
+    let (status, body) = send_json(
+        test_app(),
+        "DELETE",
+        "/v1/ybot/session/random_bot/missing",
+        None,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["bot_id"], "random_bot");
+}
+
+#[tokio::test]
+async fn test_demo_mode_limits_board_size_and_requests_per_client() {
+    let bots = YBotRegistry::new().with_bot(Arc::new(gamey::MctsBot::new(u32::MAX)));
+    let limits = gamey::bot_server::demo::DemoLimits {
+        max_board_size: 7,
+        max_requests_per_client: 1,
+        ..Default::default()
+    };
+    let state = AppState::new(bots)
+        .with_bot_timeout(Duration::from_millis(200))
+        .with_demo(limits);
+    let app = test_app_with_state(state);
+    let choose = |size: u32| {
+        let yen = YEN::from(&gamey::GameY::new(size));
+        Request::builder()
+            .method("POST")
+            .uri("/v1/ybot/choose/mcts_bot")
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_string(&yen).unwrap()))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(choose(8)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.code, ErrorCode::InvalidBoardSize);
+
+    // The first search runs until it times out, the second one is rejected
+    let (first, second) = tokio::join!(
+        app.clone().oneshot(choose(7)),
+        app.clone().oneshot(choose(7))
+    );
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    let second = second.unwrap();
+    assert_eq!(second.status(), StatusCode::OK);
+    let body = second.into_body().collect().await.unwrap().to_bytes();
+    let error: ErrorResponse = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.code, ErrorCode::TooManyRequests);
+}
+
+#[tokio::test]
+async fn test_admin_reload_rebuilds_the_bots() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("bots.toml");
+    std::fs::write(&file, "[mcts_bot]\nmax_nodes = 1000\n").unwrap();
+    let state = AppState::new(YBotRegistry::new().with_bot(Arc::new(RandomBot)))
+        .with_bots_config(file.clone())
+        .with_admin_key("secret");
+    let app = test_app_with_state(state.clone());
+
+    let (status, body) = send_admin(app.clone(), "POST", "/v1/admin/reload", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["bots"].as_array().unwrap().len(), 3);
+    assert!(state.bots().find("mcts_bot").is_some());
+
+    // A broken file keeps the bots that were loaded
+    std::fs::write(&file, "[mcts_bot]\nmax_nodes = \"many\"\n").unwrap();
+    let (status, body) = send_admin(app, "POST", "/v1/admin/reload", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "INVALID_CONFIG");
+    assert_eq!(state.bots().names().len(), 3);
+}
+
+async fn send_admin(
+    app: axum::Router,
+    method: &str,
+    uri: &str,
+    key: Option<&str>,
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::builder().method(method).uri(uri);
+    if let Some(key) = key {
+        request = request.header("authorization", format!("Bearer {}", key));
+    }
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    (status, serde_json::from_slice(&bytes).unwrap())
+}
+
+#[tokio::test]
+async fn test_admin_endpoints_need_the_admin_key() {
+    let app = test_app();
+    let (status, body) = send_admin(app, "GET", "/v1/admin/games", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "UNAUTHORIZED");
+
+    let app = test_app_with_state(create_default_state().with_admin_key("secret"));
+    for key in [None, Some("wrong")] {
+        let (status, body) = send_admin(app.clone(), "GET", "/v1/admin/config", key).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["code"], "UNAUTHORIZED");
+    }
+    let (status, body) = send_admin(app, "GET", "/v1/admin/config", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["bot_timeout_secs"], 30.0);
+    assert!(body.get("admin_key").is_none());
+}
+
+#[tokio::test]
+async fn test_admin_lists_and_deletes_games() {
+    let state = create_default_state().with_admin_key("secret");
+    let app = test_app_with_state(state.clone());
+    let game = state.games().create(5, gamey::BoardTopology::Y).unwrap();
+    let yen = serde_json::to_value(YEN::from(&gamey::GameY::new(4))).unwrap();
+    let (status, _) = send_json(app.clone(), "POST", "/v1/ybot/session/random_bot", Some(yen)).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = send_admin(app.clone(), "GET", "/v1/admin/games", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["games"][0]["game_id"], game.game_id.as_str());
+    assert_eq!(body["games"][0]["next_player"], 0);
+    assert_eq!(body["sessions"][0]["bot_id"], "random_bot");
+    assert_eq!(body["sessions"][0]["searching"], false);
+
+    let uri = format!("/v1/admin/games/{}", game.game_id);
+    let (status, _) = send_admin(app.clone(), "DELETE", &uri, Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(state.games().get(&game.game_id).is_none());
+    let (status, body) = send_admin(app, "DELETE", &uri, Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["code"], "GAME_NOT_FOUND");
+}
+
+#[tokio::test]
+async fn test_admin_reports_bot_latencies() {
+    let state = create_default_state().with_admin_key("secret");
+    let app = test_app_with_state(state);
+    let yen = serde_json::to_value(YEN::from(&gamey::GameY::new(4))).unwrap();
+    for _ in 0..3 {
+        let (status, _) =
+            send_json(app.clone(), "POST", "/v1/ybot/choose/random_bot", Some(yen.clone())).await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    let (status, body) = send_admin(app, "GET", "/v1/admin/bots", Some("secret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["bots"][0]["bot_id"], "random_bot");
+    assert_eq!(body["bots"][0]["searches"], 3);
+    assert_eq!(body["bots"][0]["timeouts"], 0);
+}
+
+#[tokio::test]
+async fn test_audit_log_records_the_moves_served() {
+    use gamey::bot_server::audit::{AuditConfig, AuditEntry, AuditLog, AuditedRequest};
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("audit.jsonl");
+    let log = AuditLog::open(AuditConfig::new(path.clone())).unwrap();
+    let app = test_app_with_state(create_default_state().with_audit_log(log));
+    let game = gamey::GameY::new(3);
+    let yen = serde_json::to_value(YEN::from(&game)).unwrap();
+    let (_, answer) =
+        send_json(app.clone(), "POST", "/v1/ybot/choose/random_bot", Some(yen)).await;
+    let (_, created) =
+        send_json(app.clone(), "POST", "/v1/games", Some(serde_json::json!({ "size": 3 }))).await;
+    let game_id = created["game_id"].as_str().unwrap();
+    let out_of_turn = serde_json::json!({
+        "type": "placement",
+        "player": 1,
+        "coords": { "x": 2, "y": 0, "z": 0 }
+    });
+    let uri = format!("/v1/games/{}/moves", game_id);
+    send_json(app, "POST", &uri, Some(out_of_turn)).await;
+
+    let entries: Vec<AuditEntry> = std::fs::read_to_string(&path)
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(entries.len(), 2);
+    let choose = &entries[0];
+    assert_eq!(choose.request, AuditedRequest::Choose);
+    assert_eq!(choose.bot_id.as_deref(), Some("random_bot"));
+    assert_eq!(choose.position_hash, format!("{:016x}", game.zobrist_hash()));
+    assert_eq!(
+        serde_json::to_value(choose.returned).unwrap(),
+        answer["coords"]
+    );
+    let played = &entries[1];
+    assert_eq!(played.request, AuditedRequest::GameMove);
+    assert_eq!(played.id.as_deref(), Some(game_id));
+    assert_eq!(played.sent, Some(gamey::Coordinates::new(2, 0, 0)));
+    assert_eq!(played.error, Some(ErrorCode::InvalidPlayerTurn));
+}
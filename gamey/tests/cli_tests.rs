@@ -1,4 +1,4 @@
-use gamey::{Command, Mode, parse_command, parse_idx};
+use gamey::{Command, Glyph, Mode, parse_command, parse_idx};
 
 // =============================================================================
 // parse_command Tests
@@ -40,6 +40,24 @@ fn test_parse_command_resign() {
     assert_eq!(command, Command::Resign);
 }
 
+#[test]
+fn test_parse_command_aliases() {
+    assert_eq!(parse_command("r", 10), Command::Resign);
+    assert_eq!(
+        parse_command("s game.yen", 10),
+        Command::Save {
+            filename: "game.yen".to_string()
+        }
+    );
+    assert_eq!(
+        parse_command("l game.yen", 10),
+        Command::Load {
+            filename: "game.yen".to_string()
+        }
+    );
+    assert!(matches!(parse_command("s", 10), Command::Error { .. }));
+}
+
 #[test]
 fn test_parse_command_help() {
     let command = parse_command("help", 10);
@@ -52,12 +70,94 @@ fn test_parse_command_exit() {
     assert_eq!(command, Command::Exit);
 }
 
+#[test]
+fn test_parse_command_history_browsing() {
+    assert_eq!(parse_command("history", 10), Command::History);
+    assert_eq!(parse_command("back", 10), Command::Back);
+    assert_eq!(parse_command("forward", 10), Command::Forward);
+    assert_eq!(parse_command("show 3", 10), Command::ShowMove { number: 3 });
+    assert!(matches!(parse_command("show", 10), Command::Error { .. }));
+    assert!(matches!(parse_command("show x", 10), Command::Error { .. }));
+}
+
+#[test]
+fn test_parse_command_takeback() {
+    assert_eq!(parse_command("takeback", 10), Command::Takeback);
+}
+
+#[test]
+fn test_parse_command_comment() {
+    assert_eq!(
+        parse_command("comment ?! too slow here", 10),
+        Command::Comment {
+            glyph: Some(Glyph::Dubious),
+            text: "too slow here".to_string()
+        }
+    );
+    assert_eq!(
+        parse_command("comment !!", 10),
+        Command::Comment {
+            glyph: Some(Glyph::Brilliant),
+            text: String::new()
+        }
+    );
+    assert_eq!(
+        parse_command("comment wins the race", 10),
+        Command::Comment {
+            glyph: None,
+            text: "wins the race".to_string()
+        }
+    );
+    assert!(matches!(
+        parse_command("comment", 10),
+        Command::Error { .. }
+    ));
+}
+
 #[test]
 fn test_parse_command_show_colors() {
     let command = parse_command("show_colors", 10);
     assert_eq!(command, Command::ShowColors);
 }
 
+#[test]
+fn test_parse_command_show_labels() {
+    let command = parse_command("show_labels", 10);
+    assert_eq!(command, Command::ShowEdgeLabels);
+}
+
+#[test]
+fn test_parse_command_show_last() {
+    let command = parse_command("show_last", 10);
+    assert_eq!(command, Command::HighlightLastMove);
+}
+
+#[test]
+fn test_parse_command_show_numbers() {
+    let command = parse_command("show_numbers", 10);
+    assert_eq!(command, Command::ShowMoveNumbers);
+}
+
+#[test]
+fn test_parse_command_solve() {
+    let command = parse_command("solve", 10);
+    assert_eq!(command, Command::Solve);
+}
+
+#[test]
+fn test_parse_command_dump() {
+    assert_eq!(
+        parse_command("dump", 10),
+        Command::Dump { filename: None }
+    );
+    assert_eq!(
+        parse_command("dump state.json", 10),
+        Command::Dump {
+            filename: Some("state.json".to_string())
+        }
+    );
+}
+
 #[test]
 fn test_parse_command_show_coords() {
     let command = parse_command("show_coords", 10);
@@ -234,6 +334,55 @@ fn test_parse_idx_large_valid_number() {
     assert_eq!(result, Ok(999));
 }
 
+// =============================================================================
+// parse_cell_in Tests
+// =============================================================================
+
+#[test]
+fn test_parse_cell_formats() {
+    let game = gamey::GameY::new(3);
+    let parse = |part| gamey::parse_cell_in(part, &game, gamey::Lang::En);
+    // Index 4 is the middle cell of the bottom row, b3
+    assert_eq!(parse("4"), Ok(4));
+    assert_eq!(parse("0,1,1"), Ok(4));
+    assert_eq!(parse("b3"), Ok(4));
+    assert_eq!(parse("B3"), Ok(4));
+    assert_eq!(parse("2,0,0"), Ok(0));
+    assert_eq!(parse("a1"), Ok(0));
+}
+
+#[test]
+fn test_parse_cell_errors_per_format() {
+    let game = gamey::GameY::new(3);
+    let parse = |part| gamey::parse_cell_in(part, &game, gamey::Lang::En).unwrap_err();
+    assert!(parse("6").contains("out of bounds"));
+    assert!(parse("1,1").contains("three numbers x,y,z"));
+    assert!(parse("1,a,1").contains("three numbers x,y,z"));
+    assert!(parse("1,1,1").contains("off the board"));
+    assert!(parse("4294967295,1,0").contains("off the board"));
+    assert!(parse("c1").contains("Invalid cell name c1"));
+    assert!(parse("a9").contains("Invalid cell name"));
+    assert!(parse("#").contains("not a number"));
+}
+
+#[test]
+fn test_parse_command_place_on_a_board() {
+    let game = gamey::GameY::with_topology(3, gamey::BoardTopology::Hex);
+    let parse = |input| gamey::parse_command_on(input, &game, gamey::Lang::En);
+    assert_eq!(parse("place 1,2,0"), Command::Place { idx: 5 });
+    assert_eq!(parse("place c2"), Command::Place { idx: 5 });
+    assert_eq!(parse("c2"), Command::Place { idx: 5 });
+    assert_eq!(parse("place 5"), Command::Place { idx: 5 });
+    assert!(
+        matches!(parse("place"), Command::Error { message } if message.contains("Cell required"))
+    );
+    assert!(
+        matches!(parse("place d1"), Command::Error { message } if message.contains("Invalid cell name"))
+    );
+    // Commands still take precedence over cell names
+    assert_eq!(parse("back"), Command::Back);
+}
+
 // =============================================================================
 // Mode enum Tests
 // =============================================================================
@@ -250,19 +399,11 @@ fn test_mode_display_human() {
     assert_eq!(format!("{}", mode), "human");
 }
 
-#[test]
-fn test_mode_display_server() {
-    let mode = Mode::Server;
-    assert_eq!(format!("{}", mode), "server");
-}
-
 #[test]
 fn test_mode_equality() {
     assert_eq!(Mode::Computer, Mode::Computer);
     assert_eq!(Mode::Human, Mode::Human);
-    assert_eq!(Mode::Server, Mode::Server);
     assert_ne!(Mode::Computer, Mode::Human);
-    assert_ne!(Mode::Human, Mode::Server);
 }
 
 // =============================================================================
@@ -270,15 +411,58 @@ fn test_mode_equality() {
 // =============================================================================
 
 use clap::Parser;
-use gamey::CliArgs;
+use gamey::{CliArgs, CliCommand, PlayArgs, SaveOptions, ServeArgs};
+
+/// Parses the options of `gamey play`.
+fn play_args(options: &[&str]) -> PlayArgs {
+    let args = CliArgs::try_parse_from(["gamey", "play"].iter().chain(options)).unwrap();
+    match args.command {
+        Some(CliCommand::Play(play)) => play,
+        other => panic!("expected the play command, got {:?}", other),
+    }
+}
+
+/// Parses the options of `gamey serve`.
+fn serve_args(options: &[&str]) -> ServeArgs {
+    let args = CliArgs::try_parse_from(["gamey", "serve"].iter().chain(options)).unwrap();
+    match args.command {
+        Some(CliCommand::Serve(serve)) => serve,
+        other => panic!("expected the serve command, got {:?}", other),
+    }
+}
+
+/// Parses a command line of any version, as the `gamey` binary does.
+fn parse_legacy(args: &[&str]) -> CliArgs {
+    CliArgs::try_parse_legacy(args.iter().copied()).unwrap().0
+}
+
+/// Returns the options of the play command, the default one, of `args`.
+fn play_of(args: CliArgs) -> PlayArgs {
+    match args.command {
+        Some(CliCommand::Play(play)) => play,
+        None => PlayArgs::default(),
+        other => panic!("expected the play command, got {:?}", other),
+    }
+}
+
+/// Returns the options of the serve command of `args`.
+fn serve_of(args: CliArgs) -> ServeArgs {
+    match args.command {
+        Some(CliCommand::Serve(serve)) => serve,
+        other => panic!("expected the serve command, got {:?}", other),
+    }
+}
 
 #[test]
 fn test_cli_args_default_values() {
     let args = CliArgs::try_parse_from(["gamey"]).unwrap();
     assert_eq!(args.size, 7);
-    assert_eq!(args.mode, Mode::Human);
-    assert_eq!(args.bot, "random_bot");
-    assert_eq!(args.port, 3000);
+    assert_eq!(args.command, None);
+    let play = play_of(args);
+    assert_eq!(play.mode, Mode::Human);
+    assert_eq!(play.bot, "random_bot");
+    assert_eq!(play, play_args(&[]));
+    assert_eq!(serve_args(&[]).port, None);
 }
 
 #[test]
@@ -295,55 +479,55 @@ fn test_cli_args_custom_size_short() {
 
 #[test]
 fn test_cli_args_mode_computer() {
-    let args = CliArgs::try_parse_from(["gamey", "--mode", "computer"]).unwrap();
-    assert_eq!(args.mode, Mode::Computer);
+    let args = parse_legacy(&["gamey", "--mode", "computer"]);
+    assert_eq!(play_of(args).mode, Mode::Computer);
 }
 
 #[test]
 fn test_cli_args_mode_human() {
-    let args = CliArgs::try_parse_from(["gamey", "--mode", "human"]).unwrap();
-    assert_eq!(args.mode, Mode::Human);
+    let args = parse_legacy(&["gamey", "--mode", "human"]);
+    assert_eq!(play_of(args).mode, Mode::Human);
 }
 
 #[test]
 fn test_cli_args_mode_server() {
-    let args = CliArgs::try_parse_from(["gamey", "--mode", "server"]).unwrap();
-    assert_eq!(args.mode, Mode::Server);
+    let args = parse_legacy(&["gamey", "--mode", "server"]);
+    assert!(!serve_of(args).jsonrpc);
 }
 
 #[test]
 fn test_cli_args_mode_short() {
-    let args = CliArgs::try_parse_from(["gamey", "-m", "computer"]).unwrap();
-    assert_eq!(args.mode, Mode::Computer);
+    let args = parse_legacy(&["gamey", "-m", "computer"]);
+    assert_eq!(play_of(args).mode, Mode::Computer);
 }
 
 #[test]
 fn test_cli_args_custom_bot() {
-    let args = CliArgs::try_parse_from(["gamey", "--bot", "smart_bot"]).unwrap();
-    assert_eq!(args.bot, "smart_bot");
+    let args = parse_legacy(&["gamey", "--bot", "smart_bot"]);
+    assert_eq!(play_of(args).bot, "smart_bot");
 }
 
 #[test]
 fn test_cli_args_custom_bot_short() {
-    let args = CliArgs::try_parse_from(["gamey", "-b", "my_bot"]).unwrap();
-    assert_eq!(args.bot, "my_bot");
+    let args = parse_legacy(&["gamey", "-b", "my_bot"]);
+    assert_eq!(play_of(args).bot, "my_bot");
 }
 
 #[test]
 fn test_cli_args_custom_port() {
-    let args = CliArgs::try_parse_from(["gamey", "--port", "8080"]).unwrap();
-    assert_eq!(args.port, 8080);
+    let args = parse_legacy(&["gamey", "--port", "8080"]);
+    assert_eq!(serve_of(args).port, Some(8080));
 }
 
 #[test]
 fn test_cli_args_custom_port_short() {
-    let args = CliArgs::try_parse_from(["gamey", "-p", "9000"]).unwrap();
-    assert_eq!(args.port, 9000);
+    let args = parse_legacy(&["gamey", "-p", "9000"]);
+    assert_eq!(serve_of(args).port, Some(9000));
 }
 
 #[test]
 fn test_cli_args_combined_options() {
-    let args = CliArgs::try_parse_from([
+    let args = parse_legacy(&[
         "gamey",
         "-s",
         "9",
@@ -353,17 +537,166 @@ fn test_cli_args_combined_options() {
         "advanced_bot",
         "-p",
         "5000",
+    ]);
+    assert_eq!(args.size, 9);
+    let play = play_of(args);
+    assert_eq!(play.mode, Mode::Computer);
+    assert_eq!(play.bot, "advanced_bot");
+    // Board options can also follow the command
+    let args = CliArgs::try_parse_from(["gamey", "serve", "-p", "5000", "-s", "9"]).unwrap();
+    assert_eq!(args.size, 9);
+}
+
+#[test]
+fn test_cli_args_options_belong_to_their_command() {
+    // Game options are not server options and the other way around
+    assert!(CliArgs::try_parse_from(["gamey", "--mode", "computer"]).is_err());
+    assert!(CliArgs::try_parse_from(["gamey", "play", "--port", "3000"]).is_err());
+    assert!(CliArgs::try_parse_from(["gamey", "serve", "--ponder"]).is_err());
+    assert!(CliArgs::try_parse_from(["gamey", "tournament", "--bot", "x"]).is_err());
+}
+
+#[test]
+fn test_cli_args_commands_without_the_prompt() {
+    let args = CliArgs::try_parse_from(["gamey", "bots"]).unwrap();
+    assert_eq!(args.command, Some(CliCommand::Bots));
+    let args = CliArgs::try_parse_from(["gamey", "solve", "-", "--size", "4"]).unwrap();
+    assert_eq!(
+        args.command,
+        Some(CliCommand::Solve {
+            position: Some("-".into())
+        })
+    );
+    let serve = serve_args(&["--jsonrpc", "--bot", "mcts_bot"]);
+    assert!(serve.jsonrpc);
+    assert_eq!(serve.bot, "mcts_bot");
+}
+
+#[test]
+fn test_run_solve() {
+    let args = CliArgs::try_parse_from(["gamey", "solve", "--size", "3"]).unwrap();
+    assert!(gamey::run_solve(&args, None).is_ok());
+    // The solver only handles Y boards
+    let args = CliArgs::try_parse_from(["gamey", "solve", "--game", "hex"]).unwrap();
+    assert!(gamey::run_solve(&args, None).is_err());
+}
+
+#[test]
+fn test_run_adjudicate() {
+    let temp = tempfile::tempdir().unwrap();
+    let position = temp.path().join("pos.yen");
+    let mut game = gamey::GameY::new(4);
+    game.play_move_list("4 0").unwrap();
+    game.save_to_file(&position).unwrap();
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "adjudicate",
+        position.to_str().unwrap(),
+        "--playouts",
+        "20",
     ])
     .unwrap();
+    match &args.command {
+        Some(CliCommand::Adjudicate {
+            bot,
+            playouts,
+            threshold,
+            ..
+        }) => {
+            assert_eq!(bot, "random_bot");
+            assert_eq!(*playouts, 20);
+            assert_eq!(*threshold, 0.75);
+        }
+        other => panic!("expected the adjudicate command, got {:?}", other),
+    }
+    assert!(gamey::run_adjudicate(&args, &position, "random_bot", 20, 0.75).is_ok());
+    assert!(gamey::run_adjudicate(&args, &position, "random_bot", 20, 0.4).is_err());
+    assert!(gamey::run_adjudicate(&args, &position, "no_such_bot", 20, 0.75).is_err());
+
+    game.play_move_list("resign").unwrap();
+    game.save_to_file(&position).unwrap();
+    let error = gamey::run_adjudicate(&args, &position, "random_bot", 20, 0.75).unwrap_err();
+    assert!(error.to_string().contains("nothing to adjudicate"));
+}
+
+#[test]
+fn test_cli_args_deprecated_forms_still_parse() {
+    let legacy = |args: &[&str]| {
+        let (args, form) = CliArgs::try_parse_legacy(args.iter().copied()).unwrap();
+        (args, form.map(|form| (form.old, form.new)))
+    };
+
+    let (args, deprecated) = legacy(&["gamey", "--mode", "server", "--port", "8080", "-s", "9"]);
+    assert_eq!(deprecated, Some(("--mode server", "serve")));
     assert_eq!(args.size, 9);
-    assert_eq!(args.mode, Mode::Computer);
-    assert_eq!(args.bot, "advanced_bot");
-    assert_eq!(args.port, 5000);
+    let Some(CliCommand::Serve(serve)) = args.command else {
+        panic!("expected the serve command");
+    };
+    assert_eq!(serve.port, Some(8080));
+    assert!(!serve.jsonrpc);
+
+    let (args, deprecated) = legacy(&["gamey", "--mode=jsonrpc", "--bot", "mcts_bot"]);
+    assert_eq!(deprecated, Some(("--mode jsonrpc", "serve --jsonrpc")));
+    let serve = serve_args(&["--jsonrpc", "--bot", "mcts_bot"]);
+    assert_eq!(args.command, Some(CliCommand::Serve(serve)));
+
+    let (args, deprecated) = legacy(&["gamey", "-m", "computer", "-b", "mcts_bot"]);
+    assert_eq!(deprecated, Some(("--mode", "play --mode")));
+    let play = play_args(&["--mode", "computer", "--bot", "mcts_bot"]);
+    assert_eq!(args.command, Some(CliCommand::Play(play)));
+
+    let (args, deprecated) = legacy(&["gamey", "play", "--moves", "4 7", "--print-final"]);
+    assert_eq!(deprecated, Some(("play --moves", "replay --moves")));
+    assert_eq!(
+        args.command,
+        Some(CliCommand::Replay {
+            moves: Some("4 7".to_string()),
+            print_final: true
+        })
+    );
+
+    // Current command lines are left as they are
+    let (args, deprecated) = legacy(&["gamey", "play", "--mode", "computer"]);
+    assert_eq!(deprecated, None);
+    let play = play_args(&["-m", "computer"]);
+    assert_eq!(args.command, Some(CliCommand::Play(play)));
+    assert_eq!(legacy(&["gamey"]).1, None);
+    assert!(CliArgs::try_parse_legacy(["gamey", "--mode", "invalid"]).is_err());
+
+    let (args, deprecated) = legacy(&["gamey", "-b", "random_bot"]);
+    assert_eq!(deprecated, Some(("--bot", "play --bot")));
+    let play = play_args(&["-b", "random_bot"]);
+    assert_eq!(args.command, Some(CliCommand::Play(play)));
+
+    let (args, deprecated) = legacy(&["gamey", "--port", "8080"]);
+    assert_eq!(deprecated, Some(("--port", "serve --port")));
+    let serve = serve_args(&["-p", "8080"]);
+    assert_eq!(args.command, Some(CliCommand::Serve(serve)));
+
+    // Play modes never used the port, which is dropped
+    let (args, deprecated) = legacy(&[
+        "gamey",
+        "--mode",
+        "computer",
+        "-b",
+        "random_bot",
+        "--port",
+        "3000",
+    ]);
+    assert_eq!(deprecated, Some(("--mode", "play --mode")));
+    let play = play_args(&["--mode", "computer", "-b", "random_bot"]);
+    assert_eq!(args.command, Some(CliCommand::Play(play)));
+    let (args, _) = legacy(&["gamey", "--port=3000", "-m", "human"]);
+    assert_eq!(args.command, Some(CliCommand::Play(play_args(&[]))));
 }
 
 #[test]
 fn test_cli_args_invalid_mode() {
-    let result = CliArgs::try_parse_from(["gamey", "--mode", "invalid"]);
+    let result = CliArgs::try_parse_legacy(["gamey", "--mode", "invalid"]);
+    assert!(result.is_err());
+    let result = CliArgs::try_parse_from(["gamey", "play", "--mode", "invalid"]);
+    assert!(result.is_err());
+    let result = CliArgs::try_parse_from(["gamey", "play", "--mode", "server"]);
     assert!(result.is_err());
 }
 
@@ -375,7 +708,9 @@ fn test_cli_args_invalid_size_not_number() {
 
 #[test]
 fn test_cli_args_invalid_port_not_number() {
-    let result = CliArgs::try_parse_from(["gamey", "--port", "not_a_port"]);
+    let result = CliArgs::try_parse_legacy(["gamey", "--port", "not_a_port"]);
+    assert!(result.is_err());
+    let result = CliArgs::try_parse_from(["gamey", "serve", "--port", "not_a_port"]);
     assert!(result.is_err());
 }
 
@@ -390,3 +725,869 @@ fn test_cli_args_version_flag() {
     let result = CliArgs::try_parse_from(["gamey", "--version"]);
     assert!(result.is_err()); // --version causes an error (but it's intentional)
 }
+
+#[test]
+fn test_cli_args_ponder_flag() {
+    let args = parse_legacy(&["gamey", "--mode", "computer", "--ponder"]);
+    assert!(play_of(args).ponder);
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert!(!play_of(args).ponder);
+}
+
+#[test]
+fn test_cli_args_yes_flag() {
+    assert!(play_args(&["--yes"]).yes);
+    assert!(play_args(&["-y"]).yes);
+    assert!(!PlayArgs::default().yes);
+}
+
+#[test]
+fn test_cli_args_teach_flag() {
+    assert!(play_args(&["--teach"]).teach);
+    assert!(!PlayArgs::default().teach);
+}
+
+#[test]
+fn test_cli_args_handicap() {
+    let args = CliArgs::try_parse_from(["gamey", "--handicap", "2"]).unwrap();
+    assert_eq!(args.handicap, 2);
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.handicap, 0);
+}
+
+#[test]
+fn test_cli_args_validate_subcommand() {
+    let args = CliArgs::try_parse_from(["gamey", "validate", "a.yen", "b.yen"]).unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Validate {
+            files: vec!["a.yen".into(), "b.yen".into()]
+        })
+    );
+    assert!(CliArgs::try_parse_from(["gamey", "validate"]).is_err());
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.command, None);
+}
+
+#[test]
+fn test_validate_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let valid = dir.path().join("valid.yen");
+    std::fs::write(
+        &valid,
+        r#"{"size": 2, "turn": 1, "players": ["B", "R"], "layout": "B/.."}"#,
+    )
+    .unwrap();
+    assert!(gamey::validate_file(&valid).is_empty());
+    let invalid = dir.path().join("invalid.yen");
+    std::fs::write(
+        &invalid,
+        r#"{"size": 2, "turn": 0, "players": ["B", "R"], "layout": "B/.."}"#,
+    )
+    .unwrap();
+    let issues = gamey::validate_file(&invalid);
+    assert_eq!(issues[0].code, gamey::ValidationCode::TurnMismatch);
+    let missing = gamey::validate_file(&dir.path().join("missing.yen"));
+    assert_eq!(missing[0].code, gamey::ValidationCode::InvalidJson);
+    assert!(!gamey::run_validate(&[valid, invalid], gamey::Lang::En));
+}
+
+#[test]
+fn test_cli_args_lang() {
+    let args = CliArgs::try_parse_from(["gamey", "--lang", "es"]).unwrap();
+    assert_eq!(args.lang, gamey::Lang::Es);
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.lang, gamey::Lang::En);
+    assert!(CliArgs::try_parse_from(["gamey", "--lang", "fr"]).is_err());
+}
+
+#[test]
+fn test_parse_command_in_spanish() {
+    let command = gamey::parse_command_in("save", 10, gamey::Lang::Es);
+    assert_eq!(
+        command,
+        Command::Error {
+            message: "El comando save necesita un nombre de fichero".to_string()
+        }
+    );
+    let result = gamey::parse_idx_in("abc", 10, gamey::Lang::Es);
+    assert!(result.unwrap_err().contains("no es un número"));
+}
+
+#[test]
+fn test_cli_args_replay_subcommand() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "replay",
+        "--moves",
+        "4 7",
+        "--size",
+        "5",
+        "--print-final",
+    ])
+    .unwrap();
+    assert_eq!(args.size, 5);
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Replay {
+            moves: Some("4 7".to_string()),
+            print_final: true
+        })
+    );
+}
+
+#[test]
+fn test_cli_args_openings() {
+    let args = CliArgs::try_parse_from([
+        "gamey", "openings", "add", "low", "--moves", "12 5", "--size", "5",
+    ])
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Openings {
+            action: Some(gamey::OpeningsAction::Add {
+                name: "low".to_string(),
+                moves: "12 5".to_string(),
+                description: String::new()
+            })
+        })
+    );
+
+    let args = CliArgs::try_parse_from(["gamey", "--opening", "center-3"]).unwrap();
+    let game = gamey::new_game(&args).unwrap();
+    assert_eq!(game.board_size(), 7);
+    assert_eq!(game.history().len(), 3);
+    let args = CliArgs::try_parse_from(["gamey", "--opening", "no-such-line"]).unwrap();
+    assert!(args.check().is_err());
+}
+
+#[test]
+fn test_cli_args_bots_config() {
+    let serve = serve_args(&["--bots-config", "limits.toml"]);
+    assert_eq!(serve.bots_config(), Some("limits.toml".into()));
+}
+
+#[test]
+fn test_cli_args_demo() {
+    assert!(!serve_args(&[]).demo);
+    assert!(serve_args(&["--demo"]).demo);
+}
+
+#[test]
+fn test_server_options_can_be_set_from_the_environment() {
+    use clap::CommandFactory;
+    let command = ServeArgs::command();
+    let variables = [
+        ("config", "GAMEY_CONFIG"),
+        ("host", "GAMEY_HOST"),
+        ("port", "GAMEY_PORT"),
+        ("grpc_port", "GAMEY_GRPC_PORT"),
+        ("games_dir", "GAMEY_GAMES_DIR"),
+        ("bots_config", "GAMEY_BOTS_CONFIG"),
+        ("demo", "GAMEY_DEMO"),
+        ("bot_timeout", "GAMEY_BOT_TIMEOUT"),
+        ("move_cache_entries", "GAMEY_MOVE_CACHE_ENTRIES"),
+        ("admin_key", "GAMEY_ADMIN_KEY"),
+        ("audit_log", "GAMEY_AUDIT_LOG"),
+    ];
+    for (option, variable) in variables {
+        let arg = command
+            .get_arguments()
+            .find(|arg| arg.get_id() == option)
+            .unwrap();
+        assert_eq!(arg.get_env(), Some(std::ffi::OsStr::new(variable)));
+    }
+}
+
+#[test]
+fn test_play_moves() {
+    let args = CliArgs::try_parse_from(["gamey", "replay", "--size", "3"]).unwrap();
+    let mut game = gamey::new_game(&args).unwrap();
+    gamey::play_moves(&mut game, "0 1 3", gamey::Lang::En).unwrap();
+    assert_eq!(game.total_cells() - game.available_cells().len() as u32, 3);
+
+    let mut game = gamey::new_game(&args).unwrap();
+    let err = gamey::play_moves(&mut game, "0 0", gamey::Lang::En).unwrap_err();
+    assert!(err.starts_with("Move 2 (0) rejected"));
+    let err = gamey::play_moves(&mut game, "x", gamey::Lang::En).unwrap_err();
+    assert!(err.contains("not a number"));
+}
+
+#[test]
+fn test_play_moves_with_annotations() {
+    let args = CliArgs::try_parse_from(["gamey", "replay", "--size", "3"]).unwrap();
+    let mut game = gamey::new_game(&args).unwrap();
+    let moves = "{opening} 0! 1?? {loses the corner}3";
+    gamey::play_moves(&mut game, moves, gamey::Lang::En).unwrap();
+    assert_eq!(game.history().len(), 3);
+    assert_eq!(
+        gamey::history_lines(&game),
+        vec![
+            "     {opening}",
+            "  1. 0: 0! (2, 0, 0)",
+            "  2. 1: 1?? (1, 0, 1)",
+            "     {loses the corner}",
+            "  3. 0: 3 (0, 0, 2)",
+        ]
+    );
+
+    let mut game = gamey::new_game(&args).unwrap();
+    let err = gamey::play_moves(&mut game, "{a comment} 0 1?x", gamey::Lang::En).unwrap_err();
+    assert!(err.starts_with("Move 2 (1?x) rejected"));
+}
+
+#[test]
+fn test_cli_args_verbosity() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.log_filter(), "warn");
+    let args = CliArgs::try_parse_from(["gamey", "-q"]).unwrap();
+    assert_eq!(args.log_filter(), "error");
+    let args = CliArgs::try_parse_from(["gamey", "-v"]).unwrap();
+    assert_eq!(args.log_filter(), "info");
+    let args = CliArgs::try_parse_from(["gamey", "validate", "x.yen", "-vv"]).unwrap();
+    assert_eq!(args.log_filter(), "debug");
+    assert!(CliArgs::try_parse_from(["gamey", "--quiet", "-v"]).is_err());
+}
+
+#[test]
+fn test_cli_args_show_bot_stats() {
+    assert!(!play_args(&["-m", "computer"]).show_bot_stats);
+    assert!(play_args(&["-m", "computer", "--show-bot-stats"]).show_bot_stats);
+}
+
+#[test]
+fn test_cli_args_check() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert!(args.check().is_ok());
+    let args = CliArgs::try_parse_from(["gamey", "play", "--bot", "nobody"]).unwrap();
+    assert!(args.check().unwrap_err().contains("nobody"));
+    let args = CliArgs::try_parse_from(["gamey", "serve", "--jsonrpc", "-b", "nobody"]).unwrap();
+    assert!(args.check().is_err());
+    let args = CliArgs::try_parse_from(["gamey", "--size", "0"]).unwrap();
+    assert!(args.check().is_err());
+    let args = CliArgs::try_parse_from(["gamey", "--size", "1025"]).unwrap();
+    assert!(args.check().unwrap_err().contains("1025"));
+    let args = CliArgs::try_parse_from(["gamey", "--size", "3", "--handicap", "4"]).unwrap();
+    assert!(args.check().is_err());
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_cli_args_conformance() {
+    let args = CliArgs::try_parse_from(["gamey", "conformance", "--url", "http://localhost:3000"])
+        .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Conformance {
+            url: "http://localhost:3000".to_string(),
+            bot_id: "random_bot".to_string()
+        })
+    );
+    assert!(CliArgs::try_parse_from(["gamey", "conformance"]).is_err());
+}
+
+#[cfg(not(feature = "client"))]
+#[test]
+fn test_cli_args_client_commands_need_the_client_feature() {
+    let url = "http://localhost:3000";
+    assert!(CliArgs::try_parse_from(["gamey", "conformance", "--url", url]).is_err());
+    let watch = ["gamey", "watch", "--server", url, "--game-id", "g1"];
+    assert!(CliArgs::try_parse_from(watch).is_err());
+}
+
+#[cfg(feature = "client")]
+#[test]
+fn test_cli_args_watch() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "watch",
+        "--server",
+        "http://localhost:3000",
+        "--game-id",
+        "g1",
+        "--game",
+        "hex",
+    ])
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Watch {
+            server: "http://localhost:3000".to_string(),
+            game_id: "g1".to_string(),
+            bot_id: None
+        })
+    );
+    assert!(
+        CliArgs::try_parse_from(["gamey", "watch", "--server", "http://localhost:3000"]).is_err()
+    );
+}
+
+#[test]
+fn test_cli_args_chaos_and_fallback() {
+    let play = play_args(&[
+        "--mode",
+        "computer",
+        "--chaos",
+        "latency=100,fail=0.5",
+        "--bot-retries",
+        "3",
+    ]);
+    let chaos = play.chaos.clone().unwrap();
+    assert_eq!(chaos.latency, std::time::Duration::from_millis(100));
+    assert_eq!(chaos.failure_rate, 0.5);
+    assert_eq!(play.bot_retries, 3);
+    assert_eq!(play.fallback_bot, "random_bot");
+    assert!(CliArgs::try_parse_from(["gamey", "play", "--chaos", "fail=2"]).is_err());
+
+    let args = CliArgs::try_parse_from(["gamey", "play", "--fallback-bot", "no_such_bot"]).unwrap();
+    assert!(args.check().is_err());
+}
+
+#[test]
+fn test_cli_args_tournament() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "tournament",
+        "--entrant",
+        "random_bot",
+        "--entrant",
+        "mine=python3 bot.py",
+        "--move-time",
+        "500",
+    ])
+    .unwrap();
+    let Some(gamey::CliCommand::Tournament {
+        action: None,
+        entrants,
+        format,
+        rounds,
+        games,
+        move_time,
+        memory,
+        report,
+        sprt,
+        dir: None,
+    }) = args.command
+    else {
+        panic!("expected the tournament command");
+    };
+    assert_eq!(entrants, vec!["random_bot", "mine=python3 bot.py"]);
+    assert_eq!(format, gamey::TournamentFormat::RoundRobin);
+    assert_eq!(
+        gamey::TournamentFormat::Swiss.format(5, rounds),
+        gamey::tournament::Format::Swiss { rounds: 3 }
+    );
+    assert_eq!((games, move_time, memory, report), (2, 500, 512, None));
+    assert_eq!(sprt, None);
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "tournament",
+        "--entrant",
+        "a=./a",
+        "--entrant",
+        "b=./b",
+        "--sprt",
+        "elo0=0,elo1=20",
+    ])
+    .unwrap();
+    let Some(gamey::CliCommand::Tournament { sprt, .. }) = args.command else {
+        panic!("expected the tournament command");
+    };
+    assert_eq!(sprt, Some(gamey::tournament::Sprt::new(0.0, 20.0)));
+    assert!(CliArgs::try_parse_from(["gamey", "tournament"]).is_err());
+
+    let args = CliArgs::try_parse_from(["gamey", "tournament", "resume", "league"]).unwrap();
+    let Some(gamey::CliCommand::Tournament {
+        action: Some(gamey::TournamentAction::Resume { dir }),
+        ..
+    }) = args.command
+    else {
+        panic!("expected tournament resume");
+    };
+    assert_eq!(dir, std::path::PathBuf::from("league"));
+}
+
+/// A round-robin between two random bots on a board of size 3.
+fn tournament_setup(games: u32) -> gamey::tournament::TournamentSetup {
+    gamey::tournament::TournamentSetup {
+        entrants: vec!["random_bot".to_string(), "random_bot".to_string()],
+        format: gamey::tournament::Format::RoundRobin,
+        games_per_pairing: games,
+        budget: gamey::tournament::BudgetReport::from(&Default::default()),
+        sprt: None,
+        seed: None,
+        start: gamey::YEN::from(&gamey::GameY::new(3)),
+    }
+}
+
+#[test]
+fn test_run_tournament_writes_report() {
+    let dir = tempfile::tempdir().unwrap();
+    let file = dir.path().join("report.json");
+    gamey::run_tournament(&tournament_setup(2), Some(&file), None).unwrap();
+    let report: gamey::tournament::TournamentReport =
+        serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+    assert_eq!(report.games.len(), 2);
+
+    let csv = dir.path().join("standings.csv");
+    gamey::run_tournament(&tournament_setup(1), Some(&csv), None).unwrap();
+    let standings = std::fs::read_to_string(&csv).unwrap();
+    assert_eq!(standings.lines().count(), 3);
+    assert!(standings.starts_with("rank,name,"));
+
+    let mut unknown = tournament_setup(2);
+    unknown.entrants = vec!["no_such_bot".to_string()];
+    assert!(gamey::run_tournament(&unknown, None, None).is_err());
+}
+
+#[test]
+fn test_run_tournament_saves_and_resumes() {
+    let temp = tempfile::tempdir().unwrap();
+    let dir = temp.path().join("league");
+    gamey::run_tournament(&tournament_setup(4), None, Some(&dir)).unwrap();
+    let games = std::fs::read_to_string(dir.join("games.jsonl")).unwrap();
+    assert_eq!(games.lines().count(), 4);
+    assert!(dir.join("report.json").exists());
+
+    // Resuming after the second game plays the last two again
+    let kept: Vec<&str> = games.lines().take(2).collect();
+    std::fs::write(dir.join("games.jsonl"), kept.join("\n") + "\n").unwrap();
+    std::fs::remove_file(dir.join("report.json")).unwrap();
+    gamey::run_tournament_resume(&dir).unwrap();
+    let games = std::fs::read_to_string(dir.join("games.jsonl")).unwrap();
+    assert_eq!(games.lines().count(), 4);
+    assert!(games.starts_with(&(kept.join("\n") + "\n")));
+    let report: gamey::tournament::TournamentReport =
+        serde_json::from_str(&std::fs::read_to_string(dir.join("report.json")).unwrap()).unwrap();
+    assert_eq!(report.games.len(), 4);
+
+    let again = gamey::run_tournament(&tournament_setup(4), None, Some(&dir));
+    assert!(again.is_err());
+}
+
+#[test]
+fn test_cli_args_analyze_openings() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "analyze-openings",
+        "--size",
+        "7",
+        "--bot",
+        "mcts_bot",
+        "--playouts",
+        "10000",
+    ])
+    .unwrap();
+    assert_eq!(args.size, 7);
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::AnalyzeOpenings {
+            bot: "mcts_bot".to_string(),
+            playouts: 10000
+        })
+    );
+
+    let args = CliArgs::try_parse_from(["gamey", "--size", "3"]).unwrap();
+    assert!(gamey::run_analyze_openings(&args, "random_bot", 5).is_ok());
+    assert!(gamey::run_analyze_openings(&args, "no_such_bot", 5).is_err());
+}
+
+#[test]
+fn test_cli_args_review() {
+    let args =
+        CliArgs::try_parse_from(["gamey", "review", "game.ymn", "--bot", "mcts_bot"]).unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Review {
+            file: "game.ymn".into(),
+            from: None,
+            bot: "mcts_bot".to_string(),
+            playouts: 100,
+            threshold: 0.2
+        })
+    );
+
+    let temp = tempfile::tempdir().unwrap();
+    let game = temp.path().join("game.ymn");
+    std::fs::write(&game, "0 4 3 1").unwrap();
+    let args = CliArgs::try_parse_from(["gamey", "--size", "3"]).unwrap();
+    assert!(gamey::run_review(&args, &game, None, "random_bot", 5, 0.2).is_ok());
+    assert!(gamey::run_review(&args, &game, None, "no_such_bot", 5, 0.2).is_err());
+    assert!(gamey::run_review(&args, &game, None, "random_bot", 5, 1.5).is_err());
+}
+
+#[test]
+fn test_run_stats_over_an_archive() {
+    let args =
+        CliArgs::try_parse_from(["gamey", "stats", "--games", "archive.jsonl", "--csv", "out"])
+            .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Stats {
+            games: "archive.jsonl".into(),
+            csv: Some("out".into())
+        })
+    );
+
+    let temp = tempfile::tempdir().unwrap();
+    let archive = temp.path().join("archive.jsonl");
+    let mut writer = gamey::archive::ArchiveWriter::append_to(&archive).unwrap();
+    for _ in 0..3 {
+        let mut game = gamey::GameY::new(3);
+        while let Some(player) = game.next_player() {
+            let coords = game.cell_coords(game.available_cells()[0]);
+            game.add_move(gamey::Movement::Placement { player, coords })
+                .unwrap();
+        }
+        writer.append(&game).unwrap();
+    }
+    let csv = temp.path().join("csv");
+    gamey::run_stats(&archive, Some(&csv)).unwrap();
+    let boards = std::fs::read_to_string(csv.join("boards.csv")).unwrap();
+    assert!(boards.lines().nth(1).unwrap().starts_with("y,3,3,3,"));
+    assert!(csv.join("lengths.csv").exists());
+    assert!(csv.join("openings.csv").exists());
+
+    std::fs::write(&archive, "not a game\n").unwrap();
+    assert!(gamey::run_stats(&archive, None).is_err());
+}
+
+#[test]
+fn test_run_find_position() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "archive",
+        "find-position",
+        "pos.yen",
+        "--games",
+        "archive.jsonl",
+    ])
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Archive {
+            action: gamey::ArchiveAction::FindPosition {
+                position: "pos.yen".into(),
+                games: "archive.jsonl".into()
+            }
+        })
+    );
+
+    let temp = tempfile::tempdir().unwrap();
+    let archive = temp.path().join("archive.jsonl");
+    let mut writer = gamey::archive::ArchiveWriter::append_to(&archive).unwrap();
+    let mut game = gamey::GameY::new(3);
+    for coords in [
+        gamey::Coordinates::new(2, 0, 0),
+        gamey::Coordinates::new(1, 1, 0),
+    ] {
+        let player = game.next_player().unwrap();
+        game.add_move(gamey::Movement::Placement { player, coords })
+            .unwrap();
+    }
+    writer.append(&game).unwrap();
+    let position = temp.path().join("pos.yen");
+    game.position_after(1)
+        .unwrap()
+        .save_to_file(&position)
+        .unwrap();
+    assert!(gamey::run_find_position(&position, &archive).is_ok());
+    assert!(gamey::run_find_position(&temp.path().join("none.yen"), &archive).is_err());
+}
+
+#[test]
+fn test_run_export() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "archive",
+        "export",
+        "--format",
+        "csv",
+        "out.csv",
+        "--games",
+        "archive.jsonl",
+    ])
+    .unwrap();
+    assert_eq!(
+        args.command,
+        Some(gamey::CliCommand::Archive {
+            action: gamey::ArchiveAction::Export {
+                out: "out.csv".into(),
+                games: "archive.jsonl".into(),
+                format: Some(gamey::archive::ExportFormat::Csv)
+            }
+        })
+    );
+    let parquet = CliArgs::try_parse_from([
+        "gamey",
+        "archive",
+        "export",
+        "--format",
+        "parquet",
+        "out.parquet",
+        "--games",
+        "archive.jsonl",
+    ]);
+    assert_eq!(parquet.is_ok(), cfg!(feature = "parquet"));
+
+    let temp = tempfile::tempdir().unwrap();
+    let archive = temp.path().join("archive.jsonl");
+    let mut writer = gamey::archive::ArchiveWriter::append_to(&archive).unwrap();
+    writer.append(&gamey::GameY::new(3)).unwrap();
+    let mut game = gamey::GameY::new(1);
+    game.add_move(gamey::Movement::Placement {
+        player: gamey::PlayerId::new(0),
+        coords: gamey::Coordinates::new(0, 0, 0),
+    })
+    .unwrap();
+    writer.append(&game).unwrap();
+    let out = temp.path().join("moves.csv");
+    gamey::run_export(&archive, &out, None).unwrap();
+    let csv = std::fs::read_to_string(&out).unwrap();
+    assert_eq!(csv.lines().count(), 2);
+    assert!(csv.ends_with("2,1,0,0,,y,1,win\n"));
+}
+
+#[test]
+fn test_cli_args_seed() {
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "tournament",
+        "--seed",
+        "42",
+        "--entrant",
+        "random_bot",
+    ])
+    .unwrap();
+    let config = args.deterministic_config().unwrap();
+    assert_eq!((config.seed, config.threads), (42, 1));
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "--seed",
+        "42",
+        "--threads",
+        "4",
+        "analyze-openings",
+    ])
+    .unwrap();
+    assert_eq!(args.deterministic_config().unwrap().threads, 4);
+    assert!(CliArgs::try_parse_from(["gamey", "--threads", "4"]).is_err());
+    assert!(
+        CliArgs::try_parse_from(["gamey"])
+            .unwrap()
+            .deterministic_config()
+            .is_none()
+    );
+}
+
+#[test]
+fn test_cli_args_save_options() {
+    let args = CliArgs::try_parse_from(["gamey"]).unwrap();
+    assert_eq!(args.save_options(), SaveOptions::default());
+    let args = CliArgs::try_parse_from(["gamey", "--backup", "--durable"]).unwrap();
+    assert!(args.save_options().backup);
+    assert!(args.save_options().durable);
+}
+
+#[test]
+fn test_run_convert() {
+    let temp = tempfile::tempdir().unwrap();
+    let games = temp.path().join("games");
+    std::fs::create_dir(&games).unwrap();
+    let mut game = gamey::GameY::new(3);
+    game.play_move_list("0 4! {a reply}").unwrap();
+    game.save_to_file(games.join("first.yen")).unwrap();
+    std::fs::write(games.join("second.ymn"), "2 1\n").unwrap();
+    std::fs::write(games.join("broken.yen"), "not a game").unwrap();
+
+    let out = temp.path().join("sgf");
+    let files = ["first.yen", "second.ymn", "broken.yen", "missing.sgf"].map(|f| games.join(f));
+    let args = CliArgs::try_parse_from(["gamey", "--size", "3"]).unwrap();
+    let converted = gamey::run_convert(&args, &files, None, gamey::GameFormat::Sgf, &out).unwrap();
+    // The broken and missing files are reported, the others converted
+    assert!(!converted);
+    let sgf = std::fs::read_to_string(out.join("first.sgf")).unwrap();
+    assert_eq!(
+        gamey::GameY::from_sgf(&sgf).unwrap().history(),
+        game.history()
+    );
+    assert!(out.join("second.sgf").exists());
+
+    // And back, in place of the originals
+    let files = [out.join("first.sgf")];
+    assert!(gamey::run_convert(&args, &files, None, gamey::GameFormat::Moves, &out).unwrap());
+    assert_eq!(
+        std::fs::read_to_string(out.join("first.ymn")).unwrap(),
+        "0 4! {a reply}\n"
+    );
+    let files = [out.join("first.ymn")];
+    assert!(
+        !gamey::run_convert(
+            &args,
+            &files,
+            Some(gamey::GameFormat::Moves),
+            gamey::GameFormat::Moves,
+            &out
+        )
+        .unwrap()
+    );
+
+    let args = CliArgs::try_parse_from([
+        "gamey",
+        "convert",
+        "--from",
+        "yen",
+        "--to",
+        "sgf",
+        "a.yen",
+        "--out-dir",
+        "sgf",
+    ])
+    .unwrap();
+    assert!(matches!(
+        args.command,
+        Some(gamey::CliCommand::Convert {
+            from: Some(gamey::GameFormat::Yen),
+            to: gamey::GameFormat::Sgf,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_verify_file() {
+    let temp = tempfile::tempdir().unwrap();
+    let board = gamey::GameY::new(3);
+    let write = |name: &str, contents: &str| {
+        let path = temp.path().join(name);
+        std::fs::write(&path, contents).unwrap();
+        path
+    };
+    let verify = |path: &std::path::Path, claimed| gamey::verify_file(path, None, claimed, &board);
+
+    let resigned = write("resigned.ymn", "0 resign\n");
+    let summary = verify(&resigned, Some("B+R")).unwrap();
+    assert_eq!(summary, "2 legal moves, result B+R confirmed");
+    assert!(verify(&resigned, Some("b+")).is_ok());
+    assert!(
+        verify(&resigned, None)
+            .unwrap()
+            .contains("no result claimed, B+R")
+    );
+    let error = verify(&resigned, Some("W+")).unwrap_err().to_string();
+    assert!(
+        error.contains("the engine finds B+R after 2 moves"),
+        "{}",
+        error
+    );
+    let error = verify(&resigned, Some("draw")).unwrap_err().to_string();
+    assert!(error.contains("invalid claimed result"), "{}", error);
+
+    let illegal = write("illegal.ymn", "0 4 0\n");
+    let error = verify(&illegal, None).unwrap_err().to_string();
+    assert!(error.contains("Invalid move 3 (0)"), "{}", error);
+    let unfinished = write("unfinished.ymn", "0\n");
+    let error = verify(&unfinished, Some("B+")).unwrap_err().to_string();
+    assert!(error.contains("not over after 1 moves"), "{}", error);
+
+    // The result recorded in the file is checked
+    let mut game = gamey::GameY::new(3);
+    game.play_move_list("0 resign").unwrap();
+    game.set_metadata(gamey::GameMetadata::default().with_result("W+R"));
+    let recorded = temp.path().join("recorded.yen");
+    game.save_to_file(&recorded).unwrap();
+    let error = verify(&recorded, None).unwrap_err().to_string();
+    assert!(error.contains("the claimed result is W+R"), "{}", error);
+    assert!(verify(&recorded, Some("B+R")).is_ok());
+    let sgf = write("recorded.sgf", &game.to_sgf().unwrap());
+    assert!(verify(&sgf, Some("B+R")).is_ok());
+
+    // A history that does not lead to the layout is a discrepancy
+    let mut game = gamey::GameY::new(3);
+    game.play_move_list("0 4").unwrap();
+    let tampered = temp.path().join("tampered.yen");
+    game.save_to_file(&tampered).unwrap();
+    let json = std::fs::read_to_string(&tampered).unwrap();
+    std::fs::write(&tampered, json.replace("B/../.R.", "B/R./...")).unwrap();
+    let error = verify(&tampered, None).unwrap_err().to_string();
+    assert!(
+        error.contains("instead of the recorded layout B/R./..."),
+        "{}",
+        error
+    );
+
+    let args = CliArgs::try_parse_from(["gamey", "--size", "3"]).unwrap();
+    assert!(gamey::run_verify(&args, std::slice::from_ref(&resigned), None, Some("B+")).unwrap());
+    assert!(!gamey::run_verify(&args, &[resigned, illegal], None, None).unwrap());
+}
+
+/// Runs the gamey binary with `args`, feeding `input` to its stdin, and
+/// returns what it writes to stdout.
+#[cfg(feature = "server")]
+fn run_piped(args: &[&str], input: &[u8]) -> String {
+    use std::io::Write;
+    use std::process::{Command, Stdio};
+
+    let mut child = Command::new(env!("CARGO_BIN_EXE_gamey"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "gamey {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[cfg(feature = "server")]
+#[test]
+fn test_commands_read_stdin_and_write_stdout() {
+    let yen = run_piped(
+        &[
+            "--size",
+            "3",
+            "convert",
+            "-",
+            "--from",
+            "moves",
+            "--to",
+            "yen",
+            "--out-dir",
+            "-",
+        ],
+        b"0 4\n",
+    );
+    let game = gamey::GameY::read_yen(yen.as_bytes()).unwrap();
+    assert_eq!(game.to_move_list(), "0 4");
+
+    let validated = run_piped(&["validate", "-"], yen.as_bytes());
+    assert_eq!(validated, "-: ok\n");
+
+    let archive = run_piped(
+        &[
+            "convert",
+            "-",
+            "--from",
+            "yen",
+            "--to",
+            "compact-yen",
+            "--out-dir",
+            "-",
+        ],
+        yen.as_bytes(),
+    );
+    let csv = run_piped(
+        &["archive", "export", "-", "--games", "-"],
+        archive.as_bytes(),
+    );
+    assert_eq!(
+        csv.lines().collect::<Vec<_>>(),
+        vec![
+            "game,move_number,player,cell,action,topology,size,result",
+            "1,1,0,0,,y,3,",
+            "1,2,1,4,,y,3,",
+        ]
+    );
+}
@@ -1,5 +1,6 @@
 use gamey::{
-    Coordinates, GameAction, GameStatus, GameY, GameYError, Movement, PlayerId, RenderOptions, YEN,
+    Compression, Coordinates, GameAction, GameMetadata, GameStatus, GameY, GameYError, Glyph,
+    Movement, PlayerId, RenderOptions, SaveOptions, Symmetry, YEN, YEN_VERSION,
 };
 use std::fs;
 use tempfile::tempdir;
@@ -614,6 +615,264 @@ fn test_save_and_load_game_file() {
     assert_eq!(yen_original.layout(), yen_loaded.layout());
 }
 
+#[test]
+fn test_load_unversioned_file() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("old.yen");
+    fs::write(
+        &file_path,
+        r#"{"size": 2, "turn": 1, "players": ["B", "R"], "layout": "B/.."}"#,
+    )
+    .unwrap();
+
+    let game = GameY::load_from_file(&file_path).unwrap();
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.layout(), "B/..");
+    assert_eq!(yen.version(), YEN_VERSION);
+}
+
+#[test]
+fn test_load_file_from_newer_version() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("future.yen");
+    fs::write(
+        &file_path,
+        r#"{"version": 1000, "size": 1, "turn": 0, "players": ["B", "R"], "layout": "."}"#,
+    )
+    .unwrap();
+
+    match GameY::load_from_file(&file_path).unwrap_err() {
+        GameYError::UnsupportedVersion { found, supported, .. } => {
+            assert_eq!(found, 1000);
+            assert_eq!(supported, YEN_VERSION);
+        }
+        other => panic!("Expected UnsupportedVersion, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_save_and_load_keeps_history_and_annotations() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("annotated.yen");
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(0, 2, 0),
+    })
+    .unwrap();
+    game.add_annotation(Some(Glyph::Interesting), "Leaves the corner");
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    game.add_move(Movement::Action {
+        player: PlayerId::new(0),
+        action: GameAction::Resign,
+    })
+    .unwrap();
+    game.save_to_file(&file_path).unwrap();
+
+    let loaded = GameY::load_from_file(&file_path).unwrap();
+    assert_eq!(loaded.history(), game.history());
+    assert_eq!(loaded.annotations(), game.annotations());
+    assert!(loaded.check_game_over());
+}
+
+#[test]
+fn test_save_and_load_keeps_metadata() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("metadata.yen");
+    let mut game = GameY::new(3);
+    let metadata = GameMetadata::default()
+        .with_event("Club night")
+        .with_date("2026-10-17")
+        .with_player(PlayerId::new(0), "Ada")
+        .with_player(PlayerId::new(1), "Grace")
+        .with_rating(PlayerId::new(0), 1720.0);
+    game.set_metadata(metadata.clone());
+    game.save_to_file(&file_path).unwrap();
+    assert_eq!(
+        GameY::load_from_file(&file_path).unwrap().metadata(),
+        &metadata
+    );
+
+    // Positions without a history keep it too
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    let yen = YEN::from(&game).with_metadata(metadata.clone());
+    let json = serde_json::to_string(&yen).unwrap();
+    assert!(json.contains(r#""metadata":{"event":"Club night""#));
+    let read = GameY::try_from(YEN::from_json(&json).unwrap()).unwrap();
+    assert_eq!(read.metadata(), &metadata);
+    // Games without metadata write no field
+    assert!(
+        !serde_json::to_string(&YEN::from(&GameY::new(3)))
+            .unwrap()
+            .contains("metadata")
+    );
+}
+
+#[test]
+fn test_load_verified_rejects_tampered_file() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("game.yen");
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    game.save_to_file(&file_path).unwrap();
+    assert!(GameY::load_verified(&file_path, None).is_ok());
+
+    let content = fs::read_to_string(&file_path).unwrap();
+    fs::write(&file_path, content.replace("B/../...", "R/../...")).unwrap();
+    assert!(matches!(
+        GameY::load_verified(&file_path, None),
+        Err(GameYError::IntegrityCheckFailed { .. })
+    ));
+    // Lenient loading still accepts the file
+    assert!(GameY::load_from_file(&file_path).is_ok());
+}
+
+#[test]
+fn test_signed_file_needs_tournament_key() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("signed.yen");
+    GameY::new(3)
+        .save_to_file_signed(&file_path, b"tournament")
+        .unwrap();
+
+    assert!(GameY::load_verified(&file_path, Some(b"tournament")).is_ok());
+    assert!(GameY::load_verified(&file_path, Some(b"guess")).is_err());
+    assert!(GameY::load_verified(&file_path, None).is_err());
+}
+
+#[test]
+fn test_save_replaces_file_and_keeps_backup() {
+    let dir = tempdir().unwrap();
+    let file_path = dir.path().join("game.yen");
+    let options = SaveOptions::default().with_backup(true).with_durable(true);
+    GameY::new(3)
+        .save_with_options(&file_path, None, &options)
+        .unwrap();
+    // Nothing to back up on the first save
+    assert!(!SaveOptions::backup_path(&file_path).exists());
+
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    game.save_with_options(&file_path, None, &options).unwrap();
+
+    assert_eq!(
+        GameY::load_from_file(&file_path).unwrap().history().len(),
+        1
+    );
+    let backup = GameY::load_from_file(SaveOptions::backup_path(&file_path)).unwrap();
+    assert!(backup.history().is_empty());
+    // The temporary file was renamed over the save
+    let mut names: Vec<String> = fs::read_dir(dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    assert_eq!(names, vec!["game.yen", "game.yen.bak"]);
+}
+
+#[test]
+fn test_failed_save_leaves_no_temporary_file() {
+    let dir = tempdir().unwrap();
+    // A directory cannot be replaced by a file
+    fs::create_dir(dir.path().join("game.yen")).unwrap();
+    match GameY::new(3).save_to_file(dir.path().join("game.yen")) {
+        Err(GameYError::IoError { message, .. }) => {
+            assert!(message.contains("Failed to write file"));
+        }
+        other => panic!("Expected IoError, got {:?}", other),
+    }
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 1);
+}
+
+#[test]
+fn test_write_and_read_yen_in_memory() {
+    let mut game = GameY::new(3);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(2, 0, 0),
+    })
+    .unwrap();
+    game.add_annotation(Some(Glyph::Good), "");
+
+    let mut buffer = Vec::new();
+    game.write_yen(&mut buffer).unwrap();
+    let read = GameY::read_yen(buffer.as_slice()).unwrap();
+    assert_eq!(read.history(), game.history());
+    assert_eq!(read.annotations(), game.annotations());
+    assert!(GameY::read_yen_verified(buffer.as_slice(), None).is_ok());
+
+    let mut signed = Vec::new();
+    game.write_yen_signed(&mut signed, b"tournament").unwrap();
+    assert!(GameY::read_yen_verified(signed.as_slice(), Some(b"tournament")).is_ok());
+    assert!(GameY::read_yen_verified(signed.as_slice(), None).is_err());
+    assert!(GameY::read_yen("not json".as_bytes()).is_err());
+}
+
+#[test]
+fn test_save_and_load_compressed_files() {
+    let dir = tempdir().unwrap();
+    let mut game = GameY::new(4);
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(3, 0, 0),
+    })
+    .unwrap();
+    for (name, compression) in [
+        ("game.yen.gz", Compression::Gzip),
+        ("game.yen.zst", Compression::Zstd),
+    ] {
+        let file_path = dir.path().join(name);
+        let saved = game.save_to_file(&file_path);
+        assert_eq!(saved.is_ok(), compression.is_available(), "{}", name);
+        if compression.is_available() {
+            // The file is not plain JSON
+            assert!(!fs::read(&file_path).unwrap().starts_with(b"{"));
+            let loaded = GameY::load_from_file(&file_path).unwrap();
+            assert_eq!(loaded.history(), game.history());
+        }
+    }
+}
+
+#[test]
+fn test_compressed_archive_keeps_appended_games() {
+    use gamey::archive::{ArchiveReader, ArchiveWriter};
+
+    let dir = tempdir().unwrap();
+    for name in ["games.jsonl.gz", "games.jsonl.zst"] {
+        let path = dir.path().join(name);
+        if !Compression::from_path(&path).is_available() {
+            assert!(ArchiveWriter::append_to(&path).is_err());
+            continue;
+        }
+        for size in [3, 4] {
+            let mut writer = ArchiveWriter::append_to(&path).unwrap();
+            writer.append(&GameY::new(size)).unwrap();
+            writer.finish().unwrap();
+        }
+        let sizes: Vec<u32> = ArchiveReader::open(&path)
+            .unwrap()
+            .map(|archived| archived.unwrap().game.board_size())
+            .collect();
+        assert_eq!(sizes, vec![3, 4], "{}", name);
+    }
+}
+
 #[test]
 fn test_load_nonexistent_file() {
     let result = GameY::load_from_file("/nonexistent/path/game.yen");
@@ -737,6 +996,10 @@ fn test_render_empty_board() {
         show_3d_coords: false,
         show_idx: false,
         show_colors: false,
+        show_edge_labels: false,
+        highlight_last_move: false,
+        show_move_numbers: false,
+        view: Symmetry::Identity,
     };
     let rendered = game.render(&options);
 
@@ -762,6 +1025,10 @@ fn test_render_with_pieces() {
         show_3d_coords: false,
         show_idx: false,
         show_colors: false,
+        show_edge_labels: false,
+        highlight_last_move: false,
+        show_move_numbers: false,
+        view: Symmetry::Identity,
     };
     let rendered = game.render(&options);
 
@@ -776,6 +1043,10 @@ fn test_render_with_3d_coords() {
         show_3d_coords: true,
         show_idx: false,
         show_colors: false,
+        show_edge_labels: false,
+        highlight_last_move: false,
+        show_move_numbers: false,
+        view: Symmetry::Identity,
     };
     let rendered = game.render(&options);
 
@@ -791,6 +1062,10 @@ fn test_render_with_indices() {
         show_3d_coords: false,
         show_idx: true,
         show_colors: false,
+        show_edge_labels: false,
+        highlight_last_move: false,
+        show_move_numbers: false,
+        view: Symmetry::Identity,
     };
     let rendered = game.render(&options);
 
@@ -891,3 +1166,93 @@ fn test_union_find_correctly_merges_components() {
         _ => panic!("Player 0 should have won"),
     }
 }
+
+#[test]
+fn test_placement_out_of_range_is_rejected() {
+    let mut game = GameY::new(3);
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(5, 0, 0),
+    });
+    assert!(matches!(result, Err(GameYError::CoordOutOfRange { id_coord: 'x', .. })));
+
+    let result = game.add_move(Movement::Placement {
+        player: PlayerId::new(0),
+        coords: Coordinates::new(1, 1, 1),
+    });
+    assert!(matches!(result, Err(GameYError::CoordOutOfRange { .. })));
+    assert!(game.history().is_empty());
+}
+
+// ============================================================================
+// Handicap Tests
+// ============================================================================
+
+#[test]
+fn test_handicap_game_starts_with_player_1() {
+    let stones = GameY::handicap_points(7, 2).unwrap();
+    let game = GameY::new_with_handicap(7, &stones).unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(1)));
+    assert_eq!(game.available_cells().len(), 26);
+    assert!(game.history().is_empty());
+    assert_eq!(game.handicap(), &stones[..]);
+}
+
+#[test]
+fn test_handicap_without_stones_is_a_normal_game() {
+    let game = GameY::new_with_handicap(5, &[]).unwrap();
+    assert_eq!(game.next_player(), Some(PlayerId::new(0)));
+}
+
+#[test]
+fn test_handicap_points_are_distinct_and_valid() {
+    for size in 1..=9 {
+        let max = (1..=4)
+            .take_while(|&k| GameY::handicap_points(size, k).is_ok())
+            .last()
+            .unwrap_or(0);
+        if max == 0 {
+            continue;
+        }
+        let points = GameY::handicap_points(size, max).unwrap();
+        assert!(GameY::new_with_handicap(size, &points).is_ok());
+    }
+    assert_eq!(
+        GameY::handicap_points(7, 1).unwrap(),
+        vec![Coordinates::new(2, 2, 2)]
+    );
+}
+
+#[test]
+fn test_too_many_handicap_stones_is_an_error() {
+    let result = GameY::handicap_points(7, 5);
+    assert!(matches!(result, Err(GameYError::InvalidHandicap { max: 4, .. })));
+}
+
+#[test]
+fn test_handicap_stone_on_same_cell_is_rejected() {
+    let c = Coordinates::new(2, 2, 2);
+    let result = GameY::new_with_handicap(7, &[c, c]);
+    assert!(matches!(result, Err(GameYError::Occupied { .. })));
+}
+
+#[test]
+fn test_handicap_yen_round_trip() {
+    let stones = GameY::handicap_points(7, 3).unwrap();
+    let mut game = GameY::new_with_handicap(7, &stones).unwrap();
+    game.add_move(Movement::Placement {
+        player: PlayerId::new(1),
+        coords: Coordinates::new(6, 0, 0),
+    })
+    .unwrap();
+
+    let yen: YEN = (&game).into();
+    assert_eq!(yen.handicap(), &stones[..]);
+    assert_eq!(yen.turn(), 0);
+
+    let loaded = GameY::try_from(yen.clone()).unwrap();
+    assert_eq!(loaded.handicap(), &stones[..]);
+    assert_eq!(loaded.next_player(), Some(PlayerId::new(0)));
+    let reloaded: YEN = (&loaded).into();
+    assert_eq!(reloaded.layout(), yen.layout());
+}